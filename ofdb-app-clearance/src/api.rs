@@ -1,4 +1,4 @@
-use ofdb_boundary::PendingClearanceForPlace;
+use ofdb_boundary::{Event, EventHistory, PendingClearanceForEvent, PendingClearanceForPlace};
 use ofdb_entities::place::{PlaceHistory, PlaceRevision};
 
 pub const API_ROOT: &str = "/api";
@@ -36,3 +36,44 @@ impl PlaceClearance {
         }
     }
 }
+
+#[derive(Clone, Debug)]
+pub struct EventClearance {
+    pub pending: PendingClearanceForEvent,
+    pub history: Option<EventHistory>,
+}
+
+impl EventClearance {
+    pub fn last_cleared_rev_nr(&self) -> Option<u64> {
+        self.pending.last_cleared_revision
+    }
+    pub fn current_rev_nr(&self) -> Option<u64> {
+        self.history
+            .as_ref()
+            .and_then(|h| h.revisions.first().map(|rev| rev.rev))
+    }
+    pub fn current_rev(&self) -> Option<&Event> {
+        self.history
+            .as_ref()
+            .and_then(|h| h.revisions.first().map(|rev| &rev.event))
+    }
+    pub fn last_cleared_rev(&self) -> Option<&Event> {
+        let nr = self.last_cleared_rev_nr()?;
+        let rev = self
+            .history
+            .as_ref()?
+            .revisions
+            .iter()
+            .find(|&x| x.rev == nr)?;
+        Some(&rev.event)
+    }
+    pub fn overview_title(&self) -> &str {
+        if let Some(rev) = self.last_cleared_rev() {
+            &rev.title
+        } else if let Some(r) = self.current_rev() {
+            &r.title
+        } else {
+            &self.pending.event_id
+        }
+    }
+}
@@ -25,6 +25,7 @@ fn App() -> impl IntoView {
     let logged_in = Signal::derive(move || token.get().is_some());
     let invalid_token = create_rw_signal(false);
     let place_clearances = create_rw_signal(HashMap::<String, api::PlaceClearance>::new());
+    let event_clearances = create_rw_signal(HashMap::<String, api::EventClearance>::new());
 
     // -- actions -- //
 
@@ -54,6 +55,25 @@ fn App() -> impl IntoView {
         }
     });
 
+    let get_event_history = create_action(move |(token, id): &(String, String)| {
+        let api = ClearanceApi::new(api::API_ROOT, token.clone());
+        let id = id.clone();
+        async move {
+            match api.event_history(&id).await {
+                Ok(eh) => {
+                    event_clearances.update(|event_clearances| {
+                        if let Some(ec) = event_clearances.get_mut(eh.id.as_str()) {
+                            ec.history = Some(eh);
+                        }
+                    });
+                }
+                Err(err) => {
+                    log::error!("{err}");
+                }
+            }
+        }
+    });
+
     let fetch_pending_clearances = create_action(move |_: &()| async move {
         match token.get() {
             Some(token) => {
@@ -91,9 +111,34 @@ fn App() -> impl IntoView {
                         {
                             set_token.update(|v| *v = None);
                             invalid_token.update(|v| *v = true);
+                            return;
                         }
                     }
                 }
+                match api.event_clearances().await {
+                    Ok(pending) => {
+                        event_clearances.update(|event_clearances| {
+                            for p in pending {
+                                let id = p.event_id.clone();
+                                if let Some(ec) = event_clearances.get_mut(&p.event_id) {
+                                    ec.pending = p;
+                                } else {
+                                    event_clearances.insert(
+                                        id.clone(),
+                                        api::EventClearance {
+                                            pending: p,
+                                            history: None,
+                                        },
+                                    );
+                                }
+                                get_event_history.dispatch((token.clone(), id));
+                            }
+                        });
+                    }
+                    Err(err) => {
+                        log::error!("{err}");
+                    }
+                }
             }
             None => {
                 log::error!("Unable to fetch pending clearances: not logged in")
@@ -120,7 +165,7 @@ fn App() -> impl IntoView {
                         view=move || {
                             if let Some(token) = token.get() {
                                 view! {
-                                    <Index token place_clearances fetch_pending_clearances/>
+                                    <Index token place_clearances event_clearances fetch_pending_clearances/>
                                 }
                                     .into_view()
                             } else {
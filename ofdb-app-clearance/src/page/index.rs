@@ -9,10 +9,15 @@ use ofdb_frontend_api::ClearanceApi;
 
 use crate::api;
 
+mod events;
+
+use events::EventClearances;
+
 #[component]
 pub fn Index(
     token: String,
     place_clearances: RwSignal<HashMap<String, api::PlaceClearance>>,
+    event_clearances: RwSignal<HashMap<String, api::EventClearance>>,
     fetch_pending_clearances: Action<(), ()>,
 ) -> impl IntoView {
     let expanded = create_rw_signal(HashSet::<String>::new());
@@ -91,8 +96,10 @@ pub fn Index(
           <div class="container">
             <div class="section">
               {move || {
-                if place_clearances.get().is_empty() {
+                if place_clearances.get().is_empty() && event_clearances.get().is_empty() {
                   view! { <p>"There is nothing to clear :)"</p> }.into_view()
+                } else if place_clearances.get().is_empty() {
+                  view! {}.into_view()
                 } else {
                   view! {
                     <div class="panel">
@@ -134,6 +141,13 @@ pub fn Index(
                 }
               }}
             </div>
+            <div class="section">
+              <EventClearances
+                token
+                event_clearances
+                fetch_pending_clearances
+              />
+            </div>
           </div>
         </main>
       </div>
@@ -567,21 +581,16 @@ fn links_cs(lastrev: Option<&PlaceRevision>, currrev: &PlaceRevision) -> Changes
     })
 }
 
-fn changeset<F>(last: Option<&PlaceRevision>, curr: &PlaceRevision, f: F) -> Changeset
+fn changeset<T, F>(last: Option<&T>, curr: &T, f: F) -> Changeset
 where
-    F: Fn(&PlaceRevision) -> String,
+    F: Fn(&T) -> String,
 {
     changeset_split(last, curr, "", f)
 }
 
-fn changeset_split<F>(
-    last: Option<&PlaceRevision>,
-    curr: &PlaceRevision,
-    split: &str,
-    f: F,
-) -> Changeset
+fn changeset_split<T, F>(last: Option<&T>, curr: &T, split: &str, f: F) -> Changeset
 where
-    F: Fn(&PlaceRevision) -> String,
+    F: Fn(&T) -> String,
 {
     let slast = last.map_or(String::from(""), &f);
     let scurr = f(curr);
@@ -0,0 +1,386 @@
+use std::collections::{HashMap, HashSet};
+
+use leptos::*;
+
+use ofdb_boundary::{ClearanceForEvent, Event, ResultCount, UnixTimeSeconds};
+use ofdb_entities::time::Timestamp;
+use ofdb_frontend_api::ClearanceApi;
+
+use super::{ClearanceError, changeset, changeset_split, table_row, table_row_always};
+use crate::api;
+
+#[component]
+pub fn EventClearances(
+    token: String,
+    event_clearances: RwSignal<HashMap<String, api::EventClearance>>,
+    fetch_pending_clearances: Action<(), ()>,
+) -> impl IntoView {
+    let expanded = create_rw_signal(HashSet::<String>::new());
+    let selected = create_rw_signal(HashSet::<String>::new());
+
+    let handle_clearance_result = move |result| {
+        match result {
+            Ok(ids) => {
+                for id in ids {
+                    event_clearances.update(|x| {
+                        x.remove(&id);
+                    });
+                    selected.update(|x| {
+                        x.remove(&id);
+                    });
+                    expanded.update(|x| {
+                        x.remove(&id);
+                    });
+                }
+            }
+            Err(err) => {
+                // TODO: handle error, e.g. show error message to the user
+                log::error!("{err:?}");
+            }
+        }
+        fetch_pending_clearances.dispatch(());
+    };
+
+    let accept = {
+        let token = token.clone();
+        create_action(move |(id, rev_nr): &(String, u64)| {
+            let c = ClearanceForEvent {
+                event_id: id.clone(),
+                cleared_revision: Some(*rev_nr),
+            };
+            let clearances = vec![c];
+            let token = token.clone();
+            async move {
+                let result = events_clearance(token, clearances).await;
+                handle_clearance_result(result);
+            }
+        })
+    };
+
+    let accept_all_selected = create_action(move |_: &()| {
+        let event_clearances = event_clearances.get();
+        let clearances = selected
+            .get()
+            .iter()
+            .filter_map(|id| event_clearances.get(id).map(|ec| (id, ec)))
+            .filter_map(|(id, ec)| ec.current_rev_nr().map(|rev| (id.to_string(), rev)))
+            .map(|(event_id, rev)| ClearanceForEvent {
+                event_id,
+                cleared_revision: Some(rev),
+            })
+            .collect();
+        let token = token.clone();
+        async move {
+            let result = events_clearance(token, clearances).await;
+            handle_clearance_result(result);
+        }
+    });
+
+    let select_all = move |_| {
+        selected.update(|selected| {
+            *selected = event_clearances.get().keys().cloned().collect();
+        });
+    };
+    let deselect_all = move |_| {
+        selected.update(|x| x.clear());
+    };
+
+    move || {
+        if event_clearances.get().is_empty() {
+            return view! {}.into_view();
+        }
+        view! {
+          <div class="panel">
+            <p class="panel-heading">
+              "Pending Event Clearances"
+              <span class="subtitle is-5">" (" {event_clearances.get().len()} ")"</span>
+            </p>
+            <div class="panel-block">
+              <div class="field is-grouped">
+                <p class="control">
+                  <button class="button" on:click=select_all>
+                    "select all"
+                  </button>
+                </p>
+                <p class="control">
+                  <button class="button" on:click=deselect_all>
+                    "deselect all"
+                  </button>
+                </p>
+              </div>
+            </div>
+            <ul>
+              <For
+                each=move || event_clearances.get()
+                key=|(id, _)| id.clone()
+                let:event_clearances
+              >
+                <EventPanelBlock
+                  event_clearance = { event_clearances.1 }
+                  expanded
+                  selected
+                  accept
+                />
+              </For>
+            </ul>
+            <div class="panel-block">
+              <button
+                class="button is-danger is-outlined is-fullwidth"
+                disabled=move || selected.get().is_empty()
+                on:click=move |_| accept_all_selected.dispatch(())
+              >
+                {format!("Accept all ({}) selected", selected.get().len())}
+              </button>
+            </div>
+          </div>
+        }
+        .into_view()
+    }
+}
+
+#[component]
+fn EventPanelBlock(
+    event_clearance: api::EventClearance,
+    expanded: RwSignal<HashSet<String>>,
+    selected: RwSignal<HashSet<String>>,
+    accept: Action<(String, u64), ()>,
+) -> impl IntoView {
+    let id = event_clearance.pending.event_id.clone();
+    let is_expanded = {
+        let id = id.clone();
+        Signal::derive(move || expanded.get().contains(&id))
+    };
+    let is_selected = {
+        let id = id.clone();
+        Signal::derive(move || selected.get().contains(&id))
+    };
+
+    view! {
+        <li class="panel-block">
+            <div>
+                <div class="level">
+                    <div class="level-left">
+                        <div class="level-item">
+                            <div class="field is-grouped">
+                                <p class="control">
+                                    <label class="checkbox">
+                                        <input
+                                            type="checkbox"
+                                            checked=move || is_selected.get()
+                                            on:click={
+                                                let id = id.clone();
+                                                move |_| {
+                                                    selected
+                                                        .update(|selected| {
+                                                            if selected.contains(&id) {
+                                                                selected.remove(&id);
+                                                            } else {
+                                                                selected.insert(id.clone());
+                                                            }
+                                                        })
+                                                }
+                                            }
+                                        />
+                                    </label>
+                                </p>
+                                <p class="control">
+                                    <button
+                                        class="button is-small"
+                                        on:click=move |_| {
+                                            expanded
+                                                .update(|expanded| {
+                                                    if expanded.contains(&id) {
+                                                        expanded.remove(&id);
+                                                    } else {
+                                                        expanded.insert(id.clone());
+                                                    }
+                                                })
+                                        }
+                                    >
+                                        <span class="icon is-small">
+                                            <i class=move || {
+                                                if is_expanded.get() {
+                                                    "fa fa-chevron-down"
+                                                } else {
+                                                    "fa fa-chevron-right"
+                                                }
+                                            }></i>
+                                        </span>
+                                    </button>
+                                </p>
+                            </div>
+                        </div>
+                        <div class="level-item">{event_clearance.overview_title().to_string()}</div>
+                    </div>
+                </div>
+                {move || {
+                    is_expanded
+                        .get()
+                        .then(|| {
+                            if let (Some(curr_rev_nr), Some(curr_rev)) = (
+                                event_clearance.current_rev_nr(),
+                                event_clearance.current_rev(),
+                            ) {
+                                view! {
+                                    <div>
+                                        <EventDetailsTable
+                                            id=event_clearance.pending.event_id.clone()
+                                            last_cleared_rev_nr=event_clearance.last_cleared_rev_nr()
+                                            lastrev=event_clearance.last_cleared_rev().cloned()
+                                            curr_rev_nr
+                                            currrev=curr_rev.clone()
+                                            accept
+                                        />
+                                    </div>
+                                }
+                                    .into_view()
+                            } else {
+                                view! { <p>"Loading current revision ..."</p> }.into_view()
+                            }
+                        })
+                }}
+            </div>
+        </li>
+    }
+}
+
+#[component]
+fn EventDetailsTable(
+    id: String,
+    last_cleared_rev_nr: Option<u64>,
+    lastrev: Option<Event>,
+    curr_rev_nr: u64,
+    currrev: Event,
+    accept: Action<(String, u64), ()>,
+) -> impl IntoView {
+    let title_cs = changeset(lastrev.as_ref(), &currrev, |e| e.title.clone());
+    let desc_cs = changeset(lastrev.as_ref(), &currrev, |e| {
+        e.description.clone().unwrap_or_default()
+    });
+    let time_cs = changeset(lastrev.as_ref(), &currrev, time_text);
+    let location_cs = changeset(lastrev.as_ref(), &currrev, location_text);
+    let contact_cs = changeset(lastrev.as_ref(), &currrev, |e| {
+        format!(
+            r#"
+        {organizer}<br>
+        {email}<br>
+        {phone}
+        "#,
+            organizer = e.organizer.clone().unwrap_or_default(),
+            email = e.email.clone().unwrap_or_default(),
+            phone = e.telephone.clone().unwrap_or_default(),
+        )
+    });
+    let links_cs = changeset(lastrev.as_ref(), &currrev, |e| {
+        format!(
+            r#"
+        {homepage}<br>
+        {image}<br>
+        {imagehref}
+        "#,
+            homepage = e.homepage.clone().unwrap_or_default(),
+            image = e.image_url.clone().unwrap_or_default(),
+            imagehref = e.image_link_url.clone().unwrap_or_default(),
+        )
+    });
+    let tags_cs = changeset_split(lastrev.as_ref(), &currrev, "\n", |e| e.tags.join("<br>\n"));
+
+    let last_rev = if let Some(nr) = last_cleared_rev_nr {
+        format!("(rev {})", nr)
+    } else {
+        String::new()
+    };
+
+    view! {
+        <table class="details-table">
+            <col class="col-head"/>
+            <col class="col-last"/>
+            <col class="col-curr"/>
+            <tr>
+                <th></th>
+                <th>"Last checked " {last_rev}</th>
+                <th>
+                    "Current "
+                    {format!("(rev {})", curr_rev_nr)}
+                    " "
+                    <button
+                        class="button is-primary"
+                        on:click=move |_| accept.dispatch((id.clone(), curr_rev_nr))
+                    >
+                        "Accept"
+                    </button>
+                </th>
+            </tr>
+            {table_row_always("Title", &title_cs)}
+            {table_row_always("Description", &desc_cs)}
+            {table_row_always("Time", &time_cs)}
+            {table_row("Location", &location_cs)}
+            {table_row("Contact", &contact_cs)}
+            {table_row("Links", &links_cs)}
+            {table_row("Tags", &tags_cs)}
+        </table>
+    }
+}
+
+fn time_text(e: &Event) -> String {
+    let format = |secs: UnixTimeSeconds| {
+        Timestamp::try_from_secs(secs.as_i64())
+            .map(|ts| ts.to_string())
+            .unwrap_or_default()
+    };
+    format!(
+        r#"
+        {start}<br>
+        {end}<br>
+        {rrule}
+        "#,
+        start = format(e.start),
+        end = e.end.map(format).unwrap_or_default(),
+        rrule = e.rrule.clone().unwrap_or_default(),
+    )
+}
+
+fn location_text(e: &Event) -> String {
+    format!(
+        r#"
+       Lat {lat}, Lon {lon}<br>
+       {street}<br>
+       {zip}<br>
+       {city}<br>
+       {country}<br>
+       {state}
+       "#,
+        lat = e.lat.map(|lat| lat.to_string()).unwrap_or_default(),
+        lon = e.lng.map(|lng| lng.to_string()).unwrap_or_default(),
+        street = e.street.clone().unwrap_or_default(),
+        zip = e.zip.clone().unwrap_or_default(),
+        city = e.city.clone().unwrap_or_default(),
+        country = e.country.clone().unwrap_or_default(),
+        state = e.state.clone().unwrap_or_default(),
+    )
+}
+
+async fn events_clearance(
+    token: String,
+    clearances: Vec<ClearanceForEvent>,
+) -> Result<Vec<String>, ClearanceError> {
+    let api = ClearanceApi::new(api::API_ROOT, token);
+    let cnt = clearances.len();
+    let ids = clearances
+        .iter()
+        .map(|c| c.event_id.to_string())
+        .collect();
+    match api.update_event_clearances(clearances).await {
+        Ok(ResultCount { count }) => {
+            if count as usize == cnt {
+                Ok(ids)
+            } else {
+                Err(ClearanceError::Incomplete)
+            }
+        }
+        Err(err) => {
+            log::error!("{err}");
+            Err(ClearanceError::Fetch)
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use super::*;
+
+/// Compare the search index with the database and optionally
+/// repair all mismatches.
+///
+/// Returns the mismatches that have been found before repairing them.
+pub fn check_index(
+    connections: &sqlite::Connections,
+    indexer: &mut dyn EventAndPlaceIndexer,
+    repair: bool,
+) -> Result<Vec<usecases::IndexMismatch>> {
+    let db = connections.shared()?;
+    let mismatches = usecases::check_index(&db, indexer)?;
+    for usecases::IndexMismatch { kind, id, problem } in &mismatches {
+        warn!("{kind:?} {id} is {problem:?} in the search index");
+    }
+    if repair && !mismatches.is_empty() {
+        usecases::repair_index(&db, indexer, &mismatches)?;
+        info!(
+            "Repaired {} mismatches in the search index",
+            mismatches.len()
+        );
+    }
+    Ok(mismatches)
+}
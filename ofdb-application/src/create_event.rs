@@ -1,7 +1,7 @@
 use std::result;
 
+use crate::db::DbReadWrite;
 use ofdb_core::gateways::notify::{NotificationEvent, NotificationGateway};
-use ofdb_db_sqlite::DbReadWrite;
 
 use super::*;
 use usecases::{Error, NewEvent, NewEventMode};
@@ -13,7 +13,8 @@ pub fn create_event(
     token: Option<&str>,
     new_event: NewEvent,
 ) -> Result<Event> {
-    let event = create_and_add_new_event(connections.exclusive()?, token, new_event)?;
+    let (event, clearance_org_ids) =
+        create_and_add_new_event(connections.exclusive()?, token, new_event)?;
 
     // Index newly added event
     // TODO: Move to a separate task/thread that doesn't delay this request
@@ -29,6 +30,12 @@ pub fn create_event(
             event.id, err
         );
     }
+    if !clearance_org_ids.is_empty() {
+        notify.notify(NotificationEvent::EventClearancePending {
+            event: &event,
+            org_ids: &clearance_org_ids,
+        });
+    }
 
     Ok(event)
 }
@@ -37,16 +44,17 @@ fn create_and_add_new_event(
     mut connection: DbReadWrite<'_>,
     token: Option<&str>,
     new_event: NewEvent,
-) -> result::Result<Event, Error> {
+) -> result::Result<(Event, Vec<Id>), Error> {
     connection.transaction(|conn| {
         let result = usecases::import_new_event(conn, token, new_event, NewEventMode::Create);
         match result {
             Ok(storable) => {
+                let clearance_org_ids = storable.clearance_org_ids().to_vec();
                 let event = usecases::store_created_event(conn, storable).map_err(|err| {
                     warn!("Failed to store newly created event: {}", err);
                     err
                 })?;
-                Ok(event)
+                Ok((event, clearance_org_ids))
             }
             Err(err) => Err(err),
         }
@@ -58,16 +66,17 @@ fn notify_event_created(
     notify: &dyn NotificationGateway,
     event: &Event,
 ) -> Result<()> {
-    if let Some(ref location) = event.location {
-        let email_addresses = {
-            let conn = connections.shared()?;
-            usecases::email_addresses_by_coordinate(&conn, location.pos)?
-        };
-        let event = NotificationEvent::EventAdded {
-            event,
-            email_addresses: &email_addresses,
-        };
-        notify.notify(event);
-    }
+    // Only events with a location could be subscribed to
+    let email_addresses = if let Some(ref location) = event.location {
+        let conn = connections.shared()?;
+        usecases::email_addresses_by_coordinate(&conn, location.pos)?
+    } else {
+        vec![]
+    };
+    let event = NotificationEvent::EventAdded {
+        event,
+        email_addresses: &email_addresses,
+    };
+    notify.notify(event);
     Ok(())
 }
@@ -13,7 +13,7 @@ pub fn create_place(
     accepted_licenses: &HashSet<String>,
 ) -> Result<Place> {
     // Create and add new entry
-    let (place, ratings) = {
+    let (place, ratings, clearance_org_ids) = {
         connections.exclusive()?.transaction(|conn| {
             // TODO:
             // combine `prepare_new_place` and `store_new_place` in ofdb-core
@@ -25,12 +25,13 @@ pub fn create_place(
                 accepted_licenses,
             ) {
                 Ok(storable) => {
+                    let clearance_org_ids = storable.clearance_org_ids().to_vec();
                     let (place, ratings) =
                         usecases::store_new_place(conn, storable).map_err(|err| {
                             warn!("Failed to store newly created place: {}", err);
                             err
                         })?;
-                    Ok((place, ratings))
+                    Ok((place, ratings, clearance_org_ids))
                 }
                 Err(err) => {
                     log::info!("Failed to prepare new place revision: {}", err);
@@ -56,6 +57,12 @@ pub fn create_place(
             place.id, err
         );
     }
+    if !clearance_org_ids.is_empty() {
+        notify.notify(NotificationEvent::PlaceClearancePending {
+            place: &place,
+            org_ids: &clearance_org_ids,
+        });
+    }
 
     Ok(place)
 }
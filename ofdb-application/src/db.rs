@@ -0,0 +1,757 @@
+//! Database backends
+//!
+//! The repositories are either stored in an SQLite or in a PostgreSQL
+//! database. The backend is selected once when creating the connection
+//! pool and all other code only deals with the types in this module.
+
+use anyhow::Result as Fallible;
+use ofdb_core::{
+    entities::*,
+    repositories::{Error as RepoError, *},
+    usecases,
+};
+
+type Result<T> = std::result::Result<T, RepoError>;
+
+/// All repositories that are implemented by a database backend
+pub trait Repositories:
+    ApiTokenRepo
+    + ChangeRepo
+    + CommentRepository
+    + DuplicateCandidateRepo
+    + EventClearanceRepo
+    + EventRepo
+    + OrganizationMemberRepo
+    + OrganizationRepo
+    + PlaceClearanceRepo
+    + PlaceRedirectRepo
+    + PlaceRepo
+    + RatingRepository
+    + ReminderRepo
+    + ReviewTokenRepo
+    + RevokedTokenRepo
+    + SubscriptionRepo
+    + TagRepo
+    + UserRepo
+    + UserTokenRepo
+    + WebhookRepo
+{
+}
+
+impl<T> Repositories for T where
+    T: ApiTokenRepo
+        + ChangeRepo
+        + CommentRepository
+        + DuplicateCandidateRepo
+        + EventClearanceRepo
+        + EventRepo
+        + OrganizationMemberRepo
+        + OrganizationRepo
+        + PlaceClearanceRepo
+        + PlaceRedirectRepo
+        + PlaceRepo
+        + RatingRepository
+        + ReminderRepo
+        + ReviewTokenRepo
+        + RevokedTokenRepo
+        + SubscriptionRepo
+        + TagRepo
+        + UserRepo
+        + UserTokenRepo
+        + WebhookRepo
+{
+}
+
+#[derive(Clone)]
+pub enum Connections {
+    Sqlite(ofdb_db_sqlite::Connections),
+    Postgres(ofdb_db_postgres::Connections),
+}
+
+impl Connections {
+    pub fn shared(&self) -> Fallible<DbReadOnly<'_>> {
+        Ok(match self {
+            Self::Sqlite(connections) => DbReadOnly::Sqlite(connections.shared()?),
+            Self::Postgres(connections) => DbReadOnly::Postgres(connections.shared()?),
+        })
+    }
+
+    pub fn exclusive(&self) -> Fallible<DbReadWrite<'_>> {
+        Ok(match self {
+            Self::Sqlite(connections) => DbReadWrite::Sqlite(connections.exclusive()?),
+            Self::Postgres(connections) => DbReadWrite::Postgres(connections.exclusive()?),
+        })
+    }
+}
+
+impl From<ofdb_db_sqlite::Connections> for Connections {
+    fn from(from: ofdb_db_sqlite::Connections) -> Self {
+        Self::Sqlite(from)
+    }
+}
+
+impl From<ofdb_db_postgres::Connections> for Connections {
+    fn from(from: ofdb_db_postgres::Connections) -> Self {
+        Self::Postgres(from)
+    }
+}
+
+pub fn run_embedded_database_migrations(conn: DbReadWrite<'_>) {
+    match conn {
+        DbReadWrite::Sqlite(conn) => ofdb_db_sqlite::run_embedded_database_migrations(conn),
+        DbReadWrite::Postgres(conn) => ofdb_db_postgres::run_embedded_database_migrations(conn),
+    }
+}
+
+pub enum DbReadOnly<'a> {
+    Sqlite(ofdb_db_sqlite::DbReadOnly<'a>),
+    Postgres(ofdb_db_postgres::DbReadOnly<'a>),
+}
+
+impl DbReadOnly<'_> {
+    fn repos(&self) -> &dyn Repositories {
+        match self {
+            Self::Sqlite(db) => db,
+            Self::Postgres(db) => db,
+        }
+    }
+
+    fn repos_mut(&mut self) -> &mut dyn Repositories {
+        match self {
+            Self::Sqlite(db) => db,
+            Self::Postgres(db) => db,
+        }
+    }
+}
+
+impl CategoryRepo for DbReadOnly<'_> {}
+
+pub enum DbReadWrite<'a> {
+    Sqlite(ofdb_db_sqlite::DbReadWrite<'a>),
+    Postgres(ofdb_db_postgres::DbReadWrite<'a>),
+}
+
+impl DbReadWrite<'_> {
+    pub fn transaction<T, F, E>(&mut self, f: F) -> std::result::Result<T, usecases::Error>
+    where
+        F: FnOnce(&mut DbConnection) -> std::result::Result<T, E>,
+        E: Into<usecases::Error>,
+    {
+        match self {
+            Self::Sqlite(db) => db.transaction(|conn| f(&mut DbConnection { repos: conn })),
+            Self::Postgres(db) => db.transaction(|conn| f(&mut DbConnection { repos: conn })),
+        }
+    }
+
+    fn repos(&self) -> &dyn Repositories {
+        match self {
+            Self::Sqlite(db) => db,
+            Self::Postgres(db) => db,
+        }
+    }
+
+    fn repos_mut(&mut self) -> &mut dyn Repositories {
+        match self {
+            Self::Sqlite(db) => db,
+            Self::Postgres(db) => db,
+        }
+    }
+}
+
+/// Connection within a transaction
+pub struct DbConnection<'a> {
+    repos: &'a mut (dyn Repositories + 'a),
+}
+
+impl DbConnection<'_> {
+    fn repos(&self) -> &dyn Repositories {
+        self.repos
+    }
+
+    fn repos_mut(&mut self) -> &mut dyn Repositories {
+        self.repos
+    }
+}
+
+macro_rules! impl_repositories {
+    ($db:ident) => {
+        impl ApiTokenRepo for $db<'_> {
+            fn create_api_token(&self, api_token: &ApiToken) -> Result<()> {
+                self.repos().create_api_token(api_token)
+            }
+            fn get_api_token_by_hash(&self, token_hash: &str) -> Result<ApiToken> {
+                self.repos().get_api_token_by_hash(token_hash)
+            }
+            fn list_api_tokens_of_org(&self, org_id: &Id) -> Result<Vec<ApiToken>> {
+                self.repos().list_api_tokens_of_org(org_id)
+            }
+            fn revoke_api_token(&self, org_id: &Id, id: &Id, revoked_at: Timestamp) -> Result<()> {
+                self.repos().revoke_api_token(org_id, id, revoked_at)
+            }
+            fn update_api_token_last_used(&self, id: &Id, last_used_at: Timestamp) -> Result<()> {
+                self.repos().update_api_token_last_used(id, last_used_at)
+            }
+        }
+
+        impl ChangeRepo for $db<'_> {
+            fn load_changes(
+                &self,
+                after: Option<&ChangeCursor>,
+                limit: u64,
+            ) -> Result<Vec<Change>> {
+                self.repos().load_changes(after, limit)
+            }
+        }
+
+        impl CommentRepository for $db<'_> {
+            fn create_comment(&self, comment: Comment) -> Result<()> {
+                self.repos().create_comment(comment)
+            }
+            fn load_comment(&self, id: &str) -> Result<Comment> {
+                self.repos().load_comment(id)
+            }
+            fn load_comments(&self, ids: &[&str]) -> Result<Vec<Comment>> {
+                self.repos().load_comments(ids)
+            }
+            fn load_comments_of_rating(&self, rating_id: &str) -> Result<Vec<Comment>> {
+                self.repos().load_comments_of_rating(rating_id)
+            }
+            fn load_all_comments_of_rating(&self, rating_id: &str) -> Result<Vec<Comment>> {
+                self.repos().load_all_comments_of_rating(rating_id)
+            }
+            fn zip_ratings_with_comments(
+                &self,
+                ratings: Vec<Rating>,
+            ) -> Result<Vec<(Rating, Vec<Comment>)>> {
+                self.repos().zip_ratings_with_comments(ratings)
+            }
+            fn archive_comments(&self, ids: &[&str], activity: &Activity) -> Result<usize> {
+                self.repos().archive_comments(ids, activity)
+            }
+            fn archive_comments_of_ratings(
+                &self,
+                rating_ids: &[&str],
+                activity: &Activity,
+            ) -> Result<usize> {
+                self.repos()
+                    .archive_comments_of_ratings(rating_ids, activity)
+            }
+            fn archive_comments_of_places(
+                &self,
+                place_ids: &[&str],
+                activity: &Activity,
+            ) -> Result<usize> {
+                self.repos().archive_comments_of_places(place_ids, activity)
+            }
+        }
+
+        impl EventClearanceRepo for $db<'_> {
+            fn add_pending_clearance_for_events(
+                &self,
+                org_ids: &[Id],
+                pending_clearance: &PendingClearanceForEvent,
+            ) -> Result<usize> {
+                self.repos()
+                    .add_pending_clearance_for_events(org_ids, pending_clearance)
+            }
+            fn count_pending_clearances_for_events(&self, org_id: &Id) -> Result<u64> {
+                self.repos().count_pending_clearances_for_events(org_id)
+            }
+            fn list_pending_clearances_for_events(
+                &self,
+                org_id: &Id,
+                pagination: &Pagination,
+            ) -> Result<Vec<PendingClearanceForEvent>> {
+                self.repos()
+                    .list_pending_clearances_for_events(org_id, pagination)
+            }
+            fn load_pending_clearances_for_events(
+                &self,
+                org_id: &Id,
+                event_ids: &[&str],
+            ) -> Result<Vec<PendingClearanceForEvent>> {
+                self.repos()
+                    .load_pending_clearances_for_events(org_id, event_ids)
+            }
+            fn update_pending_clearances_for_events(
+                &self,
+                org_id: &Id,
+                clearances: &[ClearanceForEvent],
+                activity: &ActivityLog,
+            ) -> Result<usize> {
+                self.repos()
+                    .update_pending_clearances_for_events(org_id, clearances, activity)
+            }
+            fn cleanup_pending_clearances_for_events(&self, org_id: &Id) -> Result<u64> {
+                self.repos().cleanup_pending_clearances_for_events(org_id)
+            }
+            fn list_clearance_log_for_events(
+                &self,
+                org_id: &Id,
+                pagination: &Pagination,
+            ) -> Result<Vec<ClearanceLogForEvent>> {
+                self.repos()
+                    .list_clearance_log_for_events(org_id, pagination)
+            }
+            fn add_clearance_log_for_events(
+                &self,
+                org_id: &Id,
+                log: &ClearanceLogForEvent,
+            ) -> Result<()> {
+                self.repos().add_clearance_log_for_events(org_id, log)
+            }
+        }
+
+        impl EventRepo for $db<'_> {
+            fn create_event(&self, event: Event, activity: &ActivityLog) -> Result<()> {
+                self.repos().create_event(event, activity)
+            }
+            fn update_event(&self, event: &Event, activity: &ActivityLog) -> Result<()> {
+                self.repos().update_event(event, activity)
+            }
+            fn archive_events(&self, ids: &[&str], archived: Timestamp) -> Result<usize> {
+                self.repos().archive_events(ids, archived)
+            }
+            fn get_event(&self, id: &str) -> Result<Event> {
+                self.repos().get_event(id)
+            }
+            fn get_events_chronologically(&self, ids: &[&str]) -> Result<Vec<Event>> {
+                self.repos().get_events_chronologically(ids)
+            }
+            fn all_events_chronologically(&self) -> Result<Vec<Event>> {
+                self.repos().all_events_chronologically()
+            }
+            fn all_events_in_bbox_chronologically(&self, bbox: &MapBbox) -> Result<Vec<Event>> {
+                self.repos().all_events_in_bbox_chronologically(bbox)
+            }
+            fn all_archived_events_chronologically(&self) -> Result<Vec<Event>> {
+                self.repos().all_archived_events_chronologically()
+            }
+            fn count_events(&self) -> Result<usize> {
+                self.repos().count_events()
+            }
+            fn delete_event_with_matching_tags(&self, id: &str, tags: &[&str]) -> Result<bool> {
+                self.repos().delete_event_with_matching_tags(id, tags)
+            }
+            fn is_event_owned_by_any_organization(&self, id: &str) -> Result<bool> {
+                self.repos().is_event_owned_by_any_organization(id)
+            }
+            fn get_event_history(
+                &self,
+                id: &str,
+                revision: Option<Revision>,
+            ) -> Result<EventHistory> {
+                self.repos().get_event_history(id, revision)
+            }
+            fn load_event_revision(&self, id: &str, rev: Revision) -> Result<Event> {
+                self.repos().load_event_revision(id, rev)
+            }
+        }
+
+        impl OrganizationRepo for $db<'_> {
+            fn create_org(&mut self, org: Organization) -> Result<()> {
+                self.repos_mut().create_org(org)
+            }
+            fn update_org(&mut self, org: Organization) -> Result<()> {
+                self.repos_mut().update_org(org)
+            }
+            fn delete_org(&mut self, id: &Id) -> Result<()> {
+                self.repos_mut().delete_org(id)
+            }
+            fn get_org_by_id(&self, id: &Id) -> Result<Organization> {
+                self.repos().get_org_by_id(id)
+            }
+            fn all_orgs(&self) -> Result<Vec<Organization>> {
+                self.repos().all_orgs()
+            }
+            fn get_org_by_api_token(&self, token: &str) -> Result<Organization> {
+                self.repos().get_org_by_api_token(token)
+            }
+            fn map_tag_to_clearance_org_id(&self, tag: &str) -> Result<Option<Id>> {
+                self.repos().map_tag_to_clearance_org_id(tag)
+            }
+            fn get_moderated_tags_by_org(
+                &self,
+                excluded_org_id: Option<&Id>,
+            ) -> Result<Vec<(Id, ModeratedTag)>> {
+                self.repos().get_moderated_tags_by_org(excluded_org_id)
+            }
+        }
+
+        impl OrganizationMemberRepo for $db<'_> {
+            fn add_or_update_org_member(&self, member: &OrganizationMember) -> Result<()> {
+                self.repos().add_or_update_org_member(member)
+            }
+            fn remove_org_member(&self, org_id: &Id, email: &EmailAddress) -> Result<()> {
+                self.repos().remove_org_member(org_id, email)
+            }
+            fn list_org_members(&self, org_id: &Id) -> Result<Vec<OrganizationMember>> {
+                self.repos().list_org_members(org_id)
+            }
+            fn list_org_memberships_of_user(
+                &self,
+                email: &EmailAddress,
+            ) -> Result<Vec<OrganizationMember>> {
+                self.repos().list_org_memberships_of_user(email)
+            }
+        }
+
+        impl PlaceClearanceRepo for $db<'_> {
+            fn add_pending_clearance_for_places(
+                &self,
+                org_ids: &[Id],
+                pending_clearance: &PendingClearanceForPlace,
+            ) -> Result<usize> {
+                self.repos()
+                    .add_pending_clearance_for_places(org_ids, pending_clearance)
+            }
+            fn count_pending_clearances_for_places(&self, org_id: &Id) -> Result<u64> {
+                self.repos().count_pending_clearances_for_places(org_id)
+            }
+            fn list_pending_clearances_for_places(
+                &self,
+                org_id: &Id,
+                pagination: &Pagination,
+            ) -> Result<Vec<PendingClearanceForPlace>> {
+                self.repos()
+                    .list_pending_clearances_for_places(org_id, pagination)
+            }
+            fn load_pending_clearances_for_places(
+                &self,
+                org_id: &Id,
+                place_ids: &[&str],
+            ) -> Result<Vec<PendingClearanceForPlace>> {
+                self.repos()
+                    .load_pending_clearances_for_places(org_id, place_ids)
+            }
+            fn update_pending_clearances_for_places(
+                &self,
+                org_id: &Id,
+                clearances: &[ClearanceForPlace],
+                activity: &ActivityLog,
+            ) -> Result<usize> {
+                self.repos()
+                    .update_pending_clearances_for_places(org_id, clearances, activity)
+            }
+            fn cleanup_pending_clearances_for_places(&self, org_id: &Id) -> Result<u64> {
+                self.repos().cleanup_pending_clearances_for_places(org_id)
+            }
+            fn list_clearance_log_for_places(
+                &self,
+                org_id: &Id,
+                pagination: &Pagination,
+            ) -> Result<Vec<ClearanceLogForPlace>> {
+                self.repos()
+                    .list_clearance_log_for_places(org_id, pagination)
+            }
+            fn add_clearance_log_for_places(
+                &self,
+                org_id: &Id,
+                log: &ClearanceLogForPlace,
+            ) -> Result<()> {
+                self.repos().add_clearance_log_for_places(org_id, log)
+            }
+        }
+
+        impl PlaceRepo for $db<'_> {
+            fn get_place(&self, id: &str) -> Result<(Place, ReviewStatus)> {
+                self.repos().get_place(id)
+            }
+            fn get_places(&self, ids: &[&str]) -> Result<Vec<(Place, ReviewStatus)>> {
+                self.repos().get_places(ids)
+            }
+            fn all_places(&self) -> Result<Vec<(Place, ReviewStatus)>> {
+                self.repos().all_places()
+            }
+            fn all_places_in_bbox(&self, bbox: &MapBbox) -> Result<Vec<(Place, ReviewStatus)>> {
+                self.repos().all_places_in_bbox(bbox)
+            }
+            fn count_places(&self) -> Result<usize> {
+                self.repos().count_places()
+            }
+            fn recently_changed_places(
+                &self,
+                params: &RecentlyChangedEntriesParams,
+                pagination: &Pagination,
+            ) -> Result<Vec<(Place, ReviewStatus, ActivityLog)>> {
+                self.repos().recently_changed_places(params, pagination)
+            }
+            fn find_places_not_updated_since(
+                &self,
+                not_updated_since: Timestamp,
+                pagination: &Pagination,
+            ) -> Result<Vec<(Place, ReviewStatus)>> {
+                self.repos()
+                    .find_places_not_updated_since(not_updated_since, pagination)
+            }
+            fn most_popular_place_revision_tags(
+                &self,
+                params: &MostPopularTagsParams,
+                pagination: &Pagination,
+            ) -> Result<Vec<TagFrequency>> {
+                self.repos()
+                    .most_popular_place_revision_tags(params, pagination)
+            }
+            fn review_places(
+                &self,
+                ids: &[&str],
+                status: ReviewStatus,
+                activity: &ActivityLog,
+            ) -> Result<usize> {
+                self.repos().review_places(ids, status, activity)
+            }
+            fn create_or_update_place(&self, place: Place) -> Result<()> {
+                self.repos().create_or_update_place(place)
+            }
+            fn get_place_history(
+                &self,
+                id: &str,
+                revision: Option<Revision>,
+            ) -> Result<PlaceHistory> {
+                self.repos().get_place_history(id, revision)
+            }
+            fn load_place_revision(
+                &self,
+                id: &str,
+                rev: Revision,
+            ) -> Result<(Place, ReviewStatus)> {
+                self.repos().load_place_revision(id, rev)
+            }
+        }
+
+        impl RatingRepository for $db<'_> {
+            fn create_rating(&self, rating: Rating) -> Result<()> {
+                self.repos().create_rating(rating)
+            }
+            fn load_rating(&self, id: &str) -> Result<Rating> {
+                self.repos().load_rating(id)
+            }
+            fn load_ratings(&self, ids: &[&str]) -> Result<Vec<Rating>> {
+                self.repos().load_ratings(ids)
+            }
+            fn load_ratings_of_place(&self, place_id: &str) -> Result<Vec<Rating>> {
+                self.repos().load_ratings_of_place(place_id)
+            }
+            fn load_all_ratings_of_place(&self, place_id: &str) -> Result<Vec<Rating>> {
+                self.repos().load_all_ratings_of_place(place_id)
+            }
+            fn archive_ratings(&self, ids: &[&str], activity: &Activity) -> Result<usize> {
+                self.repos().archive_ratings(ids, activity)
+            }
+            fn archive_ratings_of_places(
+                &self,
+                place_ids: &[&str],
+                activity: &Activity,
+            ) -> Result<usize> {
+                self.repos().archive_ratings_of_places(place_ids, activity)
+            }
+            fn load_place_ids_of_ratings(&self, ids: &[&str]) -> Result<Vec<String>> {
+                self.repos().load_place_ids_of_ratings(ids)
+            }
+            fn move_ratings_of_place(&self, from_place_id: &Id, to_place_id: &Id) -> Result<usize> {
+                self.repos()
+                    .move_ratings_of_place(from_place_id, to_place_id)
+            }
+        }
+
+        impl DuplicateCandidateRepo for $db<'_> {
+            fn replace_open_duplicate_candidates(
+                &self,
+                candidates: &[DuplicateCandidate],
+            ) -> Result<usize> {
+                self.repos().replace_open_duplicate_candidates(candidates)
+            }
+            fn count_open_duplicate_candidates(&self) -> Result<usize> {
+                self.repos().count_open_duplicate_candidates()
+            }
+            fn list_open_duplicate_candidates(
+                &self,
+                pagination: &Pagination,
+            ) -> Result<Vec<DuplicateCandidate>> {
+                self.repos().list_open_duplicate_candidates(pagination)
+            }
+            fn review_duplicate_candidate(
+                &self,
+                place_id: &Id,
+                duplicate_place_id: &Id,
+                state: DuplicateCandidateState,
+                activity: &Activity,
+            ) -> Result<usize> {
+                self.repos().review_duplicate_candidate(
+                    place_id,
+                    duplicate_place_id,
+                    state,
+                    activity,
+                )
+            }
+        }
+
+        impl PlaceRedirectRepo for $db<'_> {
+            fn create_place_redirect(&self, merged_place_id: &Id, place_id: &Id) -> Result<()> {
+                self.repos()
+                    .create_place_redirect(merged_place_id, place_id)
+            }
+            fn resolve_place_redirect(&self, id: &Id) -> Result<Option<Id>> {
+                self.repos().resolve_place_redirect(id)
+            }
+        }
+
+        impl ReminderRepo for $db<'_> {
+            fn find_last_sent_reminder(
+                &self,
+                place_id: &Id,
+                email: &EmailAddress,
+            ) -> Result<Option<Timestamp>> {
+                self.repos().find_last_sent_reminder(place_id, email)
+            }
+            fn save_sent_reminders(
+                &self,
+                place_id: &Id,
+                recipients: &[EmailAddress],
+                sent_at: Timestamp,
+            ) -> Result<()> {
+                self.repos()
+                    .save_sent_reminders(place_id, recipients, sent_at)
+            }
+        }
+
+        impl ReviewTokenRepo for $db<'_> {
+            fn add_review_token(&self, review_token: &ReviewToken) -> Result<()> {
+                self.repos().add_review_token(review_token)
+            }
+            fn consume_review_token(&self, review_nonce: &ReviewNonce) -> Result<ReviewToken> {
+                self.repos().consume_review_token(review_nonce)
+            }
+            fn delete_expired_review_tokens(&self, expired_before: Timestamp) -> Result<usize> {
+                self.repos().delete_expired_review_tokens(expired_before)
+            }
+        }
+
+        impl RevokedTokenRepo for $db<'_> {
+            fn add_revoked_token(&self, id: &str, expires_at: Timestamp) -> Result<()> {
+                self.repos().add_revoked_token(id, expires_at)
+            }
+            fn is_token_revoked(&self, id: &str) -> Result<bool> {
+                self.repos().is_token_revoked(id)
+            }
+            fn delete_expired_revoked_tokens(&self, expired_before: Timestamp) -> Result<usize> {
+                self.repos().delete_expired_revoked_tokens(expired_before)
+            }
+        }
+
+        impl SubscriptionRepo for $db<'_> {
+            fn create_bbox_subscription(&self, bbox: &BboxSubscription) -> Result<()> {
+                self.repos().create_bbox_subscription(bbox)
+            }
+            fn all_bbox_subscriptions(&self) -> Result<Vec<BboxSubscription>> {
+                self.repos().all_bbox_subscriptions()
+            }
+            fn all_bbox_subscriptions_by_email(
+                &self,
+                user_email: &EmailAddress,
+            ) -> Result<Vec<BboxSubscription>> {
+                self.repos().all_bbox_subscriptions_by_email(user_email)
+            }
+            fn delete_bbox_subscriptions_by_email(&self, user_email: &EmailAddress) -> Result<()> {
+                self.repos().delete_bbox_subscriptions_by_email(user_email)
+            }
+        }
+
+        impl TagRepo for $db<'_> {
+            fn create_tag_if_it_does_not_exist(&self, tag: &Tag) -> Result<()> {
+                self.repos().create_tag_if_it_does_not_exist(tag)
+            }
+            fn all_tags(&self) -> Result<Vec<Tag>> {
+                self.repos().all_tags()
+            }
+            fn count_tags(&self) -> Result<usize> {
+                self.repos().count_tags()
+            }
+        }
+
+        impl UserRepo for $db<'_> {
+            fn create_user(&self, user: &User) -> Result<()> {
+                self.repos().create_user(user)
+            }
+            fn update_user(&self, user: &User) -> Result<()> {
+                self.repos().update_user(user)
+            }
+            fn delete_user_by_email(&self, email: &EmailAddress) -> Result<()> {
+                self.repos().delete_user_by_email(email)
+            }
+            fn all_users(&self) -> Result<Vec<User>> {
+                self.repos().all_users()
+            }
+            fn count_users(&self) -> Result<usize> {
+                self.repos().count_users()
+            }
+            fn get_user_by_email(&self, email: &EmailAddress) -> Result<User> {
+                self.repos().get_user_by_email(email)
+            }
+            fn try_get_user_by_email(&self, email: &EmailAddress) -> Result<Option<User>> {
+                self.repos().try_get_user_by_email(email)
+            }
+        }
+
+        impl UserTokenRepo for $db<'_> {
+            fn replace_user_token(&self, user_token: UserToken) -> Result<EmailNonce> {
+                self.repos().replace_user_token(user_token)
+            }
+            fn consume_user_token(&self, email_nonce: &EmailNonce) -> Result<UserToken> {
+                self.repos().consume_user_token(email_nonce)
+            }
+            fn delete_expired_user_tokens(&self, expired_before: Timestamp) -> Result<usize> {
+                self.repos().delete_expired_user_tokens(expired_before)
+            }
+            fn get_user_token_by_email(&self, email: &EmailAddress) -> Result<UserToken> {
+                self.repos().get_user_token_by_email(email)
+            }
+        }
+
+        impl WebhookRepo for $db<'_> {
+            fn create_webhook(&self, webhook: &Webhook) -> Result<()> {
+                self.repos().create_webhook(webhook)
+            }
+            fn get_webhook(&self, id: &Id) -> Result<Webhook> {
+                self.repos().get_webhook(id)
+            }
+            fn list_webhooks_of_org(&self, org_id: &Id) -> Result<Vec<Webhook>> {
+                self.repos().list_webhooks_of_org(org_id)
+            }
+            fn delete_webhook(&self, org_id: &Id, id: &Id) -> Result<()> {
+                self.repos().delete_webhook(org_id, id)
+            }
+            fn create_webhook_delivery(&self, delivery: &WebhookDelivery) -> Result<()> {
+                self.repos().create_webhook_delivery(delivery)
+            }
+            fn update_webhook_delivery(&self, delivery: &WebhookDelivery) -> Result<()> {
+                self.repos().update_webhook_delivery(delivery)
+            }
+            fn claim_webhook_delivery(
+                &self,
+                delivery: &WebhookDelivery,
+                claimed_until: Timestamp,
+            ) -> Result<bool> {
+                self.repos().claim_webhook_delivery(delivery, claimed_until)
+            }
+            fn load_due_webhook_deliveries(
+                &self,
+                due_at: Timestamp,
+                limit: u64,
+            ) -> Result<Vec<WebhookDelivery>> {
+                self.repos().load_due_webhook_deliveries(due_at, limit)
+            }
+            fn list_webhook_deliveries(
+                &self,
+                webhook_id: &Id,
+                pagination: &Pagination,
+            ) -> Result<Vec<WebhookDelivery>> {
+                self.repos().list_webhook_deliveries(webhook_id, pagination)
+            }
+        }
+    };
+}
+
+impl_repositories!(DbReadOnly);
+impl_repositories!(DbReadWrite);
+impl_repositories!(DbConnection);
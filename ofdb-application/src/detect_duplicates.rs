@@ -0,0 +1,93 @@
+use super::*;
+
+/// Detect possible duplicates among all places and replace the
+/// open duplicate candidates that are awaiting a review.
+///
+/// Returns the number of new candidates.
+pub fn detect_duplicates(
+    connections: &sqlite::Connections,
+    place_index: &dyn PlaceIndex,
+) -> Result<usize> {
+    // The database is only locked while loading places and not
+    // during the whole detection that might take a while
+    let places = connections.shared()?.all_places()?;
+    let candidates = usecases::detect_duplicate_candidates(
+        place_index,
+        places,
+        |ids| connections.shared()?.get_places(ids),
+        Timestamp::now(),
+    )?;
+    let count = connections
+        .exclusive()?
+        .transaction(|conn| conn.replace_open_duplicate_candidates(&candidates))?;
+    info!(
+        "Detected {} new of {} possibly duplicate places",
+        count,
+        candidates.len()
+    );
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::prelude::*;
+
+    fn new_place(title: &str) -> NewPlace {
+        let mut place = NewPlace::from(0);
+        place.title = title.into();
+        place
+    }
+
+    #[test]
+    fn detect_similar_places_once() {
+        let fixture = BackendFixture::new();
+        fixture.create_user(
+            usecases::NewUser {
+                email: "scout@example.com".parse().unwrap(),
+                password: "secret123".into(),
+            },
+            None,
+        );
+        let place_id = fixture.create_place(new_place("Fair Coffee Shop"), None);
+        let duplicate_place_id = fixture.create_place(new_place("Fair Cofee Shop"), None);
+        fixture.create_place(new_place("Something different"), None);
+
+        let detect = || {
+            super::detect_duplicates(&fixture.db_connections, &*fixture.search_engine.borrow())
+                .unwrap()
+        };
+        assert_eq!(1, detect());
+        assert_eq!(0, detect());
+
+        let db = fixture.db_connections.shared().unwrap();
+        let candidates = db
+            .list_open_duplicate_candidates(&Pagination::default())
+            .unwrap();
+        assert_eq!(1, candidates.len());
+        let candidate = &candidates[0];
+        assert_eq!(
+            DuplicateCandidate::ordered_pair(
+                place_id.as_str().into(),
+                duplicate_place_id.as_str().into()
+            ),
+            (
+                candidate.place_id.clone(),
+                candidate.duplicate_place_id.clone()
+            )
+        );
+        assert!(candidate.similarity > 0.5);
+        drop(db);
+
+        // Dismissed candidates are not detected again
+        usecases::dismiss_duplicate_candidate(
+            &fixture.db_connections.exclusive().unwrap(),
+            place_id.as_str().into(),
+            duplicate_place_id.as_str().into(),
+            "scout@example.com".parse().unwrap(),
+        )
+        .unwrap();
+        assert_eq!(0, detect());
+        let db = fixture.db_connections.shared().unwrap();
+        assert_eq!(0, db.count_open_duplicate_candidates().unwrap());
+    }
+}
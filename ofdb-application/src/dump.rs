@@ -0,0 +1,882 @@
+//! A versioned, line-oriented archive of the database contents.
+//!
+//! Each line contains a single JSON record. The first record is a header
+//! that identifies the format and its version. All other records are
+//! written in an order that allows to restore them one after another.
+//!
+//! Archived ratings, comments and events are included. Transient data like
+//! login and review tokens, revoked tokens, sent reminders, webhook
+//! deliveries, and duplicate candidates is not included.
+
+use std::io::{self, BufRead, Write};
+
+use anyhow::anyhow;
+use ofdb_boundary as json;
+use ofdb_core::{entities::recurrence::Recurrence, repositories::Error as RepoError};
+use serde::{Deserialize, Serialize};
+
+use super::*;
+use crate::db::DbConnection;
+
+const FORMAT: &str = "openfairdb-dump";
+
+/// The current version of the archive format.
+pub const DUMP_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DumpOptions {
+    /// Include the password hashes of all users
+    pub include_password_hashes: bool,
+    /// Include the legacy API tokens of organizations
+    /// and the secrets of webhooks
+    pub include_secrets: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DumpSummary {
+    pub users: usize,
+    pub tags: usize,
+    pub organizations: usize,
+    pub organization_members: usize,
+    pub api_tokens: usize,
+    pub webhooks: usize,
+    pub places: usize,
+    pub place_redirects: usize,
+    pub ratings: usize,
+    pub comments: usize,
+    pub events: usize,
+    pub pending_clearances: usize,
+    pub clearance_logs: usize,
+    pub bbox_subscriptions: usize,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Record {
+    Header(Header),
+    User(User),
+    Tag(Tag),
+    Organization(Organization),
+    OrganizationMember(OrganizationMember),
+    ApiToken(ApiToken),
+    Webhook(Webhook),
+    Place(json::PlaceHistory),
+    PlaceRedirect(PlaceRedirect),
+    Rating(Rating),
+    Comment(Comment),
+    Event(EventHistory),
+    PendingClearanceForPlace(PendingClearanceForPlace),
+    PendingClearanceForEvent(PendingClearanceForEvent),
+    ClearanceLogForPlace(ClearanceLogForPlace),
+    ClearanceLogForEvent(ClearanceLogForEvent),
+    BboxSubscription(BboxSubscription),
+}
+
+#[derive(Serialize, Deserialize)]
+struct Header {
+    format: String,
+    version: u32,
+    created_at: json::UnixTimeMillis,
+}
+
+#[derive(Serialize, Deserialize)]
+struct User {
+    email: String,
+    email_confirmed: bool,
+    role: json::UserRole,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    password_hash: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Tag {
+    id: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Organization {
+    id: String,
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    api_token: Option<String>,
+    moderated_tags: Vec<ModeratedTag>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ModeratedTag {
+    label: String,
+    allow_add: bool,
+    allow_remove: bool,
+    require_clearance: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+struct OrganizationMember {
+    org_id: String,
+    email: String,
+    role: json::OrganizationRole,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ApiToken {
+    id: String,
+    org_id: String,
+    name: String,
+    token_hash: String,
+    scopes: Vec<String>,
+    created_at: json::UnixTimeMillis,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    expires_at: Option<json::UnixTimeMillis>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    last_used_at: Option<json::UnixTimeMillis>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    revoked_at: Option<json::UnixTimeMillis>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Webhook {
+    id: String,
+    org_id: String,
+    url: String,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    secret: Option<String>,
+    created_at: json::UnixTimeMillis,
+}
+
+#[derive(Serialize, Deserialize)]
+struct PlaceRedirect {
+    place_id: String,
+    target_place_id: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Rating {
+    id: String,
+    place_id: String,
+    created_at: json::UnixTimeMillis,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    archived_at: Option<json::UnixTimeMillis>,
+    title: String,
+    value: json::RatingValue,
+    context: json::RatingContext,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    source: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Comment {
+    id: String,
+    rating_id: String,
+    created_at: json::UnixTimeMillis,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    archived_at: Option<json::UnixTimeMillis>,
+    text: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct EventHistory {
+    #[serde(flatten)]
+    history: json::EventHistory,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    archived: Option<json::UnixTimeMillis>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct PendingClearanceForPlace {
+    org_id: String,
+    place_id: String,
+    created_at: json::UnixTimeMillis,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    last_cleared_revision: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct PendingClearanceForEvent {
+    org_id: String,
+    event_id: String,
+    created_at: json::UnixTimeMillis,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    last_cleared_revision: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ClearanceLogForPlace {
+    org_id: String,
+    place_id: String,
+    cleared_revision: u64,
+    act: json::ActivityLog,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ClearanceLogForEvent {
+    org_id: String,
+    event_id: String,
+    cleared_revision: u64,
+    act: json::ActivityLog,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BboxSubscription {
+    id: String,
+    user_email: String,
+    bbox: json::MapBbox,
+}
+
+fn write_record<W: Write>(writer: &mut W, record: &Record) -> Result<()> {
+    serde_json::to_writer(&mut *writer, record)?;
+    writer.write_all(b"\n")?;
+    Ok(())
+}
+
+/// Write the contents of the database as a stream of records.
+pub fn dump<R, W>(repo: &R, writer: &mut W, options: DumpOptions) -> Result<DumpSummary>
+where
+    R: UserRepo
+        + TagRepo
+        + OrganizationRepo
+        + OrganizationMemberRepo
+        + ApiTokenRepo
+        + WebhookRepo
+        + PlaceRepo
+        + PlaceRedirectRepo
+        + RatingRepository
+        + CommentRepository
+        + EventRepo
+        + PlaceClearanceRepo
+        + EventClearanceRepo
+        + SubscriptionRepo,
+    W: Write,
+{
+    let DumpOptions {
+        include_password_hashes,
+        include_secrets,
+    } = options;
+    let mut summary = DumpSummary::default();
+    write_record(
+        writer,
+        &Record::Header(Header {
+            format: FORMAT.to_owned(),
+            version: DUMP_VERSION,
+            created_at: Timestamp::now().into(),
+        }),
+    )?;
+    // Users must be restored first, because all other records
+    // refer to them by their e-mail address.
+    for user in repo.all_users()? {
+        let record = User {
+            email: user.email.into_string(),
+            email_confirmed: user.email_confirmed,
+            role: user.role.into(),
+            password_hash: include_password_hashes.then(|| user.password.into()),
+        };
+        write_record(writer, &Record::User(record))?;
+        summary.users += 1;
+    }
+    for tag in repo.all_tags()? {
+        write_record(writer, &Record::Tag(Tag { id: tag.id }))?;
+        summary.tags += 1;
+    }
+    let orgs = repo.all_orgs()?;
+    for org in &orgs {
+        let record = Organization {
+            id: org.id.to_string(),
+            name: org.name.clone(),
+            api_token: include_secrets.then(|| org.api_token.clone()),
+            moderated_tags: org
+                .moderated_tags
+                .iter()
+                .map(|tag| ModeratedTag {
+                    label: tag.label.clone(),
+                    allow_add: tag.allow_add,
+                    allow_remove: tag.allow_remove,
+                    require_clearance: tag.require_clearance,
+                })
+                .collect(),
+        };
+        write_record(writer, &Record::Organization(record))?;
+        summary.organizations += 1;
+        for member in repo.list_org_members(&org.id)? {
+            let record = OrganizationMember {
+                org_id: member.org_id.into(),
+                email: member.email.into_string(),
+                role: member.role.into(),
+            };
+            write_record(writer, &Record::OrganizationMember(record))?;
+            summary.organization_members += 1;
+        }
+        for api_token in repo.list_api_tokens_of_org(&org.id)? {
+            let record = ApiToken {
+                id: api_token.id.into(),
+                org_id: api_token.org_id.into(),
+                name: api_token.name,
+                token_hash: api_token.token_hash,
+                scopes: api_token
+                    .scopes
+                    .into_iter()
+                    .map(|scope| scope.as_str().to_owned())
+                    .collect(),
+                created_at: api_token.created_at.into(),
+                expires_at: api_token.expires_at.map(Into::into),
+                last_used_at: api_token.last_used_at.map(Into::into),
+                revoked_at: api_token.revoked_at.map(Into::into),
+            };
+            write_record(writer, &Record::ApiToken(record))?;
+            summary.api_tokens += 1;
+        }
+        for webhook in repo.list_webhooks_of_org(&org.id)? {
+            let record = Webhook {
+                id: webhook.id.into(),
+                org_id: webhook.org_id.into(),
+                url: webhook.url.into(),
+                secret: include_secrets.then_some(webhook.secret),
+                created_at: webhook.created_at.into(),
+            };
+            write_record(writer, &Record::Webhook(record))?;
+            summary.webhooks += 1;
+        }
+    }
+    // TODO: Split into chunks with fixed size instead of
+    // loading all places at once!
+    let places = repo.all_places()?;
+    for (place, _) in &places {
+        let history = repo.get_place_history(place.id.as_ref(), None)?;
+        write_record(writer, &Record::Place(history.into()))?;
+        summary.places += 1;
+        for rating in repo.load_all_ratings_of_place(place.id.as_ref())? {
+            let comments = repo.load_all_comments_of_rating(rating.id.as_ref())?;
+            let record = Rating {
+                id: rating.id.into(),
+                place_id: rating.place_id.into(),
+                created_at: rating.created_at.into(),
+                archived_at: rating.archived_at.map(Into::into),
+                title: rating.title,
+                value: rating.value.into(),
+                context: rating.context.into(),
+                source: rating.source,
+            };
+            write_record(writer, &Record::Rating(record))?;
+            summary.ratings += 1;
+            for comment in comments {
+                let record = Comment {
+                    id: comment.id.into(),
+                    rating_id: comment.rating_id.into(),
+                    created_at: comment.created_at.into(),
+                    archived_at: comment.archived_at.map(Into::into),
+                    text: comment.text,
+                };
+                write_record(writer, &Record::Comment(record))?;
+                summary.comments += 1;
+            }
+        }
+    }
+    // Redirects refer to both the merged and the surviving place
+    for (place, _) in &places {
+        if let Some(target_place_id) = repo.resolve_place_redirect(&place.id)? {
+            let record = PlaceRedirect {
+                place_id: place.id.to_string(),
+                target_place_id: target_place_id.into(),
+            };
+            write_record(writer, &Record::PlaceRedirect(record))?;
+            summary.place_redirects += 1;
+        }
+    }
+    let events = repo.all_events_chronologically()?;
+    let archived_events = repo.all_archived_events_chronologically()?;
+    for event in events.into_iter().chain(archived_events) {
+        let history = repo.get_event_history(event.id.as_ref(), None)?;
+        let record = EventHistory {
+            history: history.into(),
+            archived: event.archived.map(Into::into),
+        };
+        write_record(writer, &Record::Event(record))?;
+        summary.events += 1;
+    }
+    // Clearances refer to places and events
+    for org in &orgs {
+        let pagination = Pagination::default();
+        for pending in repo.list_pending_clearances_for_places(&org.id, &pagination)? {
+            let record = PendingClearanceForPlace {
+                org_id: org.id.to_string(),
+                place_id: pending.place_id.into(),
+                created_at: pending.created_at.into(),
+                last_cleared_revision: pending.last_cleared_revision.map(Into::into),
+            };
+            write_record(writer, &Record::PendingClearanceForPlace(record))?;
+            summary.pending_clearances += 1;
+        }
+        for pending in repo.list_pending_clearances_for_events(&org.id, &pagination)? {
+            let record = PendingClearanceForEvent {
+                org_id: org.id.to_string(),
+                event_id: pending.event_id.into(),
+                created_at: pending.created_at.into(),
+                last_cleared_revision: pending.last_cleared_revision.map(Into::into),
+            };
+            write_record(writer, &Record::PendingClearanceForEvent(record))?;
+            summary.pending_clearances += 1;
+        }
+        // The log is listed most recent first and restored oldest first
+        let logs = repo.list_clearance_log_for_places(&org.id, &pagination)?;
+        for log in logs.into_iter().rev() {
+            let record = ClearanceLogForPlace {
+                org_id: org.id.to_string(),
+                place_id: log.place_id.into(),
+                cleared_revision: log.cleared_revision.into(),
+                act: log.activity.into(),
+            };
+            write_record(writer, &Record::ClearanceLogForPlace(record))?;
+            summary.clearance_logs += 1;
+        }
+        let logs = repo.list_clearance_log_for_events(&org.id, &pagination)?;
+        for log in logs.into_iter().rev() {
+            let record = ClearanceLogForEvent {
+                org_id: org.id.to_string(),
+                event_id: log.event_id.into(),
+                cleared_revision: log.cleared_revision.into(),
+                act: log.activity.into(),
+            };
+            write_record(writer, &Record::ClearanceLogForEvent(record))?;
+            summary.clearance_logs += 1;
+        }
+    }
+    for subscription in repo.all_bbox_subscriptions()? {
+        let record = BboxSubscription {
+            id: subscription.id.into(),
+            user_email: subscription.user_email.into_string(),
+            bbox: subscription.bbox.into(),
+        };
+        write_record(writer, &Record::BboxSubscription(record))?;
+        summary.bbox_subscriptions += 1;
+    }
+    writer.flush()?;
+    Ok(summary)
+}
+
+fn invalid_record(line: usize, err: impl std::fmt::Display) -> RepoError {
+    anyhow!("Invalid record in line {line}: {err}").into()
+}
+
+/// Restore the records of an archive into an empty database
+/// and rebuild the search index.
+///
+/// All records are restored within a single transaction, i.e.
+/// the database remains empty if restoring any record fails.
+/// Users without a password hash need to reset their password.
+/// Organizations and webhooks without a secret get a new one.
+pub fn restore<R: BufRead>(
+    connections: &sqlite::Connections,
+    indexer: &mut dyn EventAndPlaceIndexer,
+    reader: R,
+) -> Result<DumpSummary> {
+    let mut db = connections.exclusive()?;
+    if db.count_places()? > 0 || db.count_events()? > 0 || db.count_users()? > 0 {
+        return Err(anyhow!("The database is not empty").into());
+    }
+    let mut lines = reader.lines().enumerate();
+    let header = match lines.next() {
+        Some((_, line)) => serde_json::from_str::<Record>(&line?)?,
+        None => return Err(anyhow!("The archive is empty").into()),
+    };
+    match header {
+        Record::Header(Header {
+            format, version, ..
+        }) if format == FORMAT => {
+            if version > DUMP_VERSION {
+                return Err(anyhow!("Unsupported archive version {version}").into());
+            }
+        }
+        _ => return Err(anyhow!("Missing archive header").into()),
+    }
+    let Restored {
+        summary,
+        place_ids,
+        event_ids,
+    } = db.transaction(|conn| restore_records(conn, lines))?;
+
+    info!(
+        "Rebuilding the search index for {} places and {} events",
+        place_ids.len(),
+        event_ids.len()
+    );
+    let place_ids: Vec<_> = place_ids.iter().map(String::as_str).collect();
+    for (place, status) in db.get_places(&place_ids)? {
+        let ratings = db.load_ratings_of_place(place.id.as_ref())?;
+        usecases::reindex_place(indexer, &place, status, &ratings)?;
+    }
+    let event_ids: Vec<_> = event_ids.iter().map(String::as_str).collect();
+    for event in db.get_events_chronologically(&event_ids)? {
+        usecases::index_event(indexer, &event)?;
+    }
+    indexer.flush_index()?;
+    Ok(summary)
+}
+
+struct Restored {
+    summary: DumpSummary,
+    place_ids: Vec<String>,
+    event_ids: Vec<String>,
+}
+
+fn restore_records(
+    conn: &mut DbConnection,
+    lines: impl Iterator<Item = (usize, io::Result<String>)>,
+) -> std::result::Result<Restored, RepoError> {
+    let mut summary = DumpSummary::default();
+    let mut place_ids = Vec::new();
+    let mut event_ids = Vec::new();
+    for (index, line) in lines {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let line_nr = index + 1;
+        let record = serde_json::from_str(&line).map_err(|err| invalid_record(line_nr, err))?;
+        let timestamp = |millis: json::UnixTimeMillis| {
+            Timestamp::try_from(millis).map_err(|err| invalid_record(line_nr, err))
+        };
+        let email = |email: String| {
+            email
+                .parse::<EmailAddress>()
+                .map_err(|err| invalid_record(line_nr, err))
+        };
+        match record {
+            Record::Header(_) => {
+                return Err(invalid_record(line_nr, "Unexpected header"));
+            }
+            Record::User(user) => {
+                let User {
+                    email: user_email,
+                    email_confirmed,
+                    role,
+                    password_hash,
+                } = user;
+                // An empty password hash never matches any password
+                let password = password_hash.unwrap_or_default().into();
+                conn.create_user(&ofdb_core::entities::User {
+                    email: email(user_email)?,
+                    email_confirmed,
+                    password,
+                    role: role.into(),
+                })?;
+                summary.users += 1;
+            }
+            Record::Tag(Tag { id }) => {
+                conn.create_tag_if_it_does_not_exist(&ofdb_core::entities::Tag { id })?;
+                summary.tags += 1;
+            }
+            Record::Organization(org) => {
+                let moderated_tags = org
+                    .moderated_tags
+                    .into_iter()
+                    .map(|tag| ofdb_core::entities::ModeratedTag {
+                        label: tag.label,
+                        allow_add: tag.allow_add,
+                        allow_remove: tag.allow_remove,
+                        require_clearance: tag.require_clearance,
+                    })
+                    .collect();
+                conn.create_org(ofdb_core::entities::Organization {
+                    id: org.id.into(),
+                    name: org.name,
+                    api_token: org
+                        .api_token
+                        .unwrap_or_else(ofdb_core::entities::ApiToken::generate_secret),
+                    moderated_tags,
+                })?;
+                summary.organizations += 1;
+            }
+            Record::OrganizationMember(member) => {
+                conn.add_or_update_org_member(&ofdb_core::entities::OrganizationMember {
+                    org_id: member.org_id.into(),
+                    email: email(member.email)?,
+                    role: member.role.into(),
+                })?;
+                summary.organization_members += 1;
+            }
+            Record::ApiToken(api_token) => {
+                let scopes = parse_api_token_scopes(&api_token.scopes.join(" "))
+                    .map_err(|err| invalid_record(line_nr, err))?;
+                let api_token = ofdb_core::entities::ApiToken {
+                    id: api_token.id.into(),
+                    org_id: api_token.org_id.into(),
+                    name: api_token.name,
+                    token_hash: api_token.token_hash,
+                    scopes,
+                    created_at: timestamp(api_token.created_at)?,
+                    expires_at: api_token.expires_at.map(timestamp).transpose()?,
+                    last_used_at: api_token.last_used_at.map(timestamp).transpose()?,
+                    revoked_at: api_token.revoked_at.map(timestamp).transpose()?,
+                };
+                conn.create_api_token(&api_token)?;
+                if let Some(last_used_at) = api_token.last_used_at {
+                    conn.update_api_token_last_used(&api_token.id, last_used_at)?;
+                }
+                if let Some(revoked_at) = api_token.revoked_at {
+                    conn.revoke_api_token(&api_token.org_id, &api_token.id, revoked_at)?;
+                }
+                summary.api_tokens += 1;
+            }
+            Record::Webhook(webhook) => {
+                conn.create_webhook(&ofdb_core::entities::Webhook {
+                    id: webhook.id.into(),
+                    org_id: webhook.org_id.into(),
+                    url: webhook
+                        .url
+                        .parse()
+                        .map_err(|err| invalid_record(line_nr, err))?,
+                    secret: webhook
+                        .secret
+                        .unwrap_or_else(ofdb_core::entities::Webhook::generate_secret),
+                    created_at: timestamp(webhook.created_at)?,
+                })?;
+                summary.webhooks += 1;
+            }
+            Record::Place(history) => {
+                let history =
+                    PlaceHistory::try_from(history).map_err(|err| invalid_record(line_nr, err))?;
+                place_ids.push(history.place.id.to_string());
+                restore_place_history(conn, history)?;
+                summary.places += 1;
+            }
+            Record::PlaceRedirect(redirect) => {
+                conn.create_place_redirect(
+                    &redirect.place_id.into(),
+                    &redirect.target_place_id.into(),
+                )?;
+                summary.place_redirects += 1;
+            }
+            Record::Rating(rating) => {
+                conn.create_rating(ofdb_core::entities::Rating {
+                    id: rating.id.into(),
+                    place_id: rating.place_id.into(),
+                    created_at: timestamp(rating.created_at)?,
+                    archived_at: rating.archived_at.map(timestamp).transpose()?,
+                    title: rating.title,
+                    value: rating.value.into(),
+                    context: rating.context.into(),
+                    source: rating.source,
+                })?;
+                summary.ratings += 1;
+            }
+            Record::Comment(comment) => {
+                conn.create_comment(ofdb_core::entities::Comment {
+                    id: comment.id.into(),
+                    rating_id: comment.rating_id.into(),
+                    created_at: timestamp(comment.created_at)?,
+                    archived_at: comment.archived_at.map(timestamp).transpose()?,
+                    text: comment.text,
+                })?;
+                summary.comments += 1;
+            }
+            Record::Event(EventHistory { history, archived }) => {
+                let archived = archived.map(timestamp).transpose()?;
+                let mut revisions = history
+                    .revisions
+                    .into_iter()
+                    .map(|rev| event_revision_from_json(&history.id, rev))
+                    .collect::<anyhow::Result<Vec<_>>>()
+                    .map_err(|err| invalid_record(line_nr, err))?;
+                revisions.sort_by_key(|(event, _)| event.revision);
+                restore_event_revisions(conn, revisions)?;
+                if let Some(archived) = archived {
+                    conn.archive_events(&[&history.id], archived)?;
+                }
+                event_ids.push(history.id);
+                summary.events += 1;
+            }
+            Record::PendingClearanceForPlace(pending) => {
+                conn.add_pending_clearance_for_places(
+                    &[pending.org_id.into()],
+                    &ofdb_core::entities::PendingClearanceForPlace {
+                        place_id: pending.place_id.into(),
+                        created_at: timestamp(pending.created_at)?,
+                        last_cleared_revision: pending.last_cleared_revision.map(Into::into),
+                    },
+                )?;
+                summary.pending_clearances += 1;
+            }
+            Record::PendingClearanceForEvent(pending) => {
+                conn.add_pending_clearance_for_events(
+                    &[pending.org_id.into()],
+                    &ofdb_core::entities::PendingClearanceForEvent {
+                        event_id: pending.event_id.into(),
+                        created_at: timestamp(pending.created_at)?,
+                        last_cleared_revision: pending.last_cleared_revision.map(Into::into),
+                    },
+                )?;
+                summary.pending_clearances += 1;
+            }
+            Record::ClearanceLogForPlace(log) => {
+                conn.add_clearance_log_for_places(
+                    &log.org_id.into(),
+                    &ofdb_core::entities::ClearanceLogForPlace {
+                        place_id: log.place_id.into(),
+                        cleared_revision: log.cleared_revision.into(),
+                        activity: ActivityLog::try_from(log.act)
+                            .map_err(|err| invalid_record(line_nr, err))?,
+                    },
+                )?;
+                summary.clearance_logs += 1;
+            }
+            Record::ClearanceLogForEvent(log) => {
+                conn.add_clearance_log_for_events(
+                    &log.org_id.into(),
+                    &ofdb_core::entities::ClearanceLogForEvent {
+                        event_id: log.event_id.into(),
+                        cleared_revision: log.cleared_revision.into(),
+                        activity: ActivityLog::try_from(log.act)
+                            .map_err(|err| invalid_record(line_nr, err))?,
+                    },
+                )?;
+                summary.clearance_logs += 1;
+            }
+            Record::BboxSubscription(subscription) => {
+                let json::MapBbox { sw, ne } = subscription.bbox;
+                let bbox = MapPoint::try_from_lat_lng_deg(sw.lat, sw.lng)
+                    .and_then(|sw| {
+                        MapPoint::try_from_lat_lng_deg(ne.lat, ne.lng)
+                            .map(|ne| MapBbox::new(sw, ne))
+                    })
+                    .map_err(|err| invalid_record(line_nr, err))?;
+                conn.create_bbox_subscription(&ofdb_core::entities::BboxSubscription {
+                    id: subscription.id.into(),
+                    user_email: email(subscription.user_email)?,
+                    bbox,
+                })?;
+                summary.bbox_subscriptions += 1;
+            }
+        }
+    }
+    Ok(Restored {
+        summary,
+        place_ids,
+        event_ids,
+    })
+}
+
+fn restore_place_history<R: PlaceRepo>(
+    repo: &R,
+    history: PlaceHistory,
+) -> std::result::Result<(), RepoError> {
+    let PlaceHistory {
+        place: root,
+        mut revisions,
+    } = history;
+    revisions.sort_by_key(|(place_revision, _)| place_revision.revision);
+    for (place_revision, mut reviews) in revisions {
+        let place = Place::from((root.clone(), place_revision));
+        repo.create_or_update_place(place.clone())?;
+        // The initial review is created together with the revision
+        reviews.sort_by_key(|review| review.revision);
+        for review in reviews.into_iter().filter(|r| !r.revision.is_initial()) {
+            repo.review_places(&[place.id.as_ref()], review.status, &review.activity)?;
+        }
+    }
+    Ok(())
+}
+
+fn restore_event_revisions<R: EventRepo>(
+    repo: &R,
+    revisions: Vec<(Event, ActivityLog)>,
+) -> std::result::Result<(), RepoError> {
+    for (event, activity) in revisions {
+        if event.revision.is_initial() {
+            repo.create_event(event, &activity)?;
+        } else {
+            repo.update_event(&event, &activity)?;
+        }
+    }
+    Ok(())
+}
+
+fn event_revision_from_json(
+    id: &str,
+    revision: json::EventRevision,
+) -> anyhow::Result<(Event, ActivityLog)> {
+    let json::EventRevision { rev, act, event } = revision;
+    let activity = ActivityLog::try_from(act)?;
+    let json::Event {
+        id: _,
+        title,
+        description,
+        start,
+        end,
+        lat,
+        lng,
+        street,
+        zip,
+        city,
+        country,
+        state,
+        email,
+        telephone,
+        homepage,
+        tags,
+        registration,
+        organizer,
+        image_url,
+        image_link_url,
+        rrule,
+        exdates,
+        translations,
+        lang: _,
+    } = event;
+    let address = Address {
+        street,
+        zip,
+        city,
+        country,
+        state,
+    };
+    let address = (!address.is_empty()).then_some(address);
+    let pos = match (lat, lng) {
+        (Some(lat), Some(lng)) => Some(MapPoint::try_from_lat_lng_deg(lat, lng)?),
+        _ => None,
+    };
+    let location = (pos.is_some() || address.is_some()).then(|| Location {
+        pos: pos.unwrap_or_default(),
+        address,
+    });
+    let contact = (email.is_some() || telephone.is_some()).then(|| Contact {
+        name: organizer,
+        email: email.map(EmailAddress::new_unchecked),
+        phone: telephone,
+    });
+    let parse_url = |url: Option<String>| url.map(|url| url.parse::<Url>()).transpose();
+    let recurrence = rrule
+        .map(|rule| {
+            anyhow::Ok(Recurrence {
+                rule: rule.parse()?,
+                exdates: exdates
+                    .into_iter()
+                    .map(Timestamp::try_from)
+                    .collect::<std::result::Result<_, _>>()?,
+            })
+        })
+        .transpose()?;
+    let event = Event {
+        id: id.into(),
+        revision: rev.into(),
+        title,
+        description,
+        start: start.try_into()?,
+        end: end.map(Timestamp::try_from).transpose()?,
+        location,
+        contact,
+        tags,
+        homepage: parse_url(homepage)?,
+        created_by: activity.activity.by.clone(),
+        registration: registration
+            .map(|r| r.parse())
+            .transpose()
+            .map_err(|_| anyhow!("Invalid registration type"))?,
+        archived: None,
+        image_url: parse_url(image_url)?,
+        image_link_url: parse_url(image_link_url)?,
+        recurrence,
+        translations: translations
+            .into_iter()
+            .map(Translation::try_from)
+            .collect::<std::result::Result<_, _>>()?,
+    };
+    Ok((event, activity))
+}
@@ -0,0 +1,392 @@
+use std::{cmp::Ordering, collections::HashMap};
+
+use anyhow::{Result as Fallible, bail};
+use ofdb_core::rating::Rated as _;
+
+use super::*;
+
+/// Serves search queries directly from the database.
+///
+/// Intended for answering queries while the search index is rebuilt
+/// from scratch. All places or events, or only those within the
+/// bounding box of the query, are loaded and filtered for each query.
+/// Texts only match literally, results are ordered by rating instead
+/// of relevance, and opening hours are not considered.
+#[derive(Clone)]
+pub struct FallbackIndex {
+    connections: sqlite::Connections,
+}
+
+impl FallbackIndex {
+    pub const fn new(connections: sqlite::Connections) -> Self {
+        Self { connections }
+    }
+
+    fn query_places_with_ratings(
+        &self,
+        query: &IndexQuery,
+        limit: usize,
+    ) -> Fallible<Vec<(Place, ReviewStatus, AvgRatings)>> {
+        let filter = QueryFilter::new(query);
+        if !filter.places {
+            return Ok(vec![]);
+        }
+        let db = self.connections.shared()?;
+        let places = match query.include_bbox {
+            Some(bbox) => db.all_places_in_bbox(&bbox)?,
+            None => db.all_places()?,
+        };
+        let mut places = places
+            .into_iter()
+            .filter(|(place, status)| filter.matches_place(place, *status))
+            .map(|(place, status)| {
+                let ratings = db.load_ratings_of_place(place.id.as_ref())?;
+                let avg_ratings = place.avg_ratings(&ratings);
+                Ok((place, status, avg_ratings))
+            })
+            .collect::<Fallible<Vec<_>>>()?;
+        match query.center.filter(|_| query.order_by_distance) {
+            Some(center) => places.sort_by(|(lhs, _, _), (rhs, _, _)| {
+                cmp_distance(center, lhs.location.pos, rhs.location.pos)
+            }),
+            None => places.sort_by(|(_, _, lhs), (_, _, rhs)| {
+                f64::from(rhs.total())
+                    .partial_cmp(&f64::from(lhs.total()))
+                    .unwrap_or(Ordering::Equal)
+            }),
+        }
+        places.truncate(limit);
+        Ok(places)
+    }
+
+    fn query_events(&self, query: &IndexQuery, limit: usize) -> Fallible<Vec<Event>> {
+        let filter = QueryFilter::new(query);
+        if !filter.events {
+            return Ok(vec![]);
+        }
+        let db = self.connections.shared()?;
+        let events = match query.include_bbox {
+            Some(bbox) => db.all_events_in_bbox_chronologically(&bbox)?,
+            None => db.all_events_chronologically()?,
+        };
+        let mut events: Vec<_> = events
+            .into_iter()
+            .filter(|event| filter.matches_event(event))
+            .collect();
+        if let Some(center) = query.center.filter(|_| query.order_by_distance) {
+            events.sort_by(|lhs, rhs| {
+                let lhs = lhs.location.as_ref().map(|l| l.pos);
+                let rhs = rhs.location.as_ref().map(|l| l.pos);
+                match (lhs, rhs) {
+                    (Some(lhs), Some(rhs)) => cmp_distance(center, lhs, rhs),
+                    (lhs, rhs) => rhs.is_none().cmp(&lhs.is_none()),
+                }
+            });
+        }
+        events.truncate(limit);
+        Ok(events)
+    }
+}
+
+fn cmp_distance(center: MapPoint, lhs: MapPoint, rhs: MapPoint) -> Ordering {
+    let lhs = MapPoint::distance(center, lhs).map(Distance::to_meters);
+    let rhs = MapPoint::distance(center, rhs).map(Distance::to_meters);
+    lhs.partial_cmp(&rhs).unwrap_or(Ordering::Equal)
+}
+
+// Mimics the semantics of the queries in the search index
+struct QueryFilter<'q, 'a, 'b> {
+    query: &'q IndexQuery<'a, 'b>,
+    places: bool,
+    events: bool,
+    // All of them must match
+    tags: Vec<String>,
+    // One of them must match if not empty
+    place_category_tags: Vec<String>,
+    // One of them must match if not empty
+    text_terms: Vec<String>,
+    // One of them or a text term must match if not empty
+    text_tags: Vec<String>,
+}
+
+impl<'q, 'a, 'b> QueryFilter<'q, 'a, 'b> {
+    fn new(query: &'q IndexQuery<'a, 'b>) -> Self {
+        let merged_tags = Category::merge_ids_into_tags(
+            &query
+                .categories
+                .iter()
+                .map(|c| Id::from(*c))
+                .collect::<Vec<_>>(),
+            query.hash_tags.clone(),
+        );
+        let (tags, categories) = Category::split_from_tags(merged_tags);
+        let (event_categories, place_categories): (Vec<_>, Vec<_>) = categories
+            .into_iter()
+            .partition(|category| category.id.as_str() == Category::ID_EVENT);
+        let any_category = !event_categories.is_empty() || !place_categories.is_empty();
+        Self {
+            query,
+            places: !any_category || !place_categories.is_empty(),
+            // Events don't have a review status
+            events: (!any_category || !event_categories.is_empty()) && query.status.is_none(),
+            tags: tags.iter().map(|tag| tag.to_lowercase()).collect(),
+            place_category_tags: place_categories.into_iter().map(|c| c.tag).collect(),
+            text_terms: query
+                .text
+                .iter()
+                .flat_map(|text| text.split_whitespace())
+                .map(str::to_lowercase)
+                .collect(),
+            text_tags: query
+                .text_tags
+                .iter()
+                .map(|tag| tag.to_lowercase())
+                .collect(),
+        }
+    }
+
+    fn matches_place(&self, place: &Place, status: ReviewStatus) -> bool {
+        let status_matches = match &self.query.status {
+            None => true,
+            Some(statuses) if statuses.is_empty() => status.exists(),
+            Some(statuses) => statuses.contains(&status),
+        };
+        status_matches
+            && self.matches_id(place.id.as_str())
+            && self.matches_pos(Some(place.location.pos))
+            && (self.place_category_tags.is_empty()
+                || self
+                    .place_category_tags
+                    .iter()
+                    .any(|tag| place.tags.contains(tag)))
+            && self.matches_tags_and_text(
+                &place.tags,
+                &[&place.title, &place.description]
+                    .into_iter()
+                    .chain(place.translations.iter().map(|t| &t.title))
+                    .chain(
+                        place
+                            .translations
+                            .iter()
+                            .filter_map(|t| t.description.as_ref()),
+                    )
+                    .map(|text| text.to_lowercase())
+                    .collect::<Vec<_>>(),
+            )
+    }
+
+    fn matches_event(&self, event: &Event) -> bool {
+        self.matches_id(event.id.as_str())
+            && self.matches_pos(event.location.as_ref().map(|l| l.pos))
+            && self.matches_time(event)
+            && self.matches_tags_and_text(
+                &event.tags,
+                &[Some(&event.title), event.description.as_ref()]
+                    .into_iter()
+                    .flatten()
+                    .chain(event.translations.iter().map(|t| &t.title))
+                    .chain(
+                        event
+                            .translations
+                            .iter()
+                            .filter_map(|t| t.description.as_ref()),
+                    )
+                    .map(|text| text.to_lowercase())
+                    .collect::<Vec<_>>(),
+            )
+    }
+
+    fn matches_id(&self, id: &str) -> bool {
+        self.query.ids.is_empty() || self.query.ids.contains(&id)
+    }
+
+    fn matches_pos(&self, pos: Option<MapPoint>) -> bool {
+        let IndexQuery {
+            include_bbox,
+            exclude_bbox,
+            center,
+            max_distance,
+            ..
+        } = self.query;
+        if include_bbox.is_none() && exclude_bbox.is_none() && max_distance.is_none() {
+            return true;
+        }
+        let Some(pos) = pos else {
+            return false;
+        };
+        include_bbox.is_none_or(|bbox| bbox.contains_point(pos))
+            && exclude_bbox.is_none_or(|bbox| !bbox.contains_point(pos))
+            && center
+                .zip(*max_distance)
+                .is_none_or(|(center, max_distance)| {
+                    MapPoint::distance(center, pos).is_some_and(|distance| distance <= max_distance)
+                })
+    }
+
+    fn matches_time(&self, event: &Event) -> bool {
+        let IndexQuery {
+            ts_min_lb,
+            ts_min_ub,
+            ts_max_lb,
+            ts_max_ub,
+            ..
+        } = self.query;
+        let duration = event.end.map(|end| end.as_secs() - event.start.as_secs());
+        if duration.is_none() && (ts_max_lb.is_some() || ts_max_ub.is_some()) {
+            return false;
+        }
+        // Occurrences that start after the upper bounds never match
+        let start_ub = [
+            ts_min_ub.map(|ts| ts.as_secs()),
+            ts_max_ub.map(|ts| ts.as_secs()),
+        ]
+        .into_iter()
+        .flatten()
+        .min();
+        event
+            .occurrence_starts()
+            .map(|start| start.as_secs())
+            .take_while(|start| start_ub.is_none_or(|ub| *start <= ub))
+            .any(|start| {
+                let end = start + duration.unwrap_or_default();
+                ts_min_lb.is_none_or(|lb| start >= lb.as_secs())
+                    && ts_min_ub.is_none_or(|ub| start <= ub.as_secs())
+                    && ts_max_lb.is_none_or(|lb| end >= lb.as_secs())
+                    && ts_max_ub.is_none_or(|ub| end <= ub.as_secs())
+            })
+    }
+
+    // Expects all texts in lowercase
+    fn matches_tags_and_text(&self, tags: &[String], texts: &[String]) -> bool {
+        if !self.tags.iter().all(|tag| tags.contains(tag)) {
+            return false;
+        }
+        if self.text_terms.is_empty() && self.text_tags.is_empty() {
+            return true;
+        }
+        self.text_tags.iter().any(|tag| tags.contains(tag))
+            || self
+                .text_terms
+                .iter()
+                .any(|term| texts.iter().any(|text| text.contains(term)) || tags.contains(term))
+    }
+}
+
+impl IdIndex for FallbackIndex {
+    fn query_ids(
+        &self,
+        _mode: IndexQueryMode,
+        query: &IndexQuery,
+        limit: usize,
+    ) -> Fallible<Vec<Id>> {
+        let mut ids: Vec<_> = self
+            .query_places_with_ratings(query, limit)?
+            .into_iter()
+            .map(|(place, _, _)| place.id)
+            .collect();
+        if ids.len() < limit {
+            let events = self.query_events(query, limit - ids.len())?;
+            ids.extend(events.into_iter().map(|event| event.id));
+        }
+        Ok(ids)
+    }
+
+    fn all_indexed_entries(&self) -> Fallible<Vec<IndexedEntry>> {
+        let db = self.connections.shared()?;
+        let places = db
+            .all_places()?
+            .into_iter()
+            .map(|(place, status)| IndexedEntry {
+                kind: IndexedKind::Place,
+                id: place.id,
+                status: Some(status),
+                revision: Some(place.revision),
+            });
+        let events = db
+            .all_events_chronologically()?
+            .into_iter()
+            .map(|event| IndexedEntry {
+                kind: IndexedKind::Event,
+                id: event.id,
+                status: None,
+                revision: Some(event.revision),
+            });
+        Ok(places.chain(events).collect())
+    }
+}
+
+impl PlaceIndex for FallbackIndex {
+    fn query_places(&self, query: &IndexQuery, limit: usize) -> Fallible<Vec<IndexedPlace>> {
+        Ok(self
+            .query_places_with_ratings(query, limit)?
+            .into_iter()
+            .map(|(place, status, ratings)| IndexedPlace {
+                id: place.id.into(),
+                status: Some(status),
+                pos: place.location.pos,
+                title: place.title,
+                description: place.description,
+                tags: place.tags,
+                ratings,
+                translations: place.translations,
+            })
+            .collect())
+    }
+
+    fn count_place_tags(&self, query: &IndexQuery) -> Fallible<Vec<TagFrequency>> {
+        let places = self.query_places_with_ratings(query, usize::MAX)?;
+        let mut tag_counts = HashMap::<String, TagCount>::new();
+        for tag in places.into_iter().flat_map(|(place, _, _)| place.tags) {
+            *tag_counts.entry(tag).or_default() += 1;
+        }
+        let mut tag_frequencies: Vec<_> = tag_counts
+            .into_iter()
+            .map(|(tag, count)| TagFrequency(tag, count))
+            .collect();
+        tag_frequencies
+            .sort_unstable_by(|lhs, rhs| rhs.1.cmp(&lhs.1).then_with(|| lhs.0.cmp(&rhs.0)));
+        Ok(tag_frequencies)
+    }
+
+    fn cluster_places(
+        &self,
+        query: &IndexQuery,
+        cell_size_deg: f64,
+    ) -> Fallible<Vec<PlaceCluster>> {
+        if !cell_size_deg.is_finite() || cell_size_deg <= 0.0 {
+            bail!("Invalid cell size: {}", cell_size_deg);
+        }
+        let places = self.query_places_with_ratings(query, usize::MAX)?;
+        // Sums of latitudes, longitudes, and counts per grid cell
+        let mut cells = HashMap::<(i64, i64), (f64, f64, u64)>::new();
+        for (place, _, _) in places {
+            let lat = place.location.pos.lat().to_deg();
+            let lng = place.location.pos.lng().to_deg();
+            let index = (
+                (lat / cell_size_deg).floor() as i64,
+                (lng / cell_size_deg).floor() as i64,
+            );
+            let cell = cells.entry(index).or_default();
+            cell.0 += lat;
+            cell.1 += lng;
+            cell.2 += 1;
+        }
+        let mut clusters: Vec<_> = cells
+            .into_values()
+            .filter_map(|(lat_sum, lng_sum, count)| {
+                let centroid =
+                    MapPoint::try_from_lat_lng_deg(lat_sum / count as f64, lng_sum / count as f64)
+                        .ok()?;
+                Some(PlaceCluster { centroid, count })
+            })
+            .collect();
+        clusters.sort_unstable_by(|lhs, rhs| {
+            rhs.count.cmp(&lhs.count).then_with(|| {
+                (lhs.centroid.lat().to_deg(), lhs.centroid.lng().to_deg())
+                    .partial_cmp(&(rhs.centroid.lat().to_deg(), rhs.centroid.lng().to_deg()))
+                    .unwrap_or(Ordering::Equal)
+            })
+        });
+        Ok(clusters)
+    }
+}
@@ -5,25 +5,36 @@ mod archive_comments;
 mod archive_events;
 mod archive_ratings;
 mod change_user_role;
+mod check_index;
 mod create_event;
 mod create_place;
 mod create_rating;
+mod detect_duplicates;
+mod dump;
+mod fallback_index;
+mod merge_places;
+mod rebuild_index;
 mod reset_password;
+mod restore_event;
 mod review_place_with_token;
 mod review_places;
 mod send_update_reminders;
 mod update_event;
 mod update_place;
+mod upload_image;
+mod webhooks;
 
 pub mod prelude {
     pub use super::{
         archive_comments::*, archive_events::*, archive_ratings::*, change_user_role::*,
-        create_event::*, create_place::*, create_rating::*, reset_password::*,
-        review_place_with_token::*, review_places::*, send_update_reminders::*, update_event::*,
-        update_place::*,
+        check_index::*, create_event::*, create_place::*, create_rating::*, detect_duplicates::*,
+        dump::*, fallback_index::*, merge_places::*, rebuild_index::*, reset_password::*,
+        restore_event::*, review_place_with_token::*, review_places::*, send_update_reminders::*,
+        update_event::*, update_place::*, upload_image::*, webhooks::*,
     };
 }
 
+pub mod db;
 pub mod error;
 
 pub type Result<T> = std::result::Result<T, error::AppError>;
@@ -33,6 +44,7 @@ pub(crate) use ofdb_core::{db::*, entities::*, repositories::*, usecases};
 #[cfg(test)]
 pub(crate) mod tests;
 
+// TODO: Rename, the connections are no longer restricted to SQLite
 pub(crate) mod sqlite {
-    pub use ofdb_db_sqlite::Connections;
+    pub use crate::db::Connections;
 }
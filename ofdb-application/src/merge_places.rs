@@ -0,0 +1,91 @@
+use super::*;
+
+pub fn merge_places(
+    connections: &sqlite::Connections,
+    indexer: &mut dyn PlaceIndexer,
+    merge: usecases::MergePlaces,
+) -> Result<usize> {
+    let place_id = merge.place_id.clone();
+    let merged_place_id = merge.merged_place_id.clone();
+    let rating_count = connections.exclusive()?.transaction(|conn| {
+        usecases::merge_places(conn, merge).map_err(|err| {
+            warn!(
+                "Failed to merge place {} into {}: {}",
+                merged_place_id, place_id, err
+            );
+            err
+        })
+    })?;
+    // Both the archived duplicate and the surviving place
+    // with its additional ratings need to be re-indexed
+    review_places::post_review_places(
+        connections,
+        indexer,
+        &[place_id.as_str(), merged_place_id.as_str()],
+    )?;
+    Ok(rating_count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::prelude::*;
+
+    #[test]
+    fn merge_duplicate_place() {
+        let fixture = BackendFixture::new();
+        fixture.create_user(
+            usecases::NewUser {
+                email: "scout@example.com".parse().unwrap(),
+                password: "secret123".into(),
+            },
+            None,
+        );
+        let place_id = fixture.create_place(0.into(), None);
+        let merged_place_id = fixture.create_place(1.into(), None);
+        let (rating_id, comment_id) = fixture.create_rating(new_entry_rating(
+            0,
+            &merged_place_id,
+            RatingContext::Fairness,
+            RatingValue::new(2),
+        ));
+
+        let rating_count = super::merge_places(
+            &fixture.db_connections,
+            &mut *fixture.search_engine.borrow_mut(),
+            usecases::MergePlaces {
+                place_id: place_id.as_str().into(),
+                merged_place_id: merged_place_id.as_str().into(),
+                merged_by: "scout@example.com".parse().unwrap(),
+                comment: Some("Duplicate".into()),
+            },
+        )
+        .unwrap();
+        assert_eq!(1, rating_count);
+
+        assert!(fixture.comment_exists(&comment_id));
+        assert!(!fixture.place_exists(&merged_place_id));
+        assert!(fixture.place_exists(&place_id));
+
+        let db = fixture.db_connections.shared().unwrap();
+        let ratings = db.load_ratings_of_place(&place_id).unwrap();
+        assert_eq!(rating_id, ratings[0].id.as_str());
+        assert_eq!(
+            Some(place_id.as_str().into()),
+            db.resolve_place_redirect(&merged_place_id.as_str().into())
+                .unwrap()
+        );
+
+        // The merge is logged in the history of the duplicate
+        let history = db.get_place_history(&merged_place_id, None).unwrap();
+        let (_, reviews) = history.revisions.first().unwrap();
+        let review = reviews
+            .iter()
+            .find(|review| review.status == ReviewStatus::Archived)
+            .unwrap();
+        assert_eq!(
+            Some(format!("Merged into {place_id}")),
+            review.activity.context
+        );
+        assert_eq!(Some("Duplicate"), review.activity.comment.as_deref());
+    }
+}
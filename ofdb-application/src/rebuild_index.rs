@@ -0,0 +1,31 @@
+use super::*;
+
+/// Index all places and events from scratch.
+///
+/// Database connections are only acquired for short periods,
+/// i.e. the index could be rebuilt in the background. Concurrent
+/// modifications might result in outdated documents that could
+/// be repaired with [`check_index()`](crate::prelude::check_index).
+pub fn rebuild_index(
+    connections: &sqlite::Connections,
+    indexer: &mut dyn EventAndPlaceIndexer,
+) -> Result<(usize, usize)> {
+    let places = connections.shared()?.all_places()?;
+    for (place, status) in &places {
+        let ratings = connections
+            .shared()?
+            .load_ratings_of_place(place.id.as_ref())?;
+        usecases::reindex_place(indexer, place, *status, &ratings)?;
+    }
+    let events = connections.shared()?.all_events_chronologically()?;
+    for event in &events {
+        usecases::index_event(indexer, event)?;
+    }
+    indexer.flush_index()?;
+    info!(
+        "Indexed {} places and {} events",
+        places.len(),
+        events.len()
+    );
+    Ok((places.len(), events.len()))
+}
@@ -0,0 +1,25 @@
+use super::*;
+
+pub fn restore_event(
+    connections: &sqlite::Connections,
+    indexer: &mut dyn EventIndexer,
+    org: &Organization,
+    id: &str,
+    revision: Revision,
+    restored_by: Option<EmailAddress>,
+) -> Result<Event> {
+    let event = connections.exclusive()?.transaction(|conn| {
+        usecases::restore_event_revision(conn, org, id, revision, restored_by).map_err(|err| {
+            warn!("Failed to restore revision {revision:?} of event {id}: {err}");
+            err
+        })
+    })?;
+
+    // Re-index restored event
+    // TODO: Move to a separate task/thread that doesn't delay this request
+    if let Err(err) = usecases::index_event(indexer, &event).and_then(|_| indexer.flush_index()) {
+        error!("Failed to re-index restored event {}: {}", event.id, err);
+    }
+
+    Ok(event)
+}
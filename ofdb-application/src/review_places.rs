@@ -13,7 +13,7 @@ fn exec_review_places(
     })?)
 }
 
-fn post_review_places(
+pub(crate) fn post_review_places(
     connections: &sqlite::Connections,
     indexer: &mut dyn PlaceIndexer,
     ids: &[&str],
@@ -0,0 +1,226 @@
+use std::fs;
+
+use super::prelude::*;
+use crate::Result;
+use usecases::{IndexMismatch, IndexProblem};
+
+#[test]
+fn should_detect_and_repair_index_mismatches() -> Result<()> {
+    let mut fixture = BackendFixture::new();
+    let missing_id = fixture.create_place(NewPlace::from(1), None);
+    let outdated_id = fixture.create_place(NewPlace::from(2), None);
+    let consistent_id = fixture.create_place(NewPlace::from(3), None);
+    let event = flows::create_event(
+        &fixture.db_connections,
+        fixture.search_engine.get_mut(),
+        &fixture.notify,
+        None,
+        usecases::NewEvent::new("event".into(), Timestamp::now()),
+    )?;
+
+    assert!(
+        flows::check_index(
+            &fixture.db_connections,
+            fixture.search_engine.get_mut(),
+            false
+        )?
+        .is_empty()
+    );
+
+    // Modify the database and the index independently
+    fixture.db_connections.exclusive()?.review_places(
+        &[&outdated_id],
+        ReviewStatus::Confirmed,
+        &ActivityLog {
+            activity: Activity::now(None),
+            context: None,
+            comment: None,
+        },
+    )?;
+    let (mut orphaned_place, status) = fixture.try_get_place(&consistent_id).unwrap();
+    orphaned_place.id = Id::new();
+    {
+        let search_engine = fixture.search_engine.get_mut();
+        search_engine.remove_by_id(&missing_id.as_str().into())?;
+        search_engine.add_or_update_place(&orphaned_place, status, &Default::default())?;
+        search_engine.flush_index()?;
+    }
+
+    let expected_mismatches = vec![
+        IndexMismatch {
+            kind: IndexedKind::Place,
+            id: missing_id.as_str().into(),
+            problem: IndexProblem::Missing,
+        },
+        IndexMismatch {
+            kind: IndexedKind::Place,
+            id: orphaned_place.id.clone(),
+            problem: IndexProblem::Orphaned,
+        },
+        IndexMismatch {
+            kind: IndexedKind::Place,
+            id: outdated_id.as_str().into(),
+            problem: IndexProblem::Outdated,
+        },
+    ];
+    assert_eq!(
+        expected_mismatches,
+        flows::check_index(
+            &fixture.db_connections,
+            fixture.search_engine.get_mut(),
+            false
+        )?
+    );
+    assert_eq!(
+        expected_mismatches,
+        flows::check_index(
+            &fixture.db_connections,
+            fixture.search_engine.get_mut(),
+            true
+        )?
+    );
+    assert!(
+        flows::check_index(
+            &fixture.db_connections,
+            fixture.search_engine.get_mut(),
+            false
+        )?
+        .is_empty()
+    );
+
+    let confirmed_places = fixture.query_places(&IndexQuery {
+        status: Some(vec![ReviewStatus::Confirmed]),
+        ..Default::default()
+    });
+    assert_eq!(1, confirmed_places.len());
+    assert_eq!(outdated_id, confirmed_places[0].id);
+    assert!(
+        fixture
+            .search_engine
+            .get_mut()
+            .all_indexed_entries()?
+            .iter()
+            .any(|entry| entry.kind == IndexedKind::Event && entry.id == event.id)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn should_rebuild_index_from_scratch() -> Result<()> {
+    let fixture = BackendFixture::new();
+    let place_id = fixture.create_place(NewPlace::from(1), None);
+
+    let mut search_engine = tantivy::SearchEngine::init_in_ram()?;
+    assert_eq!(
+        (1, 0),
+        flows::rebuild_index(&fixture.db_connections, &mut search_engine)?
+    );
+    assert!(flows::check_index(&fixture.db_connections, &mut search_engine, false)?.is_empty());
+    assert_eq!(
+        vec![IndexedEntry {
+            kind: IndexedKind::Place,
+            id: place_id.as_str().into(),
+            status: Some(ReviewStatus::Created),
+            revision: Some(Revision::initial()),
+        }],
+        search_engine.all_indexed_entries()?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn should_serve_queries_from_outdated_index_until_rebuilt() -> Result<()> {
+    let fixture = BackendFixture::new();
+    fixture.create_place(NewPlace::from(1), None);
+    let index_dir = std::env::temp_dir().join(format!("ofdb-index-{}", Id::new()));
+    {
+        let mut search_engine = tantivy::SearchEngine::init_with_path(Some(&index_dir))?;
+        assert!(!search_engine.is_migrating());
+        flows::rebuild_index(&fixture.db_connections, &mut search_engine)?;
+    }
+
+    // Pretend that the index has been created by a previous version
+    let outdated_dir = index_dir.join("v0");
+    fs::rename(
+        index_dir.join(format!("v{}", tantivy::INDEX_VERSION)),
+        &outdated_dir,
+    )?;
+
+    let query = IndexQuery {
+        hash_tags: vec!["tag-1".into()],
+        ..Default::default()
+    };
+    let mut search_engine = tantivy::SearchEngine::init_with_path(Some(&index_dir))?;
+    assert!(search_engine.is_migrating());
+    assert!(search_engine.all_indexed_entries()?.is_empty());
+    assert_eq!(1, search_engine.query_places(&query, 10)?.len());
+
+    flows::rebuild_index(&fixture.db_connections, &mut search_engine)?;
+    search_engine.finish_migration()?;
+    assert!(!search_engine.is_migrating());
+    assert!(!outdated_dir.exists());
+    assert_eq!(1, search_engine.all_indexed_entries()?.len());
+    assert_eq!(1, search_engine.query_places(&query, 10)?.len());
+
+    drop(search_engine);
+    fs::remove_dir_all(index_dir)?;
+    Ok(())
+}
+
+#[test]
+fn should_serve_queries_from_database_until_incompatible_index_rebuilt() -> Result<()> {
+    let fixture = BackendFixture::new();
+    let place_id = fixture.create_place(NewPlace::from(1), None);
+    fixture.create_place(NewPlace::from(2), None);
+    let index_dir = std::env::temp_dir().join(format!("ofdb-index-{}", Id::new()));
+    // Not a valid index and therefore incompatible
+    fs::create_dir_all(index_dir.join("v0"))?;
+
+    let query = IndexQuery {
+        hash_tags: vec!["tag-1".into()],
+        ..Default::default()
+    };
+    let mut search_engine = tantivy::SearchEngine::init_with_path(Some(&index_dir))?;
+    assert!(search_engine.is_migrating());
+    assert!(search_engine.query_places(&query, 10)?.is_empty());
+
+    search_engine.set_migration_fallback(Box::new(flows::FallbackIndex::new(
+        fixture.db_connections.clone(),
+    )));
+    let places = search_engine.query_places(&query, 10)?;
+    assert_eq!(1, places.len());
+    assert_eq!(place_id, places[0].id);
+    assert_eq!(
+        vec![TagFrequency("tag-1".into(), 1)],
+        search_engine.count_place_tags(&query)?
+    );
+    let text_query = IndexQuery {
+        text: Some("title 2".into()),
+        ..Default::default()
+    };
+    assert_eq!(
+        2,
+        search_engine
+            .query_ids(IndexQueryMode::WithRating, &text_query, 10)?
+            .len()
+    );
+    let bbox_query = IndexQuery {
+        include_bbox: Some(MapBbox::new(
+            MapPoint::from_lat_lng_deg(0, -10),
+            MapPoint::from_lat_lng_deg(1.5, 10),
+        )),
+        ..Default::default()
+    };
+    assert_eq!(1, search_engine.query_places(&bbox_query, 10)?.len());
+
+    flows::rebuild_index(&fixture.db_connections, &mut search_engine)?;
+    search_engine.finish_migration()?;
+    assert!(!search_engine.is_migrating());
+    assert_eq!(1, search_engine.query_places(&query, 10)?.len());
+
+    drop(search_engine);
+    fs::remove_dir_all(index_dir)?;
+    Ok(())
+}
@@ -643,6 +643,7 @@ fn should_return_the_last_cleared_revision_when_loading_or_searching_cleared_pla
                 place_id: place_id.clone(),
                 cleared_revision: None,
             }],
+            Some(fixture.user_email.clone()),
         )?
     );
     assert_eq!(
@@ -655,6 +656,17 @@ fn should_return_the_last_cleared_revision_when_loading_or_searching_cleared_pla
             .count_pending_clearances_for_places(&org.id)
             .unwrap()
     );
+    // The clearance is recorded together with the user who cleared it
+    let clearance_log = usecases::clearance::place::list_clearance_log(
+        &fixture.backend.db_connections.shared()?,
+        &org,
+        &Default::default(),
+    )?;
+    assert_eq!(place_id, &clearance_log[0].place_id);
+    assert_eq!(
+        Some(&fixture.user_email),
+        clearance_log[0].activity.activity.by.as_ref()
+    );
     // Restore archived place by confirming it
     flows::review_places(
         &fixture.backend.db_connections,
@@ -882,6 +894,7 @@ fn should_fail_when_trying_to_clear_future_revisions_of_places() -> Result<()> {
                 place_id: place_id.clone(),
                 cleared_revision: Some(new_revision.next()),
             }],
+            None,
         )
         .is_err()
     );
@@ -930,6 +943,7 @@ fn should_do_nothing_when_clearing_places_without_pending_clearances() -> Result
                     cleared_revision: Some(fixture.confirmed_place.revision),
                 }
             ],
+            None,
         )?
     );
     assert_eq!(
@@ -945,3 +959,155 @@ fn should_do_nothing_when_clearing_places_without_pending_clearances() -> Result
 
     Ok(())
 }
+
+fn create_organization_with_event_clearance_tag(backend: &BackendFixture) -> Organization {
+    let org = Organization {
+        id: Id::new(),
+        name: "organization_with_event_clearance_tag".into(),
+        api_token: "organization_with_event_clearance_tag".into(),
+        moderated_tags: vec![ModeratedTag {
+            label: "event_clearance".into(),
+            allow_add: true,
+            allow_remove: true,
+            require_clearance: true,
+        }],
+    };
+    backend
+        .db_connections
+        .exclusive()
+        .unwrap()
+        .create_org(org.clone())
+        .unwrap();
+    org
+}
+
+fn new_event_with_tag(title: &str, tag: &str) -> usecases::NewEvent {
+    usecases::NewEvent {
+        tags: Some(vec![tag.to_owned()]),
+        created_by: Some("user@example.com".parse().unwrap()),
+        ..usecases::NewEvent::new(title.into(), Timestamp::now())
+    }
+}
+
+fn query_events_by_tag(backend: &BackendFixture, tag: &str) -> Result<Vec<Event>> {
+    let query = usecases::EventQuery {
+        tags: Some(vec![tag.to_owned()]),
+        ..Default::default()
+    };
+    Ok(usecases::query_events(
+        &backend.db_connections.shared()?,
+        &*backend.search_engine.borrow(),
+        query,
+    )?)
+}
+
+#[test]
+fn should_create_pending_clearance_when_creating_event_with_moderated_tags() -> Result<()> {
+    let mut backend = BackendFixture::new();
+    let org = create_organization_with_event_clearance_tag(&backend);
+    let tag = &org.moderated_tags.first().unwrap().label;
+
+    let created_event = flows::create_event(
+        &backend.db_connections,
+        backend.search_engine.get_mut(),
+        &backend.notify,
+        None,
+        new_event_with_tag("created_event", tag),
+    )?;
+
+    assert!(created_event.revision.is_initial());
+    let pending_clearances = usecases::clearance::event::list_pending_clearances(
+        &backend.db_connections.shared()?,
+        &org,
+        &Default::default(),
+    )?;
+    assert_eq!(1, pending_clearances.len());
+    let pending_clearance = pending_clearances.first().unwrap();
+    assert_eq!(created_event.id, pending_clearance.event_id);
+    // Not yet cleared (and invisible)
+    assert_eq!(None, pending_clearance.last_cleared_revision);
+
+    Ok(())
+}
+
+#[test]
+fn should_only_query_cleared_event_revisions() -> Result<()> {
+    let mut backend = BackendFixture::new();
+    let org = create_organization_with_event_clearance_tag(&backend);
+    let tag = &org.moderated_tags.first().unwrap().label;
+
+    let created_event = flows::create_event(
+        &backend.db_connections,
+        backend.search_engine.get_mut(),
+        &backend.notify,
+        None,
+        new_event_with_tag("created_event", tag),
+    )?;
+    // Newly created events are invisible until cleared
+    assert!(query_events_by_tag(&backend, tag)?.is_empty());
+
+    let clearances = [ClearanceForEvent {
+        event_id: created_event.id.clone(),
+        cleared_revision: None,
+    }];
+    assert_eq!(
+        1,
+        usecases::clearance::event::update_pending_clearances(
+            &backend.db_connections.exclusive()?,
+            &org,
+            &clearances,
+            None,
+        )?
+    );
+    assert_eq!(
+        0,
+        usecases::clearance::event::count_pending_clearances(
+            &backend.db_connections.shared()?,
+            &org,
+        )?
+    );
+    let events = query_events_by_tag(&backend, tag)?;
+    assert_eq!(1, events.len());
+    assert_eq!("created_event", events[0].title);
+
+    let updated_event = flows::update_event(
+        &backend.db_connections,
+        backend.search_engine.get_mut(),
+        &backend.notify,
+        None,
+        created_event.id.clone(),
+        new_event_with_tag("updated_event", tag),
+    )?;
+    assert_eq!(created_event.revision.next(), updated_event.revision);
+    let pending_clearances = usecases::clearance::event::list_pending_clearances(
+        &backend.db_connections.shared()?,
+        &org,
+        &Default::default(),
+    )?;
+    assert_eq!(1, pending_clearances.len());
+    assert_eq!(
+        Some(created_event.revision),
+        pending_clearances.first().unwrap().last_cleared_revision
+    );
+    // The last cleared revision is returned instead of the current revision
+    let events = query_events_by_tag(&backend, tag)?;
+    assert_eq!(1, events.len());
+    assert_eq!("created_event", events[0].title);
+    assert_eq!(created_event.revision, events[0].revision);
+
+    let clearances = [ClearanceForEvent {
+        event_id: updated_event.id.clone(),
+        cleared_revision: Some(updated_event.revision),
+    }];
+    usecases::clearance::event::update_pending_clearances(
+        &backend.db_connections.exclusive()?,
+        &org,
+        &clearances,
+        None,
+    )?;
+    let events = query_events_by_tag(&backend, tag)?;
+    assert_eq!(1, events.len());
+    assert_eq!("updated_event", events[0].title);
+
+    Ok(())
+}
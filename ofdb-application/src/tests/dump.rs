@@ -0,0 +1,420 @@
+use super::prelude::*;
+use crate::Result;
+
+#[test]
+fn should_restore_dumped_database_into_empty_database() -> Result<()> {
+    let mut source = BackendFixture::new();
+    let email: EmailAddress = "scout@example.com".parse().unwrap();
+    source.create_user(
+        usecases::NewUser {
+            email: email.clone(),
+            password: "secret123".into(),
+        },
+        Some(Role::Scout),
+    );
+
+    // Place with two revisions and multiple reviews
+    let place_id = source.create_place(NewPlace::from(1), Some(&email));
+    flows::review_places(
+        &source.db_connections,
+        source.search_engine.get_mut(),
+        &[&place_id],
+        usecases::Review {
+            context: None,
+            reviewer_email: email.clone(),
+            status: ReviewStatus::Confirmed,
+            comment: Some("confirmed".into()),
+        },
+    )?;
+    let (old_place, _) = source.try_get_place(&place_id).unwrap();
+    let mut update_place = usecases::UpdatePlace::from(old_place.clone());
+    update_place.version = old_place.revision.next().into();
+    update_place.title = "Updated title".into();
+    flows::update_place(
+        &source.db_connections,
+        source.search_engine.get_mut(),
+        &source.notify,
+        old_place.id,
+        update_place,
+        Some(&email),
+        None,
+        &accepted_licenses(),
+    )?;
+    let (rating_id, comment_id) = source.create_rating(usecases::NewPlaceRating {
+        entry: place_id.clone(),
+        title: "rating".into(),
+        value: RatingValue::from(2),
+        context: RatingContext::Fairness,
+        comment: "comment".into(),
+        source: Some("source".into()),
+        user: None,
+    });
+
+    let org = Organization {
+        id: Id::new(),
+        name: "org".into(),
+        api_token: "api_token".into(),
+        moderated_tags: vec![ModeratedTag {
+            label: "moderated".into(),
+            allow_add: true,
+            allow_remove: false,
+            require_clearance: false,
+        }],
+    };
+    source.db_connections.exclusive()?.create_org(org.clone())?;
+
+    // Event with two revisions
+    let created_event = flows::create_event(
+        &source.db_connections,
+        source.search_engine.get_mut(),
+        &source.notify,
+        None,
+        usecases::NewEvent {
+            tags: Some(vec!["event-tag".into()]),
+            created_by: Some(email.clone()),
+            city: Some("Stuttgart".into()),
+            ..usecases::NewEvent::new("event".into(), Timestamp::now())
+        },
+    )?;
+    flows::update_event(
+        &source.db_connections,
+        source.search_engine.get_mut(),
+        &source.notify,
+        None,
+        created_event.id.clone(),
+        usecases::NewEvent {
+            tags: Some(vec!["event-tag".into()]),
+            created_by: Some(email.clone()),
+            ..usecases::NewEvent::new("updated event".into(), created_event.start)
+        },
+    )?;
+
+    let mut archive = Vec::new();
+    let dumped = flows::dump(
+        &source.db_connections.shared()?,
+        &mut archive,
+        flows::DumpOptions {
+            include_password_hashes: true,
+            include_secrets: true,
+        },
+    )?;
+    assert_eq!(1, dumped.users);
+    assert_eq!(1, dumped.organizations);
+    assert_eq!(1, dumped.places);
+    assert_eq!(1, dumped.ratings);
+    assert_eq!(1, dumped.comments);
+    assert_eq!(1, dumped.events);
+
+    let mut target = BackendFixture::new();
+    let restored = flows::restore(
+        &target.db_connections,
+        target.search_engine.get_mut(),
+        archive.as_slice(),
+    )?;
+    assert_eq!(dumped, restored);
+
+    assert_eq!(
+        source.try_get_rating(&rating_id),
+        target.try_get_rating(&rating_id)
+    );
+    assert_eq!(
+        source.try_get_comment(&comment_id),
+        target.try_get_comment(&comment_id)
+    );
+    assert!(
+        target
+            .try_get_user(&email)
+            .unwrap()
+            .password
+            .verify("secret123")
+    );
+
+    // The search index has been rebuilt
+    let places = target.query_places_by_tag("tag-1");
+    assert_eq!(1, places.len());
+    assert_eq!("Updated title", places[0].title);
+    assert!(f64::from(places[0].ratings.total()) > 0.0);
+
+    {
+        let source_db = source.db_connections.shared()?;
+        let target_db = target.db_connections.shared()?;
+        assert_eq!(
+            source_db.get_place_history(&place_id, None)?,
+            target_db.get_place_history(&place_id, None)?
+        );
+        assert_eq!(
+            source_db.get_event_history(created_event.id.as_ref(), None)?,
+            target_db.get_event_history(created_event.id.as_ref(), None)?
+        );
+        assert_eq!(source_db.all_users()?, target_db.all_users()?);
+        assert_eq!(source_db.all_tags()?, target_db.all_tags()?);
+        assert_eq!(vec![org], target_db.all_orgs()?);
+        let events = usecases::query_events(
+            &target_db,
+            &*target.search_engine.borrow(),
+            usecases::EventQuery {
+                tags: Some(vec!["event-tag".into()]),
+                ..Default::default()
+            },
+        )?;
+        assert_eq!(1, events.len());
+        assert_eq!("updated event", events[0].title);
+    }
+
+    // Only empty databases could be restored
+    assert!(
+        flows::restore(
+            &target.db_connections,
+            target.search_engine.get_mut(),
+            archive.as_slice(),
+        )
+        .is_err()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn should_omit_password_hashes_by_default() -> Result<()> {
+    let source = BackendFixture::new();
+    let email: EmailAddress = "user@example.com".parse().unwrap();
+    source.create_user(
+        usecases::NewUser {
+            email: email.clone(),
+            password: "secret123".into(),
+        },
+        None,
+    );
+
+    let mut archive = Vec::new();
+    flows::dump(
+        &source.db_connections.shared()?,
+        &mut archive,
+        Default::default(),
+    )?;
+    assert!(!String::from_utf8(archive.clone())?.contains("password_hash"));
+
+    let mut target = BackendFixture::new();
+    flows::restore(
+        &target.db_connections,
+        target.search_engine.get_mut(),
+        archive.as_slice(),
+    )?;
+    let user = target.try_get_user(&email).unwrap();
+    assert!(!user.password.verify("secret123"));
+
+    Ok(())
+}
+
+#[test]
+fn should_restore_archived_and_organization_data() -> Result<()> {
+    let mut source = BackendFixture::new();
+    let email: EmailAddress = "scout@example.com".parse().unwrap();
+    source.create_user(
+        usecases::NewUser {
+            email: email.clone(),
+            password: "secret123".into(),
+        },
+        Some(Role::Scout),
+    );
+    let place_id = source.create_place(NewPlace::from(1), Some(&email));
+    let merged_place_id = source.create_place(NewPlace::from(2), Some(&email));
+    let (rating_id, _) = source.create_rating(usecases::NewPlaceRating {
+        entry: place_id.clone(),
+        title: "rating".into(),
+        value: RatingValue::from(2),
+        context: RatingContext::Fairness,
+        comment: "comment".into(),
+        source: None,
+        user: None,
+    });
+    let created_event = flows::create_event(
+        &source.db_connections,
+        source.search_engine.get_mut(),
+        &source.notify,
+        None,
+        usecases::NewEvent {
+            created_by: Some(email.clone()),
+            ..usecases::NewEvent::new("event".into(), Timestamp::now())
+        },
+    )?;
+    let org = Organization {
+        id: Id::new(),
+        name: "org".into(),
+        api_token: "org-secret".into(),
+        moderated_tags: vec![],
+    };
+    let webhook_secret;
+    {
+        let mut db = source.db_connections.exclusive()?;
+        let activity = Activity::now(Some(email.clone()));
+        db.archive_comments_of_ratings(&[&rating_id], &activity)?;
+        db.archive_ratings(&[&rating_id], &activity)?;
+        db.archive_events(&[created_event.id.as_ref()], Timestamp::now())?;
+        db.create_place_redirect(&merged_place_id.as_str().into(), &place_id.as_str().into())?;
+        db.create_org(org.clone())?;
+        db.add_or_update_org_member(&OrganizationMember {
+            org_id: org.id.clone(),
+            email: email.clone(),
+            role: OrganizationRole::Clearer,
+        })?;
+        let (api_token, _) = usecases::create_api_token(
+            &db,
+            &org.id,
+            usecases::NewApiToken {
+                name: "token".into(),
+                scopes: vec![ApiTokenScope::ClearanceRead],
+                expires_at: None,
+            },
+            Timestamp::now(),
+        )?;
+        db.update_api_token_last_used(&api_token.id, Timestamp::now())?;
+        db.revoke_api_token(&org.id, &api_token.id, Timestamp::now())?;
+        let webhook =
+            usecases::create_webhook(&db, &org.id, "https://example.com/hook", Timestamp::now())?;
+        webhook_secret = webhook.secret;
+        db.add_pending_clearance_for_places(
+            std::slice::from_ref(&org.id),
+            &PendingClearanceForPlace {
+                place_id: place_id.as_str().into(),
+                created_at: Timestamp::now(),
+                last_cleared_revision: None,
+            },
+        )?;
+        db.add_clearance_log_for_places(
+            &org.id,
+            &ClearanceLogForPlace {
+                place_id: merged_place_id.as_str().into(),
+                cleared_revision: Revision::initial(),
+                activity: ActivityLog {
+                    activity: Activity::now(Some(email.clone())),
+                    context: Some("context".into()),
+                    comment: None,
+                },
+            },
+        )?;
+        db.create_bbox_subscription(&BboxSubscription {
+            id: Id::new(),
+            user_email: email.clone(),
+            bbox: "1.0,2.0,3.0,4.0".parse().unwrap(),
+        })?;
+    }
+
+    let mut archive = Vec::new();
+    let dumped = flows::dump(
+        &source.db_connections.shared()?,
+        &mut archive,
+        Default::default(),
+    )?;
+    let archived = String::from_utf8(archive.clone())?;
+    assert!(!archived.contains(&org.api_token));
+    assert!(!archived.contains(&webhook_secret));
+    assert_eq!(1, dumped.ratings);
+    assert_eq!(1, dumped.comments);
+    assert_eq!(1, dumped.events);
+    assert_eq!(1, dumped.organization_members);
+    assert_eq!(1, dumped.api_tokens);
+    assert_eq!(1, dumped.webhooks);
+    assert_eq!(1, dumped.place_redirects);
+    assert_eq!(1, dumped.pending_clearances);
+    assert_eq!(1, dumped.clearance_logs);
+    assert_eq!(1, dumped.bbox_subscriptions);
+
+    let mut target = BackendFixture::new();
+    let restored = flows::restore(
+        &target.db_connections,
+        target.search_engine.get_mut(),
+        archive.as_slice(),
+    )?;
+    assert_eq!(dumped, restored);
+
+    let source_db = source.db_connections.shared()?;
+    let target_db = target.db_connections.shared()?;
+    let ratings = target_db.load_all_ratings_of_place(&place_id)?;
+    assert_eq!(source_db.load_all_ratings_of_place(&place_id)?, ratings);
+    assert!(ratings[0].archived_at.is_some());
+    assert!(target_db.load_ratings_of_place(&place_id)?.is_empty());
+    let comments = target_db.load_all_comments_of_rating(&rating_id)?;
+    assert_eq!(source_db.load_all_comments_of_rating(&rating_id)?, comments);
+    assert!(comments[0].archived_at.is_some());
+    assert_eq!(
+        source_db.all_archived_events_chronologically()?,
+        target_db.all_archived_events_chronologically()?
+    );
+    assert_eq!(0, target_db.count_events()?);
+    assert_eq!(
+        Some(place_id.as_str().into()),
+        target_db.resolve_place_redirect(&merged_place_id.as_str().into())?
+    );
+    assert_eq!(
+        source_db.list_org_members(&org.id)?,
+        target_db.list_org_members(&org.id)?
+    );
+    let api_tokens = target_db.list_api_tokens_of_org(&org.id)?;
+    assert_eq!(source_db.list_api_tokens_of_org(&org.id)?, api_tokens);
+    assert!(api_tokens[0].last_used_at.is_some());
+    assert!(api_tokens[0].revoked_at.is_some());
+    // Omitted secrets are replaced by new ones
+    assert_ne!(org.api_token, target_db.get_org_by_id(&org.id)?.api_token);
+    let source_webhooks = source_db.list_webhooks_of_org(&org.id)?;
+    let target_webhooks = target_db.list_webhooks_of_org(&org.id)?;
+    assert_eq!(1, target_webhooks.len());
+    assert_ne!(webhook_secret, target_webhooks[0].secret);
+    assert_eq!(
+        Webhook {
+            secret: target_webhooks[0].secret.clone(),
+            ..source_webhooks[0].clone()
+        },
+        target_webhooks[0]
+    );
+    assert_eq!(
+        source_db.list_pending_clearances_for_places(&org.id, &Default::default())?,
+        target_db.list_pending_clearances_for_places(&org.id, &Default::default())?
+    );
+    assert_eq!(
+        source_db.list_clearance_log_for_places(&org.id, &Default::default())?,
+        target_db.list_clearance_log_for_places(&org.id, &Default::default())?
+    );
+    assert_eq!(
+        source_db.all_bbox_subscriptions()?,
+        target_db.all_bbox_subscriptions()?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn should_restore_nothing_if_any_record_is_invalid() -> Result<()> {
+    let source = BackendFixture::new();
+    let email: EmailAddress = "user@example.com".parse().unwrap();
+    source.create_user(
+        usecases::NewUser {
+            email: email.clone(),
+            password: "secret123".into(),
+        },
+        None,
+    );
+    source.create_place(NewPlace::from(1), Some(&email));
+
+    let mut archive = Vec::new();
+    flows::dump(
+        &source.db_connections.shared()?,
+        &mut archive,
+        Default::default(),
+    )?;
+    archive.extend_from_slice(b"{\"type\":\"unknown\"}\n");
+
+    let mut target = BackendFixture::new();
+    assert!(
+        flows::restore(
+            &target.db_connections,
+            target.search_engine.get_mut(),
+            archive.as_slice(),
+        )
+        .is_err()
+    );
+    assert!(target.try_get_user(&email).is_none());
+    assert_eq!(0, target.db_connections.shared()?.count_places()?);
+
+    Ok(())
+}
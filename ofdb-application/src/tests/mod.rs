@@ -1,5 +1,9 @@
+mod check_index;
 mod clearance;
+mod dump;
 mod search;
+mod translations;
+mod upload_image;
 
 pub mod prelude {
 
@@ -33,6 +37,7 @@ pub mod prelude {
             image_url: None,
             image_link_url: None,
             custom_links: vec![],
+            translations: vec![],
         }
     }
 
@@ -48,6 +53,11 @@ pub mod prelude {
             ids: vec![],
             status: vec![],
             text: None,
+            open_at: None,
+            fuzzy: false,
+            center: None,
+            radius: None,
+            order_by_distance: false,
         }
     }
 
@@ -65,7 +75,7 @@ pub mod prelude {
     }
 
     pub mod tantivy {
-        pub use ofdb_db_tantivy::SearchEngine;
+        pub use ofdb_db_tantivy::{INDEX_VERSION, SearchEngine};
     }
 
     pub use crate::prelude as flows;
@@ -87,8 +97,10 @@ pub mod prelude {
     impl BackendFixture {
         pub fn new() -> Self {
             let _ = env_logger::builder().is_test(true).try_init();
-            let db_connections = sqlite::Connections::init(":memory:", 1).unwrap();
-            ofdb_db_sqlite::run_embedded_database_migrations(db_connections.exclusive().unwrap());
+            let db_connections = sqlite::Connections::from(
+                ofdb_db_sqlite::Connections::init(":memory:", 1).unwrap(),
+            );
+            crate::db::run_embedded_database_migrations(db_connections.exclusive().unwrap());
             let search_engine = tantivy::SearchEngine::init_in_ram().unwrap();
             Self {
                 db_connections,
@@ -270,6 +282,7 @@ pub mod prelude {
                 image_url: None,
                 image_link_url: None,
                 custom_links: custom_links.into_iter().map(Into::into).collect(),
+                translations: vec![],
             }
         }
     }
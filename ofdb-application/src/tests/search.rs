@@ -152,3 +152,385 @@ fn should_find_places_by_tags() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn should_find_places_that_are_open_at_a_given_time() -> Result<()> {
+    use time::{Date, Month, PrimitiveDateTime, Time};
+
+    let fixture = BackendFixture::new();
+
+    let create_place = |title: &str, opening_hours: Option<&str>| {
+        flows::create_place(
+            &fixture.db_connections,
+            &mut *fixture.search_engine.borrow_mut(),
+            &fixture.notify,
+            usecases::NewPlace {
+                title: title.into(),
+                description: title.into(),
+                opening_hours: opening_hours.map(Into::into),
+                ..default_new_place()
+            },
+            None,
+            None,
+            &accepted_licenses(),
+        )
+        .unwrap()
+    };
+
+    let place_without_hours = create_place("without", None);
+    let place_weekdays = create_place("weekdays", Some("Mo-Fr 09:00-18:00; PH off"));
+    let place_nights = create_place("nights", Some("Fr,Sa 20:00-02:00"));
+    let place_always = create_place("always", Some("24/7"));
+
+    let search_open_at = |date: Date, time: Time| -> Result<Vec<Id>> {
+        Ok(usecases::search(
+            &fixture.db_connections.shared()?,
+            &*fixture.search_engine.borrow(),
+            usecases::SearchRequest {
+                open_at: Some(PrimitiveDateTime::new(date, time)),
+                ..default_search_request()
+            },
+            100,
+        )?
+        .0
+        .into_iter()
+        .map(|p| p.id.into())
+        .collect())
+    };
+
+    // Friday
+    let friday = Date::from_calendar_date(2024, Month::May, 17).unwrap();
+    let ids = search_open_at(friday, Time::from_hms(10, 30, 0).unwrap())?;
+    assert_eq!(2, ids.len());
+    assert!(ids.contains(&place_weekdays.id));
+    assert!(ids.contains(&place_always.id));
+
+    let ids = search_open_at(friday, Time::from_hms(23, 0, 0).unwrap())?;
+    assert_eq!(2, ids.len());
+    assert!(ids.contains(&place_nights.id));
+    assert!(ids.contains(&place_always.id));
+
+    // Early Sunday morning after Saturday night
+    let sunday = Date::from_calendar_date(2024, Month::May, 19).unwrap();
+    let ids = search_open_at(sunday, Time::from_hms(1, 55, 0).unwrap())?;
+    assert_eq!(2, ids.len());
+    assert!(ids.contains(&place_nights.id));
+    assert!(!ids.contains(&place_without_hours.id));
+
+    // Invalid opening hours are rejected
+    assert!(
+        flows::create_place(
+            &fixture.db_connections,
+            &mut *fixture.search_engine.borrow_mut(),
+            &fixture.notify,
+            usecases::NewPlace {
+                title: "invalid".into(),
+                description: "invalid".into(),
+                opening_hours: Some("Mo-Fr 9-18 Uhr".into()),
+                ..default_new_place()
+            },
+            None,
+            None,
+            &accepted_licenses(),
+        )
+        .is_err()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn should_find_places_by_fuzzy_text() -> Result<()> {
+    let fixture = BackendFixture::new();
+
+    let create_place = |title: &str, description: &str| {
+        flows::create_place(
+            &fixture.db_connections,
+            &mut *fixture.search_engine.borrow_mut(),
+            &fixture.notify,
+            usecases::NewPlace {
+                title: title.into(),
+                description: description.into(),
+                ..default_new_place()
+            },
+            None,
+            None,
+            &accepted_licenses(),
+        )
+        .unwrap()
+    };
+
+    let bio_laden = create_place("Bio-Laden", "Obst und Gemüse");
+    let kaffee = create_place("Rösterei", "Fairer Kaffee");
+    let strasse = create_place("Weltladen", "In der Hauptstraße");
+
+    let search_text = |text: &str, fuzzy: bool| -> Result<Vec<Id>> {
+        Ok(usecases::search(
+            &fixture.db_connections.shared()?,
+            &*fixture.search_engine.borrow(),
+            usecases::SearchRequest {
+                text: Some(text),
+                fuzzy,
+                ..default_search_request()
+            },
+            100,
+        )?
+        .0
+        .into_iter()
+        .map(|p| p.id.into())
+        .collect())
+    };
+
+    assert!(search_text("Biolanden", false)?.is_empty());
+    assert_eq!(vec![bio_laden.id.clone()], search_text("Biolanden", true)?);
+
+    assert!(search_text("kaffe", false)?.is_empty());
+    assert_eq!(vec![kaffee.id.clone()], search_text("kaffe", true)?);
+    assert_eq!(vec![kaffee.id], search_text("Roesterei", true)?);
+
+    assert_eq!(vec![strasse.id.clone()], search_text("hauptstrasse", true)?);
+    assert_eq!(vec![bio_laden.id], search_text("gemuese", true)?);
+
+    // Short terms must match exactly
+    assert!(search_text("obs", true)?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn should_find_places_within_radius_ordered_by_distance() -> Result<()> {
+    let fixture = BackendFixture::new();
+
+    let create_place = |title: &str, lat: f64, lng: f64| {
+        flows::create_place(
+            &fixture.db_connections,
+            &mut *fixture.search_engine.borrow_mut(),
+            &fixture.notify,
+            usecases::NewPlace {
+                title: title.into(),
+                description: title.into(),
+                lat,
+                lng,
+                ..default_new_place()
+            },
+            None,
+            None,
+            &accepted_licenses(),
+        )
+        .unwrap()
+    };
+
+    // Distances from the center are approx. 1.1 km, 0.6 km, 1.9 km and 2.8 km
+    let center = MapPoint::from_lat_lng_deg(48.0, 9.0);
+    let place_1100m = create_place("cafe one", 48.01, 9.0);
+    let place_600m = create_place("cafe two", 48.0, 9.008);
+    let place_1900m = create_place("cafe three", 47.983, 9.0);
+    let place_2800m = create_place("cafe four", 48.0, 9.038);
+
+    let search = |radius: Option<f64>, order_by_distance: bool| -> Result<Vec<Id>> {
+        Ok(usecases::search(
+            &fixture.db_connections.shared()?,
+            &*fixture.search_engine.borrow(),
+            usecases::SearchRequest {
+                bbox: MapBbox::enclosing_circle(center, Distance::from_meters(5_000.0)),
+                text: Some("cafe"),
+                center: Some(center),
+                radius: radius.map(Distance::from_meters),
+                order_by_distance,
+                ..default_search_request()
+            },
+            100,
+        )?
+        .0
+        .into_iter()
+        .map(|p| p.id.into())
+        .collect())
+    };
+
+    let ids = search(Some(2_000.0), true)?;
+    assert_eq!(
+        vec![
+            place_600m.id.clone(),
+            place_1100m.id.clone(),
+            place_1900m.id.clone()
+        ],
+        ids
+    );
+
+    let ids = search(Some(1_000.0), false)?;
+    assert_eq!(vec![place_600m.id.clone()], ids);
+
+    let ids = search(None, true)?;
+    assert_eq!(
+        vec![
+            place_600m.id,
+            place_1100m.id,
+            place_1900m.id,
+            place_2800m.id
+        ],
+        ids
+    );
+
+    // The center is required
+    assert!(matches!(
+        usecases::search(
+            &fixture.db_connections.shared()?,
+            &*fixture.search_engine.borrow(),
+            usecases::SearchRequest {
+                radius: Some(Distance::from_meters(1_000.0)),
+                ..default_search_request()
+            },
+            100,
+        ),
+        Err(usecases::Error::InvalidPosition)
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn should_count_tags_and_categories_of_visible_places() -> Result<()> {
+    let fixture = BackendFixture::new();
+
+    let create_place = |category: &str, tags: &[&str], lat: f64| {
+        flows::create_place(
+            &fixture.db_connections,
+            &mut *fixture.search_engine.borrow_mut(),
+            &fixture.notify,
+            usecases::NewPlace {
+                title: "place".into(),
+                description: "place".into(),
+                categories: vec![category.into()],
+                tags: tags.iter().map(|t| (*t).to_string()).collect(),
+                lat,
+                ..default_new_place()
+            },
+            None,
+            None,
+            &accepted_licenses(),
+        )
+        .unwrap()
+    };
+
+    create_place(Category::ID_NON_PROFIT, &["foo", "bar"], 1.0);
+    create_place(Category::ID_NON_PROFIT, &["foo"], 2.0);
+    create_place(Category::ID_COMMERCIAL, &["bar", "baz"], 3.0);
+    // Outside of the bbox
+    create_place(Category::ID_COMMERCIAL, &["baz"], 10.0);
+
+    let facets = usecases::search_facets(
+        &*fixture.search_engine.borrow(),
+        usecases::SearchRequest {
+            bbox: MapBbox::new(
+                MapPoint::from_lat_lng_deg(0.0, -1.0),
+                MapPoint::from_lat_lng_deg(5.0, 1.0),
+            ),
+            ..default_search_request()
+        },
+        2,
+    )?;
+    assert_eq!(
+        vec![TagFrequency("bar".into(), 2), TagFrequency("foo".into(), 2)],
+        facets.tags
+    );
+    assert_eq!(
+        vec![
+            (Id::from(Category::ID_NON_PROFIT), 2),
+            (Id::from(Category::ID_COMMERCIAL), 1)
+        ],
+        facets.categories
+    );
+
+    // The place at 3.0 is inside the bbox but approx. 222 km away
+    let facets = usecases::search_facets(
+        &*fixture.search_engine.borrow(),
+        usecases::SearchRequest {
+            bbox: MapBbox::new(
+                MapPoint::from_lat_lng_deg(0.0, -1.0),
+                MapPoint::from_lat_lng_deg(5.0, 1.0),
+            ),
+            center: Some(MapPoint::from_lat_lng_deg(1.0, 0.0)),
+            radius: Some(Distance::from_meters(150_000.0)),
+            ..default_search_request()
+        },
+        2,
+    )?;
+    assert_eq!(
+        vec![TagFrequency("foo".into(), 2), TagFrequency("bar".into(), 1)],
+        facets.tags
+    );
+    assert_eq!(
+        vec![(Id::from(Category::ID_NON_PROFIT), 2)],
+        facets.categories
+    );
+
+    Ok(())
+}
+
+#[test]
+fn should_aggregate_visible_places_into_clusters() -> Result<()> {
+    let fixture = BackendFixture::new();
+
+    let create_place = |lat: f64, lng: f64| {
+        flows::create_place(
+            &fixture.db_connections,
+            &mut *fixture.search_engine.borrow_mut(),
+            &fixture.notify,
+            usecases::NewPlace {
+                title: "place".into(),
+                description: "place".into(),
+                lat,
+                lng,
+                ..default_new_place()
+            },
+            None,
+            None,
+            &accepted_licenses(),
+        )
+        .unwrap()
+    };
+
+    create_place(48.1, 9.1);
+    create_place(48.3, 9.3);
+    create_place(48.2, 9.2);
+    create_place(52.5, 13.4);
+    // Outside of the bbox
+    create_place(-33.9, 151.2);
+
+    let search = |zoom: u8| {
+        usecases::search_clusters(
+            &*fixture.search_engine.borrow(),
+            usecases::SearchRequest {
+                bbox: MapBbox::new(
+                    MapPoint::from_lat_lng_deg(40.0, 0.0),
+                    MapPoint::from_lat_lng_deg(60.0, 20.0),
+                ),
+                ..default_search_request()
+            },
+            zoom,
+        )
+    };
+
+    // 22.5° x 22.5° cells
+    let clusters = search(2)?;
+    assert_eq!(1, clusters.len());
+    assert_eq!(4, clusters[0].count);
+
+    // 5.625° x 5.625° cells
+    let clusters = search(4)?;
+    assert_eq!(2, clusters.len());
+    assert_eq!(3, clusters[0].count);
+    assert!((clusters[0].centroid.lat().to_deg() - 48.2).abs() < 1e-6);
+    assert!((clusters[0].centroid.lng().to_deg() - 9.2).abs() < 1e-6);
+    assert_eq!(1, clusters[1].count);
+    assert!((clusters[1].centroid.lat().to_deg() - 52.5).abs() < 1e-6);
+
+    // Each place in a separate cell
+    let clusters = search(10)?;
+    assert_eq!(4, clusters.len());
+    assert!(clusters.iter().all(|c| c.count == 1));
+
+    assert!(search(usecases::MAX_ZOOM + 1).is_err());
+
+    Ok(())
+}
@@ -0,0 +1,191 @@
+use super::prelude::*;
+use crate::Result;
+
+fn translation(lang: &str, title: &str, description: Option<&str>) -> usecases::TranslationParam {
+    usecases::TranslationParam {
+        lang: lang.into(),
+        title: title.into(),
+        description: description.map(Into::into),
+    }
+}
+
+fn lang(tag: &str) -> Language {
+    tag.parse().unwrap()
+}
+
+fn search_places(fixture: &BackendFixture, text: &str, fuzzy: bool) -> Result<Vec<IndexedPlace>> {
+    Ok(usecases::search(
+        &fixture.db_connections.shared()?,
+        &*fixture.search_engine.borrow(),
+        usecases::SearchRequest {
+            text: Some(text),
+            fuzzy,
+            ..default_search_request()
+        },
+        100,
+    )?
+    .0)
+}
+
+#[test]
+fn create_and_update_place_with_translations() -> Result<()> {
+    let fixture = BackendFixture::new();
+    let place = flows::create_place(
+        &fixture.db_connections,
+        &mut *fixture.search_engine.borrow_mut(),
+        &fixture.notify,
+        usecases::NewPlace {
+            title: "Weltladen".into(),
+            description: "Fairer Handel".into(),
+            translations: vec![
+                translation("en", "World shop", Some("Fair trade")),
+                translation("fr", "Boutique du monde", None),
+            ],
+            ..default_new_place()
+        },
+        None,
+        None,
+        &accepted_licenses(),
+    )?;
+
+    let (mut loaded, _) = fixture.try_get_place(place.id.as_str()).unwrap();
+    assert_eq!(2, loaded.translations.len());
+    assert_eq!(Some(lang("fr")), loaded.translate(&[lang("fr-CH")]));
+    assert_eq!("Boutique du monde", loaded.title);
+    // The original description is kept if it has not been translated
+    assert_eq!("Fairer Handel", loaded.description);
+
+    // The translated title is searchable and the translations
+    // are available in the search results
+    let results = search_places(&fixture, "Boutique", false)?;
+    assert_eq!(1, results.len());
+    let mut result = results.into_iter().next().unwrap();
+    assert_eq!(Some(lang("en")), result.translate(&[lang("en-US")]));
+    assert_eq!("World shop", result.title);
+    assert_eq!("Fair trade", result.description);
+    assert_eq!(1, search_places(&fixture, "Weltladen", false)?.len());
+
+    // French translations are indexed with French stemming
+    assert!(search_places(&fixture, "boutiques", false)?.is_empty());
+    assert_eq!(1, search_places(&fixture, "boutiques", true)?.len());
+
+    // Translations are replaced on update
+    let (loaded, _) = fixture.try_get_place(place.id.as_str()).unwrap();
+    let mut update = usecases::UpdatePlace::from(loaded.clone());
+    update.version = loaded.revision.next().into();
+    update.translations = vec![translation("en", "Fair shop", None)];
+    flows::update_place(
+        &fixture.db_connections,
+        &mut *fixture.search_engine.borrow_mut(),
+        &fixture.notify,
+        place.id.clone(),
+        update,
+        None,
+        None,
+        &accepted_licenses(),
+    )?;
+    let (loaded, _) = fixture.try_get_place(place.id.as_str()).unwrap();
+    assert_eq!(1, loaded.translations.len());
+    assert_eq!("Fair shop", loaded.translations[0].title);
+    assert!(search_places(&fixture, "Boutique", false)?.is_empty());
+
+    // The history contains the translations of each revision,
+    // most recent first
+    let history = fixture
+        .db_connections
+        .shared()?
+        .get_place_history(place.id.as_str(), None)?;
+    assert_eq!(
+        vec![1, 2],
+        history
+            .revisions
+            .iter()
+            .map(|(rev, _)| rev.translations.len())
+            .collect::<Vec<_>>()
+    );
+    Ok(())
+}
+
+#[test]
+fn reject_invalid_translations() {
+    let fixture = BackendFixture::new();
+    for translations in [
+        vec![translation("no language", "title", None)],
+        vec![translation("en", " ", None)],
+        vec![
+            translation("en-GB", "title", None),
+            translation("en-gb", "title", None),
+        ],
+    ] {
+        let result = flows::create_place(
+            &fixture.db_connections,
+            &mut *fixture.search_engine.borrow_mut(),
+            &fixture.notify,
+            usecases::NewPlace {
+                title: "title".into(),
+                description: "description".into(),
+                translations,
+                ..default_new_place()
+            },
+            None,
+            None,
+            &accepted_licenses(),
+        );
+        assert!(result.is_err());
+    }
+}
+
+#[test]
+fn create_event_with_translations() -> Result<()> {
+    let fixture = BackendFixture::new();
+    let org = Organization {
+        id: Id::new(),
+        name: "org".into(),
+        api_token: "org".into(),
+        moderated_tags: vec![ModeratedTag {
+            label: "org".into(),
+            allow_add: true,
+            allow_remove: false,
+            require_clearance: false,
+        }],
+    };
+    fixture
+        .db_connections
+        .exclusive()?
+        .create_org(org.clone())?;
+    let event = flows::create_event(
+        &fixture.db_connections,
+        &mut *fixture.search_engine.borrow_mut(),
+        &fixture.notify,
+        Some(&org.api_token),
+        usecases::NewEvent {
+            tags: Some(vec!["org".into()]),
+            created_by: Some("user@example.com".parse().unwrap()),
+            translations: vec![translation("en", "Repair café", Some("Bring it along"))],
+            ..usecases::NewEvent::new("Reparaturcafé".into(), Timestamp::now())
+        },
+    )?;
+
+    let mut loaded = fixture
+        .db_connections
+        .shared()?
+        .get_event(event.id.as_str())?;
+    assert_eq!(
+        Some(lang("en")),
+        loaded.translate(&[lang("de"), lang("en")])
+    );
+    assert_eq!("Repair café", loaded.title);
+    assert_eq!(Some("Bring it along"), loaded.description.as_deref());
+
+    let events = usecases::query_events(
+        &fixture.db_connections.shared()?,
+        &*fixture.search_engine.borrow(),
+        usecases::EventQuery {
+            text: Some("repair".into()),
+            ..Default::default()
+        },
+    )?;
+    assert_eq!(1, events.len());
+    assert_eq!(1, events[0].translations.len());
+    Ok(())
+}
@@ -0,0 +1,269 @@
+use std::{cell::RefCell, collections::HashMap, io::Cursor};
+
+use image::{DynamicImage, ImageDecoder, ImageEncoder, RgbImage, codecs::jpeg::*};
+use ofdb_core::gateways::image_storage::ImageStorage;
+
+use super::prelude::*;
+use crate::{Result, prelude::ImageUpload};
+
+#[derive(Default)]
+struct InMemoryImageStorage {
+    images: RefCell<HashMap<String, (String, Vec<u8>)>>,
+}
+
+impl InMemoryImageStorage {
+    fn image(&self, url: &Url) -> (String, Vec<u8>) {
+        let key = url.as_str().strip_prefix("https://example.com/").unwrap();
+        self.images.borrow().get(key).cloned().unwrap()
+    }
+}
+
+impl ImageStorage for InMemoryImageStorage {
+    fn store_image(&self, key: &str, content_type: &str, data: &[u8]) -> anyhow::Result<Url> {
+        self.images
+            .borrow_mut()
+            .insert(key.to_string(), (content_type.to_string(), data.to_vec()));
+        Ok(format!("https://example.com/{key}").parse()?)
+    }
+}
+
+// EXIF (little endian) with the orientation "rotate 90° clockwise"
+const EXIF_ROTATE_90: [u8; 26] = [
+    b'I', b'I', 0x2A, 0x00, 0x08, 0x00, 0x00, 0x00, 0x01, 0x00, 0x12, 0x01, 0x03, 0x00, 0x01, 0x00,
+    0x00, 0x00, 0x06, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+];
+
+fn jpeg_with_exif(width: u32, height: u32) -> Vec<u8> {
+    let image = RgbImage::from_pixel(width, height, image::Rgb([200, 100, 50]));
+    let mut data = Vec::new();
+    let mut encoder = JpegEncoder::new(&mut data);
+    encoder.set_exif_metadata(EXIF_ROTATE_90.to_vec()).unwrap();
+    encoder
+        .write_image(&image, width, height, image::ExtendedColorType::Rgb8)
+        .unwrap();
+    data
+}
+
+fn decode_jpeg(data: &[u8]) -> (Option<Vec<u8>>, DynamicImage) {
+    let mut decoder = JpegDecoder::new(Cursor::new(data)).unwrap();
+    let exif = decoder.exif_metadata().unwrap();
+    (exif, DynamicImage::from_decoder(decoder).unwrap())
+}
+
+#[test]
+fn upload_place_image_without_metadata() -> Result<()> {
+    let mut fixture = BackendFixture::new();
+    let storage = InMemoryImageStorage::default();
+    let place_id = fixture.create_place(NewPlace::from(1), None);
+    let data = jpeg_with_exif(1200, 600);
+    assert!(decode_jpeg(&data).0.is_some());
+
+    let uploaded = flows::upload_place_image(
+        &fixture.db_connections,
+        fixture.search_engine.get_mut(),
+        &fixture.notify,
+        &storage,
+        place_id.clone().into(),
+        ImageUpload {
+            content_type: "image/jpeg",
+            data: &data,
+        },
+        None,
+        None,
+        &accepted_licenses(),
+    )?;
+
+    let (content_type, stored) = storage.image(&uploaded.url);
+    assert_eq!("image/jpeg", content_type);
+    let (exif, image) = decode_jpeg(&stored);
+    assert!(exif.is_none());
+    // The orientation has been applied before discarding the metadata
+    assert_eq!((600, 1200), (image.width(), image.height()));
+
+    assert_eq!(
+        vec![128, 256, 512],
+        uploaded
+            .thumbnails
+            .iter()
+            .map(|(size, _)| *size)
+            .collect::<Vec<_>>()
+    );
+    let (_, thumbnail) = decode_jpeg(&storage.image(&uploaded.thumbnails[0].1).1);
+    assert_eq!((64, 128), (thumbnail.width(), thumbnail.height()));
+
+    let (place, _) = fixture.try_get_place(&place_id).unwrap();
+    assert_eq!(Revision::from(1), place.revision);
+    assert_eq!(Some(uploaded.url), place.links.unwrap().image);
+    Ok(())
+}
+
+#[test]
+fn reject_images_with_unsupported_or_mismatching_types() {
+    let mut fixture = BackendFixture::new();
+    let storage = InMemoryImageStorage::default();
+    let place_id = fixture.create_place(NewPlace::from(1), None);
+    let jpeg = jpeg_with_exif(10, 10);
+    for (content_type, data) in [
+        ("image/gif", &jpeg[..]),
+        ("image/png", &jpeg[..]),
+        ("image/jpeg", &b"no image"[..]),
+        ("image/jpeg", &jpeg[..20]),
+    ] {
+        let result = flows::upload_place_image(
+            &fixture.db_connections,
+            fixture.search_engine.get_mut(),
+            &fixture.notify,
+            &storage,
+            place_id.clone().into(),
+            ImageUpload { content_type, data },
+            None,
+            None,
+            &accepted_licenses(),
+        );
+        assert!(result.is_err());
+    }
+    assert!(storage.images.borrow().is_empty());
+    let (place, _) = fixture.try_get_place(&place_id).unwrap();
+    assert_eq!(Revision::initial(), place.revision);
+}
+
+#[test]
+fn upload_image_of_owned_event() -> Result<()> {
+    let mut fixture = BackendFixture::new();
+    let storage = InMemoryImageStorage::default();
+    let org = Organization {
+        id: Id::new(),
+        name: "org".into(),
+        api_token: "org".into(),
+        moderated_tags: vec![ModeratedTag {
+            label: "org".into(),
+            allow_add: true,
+            allow_remove: false,
+            require_clearance: false,
+        }],
+    };
+    fixture
+        .db_connections
+        .exclusive()?
+        .create_org(org.clone())?;
+    let event = flows::create_event(
+        &fixture.db_connections,
+        fixture.search_engine.get_mut(),
+        &fixture.notify,
+        Some(&org.api_token),
+        usecases::NewEvent {
+            tags: Some(vec!["org".into()]),
+            created_by: Some("user@example.com".parse().unwrap()),
+            ..usecases::NewEvent::new("event".into(), Timestamp::now())
+        },
+    )?;
+
+    let uploaded = flows::upload_event_image(
+        &fixture.db_connections,
+        fixture.search_engine.get_mut(),
+        &fixture.notify,
+        &storage,
+        &org,
+        event.id.as_str(),
+        ImageUpload {
+            content_type: "image/jpeg",
+            data: &jpeg_with_exif(10, 10),
+        },
+        None,
+    )?;
+    let event = fixture
+        .db_connections
+        .shared()?
+        .get_event(event.id.as_str())?;
+    assert_eq!(Some(uploaded.url), event.image_url);
+    assert_eq!(Revision::from(1), event.revision);
+    Ok(())
+}
+
+#[test]
+fn upload_image_of_event_that_requires_clearance() -> Result<()> {
+    let mut fixture = BackendFixture::new();
+    let storage = InMemoryImageStorage::default();
+    let owner = Organization {
+        id: Id::new(),
+        name: "owner".into(),
+        api_token: "owner".into(),
+        moderated_tags: vec![ModeratedTag {
+            label: "owner".into(),
+            allow_add: true,
+            allow_remove: false,
+            require_clearance: false,
+        }],
+    };
+    let clearing_org = Organization {
+        id: Id::new(),
+        name: "clearing".into(),
+        api_token: "clearing".into(),
+        moderated_tags: vec![ModeratedTag {
+            label: "clearing".into(),
+            allow_add: true,
+            allow_remove: true,
+            require_clearance: true,
+        }],
+    };
+    {
+        let mut conn = fixture.db_connections.exclusive()?;
+        conn.create_org(owner.clone())?;
+        conn.create_org(clearing_org.clone())?;
+    }
+    let event = flows::create_event(
+        &fixture.db_connections,
+        fixture.search_engine.get_mut(),
+        &fixture.notify,
+        Some(&owner.api_token),
+        usecases::NewEvent {
+            tags: Some(vec!["owner".into(), "clearing".into()]),
+            created_by: Some("user@example.com".parse().unwrap()),
+            ..usecases::NewEvent::new("event".into(), Timestamp::now())
+        },
+    )?;
+    // Clear the initial revision
+    usecases::clearance::event::update_pending_clearances(
+        &fixture.db_connections.exclusive()?,
+        &clearing_org,
+        &[ClearanceForEvent {
+            event_id: event.id.clone(),
+            cleared_revision: Some(event.revision),
+        }],
+        None,
+    )?;
+    assert!(
+        usecases::clearance::event::list_pending_clearances(
+            &fixture.db_connections.shared()?,
+            &clearing_org,
+            &Default::default(),
+        )?
+        .is_empty()
+    );
+
+    flows::upload_event_image(
+        &fixture.db_connections,
+        fixture.search_engine.get_mut(),
+        &fixture.notify,
+        &storage,
+        &owner,
+        event.id.as_str(),
+        ImageUpload {
+            content_type: "image/jpeg",
+            data: &jpeg_with_exif(10, 10),
+        },
+        Some("user@example.com".parse().unwrap()),
+    )?;
+    let pending_clearances = usecases::clearance::event::list_pending_clearances(
+        &fixture.db_connections.shared()?,
+        &clearing_org,
+        &Default::default(),
+    )?;
+    assert_eq!(1, pending_clearances.len());
+    assert_eq!(event.id, pending_clearances[0].event_id);
+    assert_eq!(
+        Some(Revision::initial()),
+        pending_clearances[0].last_cleared_revision
+    );
+    Ok(())
+}
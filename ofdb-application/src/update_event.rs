@@ -11,7 +11,7 @@ pub fn update_event(
     new_event: usecases::NewEvent,
 ) -> Result<Event> {
     // Create and add new event
-    let event = {
+    let (event, clearance_org_ids) = {
         connections.exclusive()?.transaction(|conn| {
             match usecases::import_new_event(
                 conn,
@@ -20,11 +20,12 @@ pub fn update_event(
                 usecases::NewEventMode::Update(id.as_str()),
             ) {
                 Ok(storable) => {
+                    let clearance_org_ids = storable.clearance_org_ids().to_vec();
                     let event = usecases::store_updated_event(conn, storable).map_err(|err| {
                         warn!("Failed to store updated event: {}", err);
                         err
                     })?;
-                    Ok(event)
+                    Ok((event, clearance_org_ids))
                 }
                 Err(err) => Err(err),
             }
@@ -45,25 +46,32 @@ pub fn update_event(
             event.id, err
         );
     }
+    if !clearance_org_ids.is_empty() {
+        notify.notify(NotificationEvent::EventClearancePending {
+            event: &event,
+            org_ids: &clearance_org_ids,
+        });
+    }
 
     Ok(event)
 }
 
-fn notify_event_updated(
+pub(crate) fn notify_event_updated(
     connections: &sqlite::Connections,
     notify: &dyn NotificationGateway,
     event: &Event,
 ) -> Result<()> {
-    if let Some(ref location) = event.location {
-        let email_addresses = {
-            let conn = connections.shared()?;
-            usecases::email_addresses_by_coordinate(&conn, location.pos)?
-        };
-        let event = NotificationEvent::EventUpdated {
-            event,
-            email_addresses: &email_addresses,
-        };
-        notify.notify(event);
-    }
+    // Only events with a location could be subscribed to
+    let email_addresses = if let Some(ref location) = event.location {
+        let conn = connections.shared()?;
+        usecases::email_addresses_by_coordinate(&conn, location.pos)?
+    } else {
+        vec![]
+    };
+    let event = NotificationEvent::EventUpdated {
+        event,
+        email_addresses: &email_addresses,
+    };
+    notify.notify(event);
     Ok(())
 }
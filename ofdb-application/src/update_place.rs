@@ -15,7 +15,7 @@ pub fn update_place(
     accepted_licenses: &HashSet<String>,
 ) -> Result<Place> {
     // Update existing entry
-    let (place, ratings) = {
+    let (place, ratings, clearance_org_ids) = {
         connections.exclusive()?.transaction(|conn| {
             match usecases::prepare_updated_place(
                 conn,
@@ -26,12 +26,13 @@ pub fn update_place(
                 accepted_licenses,
             ) {
                 Ok(storable) => {
+                    let clearance_org_ids = storable.clearance_org_ids().to_vec();
                     let (place, ratings) =
                         usecases::store_updated_place(conn, storable).map_err(|err| {
                             warn!("Failed to store updated place: {}", err);
                             err
                         })?;
-                    Ok((place, ratings))
+                    Ok((place, ratings, clearance_org_ids))
                 }
                 Err(err) => Err(err),
             }
@@ -54,6 +55,12 @@ pub fn update_place(
             place.id, err
         );
     }
+    if !clearance_org_ids.is_empty() {
+        notify.notify(NotificationEvent::PlaceClearancePending {
+            place: &place,
+            org_ids: &clearance_org_ids,
+        });
+    }
 
     Ok(place)
 }
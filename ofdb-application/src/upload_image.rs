@@ -0,0 +1,192 @@
+use std::{collections::HashSet, io::Cursor};
+
+use image::{
+    DynamicImage, ImageDecoder, ImageFormat, ImageReader, Limits, codecs::jpeg::JpegEncoder,
+    imageops::FilterType, metadata::Orientation,
+};
+use ofdb_core::gateways::{
+    image_storage::ImageStorage,
+    notify::{NotificationEvent, NotificationGateway},
+};
+
+use super::*;
+use usecases::Error as ParameterError;
+
+/// Content types of images that could be uploaded
+pub const SUPPORTED_IMAGE_TYPES: [&str; 3] = ["image/jpeg", "image/png", "image/webp"];
+
+/// Maximum width and height of the generated thumbnails
+pub const THUMBNAIL_SIZES: [u32; 3] = [128, 256, 512];
+
+// Larger images are scaled down before storing them
+const MAX_IMAGE_SIZE: u32 = 2048;
+
+// Reject images that would require excessive memory for decoding
+const MAX_DECODED_IMAGE_SIZE: u32 = 10_000;
+
+const JPEG_QUALITY: u8 = 85;
+
+pub struct ImageUpload<'a> {
+    pub content_type: &'a str,
+    pub data: &'a [u8],
+}
+
+#[derive(Debug, Clone)]
+pub struct UploadedImage {
+    pub url: Url,
+    /// Thumbnails by their maximum width and height
+    pub thumbnails: Vec<(u32, Url)>,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn upload_place_image(
+    connections: &sqlite::Connections,
+    indexer: &mut dyn PlaceIndexer,
+    notify: &dyn NotificationGateway,
+    storage: &dyn ImageStorage,
+    id: Id,
+    upload: ImageUpload,
+    created_by_email: Option<&EmailAddress>,
+    created_by_org: Option<&Organization>,
+    accepted_licenses: &HashSet<String>,
+) -> Result<UploadedImage> {
+    // Don't store images of nonexistent places
+    let (place, _) = connections.shared()?.get_place(id.as_str())?;
+    let image = store_image(storage, &format!("places/{id}"), &upload)?;
+    let mut update_place = usecases::UpdatePlace::from(place.clone());
+    update_place.version = place.revision.next().into();
+    update_place.image_url = Some(image.url.to_string());
+    crate::prelude::update_place(
+        connections,
+        indexer,
+        notify,
+        id,
+        update_place,
+        created_by_email,
+        created_by_org,
+        accepted_licenses,
+    )?;
+    Ok(image)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn upload_event_image(
+    connections: &sqlite::Connections,
+    indexer: &mut dyn EventIndexer,
+    notify: &dyn NotificationGateway,
+    storage: &dyn ImageStorage,
+    org: &Organization,
+    id: &str,
+    upload: ImageUpload,
+    uploaded_by: Option<EmailAddress>,
+) -> Result<UploadedImage> {
+    // Don't store images of nonexistent events
+    connections.shared()?.get_event(id)?;
+    let image = store_image(storage, &format!("events/{id}"), &upload)?;
+    let (event, clearance_org_ids) = connections.exclusive()?.transaction(|conn| {
+        usecases::set_event_image(conn, org, id, image.url.clone(), uploaded_by).map_err(|err| {
+            warn!("Failed to set image of event {id}: {err}");
+            err
+        })
+    })?;
+
+    // Re-index updated event
+    // TODO: Move to a separate task/thread that doesn't delay this request
+    if let Err(err) = usecases::index_event(indexer, &event).and_then(|_| indexer.flush_index()) {
+        error!("Failed to re-index updated event {}: {}", event.id, err);
+    }
+
+    // Send subscription e-mails
+    // TODO: Move to a separate task/thread that doesn't delay this request
+    if let Err(err) = crate::update_event::notify_event_updated(connections, notify, &event) {
+        error!(
+            "Failed to send notifications for updated event {}: {}",
+            event.id, err
+        );
+    }
+    if !clearance_org_ids.is_empty() {
+        notify.notify(NotificationEvent::EventClearancePending {
+            event: &event,
+            org_ids: &clearance_org_ids,
+        });
+    }
+
+    Ok(image)
+}
+
+/// Store the decoded image and its thumbnails with keys
+/// that start with the given prefix.
+fn store_image(
+    storage: &dyn ImageStorage,
+    prefix: &str,
+    upload: &ImageUpload,
+) -> Result<UploadedImage> {
+    let image = decode_image(upload)?;
+    let image = if image.width() > MAX_IMAGE_SIZE || image.height() > MAX_IMAGE_SIZE {
+        image.resize(MAX_IMAGE_SIZE, MAX_IMAGE_SIZE, FilterType::Lanczos3)
+    } else {
+        image
+    };
+    let name = format!("{prefix}/{}", Id::new());
+    let url = {
+        let (extension, content_type, data) = encode_image(&image)?;
+        storage.store_image(&format!("{name}.{extension}"), content_type, &data)?
+    };
+    let mut thumbnails = Vec::with_capacity(THUMBNAIL_SIZES.len());
+    for size in THUMBNAIL_SIZES {
+        // Small images are never scaled up
+        let thumbnail = if image.width() > size || image.height() > size {
+            image.thumbnail(size, size)
+        } else {
+            image.clone()
+        };
+        let (extension, content_type, data) = encode_image(&thumbnail)?;
+        let url =
+            storage.store_image(&format!("{name}-{size}.{extension}"), content_type, &data)?;
+        thumbnails.push((size, url));
+    }
+    Ok(UploadedImage { url, thumbnails })
+}
+
+fn decode_image(upload: &ImageUpload) -> Result<DynamicImage> {
+    let format = Some(upload.content_type)
+        .filter(|content_type| SUPPORTED_IMAGE_TYPES.contains(content_type))
+        .and_then(ImageFormat::from_mime_type)
+        .ok_or(ParameterError::ImageType)?;
+    // The declared type must match the actual contents
+    if image::guess_format(upload.data).ok() != Some(format) {
+        return Err(ParameterError::ImageType.into());
+    }
+    let mut reader = ImageReader::with_format(Cursor::new(upload.data), format);
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_DECODED_IMAGE_SIZE);
+    limits.max_image_height = Some(MAX_DECODED_IMAGE_SIZE);
+    reader.limits(limits);
+    let mut decoder = reader.into_decoder().map_err(|_| ParameterError::Image)?;
+    // The orientation is lost together with all other metadata
+    let orientation = decoder.orientation().unwrap_or(Orientation::NoTransforms);
+    let mut image = DynamicImage::from_decoder(decoder).map_err(|_| ParameterError::Image)?;
+    image.apply_orientation(orientation);
+    Ok(image)
+}
+
+/// Encode only the pixels of the image, i.e. strip all metadata
+/// like EXIF that might contain GPS coordinates or device details.
+///
+/// Images with transparency are stored as PNG and all others as JPEG.
+fn encode_image(image: &DynamicImage) -> Result<(&'static str, &'static str, Vec<u8>)> {
+    let mut data = Vec::new();
+    if image.color().has_alpha() {
+        image
+            .write_to(&mut Cursor::new(&mut data), ImageFormat::Png)
+            .map_err(anyhow::Error::from)?;
+        Ok(("png", "image/png", data))
+    } else {
+        let encoder = JpegEncoder::new_with_quality(&mut data, JPEG_QUALITY);
+        image
+            .to_rgb8()
+            .write_with_encoder(encoder)
+            .map_err(anyhow::Error::from)?;
+        Ok(("jpg", "image/jpeg", data))
+    }
+}
@@ -0,0 +1,258 @@
+use ofdb_boundary::{WebhookItem, WebhookPayload};
+use ofdb_core::gateways::{
+    notify::{NotificationEvent, NotificationGateway},
+    webhook::WebhookGateway,
+};
+
+use super::*;
+
+/// The maximum number of deliveries that are attempted at once.
+const MAX_DUE_DELIVERIES: u64 = 100;
+
+/// Schedules the delivery of notification events to the
+/// webhooks of the affected organizations.
+///
+/// Organizations are affected if they moderate one of the tags
+/// or if they need to clear the changes.
+#[derive(Clone)]
+pub struct WebhookNotifications {
+    connections: sqlite::Connections,
+}
+
+impl WebhookNotifications {
+    pub const fn new(connections: sqlite::Connections) -> Self {
+        Self { connections }
+    }
+
+    fn enqueue(&self, event: NotificationEvent) -> Result<usize> {
+        use NotificationEvent as E;
+        use WebhookEventType as T;
+        let (event_type, item, org_ids): (_, WebhookItem, _) = match event {
+            E::PlaceAdded { place, .. } => (T::PlaceAdded, place.clone().into(), None),
+            E::PlaceUpdated { place, .. } => (T::PlaceUpdated, place.clone().into(), None),
+            E::PlaceClearancePending { place, org_ids } => (
+                T::PlaceClearancePending,
+                place.clone().into(),
+                Some(org_ids),
+            ),
+            E::EventAdded { event, .. } => (T::EventAdded, event.clone().into(), None),
+            E::EventUpdated { event, .. } => (T::EventUpdated, event.clone().into(), None),
+            E::EventClearancePending { event, org_ids } => (
+                T::EventClearancePending,
+                event.clone().into(),
+                Some(org_ids),
+            ),
+            E::UserRegistered { .. }
+            | E::UserResetPasswordRequested { .. }
+            | E::ReminderCreated { .. } => return Ok(0),
+        };
+        let tags = match &item {
+            WebhookItem::Place { tags, .. } | WebhookItem::Event { tags, .. } => tags.clone(),
+        };
+        let created_at = Timestamp::now();
+        let payload = serde_json::to_string(&WebhookPayload {
+            event_type: event_type.to_string(),
+            created_at: created_at.into(),
+            item,
+        })?;
+        let count = self.connections.exclusive()?.transaction(|conn| {
+            let org_ids = match org_ids {
+                Some(org_ids) => org_ids.to_vec(),
+                None => usecases::org_ids_by_moderated_tags(conn, &tags)?,
+            };
+            usecases::enqueue_webhook_deliveries(conn, &org_ids, event_type, &payload, created_at)
+        })?;
+        Ok(count)
+    }
+}
+
+impl NotificationGateway for WebhookNotifications {
+    fn notify(&self, event: NotificationEvent) {
+        let event_type = event.kind();
+        match self.enqueue(event) {
+            Ok(0) => (),
+            Ok(count) => debug!("Scheduled {count} webhook deliveries for {event_type:?}"),
+            Err(err) => error!("Failed to schedule webhook deliveries for {event_type:?}: {err}"),
+        }
+    }
+}
+
+/// Send all pending webhook deliveries that are due.
+///
+/// Returns the number of attempted deliveries.
+pub fn deliver_webhooks(
+    connections: &sqlite::Connections,
+    gateway: &dyn WebhookGateway,
+) -> Result<usize> {
+    let due_deliveries = {
+        let conn = connections.shared()?;
+        usecases::load_due_webhook_deliveries(&conn, Timestamp::now(), MAX_DUE_DELIVERIES)?
+    };
+    let mut count = 0;
+    for (webhook, delivery) in due_deliveries {
+        // Skip deliveries that are attempted by another instance
+        let claimed = {
+            let conn = connections.exclusive()?;
+            usecases::claim_webhook_delivery(&conn, delivery, Timestamp::now())?
+        };
+        let Some(delivery) = claimed else {
+            continue;
+        };
+        count += 1;
+        // The HTTP request is sent without holding a database connection
+        let delivery =
+            usecases::attempt_webhook_delivery(gateway, &webhook, delivery, Timestamp::now());
+        if let Some(err) = &delivery.last_error {
+            warn!(
+                "Failed to deliver {} to webhook {} (attempt {}): {err}",
+                delivery.event_type, webhook.id, delivery.attempts
+            );
+        }
+        connections
+            .exclusive()?
+            .transaction(|conn| conn.update_webhook_delivery(&delivery))?;
+    }
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::prelude::*;
+    use std::cell::RefCell;
+
+    struct MockWebhookGw {
+        status: u16,
+        delivered: RefCell<Vec<(Id, String)>>,
+    }
+
+    impl WebhookGateway for MockWebhookGw {
+        fn deliver(&self, webhook: &Webhook, delivery: &WebhookDelivery) -> anyhow::Result<u16> {
+            self.delivered
+                .borrow_mut()
+                .push((webhook.id.clone(), delivery.payload.clone()));
+            Ok(self.status)
+        }
+    }
+
+    fn create_org_with_webhook(fixture: &BackendFixture) -> (Organization, Webhook) {
+        let org = Organization {
+            id: Id::new(),
+            name: "org".into(),
+            api_token: "org".into(),
+            moderated_tags: vec![ModeratedTag {
+                label: "moderated".into(),
+                allow_add: true,
+                allow_remove: true,
+                require_clearance: true,
+            }],
+        };
+        let mut conn = fixture.db_connections.exclusive().unwrap();
+        conn.create_org(org.clone()).unwrap();
+        let webhook =
+            usecases::create_webhook(&conn, &org.id, "https://example.org/hook", Timestamp::now())
+                .unwrap();
+        (org, webhook)
+    }
+
+    fn create_place(fixture: &BackendFixture, notify: &dyn NotificationGateway, tag: &str) {
+        flows::create_place(
+            &fixture.db_connections,
+            &mut *fixture.search_engine.borrow_mut(),
+            notify,
+            usecases::NewPlace {
+                title: "place".into(),
+                description: "place".into(),
+                tags: vec![tag.into()],
+                ..default_new_place()
+            },
+            None,
+            None,
+            &accepted_licenses(),
+        )
+        .unwrap();
+    }
+
+    fn deliveries(fixture: &BackendFixture, webhook: &Webhook) -> Vec<WebhookDelivery> {
+        let conn = fixture.db_connections.shared().unwrap();
+        conn.list_webhook_deliveries(&webhook.id, &Default::default())
+            .unwrap()
+    }
+
+    #[test]
+    fn deliver_place_added_and_clearance_pending() {
+        let fixture = BackendFixture::new();
+        let (_, webhook) = create_org_with_webhook(&fixture);
+        let notify = WebhookNotifications::new(fixture.db_connections.clone());
+
+        create_place(&fixture, &notify, "unmoderated");
+        assert!(deliveries(&fixture, &webhook).is_empty());
+
+        create_place(&fixture, &notify, "moderated");
+        let pending = deliveries(&fixture, &webhook);
+        assert_eq!(pending.len(), 2);
+        assert!(
+            pending
+                .iter()
+                .all(|d| d.status == WebhookDeliveryStatus::Pending)
+        );
+
+        let gw = MockWebhookGw {
+            status: 200,
+            delivered: Default::default(),
+        };
+        assert_eq!(deliver_webhooks(&fixture.db_connections, &gw).unwrap(), 2);
+        let delivered = gw.delivered.take();
+        let mut event_types: Vec<_> = delivered
+            .iter()
+            .map(|(webhook_id, payload)| {
+                assert_eq!(webhook_id, &webhook.id);
+                let payload: WebhookPayload = serde_json::from_str(payload).unwrap();
+                assert!(matches!(
+                    &payload.item,
+                    WebhookItem::Place { title, .. } if title == "place"
+                ));
+                payload.event_type
+            })
+            .collect();
+        event_types.sort_unstable();
+        assert_eq!(event_types, vec!["place.added", "place.clearance_pending"]);
+        assert!(
+            deliveries(&fixture, &webhook)
+                .iter()
+                .all(|d| d.status == WebhookDeliveryStatus::Delivered && d.attempts == 1)
+        );
+
+        // Nothing left to deliver
+        assert_eq!(deliver_webhooks(&fixture.db_connections, &gw).unwrap(), 0);
+    }
+
+    #[test]
+    fn retry_failed_deliveries_later() {
+        let fixture = BackendFixture::new();
+        let (org, webhook) = create_org_with_webhook(&fixture);
+        {
+            let conn = fixture.db_connections.exclusive().unwrap();
+            usecases::enqueue_webhook_deliveries(
+                &conn,
+                &[org.id],
+                WebhookEventType::EventAdded,
+                "{}",
+                Timestamp::now(),
+            )
+            .unwrap();
+        }
+        let gw = MockWebhookGw {
+            status: 503,
+            delivered: Default::default(),
+        };
+        assert_eq!(deliver_webhooks(&fixture.db_connections, &gw).unwrap(), 1);
+        let delivery = deliveries(&fixture, &webhook).remove(0);
+        assert_eq!(delivery.status, WebhookDeliveryStatus::Pending);
+        assert_eq!(delivery.attempts, 1);
+        assert!(delivery.last_error.is_some());
+        assert!(delivery.next_attempt_at > delivery.last_attempt_at);
+        // Not yet due
+        assert_eq!(deliver_webhooks(&fixture.db_connections, &gw).unwrap(), 0);
+    }
+}
@@ -59,6 +59,37 @@ impl From<CustomLink> for e::links::CustomLink {
     }
 }
 
+impl From<e::translation::Translation> for Translation {
+    fn from(from: e::translation::Translation) -> Self {
+        let e::translation::Translation {
+            lang,
+            title,
+            description,
+        } = from;
+        Self {
+            lang: lang.into(),
+            title,
+            description,
+        }
+    }
+}
+
+impl TryFrom<Translation> for e::translation::Translation {
+    type Error = e::translation::LanguageParseError;
+    fn try_from(from: Translation) -> Result<Self, Self::Error> {
+        let Translation {
+            lang,
+            title,
+            description,
+        } = from;
+        Ok(Self {
+            lang: lang.parse()?,
+            title,
+            description,
+        })
+    }
+}
+
 impl From<e::category::Category> for Category {
     fn from(from: e::category::Category) -> Self {
         let name = from.name();
@@ -210,6 +241,8 @@ impl From<e::event::Event> for Event {
             registration,
             image_url,
             image_link_url,
+            recurrence,
+            translations,
             ..
         } = e;
 
@@ -250,6 +283,12 @@ impl From<e::event::Event> for Event {
 
         let start = start.into();
         let end = end.map(Into::into);
+        let (rrule, exdates) = recurrence
+            .map(|r| {
+                let exdates = r.exdates.into_iter().map(Into::into).collect();
+                (Some(r.rule.to_string()), exdates)
+            })
+            .unwrap_or_default();
 
         Event {
             id: id.into(),
@@ -272,6 +311,10 @@ impl From<e::event::Event> for Event {
             organizer,
             image_url: image_url.map(Into::into),
             image_link_url: image_link_url.map(Into::into),
+            rrule,
+            exdates,
+            translations: translations.into_iter().map(Into::into).collect(),
+            lang: None,
         }
     }
 }
@@ -291,6 +334,25 @@ impl From<e::clearance::PendingClearanceForPlace> for PendingClearanceForPlace {
     }
 }
 
+impl From<e::clearance::ClearanceLogForPlace> for ClearanceLogForPlace {
+    fn from(from: e::clearance::ClearanceLogForPlace) -> Self {
+        let e::clearance::ClearanceLogForPlace {
+            place_id,
+            cleared_revision,
+            activity,
+        } = from;
+        Self {
+            place_id: place_id.into(),
+            cleared_revision: cleared_revision.into(),
+            created_at: activity.activity.at.into(),
+            created_by: activity
+                .activity
+                .by
+                .map(e::email::EmailAddress::into_string),
+        }
+    }
+}
+
 impl From<ClearanceForPlace> for e::clearance::ClearanceForPlace {
     fn from(from: ClearanceForPlace) -> Self {
         let ClearanceForPlace {
@@ -304,6 +366,319 @@ impl From<ClearanceForPlace> for e::clearance::ClearanceForPlace {
     }
 }
 
+impl From<e::api_token::ApiToken> for ApiToken {
+    fn from(from: e::api_token::ApiToken) -> Self {
+        let e::api_token::ApiToken {
+            id,
+            org_id: _,
+            name,
+            token_hash: _,
+            scopes,
+            created_at,
+            expires_at,
+            last_used_at,
+            revoked_at,
+        } = from;
+        Self {
+            id: id.into(),
+            name,
+            scopes: scopes.into_iter().map(|s| s.to_string()).collect(),
+            created_at: created_at.into(),
+            expires_at: expires_at.map(Into::into),
+            last_used_at: last_used_at.map(Into::into),
+            revoked_at: revoked_at.map(Into::into),
+        }
+    }
+}
+
+impl From<e::organization::ModeratedTag> for ModeratedTag {
+    fn from(from: e::organization::ModeratedTag) -> Self {
+        let e::organization::ModeratedTag {
+            label,
+            allow_add,
+            allow_remove,
+            require_clearance,
+        } = from;
+        Self {
+            label,
+            allow_add,
+            allow_remove,
+            require_clearance,
+        }
+    }
+}
+
+impl From<ModeratedTag> for e::organization::ModeratedTag {
+    fn from(from: ModeratedTag) -> Self {
+        let ModeratedTag {
+            label,
+            allow_add,
+            allow_remove,
+            require_clearance,
+        } = from;
+        Self {
+            label,
+            allow_add,
+            allow_remove,
+            require_clearance,
+        }
+    }
+}
+
+impl From<e::organization::Organization> for Organization {
+    fn from(from: e::organization::Organization) -> Self {
+        let e::organization::Organization {
+            id,
+            name,
+            api_token: _,
+            moderated_tags,
+        } = from;
+        Self {
+            id: id.into(),
+            name,
+            moderated_tags: moderated_tags.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<e::organization::OrganizationRole> for OrganizationRole {
+    fn from(from: e::organization::OrganizationRole) -> Self {
+        use e::organization::OrganizationRole::*;
+        match from {
+            Viewer => OrganizationRole::Viewer,
+            Editor => OrganizationRole::Editor,
+            Clearer => OrganizationRole::Clearer,
+        }
+    }
+}
+
+impl From<OrganizationRole> for e::organization::OrganizationRole {
+    fn from(from: OrganizationRole) -> Self {
+        use e::organization::OrganizationRole::*;
+        match from {
+            OrganizationRole::Viewer => Viewer,
+            OrganizationRole::Editor => Editor,
+            OrganizationRole::Clearer => Clearer,
+        }
+    }
+}
+
+impl From<e::organization::OrganizationMember> for OrganizationMember {
+    fn from(from: e::organization::OrganizationMember) -> Self {
+        let e::organization::OrganizationMember {
+            org_id,
+            email,
+            role,
+        } = from;
+        Self {
+            org_id: org_id.into(),
+            email: email.into_string(),
+            role: role.into(),
+        }
+    }
+}
+
+impl From<e::webhook::Webhook> for Webhook {
+    fn from(from: e::webhook::Webhook) -> Self {
+        let e::webhook::Webhook {
+            id,
+            org_id: _,
+            url,
+            secret: _,
+            created_at,
+        } = from;
+        Self {
+            id: id.into(),
+            url: url.into(),
+            created_at: created_at.into(),
+        }
+    }
+}
+
+impl From<e::webhook::WebhookDeliveryStatus> for WebhookDeliveryStatus {
+    fn from(from: e::webhook::WebhookDeliveryStatus) -> Self {
+        use e::webhook::WebhookDeliveryStatus as E;
+        match from {
+            E::Pending => Self::Pending,
+            E::Delivered => Self::Delivered,
+            E::Failed => Self::Failed,
+        }
+    }
+}
+
+impl From<e::webhook::WebhookDelivery> for WebhookDelivery {
+    fn from(from: e::webhook::WebhookDelivery) -> Self {
+        let e::webhook::WebhookDelivery {
+            id,
+            webhook_id: _,
+            event_type,
+            payload: _,
+            created_at,
+            status,
+            attempts,
+            next_attempt_at,
+            last_attempt_at,
+            last_response_status,
+            last_error,
+        } = from;
+        Self {
+            id: id.into(),
+            event_type: event_type.to_string(),
+            created_at: created_at.into(),
+            status: status.into(),
+            attempts,
+            next_attempt_at: next_attempt_at.map(Into::into),
+            last_attempt_at: last_attempt_at.map(Into::into),
+            last_response_status,
+            last_error,
+        }
+    }
+}
+
+impl From<e::place::Place> for WebhookItem {
+    fn from(from: e::place::Place) -> Self {
+        let e::place::Place {
+            id,
+            revision,
+            title,
+            location,
+            tags,
+            ..
+        } = from;
+        Self::Place {
+            id: id.into(),
+            revision: revision.into(),
+            title,
+            lat: location.pos.lat().to_deg(),
+            lng: location.pos.lng().to_deg(),
+            tags,
+        }
+    }
+}
+
+impl From<e::event::Event> for WebhookItem {
+    fn from(from: e::event::Event) -> Self {
+        let e::event::Event {
+            id,
+            revision,
+            title,
+            start,
+            tags,
+            ..
+        } = from;
+        Self::Event {
+            id: id.into(),
+            revision: revision.into(),
+            title,
+            start: start.into(),
+            tags,
+        }
+    }
+}
+
+impl From<e::change::ChangeKind> for ChangeKind {
+    fn from(from: e::change::ChangeKind) -> Self {
+        use e::change::ChangeKind as E;
+        match from {
+            E::Created => Self::Created,
+            E::Updated => Self::Updated,
+            E::Reviewed => Self::Reviewed,
+            E::Archived => Self::Archived,
+            E::Rejected => Self::Rejected,
+            E::Deleted => Self::Deleted,
+        }
+    }
+}
+
+impl From<e::change::ChangedItem> for ChangedItem {
+    fn from(from: e::change::ChangedItem) -> Self {
+        use e::change::ChangedItem as E;
+        match from {
+            E::Place {
+                id,
+                revision,
+                status,
+            } => Self::Place {
+                id: id.into(),
+                revision: revision.into(),
+                status: status.into(),
+            },
+            E::Event { id, revision } => Self::Event {
+                id: id.into(),
+                revision: revision.map(Into::into),
+            },
+            E::Rating { id, place_id } => Self::Rating {
+                id: id.into(),
+                place_id: place_id.into(),
+            },
+        }
+    }
+}
+
+impl From<e::change::Change> for Change {
+    fn from(from: e::change::Change) -> Self {
+        let e::change::Change {
+            cursor,
+            changed_at,
+            kind,
+            item,
+        } = from;
+        Self {
+            cursor: cursor.to_string(),
+            changed_at: changed_at.into(),
+            kind: kind.into(),
+            item: item.into(),
+        }
+    }
+}
+
+impl From<e::clearance::PendingClearanceForEvent> for PendingClearanceForEvent {
+    fn from(from: e::clearance::PendingClearanceForEvent) -> Self {
+        let e::clearance::PendingClearanceForEvent {
+            event_id,
+            created_at,
+            last_cleared_revision,
+        } = from;
+        Self {
+            event_id: event_id.into(),
+            created_at: created_at.into(),
+            last_cleared_revision: last_cleared_revision.map(Into::into),
+        }
+    }
+}
+
+impl From<e::clearance::ClearanceLogForEvent> for ClearanceLogForEvent {
+    fn from(from: e::clearance::ClearanceLogForEvent) -> Self {
+        let e::clearance::ClearanceLogForEvent {
+            event_id,
+            cleared_revision,
+            activity,
+        } = from;
+        Self {
+            event_id: event_id.into(),
+            cleared_revision: cleared_revision.into(),
+            created_at: activity.activity.at.into(),
+            created_by: activity
+                .activity
+                .by
+                .map(e::email::EmailAddress::into_string),
+        }
+    }
+}
+
+impl From<ClearanceForEvent> for e::clearance::ClearanceForEvent {
+    fn from(from: ClearanceForEvent) -> Self {
+        let ClearanceForEvent {
+            event_id,
+            cleared_revision,
+        } = from;
+        Self {
+            event_id: event_id.into(),
+            cleared_revision: cleared_revision.map(Into::into),
+        }
+    }
+}
+
 impl From<e::geo::MapPoint> for LatLonDegrees {
     fn from(from: e::geo::MapPoint) -> Self {
         Self(from.lat().to_deg(), from.lng().to_deg())
@@ -516,6 +891,7 @@ impl From<e::place::PlaceRevision> for PlaceRevision {
             founded_on,
             links,
             tags,
+            translations,
         } = from;
         Self {
             revision: revision.into(),
@@ -528,6 +904,7 @@ impl From<e::place::PlaceRevision> for PlaceRevision {
             founded_on,
             links: links.map(Into::into).unwrap_or_default(),
             tags,
+            translations: translations.into_iter().map(Into::into).collect(),
         }
     }
 }
@@ -540,6 +917,8 @@ pub enum PlaceRevisionConversionError {
     Activity(#[from] ActivityConversionError),
     #[error(transparent)]
     Email(#[from] e::email::EmailAddressParseError),
+    #[error(transparent)]
+    Language(#[from] e::translation::LanguageParseError),
 }
 
 impl TryFrom<PlaceRevision> for e::place::PlaceRevision {
@@ -556,6 +935,7 @@ impl TryFrom<PlaceRevision> for e::place::PlaceRevision {
             founded_on,
             links,
             tags,
+            translations,
         } = from;
 
         Ok(Self {
@@ -569,6 +949,10 @@ impl TryFrom<PlaceRevision> for e::place::PlaceRevision {
             founded_on,
             links: Some(links.into()),
             tags,
+            translations: translations
+                .into_iter()
+                .map(e::translation::Translation::try_from)
+                .collect::<Result<_, _>>()?,
         })
     }
 }
@@ -591,6 +975,23 @@ impl From<e::place::PlaceHistory> for PlaceHistory {
     }
 }
 
+impl From<e::event::EventHistory> for EventHistory {
+    fn from(from: e::event::EventHistory) -> Self {
+        let e::event::EventHistory { id, revisions } = from;
+        Self {
+            id: id.into(),
+            revisions: revisions
+                .into_iter()
+                .map(|(event, activity_log)| EventRevision {
+                    rev: event.revision.into(),
+                    act: activity_log.into(),
+                    event: event.into(),
+                })
+                .collect(),
+        }
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum PlaceHistoryConversionError {
     #[error(transparent)]
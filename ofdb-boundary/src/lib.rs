@@ -82,6 +82,17 @@ pub struct Entry {
 
     #[serde(rename = "custom", skip_serializing_if = "Vec::is_empty", default = "Default::default")]
     pub custom_links   : Vec<CustomLink>,
+
+    #[serde(skip_serializing_if = "std::ops::Not::not", default = "Default::default")]
+    pub opening_hours_invalid: bool,
+
+    #[serde(skip_serializing_if = "Vec::is_empty", default = "Default::default")]
+    pub translations   : Vec<Translation>,
+
+    /// The language of the selected translation of the title
+    /// and description, if any.
+    #[serde(skip_serializing_if = "Option::is_none", default = "Default::default")]
+    pub lang           : Option<String>,
 }
 
 #[rustfmt::skip]
@@ -93,6 +104,18 @@ pub struct CustomLink {
     pub description    : Option<String>,
 }
 
+/// The title and description in another language.
+#[rustfmt::skip]
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone, PartialEq, Eq))]
+pub struct Translation {
+    /// BCP 47 language tag, e.g. `en` or `fr-CH`
+    pub lang           : String,
+    pub title          : String,
+    #[serde(skip_serializing_if = "Option::is_none", default = "Default::default")]
+    pub description    : Option<String>,
+}
+
 #[rustfmt::skip]
 #[derive(Serialize, Deserialize)]
 #[cfg_attr(feature = "extra-derive", derive(Debug, Clone, PartialEq))]
@@ -127,6 +150,9 @@ pub struct NewPlace {
 
     #[serde(skip_serializing_if = "Vec::is_empty", default = "Default::default")]
     pub links          : Vec<CustomLink>,
+
+    #[serde(skip_serializing_if = "Vec::is_empty", default = "Default::default")]
+    pub translations   : Vec<Translation>,
 }
 
 #[rustfmt::skip]
@@ -156,6 +182,9 @@ pub struct UpdatePlace {
 
     #[serde(skip_serializing_if = "Vec::is_empty", default = "Default::default")]
     pub links          : Vec<CustomLink>,
+
+    #[serde(skip_serializing_if = "Vec::is_empty", default = "Default::default")]
+    pub translations   : Vec<Translation>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -181,6 +210,12 @@ pub struct NewEvent {
     pub organizer: Option<String>,
     pub image_url: Option<String>,
     pub image_link_url: Option<String>,
+    /// iCalendar recurrence rule, e.g. `FREQ=WEEKLY;BYDAY=SA`
+    pub rrule: Option<String>,
+    /// Start times of excluded occurrences
+    pub exdates: Option<Vec<i64>>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default = "Default::default")]
+    pub translations: Vec<Translation>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -222,6 +257,16 @@ pub struct Event {
     pub image_url: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub image_link_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rrule: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default = "Default::default")]
+    pub exdates: Vec<UnixTimeSeconds>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default = "Default::default")]
+    pub translations: Vec<Translation>,
+    /// The language of the selected translation of the title
+    /// and description, if any.
+    #[serde(skip_serializing_if = "Option::is_none", default = "Default::default")]
+    pub lang: Option<String>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -326,6 +371,13 @@ pub struct PlaceSearchResult {
     pub categories: Vec<String>,
     pub tags: Vec<String>,
     pub ratings: EntrySearchRatings,
+    /// The distance in meters from the center of a radius search
+    #[serde(skip_serializing_if = "Option::is_none", default = "Default::default")]
+    pub distance: Option<f64>,
+    /// The language of the selected translation of the title
+    /// and description, if any.
+    #[serde(skip_serializing_if = "Option::is_none", default = "Default::default")]
+    pub lang: Option<String>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -348,11 +400,34 @@ pub struct Review {
     pub comment: Option<String>,
 }
 
+/// Merge a duplicate into the place
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone))]
+pub struct MergePlace {
+    pub merged_place_id: String,
+    pub comment: Option<String>,
+}
+
 #[derive(Serialize, Deserialize)]
 #[cfg_attr(feature = "extra-derive", derive(Debug, Clone))]
 pub struct SearchResponse {
     pub visible: Vec<PlaceSearchResult>,
     pub invisible: Vec<PlaceSearchResult>,
+    #[serde(skip_serializing_if = "Option::is_none", default = "Default::default")]
+    pub facets: Option<SearchFacets>,
+}
+
+/// Counts of the visible search results.
+///
+/// Only the requested facets are included.
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone, PartialEq, Eq))]
+pub struct SearchFacets {
+    /// The most frequent tags
+    #[serde(skip_serializing_if = "Option::is_none", default = "Default::default")]
+    pub tags: Option<Vec<TagFrequency>>,
+    #[serde(skip_serializing_if = "Option::is_none", default = "Default::default")]
+    pub categories: Option<Vec<CategoryFrequency>>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -422,6 +497,22 @@ pub struct ConfirmEmailAddress {
 #[cfg_attr(feature = "extra-derive", derive(Debug, Clone, PartialEq, Eq))]
 pub struct TagFrequency(pub String, pub u64);
 
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone, PartialEq, Eq))]
+pub struct CategoryFrequency(pub String, pub u64);
+
+/// Nearby places that are aggregated into a single cluster.
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone, PartialEq))]
+pub struct PlaceCluster {
+    /// The latitude of the centroid
+    pub lat: f64,
+    /// The longitude of the centroid
+    pub lng: f64,
+    /// The number of places
+    pub count: u64,
+}
+
 #[derive(Serialize, Deserialize)]
 #[cfg_attr(feature = "extra-derive", derive(Debug, Clone, PartialEq))]
 pub struct Rating {
@@ -461,6 +552,309 @@ pub struct ClearanceForPlace {
     pub cleared_revision: Option<RevisionValue>,
 }
 
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone, PartialEq, Eq))]
+pub struct PendingClearanceForEvent {
+    pub event_id: String,
+    pub created_at: UnixTimeMillis,
+    pub last_cleared_revision: Option<RevisionValue>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone, PartialEq, Eq))]
+pub struct ClearanceForEvent {
+    pub event_id: String,
+    pub cleared_revision: Option<RevisionValue>,
+}
+
+/// A recorded clearance of a place revision.
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone, PartialEq, Eq))]
+pub struct ClearanceLogForPlace {
+    pub place_id: String,
+    pub cleared_revision: RevisionValue,
+    pub created_at: UnixTimeMillis,
+    pub created_by: Option<String>,
+}
+
+/// A recorded clearance of an event revision.
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone, PartialEq, Eq))]
+pub struct ClearanceLogForEvent {
+    pub event_id: String,
+    pub cleared_revision: RevisionValue,
+    pub created_at: UnixTimeMillis,
+    pub created_by: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone, PartialEq, Eq))]
+pub struct ApiToken {
+    pub id: String,
+    pub name: String,
+    pub scopes: Vec<String>,
+    pub created_at: UnixTimeMillis,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<UnixTimeMillis>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_used_at: Option<UnixTimeMillis>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revoked_at: Option<UnixTimeMillis>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone, PartialEq, Eq))]
+pub struct NewApiToken {
+    pub name: String,
+    pub scopes: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default = "Default::default")]
+    pub expires_at: Option<UnixTimeMillis>,
+}
+
+/// A newly created API token.
+///
+/// The secret is only returned once and cannot be recovered later.
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone, PartialEq, Eq))]
+pub struct CreatedApiToken {
+    pub api_token: ApiToken,
+    pub secret: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone, PartialEq, Eq))]
+pub struct ModeratedTag {
+    pub label: String,
+    pub allow_add: bool,
+    pub allow_remove: bool,
+    pub require_clearance: bool,
+}
+
+/// An organization that moderates tags.
+///
+/// The API token of the organization is never returned.
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone, PartialEq, Eq))]
+pub struct Organization {
+    pub id: String,
+    pub name: String,
+    pub moderated_tags: Vec<ModeratedTag>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone, PartialEq, Eq))]
+pub struct NewOrganization {
+    pub name: String,
+    #[serde(default = "Default::default")]
+    pub moderated_tags: Vec<ModeratedTag>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone, PartialEq, Eq))]
+pub struct UpdateOrganization {
+    pub name: String,
+}
+
+/// A newly created organization.
+///
+/// The API token is only returned once and cannot be recovered later.
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone, PartialEq, Eq))]
+pub struct CreatedOrganization {
+    pub organization: Organization,
+    pub api_token: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "extra-derive",
+    derive(Debug, Clone, Copy, PartialEq, Eq, Hash)
+)]
+#[serde(rename_all = "lowercase")]
+pub enum OrganizationRole {
+    Viewer,
+    Editor,
+    Clearer,
+}
+
+/// A registered user that acts on behalf of an organization.
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone, PartialEq, Eq))]
+pub struct OrganizationMember {
+    pub org_id: String,
+    pub email: String,
+    pub role: OrganizationRole,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone, PartialEq, Eq))]
+pub struct NewOrganizationMember {
+    pub email: String,
+    pub role: OrganizationRole,
+}
+
+/// A webhook of an organization.
+///
+/// The secret for verifying the signatures is never returned.
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone, PartialEq, Eq))]
+pub struct Webhook {
+    pub id: String,
+    pub url: String,
+    pub created_at: UnixTimeMillis,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone, PartialEq, Eq))]
+pub struct NewWebhook {
+    pub url: String,
+}
+
+/// A newly created webhook.
+///
+/// The secret is only returned once and cannot be recovered later.
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone, PartialEq, Eq))]
+pub struct CreatedWebhook {
+    pub webhook: Webhook,
+    pub secret: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone, Copy, PartialEq, Eq))]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookDeliveryStatus {
+    Pending,
+    Delivered,
+    Failed,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone, PartialEq, Eq))]
+pub struct WebhookDelivery {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub event_type: String,
+    pub created_at: UnixTimeMillis,
+    pub status: WebhookDeliveryStatus,
+    pub attempts: u32,
+    #[serde(skip_serializing_if = "Option::is_none", default = "Default::default")]
+    pub next_attempt_at: Option<UnixTimeMillis>,
+    #[serde(skip_serializing_if = "Option::is_none", default = "Default::default")]
+    pub last_attempt_at: Option<UnixTimeMillis>,
+    #[serde(skip_serializing_if = "Option::is_none", default = "Default::default")]
+    pub last_response_status: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none", default = "Default::default")]
+    pub last_error: Option<String>,
+}
+
+/// The item a webhook payload refers to.
+///
+/// Only the most important fields are included,
+/// all details could be requested by the id.
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone, PartialEq))]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookItem {
+    Place {
+        id: String,
+        revision: RevisionValue,
+        title: String,
+        lat: f64,
+        lng: f64,
+        tags: Vec<String>,
+    },
+    Event {
+        id: String,
+        revision: RevisionValue,
+        title: String,
+        start: UnixTimeSeconds,
+        tags: Vec<String>,
+    },
+}
+
+/// The JSON body that is sent to webhooks.
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone, PartialEq))]
+pub struct WebhookPayload {
+    #[serde(rename = "type")]
+    pub event_type: String,
+    pub created_at: UnixTimeMillis,
+    #[serde(flatten)]
+    pub item: WebhookItem,
+}
+
+/// An uploaded image of a place or event.
+///
+/// The URL of the image has already been stored
+/// in the links of the place or event.
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone, PartialEq, Eq))]
+pub struct UploadedImage {
+    pub url: Url,
+    pub thumbnails: Vec<Thumbnail>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone, PartialEq, Eq))]
+pub struct Thumbnail {
+    /// Maximum width and height in pixels
+    pub size: u32,
+    pub url: Url,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone, Copy, PartialEq, Eq))]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    Created,
+    Updated,
+    Reviewed,
+    Archived,
+    Rejected,
+    Deleted,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone, PartialEq, Eq))]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum ChangedItem {
+    Place {
+        id: String,
+        revision: RevisionValue,
+        status: ReviewStatus,
+    },
+    Event {
+        id: String,
+        #[serde(skip_serializing_if = "Option::is_none", default = "Default::default")]
+        revision: Option<RevisionValue>,
+    },
+    Rating {
+        id: String,
+        place_id: String,
+    },
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone, PartialEq, Eq))]
+pub struct Change {
+    /// Opaque cursor for resuming the change feed after this change
+    pub cursor: String,
+    pub changed_at: UnixTimeMillis,
+    pub kind: ChangeKind,
+    #[serde(flatten)]
+    pub item: ChangedItem,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone, PartialEq, Eq))]
+pub struct Changes {
+    pub changes: Vec<Change>,
+    /// Opaque cursor for requesting subsequent changes
+    #[serde(skip_serializing_if = "Option::is_none", default = "Default::default")]
+    pub next_cursor: Option<String>,
+}
+
 #[derive(Serialize, Deserialize)]
 #[cfg_attr(feature = "extra-derive", derive(Debug, Clone, PartialEq, Eq))]
 pub struct ResultCount {
@@ -626,6 +1020,13 @@ pub struct PlaceRevision {
         default = "Default::default"
     )]
     pub tags: Vec<String>,
+
+    #[serde(
+        rename = "i18n",
+        skip_serializing_if = "Vec::is_empty",
+        default = "Default::default"
+    )]
+    pub translations: Vec<Translation>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -636,7 +1037,22 @@ pub struct PlaceHistory {
 }
 
 #[derive(Serialize, Deserialize)]
-#[cfg_attr(feature = "extra-derive", derive(Debug))]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone))]
+pub struct EventHistory {
+    pub id: String,
+    pub revisions: Vec<EventRevision>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone))]
+pub struct EventRevision {
+    pub rev: u64,
+    pub act: ActivityLog,
+    pub event: Event,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone))]
 pub struct ActivityLog {
     pub at: UnixTimeMillis,
 
@@ -689,6 +1105,7 @@ impl From<Entry> for UpdatePlace {
             image_url,
             image_link_url,
             custom_links,
+            translations,
             ..
         } = e;
 
@@ -714,6 +1131,7 @@ impl From<Entry> for UpdatePlace {
             image_url,
             image_link_url,
             links: custom_links,
+            translations,
         }
     }
 }
@@ -722,6 +1140,15 @@ impl From<Entry> for UpdatePlace {
 #[cfg_attr(feature = "extra-derive", derive(Debug, Clone))]
 pub struct JwtToken {
     pub token: String,
+    /// Could be exchanged once for new tokens of the same session.
+    #[serde(skip_serializing_if = "Option::is_none", default = "Default::default")]
+    pub refresh_token: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone, PartialEq, Eq))]
+pub struct RefreshToken {
+    pub refresh_token: String,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -739,4 +1166,20 @@ pub struct Error {
 pub enum DuplicateType {
     SimilarChars,
     SimilarWords,
+    SamePhone,
+    SameEmail,
+    SameAddress,
+    SameHomepage,
+}
+
+/// A possible duplicate of a place
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone))]
+pub struct DuplicatePlace {
+    #[serde(flatten)]
+    pub place: PlaceSearchResult,
+    /// From 0.0 (different) to 1.0 (most likely identical)
+    pub score: f64,
+    /// All contributing reasons ordered by decreasing weight
+    pub reasons: Vec<DuplicateType>,
 }
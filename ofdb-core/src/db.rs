@@ -1,5 +1,6 @@
 use crate::entities::*;
 use anyhow::Result as Fallible;
+use time::PrimitiveDateTime;
 
 #[derive(Copy, Clone, Debug)]
 pub enum IndexQueryMode {
@@ -27,12 +28,48 @@ pub struct IndexQuery<'a, 'b> {
     pub ts_min_ub: Option<Timestamp>, // upper bound (inclusive)
     pub ts_max_lb: Option<Timestamp>, // lower bound (inclusive)
     pub ts_max_ub: Option<Timestamp>, // upper bound (inclusive)
+    // Only include places that are open at the given local time
+    // according to their opening hours. Public holidays are not
+    // considered.
+    pub open_at: Option<PrimitiveDateTime>,
+    // Additionally match the text with a tolerance for typos,
+    // word variants and special characters (e.g. "kaffe" matches
+    // "Kaffee"). Only supported if enabled when building the index.
+    pub fuzzy: bool,
+    // Reference point for `max_distance` and `order_by_distance`
+    pub center: Option<MapPoint>,
+    // Only include entries within the given great-circle distance
+    // from `center`. The bounding box should be restricted
+    // accordingly for efficiency, see `MapBbox::enclosing_circle()`.
+    pub max_distance: Option<Distance>,
+    // Order the results by their distance from `center`, nearest
+    // first, instead of their score or rating
+    pub order_by_distance: bool,
 }
 
 pub trait Indexer {
     fn flush_index(&mut self) -> Fallible<()>;
 }
 
+/// The kind of an indexed document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IndexedKind {
+    Place,
+    Event,
+}
+
+/// Identifies the indexed revision of a place or event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexedEntry {
+    pub kind: IndexedKind,
+    pub id: Id,
+    /// Only available for places
+    pub status: Option<ReviewStatus>,
+    /// Missing if the document has been indexed before
+    /// revisions were stored in the index
+    pub revision: Option<Revision>,
+}
+
 pub trait IdIndex {
     fn query_ids(
         &self,
@@ -40,6 +77,9 @@ pub trait IdIndex {
         query: &IndexQuery,
         limit: usize,
     ) -> Fallible<Vec<Id>>;
+
+    /// All indexed places and events in no particular order.
+    fn all_indexed_entries(&self) -> Fallible<Vec<IndexedEntry>>;
 }
 
 pub trait IdIndexer: Indexer + IdIndex {
@@ -55,10 +95,42 @@ pub struct IndexedPlace {
     pub description: String,
     pub tags: Vec<String>,
     pub ratings: AvgRatings,
+    pub translations: Vec<Translation>,
+}
+
+impl IndexedPlace {
+    /// Replace the title and description by the translation that
+    /// matches the preferred languages best and return its language.
+    pub fn translate(&mut self, preferred: &[Language]) -> Option<Language> {
+        let translation = select_translation(&self.translations, preferred)?;
+        self.title.clone_from(&translation.title);
+        if let Some(description) = &translation.description {
+            self.description.clone_from(description);
+        }
+        Some(translation.lang.clone())
+    }
+}
+
+/// Places that are aggregated into a single cell of a grid.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlaceCluster {
+    /// The mean position of all places in this cluster
+    pub centroid: MapPoint,
+    pub count: u64,
 }
 
 pub trait PlaceIndex {
     fn query_places(&self, query: &IndexQuery, limit: usize) -> Fallible<Vec<IndexedPlace>>;
+
+    /// Count the tags of all places that match the query,
+    /// most frequent first.
+    fn count_place_tags(&self, query: &IndexQuery) -> Fallible<Vec<TagFrequency>>;
+
+    /// Aggregate all places that match the query into the cells
+    /// of a regular lat/lng grid with the given cell size in degrees,
+    /// largest clusters first.
+    fn cluster_places(&self, query: &IndexQuery, cell_size_deg: f64)
+    -> Fallible<Vec<PlaceCluster>>;
 }
 
 pub trait PlaceIndexer: IdIndexer + PlaceIndex {
@@ -74,4 +146,9 @@ pub trait EventIndexer: IdIndexer {
     fn add_or_update_event(&self, event: &Event) -> Fallible<()>;
 }
 
-pub trait EventAndPlaceIndexer: PlaceIndexer + EventIndexer {}
+/// Query places and events without modifying the index.
+pub trait EventAndPlaceIndex: IdIndex + PlaceIndex {}
+
+impl<T> EventAndPlaceIndex for T where T: IdIndex + PlaceIndex + ?Sized {}
+
+pub trait EventAndPlaceIndexer: PlaceIndexer + EventIndexer + EventAndPlaceIndex {}
@@ -0,0 +1,8 @@
+use ofdb_entities::url::Url;
+
+/// Persistent storage for uploaded images.
+pub trait ImageStorage {
+    /// Store the encoded image under the given relative key,
+    /// e.g. `places/<id>/<image>.jpg`, and return its public URL.
+    fn store_image(&self, key: &str, content_type: &str, data: &[u8]) -> anyhow::Result<Url>;
+}
@@ -1,2 +1,4 @@
 pub mod geocode;
+pub mod image_storage;
 pub mod notify;
+pub mod webhook;
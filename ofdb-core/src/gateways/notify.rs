@@ -2,12 +2,13 @@ use ofdb_entities::{
     category::Category,
     email::{EmailAddress, EmailContent},
     event::Event,
+    id::Id,
     nonce::EmailNonce,
     place::Place,
     user::User,
 };
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum NotificationEvent<'a> {
     PlaceAdded {
         place: &'a Place,
@@ -33,6 +34,16 @@ pub enum NotificationEvent<'a> {
         // TODO: pass affected subscriptions instead of email addresses.
         email_addresses: &'a [EmailAddress],
     },
+    PlaceClearancePending {
+        place: &'a Place,
+        /// The organizations that need to clear the place
+        org_ids: &'a [Id],
+    },
+    EventClearancePending {
+        event: &'a Event,
+        /// The organizations that need to clear the event
+        org_ids: &'a [Id],
+    },
     UserRegistered {
         user: &'a User,
         // TODO: don't pass confirmation URL,
@@ -57,6 +68,8 @@ impl NotificationEvent<'_> {
             Self::PlaceUpdated { .. } => T::PlaceUpdated,
             Self::EventAdded { .. } => T::EventAdded,
             Self::EventUpdated { .. } => T::EventUpdated,
+            Self::PlaceClearancePending { .. } => T::PlaceClearancePending,
+            Self::EventClearancePending { .. } => T::EventClearancePending,
             Self::UserRegistered { .. } => T::UserRegistered,
             Self::UserResetPasswordRequested { .. } => T::UserResetPasswordRequested,
             Self::ReminderCreated { .. } => T::ReminderCreated,
@@ -70,6 +83,8 @@ pub enum NotificationType {
     PlaceUpdated,
     EventAdded,
     EventUpdated,
+    PlaceClearancePending,
+    EventClearancePending,
     UserRegistered,
     UserResetPasswordRequested,
     ReminderCreated,
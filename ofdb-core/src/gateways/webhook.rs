@@ -0,0 +1,8 @@
+use ofdb_entities::webhook::{Webhook, WebhookDelivery};
+
+pub trait WebhookGateway {
+    /// Send the payload of a delivery to the URL of the webhook.
+    ///
+    /// Returns the HTTP status code of the response.
+    fn deliver(&self, webhook: &Webhook, delivery: &WebhookDelivery) -> anyhow::Result<u16>;
+}
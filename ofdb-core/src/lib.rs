@@ -13,11 +13,12 @@ pub mod entities {
     #[cfg(test)]
     pub use ofdb_entities::builders::*;
     pub use ofdb_entities::{
-        activity::*, address::*, category::*, clearance::*, comment::*, contact::*, email::*,
-        event::*, geo::*, id::*, links::*, location::*, nonce::*, organization::*, password::*,
-        place::*, rating::*, review::*, revision::*, subscription::*, tag::*, time::*, url::Url,
-        user::*,
+        activity::*, address::*, api_token::*, category::*, change::*, clearance::*, comment::*,
+        contact::*, duplicate::*, email::*, event::*, geo::*, id::*, links::*, location::*,
+        nonce::*, organization::*, password::*, place::*, rating::*, review::*, revision::*,
+        subscription::*, tag::*, time::*, translation::*, url::Url, user::*, webhook::*,
     };
+    pub use ofdb_entities::{opening_hours, recurrence};
 }
 
 pub use repositories::Error as RepoError;
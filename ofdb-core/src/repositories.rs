@@ -33,6 +33,9 @@ pub trait CommentRepository {
     fn load_comments(&self, id: &[&str]) -> Result<Vec<Comment>>;
     fn load_comments_of_rating(&self, rating_id: &str) -> Result<Vec<Comment>>;
 
+    // All comments, archived or not
+    fn load_all_comments_of_rating(&self, rating_id: &str) -> Result<Vec<Comment>>;
+
     // Only unarchived comments (even if the rating has already been archived)
     fn zip_ratings_with_comments(
         &self,
@@ -65,10 +68,16 @@ pub trait RatingRepository {
     fn load_ratings(&self, ids: &[&str]) -> Result<Vec<Rating>>;
     fn load_ratings_of_place(&self, place_id: &str) -> Result<Vec<Rating>>;
 
+    // All ratings without comments, archived or not
+    fn load_all_ratings_of_place(&self, place_id: &str) -> Result<Vec<Rating>>;
+
     fn archive_ratings(&self, ids: &[&str], activity: &Activity) -> Result<usize>;
     fn archive_ratings_of_places(&self, place_ids: &[&str], activity: &Activity) -> Result<usize>;
 
     fn load_place_ids_of_ratings(&self, ids: &[&str]) -> Result<Vec<String>>;
+
+    // All ratings including their comments, archived or not
+    fn move_ratings_of_place(&self, from_place_id: &Id, to_place_id: &Id) -> Result<usize>;
 }
 
 pub trait UserTokenRepo {
@@ -87,6 +96,21 @@ pub trait ReviewTokenRepo {
     fn delete_expired_review_tokens(&self, expired_before: Timestamp) -> Result<usize>;
 }
 
+// Revoked (authentication) tokens are identified by an opaque id
+// and only need to be remembered until they would expire anyway.
+pub trait RevokedTokenRepo {
+    fn add_revoked_token(&self, id: &str, expires_at: Timestamp) -> Result<()>;
+    fn is_token_revoked(&self, id: &str) -> Result<bool>;
+    fn delete_expired_revoked_tokens(&self, expired_before: Timestamp) -> Result<usize>;
+}
+
+// The changes of places, events and ratings are logged by the
+// database within the same transaction.
+pub trait ChangeRepo {
+    /// Load the changes after the given cursor in commit order.
+    fn load_changes(&self, after: Option<&ChangeCursor>, limit: u64) -> Result<Vec<Change>>;
+}
+
 #[derive(Clone, Debug, Copy, Default, PartialEq, Eq, Hash)]
 pub struct Pagination {
     pub offset: Option<u64>,
@@ -113,6 +137,14 @@ pub trait PlaceRepo {
     fn all_places(&self) -> Result<Vec<(Place, ReviewStatus)>>; // TODO: remove
     fn count_places(&self) -> Result<usize>;
 
+    // Backends with a spatial index should override the default
+    // implementation that filters all places.
+    fn all_places_in_bbox(&self, bbox: &MapBbox) -> Result<Vec<(Place, ReviewStatus)>> {
+        let mut places = self.all_places()?;
+        places.retain(|(place, _)| bbox.contains_point(place.location.pos));
+        Ok(places)
+    }
+
     fn recently_changed_places(
         &self,
         params: &RecentlyChangedEntriesParams,
@@ -148,8 +180,48 @@ pub trait PlaceRepo {
     fn load_place_revision(&self, id: &str, rev: Revision) -> Result<(Place, ReviewStatus)>;
 }
 
+// Merged places redirect to the surviving place
+pub trait PlaceRedirectRepo {
+    // Existing redirects to the merged place are updated
+    // to point to the surviving place
+    fn create_place_redirect(&self, merged_place_id: &Id, place_id: &Id) -> Result<()>;
+    fn resolve_place_redirect(&self, id: &Id) -> Result<Option<Id>>;
+}
+
+pub trait DuplicateCandidateRepo {
+    // New candidates are added and the similarity of open candidates
+    // is updated. Open candidates that have not been detected again
+    // are removed. Returns the number of new candidates.
+    fn replace_open_duplicate_candidates(&self, candidates: &[DuplicateCandidate])
+    -> Result<usize>;
+    fn count_open_duplicate_candidates(&self) -> Result<usize>;
+    // Ordered by descending similarity
+    fn list_open_duplicate_candidates(
+        &self,
+        pagination: &Pagination,
+    ) -> Result<Vec<DuplicateCandidate>>;
+    // Only open candidates could be reviewed. Returns the number
+    // of reviewed candidates, i.e. either 0 or 1.
+    fn review_duplicate_candidate(
+        &self,
+        place_id: &Id,
+        duplicate_place_id: &Id,
+        state: DuplicateCandidateState,
+        activity: &Activity,
+    ) -> Result<usize>;
+}
+
 pub trait OrganizationRepo {
     fn create_org(&mut self, org: Organization) -> Result<()>;
+    /// Update the name and replace the moderated tags.
+    ///
+    /// The API token of the organization is not modified.
+    fn update_org(&mut self, org: Organization) -> Result<()>;
+    /// Delete the organization together with its moderated tags,
+    /// members, pending and recorded clearances, API tokens, and webhooks.
+    fn delete_org(&mut self, id: &Id) -> Result<()>;
+    fn get_org_by_id(&self, id: &Id) -> Result<Organization>;
+    fn all_orgs(&self) -> Result<Vec<Organization>>;
     fn get_org_by_api_token(&self, token: &str) -> Result<Organization>;
     fn map_tag_to_clearance_org_id(&self, tag: &str) -> Result<Option<Id>>;
     fn get_moderated_tags_by_org(
@@ -158,6 +230,55 @@ pub trait OrganizationRepo {
     ) -> Result<Vec<(Id, ModeratedTag)>>;
 }
 
+pub trait ApiTokenRepo {
+    fn create_api_token(&self, api_token: &ApiToken) -> Result<()>;
+    fn get_api_token_by_hash(&self, token_hash: &str) -> Result<ApiToken>;
+    fn list_api_tokens_of_org(&self, org_id: &Id) -> Result<Vec<ApiToken>>;
+    fn revoke_api_token(&self, org_id: &Id, id: &Id, revoked_at: Timestamp) -> Result<()>;
+    fn update_api_token_last_used(&self, id: &Id, last_used_at: Timestamp) -> Result<()>;
+}
+
+pub trait OrganizationMemberRepo {
+    /// Add a new member or change the role of an existing member.
+    fn add_or_update_org_member(&self, member: &OrganizationMember) -> Result<()>;
+    fn remove_org_member(&self, org_id: &Id, email: &EmailAddress) -> Result<()>;
+    fn list_org_members(&self, org_id: &Id) -> Result<Vec<OrganizationMember>>;
+    fn list_org_memberships_of_user(&self, email: &EmailAddress)
+    -> Result<Vec<OrganizationMember>>;
+}
+
+pub trait WebhookRepo {
+    fn create_webhook(&self, webhook: &Webhook) -> Result<()>;
+    fn get_webhook(&self, id: &Id) -> Result<Webhook>;
+    fn list_webhooks_of_org(&self, org_id: &Id) -> Result<Vec<Webhook>>;
+    /// Delete the webhook together with all its deliveries.
+    fn delete_webhook(&self, org_id: &Id, id: &Id) -> Result<()>;
+
+    fn create_webhook_delivery(&self, delivery: &WebhookDelivery) -> Result<()>;
+    fn update_webhook_delivery(&self, delivery: &WebhookDelivery) -> Result<()>;
+    /// Postpone the next attempt of a pending delivery unless it has
+    /// been modified concurrently since it was loaded.
+    ///
+    /// Returns `false` if the delivery could not be claimed.
+    fn claim_webhook_delivery(
+        &self,
+        delivery: &WebhookDelivery,
+        claimed_until: Timestamp,
+    ) -> Result<bool>;
+    /// Load pending deliveries that are due, oldest first.
+    fn load_due_webhook_deliveries(
+        &self,
+        due_at: Timestamp,
+        limit: u64,
+    ) -> Result<Vec<WebhookDelivery>>;
+    /// List the deliveries of a webhook, most recent first.
+    fn list_webhook_deliveries(
+        &self,
+        webhook_id: &Id,
+        pagination: &Pagination,
+    ) -> Result<Vec<WebhookDelivery>>;
+}
+
 pub trait PlaceClearanceRepo {
     fn add_pending_clearance_for_places(
         &self,
@@ -175,23 +296,86 @@ pub trait PlaceClearanceRepo {
         org_id: &Id,
         place_ids: &[&str],
     ) -> Result<Vec<PendingClearanceForPlace>>;
+    /// Update the pending clearances and record each
+    /// clearance with the given activity.
     fn update_pending_clearances_for_places(
         &self,
         org_id: &Id,
         clearances: &[ClearanceForPlace],
+        activity: &ActivityLog,
     ) -> Result<usize>;
     fn cleanup_pending_clearances_for_places(&self, org_id: &Id) -> Result<u64>;
+    /// List the recorded clearances, most recent first.
+    fn list_clearance_log_for_places(
+        &self,
+        org_id: &Id,
+        pagination: &Pagination,
+    ) -> Result<Vec<ClearanceLogForPlace>>;
+    /// Record a clearance without updating the pending clearances.
+    fn add_clearance_log_for_places(&self, org_id: &Id, log: &ClearanceLogForPlace) -> Result<()>;
+}
+
+pub trait EventClearanceRepo {
+    fn add_pending_clearance_for_events(
+        &self,
+        org_ids: &[Id],
+        pending_clearance: &PendingClearanceForEvent,
+    ) -> Result<usize>;
+    fn count_pending_clearances_for_events(&self, org_id: &Id) -> Result<u64>;
+    fn list_pending_clearances_for_events(
+        &self,
+        org_id: &Id,
+        pagination: &Pagination,
+    ) -> Result<Vec<PendingClearanceForEvent>>;
+    fn load_pending_clearances_for_events(
+        &self,
+        org_id: &Id,
+        event_ids: &[&str],
+    ) -> Result<Vec<PendingClearanceForEvent>>;
+    /// Update the pending clearances and record each
+    /// clearance with the given activity.
+    fn update_pending_clearances_for_events(
+        &self,
+        org_id: &Id,
+        clearances: &[ClearanceForEvent],
+        activity: &ActivityLog,
+    ) -> Result<usize>;
+    fn cleanup_pending_clearances_for_events(&self, org_id: &Id) -> Result<u64>;
+    /// List the recorded clearances, most recent first.
+    fn list_clearance_log_for_events(
+        &self,
+        org_id: &Id,
+        pagination: &Pagination,
+    ) -> Result<Vec<ClearanceLogForEvent>>;
+    /// Record a clearance without updating the pending clearances.
+    fn add_clearance_log_for_events(&self, org_id: &Id, log: &ClearanceLogForEvent) -> Result<()>;
 }
 
 pub trait EventRepo {
-    fn create_event(&self, event: Event) -> Result<()>;
-    fn update_event(&self, event: &Event) -> Result<()>;
+    // Both create and update store a new revision of the event
+    // that is logged with the given activity.
+    fn create_event(&self, event: Event, activity: &ActivityLog) -> Result<()>;
+    fn update_event(&self, event: &Event, activity: &ActivityLog) -> Result<()>;
     fn archive_events(&self, ids: &[&str], archived: Timestamp) -> Result<usize>;
 
     fn get_event(&self, id: &str) -> Result<Event>;
     fn get_events_chronologically(&self, ids: &[&str]) -> Result<Vec<Event>>;
 
     fn all_events_chronologically(&self) -> Result<Vec<Event>>;
+    fn all_archived_events_chronologically(&self) -> Result<Vec<Event>>;
+
+    // Events without a location are never contained. Backends with a
+    // spatial index should override the default implementation.
+    fn all_events_in_bbox_chronologically(&self, bbox: &MapBbox) -> Result<Vec<Event>> {
+        let mut events = self.all_events_chronologically()?;
+        events.retain(|event| {
+            event
+                .location
+                .as_ref()
+                .is_some_and(|location| bbox.contains_point(location.pos))
+        });
+        Ok(events)
+    }
 
     fn count_events(&self) -> Result<usize>;
 
@@ -203,6 +387,12 @@ pub trait EventRepo {
     fn delete_event_with_matching_tags(&self, id: &str, tags: &[&str]) -> Result<bool>;
 
     fn is_event_owned_by_any_organization(&self, id: &str) -> Result<bool>;
+
+    // TODO: use typed ID
+    fn get_event_history(&self, id: &str, revision: Option<Revision>) -> Result<EventHistory>;
+
+    // TODO: use typed ID
+    fn load_event_revision(&self, id: &str, rev: Revision) -> Result<Event>;
 }
 
 pub trait UserRepo {
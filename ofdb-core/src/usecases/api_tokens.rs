@@ -0,0 +1,264 @@
+use time::Duration;
+
+use super::prelude::*;
+use crate::repositories::Error as RepoError;
+
+/// The usage of an API token is recorded at most once per interval.
+pub const API_TOKEN_USAGE_INTERVAL: Duration = Duration::minutes(1);
+
+#[rustfmt::skip]
+#[derive(Debug, Clone)]
+pub struct NewApiToken {
+    pub name       : String,
+    pub scopes     : Vec<ApiTokenScope>,
+    pub expires_at : Option<Timestamp>,
+}
+
+/// Create a new API token for an organization.
+///
+/// Returns the stored token together with the secret that
+/// is only available once, because only its hash is stored.
+pub fn create_api_token<R>(
+    repo: &R,
+    org_id: &Id,
+    new_token: NewApiToken,
+    created_at: Timestamp,
+) -> Result<(ApiToken, String)>
+where
+    R: OrganizationRepo + ApiTokenRepo,
+{
+    let NewApiToken {
+        name,
+        mut scopes,
+        expires_at,
+    } = new_token;
+    let name = name.trim().to_owned();
+    if name.is_empty() {
+        return Err(Error::ApiTokenName);
+    }
+    scopes.sort_unstable();
+    scopes.dedup();
+    if scopes.is_empty() {
+        return Err(Error::ApiTokenScopes);
+    }
+    if expires_at.is_some_and(|expires_at| expires_at <= created_at) {
+        return Err(Error::TokenExpired);
+    }
+    let org = repo.get_org_by_id(org_id)?;
+    let secret = ApiToken::generate_secret();
+    let api_token = ApiToken {
+        id: Id::new(),
+        org_id: org.id,
+        name,
+        token_hash: ApiToken::hash_secret(&secret),
+        scopes,
+        created_at,
+        expires_at,
+        last_used_at: None,
+        revoked_at: None,
+    };
+    repo.create_api_token(&api_token)?;
+    Ok((api_token, secret))
+}
+
+pub fn list_api_tokens<R>(repo: &R, org_id: &Id) -> Result<Vec<ApiToken>>
+where
+    R: OrganizationRepo + ApiTokenRepo,
+{
+    let org = repo.get_org_by_id(org_id)?;
+    Ok(repo.list_api_tokens_of_org(&org.id)?)
+}
+
+pub fn revoke_api_token<R>(repo: &R, org_id: &Id, id: &Id, revoked_at: Timestamp) -> Result<()>
+where
+    R: ApiTokenRepo,
+{
+    Ok(repo.revoke_api_token(org_id, id, revoked_at)?)
+}
+
+/// Find the first valid API token that matches one of the given secrets.
+pub fn find_valid_api_token<R>(
+    repo: &R,
+    secrets: &[String],
+    at: Timestamp,
+) -> Result<Option<ApiToken>>
+where
+    R: ApiTokenRepo,
+{
+    for secret in secrets {
+        match repo.get_api_token_by_hash(&ApiToken::hash_secret(secret)) {
+            Ok(api_token) if api_token.is_valid_at(at) => return Ok(Some(api_token)),
+            Ok(_) | Err(RepoError::NotFound) => (),
+            Err(e) => return Err(Error::Repo(e)),
+        }
+    }
+    Ok(None)
+}
+
+/// Check if a usage of the API token needs to be recorded.
+///
+/// Frequent usages are not recorded to avoid a write
+/// access to the database for every request.
+pub fn is_api_token_usage_outdated(api_token: &ApiToken, used_at: Timestamp) -> bool {
+    api_token
+        .last_used_at
+        .is_none_or(|last_used_at| last_used_at + API_TOKEN_USAGE_INTERVAL <= used_at)
+}
+
+pub fn record_api_token_usage<R>(repo: &R, id: &Id, used_at: Timestamp) -> Result<()>
+where
+    R: ApiTokenRepo,
+{
+    Ok(repo.update_api_token_last_used(id, used_at)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::tests::MockDb, *};
+    use crate::usecases::authorize_organization_by_possible_api_tokens as authorize;
+
+    fn db() -> MockDb {
+        MockDb {
+            orgs: vec![Organization {
+                id: "foo".into(),
+                name: "foo".into(),
+                api_token: "legacy".into(),
+                moderated_tags: vec![],
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn new_token(scopes: Vec<ApiTokenScope>) -> NewApiToken {
+        NewApiToken {
+            name: "import".into(),
+            scopes,
+            expires_at: None,
+        }
+    }
+
+    #[test]
+    fn create_with_invalid_parameters() {
+        let db = db();
+        let now = Timestamp::now();
+        let org_id = Id::from("foo");
+        let token = NewApiToken {
+            name: " ".into(),
+            ..new_token(vec![ApiTokenScope::EventsRead])
+        };
+        assert!(matches!(
+            create_api_token(&db, &org_id, token, now),
+            Err(Error::ApiTokenName)
+        ));
+        assert!(matches!(
+            create_api_token(&db, &org_id, new_token(vec![]), now),
+            Err(Error::ApiTokenScopes)
+        ));
+        assert!(matches!(
+            create_api_token(
+                &db,
+                &"bar".into(),
+                new_token(vec![ApiTokenScope::EventsRead]),
+                now
+            ),
+            Err(Error::Repo(RepoError::NotFound))
+        ));
+        assert!(db.api_tokens.borrow().is_empty());
+    }
+
+    #[test]
+    fn authorize_with_scopes() {
+        let db = db();
+        let org_id = Id::from("foo");
+        let scopes = vec![ApiTokenScope::EventsWrite, ApiTokenScope::EventsWrite];
+        let (api_token, secret) =
+            create_api_token(&db, &org_id, new_token(scopes), Timestamp::now()).unwrap();
+        assert_eq!(api_token.scopes, vec![ApiTokenScope::EventsWrite]);
+        assert_ne!(api_token.token_hash, secret);
+
+        let tokens = vec![secret];
+        let (org, used_token) = authorize(&db, &tokens, ApiTokenScope::EventsWrite).unwrap();
+        assert_eq!(org.id, org_id);
+        assert_eq!(used_token.map(|t| t.id), Some(api_token.id));
+        assert!(matches!(
+            authorize(&db, &tokens, ApiTokenScope::ExportCsv),
+            Err(Error::Forbidden)
+        ));
+        assert!(matches!(
+            authorize(&db, &["unknown".to_string()], ApiTokenScope::EventsWrite),
+            Err(Error::Unauthorized)
+        ));
+        // The API token of the organization grants only the scopes
+        // that have been available before named API tokens
+        assert!(authorize(&db, &["legacy".to_string()], ApiTokenScope::ExportCsv).is_ok());
+        assert!(matches!(
+            authorize(&db, &["legacy".to_string()], ApiTokenScope::ExportGeoJson),
+            Err(Error::Forbidden)
+        ));
+    }
+
+    #[test]
+    fn reject_revoked_and_expired_tokens() {
+        let db = db();
+        let org_id = Id::from("foo");
+        let now = Timestamp::now();
+        let (revoked, revoked_secret) = create_api_token(
+            &db,
+            &org_id,
+            new_token(vec![ApiTokenScope::EventsRead]),
+            now,
+        )
+        .unwrap();
+        revoke_api_token(&db, &org_id, &revoked.id, now).unwrap();
+        assert!(matches!(
+            authorize(&db, &[revoked_secret], ApiTokenScope::EventsRead),
+            Err(Error::Unauthorized)
+        ));
+
+        let (_, expired_secret) = create_api_token(
+            &db,
+            &org_id,
+            NewApiToken {
+                expires_at: Some(now + Duration::milliseconds(1)),
+                ..new_token(vec![ApiTokenScope::EventsRead])
+            },
+            now,
+        )
+        .unwrap();
+        let later = now + Duration::seconds(1);
+        assert!(
+            find_valid_api_token(&db, &[expired_secret], later)
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn record_usage() {
+        let db = db();
+        let org_id = Id::from("foo");
+        let now = Timestamp::now();
+        let (api_token, secret) = create_api_token(
+            &db,
+            &org_id,
+            new_token(vec![ApiTokenScope::EventsRead]),
+            now,
+        )
+        .unwrap();
+        let found = find_valid_api_token(&db, &[secret], now).unwrap().unwrap();
+        assert_eq!(found.id, api_token.id);
+        assert!(is_api_token_usage_outdated(&found, now));
+        record_api_token_usage(&db, &found.id, now).unwrap();
+        let api_tokens = list_api_tokens(&db, &org_id).unwrap();
+        assert_eq!(api_tokens.len(), 1);
+        assert_eq!(api_tokens[0].last_used_at, Some(now));
+        assert!(!is_api_token_usage_outdated(
+            &api_tokens[0],
+            now + Duration::seconds(59)
+        ));
+        assert!(is_api_token_usage_outdated(
+            &api_tokens[0],
+            now + API_TOKEN_USAGE_INTERVAL
+        ));
+    }
+}
@@ -1,23 +1,97 @@
 use super::prelude::*;
 use crate::repositories::Error as RepoError;
 
+/// The scopes that are granted by the legacy API token of an organization.
+///
+/// These are the operations that have been permitted before the
+/// introduction of named API tokens. New scopes are not included.
+const LEGACY_API_TOKEN_SCOPES: &[ApiTokenScope] = &[
+    ApiTokenScope::PlacesWrite,
+    ApiTokenScope::EventsRead,
+    ApiTokenScope::EventsWrite,
+    ApiTokenScope::HistoryRead,
+    ApiTokenScope::ClearanceRead,
+    ApiTokenScope::ClearanceWrite,
+    ApiTokenScope::ExportCsv,
+    ApiTokenScope::ExportIcal,
+];
+
+/// Authorize an organization by one of the given bearer tokens.
+///
+/// The legacy API token of an organization grants a fixed set of scopes.
+/// Named API tokens must be valid and include the requested scope.
+///
+/// Returns the named API token that has been used, if any.
 pub fn authorize_organization_by_possible_api_tokens<R>(
     repo: &R,
     tokens: &[String],
-) -> Result<Organization>
+    scope: ApiTokenScope,
+) -> Result<(Organization, Option<ApiToken>)>
 where
-    R: UserRepo + OrganizationRepo,
+    R: OrganizationRepo + ApiTokenRepo,
 {
+    let mut missing_scope = false;
     for token in tokens {
         match repo.get_org_by_api_token(token) {
-            Ok(org) => return Ok(org),
+            Ok(org) if LEGACY_API_TOKEN_SCOPES.contains(&scope) => return Ok((org, None)),
+            Ok(_) => missing_scope = true,
             Err(RepoError::NotFound) => (),
             Err(e) => return Err(Error::Repo(e)),
         }
     }
+    let now = Timestamp::now();
+    for token in tokens {
+        let Some(api_token) = super::find_valid_api_token(repo, std::slice::from_ref(token), now)?
+        else {
+            continue;
+        };
+        if !api_token.has_scope(scope) {
+            missing_scope = true;
+            continue;
+        }
+        let org = repo.get_org_by_id(&api_token.org_id)?;
+        return Ok((org, Some(api_token)));
+    }
+    if missing_scope {
+        return Err(Error::Forbidden);
+    }
     Err(Error::Unauthorized)
 }
 
+/// Authorize an organization by the membership of a registered user.
+///
+/// The role of the user within the organization must grant the
+/// requested scope. Users that are members of multiple organizations
+/// must select one of them explicitly.
+pub fn authorize_organization_by_member<R>(
+    repo: &R,
+    email: &EmailAddress,
+    org_id: Option<&Id>,
+    scope: ApiTokenScope,
+) -> Result<Organization>
+where
+    R: OrganizationRepo + OrganizationMemberRepo,
+{
+    let memberships: Vec<_> = repo
+        .list_org_memberships_of_user(email)?
+        .into_iter()
+        .filter(|member| org_id.is_none_or(|org_id| member.org_id == *org_id))
+        .collect();
+    if memberships.is_empty() {
+        return Err(Error::Unauthorized);
+    }
+    let mut granted = memberships
+        .into_iter()
+        .filter(|member| member.role.grants(scope));
+    let Some(member) = granted.next() else {
+        return Err(Error::Forbidden);
+    };
+    if granted.next().is_some() {
+        return Err(Error::AmbiguousOrganization);
+    }
+    Ok(repo.get_org_by_id(&member.org_id)?)
+}
+
 pub fn authorize_user_by_email<R>(
     repo: &R,
     email: &EmailAddress,
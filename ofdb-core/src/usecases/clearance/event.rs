@@ -0,0 +1,109 @@
+use crate::usecases::prelude::*;
+use std::collections::HashMap;
+
+pub(crate) fn add_pending_clearance<R: EventClearanceRepo>(
+    repo: &R,
+    org_ids: &[Id],
+    pending_clearance: &PendingClearanceForEvent,
+) -> Result<usize> {
+    Ok(repo.add_pending_clearance_for_events(org_ids, pending_clearance)?)
+}
+
+pub fn count_pending_clearances<R: OrganizationRepo + EventClearanceRepo>(
+    repo: &R,
+    org: &Organization,
+) -> Result<u64> {
+    Ok(repo.count_pending_clearances_for_events(&org.id)?)
+}
+
+pub fn list_pending_clearances<R: OrganizationRepo + EventClearanceRepo>(
+    repo: &R,
+    org: &Organization,
+    pagination: &Pagination,
+) -> Result<Vec<PendingClearanceForEvent>> {
+    Ok(repo.list_pending_clearances_for_events(&org.id, pagination)?)
+}
+
+/// Clear pending changes of events on behalf of an organization.
+///
+/// The clearance log records `cleared_by` as the author of each
+/// clearance, or no author if an API token has been used.
+pub fn update_pending_clearances<R: OrganizationRepo + EventClearanceRepo>(
+    repo: &R,
+    org: &Organization,
+    clearances: &[ClearanceForEvent],
+    cleared_by: Option<EmailAddress>,
+) -> Result<usize> {
+    let activity = ActivityLog {
+        activity: Activity::now(cleared_by),
+        context: None,
+        comment: None,
+    };
+    let count = repo.update_pending_clearances_for_events(&org.id, clearances, &activity)?;
+    log::info!(
+        "Updated {} of {} pending clearance(s) for events on behalf of organization '{}'",
+        count,
+        clearances.len(),
+        org.name
+    );
+    repo.cleanup_pending_clearances_for_events(&org.id)?;
+    Ok(count)
+}
+
+pub fn list_clearance_log<R: OrganizationRepo + EventClearanceRepo>(
+    repo: &R,
+    org: &Organization,
+    pagination: &Pagination,
+) -> Result<Vec<ClearanceLogForEvent>> {
+    Ok(repo.list_clearance_log_for_events(&org.id, pagination)?)
+}
+
+pub fn clear_repo_results<R: EventRepo + EventClearanceRepo>(
+    repo: &R,
+    org_id: &Id,
+    org_tag: &str,
+    results: Vec<Event>,
+) -> Result<Vec<Event>> {
+    let event_ids: Vec<_> = results.iter().map(|e| e.id.as_str()).collect();
+    let pending_clearances = repo.load_pending_clearances_for_events(org_id, &event_ids)?;
+    if pending_clearances.is_empty() {
+        // No filtering required
+        return Ok(results);
+    }
+    let pending_clearances: HashMap<_, _> = pending_clearances
+        .into_iter()
+        .map(|p| (p.event_id.to_string(), p))
+        .collect();
+    let mut cleared_results = Vec::with_capacity(results.len());
+    for mut event in results.into_iter() {
+        let pending_clearance = pending_clearances.get(event.id.as_str());
+        if let Some(pending_clearance) = pending_clearance {
+            if let Some(last_cleared_revision) = &pending_clearance.last_cleared_revision {
+                let last_cleared_event =
+                    repo.load_event_revision(event.id.as_str(), *last_cleared_revision)?;
+                debug_assert_eq!(*last_cleared_revision, last_cleared_event.revision);
+                if !last_cleared_event
+                    .tags
+                    .iter()
+                    .map(String::as_str)
+                    .any(|tag| tag == org_tag)
+                {
+                    // Remove previously untagged events from the result
+                    continue;
+                }
+                // Archiving is independent of the revision
+                let archived = event.archived;
+                // Replace the actual/current result item with the last cleared revision
+                event = Event {
+                    archived,
+                    ..last_cleared_event
+                };
+            } else {
+                // Skip newly created but not yet cleared entry
+                continue;
+            }
+        }
+        cleared_results.push(event);
+    }
+    Ok(cleared_results)
+}
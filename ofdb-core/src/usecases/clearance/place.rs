@@ -24,12 +24,22 @@ pub fn list_pending_clearances<R: OrganizationRepo + PlaceClearanceRepo>(
     Ok(repo.list_pending_clearances_for_places(&org.id, pagination)?)
 }
 
+/// Clear pending changes of places on behalf of an organization.
+///
+/// The clearance log records `cleared_by` as the author of each
+/// clearance, or no author if an API token has been used.
 pub fn update_pending_clearances<R: OrganizationRepo + PlaceClearanceRepo>(
     repo: &R,
     org: &Organization,
     clearances: &[ClearanceForPlace],
+    cleared_by: Option<EmailAddress>,
 ) -> Result<usize> {
-    let count = repo.update_pending_clearances_for_places(&org.id, clearances)?;
+    let activity = ActivityLog {
+        activity: Activity::now(cleared_by),
+        context: None,
+        comment: None,
+    };
+    let count = repo.update_pending_clearances_for_places(&org.id, clearances, &activity)?;
     log::info!(
         "Updated {} of {} pending clearance(s) for places on behalf of organization '{}'",
         count,
@@ -40,6 +50,14 @@ pub fn update_pending_clearances<R: OrganizationRepo + PlaceClearanceRepo>(
     Ok(count)
 }
 
+pub fn list_clearance_log<R: OrganizationRepo + PlaceClearanceRepo>(
+    repo: &R,
+    org: &Organization,
+    pagination: &Pagination,
+) -> Result<Vec<ClearanceLogForPlace>> {
+    Ok(repo.list_clearance_log_for_places(&org.id, pagination)?)
+}
+
 pub fn clear_repo_results<R: PlaceRepo + PlaceClearanceRepo>(
     repo: &R,
     org_id: &Id,
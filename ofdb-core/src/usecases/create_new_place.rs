@@ -2,7 +2,10 @@ use std::collections::HashSet;
 
 use time::Date;
 
-use super::{CustomLinkParam, parse_custom_link_param};
+use super::{
+    CustomLinkParam, TranslationParam, parse_custom_link_param, parse_opening_hours_param,
+    parse_translation_params,
+};
 use crate::{
     usecases::{authorize, prelude::*},
     util::{parse::parse_url_param, validate::Validate},
@@ -43,6 +46,7 @@ pub struct NewPlace {
     pub image_url      : Option<String>,
     pub image_link_url : Option<String>,
     pub custom_links   : Vec<CustomLinkParam>,
+    pub translations   : Vec<TranslationParam>,
 }
 
 #[derive(Debug, Clone)]
@@ -51,6 +55,13 @@ pub struct StorablePlace {
     clearance_org_ids: Vec<Id>,
 }
 
+impl StorablePlace {
+    /// The organizations that need to clear the stored changes.
+    pub fn clearance_org_ids(&self) -> &[Id] {
+        &self.clearance_org_ids
+    }
+}
+
 pub fn prepare_new_place<R>(
     repo: &R,
     e: NewPlace,
@@ -83,6 +94,7 @@ where
         image_url,
         image_link_url,
         custom_links: custom_links_param,
+        translations,
     } = e;
     let pos = MapPoint::try_from_lat_lng_deg(lat, lng).map_err(|_| Error::InvalidPosition)?;
 
@@ -159,11 +171,12 @@ where
         location,
         contact,
         opening_hours: opening_hours
-            .map(|s| s.parse().map_err(|_| Error::InvalidOpeningHours))
+            .map(|s| parse_opening_hours_param(&s, None))
             .transpose()?,
         founded_on,
         links,
         tags: new_tags,
+        translations: parse_translation_params(translations)?,
     };
     place.validate()?;
     if !accepted_licenses.contains(&place.license) {
@@ -235,6 +248,7 @@ mod tests {
             image_url     : None,
             image_link_url: None,
             custom_links: vec![],
+            translations: vec![],
         };
         let mock_db = MockDb::default();
         let now = Timestamp::now();
@@ -282,6 +296,7 @@ mod tests {
             image_url     : None,
             image_link_url: None,
             custom_links: vec![],
+            translations: vec![],
         };
         let mock_db: MockDb = MockDb::default();
         assert!(prepare_new_place(&mock_db, x, None, None, &accepted_licenses()).is_err());
@@ -312,6 +327,7 @@ mod tests {
             image_url     : None,
             image_link_url: None,
             custom_links: vec![],
+            translations: vec![],
         };
         let mock_db = MockDb::default();
         let e = prepare_new_place(&mock_db, x, None, None, &accepted_licenses()).unwrap();
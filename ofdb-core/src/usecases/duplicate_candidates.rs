@@ -0,0 +1,103 @@
+use std::collections::{BTreeSet, HashSet};
+
+use super::prelude::*;
+use crate::repositories::Error as RepoError;
+
+/// Detect possible duplicates among all visible places.
+///
+/// Each pair of places is only reported once. The score of
+/// the duplicate is stored as the similarity of the pair.
+///
+/// Nearby places that are not contained in `places` are
+/// loaded by `get_places`.
+pub fn detect_duplicate_candidates<F>(
+    place_index: &dyn PlaceIndex,
+    mut places: Vec<(Place, ReviewStatus)>,
+    get_places: F,
+    detected_at: Timestamp,
+) -> Result<Vec<DuplicateCandidate>>
+where
+    F: FnMut(&[&str]) -> std::result::Result<Vec<(Place, ReviewStatus)>, RepoError>,
+{
+    places.retain(|(_, status)| status.exists());
+    let duplicates = super::find_duplicates_with(place_index, &places, get_places)?;
+    let visible_ids: HashSet<_> = places.iter().map(|(place, _)| place.id.as_str()).collect();
+    let mut pairs = BTreeSet::new();
+    let mut candidates = Vec::new();
+    for (lhs, rhs, duplicate) in duplicates {
+        // The search index might not be in sync with the database
+        if !visible_ids.contains(lhs.as_str()) || !visible_ids.contains(rhs.as_str()) {
+            continue;
+        }
+        let (place_id, duplicate_place_id) = DuplicateCandidate::ordered_pair(lhs, rhs);
+        if !pairs.insert((place_id.clone(), duplicate_place_id.clone())) {
+            continue;
+        }
+        candidates.push(DuplicateCandidate {
+            place_id,
+            duplicate_place_id,
+            similarity: duplicate.score,
+            detected_at,
+            state: DuplicateCandidateState::Open,
+            reviewed: None,
+        });
+    }
+    Ok(candidates)
+}
+
+/// Mark an open pair of places as not being duplicates.
+pub fn dismiss_duplicate_candidate<R>(
+    repo: &R,
+    place_id: Id,
+    duplicate_place_id: Id,
+    dismissed_by: EmailAddress,
+) -> Result<()>
+where
+    R: DuplicateCandidateRepo,
+{
+    let (place_id, duplicate_place_id) =
+        DuplicateCandidate::ordered_pair(place_id, duplicate_place_id);
+    let count = repo.review_duplicate_candidate(
+        &place_id,
+        &duplicate_place_id,
+        DuplicateCandidateState::Dismissed,
+        &Activity::now(Some(dismissed_by)),
+    )?;
+    if count == 0 {
+        return Err(RepoError::NotFound.into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::tests::MockDb, *};
+
+    fn open_candidate(place_id: &str, duplicate_place_id: &str) -> DuplicateCandidate {
+        DuplicateCandidate {
+            place_id: place_id.into(),
+            duplicate_place_id: duplicate_place_id.into(),
+            similarity: 0.9,
+            detected_at: Timestamp::now(),
+            state: DuplicateCandidateState::Open,
+            reviewed: None,
+        }
+    }
+
+    #[test]
+    fn dismiss_open_candidates_only() {
+        let db = MockDb::default();
+        db.replace_open_duplicate_candidates(&[open_candidate("a", "b")])
+            .unwrap();
+        let email: EmailAddress = "scout@example.com".parse().unwrap();
+        // The order of the places doesn't matter
+        dismiss_duplicate_candidate(&db, "b".into(), "a".into(), email.clone()).unwrap();
+        assert_eq!(0, db.count_open_duplicate_candidates().unwrap());
+        assert!(dismiss_duplicate_candidate(&db, "a".into(), "b".into(), email).is_err());
+
+        // Dismissed candidates are not reopened
+        db.replace_open_duplicate_candidates(&[open_candidate("a", "b")])
+            .unwrap();
+        assert_eq!(0, db.count_open_duplicate_candidates().unwrap());
+    }
+}
@@ -1,7 +1,9 @@
 use crate::{
     repositories,
     usecases::ReviewPlaceWithNonceError,
-    util::validate::{ContactInvalidation, EventInvalidation, PlaceInvalidation},
+    util::validate::{
+        ContactInvalidation, EventInvalidation, OpeningHoursInvalidation, PlaceInvalidation,
+    },
 };
 use thiserror::Error;
 
@@ -47,22 +49,60 @@ pub enum Error {
     EndDateBeforeStart,
     #[error("The tag is owned by an organization")]
     ModeratedTag,
+    #[error("Invalid tag")]
+    Tag,
+    #[error("Invalid organization name")]
+    OrganizationName,
+    #[error("Invalid organization role")]
+    OrganizationRole,
+    #[error("The user is a member of multiple organizations")]
+    AmbiguousOrganization,
+    #[error("Only two different visible places could be merged")]
+    MergePlaces,
     #[error("Missing the email of the creator")]
     CreatorEmail,
     #[error("Invalid opening hours")]
     InvalidOpeningHours,
     #[error("Invalid position")]
     InvalidPosition,
+    #[error("Invalid radius")]
+    InvalidRadius,
+    #[error("Unsupported sort order")]
+    Sort,
+    #[error("Unsupported facet")]
+    Facet,
+    #[error("Invalid zoom level")]
+    Zoom,
     #[error("Invalid limit")]
     InvalidLimit,
+    #[error("Invalid local date and time")]
+    InvalidLocalDateTime,
+    #[error("Invalid recurrence rule")]
+    InvalidRecurrenceRule,
     #[error("Token invalid")]
     TokenInvalid,
     #[error("Token expired")]
     TokenExpired,
+    #[error("Invalid API token name")]
+    ApiTokenName,
+    #[error("Missing API token scopes")]
+    ApiTokenScopes,
+    #[error("Invalid API token scope")]
+    ApiTokenScope,
     #[error("Invalid nonce")]
     InvalidNonce,
+    #[error("Invalid change cursor")]
+    ChangeCursor,
     #[error("Missing id list")]
     EmptyIdList,
+    #[error("Unsupported image type")]
+    ImageType,
+    #[error("The image is too large")]
+    ImageSize,
+    #[error("Invalid image")]
+    Image,
+    #[error("Invalid or duplicate language")]
+    Language,
     #[error(transparent)]
     Repo(#[from] repositories::Error),
     #[error("Invalid or outdated place revision")]
@@ -81,6 +121,12 @@ impl From<ofdb_entities::event::RegistrationTypeParseError> for Error {
     }
 }
 
+impl From<ofdb_entities::recurrence::ParseError> for Error {
+    fn from(_: ofdb_entities::recurrence::ParseError) -> Self {
+        Self::InvalidRecurrenceRule
+    }
+}
+
 impl From<ofdb_entities::nonce::EmailNonceDecodingError> for Error {
     fn from(_: ofdb_entities::nonce::EmailNonceDecodingError) -> Self {
         Self::InvalidNonce
@@ -120,6 +166,14 @@ impl From<ContactInvalidation> for Error {
     }
 }
 
+impl From<OpeningHoursInvalidation> for Error {
+    fn from(err: OpeningHoursInvalidation) -> Self {
+        match err {
+            OpeningHoursInvalidation::Syntax(_) => Self::InvalidOpeningHours,
+        }
+    }
+}
+
 impl From<ReviewPlaceWithNonceError> for Error {
     fn from(err: ReviewPlaceWithNonceError) -> Self {
         match err {
@@ -2,53 +2,145 @@ use crate::{
     db,
     repositories::Error as RepoError,
     usecases::{create_new_place::NewPlace, prelude::*},
+    util::parse::parse_url_param,
 };
-use std::{cmp::min, collections::HashSet};
+use std::{
+    cmp::min,
+    collections::{HashMap, HashSet},
+};
+use url::Url;
 
-#[derive(Debug, PartialEq, Eq)]
+/// A reason why two places are considered as duplicates
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DuplicateType {
     SimilarChars,
     SimilarWords,
+    SamePhone,
+    SameEmail,
+    SameAddress,
+    SameHomepage,
+}
+
+impl DuplicateType {
+    // The contribution to the score of a duplicate
+    const fn weight(self) -> f64 {
+        match self {
+            Self::SimilarChars | Self::SimilarWords => 0.4,
+            Self::SamePhone | Self::SameEmail | Self::SameHomepage => 0.4,
+            Self::SameAddress => 0.3,
+        }
+    }
+}
+
+/// A possible duplicate of a place
+#[derive(Debug, Clone, PartialEq)]
+pub struct Duplicate {
+    /// From 0.0 (different) to 1.0 (most likely identical)
+    pub score: f64,
+    /// All contributing reasons ordered by decreasing weight
+    pub reasons: Vec<DuplicateType>,
 }
 
-// Return vector of places like: (entry1ID, entry2ID, reason)
+impl Duplicate {
+    /// The reason that contributed most to the score
+    pub fn main_reason(&self) -> DuplicateType {
+        self.reasons[0]
+    }
+}
+
+// Return vector of places like: (entry1ID, entry2ID, duplicate)
 // where entry1 and entry2 are similar places.
-pub fn find_duplicates(
+//
+// Nearby places that are not contained in `places` are loaded
+// from the repository for comparing their contact data.
+pub fn find_duplicates<R>(
+    repo: &R,
     place_index: &dyn PlaceIndex,
     places: &[(Place, ReviewStatus)],
-) -> Result<Vec<(Id, Id, DuplicateType)>> {
+) -> Result<Vec<(Id, Id, Duplicate)>>
+where
+    R: PlaceRepo,
+{
+    find_duplicates_with(place_index, places, |ids| repo.get_places(ids))
+}
+
+// Same as `find_duplicates()`, but nearby places are loaded
+// by the given function, e.g. to keep database locks short.
+pub fn find_duplicates_with<F>(
+    place_index: &dyn PlaceIndex,
+    places: &[(Place, ReviewStatus)],
+    mut get_places: F,
+) -> Result<Vec<(Id, Id, Duplicate)>>
+where
+    F: FnMut(&[&str]) -> std::result::Result<Vec<(Place, ReviewStatus)>, RepoError>,
+{
+    let known_places: HashMap<_, _> = places
+        .iter()
+        .map(|(place, _)| (place.id.as_str(), place))
+        .collect();
     let mut duplicates = Vec::new();
     for (p1, _) in places {
         let nearby_places = search_nearby_places(place_index, p1.location.pos)?;
-        for p2 in nearby_places {
-            if let Some(t) = is_duplicate(p1, &p2) {
-                duplicates.push((p1.id.clone(), Id::from(p2.id), t));
+        let mut unknown_ids = Vec::new();
+        for p2 in &nearby_places {
+            match known_places.get(p2.id.as_str()) {
+                Some(p2) => {
+                    if let Some(d) = is_duplicate(p1, p2) {
+                        duplicates.push((p1.id.clone(), p2.id.clone(), d));
+                    }
+                }
+                None => unknown_ids.push(p2.id.as_str()),
+            }
+        }
+        if unknown_ids.is_empty() {
+            continue;
+        }
+        for (p2, _) in get_places(&unknown_ids)? {
+            if let Some(d) = is_duplicate(p1, &p2) {
+                duplicates.push((p1.id.clone(), p2.id, d));
             }
         }
     }
     Ok(duplicates)
 }
 
-pub fn retain_duplicates_of(
-    nearby_places: Vec<IndexedPlace>,
-    new_place: &NewPlace,
-) -> Vec<IndexedPlace> {
-    nearby_places
-        .into_iter()
-        .filter(|p| is_duplicate_of(new_place, p))
-        .collect()
-}
-
 const MAX_NEARBY_RESULTS: usize = 1000;
 
 const MAX_NEARBY_RADIUS: Distance = Distance::from_meters(100.0);
 
-const MAX_NEARBY_DIAMETER: Distance = Distance::from_meters(MAX_NEARBY_RADIUS.to_meters() * 2.0);
+// Places at the same address are allowed to be located a bit farther
+// apart, e.g. if they have been geocoded differently.
+const MAX_SAME_ADDRESS_RADIUS: Distance = Distance::from_meters(250.0);
+
+const MAX_NEARBY_DIAMETER: Distance =
+    Distance::from_meters(MAX_SAME_ADDRESS_RADIUS.to_meters() * 2.0);
 
 const MAX_TEXT_RELATIVE_EDIT_DISTANCE: f64 = 0.3; // max. 30% text difference
 
 const MAX_WORDS_HAMMING_DISTANCE: u32 = 2; // up to 2 words may differ
 
+// Additional weight of similar titles depending on their similarity
+const TITLE_SIMILARITY_WEIGHT: f64 = 0.2;
+
+const MIN_DUPLICATE_SCORE: f64 = 0.4;
+
+// Phone numbers with less digits are ignored
+const MIN_PHONE_DIGITS: usize = 6;
+
+// Homepages on these hosts are only considered as equal
+// if the full path matches.
+const SHARED_HOMEPAGE_HOSTS: &[&str] = &[
+    "facebook.com",
+    "goo.gl",
+    "instagram.com",
+    "linkedin.com",
+    "linktr.ee",
+    "sites.google.com",
+    "twitter.com",
+    "x.com",
+    "youtube.com",
+];
+
 fn search_nearby_places(
     place_index: &dyn db::PlaceIndex,
     center: MapPoint,
@@ -63,41 +155,212 @@ fn search_nearby_places(
         .map_err(RepoError::Other)?)
 }
 
-pub fn search_duplicates(
+/// Search for possible duplicates of a new place.
+///
+/// The results are ordered by decreasing score.
+pub fn search_duplicates<R>(
+    repo: &R,
     place_index: &dyn db::PlaceIndex,
     new_place: &NewPlace,
-) -> Result<Vec<IndexedPlace>> {
+) -> Result<Vec<(IndexedPlace, Duplicate)>>
+where
+    R: PlaceRepo,
+{
     let center = MapPoint::new(
         LatCoord::from_deg(new_place.lat),
         LngCoord::from_deg(new_place.lng),
     );
     let nearby_places = search_nearby_places(place_index, center)?;
-    Ok(retain_duplicates_of(nearby_places, new_place))
+    if nearby_places.is_empty() {
+        return Ok(vec![]);
+    }
+    let ids: Vec<_> = nearby_places.iter().map(|p| p.id.as_str()).collect();
+    let places: HashMap<_, _> = repo
+        .get_places(&ids)?
+        .into_iter()
+        .map(|(place, _)| (place.id.to_string(), place))
+        .collect();
+    let mut duplicates: Vec<_> = nearby_places
+        .into_iter()
+        .filter_map(|indexed_place| {
+            let place = places.get(&indexed_place.id)?;
+            let duplicate = is_duplicate_of(new_place, place)?;
+            Some((indexed_place, duplicate))
+        })
+        .collect();
+    duplicates.sort_by(|(_, lhs), (_, rhs)| rhs.score.total_cmp(&lhs.score));
+    Ok(duplicates)
 }
 
 pub fn nearby_bbox(center: MapPoint) -> MapBbox {
     MapBbox::centered_around(center, MAX_NEARBY_DIAMETER, MAX_NEARBY_DIAMETER)
 }
 
-// returns a DuplicateType if the two places have a similar title and location,
-// otherwise returns None.
-fn is_duplicate(e1: &Place, e2: &IndexedPlace) -> Option<DuplicateType> {
-    if e1.id.as_str() == e2.id.as_str() {
-        // Skip identical places
-        return None;
+// Normalized properties of a place that are compared
+// for detecting duplicates
+struct DuplicateFeatures<'a> {
+    title: &'a str,
+    pos: MapPoint,
+    phone: Option<String>,
+    email: Option<String>,
+    address: Option<String>,
+    homepage: Option<String>,
+}
+
+impl<'a> From<&'a Place> for DuplicateFeatures<'a> {
+    fn from(from: &'a Place) -> Self {
+        let contact = from.contact.as_ref();
+        let address = from.location.address.as_ref();
+        Self {
+            title: &from.title,
+            pos: from.location.pos,
+            phone: contact
+                .and_then(|c| c.phone.as_deref())
+                .and_then(normalize_phone),
+            email: contact
+                .and_then(|c| c.email.as_ref())
+                .map(|e| normalize_email(e.as_str())),
+            address: address
+                .and_then(|a| normalize_address(a.street.as_deref()?, a.zip.as_deref()?)),
+            homepage: from
+                .links
+                .as_ref()
+                .and_then(|l| l.homepage.as_ref())
+                .and_then(normalize_homepage),
+        }
     }
-    if !is_in_close_proximity_pos(&e1.location.pos, &e2.pos, MAX_NEARBY_RADIUS) {
+}
+
+impl<'a> From<&'a NewPlace> for DuplicateFeatures<'a> {
+    fn from(from: &'a NewPlace) -> Self {
+        Self {
+            title: &from.title,
+            pos: MapPoint::from_lat_lng_deg(from.lat, from.lng),
+            phone: from.telephone.as_deref().and_then(normalize_phone),
+            email: from.email.as_ref().map(|e| normalize_email(e.as_str())),
+            address: normalize_address(
+                from.street.as_deref().unwrap_or_default(),
+                from.zip.as_deref().unwrap_or_default(),
+            ),
+            homepage: from
+                .homepage
+                .as_deref()
+                .and_then(|url| parse_url_param(url).ok().flatten())
+                .as_ref()
+                .and_then(normalize_homepage),
+        }
+    }
+}
+
+// Only the digits without leading zeros, i.e. the national
+// or international prefix
+fn normalize_phone(phone: &str) -> Option<String> {
+    let digits: String = phone
+        .chars()
+        .filter(char::is_ascii_digit)
+        .skip_while(|c| *c == '0')
+        .collect();
+    (digits.len() >= MIN_PHONE_DIGITS).then_some(digits)
+}
+
+fn normalize_email(email: &str) -> String {
+    email.trim().to_lowercase()
+}
+
+fn normalize_address(street: &str, zip: &str) -> Option<String> {
+    let street = street
+        .to_lowercase()
+        .replace("straße", "str")
+        .replace("strasse", "str");
+    let street: String = street.chars().filter(|c| c.is_alphanumeric()).collect();
+    let zip: String = zip.chars().filter(|c| c.is_alphanumeric()).collect();
+    if street.is_empty() || zip.is_empty() {
         return None;
     }
-    if is_similar_text(&e1.title, &e2.title, MAX_TEXT_RELATIVE_EDIT_DISTANCE, 0) {
+    Some(format!("{zip} {street}"))
+}
+
+// The host without a `www.` prefix
+fn normalize_homepage(url: &Url) -> Option<String> {
+    let host = url.host_str()?.to_lowercase();
+    let host = host.strip_prefix("www.").unwrap_or(&host);
+    if SHARED_HOMEPAGE_HOSTS.contains(&host) {
+        let path = url.path().trim_end_matches('/').to_lowercase();
+        return Some(format!("{host}{path}"));
+    }
+    Some(host.to_owned())
+}
+
+// Phone numbers are compared by their suffix to ignore
+// different national and international prefixes
+fn same_phone(phone1: &str, phone2: &str) -> bool {
+    phone1.ends_with(phone2) || phone2.ends_with(phone1)
+}
+
+fn similar_title(title1: &str, title2: &str) -> Option<DuplicateType> {
+    if is_similar_text(title1, title2, MAX_TEXT_RELATIVE_EDIT_DISTANCE, 0) {
         return Some(DuplicateType::SimilarChars);
     }
-    if is_similar_text(&e1.title, &e2.title, 0.0, MAX_WORDS_HAMMING_DISTANCE) {
+    if is_similar_text(title1, title2, 0.0, MAX_WORDS_HAMMING_DISTANCE) {
         return Some(DuplicateType::SimilarWords);
     }
     None
 }
 
+fn score_duplicate(p1: &DuplicateFeatures, p2: &DuplicateFeatures) -> Option<Duplicate> {
+    if !is_in_close_proximity_pos(&p1.pos, &p2.pos, MAX_SAME_ADDRESS_RADIUS) {
+        return None;
+    }
+    let mut reasons = Vec::new();
+    let mut score = 0.0;
+    if let Some(reason) = similar_title(p1.title, p2.title) {
+        reasons.push(reason);
+        score += TITLE_SIMILARITY_WEIGHT * title_similarity(p1.title, p2.title);
+    }
+    let same = |lhs: &Option<String>, rhs: &Option<String>| matches!((lhs, rhs), (Some(lhs), Some(rhs)) if lhs == rhs);
+    let same_phone = matches!(
+        (&p1.phone, &p2.phone),
+        (Some(lhs), Some(rhs)) if same_phone(lhs, rhs)
+    );
+    for (reason, matching) in [
+        (DuplicateType::SamePhone, same_phone),
+        (DuplicateType::SameEmail, same(&p1.email, &p2.email)),
+        (
+            DuplicateType::SameHomepage,
+            same(&p1.homepage, &p2.homepage),
+        ),
+        (DuplicateType::SameAddress, same(&p1.address, &p2.address)),
+    ] {
+        if matching {
+            reasons.push(reason);
+        }
+    }
+    score += reasons.iter().map(|r| r.weight()).sum::<f64>();
+    if reasons.is_empty() || score < MIN_DUPLICATE_SCORE {
+        return None;
+    }
+    // Places outside of the nearby radius must be located at the same address
+    if !reasons.contains(&DuplicateType::SameAddress)
+        && !is_in_close_proximity_pos(&p1.pos, &p2.pos, MAX_NEARBY_RADIUS)
+    {
+        return None;
+    }
+    Some(Duplicate {
+        score: score.min(1.0),
+        reasons,
+    })
+}
+
+// returns a Duplicate if the two places have a similar title, contact data,
+// or address and are located close together, otherwise returns None.
+fn is_duplicate(e1: &Place, e2: &Place) -> Option<Duplicate> {
+    if e1.id == e2.id {
+        // Skip identical places
+        return None;
+    }
+    score_duplicate(&e1.into(), &e2.into())
+}
+
 fn is_similar_text(
     text1: &str,
     text2: &str,
@@ -110,21 +373,21 @@ fn is_similar_text(
         || words_equal_except_k_words(text1, text2, max_words_hamming_distance)
 }
 
-fn is_duplicate_of(new_place: &NewPlace, indexed_place: &IndexedPlace) -> bool {
-    let pos = MapPoint::from_lat_lng_deg(new_place.lat, new_place.lng);
-    if !is_in_close_proximity_pos(&pos, &indexed_place.pos, MAX_NEARBY_RADIUS) {
-        return false;
+// Similarity of two titles between 0.0 (different) and 1.0 (identical)
+// based on the relative edit distance
+pub(crate) fn title_similarity(title1: &str, title2: &str) -> f64 {
+    let title1 = title1.to_lowercase();
+    let title2 = title2.to_lowercase();
+    let max_len = title1.len().max(title2.len());
+    if max_len == 0 {
+        return 1.0;
     }
-    // TODO: Compare more (text) fields than just the title?
-    if !is_similar_text(
-        &new_place.title,
-        &indexed_place.title,
-        MAX_TEXT_RELATIVE_EDIT_DISTANCE,
-        MAX_WORDS_HAMMING_DISTANCE,
-    ) {
-        return false;
-    }
-    true
+    let dist = levenshtein_distance(&title1, &title2).min(max_len);
+    1.0 - dist as f64 / max_len as f64
+}
+
+fn is_duplicate_of(new_place: &NewPlace, place: &Place) -> Option<Duplicate> {
+    score_duplicate(&new_place.into(), &place.into())
 }
 
 fn is_in_close_proximity_pos(p1: &MapPoint, p2: &MapPoint, max_dist: Distance) -> bool {
@@ -235,15 +498,36 @@ mod tests {
             .finish()
     }
 
-    fn new_indexed_place(title: String, description: String, pos: MapPoint) -> IndexedPlace {
-        let id = title.clone();
-        IndexedPlace {
-            id,
-            title,
-            description,
-            pos,
+    fn new_place_with_address(title: &str, pos: MapPoint, street: &str, zip: &str) -> Place {
+        let mut place = new_place(title.to_string(), String::new(), pos);
+        place.location.address = Some(Address::build().street(street).zip(zip).finish());
+        place
+    }
+
+    fn new_place_with_contact(title: &str, phone: Option<&str>, email: Option<&str>) -> Place {
+        let mut place = new_place(
+            title.to_string(),
+            String::new(),
+            MapPoint::from_lat_lng_deg(48.0, 9.0),
+        );
+        place.contact = Some(Contact {
+            name: None,
+            phone: phone.map(Into::into),
+            email: email.map(|email| email.parse().unwrap()),
+        });
+        place
+    }
+
+    fn with_homepage(mut place: Place, homepage: &str) -> Place {
+        place.links = Some(Links {
+            homepage: Some(homepage.parse().unwrap()),
             ..Default::default()
-        }
+        });
+        place
+    }
+
+    fn reasons(duplicate: Option<Duplicate>) -> Vec<DuplicateType> {
+        duplicate.map(|d| d.reasons).unwrap_or_default()
     }
 
     #[test]
@@ -316,42 +600,43 @@ mod tests {
             image_url: None,
             image_link_url: None,
             custom_links: vec![],
+            translations: vec![],
         };
         let new_y = NewPlace {
             lat: 47.13153745093964,
             ..new_x.clone()
         };
 
-        let x = new_indexed_place(
+        let x = new_place(
             new_x.title.clone(),
             new_x.description.clone(),
             MapPoint::from_lat_lng_deg(new_x.lat, new_x.lng),
         );
         // small hamming distance: 2 words in title missing
-        let similar_title_words1 = new_indexed_place(
+        let similar_title_words1 = new_place(
             "Eintrag".to_string(),
             "Hallo! Ein Eintrag".to_string(),
             MapPoint::from_lat_lng_deg(47.23153745093970, 5.003816366195679),
         );
         // small hamming distance: 2 words in title differ
-        let similar_title_words2 = new_indexed_place(
+        let similar_title_words2 = new_place(
             "En Eintrg Blablala".to_string(),
             "Hallo! Ein Eintrag".to_string(),
             MapPoint::from_lat_lng_deg(47.23153745093955, 5.003816366195679),
         );
         // small levenshtein distance: some typos in title
-        let similar_title_characters = new_indexed_place(
+        let similar_title_characters = new_place(
             "Enn Eintrxg Blablalx".to_string(),
             "Hallo! Ein Eintrag".to_string(),
             MapPoint::from_lat_lng_deg(47.23153745093955, 5.003816366195679),
         );
 
-        assert!(is_duplicate_of(&new_x, &x));
-        assert!(is_duplicate_of(&new_x, &similar_title_words1));
-        assert!(is_duplicate_of(&new_x, &similar_title_words2));
-        assert!(is_duplicate_of(&new_x, &similar_title_characters));
+        assert!(is_duplicate_of(&new_x, &x).is_some());
+        assert!(is_duplicate_of(&new_x, &similar_title_words1).is_some());
+        assert!(is_duplicate_of(&new_x, &similar_title_words2).is_some());
+        assert!(is_duplicate_of(&new_x, &similar_title_characters).is_some());
 
-        assert!(!is_duplicate_of(&new_y, &x));
+        assert!(is_duplicate_of(&new_y, &x).is_none());
     }
 
     #[test]
@@ -372,33 +657,42 @@ mod tests {
             MapPoint::from_lat_lng_deg(47.23153745093955, 5.003816366195679),
         );
 
-        let ip2 = new_indexed_place(
+        let ip2 = new_place(
             "Eintrag".to_string(),
             "Hallo! Ein Eintrag".to_string(),
             MapPoint::from_lat_lng_deg(47.23153745093970, 5.003816366195679),
         );
-        let ip3 = new_indexed_place(
+        let ip3 = new_place(
             "Enn Eintrxg Blablalx".to_string(),
             "Hallo! Ein Eintrag".to_string(),
             MapPoint::from_lat_lng_deg(47.23153745093955, 5.003816366195679),
         );
-        let ip4 = new_indexed_place(
+        let ip4 = new_place(
             "En Eintrg Blablala".to_string(),
             "Hallo! Ein Eintrag".to_string(),
             MapPoint::from_lat_lng_deg(47.23153745093955, 5.003816366195679),
         );
-        let ip5 = new_indexed_place(
+        let ip5 = new_place(
             "Ein Eintrag Blabla".to_string(),
             "Hallo! Ein Eintrag".to_string(),
             MapPoint::from_lat_lng_deg(40.23153745093960, 5.003816366195670),
         );
 
         // titles have a word that is equal
-        assert_eq!(Some(DuplicateType::SimilarWords), is_duplicate(&p1, &ip2));
+        assert_eq!(
+            Some(DuplicateType::SimilarWords),
+            is_duplicate(&p1, &ip2).map(|d| d.main_reason())
+        );
         // titles similar: small levenshtein distance
-        assert_eq!(Some(DuplicateType::SimilarChars), is_duplicate(&p1, &ip4));
+        assert_eq!(
+            Some(DuplicateType::SimilarChars),
+            is_duplicate(&p1, &ip4).map(|d| d.main_reason())
+        );
         // titles similar: small hamming distance
-        assert_eq!(Some(DuplicateType::SimilarChars), is_duplicate(&p1, &ip3));
+        assert_eq!(
+            Some(DuplicateType::SimilarChars),
+            is_duplicate(&p1, &ip3).map(|d| d.main_reason())
+        );
         // titles not similar
         assert_eq!(None, is_duplicate(&p2, &ip4));
         // places not located close together
@@ -427,4 +721,115 @@ mod tests {
         assert_eq!(1, levenshtein_distance("12345", "a12345")); // insert a
         assert_eq!(1, levenshtein_distance("aabaa", "aacaa")); // replace b by c
     }
+
+    #[test]
+    fn test_title_similarity() {
+        assert_eq!(1.0, title_similarity("Weltladen", "weltladen"));
+        assert_eq!(0.8, title_similarity("aabaa", "aacaa"));
+        assert_eq!(0.0, title_similarity("abc", ""));
+        assert!(
+            title_similarity("Weltladen", "Weltlaeden")
+                > title_similarity("Weltladen", "Unverpackt")
+        );
+    }
+
+    #[test]
+    fn test_same_contact_data() {
+        let p1 = new_place_with_contact("Weltladen", Some("+49 (711) 123456"), None);
+        let p2 = new_place_with_contact("Fair Trade Shop", Some("0711 / 12 34 56"), None);
+        let p3 = new_place_with_contact("Unverpackt", None, Some("Info@Example.com"));
+        let p4 = new_place_with_contact("Bioladen", None, Some("info@example.com "));
+        assert_eq!(
+            vec![DuplicateType::SamePhone],
+            reasons(is_duplicate(&p1, &p2))
+        );
+        assert_eq!(
+            vec![DuplicateType::SameEmail],
+            reasons(is_duplicate(&p3, &p4))
+        );
+        assert!(is_duplicate(&p1, &p3).is_none());
+
+        // Too short numbers are ignored
+        let p5 = new_place_with_contact("Unverpackt", Some("123"), None);
+        let p6 = new_place_with_contact("Bioladen", Some("0123"), None);
+        assert!(is_duplicate(&p5, &p6).is_none());
+    }
+
+    #[test]
+    fn test_same_homepage() {
+        let p1 = new_place_with_contact("Weltladen", None, None);
+        let p2 = new_place_with_contact("Fair Trade Shop", None, None);
+        // Different titles but the same homepage domain
+        let p1 = with_homepage(p1, "https://www.example.com/");
+        let p2 = with_homepage(p2, "http://example.com/shop");
+        assert_eq!(
+            vec![DuplicateType::SameHomepage],
+            reasons(is_duplicate(&p1, &p2))
+        );
+
+        let p3 = with_homepage(
+            new_place_with_contact("Weltladen", None, Some("a@example.com")),
+            "https://www.example.com/",
+        );
+        let p4 = with_homepage(
+            new_place_with_contact("Fair Trade Shop", None, Some("a@example.com")),
+            "http://example.com/shop",
+        );
+        let duplicate = is_duplicate(&p3, &p4).unwrap();
+        assert_eq!(
+            vec![DuplicateType::SameEmail, DuplicateType::SameHomepage],
+            duplicate.reasons
+        );
+        assert!(duplicate.score > DuplicateType::SameEmail.weight());
+    }
+
+    #[test]
+    fn test_shared_homepage_hosts() {
+        let homepage = |url| normalize_homepage(&url::Url::parse(url).unwrap());
+        assert_eq!(
+            homepage("https://www.example.com/foo"),
+            homepage("http://example.com/bar")
+        );
+        assert_ne!(
+            homepage("https://www.facebook.com/foo"),
+            homepage("https://facebook.com/bar")
+        );
+        assert_eq!(
+            homepage("https://www.facebook.com/Foo/"),
+            homepage("https://facebook.com/foo")
+        );
+    }
+
+    #[test]
+    fn test_same_address_outside_of_nearby_radius() {
+        // ~170 m apart
+        let pos1 = MapPoint::from_lat_lng_deg(48.7758, 9.1829);
+        let pos2 = MapPoint::from_lat_lng_deg(48.7773, 9.1829);
+        let p1 = new_place_with_address("Weltladen", pos1, "Hauptstraße 1", "70173");
+        let p2 = new_place_with_address("Weltladen am Markt", pos2, "Hauptstr. 1", "70173");
+        assert_eq!(
+            vec![DuplicateType::SimilarWords, DuplicateType::SameAddress],
+            reasons(is_duplicate(&p1, &p2))
+        );
+
+        // Similar titles that are not located at the same address
+        let p3 = new_place_with_address("Weltladen am Markt", pos2, "Hauptstr. 2", "70173");
+        assert!(is_duplicate(&p1, &p3).is_none());
+
+        // Only the same address is not sufficient
+        let p4 = new_place_with_address("Unverpackt", pos1, "Hauptstr. 1", "70173");
+        assert!(is_duplicate(&p1, &p4).is_none());
+    }
+
+    #[test]
+    fn test_normalize_phone() {
+        assert_eq!(
+            Some("49711123456".into()),
+            normalize_phone("+49 711 123456")
+        );
+        assert_eq!(Some("711123456".into()), normalize_phone("0711-123456"));
+        assert_eq!(None, normalize_phone("110"));
+        assert!(same_phone("49711123456", "711123456"));
+        assert!(!same_phone("49711123456", "711123457"));
+    }
 }
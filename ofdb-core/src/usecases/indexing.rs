@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use crate::{rating::Rated, usecases::prelude::*};
 use anyhow::Result as Fallible;
 
@@ -19,3 +21,102 @@ pub fn index_event(indexer: &dyn EventIndexer, event: &Event) -> Fallible<()> {
 pub fn unindex_event(indexer: &dyn EventIndexer, id: &Id) -> Fallible<()> {
     indexer.remove_by_id(id)
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum IndexProblem {
+    /// Stored in the database but not indexed
+    Missing,
+    /// Indexed but not stored in the database (anymore)
+    Orphaned,
+    /// The indexed status or revision differs from the database
+    Outdated,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexMismatch {
+    pub kind: IndexedKind,
+    pub id: Id,
+    pub problem: IndexProblem,
+}
+
+/// Compare the ids, review status, and revisions of all places
+/// and events in the database with the search index.
+pub fn check_index<R>(repo: &R, index: &dyn IdIndex) -> Fallible<Vec<IndexMismatch>>
+where
+    R: PlaceRepo + EventRepo,
+{
+    let mut indexed: HashMap<(IndexedKind, String), IndexedEntry> = index
+        .all_indexed_entries()?
+        .into_iter()
+        .map(|entry| ((entry.kind, entry.id.to_string()), entry))
+        .collect();
+    let expected = repo
+        .all_places()?
+        .into_iter()
+        .map(|(place, status)| IndexedEntry {
+            kind: IndexedKind::Place,
+            id: place.id,
+            status: Some(status),
+            revision: Some(place.revision),
+        })
+        .chain(
+            repo.all_events_chronologically()?
+                .into_iter()
+                .map(|event| IndexedEntry {
+                    kind: IndexedKind::Event,
+                    id: event.id,
+                    status: None,
+                    revision: Some(event.revision),
+                }),
+        );
+    let mut mismatches = Vec::new();
+    for entry in expected {
+        let problem = match indexed.remove(&(entry.kind, entry.id.to_string())) {
+            None => IndexProblem::Missing,
+            Some(indexed_entry) if indexed_entry != entry => IndexProblem::Outdated,
+            Some(_) => continue,
+        };
+        mismatches.push(IndexMismatch {
+            kind: entry.kind,
+            id: entry.id,
+            problem,
+        });
+    }
+    mismatches.extend(
+        indexed
+            .into_values()
+            .map(|IndexedEntry { kind, id, .. }| IndexMismatch {
+                kind,
+                id,
+                problem: IndexProblem::Orphaned,
+            }),
+    );
+    mismatches.sort_by(|lhs, rhs| (lhs.problem, &lhs.id).cmp(&(rhs.problem, &rhs.id)));
+    Ok(mismatches)
+}
+
+/// Reindex missing or outdated and remove orphaned documents.
+pub fn repair_index<R>(
+    repo: &R,
+    indexer: &mut dyn EventAndPlaceIndexer,
+    mismatches: &[IndexMismatch],
+) -> Fallible<()>
+where
+    R: PlaceRepo + EventRepo + RatingRepository,
+{
+    for IndexMismatch { kind, id, problem } in mismatches {
+        match (kind, problem) {
+            (_, IndexProblem::Orphaned) => indexer.remove_by_id(id)?,
+            (IndexedKind::Place, _) => {
+                let (place, status) = repo.get_place(id.as_str())?;
+                let ratings = repo.load_ratings_of_place(id.as_str())?;
+                reindex_place(indexer, &place, status, &ratings)?;
+            }
+            (IndexedKind::Event, _) => {
+                let event = repo.get_event(id.as_str())?;
+                index_event(indexer, &event)?;
+            }
+        }
+    }
+    indexer.flush_index()
+}
@@ -0,0 +1,102 @@
+use super::prelude::*;
+
+/// The maximum number of changes per request
+pub const MAX_CHANGES_LIMIT: u64 = 1000;
+
+#[derive(Debug)]
+pub struct Changes {
+    pub changes: Vec<Change>,
+    /// The cursor for resuming after the last change or
+    /// the requested cursor if there have been no new changes.
+    pub next_cursor: Option<ChangeCursor>,
+}
+
+pub fn load_changes<R: ChangeRepo>(
+    repo: &R,
+    cursor: Option<&str>,
+    limit: Option<u64>,
+) -> Result<Changes> {
+    let cursor = cursor
+        .map(str::parse::<ChangeCursor>)
+        .transpose()
+        .map_err(|_| Error::ChangeCursor)?;
+    let limit = limit.unwrap_or(MAX_CHANGES_LIMIT);
+    if limit == 0 || limit > MAX_CHANGES_LIMIT {
+        return Err(Error::InvalidLimit);
+    }
+    let changes = repo.load_changes(cursor.as_ref(), limit)?;
+    debug_assert!(changes.len() as u64 <= limit);
+    debug_assert!(changes.windows(2).all(|w| w[0].cursor < w[1].cursor));
+    let next_cursor = changes.last().map(|c| c.cursor).or(cursor);
+    Ok(Changes {
+        changes,
+        next_cursor,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::tests::MockDb, *};
+
+    fn place_change(at: i64, seq: i64) -> Change {
+        Change {
+            cursor: seq.into(),
+            changed_at: Timestamp::try_from_millis(at).unwrap(),
+            kind: ChangeKind::Created,
+            item: ChangedItem::Place {
+                id: format!("place{seq}").into(),
+                revision: Revision::initial(),
+                status: ReviewStatus::Created,
+            },
+        }
+    }
+
+    #[test]
+    fn resume_after_cursor() {
+        let db = MockDb::default();
+        db.changes.borrow_mut().extend([
+            place_change(1, 1),
+            place_change(1, 2),
+            place_change(5, 3),
+        ]);
+
+        let Changes {
+            changes,
+            next_cursor,
+        } = load_changes(&db, None, Some(2)).unwrap();
+        assert_eq!(changes, db.changes.borrow()[..2]);
+        let next_cursor = next_cursor.unwrap().to_string();
+
+        let Changes {
+            changes,
+            next_cursor,
+        } = load_changes(&db, Some(&next_cursor), Some(2)).unwrap();
+        assert_eq!(changes, db.changes.borrow()[2..]);
+        let next_cursor = next_cursor.unwrap().to_string();
+
+        // No more changes
+        let Changes {
+            changes,
+            next_cursor: unchanged_cursor,
+        } = load_changes(&db, Some(&next_cursor), None).unwrap();
+        assert!(changes.is_empty());
+        assert_eq!(Some(next_cursor), unchanged_cursor.map(|c| c.to_string()));
+    }
+
+    #[test]
+    fn reject_invalid_parameters() {
+        let db = MockDb::default();
+        assert!(matches!(
+            load_changes(&db, Some("invalid"), None),
+            Err(Error::ChangeCursor)
+        ));
+        assert!(matches!(
+            load_changes(&db, None, Some(0)),
+            Err(Error::InvalidLimit)
+        ));
+        assert!(matches!(
+            load_changes(&db, None, Some(MAX_CHANGES_LIMIT + 1)),
+            Err(Error::InvalidLimit)
+        ));
+    }
+}
@@ -1,11 +1,19 @@
 use super::{clearance, prelude::*};
 
-pub fn load_places<R: PlaceRepo + PlaceClearanceRepo + OrganizationRepo>(
+/// Load places by id.
+///
+/// Merged places are replaced by the surviving place.
+pub fn load_places<R>(
     repo: &R,
     ids: &[&str],
     org_tag: Option<&str>,
-) -> Result<Vec<(Place, ReviewStatus)>> {
-    let places = repo.get_places(ids)?;
+) -> Result<Vec<(Place, ReviewStatus)>>
+where
+    R: PlaceRepo + PlaceClearanceRepo + PlaceRedirectRepo + OrganizationRepo,
+{
+    let ids = resolve_place_redirects(repo, ids)?;
+    let ids: Vec<_> = ids.iter().map(String::as_str).collect();
+    let places = repo.get_places(&ids)?;
     if let Some(org_tag) = org_tag
         && let Some(org_id) = repo.map_tag_to_clearance_org_id(org_tag)?
     {
@@ -13,3 +21,17 @@ pub fn load_places<R: PlaceRepo + PlaceClearanceRepo + OrganizationRepo>(
     }
     Ok(places)
 }
+
+fn resolve_place_redirects<R: PlaceRedirectRepo>(repo: &R, ids: &[&str]) -> Result<Vec<String>> {
+    let mut resolved_ids = Vec::with_capacity(ids.len());
+    for id in ids {
+        let id = repo
+            .resolve_place_redirect(&(*id).into())?
+            .map(String::from)
+            .unwrap_or_else(|| (*id).to_owned());
+        if !resolved_ids.contains(&id) {
+            resolved_ids.push(id);
+        }
+    }
+    Ok(resolved_ids)
+}
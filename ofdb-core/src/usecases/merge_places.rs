@@ -0,0 +1,167 @@
+use super::prelude::*;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct MergePlaces {
+    /// The surviving place
+    pub place_id: Id,
+    /// The duplicate that is merged into the surviving place
+    pub merged_place_id: Id,
+    pub merged_by: EmailAddress,
+    pub comment: Option<String>,
+}
+
+/// Merge a duplicate into the surviving place.
+///
+/// All ratings and comments are moved onto the surviving place.
+/// The duplicate is archived and redirects to the surviving
+/// place afterwards. An open duplicate candidate of both places
+/// is marked as merged. Returns the number of moved ratings.
+pub fn merge_places<R>(repo: &R, merge: MergePlaces) -> Result<usize>
+where
+    R: PlaceRepo + RatingRepository + PlaceRedirectRepo + DuplicateCandidateRepo,
+{
+    let MergePlaces {
+        place_id,
+        merged_place_id,
+        merged_by,
+        comment,
+    } = merge;
+    if place_id == merged_place_id {
+        return Err(Error::MergePlaces);
+    }
+    let (_, status) = repo.get_place(place_id.as_str())?;
+    let (_, merged_status) = repo.get_place(merged_place_id.as_str())?;
+    if !status.exists() || !merged_status.exists() {
+        return Err(Error::MergePlaces);
+    }
+    log::info!("Merging place {merged_place_id} into {place_id}");
+    let rating_count = repo.move_ratings_of_place(&merged_place_id, &place_id)?;
+    let activity_log = ActivityLog {
+        activity: Activity::now(Some(merged_by)),
+        context: Some(format!("Merged into {place_id}")),
+        comment,
+    };
+    let place_count = repo.review_places(
+        &[merged_place_id.as_str()],
+        ReviewStatus::Archived,
+        &activity_log,
+    )?;
+    debug_assert_eq!(1, place_count);
+    repo.create_place_redirect(&merged_place_id, &place_id)?;
+    let (lhs, rhs) = DuplicateCandidate::ordered_pair(place_id, merged_place_id);
+    repo.review_duplicate_candidate(
+        &lhs,
+        &rhs,
+        DuplicateCandidateState::Merged,
+        &activity_log.activity,
+    )?;
+    Ok(rating_count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::tests::MockDb, *};
+
+    fn create_place(db: &MockDb, status: ReviewStatus) -> Id {
+        let place = Place::build().id(Id::new().as_ref()).finish();
+        let id = place.id.clone();
+        db.entries.borrow_mut().push((place, status));
+        id
+    }
+
+    fn merge(place_id: &Id, merged_place_id: &Id) -> MergePlaces {
+        MergePlaces {
+            place_id: place_id.clone(),
+            merged_place_id: merged_place_id.clone(),
+            merged_by: "scout@example.com".parse().unwrap(),
+            comment: None,
+        }
+    }
+
+    #[test]
+    fn merge_duplicate_into_surviving_place() {
+        let db = MockDb::default();
+        let place_id = create_place(&db, ReviewStatus::Created);
+        let merged_place_id = create_place(&db, ReviewStatus::Confirmed);
+        let (lhs, rhs) =
+            DuplicateCandidate::ordered_pair(place_id.clone(), merged_place_id.clone());
+        db.replace_open_duplicate_candidates(&[DuplicateCandidate {
+            place_id: lhs,
+            duplicate_place_id: rhs,
+            similarity: 1.0,
+            detected_at: Timestamp::now(),
+            state: DuplicateCandidateState::Open,
+            reviewed: None,
+        }])
+        .unwrap();
+        for (id, place_id) in [("a", &place_id), ("b", &merged_place_id)] {
+            db.create_rating(Rating {
+                id: id.into(),
+                place_id: place_id.clone(),
+                created_at: Timestamp::now(),
+                archived_at: None,
+                title: "title".into(),
+                value: RatingValue::new(1),
+                context: RatingContext::Fairness,
+                source: None,
+            })
+            .unwrap();
+        }
+
+        assert_eq!(
+            1,
+            merge_places(&db, merge(&place_id, &merged_place_id)).unwrap()
+        );
+        assert_eq!(
+            2,
+            db.load_ratings_of_place(place_id.as_str()).unwrap().len()
+        );
+        assert!(
+            db.entries
+                .borrow()
+                .iter()
+                .any(|(p, s)| p.id == merged_place_id && *s == ReviewStatus::Archived)
+        );
+        assert_eq!(
+            Some(place_id.clone()),
+            db.resolve_place_redirect(&merged_place_id).unwrap()
+        );
+        assert!(db.resolve_place_redirect(&place_id).unwrap().is_none());
+        assert_eq!(
+            DuplicateCandidateState::Merged,
+            db.duplicate_candidates.borrow()[0].state
+        );
+
+        // The duplicate has already been merged
+        assert!(merge_places(&db, merge(&place_id, &merged_place_id)).is_err());
+    }
+
+    #[test]
+    fn redirects_follow_subsequent_merges() {
+        let db = MockDb::default();
+        let first = create_place(&db, ReviewStatus::Created);
+        let second = create_place(&db, ReviewStatus::Created);
+        let third = create_place(&db, ReviewStatus::Created);
+        merge_places(&db, merge(&second, &first)).unwrap();
+        merge_places(&db, merge(&third, &second)).unwrap();
+        assert_eq!(
+            Some(third.clone()),
+            db.resolve_place_redirect(&first).unwrap()
+        );
+        assert_eq!(Some(third), db.resolve_place_redirect(&second).unwrap());
+    }
+
+    #[test]
+    fn reject_merging_a_place_with_itself() {
+        let db = MockDb::default();
+        let place_id = create_place(&db, ReviewStatus::Created);
+        assert!(matches!(
+            merge_places(&db, merge(&place_id, &place_id)),
+            Err(Error::MergePlaces)
+        ));
+        assert_eq!(
+            ReviewStatus::Created,
+            db.get_place(place_id.as_str()).unwrap().1
+        );
+    }
+}
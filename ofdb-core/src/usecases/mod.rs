@@ -1,5 +1,9 @@
-use crate::{entities::*, util::parse::parse_url_param};
+use crate::{
+    entities::*,
+    util::{parse::parse_url_param, validate::Validate},
+};
 
+mod api_tokens;
 mod archive_comments;
 mod archive_events;
 mod archive_ratings;
@@ -13,6 +17,7 @@ mod create_new_place;
 mod create_new_user;
 mod delete_event;
 mod delete_user;
+mod duplicate_candidates;
 mod email_addresses_by_coordinate;
 mod error;
 mod export_event;
@@ -24,21 +29,28 @@ mod get_bbox_subscriptions;
 mod get_event;
 mod get_user;
 mod indexing;
+mod load_changes;
 mod load_places;
 mod load_ratings_with_comments;
 mod login;
+mod merge_places;
+mod organizations;
 mod query_events;
 mod rate_place;
 mod register;
+mod restore_event;
 mod review_places;
 mod review_tokens;
+mod revoked_tokens;
 mod search;
 mod send_update_reminders;
+mod set_event_image;
 mod store_event;
 mod subscribe_to_bbox;
 mod unsubscribe_all_bboxes;
 mod update_place;
 mod user_tokens;
+mod webhooks;
 
 #[cfg(test)]
 pub mod tests;
@@ -46,15 +58,17 @@ pub mod tests;
 type Result<T> = std::result::Result<T, Error>;
 
 pub use self::{
-    archive_comments::*, archive_events::*, archive_ratings::*, authorize::*,
+    api_tokens::*, archive_comments::*, archive_events::*, archive_ratings::*, authorize::*,
     bbox_subscriptions_by_coordinate::*, change_user_role::*, confirm_email::*,
     confirm_email_and_reset_password::*, create_new_place::*, create_new_user::*, delete_event::*,
-    delete_user::*, email_addresses_by_coordinate::*, error::Error, export_event::*,
-    export_place::*, filter_event::*, filter_place::*, find_duplicates::*,
-    get_bbox_subscriptions::*, get_event::*, get_user::*, indexing::*, load_places::*,
-    load_ratings_with_comments::*, login::*, query_events::*, rate_place::*, register::*,
-    review_places::*, review_tokens::*, search::*, send_update_reminders::*, store_event::*,
-    subscribe_to_bbox::*, unsubscribe_all_bboxes::*, update_place::*, user_tokens::*,
+    delete_user::*, duplicate_candidates::*, email_addresses_by_coordinate::*, error::Error,
+    export_event::*, export_place::*, filter_event::*, filter_place::*, find_duplicates::*,
+    get_bbox_subscriptions::*, get_event::*, get_user::*, indexing::*, load_changes::*,
+    load_places::*, load_ratings_with_comments::*, login::*, merge_places::*, organizations::*,
+    query_events::*, rate_place::*, register::*, restore_event::*, review_places::*,
+    review_tokens::*, revoked_tokens::*, search::*, send_update_reminders::*, set_event_image::*,
+    store_event::*, subscribe_to_bbox::*, unsubscribe_all_bboxes::*, update_place::*,
+    user_tokens::*, webhooks::*,
 };
 
 mod prelude {
@@ -116,3 +130,67 @@ fn parse_custom_link_param(from: CustomLinkParam) -> Result<CustomLink> {
         description,
     })
 }
+
+#[derive(Debug, Clone)]
+pub struct TranslationParam {
+    pub lang: String,
+    pub title: String,
+    pub description: Option<String>,
+}
+
+impl From<Translation> for TranslationParam {
+    fn from(from: Translation) -> Self {
+        let Translation {
+            lang,
+            title,
+            description,
+        } = from;
+        Self {
+            lang: lang.into(),
+            title,
+            description,
+        }
+    }
+}
+
+/// Parse and validate the translations of a title and description.
+///
+/// Each language must only occur once.
+fn parse_translation_params(from: Vec<TranslationParam>) -> Result<Vec<Translation>> {
+    let mut translations: Vec<Translation> = Vec::with_capacity(from.len());
+    for TranslationParam {
+        lang,
+        title,
+        description,
+    } in from
+    {
+        let lang = lang.parse::<Language>().map_err(|_| Error::Language)?;
+        if translations.iter().any(|t| t.lang == lang) {
+            return Err(Error::Language);
+        }
+        let title = title.trim();
+        if title.is_empty() {
+            return Err(Error::Title);
+        }
+        translations.push(Translation {
+            lang,
+            title: title.to_owned(),
+            description: description.filter(|d| !d.trim().is_empty()),
+        });
+    }
+    Ok(translations)
+}
+
+/// Parse and validate the opening hours of a place.
+///
+/// Legacy values that do not comply with the OpenStreetMap syntax
+/// are preserved as long as they are not modified.
+fn parse_opening_hours_param(from: &str, current: Option<&OpeningHours>) -> Result<OpeningHours> {
+    let opening_hours = from
+        .parse::<OpeningHours>()
+        .map_err(|_| Error::InvalidOpeningHours)?;
+    if current != Some(&opening_hours) {
+        opening_hours.validate()?;
+    }
+    Ok(opening_hours)
+}
@@ -0,0 +1,373 @@
+use std::collections::HashSet;
+
+use super::{prelude::*, prepare_tag_list};
+use crate::repositories::Error as RepoError;
+
+#[rustfmt::skip]
+#[derive(Debug, Clone)]
+pub struct NewOrganization {
+    pub name           : String,
+    pub moderated_tags : Vec<ModeratedTag>,
+}
+
+fn parse_org_name(name: &str) -> Result<String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(Error::OrganizationName);
+    }
+    Ok(name.to_owned())
+}
+
+// The label of a moderated tag must be a single, normalized tag
+fn parse_moderated_tag(tag: ModeratedTag) -> Result<ModeratedTag> {
+    let mut labels = prepare_tag_list([tag.label.as_str()]);
+    if labels.len() != 1 {
+        return Err(Error::Tag);
+    }
+    Ok(ModeratedTag {
+        label: labels.swap_remove(0),
+        ..tag
+    })
+}
+
+// Each tag could only be moderated by a single organization
+fn check_moderated_tags<R>(repo: &R, org_id: &Id, moderated_tags: &[ModeratedTag]) -> Result<()>
+where
+    R: OrganizationRepo,
+{
+    let mut labels = HashSet::new();
+    if moderated_tags
+        .iter()
+        .any(|tag| !labels.insert(tag.label.as_str()))
+    {
+        return Err(Error::Tag);
+    }
+    if repo
+        .get_moderated_tags_by_org(Some(org_id))?
+        .iter()
+        .any(|(_, tag)| labels.contains(tag.label.as_str()))
+    {
+        return Err(Error::ModeratedTag);
+    }
+    Ok(())
+}
+
+/// Create a new organization.
+///
+/// A random secret is generated as the legacy API token of
+/// the organization.
+pub fn create_org<R>(repo: &mut R, new_org: NewOrganization) -> Result<Organization>
+where
+    R: OrganizationRepo,
+{
+    let NewOrganization {
+        name,
+        moderated_tags,
+    } = new_org;
+    let name = parse_org_name(&name)?;
+    let moderated_tags = moderated_tags
+        .into_iter()
+        .map(parse_moderated_tag)
+        .collect::<Result<Vec<_>>>()?;
+    let org = Organization {
+        id: Id::new(),
+        name,
+        api_token: ApiToken::generate_secret(),
+        moderated_tags,
+    };
+    check_moderated_tags(repo, &org.id, &org.moderated_tags)?;
+    repo.create_org(org.clone())?;
+    Ok(org)
+}
+
+pub fn rename_org<R>(repo: &mut R, id: &Id, name: &str) -> Result<Organization>
+where
+    R: OrganizationRepo,
+{
+    let mut org = repo.get_org_by_id(id)?;
+    org.name = parse_org_name(name)?;
+    repo.update_org(org.clone())?;
+    Ok(org)
+}
+
+pub fn delete_org<R>(repo: &mut R, id: &Id) -> Result<()>
+where
+    R: OrganizationRepo,
+{
+    Ok(repo.delete_org(id)?)
+}
+
+/// Add a new moderated tag or update the permissions
+/// of an existing one.
+pub fn add_or_update_moderated_tag<R>(
+    repo: &mut R,
+    org_id: &Id,
+    moderated_tag: ModeratedTag,
+) -> Result<Organization>
+where
+    R: OrganizationRepo,
+{
+    let moderated_tag = parse_moderated_tag(moderated_tag)?;
+    let mut org = repo.get_org_by_id(org_id)?;
+    if let Some(existing) = org
+        .moderated_tags
+        .iter_mut()
+        .find(|tag| tag.label == moderated_tag.label)
+    {
+        *existing = moderated_tag;
+    } else {
+        org.moderated_tags.push(moderated_tag);
+    }
+    check_moderated_tags(repo, &org.id, &org.moderated_tags)?;
+    repo.update_org(org.clone())?;
+    Ok(org)
+}
+
+pub fn remove_moderated_tag<R>(repo: &mut R, org_id: &Id, label: &str) -> Result<Organization>
+where
+    R: OrganizationRepo,
+{
+    let mut org = repo.get_org_by_id(org_id)?;
+    let count = org.moderated_tags.len();
+    org.moderated_tags.retain(|tag| tag.label != label);
+    if org.moderated_tags.len() == count {
+        return Err(RepoError::NotFound.into());
+    }
+    repo.update_org(org.clone())?;
+    Ok(org)
+}
+
+/// Add a registered user as a member of the organization
+/// or change the role of an existing member.
+pub fn add_or_update_org_member<R>(
+    repo: &R,
+    org_id: &Id,
+    email: &EmailAddress,
+    role: OrganizationRole,
+) -> Result<OrganizationMember>
+where
+    R: OrganizationRepo + OrganizationMemberRepo + UserRepo,
+{
+    let org = repo.get_org_by_id(org_id)?;
+    let Some(user) = repo.try_get_user_by_email(email)? else {
+        return Err(Error::UserDoesNotExist);
+    };
+    let member = OrganizationMember {
+        org_id: org.id,
+        email: user.email,
+        role,
+    };
+    repo.add_or_update_org_member(&member)?;
+    Ok(member)
+}
+
+pub fn remove_org_member<R>(repo: &R, org_id: &Id, email: &EmailAddress) -> Result<()>
+where
+    R: OrganizationMemberRepo,
+{
+    Ok(repo.remove_org_member(org_id, email)?)
+}
+
+/// Visible places that are tagged with any of the moderated
+/// tags of the organization.
+pub fn org_places<R>(
+    repo: &R,
+    index: &dyn PlaceIndex,
+    org_id: &Id,
+    limit: usize,
+) -> Result<Vec<IndexedPlace>>
+where
+    R: OrganizationRepo,
+{
+    let org = repo.get_org_by_id(org_id)?;
+    let mut place_ids = HashSet::new();
+    let mut places = Vec::new();
+    for ModeratedTag { label, .. } in org.moderated_tags {
+        let query = IndexQuery {
+            status: Some(vec![]),
+            hash_tags: vec![label],
+            ..Default::default()
+        };
+        let tagged_places = index
+            .query_places(&query, limit)
+            .map_err(RepoError::Other)?;
+        places.extend(
+            tagged_places
+                .into_iter()
+                .filter(|place| place_ids.insert(place.id.clone())),
+        );
+    }
+    places.sort_by(|lhs, rhs| lhs.title.cmp(&rhs.title));
+    places.truncate(limit);
+    Ok(places)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::tests::MockDb, *};
+
+    fn moderated_tag(label: &str) -> ModeratedTag {
+        ModeratedTag {
+            label: label.into(),
+            allow_add: true,
+            allow_remove: false,
+            require_clearance: false,
+        }
+    }
+
+    #[test]
+    fn create_org_with_normalized_tags() {
+        let mut db = MockDb::default();
+        let org = create_org(
+            &mut db,
+            NewOrganization {
+                name: " Org ".into(),
+                moderated_tags: vec![moderated_tag("#Foo")],
+            },
+        )
+        .unwrap();
+        assert_eq!("Org", org.name);
+        assert_eq!("foo", org.moderated_tags[0].label);
+        assert!(!org.api_token.is_empty());
+        assert_eq!(org.id, db.get_org_by_api_token(&org.api_token).unwrap().id);
+    }
+
+    #[test]
+    fn reject_invalid_names_and_tags() {
+        let mut db = MockDb::default();
+        for (name, tags) in [
+            (" ", vec![]),
+            ("org", vec![moderated_tag("")]),
+            ("org", vec![moderated_tag("foo bar")]),
+            ("org", vec![moderated_tag("foo"), moderated_tag("#foo")]),
+        ] {
+            assert!(
+                create_org(
+                    &mut db,
+                    NewOrganization {
+                        name: name.into(),
+                        moderated_tags: tags,
+                    },
+                )
+                .is_err()
+            );
+        }
+        assert!(db.orgs.is_empty());
+    }
+
+    #[test]
+    fn tags_are_moderated_by_a_single_org() {
+        let mut db = MockDb::default();
+        let org = create_org(
+            &mut db,
+            NewOrganization {
+                name: "org".into(),
+                moderated_tags: vec![moderated_tag("foo")],
+            },
+        )
+        .unwrap();
+        let other = create_org(
+            &mut db,
+            NewOrganization {
+                name: "other".into(),
+                moderated_tags: vec![],
+            },
+        )
+        .unwrap();
+        assert!(matches!(
+            add_or_update_moderated_tag(&mut db, &other.id, moderated_tag("foo")),
+            Err(Error::ModeratedTag)
+        ));
+
+        // Update the permissions of an existing tag
+        let org = add_or_update_moderated_tag(
+            &mut db,
+            &org.id,
+            ModeratedTag {
+                require_clearance: true,
+                ..moderated_tag("foo")
+            },
+        )
+        .unwrap();
+        assert_eq!(1, org.moderated_tags.len());
+        assert!(org.moderated_tags[0].require_clearance);
+
+        remove_moderated_tag(&mut db, &org.id, "foo").unwrap();
+        assert!(remove_moderated_tag(&mut db, &org.id, "foo").is_err());
+        add_or_update_moderated_tag(&mut db, &other.id, moderated_tag("foo")).unwrap();
+        assert_eq!(
+            Some(other.id),
+            db.get_moderated_tags_by_org(None)
+                .unwrap()
+                .into_iter()
+                .find(|(_, tag)| tag.label == "foo")
+                .map(|(id, _)| id)
+        );
+    }
+
+    #[test]
+    fn authorize_members_by_role() {
+        let mut db = MockDb::default();
+        let new_org = |name: &str| NewOrganization {
+            name: name.into(),
+            moderated_tags: vec![],
+        };
+        let org = create_org(&mut db, new_org("org")).unwrap();
+        let other = create_org(&mut db, new_org("other")).unwrap();
+        let email: EmailAddress = "member@example.com".parse().unwrap();
+        assert!(matches!(
+            add_or_update_org_member(&db, &org.id, &email, OrganizationRole::Viewer),
+            Err(Error::UserDoesNotExist)
+        ));
+        db.create_user(&User {
+            email: email.clone(),
+            email_confirmed: true,
+            password: "secret".parse().unwrap(),
+            role: Role::User,
+        })
+        .unwrap();
+        let authorize = |db: &MockDb, org_id: Option<&Id>, scope| {
+            super::super::authorize_organization_by_member(db, &email, org_id, scope)
+        };
+        assert!(matches!(
+            authorize(&db, None, ApiTokenScope::ClearanceRead),
+            Err(Error::Unauthorized)
+        ));
+
+        add_or_update_org_member(&db, &org.id, &email, OrganizationRole::Viewer).unwrap();
+        assert_eq!(
+            org.id,
+            authorize(&db, None, ApiTokenScope::ClearanceRead)
+                .unwrap()
+                .id
+        );
+        assert!(matches!(
+            authorize(&db, None, ApiTokenScope::ClearanceWrite),
+            Err(Error::Forbidden)
+        ));
+
+        // Members of multiple organizations must select one of them
+        add_or_update_org_member(&db, &other.id, &email, OrganizationRole::Clearer).unwrap();
+        assert_eq!(
+            other.id,
+            authorize(&db, None, ApiTokenScope::ClearanceWrite)
+                .unwrap()
+                .id
+        );
+        assert!(matches!(
+            authorize(&db, None, ApiTokenScope::ClearanceRead),
+            Err(Error::AmbiguousOrganization)
+        ));
+        assert_eq!(
+            org.id,
+            authorize(&db, Some(&org.id), ApiTokenScope::ClearanceRead)
+                .unwrap()
+                .id
+        );
+
+        remove_org_member(&db, &org.id, &email).unwrap();
+        assert!(remove_org_member(&db, &org.id, &email).is_err());
+        delete_org(&mut db, &other.id).unwrap();
+        assert!(db.list_org_memberships_of_user(&email).unwrap().is_empty());
+    }
+}
@@ -1,8 +1,10 @@
+use time::Duration;
+
 use crate::{
     bbox,
     repositories::Error as RepoError,
     tag,
-    usecases::prelude::*,
+    usecases::{clearance, prelude::*},
     util::{extract_hash_tags, remove_hash_tags},
 };
 
@@ -47,10 +49,28 @@ impl EventQuery {
     }
 }
 
-#[allow(clippy::absurd_extreme_comparisons)]
+/// Query events and expand recurring events into their
+/// occurrences within the requested time window.
+///
+/// Recurring events are not expanded if the query is empty.
 pub fn query_events<R>(repo: &R, index: &dyn IdIndex, query: EventQuery) -> Result<Vec<Event>>
 where
-    R: EventRepo + UserRepo,
+    R: EventRepo + EventClearanceRepo + OrganizationRepo + UserRepo,
+{
+    if query.is_empty() {
+        return query_event_series(repo, index, query);
+    }
+    let window = OccurrenceWindow::from(&query);
+    let limit = query.limit.unwrap_or(DEFAULT_RESULT_LIMIT);
+    let events = query_event_series(repo, index, query)?;
+    Ok(expand_occurrences(events, &window, limit))
+}
+
+/// Query events without expanding recurring events.
+#[allow(clippy::absurd_extreme_comparisons)]
+pub fn query_event_series<R>(repo: &R, index: &dyn IdIndex, query: EventQuery) -> Result<Vec<Event>>
+where
+    R: EventRepo + EventClearanceRepo + OrganizationRepo + UserRepo,
 {
     if query.is_empty() {
         // Special case for backwards compatibility
@@ -76,6 +96,15 @@ where
         }
     }
 
+    // Results that are tagged by an organization which requires
+    // clearance must only contain cleared revisions.
+    let mut clearance_org_tags = Vec::new();
+    for tag in &hash_tags {
+        if let Some(org_id) = repo.map_tag_to_clearance_org_id(tag)? {
+            clearance_org_tags.push((org_id, tag.clone()));
+        }
+    }
+
     let text = text
         .as_deref()
         .map(remove_hash_tags)
@@ -152,5 +181,158 @@ where
         }
     }
 
+    for (org_id, org_tag) in &clearance_org_tags {
+        events = clearance::event::clear_repo_results(repo, org_id, org_tag, events)?;
+    }
+
     Ok(events)
 }
+
+#[derive(Debug, Clone, Copy)]
+struct OccurrenceWindow {
+    start_min: Option<Timestamp>,
+    start_max: Option<Timestamp>,
+    end_min: Option<Timestamp>,
+    end_max: Option<Timestamp>,
+}
+
+impl From<&EventQuery> for OccurrenceWindow {
+    fn from(query: &EventQuery) -> Self {
+        let EventQuery {
+            start_min,
+            start_max,
+            end_min,
+            end_max,
+            ..
+        } = *query;
+        Self {
+            start_min,
+            start_max,
+            end_min,
+            end_max,
+        }
+    }
+}
+
+fn expand_occurrences(events: Vec<Event>, window: &OccurrenceWindow, limit: usize) -> Vec<Event> {
+    let OccurrenceWindow {
+        start_min,
+        start_max,
+        end_min,
+        end_max,
+    } = *window;
+    let mut occurrences = Vec::with_capacity(events.len());
+    for event in events {
+        if event.recurrence.is_none() {
+            // Single events have already been filtered by the index
+            occurrences.push(event);
+            continue;
+        }
+        let duration = event.end.map(|end| end.as_secs() - event.start.as_secs());
+        if duration.is_none() && (end_min.is_some() || end_max.is_some()) {
+            continue;
+        }
+        let end_of = |start: Timestamp| duration.map(|secs| start + Duration::seconds(secs));
+        // Both start and end of the occurrences are strictly increasing
+        let too_early = |start: &Timestamp| {
+            start_min.is_some_and(|min| *start < min)
+                || end_min.is_some_and(|min| end_of(*start).is_some_and(|end| end < min))
+        };
+        let too_late = |start: &Timestamp| {
+            start_max.is_some_and(|max| *start > max)
+                || end_max.is_some_and(|max| end_of(*start).is_some_and(|end| end > max))
+        };
+        let starts: Vec<_> = event
+            .occurrence_starts()
+            .skip_while(too_early)
+            .take_while(|start| !too_late(start))
+            .take(limit)
+            .collect();
+        occurrences.extend(starts.into_iter().map(|start| event.occurrence(start)));
+    }
+    occurrences.sort_by_key(|event| event.start);
+    occurrences.truncate(limit);
+    occurrences
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn weekly_event(start: Timestamp, rule: &str) -> Event {
+        Event {
+            id: Id::new(),
+            title: "Repair Café".into(),
+            description: None,
+            start,
+            end: Some(start + Duration::hours(3)),
+            location: None,
+            contact: None,
+            tags: vec![],
+            homepage: None,
+            created_by: None,
+            registration: None,
+            archived: None,
+            image_url: None,
+            image_link_url: None,
+            recurrence: Some(recurrence::Recurrence {
+                rule: rule.parse().unwrap(),
+                exdates: vec![start + Duration::weeks(1)],
+            }),
+            revision: Revision::initial(),
+            translations: vec![],
+        }
+    }
+
+    #[test]
+    fn expand_occurrences_within_time_window() {
+        let start = Timestamp::try_from_secs(1_714_586_400).unwrap();
+        let event = weekly_event(start, "FREQ=WEEKLY");
+        let window = OccurrenceWindow {
+            start_min: Some(start + Duration::days(1)),
+            start_max: Some(start + Duration::weeks(4)),
+            end_min: None,
+            end_max: None,
+        };
+        let occurrences = expand_occurrences(vec![event.clone()], &window, 10);
+        assert_eq!(
+            vec![
+                start + Duration::weeks(2),
+                start + Duration::weeks(3),
+                start + Duration::weeks(4),
+            ],
+            occurrences.iter().map(|e| e.start).collect::<Vec<_>>()
+        );
+        assert!(occurrences.iter().all(|e| e.id == event.id));
+        assert_eq!(
+            Some(start + Duration::weeks(2) + Duration::hours(3)),
+            occurrences[0].end
+        );
+
+        // Unbounded time window
+        let window = OccurrenceWindow {
+            start_min: Some(start),
+            start_max: None,
+            end_min: None,
+            end_max: None,
+        };
+        assert_eq!(5, expand_occurrences(vec![event], &window, 5).len());
+    }
+
+    #[test]
+    fn expand_occurrences_by_end_time() {
+        let start = Timestamp::try_from_secs(1_714_586_400).unwrap();
+        let event = weekly_event(start, "FREQ=WEEKLY;COUNT=3");
+        let window = OccurrenceWindow {
+            start_min: None,
+            start_max: None,
+            end_min: Some(start + Duration::hours(4)),
+            end_max: None,
+        };
+        let occurrences = expand_occurrences(vec![event], &window, 10);
+        assert_eq!(
+            vec![start + Duration::weeks(2)],
+            occurrences.iter().map(|e| e.start).collect::<Vec<_>>()
+        );
+    }
+}
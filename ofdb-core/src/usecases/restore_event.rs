@@ -0,0 +1,157 @@
+use super::prelude::*;
+use crate::usecases::authorize::authorize_editing_of_tagged_entry;
+
+/// Restore an older revision of an event that is owned by the organization.
+///
+/// The contents of the older revision are stored as a new revision,
+/// i.e. the history of the event is never rewritten.
+///
+/// `restored_by` is the member of the organization that restores
+/// the revision, if any. The organization is recorded as the context
+/// of the new revision.
+pub fn restore_event_revision<R>(
+    repo: &R,
+    org: &Organization,
+    id: &str,
+    revision: Revision,
+    restored_by: Option<EmailAddress>,
+) -> Result<Event>
+where
+    R: OrganizationRepo + EventRepo,
+{
+    let moderated_tags = || org.moderated_tags.iter().map(|t| t.label.as_str());
+    let current = repo.get_event(id)?;
+    if !current.is_owned(moderated_tags()) {
+        return Err(Error::ModeratedTag);
+    }
+    let restored = repo.load_event_revision(id, revision)?;
+    // The organization must not lose the ownership of the event
+    // by restoring an older revision.
+    if !restored.is_owned(moderated_tags()) {
+        return Err(Error::ModeratedTag);
+    }
+    authorize_editing_of_tagged_entry(repo, &current.tags, &restored.tags, Some(org))?;
+    let event = Event {
+        revision: current.revision.next(),
+        archived: current.archived,
+        ..restored
+    };
+    let activity = ActivityLog {
+        activity: Activity::now(restored_by),
+        context: Some(format!("Restored by org {}", org.id)),
+        comment: Some(format!(
+            "Restored revision {}",
+            RevisionValue::from(revision)
+        )),
+    };
+    log::debug!("Restoring revision {revision:?} of event {id}");
+    repo.update_event(&event, &activity)?;
+    Ok(event)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        super::tests::{Builder, MockDb, anonymous_activity, org_with_tag},
+        *,
+    };
+
+    fn event(revision: u64, title: &str, tags: &[&str]) -> Event {
+        Event::build()
+            .id("x")
+            .revision(revision)
+            .title(title)
+            .tags(tags.to_vec())
+            .finish()
+    }
+
+    #[test]
+    fn restore_older_revision_as_new_revision() {
+        let db = MockDb {
+            orgs: vec![org_with_tag("a")],
+            ..Default::default()
+        };
+        db.create_event(event(0, "first", &["a"]), &anonymous_activity())
+            .unwrap();
+        db.update_event(&event(1, "second", &["a"]), &anonymous_activity())
+            .unwrap();
+
+        let member: EmailAddress = "member@example.com".parse().unwrap();
+        let restored = restore_event_revision(
+            &db,
+            &db.orgs[0],
+            "x",
+            Revision::initial(),
+            Some(member.clone()),
+        )
+        .unwrap();
+        assert_eq!(restored.title, "first");
+        assert_eq!(restored.revision, Revision::from(2));
+        assert_eq!(db.get_event("x").unwrap(), restored);
+
+        let history = db.get_event_history("x", None).unwrap();
+        assert_eq!(history.revisions.len(), 3);
+        let (latest, activity) = &history.revisions[0];
+        assert_eq!(latest, &restored);
+        assert_eq!(activity.comment.as_deref(), Some("Restored revision 0"));
+        assert_eq!(activity.context.as_deref(), Some("Restored by org a"));
+        assert_eq!(activity.activity.by, Some(member));
+    }
+
+    #[test]
+    fn restore_revision_without_member() {
+        let db = MockDb {
+            orgs: vec![org_with_tag("a")],
+            ..Default::default()
+        };
+        let created = Event::build()
+            .id("x")
+            .title("first")
+            .tags(vec!["a"])
+            .created_by("creator@example.com")
+            .finish();
+        db.create_event(created, &anonymous_activity()).unwrap();
+        db.update_event(&event(1, "second", &["a"]), &anonymous_activity())
+            .unwrap();
+
+        restore_event_revision(&db, &db.orgs[0], "x", Revision::initial(), None).unwrap();
+        let history = db.get_event_history("x", None).unwrap();
+        let (_, activity) = &history.revisions[0];
+        // The creator of the restored revision is not credited
+        assert_eq!(activity.activity.by, None);
+        assert_eq!(activity.context.as_deref(), Some("Restored by org a"));
+    }
+
+    #[test]
+    fn restore_revision_of_event_owned_by_another_org() {
+        let db = MockDb {
+            orgs: vec![org_with_tag("a"), org_with_tag("b")],
+            ..Default::default()
+        };
+        db.create_event(event(0, "first", &["a"]), &anonymous_activity())
+            .unwrap();
+        db.update_event(&event(1, "second", &["a"]), &anonymous_activity())
+            .unwrap();
+        assert!(matches!(
+            restore_event_revision(&db, &db.orgs[1], "x", Revision::initial(), None),
+            Err(Error::ModeratedTag)
+        ));
+    }
+
+    #[test]
+    fn restore_revision_without_ownership() {
+        let db = MockDb {
+            orgs: vec![org_with_tag("a")],
+            ..Default::default()
+        };
+        db.create_event(event(0, "first", &[]), &anonymous_activity())
+            .unwrap();
+        db.update_event(&event(1, "second", &["a"]), &anonymous_activity())
+            .unwrap();
+        assert!(matches!(
+            restore_event_revision(&db, &db.orgs[0], "x", Revision::initial(), None),
+            Err(Error::ModeratedTag)
+        ));
+        assert_eq!(db.get_event("x").unwrap().title, "second");
+    }
+}
@@ -0,0 +1,14 @@
+use super::prelude::*;
+
+pub fn revoke_token<R: RevokedTokenRepo>(repo: &R, id: &str, expires_at: Timestamp) -> Result<()> {
+    Ok(repo.add_revoked_token(id, expires_at)?)
+}
+
+pub fn is_token_revoked<R: RevokedTokenRepo>(repo: &R, id: &str) -> Result<bool> {
+    Ok(repo.is_token_revoked(id)?)
+}
+
+pub fn delete_expired_revoked_tokens<R: RevokedTokenRepo>(repo: &R) -> Result<usize> {
+    let expired_before = Timestamp::now();
+    Ok(repo.delete_expired_revoked_tokens(expired_before)?)
+}
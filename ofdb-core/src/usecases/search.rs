@@ -2,6 +2,7 @@ use crate::{
     bbox, entities::MapBbox, repositories::Error as RepoError, tag, usecases::prelude::*, util,
 };
 use std::collections::HashMap;
+use time::PrimitiveDateTime;
 
 #[rustfmt::skip]
 #[derive(Debug, Clone)]
@@ -13,6 +14,13 @@ pub struct SearchRequest<'a> {
     pub hash_tags  : Vec<&'a str>,
     pub text       : Option<&'a str>,
     pub status     : Vec<ReviewStatus>,
+    pub open_at    : Option<PrimitiveDateTime>,
+    pub fuzzy      : bool,
+    // Only search within the radius around `center`
+    // instead of the extended `bbox`
+    pub center     : Option<MapPoint>,
+    pub radius     : Option<Distance>,
+    pub order_by_distance: bool,
 }
 
 pub fn clear_search_results<R>(
@@ -55,6 +63,7 @@ where
                     location: Location { pos, .. },
                     tags,
                     title,
+                    translations,
                     ..
                 } = last_cleared_place;
                 if !tags.iter().map(String::as_str).any(|tag| tag == org_tag) {
@@ -72,6 +81,7 @@ where
                     status: Some(current_status),
                     tags,
                     title,
+                    translations,
                 };
             } else {
                 // Skip newly created but not yet cleared entry
@@ -83,15 +93,7 @@ where
     Ok(cleared_results)
 }
 
-pub fn search<R>(
-    repo: &R,
-    index: &dyn PlaceIndex,
-    req: SearchRequest,
-    limit: usize,
-) -> Result<(Vec<IndexedPlace>, Vec<IndexedPlace>)>
-where
-    R: PlaceRepo + PlaceClearanceRepo + OrganizationRepo,
-{
+fn visible_places_query(req: SearchRequest) -> Result<IndexQuery> {
     let SearchRequest {
         bbox: visible_bbox,
         ids,
@@ -100,8 +102,23 @@ where
         hash_tags: req_hash_tags,
         text,
         status,
+        open_at,
+        fuzzy,
+        center,
+        radius,
+        order_by_distance,
     } = req;
 
+    if (radius.is_some() || order_by_distance) && center.is_none() {
+        return Err(Error::InvalidPosition);
+    }
+    if center.is_some_and(|center| !center.is_valid()) {
+        return Err(Error::InvalidPosition);
+    }
+    if radius.is_some_and(|radius| !radius.is_valid() || !radius.to_meters().is_finite()) {
+        return Err(Error::InvalidRadius);
+    }
+
     let mut hash_tags = text.map(util::extract_hash_tags).unwrap_or_default();
     hash_tags.reserve(req_hash_tags.len() + 1);
     for hash_tag in req_hash_tags {
@@ -120,7 +137,7 @@ where
         .map(tag::split_text_into_tags)
         .unwrap_or_default();
 
-    let visible_places_query = IndexQuery {
+    Ok(IndexQuery {
         include_bbox: Some(visible_bbox),
         exclude_bbox: None,
         categories,
@@ -129,8 +146,28 @@ where
         text_tags,
         text,
         status: Some(status),
+        open_at,
+        fuzzy,
+        center,
+        max_distance: radius,
+        order_by_distance,
         ..Default::default()
-    };
+    })
+}
+
+pub fn search<R>(
+    repo: &R,
+    index: &dyn PlaceIndex,
+    req: SearchRequest,
+    limit: usize,
+) -> Result<(Vec<IndexedPlace>, Vec<IndexedPlace>)>
+where
+    R: PlaceRepo + PlaceClearanceRepo + OrganizationRepo,
+{
+    let visible_bbox = req.bbox;
+    let org_tag = req.org_tag;
+    let radius = req.radius;
+    let visible_places_query = visible_places_query(req)?;
 
     // 1st query: Search for visible results only
     // This is required to reliably retrieve all available results!
@@ -150,7 +187,8 @@ where
     }
 
     // 2nd query: Search for remaining invisible results
-    let mut invisible_places = if visible_places.len() < limit {
+    // that are not needed when searching within a radius
+    let mut invisible_places = if radius.is_none() && visible_places.len() < limit {
         let invisible_places_query = IndexQuery {
             include_bbox: Some(bbox::extend_bbox(&visible_bbox)),
             exclude_bbox: visible_places_query.include_bbox,
@@ -176,14 +214,74 @@ where
     Ok((visible_places, invisible_places))
 }
 
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchFacets {
+    /// The most frequent tags, excluding categories
+    pub tags: Vec<TagFrequency>,
+    pub categories: Vec<(Id, TagCount)>,
+}
+
+/// Count the tags and categories of all visible search results.
+///
+/// The counts refer to the current revisions of the places,
+/// even if only cleared revisions are returned by the search.
+pub fn search_facets(
+    index: &dyn PlaceIndex,
+    req: SearchRequest,
+    max_tags: usize,
+) -> Result<SearchFacets> {
+    let query = visible_places_query(req)?;
+    let tag_frequencies = index.count_place_tags(&query).map_err(RepoError::Other)?;
+    let mut facets = SearchFacets::default();
+    for TagFrequency(tag, count) in tag_frequencies {
+        let (tags, categories) = Category::split_from_tags(vec![tag]);
+        if let Some(category) = categories.into_iter().next() {
+            facets.categories.push((category.id, count));
+        } else if facets.tags.len() < max_tags {
+            facets
+                .tags
+                .extend(tags.into_iter().map(|tag| TagFrequency(tag, count)));
+        }
+    }
+    Ok(facets)
+}
+
+/// The maximum zoom level of web maps
+pub const MAX_ZOOM: u8 = 22;
+
+// The number of grid cells along each axis of a map tile
+// (256 x 256 px) for aggregating places into clusters
+const CLUSTER_CELLS_PER_TILE: u32 = 4;
+
+/// Aggregate all visible search results into clusters that
+/// fit the resolution of a map at the given zoom level.
+pub fn search_clusters(
+    index: &dyn PlaceIndex,
+    req: SearchRequest,
+    zoom: u8,
+) -> Result<Vec<PlaceCluster>> {
+    if zoom > MAX_ZOOM {
+        return Err(Error::Zoom);
+    }
+    let query = visible_places_query(req)?;
+    let cells_per_360_deg = (1u32 << zoom) * CLUSTER_CELLS_PER_TILE;
+    let cell_size_deg = 360.0 / f64::from(cells_per_360_deg);
+    let clusters = index
+        .cluster_places(&query, cell_size_deg)
+        .map_err(RepoError::Other)?;
+    Ok(clusters)
+}
+
 /// The global search usecase is like the one
 /// of usual internet search engines that exists
 /// of only one single search input.
 /// So here we don't care about tags, categories etc.
-/// We also ignore the rating of an entry for now.
+/// We also ignore the rating of an entry for now,
+/// but tolerate typos.
 pub fn global_search(index: &dyn PlaceIndex, txt: &str, limit: usize) -> Result<Vec<IndexedPlace>> {
     let index_query = IndexQuery {
         text: Some(txt.into()),
+        fuzzy: true,
         ..Default::default()
     };
 
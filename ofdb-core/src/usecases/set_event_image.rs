@@ -0,0 +1,134 @@
+use super::prelude::*;
+use crate::usecases::authorize::authorize_editing_of_tagged_entry;
+
+/// Replace the image of an event that is owned by the organization.
+///
+/// The event is stored as a new revision with the given image URL.
+/// `uploaded_by` is the member of the organization that uploaded
+/// the image, if any.
+///
+/// Returns the updated event and the ids of all organizations
+/// that need to clear the new revision.
+pub fn set_event_image<R>(
+    repo: &R,
+    org: &Organization,
+    id: &str,
+    image_url: Url,
+    uploaded_by: Option<EmailAddress>,
+) -> Result<(Event, Vec<Id>)>
+where
+    R: OrganizationRepo + EventRepo + EventClearanceRepo,
+{
+    let current = repo.get_event(id)?;
+    let moderated_tags = org.moderated_tags.iter().map(|t| t.label.as_str());
+    if !current.is_owned(moderated_tags) {
+        return Err(Error::ModeratedTag);
+    }
+    // The tags are not modified, but other organizations might
+    // still require clearance of the new revision.
+    let clearance_org_ids =
+        authorize_editing_of_tagged_entry(repo, &current.tags, &current.tags, Some(org))?;
+    let last_cleared_revision = Some(current.revision);
+    let event = Event {
+        revision: current.revision.next(),
+        image_url: Some(image_url),
+        ..current
+    };
+    let activity = ActivityLog {
+        activity: Activity::now(uploaded_by),
+        context: Some(format!("Uploaded by org {}", org.id)),
+        comment: Some("Uploaded image".to_string()),
+    };
+    log::debug!("Replacing the image of event {id}");
+    repo.update_event(&event, &activity)?;
+    if !clearance_org_ids.is_empty() {
+        let pending_clearance = PendingClearanceForEvent {
+            event_id: event.id.clone(),
+            created_at: activity.activity.at,
+            last_cleared_revision,
+        };
+        super::clearance::event::add_pending_clearance(
+            repo,
+            &clearance_org_ids,
+            &pending_clearance,
+        )?;
+    }
+    Ok((event, clearance_org_ids))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        super::tests::{Builder, MockDb, anonymous_activity, org_with_tag},
+        *,
+    };
+
+    fn event(tags: &[&str]) -> Event {
+        Event::build()
+            .id("x")
+            .title("foo")
+            .tags(tags.to_vec())
+            .finish()
+    }
+
+    #[test]
+    fn set_image_as_new_revision() {
+        let db = MockDb {
+            orgs: vec![org_with_tag("a")],
+            ..Default::default()
+        };
+        db.create_event(event(&["a"]), &anonymous_activity())
+            .unwrap();
+        let url: Url = "https://example.com/images/x.jpg".parse().unwrap();
+        let member: EmailAddress = "member@example.com".parse().unwrap();
+        let (updated, clearance_org_ids) =
+            set_event_image(&db, &db.orgs[0], "x", url.clone(), Some(member.clone())).unwrap();
+        assert_eq!(updated.image_url, Some(url));
+        assert_eq!(updated.revision, Revision::from(1));
+        assert!(clearance_org_ids.is_empty());
+        assert_eq!(db.get_event("x").unwrap(), updated);
+
+        let history = db.get_event_history("x", None).unwrap();
+        let (_, activity) = &history.revisions[0];
+        assert_eq!(activity.activity.by, Some(member));
+        assert_eq!(activity.context.as_deref(), Some("Uploaded by org a"));
+    }
+
+    #[test]
+    fn set_image_of_event_that_requires_clearance() {
+        let clearing_org = Organization {
+            moderated_tags: vec![ModeratedTag {
+                label: "b".into(),
+                allow_add: true,
+                allow_remove: true,
+                require_clearance: true,
+            }],
+            ..org_with_tag("b")
+        };
+        let db = MockDb {
+            orgs: vec![org_with_tag("a"), clearing_org],
+            ..Default::default()
+        };
+        db.create_event(event(&["a", "b"]), &anonymous_activity())
+            .unwrap();
+        let url = "https://example.com/images/x.jpg".parse().unwrap();
+        let (_, clearance_org_ids) = set_event_image(&db, &db.orgs[0], "x", url, None).unwrap();
+        assert_eq!(clearance_org_ids, vec![Id::from("b")]);
+    }
+
+    #[test]
+    fn set_image_of_event_owned_by_another_org() {
+        let db = MockDb {
+            orgs: vec![org_with_tag("a"), org_with_tag("b")],
+            ..Default::default()
+        };
+        db.create_event(event(&["a"]), &anonymous_activity())
+            .unwrap();
+        let url = "https://example.com/images/x.jpg".parse().unwrap();
+        assert!(matches!(
+            set_event_image(&db, &db.orgs[1], "x", url, None),
+            Err(Error::ModeratedTag)
+        ));
+        assert!(db.get_event("x").unwrap().image_url.is_none());
+    }
+}
@@ -1,10 +1,11 @@
 use std::str::FromStr;
 
 use crate::{
+    entities::recurrence::Recurrence,
     repositories::Error as RepoError,
     usecases::{
-        authorize::authorize_editing_of_tagged_entry, create_new_user::create_user_from_email,
-        prelude::*,
+        TranslationParam, authorize::authorize_editing_of_tagged_entry,
+        create_new_user::create_user_from_email, parse_translation_params, prelude::*,
     },
     util::{
         parse::parse_url_param,
@@ -35,6 +36,9 @@ pub struct NewEvent {
     pub organizer      : Option<String>,
     pub image_url      : Option<String>,
     pub image_link_url : Option<String>,
+    pub rrule          : Option<String>,
+    pub exdates        : Option<Vec<Timestamp>>,
+    pub translations   : Vec<TranslationParam>,
 }
 
 // TODO:
@@ -68,6 +72,9 @@ impl NewEvent {
             organizer: None,
             image_url: None,
             image_link_url: None,
+            rrule: None,
+            exdates: None,
+            translations: vec![],
         }
     }
 }
@@ -78,7 +85,18 @@ pub enum NewEventMode<'a> {
 }
 
 #[derive(Debug, Clone)]
-pub struct StorableEvent(Event);
+pub struct StorableEvent {
+    event: Event,
+    clearance_org_ids: Vec<Id>,
+    last_cleared_revision: Option<Revision>,
+}
+
+impl StorableEvent {
+    /// The organizations that need to clear the stored changes.
+    pub fn clearance_org_ids(&self) -> &[Id] {
+        &self.clearance_org_ids
+    }
+}
 
 pub fn import_new_event<R>(
     repo: &R,
@@ -110,7 +128,9 @@ where
         homepage,
         image_url,
         image_link_url,
-        ..
+        rrule,
+        exdates,
+        translations,
     } = e;
     let org = token
         .map(|t| {
@@ -124,7 +144,7 @@ where
         })
         .transpose()?;
     let mut new_tags = super::prepare_tag_list(tags.unwrap_or_default().iter().map(String::as_str));
-    let _clearance_org_ids = if let Some(org) = org {
+    let clearance_org_ids = if let Some(org) = org {
         // Implicitly add missing owned tags to prevent events with
         // undefined ownership!
         let org_tag_count = new_tags
@@ -196,8 +216,6 @@ where
     } else {
         authorize_editing_of_tagged_entry(repo, &[], &new_tags, None)?
     };
-    // TODO: Record pending clearance for events
-    debug_assert!(_clearance_org_ids.is_empty());
     new_tags.sort_unstable();
     new_tags.dedup();
 
@@ -248,9 +266,12 @@ where
         None
     };
 
-    let id = match mode {
-        NewEventMode::Create => Id::new(),
-        NewEventMode::Update(id) => Id::from(id),
+    let (id, revision, last_cleared_revision) = match mode {
+        NewEventMode::Create => (Id::new(), Revision::initial(), None),
+        NewEventMode::Update(id) => {
+            let old_revision = repo.get_event(id)?.revision;
+            (Id::from(id), old_revision.next(), Some(old_revision))
+        }
     };
 
     let created_by = if let Some(email) = created_by {
@@ -309,8 +330,24 @@ where
         .and_then(|ref url| parse_url_param(url).transpose())
         .transpose()?;
 
+    let exdates = exdates.unwrap_or_default();
+    let recurrence = match rrule.as_deref().map(str::trim).filter(|r| !r.is_empty()) {
+        Some(rrule) => Some(Recurrence {
+            rule: rrule.parse()?,
+            exdates,
+        }),
+        None => {
+            if !exdates.is_empty() {
+                // Excluded dates are meaningless without a recurrence rule
+                return Err(Error::InvalidRecurrenceRule);
+            }
+            None
+        }
+    };
+
     let event = Event {
         id,
+        revision,
         title,
         start,
         end,
@@ -324,35 +361,83 @@ where
         archived: None,
         image_url,
         image_link_url,
+        recurrence,
+        translations: parse_translation_params(translations)?,
     };
     let event = event.auto_correct();
     event.validate()?;
-    Ok(StorableEvent(event))
+    Ok(StorableEvent {
+        event,
+        clearance_org_ids,
+        last_cleared_revision,
+    })
 }
 
 pub fn store_created_event<R>(repo: &R, storable: StorableEvent) -> Result<Event>
 where
-    R: TagRepo + EventRepo,
+    R: TagRepo + EventRepo + EventClearanceRepo,
 {
-    let StorableEvent(event) = storable;
+    let StorableEvent {
+        event,
+        clearance_org_ids,
+        last_cleared_revision,
+    } = storable;
     log::debug!("Storing newly created event: {:?}", event);
     for t in &event.tags {
         repo.create_tag_if_it_does_not_exist(&Tag { id: t.clone() })?;
     }
-    repo.create_event(event.clone())?;
+    let activity = ActivityLog {
+        activity: Activity::now(event.created_by.clone()),
+        context: None,
+        comment: None,
+    };
+    repo.create_event(event.clone(), &activity)?;
+    if !clearance_org_ids.is_empty() {
+        let pending_clearance = PendingClearanceForEvent {
+            event_id: event.id.clone(),
+            created_at: activity.activity.at,
+            last_cleared_revision,
+        };
+        super::clearance::event::add_pending_clearance(
+            repo,
+            &clearance_org_ids,
+            &pending_clearance,
+        )?;
+    }
     Ok(event)
 }
 
 pub fn store_updated_event<R>(repo: &R, storable: StorableEvent) -> Result<Event>
 where
-    R: TagRepo + EventRepo,
+    R: TagRepo + EventRepo + EventClearanceRepo,
 {
-    let StorableEvent(event) = storable;
+    let StorableEvent {
+        event,
+        clearance_org_ids,
+        last_cleared_revision,
+    } = storable;
     log::debug!("Storing updated event: {:?}", event);
     for t in &event.tags {
         repo.create_tag_if_it_does_not_exist(&Tag { id: t.clone() })?;
     }
-    repo.update_event(&event)?;
+    let activity = ActivityLog {
+        activity: Activity::now(event.created_by.clone()),
+        context: None,
+        comment: None,
+    };
+    repo.update_event(&event, &activity)?;
+    if !clearance_org_ids.is_empty() {
+        let pending_clearance = PendingClearanceForEvent {
+            event_id: event.id.clone(),
+            created_at: activity.activity.at,
+            last_cleared_revision,
+        };
+        super::clearance::event::add_pending_clearance(
+            repo,
+            &clearance_org_ids,
+            &pending_clearance,
+        )?;
+    }
     Ok(event)
 }
 
@@ -363,7 +448,7 @@ mod tests {
 
     fn create_new_event<R>(repo: &R, token: Option<&str>, e: NewEvent) -> Result<Event>
     where
-        R: OrganizationRepo + UserRepo + EventRepo + TagRepo,
+        R: OrganizationRepo + UserRepo + EventRepo + EventClearanceRepo + TagRepo,
     {
         let s = import_new_event(repo, token, e, NewEventMode::Create)?;
         store_created_event(repo, s)
@@ -394,6 +479,9 @@ mod tests {
             organizer    : None,
             image_url     : Some("http://somewhere.com/image_url.jpg".to_string()),
             image_link_url: Some("my.url/test.ext".to_string()),
+            rrule        : None,
+            exdates      : None,
+            translations: vec![],
         };
         let mock_db = MockDb::default();
         let id = create_new_event(&mock_db, None, x).unwrap().id;
@@ -441,6 +529,9 @@ mod tests {
             organizer    : None,
             image_url     : None,
             image_link_url: None,
+            rrule        : None,
+            exdates      : None,
+            translations: vec![],
         };
         let mock_db: MockDb = MockDb::default();
         assert!(create_new_event(&mock_db, None, x).is_err());
@@ -470,6 +561,9 @@ mod tests {
             organizer    : None,
             image_url     : None,
             image_link_url: None,
+            rrule        : None,
+            exdates      : None,
+            translations: vec![],
         };
         let mock_db: MockDb = MockDb::default();
         assert!(create_new_event(&mock_db, None, x).is_ok());
@@ -516,9 +610,52 @@ mod tests {
             organizer    : None,
             image_url     : None,
             image_link_url: None,
+            rrule        : None,
+            exdates      : None,
+            translations: vec![],
         };
         assert!(create_new_event(&mock_db, None, x).is_ok());
         let users = mock_db.all_users().unwrap();
         assert_eq!(users.len(), 1);
     }
+
+    #[test]
+    fn create_recurring_event() {
+        let mock_db: MockDb = MockDb::default();
+        let start = Timestamp::try_from_secs(1_714_586_400).unwrap();
+        let x = NewEvent {
+            created_by: Some("fooo@bar.tld".parse().unwrap()),
+            rrule: Some("FREQ=WEEKLY;BYDAY=WE".into()),
+            exdates: Some(vec![start + time::Duration::weeks(1)]),
+            ..NewEvent::new("foo".into(), start)
+        };
+        let event = create_new_event(&mock_db, None, x).unwrap();
+        let recurrence = event.recurrence.unwrap();
+        assert_eq!("FREQ=WEEKLY;BYDAY=WE", recurrence.rule.to_string());
+        assert_eq!(vec![start + time::Duration::weeks(1)], recurrence.exdates);
+    }
+
+    #[test]
+    fn create_event_with_invalid_recurrence() {
+        let mock_db: MockDb = MockDb::default();
+        let start = Timestamp::now();
+        let x = NewEvent {
+            created_by: Some("fooo@bar.tld".parse().unwrap()),
+            rrule: Some("every monday".into()),
+            ..NewEvent::new("foo".into(), start)
+        };
+        assert!(matches!(
+            create_new_event(&mock_db, None, x),
+            Err(Error::InvalidRecurrenceRule)
+        ));
+        let x = NewEvent {
+            created_by: Some("fooo@bar.tld".parse().unwrap()),
+            exdates: Some(vec![start]),
+            ..NewEvent::new("foo".into(), start)
+        };
+        assert!(matches!(
+            create_new_event(&mock_db, None, x),
+            Err(Error::InvalidRecurrenceRule)
+        ));
+    }
 }
@@ -1,4 +1,4 @@
-pub use self::new_place_builder::*;
+pub use self::{fixtures::*, new_place_builder::*};
 pub use ofdb_entities::builders::Builder;
 
 pub mod new_place_builder {
@@ -68,8 +68,37 @@ pub mod new_place_builder {
                     image_url: None,
                     image_link_url: None,
                     custom_links: vec![],
+                    translations: vec![],
                 },
             }
         }
     }
 }
+
+pub mod fixtures {
+
+    use crate::usecases::prelude::*;
+
+    /// An organization that owns entries with the given tag.
+    pub fn org_with_tag(tag: &str) -> Organization {
+        Organization {
+            id: tag.into(),
+            name: tag.into(),
+            api_token: tag.into(),
+            moderated_tags: vec![ModeratedTag {
+                label: tag.into(),
+                allow_add: true,
+                allow_remove: false,
+                require_clearance: false,
+            }],
+        }
+    }
+
+    pub fn anonymous_activity() -> ActivityLog {
+        ActivityLog {
+            activity: Activity::now(None),
+            context: None,
+            comment: None,
+        }
+    }
+}
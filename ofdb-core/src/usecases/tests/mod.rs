@@ -75,6 +75,24 @@ impl Key for BboxSubscription {
     }
 }
 
+impl Key for ApiToken {
+    fn key(&self) -> &str {
+        self.id.as_ref()
+    }
+}
+
+impl Key for Webhook {
+    fn key(&self) -> &str {
+        self.id.as_ref()
+    }
+}
+
+impl Key for WebhookDelivery {
+    fn key(&self) -> &str {
+        self.id.as_ref()
+    }
+}
+
 impl Key for Organization {
     fn key(&self) -> &str {
         self.id.as_ref()
@@ -85,14 +103,22 @@ impl Key for Organization {
 pub struct MockDb {
     pub entries: RefCell<Vec<(Place, ReviewStatus)>>,
     pub events: RefCell<Vec<Event>>,
+    pub event_revisions: RefCell<Vec<(Event, ActivityLog)>>,
     pub tags: RefCell<Vec<Tag>>,
     pub users: RefCell<Vec<User>>,
     pub ratings: RefCell<Vec<Rating>>,
+    pub place_redirects: RefCell<Vec<(Id, Id)>>,
+    pub duplicate_candidates: RefCell<Vec<DuplicateCandidate>>,
     pub comments: RefCell<Vec<Comment>>,
     pub bbox_subscriptions: RefCell<Vec<BboxSubscription>>,
     pub orgs: Vec<Organization>,
+    pub api_tokens: RefCell<Vec<ApiToken>>,
+    pub org_members: RefCell<Vec<OrganizationMember>>,
     pub token: RefCell<Vec<UserToken>>,
     pub sent_reminders: RefCell<Vec<(Id, Vec<EmailAddress>, Timestamp)>>,
+    pub changes: RefCell<Vec<Change>>,
+    pub webhooks: RefCell<Vec<Webhook>>,
+    pub webhook_deliveries: RefCell<Vec<WebhookDelivery>>,
 }
 
 impl UserTokenRepo for MockDb {
@@ -154,6 +180,10 @@ impl IdIndex for DummySearchEngine {
     ) -> Fallible<Vec<Id>> {
         unimplemented!();
     }
+
+    fn all_indexed_entries(&self) -> Fallible<Vec<IndexedEntry>> {
+        unimplemented!();
+    }
 }
 
 impl IdIndexer for DummySearchEngine {
@@ -166,6 +196,18 @@ impl PlaceIndex for DummySearchEngine {
     fn query_places(&self, _query: &IndexQuery, _limit: usize) -> Fallible<Vec<IndexedPlace>> {
         unimplemented!();
     }
+
+    fn count_place_tags(&self, _query: &IndexQuery) -> Fallible<Vec<TagFrequency>> {
+        unimplemented!();
+    }
+
+    fn cluster_places(
+        &self,
+        _query: &IndexQuery,
+        _cell_size_deg: f64,
+    ) -> Fallible<Vec<PlaceCluster>> {
+        unimplemented!();
+    }
 }
 
 impl PlaceIndexer for DummySearchEngine {
@@ -286,11 +328,18 @@ impl PlaceRepo for MockDb {
 
     fn review_places(
         &self,
-        _ids: &[&str],
-        _status: ReviewStatus,
+        ids: &[&str],
+        status: ReviewStatus,
         _activity: &ActivityLog,
     ) -> RepoResult<usize> {
-        unimplemented!();
+        let mut count = 0;
+        for (p, s) in self.entries.borrow_mut().iter_mut() {
+            if *s != status && ids.iter().any(|id| p.id.as_str() == *id) {
+                *s = status;
+                count += 1;
+            }
+        }
+        Ok(count)
     }
 
     fn get_place_history(
@@ -307,8 +356,12 @@ impl PlaceRepo for MockDb {
 }
 
 impl EventRepo for MockDb {
-    fn create_event(&self, e: Event) -> RepoResult<()> {
-        create(&mut self.events.borrow_mut(), e)
+    fn create_event(&self, e: Event, activity: &ActivityLog) -> RepoResult<()> {
+        create(&mut self.events.borrow_mut(), e.clone())?;
+        self.event_revisions
+            .borrow_mut()
+            .push((e, activity.clone()));
+        Ok(())
     }
 
     fn get_event(&self, id: &str) -> RepoResult<Event> {
@@ -333,6 +386,10 @@ impl EventRepo for MockDb {
         Ok(events)
     }
 
+    fn all_archived_events_chronologically(&self) -> RepoResult<Vec<Event>> {
+        unimplemented!();
+    }
+
     fn get_events_chronologically(&self, ids: &[&str]) -> RepoResult<Vec<Event>> {
         let mut events: Vec<_> = self
             .events
@@ -350,8 +407,16 @@ impl EventRepo for MockDb {
         self.all_events_chronologically().map(|v| v.len())
     }
 
-    fn update_event(&self, e: &Event) -> RepoResult<()> {
-        update(&mut self.events.borrow_mut(), e)
+    fn update_event(&self, e: &Event, activity: &ActivityLog) -> RepoResult<()> {
+        let old = get(&self.events.borrow(), e.id.as_ref())?;
+        if old.revision.next() != e.revision {
+            return Err(RepoError::InvalidVersion);
+        }
+        update(&mut self.events.borrow_mut(), e)?;
+        self.event_revisions
+            .borrow_mut()
+            .push((e.clone(), activity.clone()));
+        Ok(())
     }
 
     fn archive_events(&self, _ids: &[&str], _archived: Timestamp) -> RepoResult<usize> {
@@ -365,6 +430,30 @@ impl EventRepo for MockDb {
     fn is_event_owned_by_any_organization(&self, _id: &str) -> RepoResult<bool> {
         unimplemented!();
     }
+
+    fn get_event_history(&self, id: &str, revision: Option<Revision>) -> RepoResult<EventHistory> {
+        let revisions: Vec<_> = self
+            .event_revisions
+            .borrow()
+            .iter()
+            .rev()
+            .filter(|(e, _)| e.id.as_str() == id)
+            .filter(|(e, _)| revision.is_none() || Some(e.revision) == revision)
+            .cloned()
+            .collect();
+        if revisions.is_empty() {
+            return Err(RepoError::NotFound);
+        }
+        Ok(EventHistory {
+            id: id.into(),
+            revisions,
+        })
+    }
+
+    fn load_event_revision(&self, id: &str, rev: Revision) -> RepoResult<Event> {
+        self.get_event_history(id, Some(rev))
+            .map(|mut h| h.revisions.swap_remove(0).0)
+    }
 }
 
 impl UserRepo for MockDb {
@@ -439,6 +528,10 @@ impl CommentRepository for MockDb {
             .collect())
     }
 
+    fn load_all_comments_of_rating(&self, _rating_id: &str) -> RepoResult<Vec<Comment>> {
+        unimplemented!();
+    }
+
     fn archive_comments(&self, _ids: &[&str], _activity: &Activity) -> RepoResult<usize> {
         unimplemented!();
     }
@@ -462,6 +555,33 @@ impl OrganizationRepo for MockDb {
     fn create_org(&mut self, o: Organization) -> RepoResult<()> {
         create(&mut self.orgs, o)
     }
+    fn update_org(&mut self, o: Organization) -> RepoResult<()> {
+        let existing = self
+            .orgs
+            .iter_mut()
+            .find(|x| x.id == o.id)
+            .ok_or(RepoError::NotFound)?;
+        *existing = Organization {
+            api_token: existing.api_token.clone(),
+            ..o
+        };
+        Ok(())
+    }
+    fn delete_org(&mut self, id: &Id) -> RepoResult<()> {
+        let count = self.orgs.len();
+        self.orgs.retain(|o| o.id != *id);
+        if self.orgs.len() == count {
+            return Err(RepoError::NotFound);
+        }
+        self.org_members.borrow_mut().retain(|m| m.org_id != *id);
+        Ok(())
+    }
+    fn get_org_by_id(&self, id: &Id) -> RepoResult<Organization> {
+        get(&self.orgs, id.as_ref())
+    }
+    fn all_orgs(&self) -> RepoResult<Vec<Organization>> {
+        Ok(self.orgs.clone())
+    }
     fn get_org_by_api_token(&self, token: &str) -> RepoResult<Organization> {
         let o = self
             .orgs
@@ -534,6 +654,10 @@ impl RatingRepository for MockDb {
             .collect())
     }
 
+    fn load_all_ratings_of_place(&self, _place_id: &str) -> RepoResult<Vec<Rating>> {
+        unimplemented!();
+    }
+
     fn load_place_ids_of_ratings(&self, _ids: &[&str]) -> RepoResult<Vec<String>> {
         unimplemented!();
     }
@@ -547,6 +671,37 @@ impl RatingRepository for MockDb {
     ) -> RepoResult<usize> {
         unimplemented!();
     }
+    fn move_ratings_of_place(&self, from_place_id: &Id, to_place_id: &Id) -> RepoResult<usize> {
+        let mut count = 0;
+        for r in self.ratings.borrow_mut().iter_mut() {
+            if &r.place_id == from_place_id {
+                r.place_id = to_place_id.clone();
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+}
+
+impl PlaceRedirectRepo for MockDb {
+    fn create_place_redirect(&self, merged_place_id: &Id, place_id: &Id) -> RepoResult<()> {
+        let mut redirects = self.place_redirects.borrow_mut();
+        for (_, to) in redirects.iter_mut() {
+            if to == merged_place_id {
+                *to = place_id.clone();
+            }
+        }
+        redirects.push((merged_place_id.clone(), place_id.clone()));
+        Ok(())
+    }
+    fn resolve_place_redirect(&self, id: &Id) -> RepoResult<Option<Id>> {
+        Ok(self
+            .place_redirects
+            .borrow()
+            .iter()
+            .find(|(from, _)| from == id)
+            .map(|(_, to)| to.clone()))
+    }
 }
 
 impl PlaceClearanceRepo for MockDb {
@@ -582,6 +737,7 @@ impl PlaceClearanceRepo for MockDb {
         &self,
         _org_id: &Id,
         _clearances: &[ClearanceForPlace],
+        _activity: &ActivityLog,
     ) -> RepoResult<usize> {
         Ok(0)
     }
@@ -589,6 +745,321 @@ impl PlaceClearanceRepo for MockDb {
     fn cleanup_pending_clearances_for_places(&self, _org_id: &Id) -> RepoResult<u64> {
         Ok(0)
     }
+
+    fn list_clearance_log_for_places(
+        &self,
+        _org_id: &Id,
+        _pagination: &Pagination,
+    ) -> RepoResult<Vec<ClearanceLogForPlace>> {
+        Ok(vec![])
+    }
+
+    fn add_clearance_log_for_places(
+        &self,
+        _org_id: &Id,
+        _log: &ClearanceLogForPlace,
+    ) -> RepoResult<()> {
+        unimplemented!();
+    }
+}
+
+impl DuplicateCandidateRepo for MockDb {
+    fn replace_open_duplicate_candidates(
+        &self,
+        candidates: &[DuplicateCandidate],
+    ) -> RepoResult<usize> {
+        let mut existing = self.duplicate_candidates.borrow_mut();
+        existing.retain(|c| {
+            c.state != DuplicateCandidateState::Open
+                || candidates.iter().any(|new| {
+                    new.place_id == c.place_id && new.duplicate_place_id == c.duplicate_place_id
+                })
+        });
+        let mut count = 0;
+        for new in candidates {
+            if let Some(c) = existing.iter_mut().find(|c| {
+                c.place_id == new.place_id && c.duplicate_place_id == new.duplicate_place_id
+            }) {
+                if c.state == DuplicateCandidateState::Open {
+                    c.similarity = new.similarity;
+                }
+                continue;
+            }
+            existing.push(new.clone());
+            count += 1;
+        }
+        Ok(count)
+    }
+    fn count_open_duplicate_candidates(&self) -> RepoResult<usize> {
+        Ok(self
+            .duplicate_candidates
+            .borrow()
+            .iter()
+            .filter(|c| c.state == DuplicateCandidateState::Open)
+            .count())
+    }
+    fn list_open_duplicate_candidates(
+        &self,
+        _pagination: &Pagination,
+    ) -> RepoResult<Vec<DuplicateCandidate>> {
+        unimplemented!();
+    }
+    fn review_duplicate_candidate(
+        &self,
+        place_id: &Id,
+        duplicate_place_id: &Id,
+        state: DuplicateCandidateState,
+        activity: &Activity,
+    ) -> RepoResult<usize> {
+        let mut candidates = self.duplicate_candidates.borrow_mut();
+        let Some(c) = candidates.iter_mut().find(|c| {
+            c.state == DuplicateCandidateState::Open
+                && &c.place_id == place_id
+                && &c.duplicate_place_id == duplicate_place_id
+        }) else {
+            return Ok(0);
+        };
+        c.state = state;
+        c.reviewed = Some(activity.clone());
+        Ok(1)
+    }
+}
+
+impl OrganizationMemberRepo for MockDb {
+    fn add_or_update_org_member(&self, member: &OrganizationMember) -> RepoResult<()> {
+        let mut members = self.org_members.borrow_mut();
+        members.retain(|m| m.org_id != member.org_id || m.email != member.email);
+        members.push(member.clone());
+        Ok(())
+    }
+    fn remove_org_member(&self, org_id: &Id, email: &EmailAddress) -> RepoResult<()> {
+        let mut members = self.org_members.borrow_mut();
+        let count = members.len();
+        members.retain(|m| &m.org_id != org_id || &m.email != email);
+        if members.len() == count {
+            return Err(RepoError::NotFound);
+        }
+        Ok(())
+    }
+    fn list_org_members(&self, org_id: &Id) -> RepoResult<Vec<OrganizationMember>> {
+        Ok(self
+            .org_members
+            .borrow()
+            .iter()
+            .filter(|m| &m.org_id == org_id)
+            .cloned()
+            .collect())
+    }
+    fn list_org_memberships_of_user(
+        &self,
+        email: &EmailAddress,
+    ) -> RepoResult<Vec<OrganizationMember>> {
+        Ok(self
+            .org_members
+            .borrow()
+            .iter()
+            .filter(|m| &m.email == email)
+            .cloned()
+            .collect())
+    }
+}
+
+impl ApiTokenRepo for MockDb {
+    fn create_api_token(&self, api_token: &ApiToken) -> RepoResult<()> {
+        create(&mut self.api_tokens.borrow_mut(), api_token.clone())
+    }
+    fn get_api_token_by_hash(&self, token_hash: &str) -> RepoResult<ApiToken> {
+        self.api_tokens
+            .borrow()
+            .iter()
+            .find(|t| t.token_hash == token_hash)
+            .cloned()
+            .ok_or(RepoError::NotFound)
+    }
+    fn list_api_tokens_of_org(&self, org_id: &Id) -> RepoResult<Vec<ApiToken>> {
+        Ok(self
+            .api_tokens
+            .borrow()
+            .iter()
+            .filter(|t| &t.org_id == org_id)
+            .cloned()
+            .collect())
+    }
+    fn revoke_api_token(&self, org_id: &Id, id: &Id, revoked_at: Timestamp) -> RepoResult<()> {
+        let mut api_tokens = self.api_tokens.borrow_mut();
+        let api_token = api_tokens
+            .iter_mut()
+            .find(|t| &t.org_id == org_id && &t.id == id)
+            .ok_or(RepoError::NotFound)?;
+        api_token.revoked_at.get_or_insert(revoked_at);
+        Ok(())
+    }
+    fn update_api_token_last_used(&self, id: &Id, last_used_at: Timestamp) -> RepoResult<()> {
+        let mut api_tokens = self.api_tokens.borrow_mut();
+        let api_token = api_tokens
+            .iter_mut()
+            .find(|t| &t.id == id)
+            .ok_or(RepoError::NotFound)?;
+        api_token.last_used_at = Some(last_used_at);
+        Ok(())
+    }
+}
+
+impl WebhookRepo for MockDb {
+    fn create_webhook(&self, webhook: &Webhook) -> RepoResult<()> {
+        create(&mut self.webhooks.borrow_mut(), webhook.clone())
+    }
+    fn get_webhook(&self, id: &Id) -> RepoResult<Webhook> {
+        get(&self.webhooks.borrow(), id.as_ref())
+    }
+    fn list_webhooks_of_org(&self, org_id: &Id) -> RepoResult<Vec<Webhook>> {
+        Ok(self
+            .webhooks
+            .borrow()
+            .iter()
+            .filter(|w| &w.org_id == org_id)
+            .cloned()
+            .collect())
+    }
+    fn delete_webhook(&self, org_id: &Id, id: &Id) -> RepoResult<()> {
+        let mut webhooks = self.webhooks.borrow_mut();
+        let len_before = webhooks.len();
+        webhooks.retain(|w| !(&w.org_id == org_id && &w.id == id));
+        if webhooks.len() == len_before {
+            return Err(RepoError::NotFound);
+        }
+        self.webhook_deliveries
+            .borrow_mut()
+            .retain(|d| &d.webhook_id != id);
+        Ok(())
+    }
+    fn create_webhook_delivery(&self, delivery: &WebhookDelivery) -> RepoResult<()> {
+        create(&mut self.webhook_deliveries.borrow_mut(), delivery.clone())
+    }
+    fn update_webhook_delivery(&self, delivery: &WebhookDelivery) -> RepoResult<()> {
+        update(&mut self.webhook_deliveries.borrow_mut(), delivery)
+    }
+    fn claim_webhook_delivery(
+        &self,
+        delivery: &WebhookDelivery,
+        claimed_until: Timestamp,
+    ) -> RepoResult<bool> {
+        let mut deliveries = self.webhook_deliveries.borrow_mut();
+        let Some(stored) = deliveries.iter_mut().find(|d| {
+            d.id == delivery.id
+                && d.status == WebhookDeliveryStatus::Pending
+                && d.attempts == delivery.attempts
+                && d.next_attempt_at.is_some()
+                && d.next_attempt_at == delivery.next_attempt_at
+        }) else {
+            return Ok(false);
+        };
+        stored.next_attempt_at = Some(claimed_until);
+        Ok(true)
+    }
+    fn load_due_webhook_deliveries(
+        &self,
+        due_at: Timestamp,
+        limit: u64,
+    ) -> RepoResult<Vec<WebhookDelivery>> {
+        Ok(self
+            .webhook_deliveries
+            .borrow()
+            .iter()
+            .filter(|d| {
+                d.status == WebhookDeliveryStatus::Pending
+                    && d.next_attempt_at.is_some_and(|at| at <= due_at)
+            })
+            .take(limit as usize)
+            .cloned()
+            .collect())
+    }
+    fn list_webhook_deliveries(
+        &self,
+        webhook_id: &Id,
+        _pagination: &Pagination,
+    ) -> RepoResult<Vec<WebhookDelivery>> {
+        Ok(self
+            .webhook_deliveries
+            .borrow()
+            .iter()
+            .rev()
+            .filter(|d| &d.webhook_id == webhook_id)
+            .cloned()
+            .collect())
+    }
+}
+
+impl ChangeRepo for MockDb {
+    fn load_changes(&self, after: Option<&ChangeCursor>, limit: u64) -> RepoResult<Vec<Change>> {
+        Ok(self
+            .changes
+            .borrow()
+            .iter()
+            .filter(|c| after.is_none_or(|after| &c.cursor > after))
+            .take(limit as usize)
+            .cloned()
+            .collect())
+    }
+}
+
+impl EventClearanceRepo for MockDb {
+    fn add_pending_clearance_for_events(
+        &self,
+        org_ids: &[Id],
+        _pending_clearance: &PendingClearanceForEvent,
+    ) -> RepoResult<usize> {
+        Ok(org_ids.len())
+    }
+
+    fn count_pending_clearances_for_events(&self, _org_id: &Id) -> RepoResult<u64> {
+        Ok(0)
+    }
+
+    fn list_pending_clearances_for_events(
+        &self,
+        _org_id: &Id,
+        _pagination: &Pagination,
+    ) -> RepoResult<Vec<PendingClearanceForEvent>> {
+        Ok(vec![])
+    }
+
+    fn load_pending_clearances_for_events(
+        &self,
+        _org_id: &Id,
+        _event_ids: &[&str],
+    ) -> RepoResult<Vec<PendingClearanceForEvent>> {
+        Ok(vec![])
+    }
+
+    fn update_pending_clearances_for_events(
+        &self,
+        _org_id: &Id,
+        _clearances: &[ClearanceForEvent],
+        _activity: &ActivityLog,
+    ) -> RepoResult<usize> {
+        Ok(0)
+    }
+
+    fn cleanup_pending_clearances_for_events(&self, _org_id: &Id) -> RepoResult<u64> {
+        Ok(0)
+    }
+
+    fn list_clearance_log_for_events(
+        &self,
+        _org_id: &Id,
+        _pagination: &Pagination,
+    ) -> RepoResult<Vec<ClearanceLogForEvent>> {
+        Ok(vec![])
+    }
+
+    fn add_clearance_log_for_events(
+        &self,
+        _org_id: &Id,
+        _log: &ClearanceLogForEvent,
+    ) -> RepoResult<()> {
+        unimplemented!();
+    }
 }
 
 impl TagRepo for MockDb {
@@ -890,22 +1361,32 @@ fn receive_event_with_creators_email() {
         role: Role::Guest,
     })
     .unwrap();
-    db.create_event(Event {
-        id: "x".into(),
-        title: "t".into(),
-        description: None,
-        start: Timestamp::try_from_secs(0).unwrap(),
-        end: None,
-        contact: None,
-        location: None,
-        homepage: None,
-        tags: vec![],
-        created_by: Some(EmailAddress::new_unchecked("abc@abc.de".to_string())),
-        registration: None,
-        archived: None,
-        image_url: None,
-        image_link_url: None,
-    })
+    db.create_event(
+        Event {
+            id: "x".into(),
+            revision: Revision::initial(),
+            title: "t".into(),
+            description: None,
+            start: Timestamp::try_from_secs(0).unwrap(),
+            end: None,
+            contact: None,
+            location: None,
+            homepage: None,
+            tags: vec![],
+            created_by: Some(EmailAddress::new_unchecked("abc@abc.de".to_string())),
+            registration: None,
+            archived: None,
+            image_url: None,
+            image_link_url: None,
+            recurrence: None,
+            translations: vec![],
+        },
+        &ActivityLog {
+            activity: Activity::now(None),
+            context: None,
+            comment: None,
+        },
+    )
     .unwrap();
     let e = usecases::get_event(&db, "x").unwrap();
     assert_eq!(e.created_by.unwrap().as_str(), "abc@abc.de");
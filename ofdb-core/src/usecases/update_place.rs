@@ -5,8 +5,8 @@ use time::Date;
 use crate::{
     repositories::Error as RepoError,
     usecases::{
-        CustomLinkParam, authorize::authorize_editing_of_tagged_entry, parse_custom_link_param,
-        prelude::*,
+        CustomLinkParam, TranslationParam, authorize::authorize_editing_of_tagged_entry,
+        parse_custom_link_param, parse_opening_hours_param, parse_translation_params, prelude::*,
     },
     util::{parse::parse_url_param, validate::Validate},
 };
@@ -35,6 +35,7 @@ pub struct UpdatePlace {
     pub image_url      : Option<String>,
     pub image_link_url : Option<String>,
     pub custom_links   : Vec<CustomLinkParam>,
+    pub translations   : Vec<TranslationParam>,
 }
 
 impl From<Place> for UpdatePlace {
@@ -52,6 +53,7 @@ impl From<Place> for UpdatePlace {
             revision,
             tags,
             title,
+            translations,
         } = from;
         let (city, country, state, street, zip) = address
             .map(|a| (a.city, a.country, a.state, a.street, a.zip))
@@ -89,6 +91,7 @@ impl From<Place> for UpdatePlace {
             tags,
             telephone,
             title,
+            translations: translations.into_iter().map(Into::into).collect(),
             version: revision.into(),
             zip,
         }
@@ -101,6 +104,13 @@ pub struct Storable {
     last_cleared_revision: Revision,
 }
 
+impl Storable {
+    /// The organizations that need to clear the stored changes.
+    pub fn clearance_org_ids(&self) -> &[Id] {
+        &self.clearance_org_ids
+    }
+}
+
 pub fn prepare_updated_place<R>(
     repo: &R,
     place_id: Id,
@@ -134,6 +144,7 @@ where
         image_url,
         image_link_url,
         custom_links: custom_links_param,
+        translations,
         ..
     } = e;
     let pos = MapPoint::try_from_lat_lng_deg(lat, lng).map_err(|_| Error::InvalidPosition)?;
@@ -150,7 +161,7 @@ where
         Some(address)
     };
 
-    let (revision, last_cleared_revision, old_tags, license) = {
+    let (revision, last_cleared_revision, old_tags, old_opening_hours, license) = {
         let (old_place, _review_status) = repo.get_place(place_id.as_str())?;
         // Check for revision conflict (optimistic locking)
         let revision = Revision::from(version);
@@ -162,7 +173,15 @@ where
         let license = old_place.license;
         // The existing tags are needed for authorization
         let old_tags = old_place.tags;
-        (revision, last_cleared_revision, old_tags, license)
+        // Unmodified legacy opening hours are preserved
+        let old_opening_hours = old_place.opening_hours;
+        (
+            revision,
+            last_cleared_revision,
+            old_tags,
+            old_opening_hours,
+            license,
+        )
     };
 
     let categories: Vec<_> = categories.into_iter().map(Id::from).collect();
@@ -214,11 +233,12 @@ where
             phone,
         }),
         opening_hours: opening_hours
-            .map(|s| s.parse().map_err(|_| Error::InvalidOpeningHours))
+            .map(|s| parse_opening_hours_param(&s, old_opening_hours.as_ref()))
             .transpose()?,
         founded_on,
         links,
         tags: new_tags,
+        translations: parse_translation_params(translations)?,
     };
     place.validate()?;
     if !accepted_licenses.contains(&place.license) {
@@ -302,6 +322,7 @@ mod tests {
             image_url     : Some("img2".into()),
             image_link_url: old.links.as_ref().and_then(|l| l.image_href.as_ref()).map(|url| url.as_str().to_string()),
             custom_links: vec![],
+            translations: vec![],
         };
         let mock_db = MockDb {
             entries: vec![(old, ReviewStatus::Created)].into(),
@@ -353,6 +374,89 @@ mod tests {
         );
     }
 
+    #[test]
+    fn update_place_with_legacy_opening_hours() {
+        let id = Id::new();
+        let mut old = Place::build()
+            .id(id.as_ref())
+            .revision(1)
+            .title("foo")
+            .description("bar")
+            .license("ODbL-1.0")
+            .finish();
+        old.opening_hours = Some("Mo-Fr 9-18 Uhr".parse().unwrap());
+
+        #[rustfmt::skip]
+        let new = UpdatePlace {
+            version     : 2,
+            title       : "foo".into(),
+            description : "bar".into(),
+            lat         : 0.0,
+            lng         : 0.0,
+            street      : None,
+            zip         : None,
+            city        : None,
+            country     : None,
+            state       : None,
+            contact_name: None,
+            email       : None,
+            telephone   : None,
+            homepage    : None,
+            opening_hours: Some("Mo-Fr 9-18 Uhr".into()),
+            founded_on  : None,
+            categories  : vec![],
+            tags        : vec![],
+            image_url     : None,
+            image_link_url: None,
+            custom_links: vec![],
+            translations: vec![],
+        };
+        let mock_db = MockDb {
+            entries: vec![(old, ReviewStatus::Created)].into(),
+            ..Default::default()
+        };
+        // Unmodified legacy values are preserved
+        assert!(
+            prepare_updated_place(
+                &mock_db,
+                id.clone(),
+                new.clone(),
+                None,
+                None,
+                &accepted_licenses()
+            )
+            .is_ok()
+        );
+        // Modified values must be valid
+        let err = prepare_updated_place(
+            &mock_db,
+            id.clone(),
+            UpdatePlace {
+                opening_hours: Some("Mo-Sa 9-18 Uhr".into()),
+                ..new.clone()
+            },
+            None,
+            None,
+            &accepted_licenses(),
+        )
+        .err();
+        assert!(matches!(err, Some(Error::InvalidOpeningHours)));
+        assert!(
+            prepare_updated_place(
+                &mock_db,
+                id,
+                UpdatePlace {
+                    opening_hours: Some("Mo-Sa 09:00-18:00".into()),
+                    ..new
+                },
+                None,
+                None,
+                &accepted_licenses()
+            )
+            .is_ok()
+        );
+    }
+
     #[test]
     fn update_place_with_invalid_version() {
         let id = Id::new();
@@ -387,6 +491,7 @@ mod tests {
             image_url     : None,
             image_link_url: None,
             custom_links: vec![],
+            translations: vec![],
         };
         let mock_db = MockDb {
             entries: vec![(old, ReviewStatus::Created)].into(),
@@ -434,6 +539,7 @@ mod tests {
             image_url     : None,
             image_link_url: None,
             custom_links: vec![],
+            translations: vec![],
         };
         let mock_db = MockDb::default();
         let result = prepare_updated_place(&mock_db, id, new, None, None, &accepted_licenses());
@@ -481,6 +587,7 @@ mod tests {
             image_url     : None,
             image_link_url: None,
             custom_links: vec![],
+            translations: vec![],
         };
         let mock_db = MockDb {
             entries: vec![(old, ReviewStatus::Created)].into(),
@@ -0,0 +1,401 @@
+use time::Duration;
+
+use super::prelude::*;
+use crate::{gateways::webhook::WebhookGateway, repositories::Error as RepoError};
+
+/// Failed deliveries are retried until this number of attempts is reached.
+pub const MAX_WEBHOOK_DELIVERY_ATTEMPTS: u32 = 8;
+
+/// The delay before the first retry that is doubled on each subsequent attempt.
+const WEBHOOK_RETRY_BASE_DELAY: Duration = Duration::minutes(1);
+
+/// Claimed deliveries are attempted again after this period if
+/// the outcome of the attempt has not been recorded until then,
+/// e.g. because the server has been stopped in the meantime.
+const WEBHOOK_DELIVERY_CLAIM_PERIOD: Duration = Duration::minutes(5);
+
+/// Register a new webhook for an organization.
+///
+/// The returned webhook contains the generated secret
+/// that is needed by the receiver to verify the payloads.
+pub fn create_webhook<R>(repo: &R, org_id: &Id, url: &str, created_at: Timestamp) -> Result<Webhook>
+where
+    R: OrganizationRepo + WebhookRepo,
+{
+    let url = url.trim().parse::<Url>()?;
+    if !(url.as_str().starts_with("https://") || url.as_str().starts_with("http://")) {
+        return Err(Error::Url);
+    }
+    let org = repo.get_org_by_id(org_id)?;
+    let webhook = Webhook {
+        id: Id::new(),
+        org_id: org.id,
+        url,
+        secret: Webhook::generate_secret(),
+        created_at,
+    };
+    repo.create_webhook(&webhook)?;
+    Ok(webhook)
+}
+
+pub fn list_webhooks<R>(repo: &R, org_id: &Id) -> Result<Vec<Webhook>>
+where
+    R: OrganizationRepo + WebhookRepo,
+{
+    let org = repo.get_org_by_id(org_id)?;
+    Ok(repo.list_webhooks_of_org(&org.id)?)
+}
+
+pub fn delete_webhook<R>(repo: &R, org_id: &Id, id: &Id) -> Result<()>
+where
+    R: WebhookRepo,
+{
+    Ok(repo.delete_webhook(org_id, id)?)
+}
+
+pub fn list_webhook_deliveries<R>(
+    repo: &R,
+    org_id: &Id,
+    webhook_id: &Id,
+    pagination: &Pagination,
+) -> Result<Vec<WebhookDelivery>>
+where
+    R: WebhookRepo,
+{
+    let webhook = repo.get_webhook(webhook_id)?;
+    if &webhook.org_id != org_id {
+        return Err(Error::Repo(RepoError::NotFound));
+    }
+    Ok(repo.list_webhook_deliveries(&webhook.id, pagination)?)
+}
+
+/// The organizations that moderate at least one of the tags.
+pub fn org_ids_by_moderated_tags<R>(repo: &R, tags: &[String]) -> Result<Vec<Id>>
+where
+    R: OrganizationRepo,
+{
+    let mut org_ids: Vec<_> = repo
+        .get_moderated_tags_by_org(None)?
+        .into_iter()
+        .filter(|(_, moderated_tag)| tags.contains(&moderated_tag.label))
+        .map(|(org_id, _)| org_id)
+        .collect();
+    org_ids.sort_unstable();
+    org_ids.dedup();
+    Ok(org_ids)
+}
+
+/// Schedule the delivery of a payload to all webhooks of the organizations.
+///
+/// Returns the number of scheduled deliveries.
+pub fn enqueue_webhook_deliveries<R>(
+    repo: &R,
+    org_ids: &[Id],
+    event_type: WebhookEventType,
+    payload: &str,
+    created_at: Timestamp,
+) -> Result<usize>
+where
+    R: WebhookRepo,
+{
+    let mut count = 0;
+    for org_id in org_ids {
+        for webhook in repo.list_webhooks_of_org(org_id)? {
+            let delivery = WebhookDelivery {
+                id: Id::new(),
+                webhook_id: webhook.id,
+                event_type,
+                payload: payload.to_owned(),
+                created_at,
+                status: WebhookDeliveryStatus::Pending,
+                attempts: 0,
+                next_attempt_at: Some(created_at),
+                last_attempt_at: None,
+                last_response_status: None,
+                last_error: None,
+            };
+            repo.create_webhook_delivery(&delivery)?;
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+/// Load the pending deliveries that are due together with their webhooks.
+pub fn load_due_webhook_deliveries<R>(
+    repo: &R,
+    due_at: Timestamp,
+    limit: u64,
+) -> Result<Vec<(Webhook, WebhookDelivery)>>
+where
+    R: WebhookRepo,
+{
+    repo.load_due_webhook_deliveries(due_at, limit)?
+        .into_iter()
+        .map(|delivery| {
+            let webhook = repo.get_webhook(&delivery.webhook_id)?;
+            Ok((webhook, delivery))
+        })
+        .collect()
+}
+
+/// Claim a due delivery before attempting it.
+///
+/// Prevents that multiple server instances send the same delivery
+/// concurrently. Returns `None` if the delivery has already been
+/// claimed by another instance.
+pub fn claim_webhook_delivery<R>(
+    repo: &R,
+    mut delivery: WebhookDelivery,
+    claimed_at: Timestamp,
+) -> Result<Option<WebhookDelivery>>
+where
+    R: WebhookRepo,
+{
+    let claimed_until = claimed_at + WEBHOOK_DELIVERY_CLAIM_PERIOD;
+    if !repo.claim_webhook_delivery(&delivery, claimed_until)? {
+        return Ok(None);
+    }
+    delivery.next_attempt_at = Some(claimed_until);
+    Ok(Some(delivery))
+}
+
+/// Send the payload to the webhook and record the outcome of the attempt.
+///
+/// The updated delivery still needs to be stored.
+pub fn attempt_webhook_delivery<G>(
+    gateway: &G,
+    webhook: &Webhook,
+    mut delivery: WebhookDelivery,
+    attempted_at: Timestamp,
+) -> WebhookDelivery
+where
+    G: WebhookGateway + ?Sized,
+{
+    delivery.attempts += 1;
+    delivery.last_attempt_at = Some(attempted_at);
+    let (response_status, error) = match gateway.deliver(webhook, &delivery) {
+        Ok(status) if (200..300).contains(&status) => (Some(status), None),
+        Ok(status) => (
+            Some(status),
+            Some(format!("Unexpected response status {status}")),
+        ),
+        Err(err) => (None, Some(err.to_string())),
+    };
+    delivery.last_response_status = response_status;
+    delivery.last_error = error;
+    if delivery.last_error.is_none() {
+        delivery.status = WebhookDeliveryStatus::Delivered;
+        delivery.next_attempt_at = None;
+    } else if delivery.attempts >= MAX_WEBHOOK_DELIVERY_ATTEMPTS {
+        delivery.status = WebhookDeliveryStatus::Failed;
+        delivery.next_attempt_at = None;
+    } else {
+        delivery.next_attempt_at = Some(attempted_at + webhook_retry_delay(delivery.attempts));
+    }
+    delivery
+}
+
+/// Exponential backoff after the given number of failed attempts.
+pub fn webhook_retry_delay(failed_attempts: u32) -> Duration {
+    WEBHOOK_RETRY_BASE_DELAY * 2_i32.pow(failed_attempts.saturating_sub(1).min(16))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::tests::MockDb, *};
+    use std::cell::RefCell;
+
+    fn db() -> MockDb {
+        MockDb {
+            orgs: vec![
+                Organization {
+                    id: "foo".into(),
+                    name: "foo".into(),
+                    api_token: "foo".into(),
+                    moderated_tags: vec![ModeratedTag {
+                        label: "foo".into(),
+                        allow_add: true,
+                        allow_remove: true,
+                        require_clearance: false,
+                    }],
+                },
+                Organization {
+                    id: "bar".into(),
+                    name: "bar".into(),
+                    api_token: "bar".into(),
+                    moderated_tags: vec![],
+                },
+            ],
+            ..Default::default()
+        }
+    }
+
+    struct MockGateway {
+        responses: RefCell<Vec<anyhow::Result<u16>>>,
+    }
+
+    impl WebhookGateway for MockGateway {
+        fn deliver(&self, _: &Webhook, _: &WebhookDelivery) -> anyhow::Result<u16> {
+            self.responses.borrow_mut().remove(0)
+        }
+    }
+
+    #[test]
+    fn create_with_invalid_url() {
+        let db = db();
+        let now = Timestamp::now();
+        let org_id = Id::from("foo");
+        assert!(matches!(
+            create_webhook(&db, &org_id, "not a url", now),
+            Err(Error::Url)
+        ));
+        assert!(matches!(
+            create_webhook(&db, &org_id, "ftp://example.org/hook", now),
+            Err(Error::Url)
+        ));
+        assert!(matches!(
+            create_webhook(&db, &"baz".into(), "https://example.org/hook", now),
+            Err(Error::Repo(RepoError::NotFound))
+        ));
+        assert!(db.webhooks.borrow().is_empty());
+    }
+
+    #[test]
+    fn enqueue_for_organizations_with_moderated_tags() {
+        let db = db();
+        let now = Timestamp::now();
+        let foo = Id::from("foo");
+        let bar = Id::from("bar");
+        create_webhook(&db, &foo, "https://example.org/a", now).unwrap();
+        create_webhook(&db, &foo, "https://example.org/b", now).unwrap();
+        create_webhook(&db, &bar, "https://example.org/c", now).unwrap();
+
+        let tags = vec!["foo".to_string(), "baz".to_string()];
+        let org_ids = org_ids_by_moderated_tags(&db, &tags).unwrap();
+        assert_eq!(org_ids, vec![foo.clone()]);
+
+        let count =
+            enqueue_webhook_deliveries(&db, &org_ids, WebhookEventType::PlaceAdded, "{}", now)
+                .unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(load_due_webhook_deliveries(&db, now, 10).unwrap().len(), 2);
+
+        let webhook_id = db.webhooks.borrow()[0].id.clone();
+        let pagination = Pagination::default();
+        assert_eq!(
+            list_webhook_deliveries(&db, &foo, &webhook_id, &pagination)
+                .unwrap()
+                .len(),
+            1
+        );
+        assert!(list_webhook_deliveries(&db, &bar, &webhook_id, &pagination).is_err());
+
+        delete_webhook(&db, &foo, &webhook_id).unwrap();
+        assert_eq!(list_webhooks(&db, &foo).unwrap().len(), 1);
+        assert_eq!(db.webhook_deliveries.borrow().len(), 1);
+    }
+
+    #[test]
+    fn claim_due_delivery_only_once() {
+        let db = db();
+        let now = Timestamp::now();
+        let org_id = Id::from("foo");
+        create_webhook(&db, &org_id, "https://example.org/a", now).unwrap();
+        enqueue_webhook_deliveries(&db, &[org_id], WebhookEventType::PlaceAdded, "{}", now)
+            .unwrap();
+        let (_, delivery) = load_due_webhook_deliveries(&db, now, 10)
+            .unwrap()
+            .pop()
+            .unwrap();
+
+        let claimed = claim_webhook_delivery(&db, delivery.clone(), now)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            claimed.next_attempt_at,
+            Some(now + WEBHOOK_DELIVERY_CLAIM_PERIOD)
+        );
+        assert!(
+            claim_webhook_delivery(&db, delivery, now)
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            load_due_webhook_deliveries(&db, now, 10)
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            load_due_webhook_deliveries(&db, now + WEBHOOK_DELIVERY_CLAIM_PERIOD, 10)
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn retry_with_backoff() {
+        let db = db();
+        let now = Timestamp::now();
+        let org_id = Id::from("foo");
+        let webhook = create_webhook(&db, &org_id, "https://example.org/a", now).unwrap();
+        enqueue_webhook_deliveries(&db, &[org_id], WebhookEventType::EventAdded, "{}", now)
+            .unwrap();
+        let delivery = db.webhook_deliveries.borrow()[0].clone();
+
+        let gateway = MockGateway {
+            responses: RefCell::new(vec![
+                Err(anyhow::anyhow!("Connection refused")),
+                Ok(500),
+                Ok(204),
+            ]),
+        };
+        let delivery = attempt_webhook_delivery(&gateway, &webhook, delivery, now);
+        assert_eq!(delivery.status, WebhookDeliveryStatus::Pending);
+        assert_eq!(delivery.next_attempt_at, Some(now + Duration::minutes(1)));
+        assert!(delivery.last_error.is_some());
+
+        let delivery = attempt_webhook_delivery(&gateway, &webhook, delivery, now);
+        assert_eq!(delivery.status, WebhookDeliveryStatus::Pending);
+        assert_eq!(delivery.last_response_status, Some(500));
+        assert_eq!(delivery.next_attempt_at, Some(now + Duration::minutes(2)));
+
+        let delivery = attempt_webhook_delivery(&gateway, &webhook, delivery, now);
+        assert_eq!(delivery.status, WebhookDeliveryStatus::Delivered);
+        assert_eq!(delivery.attempts, 3);
+        assert_eq!(delivery.next_attempt_at, None);
+        assert_eq!(delivery.last_error, None);
+    }
+
+    #[test]
+    fn give_up_after_max_attempts() {
+        let webhook = Webhook {
+            id: Id::new(),
+            org_id: "foo".into(),
+            url: "https://example.org".parse().unwrap(),
+            secret: "secret".into(),
+            created_at: Timestamp::now(),
+        };
+        let now = Timestamp::now();
+        let delivery = WebhookDelivery {
+            id: Id::new(),
+            webhook_id: webhook.id.clone(),
+            event_type: WebhookEventType::PlaceUpdated,
+            payload: "{}".into(),
+            created_at: now,
+            status: WebhookDeliveryStatus::Pending,
+            attempts: MAX_WEBHOOK_DELIVERY_ATTEMPTS - 1,
+            next_attempt_at: Some(now),
+            last_attempt_at: None,
+            last_response_status: None,
+            last_error: None,
+        };
+        let gateway = MockGateway {
+            responses: RefCell::new(vec![Ok(404)]),
+        };
+        let delivery = attempt_webhook_delivery(&gateway, &webhook, delivery, now);
+        assert_eq!(delivery.status, WebhookDeliveryStatus::Failed);
+        assert_eq!(delivery.next_attempt_at, None);
+    }
+}
@@ -4,7 +4,8 @@ use ofdb_entities::{
     event::Event,
     geo::{MapBbox, MapPoint},
     location::Location,
-    place::Place,
+    opening_hours::ParseError as OpeningHoursRulesParseError,
+    place::{OpeningHours, Place},
 };
 use thiserror::Error;
 
@@ -77,6 +78,24 @@ impl Validate for Contact {
     }
 }
 
+#[derive(Debug, Error)]
+pub enum OpeningHoursInvalidation {
+    #[error("Invalid opening hours syntax: {0}")]
+    Syntax(#[from] OpeningHoursRulesParseError),
+}
+
+impl Validate for OpeningHours {
+    type Error = OpeningHoursInvalidation;
+    fn validate(&self) -> Result<(), Self::Error> {
+        // NOTE:
+        // Legacy values that don't comply with the OpenStreetMap
+        // syntax are accepted when loading places and must only
+        // be validated on input.
+        self.rules()?;
+        Ok(())
+    }
+}
+
 impl AutoCorrect for Event {
     fn auto_correct(mut self) -> Self {
         self.description = self.description.filter(|x| !x.is_empty());
@@ -160,7 +179,7 @@ impl AutoCorrect for Address {
 
 #[cfg(test)]
 mod tests {
-    use ofdb_entities::{email::EmailAddress, time::Timestamp};
+    use ofdb_entities::{email::EmailAddress, revision::Revision, time::Timestamp};
     use time::Duration;
 
     use super::*;
@@ -217,6 +236,9 @@ mod tests {
             archived: None,
             image_url: None,
             image_link_url: None,
+            recurrence: None,
+            revision: Revision::initial(),
+            translations: vec![],
         };
 
         let mut x = e.clone();
@@ -321,6 +343,9 @@ mod tests {
             archived: None,
             image_url: None,
             image_link_url: None,
+            recurrence: None,
+            revision: Revision::initial(),
+            translations: vec![],
         };
         assert!(e.validate().is_ok());
         assert!(
@@ -358,10 +383,21 @@ mod tests {
             archived: None,
             image_url: None,
             image_link_url: None,
+            recurrence: None,
+            revision: Revision::initial(),
+            translations: vec![],
         };
         assert!(e.validate().is_err());
     }
 
+    #[test]
+    fn opening_hours_test() {
+        let valid: OpeningHours = "Mo-Fr 09:00-18:00; Sa 10:00-14:00; PH off".parse().unwrap();
+        assert!(valid.validate().is_ok());
+        let legacy: OpeningHours = "Mo-Fr 9-18 Uhr".parse().unwrap();
+        assert!(legacy.validate().is_err());
+    }
+
     #[test]
     fn bbox_test() {
         let p1 = MapPoint::from_lat_lng_deg(48.123, 5.123);
@@ -0,0 +1,25 @@
+DROP TABLE revoked_tokens;
+DROP TABLE review_tokens;
+DROP TABLE sent_reminders;
+DROP TABLE webhook_delivery;
+DROP TABLE organization_webhook;
+DROP TABLE organization_api_token;
+DROP TABLE organization_event_clearance;
+DROP TABLE organization_place_clearance;
+DROP TABLE organization_tag;
+DROP TABLE organization;
+DROP TABLE bbox_subscriptions;
+DROP TABLE event_revision_tag;
+DROP TABLE event_revision;
+DROP TABLE event_tags;
+DROP TABLE events;
+DROP TABLE place_rating_comment;
+DROP TABLE place_rating;
+DROP TABLE place_revision_custom_link;
+DROP TABLE place_revision_tag;
+DROP TABLE place_revision_review;
+DROP TABLE place_revision;
+DROP TABLE place;
+DROP TABLE user_tokens;
+DROP TABLE users;
+DROP TABLE tags;
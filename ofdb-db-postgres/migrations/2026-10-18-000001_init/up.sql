@@ -0,0 +1,461 @@
+-- Initial schema that corresponds to the schema of the SQLite database
+-- after applying all of its migrations.
+--
+-- All timestamps are stored as BIGINT, either in seconds or in
+-- milliseconds (postfix `_at`). Foreign keys are checked on commit
+-- like in the SQLite database (PRAGMA defer_foreign_keys = 1).
+
+-----------------------------------------------------------------------
+-- Tags
+-----------------------------------------------------------------------
+
+CREATE TABLE tags (
+    id TEXT PRIMARY KEY NOT NULL
+);
+
+-----------------------------------------------------------------------
+-- Users
+-----------------------------------------------------------------------
+
+CREATE TABLE users (
+    id              BIGSERIAL PRIMARY KEY,
+    email           TEXT      NOT NULL,
+    email_confirmed BOOLEAN   NOT NULL DEFAULT FALSE,
+    password        TEXT      NOT NULL,
+    role            SMALLINT  NOT NULL
+);
+
+-- E-mail addresses are unique regardless of their case
+-- (COLLATE NOCASE in SQLite)
+CREATE UNIQUE INDEX users_uc_email ON users (lower(email));
+
+CREATE TABLE user_tokens (
+    id          BIGSERIAL PRIMARY KEY,
+    user_id     BIGINT NOT NULL,
+    expires_at  BIGINT NOT NULL,
+    nonce       TEXT   NOT NULL,
+    UNIQUE (user_id),
+    UNIQUE (nonce),
+    FOREIGN KEY (user_id) REFERENCES users(id) DEFERRABLE INITIALLY DEFERRED
+);
+
+-----------------------------------------------------------------------
+-- Places
+-----------------------------------------------------------------------
+
+CREATE TABLE place (
+    rowid       BIGSERIAL PRIMARY KEY,
+    --
+    current_rev BIGINT NOT NULL, -- latest revision (mutable) from place_revision
+    --
+    id          TEXT   NOT NULL,
+    license     TEXT   NOT NULL,
+    --
+    UNIQUE (id)
+);
+
+CREATE TABLE place_revision (
+    rowid          BIGSERIAL PRIMARY KEY,
+    parent_rowid   BIGINT NOT NULL,
+    --
+    rev            BIGINT NOT NULL,
+    created_at     BIGINT NOT NULL,
+    created_by     BIGINT,
+    --
+    current_status SMALLINT NOT NULL, -- latest status (mutable) from place_revision_review
+    --
+    title          TEXT NOT NULL,
+    description    TEXT NOT NULL,
+    lat            DOUBLE PRECISION NOT NULL,
+    lon            DOUBLE PRECISION NOT NULL,
+    street         TEXT,
+    zip            TEXT,
+    city           TEXT,
+    country        TEXT,
+    state          TEXT,
+    contact_name   TEXT,
+    email          TEXT,
+    phone          TEXT,
+    homepage       TEXT,
+    opening_hours  TEXT,
+    founded_on     TEXT,
+    image_url      TEXT,
+    image_link_url TEXT,
+    --
+    UNIQUE (parent_rowid, rev),
+    FOREIGN KEY (parent_rowid) REFERENCES place(rowid) DEFERRABLE INITIALLY DEFERRED,
+    FOREIGN KEY (created_by) REFERENCES users(id) DEFERRABLE INITIALLY DEFERRED
+);
+
+CREATE INDEX place_revision_idx_created_at ON place_revision (created_at);
+CREATE INDEX place_revision_idx_created_by ON place_revision (created_by);
+
+CREATE TABLE place_revision_review (
+    rowid        BIGSERIAL PRIMARY KEY,
+    parent_rowid BIGINT NOT NULL,
+    --
+    rev          BIGINT NOT NULL,
+    created_at   BIGINT NOT NULL,
+    created_by   BIGINT,
+    --
+    status       SMALLINT NOT NULL,
+    context      TEXT, -- system context, e.g. client IP address, ...
+    comment      TEXT, -- human-written textual comment
+    --
+    UNIQUE (parent_rowid, rev),
+    FOREIGN KEY (parent_rowid) REFERENCES place_revision(rowid) DEFERRABLE INITIALLY DEFERRED,
+    FOREIGN KEY (created_by) REFERENCES users(id) DEFERRABLE INITIALLY DEFERRED
+);
+
+CREATE INDEX place_revision_review_idx_created_at ON place_revision_review (created_at);
+CREATE INDEX place_revision_review_idx_created_by ON place_revision_review (created_by);
+
+CREATE TABLE place_revision_tag (
+    parent_rowid BIGINT NOT NULL,
+    tag          TEXT   NOT NULL,
+    PRIMARY KEY (parent_rowid, tag),
+    FOREIGN KEY (parent_rowid) REFERENCES place_revision(rowid) DEFERRABLE INITIALLY DEFERRED
+);
+
+CREATE INDEX place_revision_tag_idx_tag ON place_revision_tag (tag);
+
+CREATE TABLE place_revision_custom_link (
+    parent_rowid BIGINT NOT NULL,
+    --
+    url          TEXT NOT NULL,
+    title        TEXT,
+    description  TEXT,
+    PRIMARY KEY (parent_rowid, url),
+    FOREIGN KEY (parent_rowid) REFERENCES place_revision(rowid) DEFERRABLE INITIALLY DEFERRED
+);
+
+CREATE TABLE place_rating (
+    rowid        BIGSERIAL PRIMARY KEY,
+    parent_rowid BIGINT NOT NULL,
+    --
+    created_at   BIGINT NOT NULL,
+    created_by   BIGINT,
+    archived_at  BIGINT,
+    archived_by  BIGINT,
+    --
+    id           TEXT NOT NULL,
+    title        TEXT NOT NULL,
+    value        SMALLINT NOT NULL,
+    context      TEXT NOT NULL,
+    source       TEXT,
+    --
+    UNIQUE (id),
+    FOREIGN KEY (parent_rowid) REFERENCES place(rowid) DEFERRABLE INITIALLY DEFERRED,
+    FOREIGN KEY (created_by) REFERENCES users(id) DEFERRABLE INITIALLY DEFERRED,
+    FOREIGN KEY (archived_by) REFERENCES users(id) DEFERRABLE INITIALLY DEFERRED
+);
+
+CREATE INDEX place_rating_idx_parent_rowid ON place_rating (parent_rowid);
+CREATE INDEX place_rating_idx_created_at ON place_rating (created_at);
+CREATE INDEX place_rating_idx_created_by ON place_rating (created_by);
+CREATE INDEX place_rating_idx_archived_at ON place_rating (archived_at);
+CREATE INDEX place_rating_idx_archived_by ON place_rating (archived_by);
+
+CREATE TABLE place_rating_comment (
+    rowid        BIGSERIAL PRIMARY KEY,
+    parent_rowid BIGINT NOT NULL,
+    --
+    created_at   BIGINT NOT NULL,
+    created_by   BIGINT,
+    archived_at  BIGINT,
+    archived_by  BIGINT,
+    --
+    id           TEXT NOT NULL,
+    text         TEXT NOT NULL,
+    --
+    UNIQUE (id),
+    FOREIGN KEY (parent_rowid) REFERENCES place_rating(rowid) DEFERRABLE INITIALLY DEFERRED,
+    FOREIGN KEY (created_by) REFERENCES users(id) DEFERRABLE INITIALLY DEFERRED,
+    FOREIGN KEY (archived_by) REFERENCES users(id) DEFERRABLE INITIALLY DEFERRED
+);
+
+CREATE INDEX place_rating_comment_idx_parent_rowid ON place_rating_comment (parent_rowid);
+CREATE INDEX place_rating_comment_idx_created_at ON place_rating_comment (created_at);
+CREATE INDEX place_rating_comment_idx_created_by ON place_rating_comment (created_by);
+CREATE INDEX place_rating_comment_idx_archived_at ON place_rating_comment (archived_at);
+CREATE INDEX place_rating_comment_idx_archived_by ON place_rating_comment (archived_by);
+
+-----------------------------------------------------------------------
+-- Events
+-----------------------------------------------------------------------
+
+CREATE TABLE events (
+    id             BIGSERIAL PRIMARY KEY,
+    uid            TEXT NOT NULL,
+    title          TEXT NOT NULL,
+    description    TEXT,
+    start          BIGINT NOT NULL,
+    "end"          BIGINT,
+    lat            DOUBLE PRECISION,
+    lng            DOUBLE PRECISION,
+    street         TEXT,
+    zip            TEXT,
+    city           TEXT,
+    country        TEXT,
+    state          TEXT,
+    email          TEXT,
+    telephone      TEXT,
+    homepage       TEXT,
+    created_by     BIGINT,
+    registration   SMALLINT,
+    organizer      TEXT,
+    archived       BIGINT DEFAULT NULL,
+    image_url      TEXT,
+    image_link_url TEXT,
+    rrule          TEXT,
+    exdates        TEXT,
+    current_rev    BIGINT NOT NULL DEFAULT 0,
+    --
+    UNIQUE (uid),
+    FOREIGN KEY (created_by) REFERENCES users(id) DEFERRABLE INITIALLY DEFERRED
+);
+
+CREATE INDEX events_fk_created_by ON events (created_by);
+
+CREATE TABLE event_tags (
+    event_id BIGINT NOT NULL,
+    tag      TEXT   NOT NULL,
+    PRIMARY KEY (event_id, tag),
+    FOREIGN KEY (event_id) REFERENCES events(id) DEFERRABLE INITIALLY DEFERRED
+);
+
+CREATE INDEX event_tag_index ON event_tags (tag);
+
+CREATE TABLE event_revision (
+    rowid          BIGSERIAL PRIMARY KEY,
+    parent_rowid   BIGINT NOT NULL,
+    --
+    rev            BIGINT NOT NULL,
+    created_at     BIGINT NOT NULL,
+    created_by     BIGINT,
+    context        TEXT,
+    comment        TEXT,
+    --
+    title          TEXT NOT NULL,
+    description    TEXT,
+    start          BIGINT NOT NULL,
+    "end"          BIGINT,
+    lat            DOUBLE PRECISION,
+    lng            DOUBLE PRECISION,
+    street         TEXT,
+    zip            TEXT,
+    city           TEXT,
+    country        TEXT,
+    state          TEXT,
+    email          TEXT,
+    telephone      TEXT,
+    homepage       TEXT,
+    registration   SMALLINT,
+    organizer      TEXT,
+    image_url      TEXT,
+    image_link_url TEXT,
+    rrule          TEXT,
+    exdates        TEXT,
+    --
+    UNIQUE (parent_rowid, rev),
+    FOREIGN KEY (parent_rowid) REFERENCES events(id) DEFERRABLE INITIALLY DEFERRED,
+    FOREIGN KEY (created_by) REFERENCES users(id) DEFERRABLE INITIALLY DEFERRED
+);
+
+CREATE INDEX event_revision_idx_parent_rowid ON event_revision (parent_rowid);
+
+CREATE TABLE event_revision_tag (
+    parent_rowid BIGINT NOT NULL,
+    tag          TEXT   NOT NULL,
+    --
+    PRIMARY KEY (parent_rowid, tag),
+    FOREIGN KEY (parent_rowid) REFERENCES event_revision(rowid) DEFERRABLE INITIALLY DEFERRED
+);
+
+-----------------------------------------------------------------------
+-- Subscriptions
+-----------------------------------------------------------------------
+
+CREATE TABLE bbox_subscriptions (
+    id              BIGSERIAL PRIMARY KEY,
+    uid             TEXT   NOT NULL,
+    user_id         BIGINT NOT NULL,
+    south_west_lat  DOUBLE PRECISION NOT NULL,
+    south_west_lng  DOUBLE PRECISION NOT NULL,
+    north_east_lat  DOUBLE PRECISION NOT NULL,
+    north_east_lng  DOUBLE PRECISION NOT NULL,
+    UNIQUE (uid),
+    FOREIGN KEY (user_id) REFERENCES users(id) DEFERRABLE INITIALLY DEFERRED
+);
+
+-----------------------------------------------------------------------
+-- Organizations
+-----------------------------------------------------------------------
+
+CREATE TABLE organization (
+    rowid     BIGSERIAL PRIMARY KEY,
+    --
+    id        TEXT NOT NULL,
+    name      TEXT NOT NULL,
+    api_token TEXT NOT NULL,
+    --
+    UNIQUE (id)
+);
+
+CREATE TABLE organization_tag (
+    org_rowid BIGINT NOT NULL,
+    tag_label TEXT   NOT NULL,
+    --
+    tag_allow_add     SMALLINT NOT NULL,
+    tag_allow_remove  SMALLINT NOT NULL,
+    require_clearance SMALLINT NOT NULL,
+    --
+    PRIMARY KEY (org_rowid, tag_label),
+    FOREIGN KEY (org_rowid) REFERENCES organization(rowid) DEFERRABLE INITIALLY DEFERRED
+    -- no FK for tag_label (may not yet exist)
+);
+
+CREATE INDEX organization_tag_idx_tag_label ON organization_tag (tag_label);
+
+CREATE TABLE organization_place_clearance (
+    rowid        BIGSERIAL PRIMARY KEY,
+    --
+    org_rowid    BIGINT NOT NULL,
+    place_rowid  BIGINT NOT NULL,
+    --
+    created_at            BIGINT NOT NULL,
+    last_cleared_revision BIGINT, -- last cleared revision number or NULL if the place is new and has not been cleared yet
+    --
+    UNIQUE (org_rowid, place_rowid),
+    FOREIGN KEY (org_rowid) REFERENCES organization(rowid) DEFERRABLE INITIALLY DEFERRED,
+    FOREIGN KEY (place_rowid) REFERENCES place(rowid) DEFERRABLE INITIALLY DEFERRED
+);
+
+CREATE TABLE organization_event_clearance (
+    rowid        BIGSERIAL PRIMARY KEY,
+    --
+    org_rowid    BIGINT NOT NULL,
+    event_rowid  BIGINT NOT NULL,
+    --
+    created_at            BIGINT NOT NULL,
+    last_cleared_revision BIGINT, -- last cleared revision number or NULL if the event is new and has not been cleared yet
+    --
+    UNIQUE (org_rowid, event_rowid),
+    FOREIGN KEY (org_rowid) REFERENCES organization(rowid) DEFERRABLE INITIALLY DEFERRED,
+    FOREIGN KEY (event_rowid) REFERENCES events(id) DEFERRABLE INITIALLY DEFERRED
+);
+
+CREATE TABLE organization_api_token (
+    rowid        BIGSERIAL PRIMARY KEY,
+    id           TEXT NOT NULL,
+    --
+    org_rowid    BIGINT NOT NULL,
+    --
+    name         TEXT NOT NULL,
+    token_hash   TEXT NOT NULL, -- hex encoded SHA-256 hash of the secret token
+    scopes       TEXT NOT NULL, -- space separated list of scopes
+    --
+    created_at   BIGINT NOT NULL,
+    expires_at   BIGINT,
+    last_used_at BIGINT,
+    revoked_at   BIGINT,
+    --
+    UNIQUE (id),
+    UNIQUE (token_hash),
+    FOREIGN KEY (org_rowid) REFERENCES organization(rowid) DEFERRABLE INITIALLY DEFERRED
+);
+
+CREATE INDEX organization_api_token_idx_org_rowid ON organization_api_token (org_rowid);
+
+CREATE TABLE organization_webhook (
+    rowid      BIGSERIAL PRIMARY KEY,
+    id         TEXT NOT NULL,
+    --
+    org_rowid  BIGINT NOT NULL,
+    --
+    url        TEXT NOT NULL,
+    secret     TEXT NOT NULL, -- shared secret for signing the payloads
+    created_at BIGINT NOT NULL,
+    --
+    UNIQUE (id),
+    FOREIGN KEY (org_rowid) REFERENCES organization(rowid) DEFERRABLE INITIALLY DEFERRED
+);
+
+CREATE INDEX organization_webhook_idx_org_rowid ON organization_webhook (org_rowid);
+
+CREATE TABLE webhook_delivery (
+    rowid                BIGSERIAL PRIMARY KEY,
+    id                   TEXT NOT NULL,
+    --
+    webhook_rowid        BIGINT NOT NULL,
+    --
+    event_type           TEXT NOT NULL,
+    payload              TEXT NOT NULL, -- JSON
+    created_at           BIGINT NOT NULL,
+    status               SMALLINT NOT NULL, -- 0 = pending, 1 = delivered, 2 = failed
+    attempts             BIGINT NOT NULL,
+    next_attempt_at      BIGINT, -- only set for pending deliveries
+    last_attempt_at      BIGINT,
+    last_response_status BIGINT,
+    last_error           TEXT,
+    --
+    UNIQUE (id),
+    FOREIGN KEY (webhook_rowid) REFERENCES organization_webhook(rowid) DEFERRABLE INITIALLY DEFERRED
+);
+
+CREATE INDEX webhook_delivery_idx_webhook_rowid ON webhook_delivery (webhook_rowid);
+CREATE INDEX webhook_delivery_idx_next_attempt_at ON webhook_delivery (next_attempt_at);
+
+-----------------------------------------------------------------------
+-- Reminders and tokens
+-----------------------------------------------------------------------
+
+CREATE TABLE sent_reminders (
+    rowid           BIGSERIAL PRIMARY KEY,
+    --
+    place_rowid     BIGINT NOT NULL,
+    --
+    sent_at         BIGINT NOT NULL,
+    sent_to_email   TEXT   NOT NULL,
+    --
+    FOREIGN KEY (place_rowid) REFERENCES place(rowid) DEFERRABLE INITIALLY DEFERRED
+);
+
+CREATE TABLE review_tokens (
+    rowid         BIGSERIAL PRIMARY KEY,
+    --
+    place_rowid   BIGINT NOT NULL,
+    --
+    revision      BIGINT NOT NULL,
+    expires_at    BIGINT NOT NULL,
+    nonce         TEXT   NOT NULL,
+    --
+    UNIQUE (nonce),
+    FOREIGN KEY (place_rowid) REFERENCES place(rowid) DEFERRABLE INITIALLY DEFERRED
+);
+
+CREATE TABLE revoked_tokens (
+    id         TEXT PRIMARY KEY NOT NULL,
+    expires_at BIGINT NOT NULL
+);
+
+CREATE INDEX revoked_tokens_idx_expires_at ON revoked_tokens (expires_at);
+
+-----------------------------------------------------------------------
+-- Geometries (PostGIS)
+-----------------------------------------------------------------------
+
+-- The coordinates of places and events are mirrored into generated
+-- geometry columns. Their spatial indexes are used for queries by
+-- bounding box.
+CREATE EXTENSION IF NOT EXISTS postgis;
+
+ALTER TABLE place_revision
+    ADD COLUMN geom geometry(Point, 4326)
+    GENERATED ALWAYS AS (ST_SetSRID(ST_MakePoint(lon, lat), 4326)) STORED;
+CREATE INDEX place_revision_idx_geom ON place_revision USING GIST (geom);
+
+ALTER TABLE events
+    ADD COLUMN geom geometry(Point, 4326)
+    GENERATED ALWAYS AS (ST_SetSRID(ST_MakePoint(lng, lat), 4326)) STORED;
+CREATE INDEX events_idx_geom ON events USING GIST (geom);
@@ -0,0 +1,2 @@
+DROP TABLE event_revision_translation;
+DROP TABLE place_revision_translation;
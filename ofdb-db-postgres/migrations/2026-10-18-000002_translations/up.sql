@@ -0,0 +1,22 @@
+-- Titles and descriptions in other languages, keyed by BCP 47 language tags
+CREATE TABLE place_revision_translation (
+    parent_rowid BIGINT NOT NULL,
+    --
+    lang         TEXT NOT NULL,
+    title        TEXT NOT NULL,
+    description  TEXT,
+    PRIMARY KEY (parent_rowid, lang),
+    FOREIGN KEY (parent_rowid) REFERENCES place_revision(rowid) DEFERRABLE INITIALLY DEFERRED
+);
+
+-- The translations of the current state of an event are those
+-- of its current revision
+CREATE TABLE event_revision_translation (
+    parent_rowid BIGINT NOT NULL,
+    --
+    lang         TEXT NOT NULL,
+    title        TEXT NOT NULL,
+    description  TEXT,
+    PRIMARY KEY (parent_rowid, lang),
+    FOREIGN KEY (parent_rowid) REFERENCES event_revision(rowid) DEFERRABLE INITIALLY DEFERRED
+);
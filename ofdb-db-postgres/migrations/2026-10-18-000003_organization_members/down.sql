@@ -0,0 +1,3 @@
+DROP TABLE organization_event_clearance_log;
+DROP TABLE organization_place_clearance_log;
+DROP TABLE organization_member;
@@ -0,0 +1,54 @@
+-- Registered users that act on behalf of an organization
+CREATE TABLE organization_member (
+    rowid     BIGSERIAL PRIMARY KEY,
+    --
+    org_rowid BIGINT NOT NULL,
+    user_id   BIGINT NOT NULL,
+    --
+    role      TEXT NOT NULL, -- viewer, editor, or clearer
+    --
+    UNIQUE (org_rowid, user_id),
+    FOREIGN KEY (org_rowid) REFERENCES organization(rowid) DEFERRABLE INITIALLY DEFERRED,
+    FOREIGN KEY (user_id) REFERENCES users(id) DEFERRABLE INITIALLY DEFERRED
+);
+
+CREATE INDEX organization_member_idx_user_id ON organization_member (user_id);
+
+-- Recorded clearances of place revisions
+--
+-- The clearances are kept for auditing and therefore
+-- don't reference the places or users.
+CREATE TABLE organization_place_clearance_log (
+    rowid            BIGSERIAL PRIMARY KEY,
+    --
+    org_rowid        BIGINT NOT NULL,
+    --
+    place_id         TEXT NOT NULL,
+    cleared_revision BIGINT NOT NULL,
+    created_at       BIGINT NOT NULL,
+    created_by       TEXT, -- e-mail address
+    context          TEXT,
+    comment          TEXT,
+    --
+    FOREIGN KEY (org_rowid) REFERENCES organization(rowid) DEFERRABLE INITIALLY DEFERRED
+);
+
+CREATE INDEX organization_place_clearance_log_idx_org_rowid ON organization_place_clearance_log (org_rowid);
+
+-- Recorded clearances of event revisions
+CREATE TABLE organization_event_clearance_log (
+    rowid            BIGSERIAL PRIMARY KEY,
+    --
+    org_rowid        BIGINT NOT NULL,
+    --
+    event_id         TEXT NOT NULL,
+    cleared_revision BIGINT NOT NULL,
+    created_at       BIGINT NOT NULL,
+    created_by       TEXT, -- e-mail address
+    context          TEXT,
+    comment          TEXT,
+    --
+    FOREIGN KEY (org_rowid) REFERENCES organization(rowid) DEFERRABLE INITIALLY DEFERRED
+);
+
+CREATE INDEX organization_event_clearance_log_idx_org_rowid ON organization_event_clearance_log (org_rowid);
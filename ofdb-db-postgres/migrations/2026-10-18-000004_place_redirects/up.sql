@@ -0,0 +1,13 @@
+-- Merged places redirect to the surviving place
+CREATE TABLE place_redirect (
+    rowid               BIGSERIAL PRIMARY KEY,
+    --
+    place_rowid         BIGINT NOT NULL, -- the merged place
+    target_place_rowid  BIGINT NOT NULL, -- the surviving place
+    --
+    UNIQUE (place_rowid),
+    FOREIGN KEY (place_rowid) REFERENCES place(rowid) DEFERRABLE INITIALLY DEFERRED,
+    FOREIGN KEY (target_place_rowid) REFERENCES place(rowid) DEFERRABLE INITIALLY DEFERRED
+);
+
+CREATE INDEX place_redirect_idx_target_place_rowid ON place_redirect (target_place_rowid);
@@ -0,0 +1,18 @@
+-- Pairs of possibly duplicate places that are detected periodically
+--
+-- Each pair is stored only once with the lower place id first.
+CREATE TABLE place_duplicate_candidate (
+    rowid              BIGSERIAL PRIMARY KEY,
+    --
+    place_id           TEXT NOT NULL,
+    duplicate_place_id TEXT NOT NULL,
+    similarity         DOUBLE PRECISION NOT NULL,
+    detected_at        BIGINT NOT NULL,
+    state              TEXT NOT NULL, -- open, dismissed, or merged
+    reviewed_at        BIGINT,
+    reviewed_by        TEXT, -- e-mail address
+    --
+    UNIQUE (place_id, duplicate_place_id)
+);
+
+CREATE INDEX place_duplicate_candidate_idx_state ON place_duplicate_candidate (state);
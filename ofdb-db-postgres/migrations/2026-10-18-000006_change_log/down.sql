@@ -0,0 +1,17 @@
+DROP TRIGGER change_log_rating_move ON place_rating;
+DROP TRIGGER change_log_rating_archive ON place_rating;
+DROP TRIGGER change_log_rating_create ON place_rating;
+DROP TRIGGER change_log_event_delete ON events;
+DROP TRIGGER change_log_event_archive ON events;
+DROP TRIGGER change_log_event_revision ON event_revision;
+DROP TRIGGER change_log_place_review ON place_revision_review;
+DROP FUNCTION change_log_rating_move();
+DROP FUNCTION change_log_rating_archive();
+DROP FUNCTION change_log_rating_create();
+DROP FUNCTION change_log_event_delete();
+DROP FUNCTION change_log_event_archive();
+DROP FUNCTION change_log_event_revision();
+DROP FUNCTION change_log_place_review();
+DROP FUNCTION now_millis();
+DROP FUNCTION lock_change_log();
+DROP TABLE change_log;
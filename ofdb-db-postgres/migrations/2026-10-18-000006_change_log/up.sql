@@ -0,0 +1,191 @@
+-- The feed of all changes in the order in which they have been committed
+--
+-- The rows are written by triggers within the same transaction as
+-- the change itself. Clients resume the feed after the last rowid
+-- they have seen, i.e. the rowid must increase monotonically and
+-- must never be reused.
+CREATE TABLE change_log (
+    rowid       BIGSERIAL PRIMARY KEY,
+    --
+    changed_at  BIGINT NOT NULL, -- unix time in milliseconds
+    kind        TEXT NOT NULL, -- created, updated, reviewed, archived, rejected, or deleted
+    item_type   TEXT NOT NULL, -- place, event, or rating
+    item_id     TEXT NOT NULL,
+    item_rev    BIGINT, -- places and created/updated events
+    item_status SMALLINT, -- review status of places
+    place_id    TEXT -- place of ratings
+);
+
+-- Existing changes in chronological order
+INSERT INTO change_log (changed_at, kind, item_type, item_id, item_rev, item_status, place_id)
+SELECT changed_at, kind, item_type, item_id, item_rev, item_status, place_id FROM (
+    SELECT
+        review.created_at AS changed_at,
+        0 AS source,
+        review.rowid AS source_rowid,
+        CASE
+            WHEN review.rev > 0 THEN
+                CASE review.status WHEN 0 THEN 'archived' WHEN -1 THEN 'rejected' ELSE 'reviewed' END
+            WHEN rev.rev > 0 THEN 'updated'
+            ELSE 'created'
+        END AS kind,
+        'place' AS item_type,
+        place.id AS item_id,
+        rev.rev AS item_rev,
+        review.status AS item_status,
+        NULL AS place_id
+    FROM place_revision_review review
+    JOIN place_revision rev ON rev.rowid = review.parent_rowid
+    JOIN place ON place.rowid = rev.parent_rowid
+    UNION ALL
+    SELECT
+        rev.created_at, 1, rev.rowid,
+        CASE WHEN rev.rev > 0 THEN 'updated' ELSE 'created' END,
+        'event', events.uid, rev.rev, NULL, NULL
+    FROM event_revision rev
+    JOIN events ON events.id = rev.parent_rowid
+    UNION ALL
+    SELECT
+        events.archived * 1000, 2, events.id,
+        'archived', 'event', events.uid, NULL, NULL, NULL
+    FROM events
+    WHERE events.archived IS NOT NULL
+    UNION ALL
+    SELECT
+        rating.created_at, 3, rating.rowid,
+        'created', 'rating', rating.id, NULL, NULL, place.id
+    FROM place_rating rating
+    JOIN place ON place.rowid = rating.parent_rowid
+    UNION ALL
+    SELECT
+        rating.archived_at, 4, rating.rowid,
+        'archived', 'rating', rating.id, NULL, NULL, place.id
+    FROM place_rating rating
+    JOIN place ON place.rowid = rating.parent_rowid
+    WHERE rating.archived_at IS NOT NULL
+) AS changes
+ORDER BY changed_at, source, source_rowid;
+
+-- Row ids are assigned when inserting a row, but concurrent transactions
+-- might commit in a different order. Readers could then skip a row that
+-- becomes visible after a subsequent row. The lock serializes all writers
+-- of the change log until they commit.
+CREATE FUNCTION lock_change_log() RETURNS void AS $$
+BEGIN
+    PERFORM pg_advisory_xact_lock(hashtext('change_log'));
+END
+$$ LANGUAGE plpgsql;
+
+CREATE FUNCTION now_millis() RETURNS BIGINT AS $$
+    SELECT (extract(epoch FROM clock_timestamp()) * 1000)::BIGINT
+$$ LANGUAGE sql;
+
+CREATE FUNCTION change_log_place_review() RETURNS trigger AS $$
+BEGIN
+    PERFORM lock_change_log();
+    INSERT INTO change_log (changed_at, kind, item_type, item_id, item_rev, item_status)
+    SELECT
+        NEW.created_at,
+        CASE
+            WHEN NEW.rev > 0 THEN
+                CASE NEW.status WHEN 0 THEN 'archived' WHEN -1 THEN 'rejected' ELSE 'reviewed' END
+            WHEN rev.rev > 0 THEN 'updated'
+            ELSE 'created'
+        END,
+        'place', place.id, rev.rev, NEW.status
+    FROM place_revision rev
+    JOIN place ON place.rowid = rev.parent_rowid
+    WHERE rev.rowid = NEW.parent_rowid;
+    RETURN NULL;
+END
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER change_log_place_review AFTER INSERT ON place_revision_review
+FOR EACH ROW EXECUTE FUNCTION change_log_place_review();
+
+CREATE FUNCTION change_log_event_revision() RETURNS trigger AS $$
+BEGIN
+    PERFORM lock_change_log();
+    INSERT INTO change_log (changed_at, kind, item_type, item_id, item_rev)
+    SELECT
+        NEW.created_at,
+        CASE WHEN NEW.rev > 0 THEN 'updated' ELSE 'created' END,
+        'event', uid, NEW.rev
+    FROM events
+    WHERE id = NEW.parent_rowid;
+    RETURN NULL;
+END
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER change_log_event_revision AFTER INSERT ON event_revision
+FOR EACH ROW EXECUTE FUNCTION change_log_event_revision();
+
+CREATE FUNCTION change_log_event_archive() RETURNS trigger AS $$
+BEGIN
+    PERFORM lock_change_log();
+    INSERT INTO change_log (changed_at, kind, item_type, item_id)
+    VALUES (NEW.archived * 1000, 'archived', 'event', NEW.uid);
+    RETURN NULL;
+END
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER change_log_event_archive AFTER UPDATE OF archived ON events
+FOR EACH ROW WHEN (OLD.archived IS NULL AND NEW.archived IS NOT NULL)
+EXECUTE FUNCTION change_log_event_archive();
+
+CREATE FUNCTION change_log_event_delete() RETURNS trigger AS $$
+BEGIN
+    PERFORM lock_change_log();
+    INSERT INTO change_log (changed_at, kind, item_type, item_id)
+    VALUES (now_millis(), 'deleted', 'event', OLD.uid);
+    RETURN NULL;
+END
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER change_log_event_delete AFTER DELETE ON events
+FOR EACH ROW EXECUTE FUNCTION change_log_event_delete();
+
+CREATE FUNCTION change_log_rating_create() RETURNS trigger AS $$
+BEGIN
+    PERFORM lock_change_log();
+    INSERT INTO change_log (changed_at, kind, item_type, item_id, place_id)
+    SELECT NEW.created_at, 'created', 'rating', NEW.id, id
+    FROM place
+    WHERE rowid = NEW.parent_rowid;
+    RETURN NULL;
+END
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER change_log_rating_create AFTER INSERT ON place_rating
+FOR EACH ROW EXECUTE FUNCTION change_log_rating_create();
+
+CREATE FUNCTION change_log_rating_archive() RETURNS trigger AS $$
+BEGIN
+    PERFORM lock_change_log();
+    INSERT INTO change_log (changed_at, kind, item_type, item_id, place_id)
+    SELECT NEW.archived_at, 'archived', 'rating', NEW.id, id
+    FROM place
+    WHERE rowid = NEW.parent_rowid;
+    RETURN NULL;
+END
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER change_log_rating_archive AFTER UPDATE OF archived_at ON place_rating
+FOR EACH ROW WHEN (OLD.archived_at IS NULL AND NEW.archived_at IS NOT NULL)
+EXECUTE FUNCTION change_log_rating_archive();
+
+-- Ratings are moved when merging places
+CREATE FUNCTION change_log_rating_move() RETURNS trigger AS $$
+BEGIN
+    PERFORM lock_change_log();
+    INSERT INTO change_log (changed_at, kind, item_type, item_id, place_id)
+    SELECT now_millis(), 'updated', 'rating', NEW.id, id
+    FROM place
+    WHERE rowid = NEW.parent_rowid;
+    RETURN NULL;
+END
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER change_log_rating_move AFTER UPDATE OF parent_rowid ON place_rating
+FOR EACH ROW WHEN (OLD.parent_rowid IS DISTINCT FROM NEW.parent_rowid)
+EXECUTE FUNCTION change_log_rating_move();
@@ -0,0 +1,409 @@
+//! Import of an existing SQLite database
+//!
+//! Both databases share the same tables and columns. All rows are
+//! copied verbatim including their row ids that are referenced by
+//! foreign keys.
+
+use anyhow::{Result as Fallible, bail};
+use diesel::{pg::PgConnection, prelude::*, sqlite::SqliteConnection};
+
+use super::{Connections, schema};
+
+// Stay well below the limit of 65535 bind parameters per statement
+const ROWS_PER_INSERT: usize = 1000;
+
+/// Number of imported rows per table
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportStatistics {
+    pub tables: Vec<(&'static str, usize)>,
+}
+
+impl ImportStatistics {
+    pub fn total_rows(&self) -> usize {
+        self.tables.iter().map(|(_, rows)| rows).sum()
+    }
+}
+
+macro_rules! import_table {
+    ($source:expr, $target:expr, $stats:expr, $table:ident {
+        $($column:ident -> $type:ty,)+
+    }) => {{
+        #[derive(Queryable, Selectable, Insertable)]
+        #[diesel(table_name = schema::$table)]
+        struct Row {
+            $($column: $type,)+
+        }
+        let existing_rows = schema::$table::table
+            .count()
+            .get_result::<i64>($target)?;
+        if existing_rows > 0 {
+            bail!(
+                "Table {} of the PostgreSQL database is not empty",
+                stringify!($table)
+            );
+        }
+        let rows = schema::$table::table
+            .select(Row::as_select())
+            .load::<Row>($source)?;
+        for chunk in rows.chunks(ROWS_PER_INSERT) {
+            diesel::insert_into(schema::$table::table)
+                .values(chunk)
+                .execute($target)?;
+        }
+        log::info!("Imported {} row(s) into {}", rows.len(), stringify!($table));
+        $stats.tables.push((stringify!($table), rows.len()));
+    }};
+}
+
+/// Copy all data from an SQLite database into an empty PostgreSQL database
+///
+/// The PostgreSQL database must have been migrated before. All data is
+/// imported within a single transaction, i.e. nothing is imported if
+/// any table of the PostgreSQL database already contains data.
+pub fn import_sqlite_database(
+    sqlite_url: &str,
+    target: &Connections,
+) -> Fallible<ImportStatistics> {
+    let mut source = SqliteConnection::establish(sqlite_url)?;
+    let target = target.exclusive()?;
+    let target = &mut *target.pg_conn();
+    // Read from a consistent snapshot of the SQLite database
+    source.transaction(|source| target.transaction(|target| import_tables(source, target)))
+}
+
+fn import_tables(
+    source: &mut SqliteConnection,
+    target: &mut PgConnection,
+) -> Fallible<ImportStatistics> {
+    let mut stats = ImportStatistics::default();
+    import_table!(source, target, stats, tags {
+        id -> String,
+    });
+    import_table!(source, target, stats, users {
+        id -> i64,
+        email -> String,
+        email_confirmed -> bool,
+        password -> String,
+        role -> i16,
+    });
+    import_table!(source, target, stats, user_tokens {
+        id -> i64,
+        user_id -> i64,
+        expires_at -> i64,
+        nonce -> String,
+    });
+    import_table!(source, target, stats, place {
+        rowid -> i64,
+        current_rev -> i64,
+        id -> String,
+        license -> String,
+    });
+    import_table!(source, target, stats, place_revision {
+        rowid -> i64,
+        parent_rowid -> i64,
+        rev -> i64,
+        created_at -> i64,
+        created_by -> Option<i64>,
+        current_status -> i16,
+        title -> String,
+        description -> String,
+        lat -> f64,
+        lon -> f64,
+        street -> Option<String>,
+        zip -> Option<String>,
+        city -> Option<String>,
+        country -> Option<String>,
+        state -> Option<String>,
+        contact_name -> Option<String>,
+        email -> Option<String>,
+        phone -> Option<String>,
+        homepage -> Option<String>,
+        opening_hours -> Option<String>,
+        founded_on -> Option<String>,
+        image_url -> Option<String>,
+        image_link_url -> Option<String>,
+    });
+    import_table!(source, target, stats, place_revision_review {
+        rowid -> i64,
+        parent_rowid -> i64,
+        rev -> i64,
+        created_at -> i64,
+        created_by -> Option<i64>,
+        status -> i16,
+        context -> Option<String>,
+        comment -> Option<String>,
+    });
+    import_table!(source, target, stats, place_revision_tag {
+        parent_rowid -> i64,
+        tag -> String,
+    });
+    import_table!(source, target, stats, place_revision_custom_link {
+        parent_rowid -> i64,
+        url -> String,
+        title -> Option<String>,
+        description -> Option<String>,
+    });
+    import_table!(source, target, stats, place_revision_translation {
+        parent_rowid -> i64,
+        lang -> String,
+        title -> String,
+        description -> Option<String>,
+    });
+    import_table!(source, target, stats, place_rating {
+        rowid -> i64,
+        parent_rowid -> i64,
+        created_at -> i64,
+        created_by -> Option<i64>,
+        archived_at -> Option<i64>,
+        archived_by -> Option<i64>,
+        id -> String,
+        title -> String,
+        value -> i16,
+        context -> String,
+        source -> Option<String>,
+    });
+    import_table!(source, target, stats, place_rating_comment {
+        rowid -> i64,
+        parent_rowid -> i64,
+        created_at -> i64,
+        created_by -> Option<i64>,
+        archived_at -> Option<i64>,
+        archived_by -> Option<i64>,
+        id -> String,
+        text -> String,
+    });
+    import_table!(source, target, stats, place_redirect {
+        rowid -> i64,
+        place_rowid -> i64,
+        target_place_rowid -> i64,
+    });
+    import_table!(source, target, stats, place_duplicate_candidate {
+        rowid -> i64,
+        place_id -> String,
+        duplicate_place_id -> String,
+        similarity -> f64,
+        detected_at -> i64,
+        state -> String,
+        reviewed_at -> Option<i64>,
+        reviewed_by -> Option<String>,
+    });
+    import_table!(source, target, stats, events {
+        id -> i64,
+        uid -> String,
+        title -> String,
+        description -> Option<String>,
+        start -> i64,
+        end -> Option<i64>,
+        lat -> Option<f64>,
+        lng -> Option<f64>,
+        street -> Option<String>,
+        zip -> Option<String>,
+        city -> Option<String>,
+        country -> Option<String>,
+        state -> Option<String>,
+        email -> Option<String>,
+        telephone -> Option<String>,
+        homepage -> Option<String>,
+        created_by -> Option<i64>,
+        registration -> Option<i16>,
+        organizer -> Option<String>,
+        archived -> Option<i64>,
+        image_url -> Option<String>,
+        image_link_url -> Option<String>,
+        rrule -> Option<String>,
+        exdates -> Option<String>,
+        current_rev -> i64,
+    });
+    import_table!(source, target, stats, event_tags {
+        event_id -> i64,
+        tag -> String,
+    });
+    import_table!(source, target, stats, event_revision {
+        rowid -> i64,
+        parent_rowid -> i64,
+        rev -> i64,
+        created_at -> i64,
+        created_by -> Option<i64>,
+        context -> Option<String>,
+        comment -> Option<String>,
+        title -> String,
+        description -> Option<String>,
+        start -> i64,
+        end -> Option<i64>,
+        lat -> Option<f64>,
+        lng -> Option<f64>,
+        street -> Option<String>,
+        zip -> Option<String>,
+        city -> Option<String>,
+        country -> Option<String>,
+        state -> Option<String>,
+        email -> Option<String>,
+        telephone -> Option<String>,
+        homepage -> Option<String>,
+        registration -> Option<i16>,
+        organizer -> Option<String>,
+        image_url -> Option<String>,
+        image_link_url -> Option<String>,
+        rrule -> Option<String>,
+        exdates -> Option<String>,
+    });
+    import_table!(source, target, stats, event_revision_tag {
+        parent_rowid -> i64,
+        tag -> String,
+    });
+    import_table!(source, target, stats, event_revision_translation {
+        parent_rowid -> i64,
+        lang -> String,
+        title -> String,
+        description -> Option<String>,
+    });
+    import_table!(source, target, stats, bbox_subscriptions {
+        id -> i64,
+        uid -> String,
+        user_id -> i64,
+        south_west_lat -> f64,
+        south_west_lng -> f64,
+        north_east_lat -> f64,
+        north_east_lng -> f64,
+    });
+    import_table!(source, target, stats, organization {
+        rowid -> i64,
+        id -> String,
+        name -> String,
+        api_token -> String,
+    });
+    import_table!(source, target, stats, organization_tag {
+        org_rowid -> i64,
+        tag_label -> String,
+        tag_allow_add -> i16,
+        tag_allow_remove -> i16,
+        require_clearance -> i16,
+    });
+    import_table!(source, target, stats, organization_place_clearance {
+        rowid -> i64,
+        org_rowid -> i64,
+        place_rowid -> i64,
+        created_at -> i64,
+        last_cleared_revision -> Option<i64>,
+    });
+    import_table!(source, target, stats, organization_event_clearance {
+        rowid -> i64,
+        org_rowid -> i64,
+        event_rowid -> i64,
+        created_at -> i64,
+        last_cleared_revision -> Option<i64>,
+    });
+    import_table!(source, target, stats, organization_api_token {
+        rowid -> i64,
+        id -> String,
+        org_rowid -> i64,
+        name -> String,
+        token_hash -> String,
+        scopes -> String,
+        created_at -> i64,
+        expires_at -> Option<i64>,
+        last_used_at -> Option<i64>,
+        revoked_at -> Option<i64>,
+    });
+    import_table!(source, target, stats, organization_webhook {
+        rowid -> i64,
+        id -> String,
+        org_rowid -> i64,
+        url -> String,
+        secret -> String,
+        created_at -> i64,
+    });
+    import_table!(source, target, stats, webhook_delivery {
+        rowid -> i64,
+        id -> String,
+        webhook_rowid -> i64,
+        event_type -> String,
+        payload -> String,
+        created_at -> i64,
+        status -> i16,
+        attempts -> i64,
+        next_attempt_at -> Option<i64>,
+        last_attempt_at -> Option<i64>,
+        last_response_status -> Option<i64>,
+        last_error -> Option<String>,
+    });
+    import_table!(source, target, stats, organization_member {
+        rowid -> i64,
+        org_rowid -> i64,
+        user_id -> i64,
+        role -> String,
+    });
+    import_table!(source, target, stats, organization_place_clearance_log {
+        rowid -> i64,
+        org_rowid -> i64,
+        place_id -> String,
+        cleared_revision -> i64,
+        created_at -> i64,
+        created_by -> Option<String>,
+        context -> Option<String>,
+        comment -> Option<String>,
+    });
+    import_table!(source, target, stats, organization_event_clearance_log {
+        rowid -> i64,
+        org_rowid -> i64,
+        event_id -> String,
+        cleared_revision -> i64,
+        created_at -> i64,
+        created_by -> Option<String>,
+        context -> Option<String>,
+        comment -> Option<String>,
+    });
+    import_table!(source, target, stats, sent_reminders {
+        rowid -> i64,
+        place_rowid -> i64,
+        sent_at -> i64,
+        sent_to_email -> String,
+    });
+    import_table!(source, target, stats, review_tokens {
+        rowid -> i64,
+        place_rowid -> i64,
+        revision -> i64,
+        expires_at -> i64,
+        nonce -> String,
+    });
+    import_table!(source, target, stats, revoked_tokens {
+        id -> String,
+        expires_at -> i64,
+    });
+    // The triggers have logged all imported rows as new changes.
+    // They are replaced by the original change log.
+    diesel::delete(schema::change_log::table).execute(target)?;
+    import_table!(source, target, stats, change_log {
+        rowid -> i64,
+        changed_at -> i64,
+        kind -> String,
+        item_type -> String,
+        item_id -> String,
+        item_rev -> Option<i64>,
+        item_status -> Option<i16>,
+        place_id -> Option<String>,
+    });
+    // The imported row ids have been assigned explicitly. All sequences
+    // need to continue after the largest imported value.
+    diesel::sql_query(
+        r#"
+DO $$
+DECLARE
+    col RECORD;
+BEGIN
+    FOR col IN
+        SELECT table_name, column_name FROM information_schema.columns
+        WHERE table_schema = current_schema() AND column_default LIKE 'nextval%'
+    LOOP
+        EXECUTE format(
+            'SELECT setval(pg_get_serial_sequence(%L, %L), COALESCE(MAX(%I), 0) + 1, false) FROM %I',
+            col.table_name, col.column_name, col.column_name, col.table_name
+        );
+    END LOOP;
+END
+$$;
+"#,
+    )
+    .execute(target)?;
+    Ok(stats)
+}
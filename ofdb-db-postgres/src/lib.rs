@@ -0,0 +1,141 @@
+#[macro_use]
+extern crate diesel;
+
+use std::cell::{RefCell, RefMut};
+
+use anyhow::Result as Fallible;
+use diesel::{pg::PgConnection, r2d2};
+use diesel_migrations::{EmbeddedMigrations, MigrationHarness, embed_migrations};
+use ofdb_core::{repositories as repo, usecases as uc};
+
+mod import;
+mod models;
+mod repo_impl;
+mod schema;
+mod util;
+
+#[cfg(test)]
+mod tests;
+
+pub use self::import::{ImportStatistics, import_sqlite_database};
+
+const MIGRATIONS: EmbeddedMigrations = embed_migrations!("./migrations");
+
+type ConnectionManager = r2d2::ConnectionManager<PgConnection>;
+type ConnectionPool = r2d2::Pool<ConnectionManager>;
+type PooledConnection = r2d2::PooledConnection<ConnectionManager>;
+
+pub struct DbReadOnly<'a> {
+    _pool: &'a ConnectionPool,
+    conn: RefCell<PooledConnection>,
+}
+
+impl<'a> DbReadOnly<'a> {
+    fn try_new(pool: &'a ConnectionPool) -> Fallible<Self> {
+        let conn = pool.get().inspect_err(|err| {
+            log::error!("Failed to obtain pooled database connection for read-only access: {err}");
+        })?;
+        Ok(Self {
+            _pool: pool,
+            conn: RefCell::new(conn),
+        })
+    }
+}
+
+pub struct DbReadWrite<'a> {
+    _pool: &'a ConnectionPool,
+    conn: RefCell<PooledConnection>,
+}
+
+pub struct DbConnection<'a> {
+    conn: RefCell<&'a mut PgConnection>,
+}
+
+impl<'a> DbConnection<'a> {
+    fn new(conn: &'a mut PgConnection) -> Self {
+        Self {
+            conn: RefCell::new(conn),
+        }
+    }
+}
+
+impl<'a> DbReadWrite<'a> {
+    fn try_new(pool: &'a ConnectionPool) -> Fallible<Self> {
+        let conn = pool.get().inspect_err(|err| {
+            log::error!("Failed to obtain pooled database connection for read/write access: {err}");
+        })?;
+        Ok(Self {
+            _pool: pool,
+            conn: RefCell::new(conn),
+        })
+    }
+
+    pub fn transaction<T, F, E>(&mut self, f: F) -> Result<T, uc::Error>
+    where
+        F: FnOnce(&mut DbConnection) -> Result<T, E>,
+        E: Into<uc::Error>,
+    {
+        let mut usecase_error = None;
+        use diesel::Connection;
+        self.conn
+            .borrow_mut()
+            .transaction(|conn| {
+                f(&mut DbConnection::new(conn))
+                    .map_err(Into::into)
+                    .map_err(|err| {
+                        usecase_error = Some(err);
+                        diesel::result::Error::RollbackTransaction
+                    })
+            })
+            .map_err(|err| {
+                if let Some(usecase_error) = usecase_error {
+                    debug_assert!(matches!(err, diesel::result::Error::RollbackTransaction));
+                    usecase_error
+                } else {
+                    let err = match err {
+                        diesel::result::Error::NotFound => repo::Error::NotFound,
+                        _ => repo::Error::Other(err.into()),
+                    };
+                    uc::Error::Repo(err)
+                }
+            })
+    }
+
+    fn pg_conn(&self) -> RefMut<'_, PooledConnection> {
+        self.conn.borrow_mut()
+    }
+}
+
+#[derive(Clone)]
+pub struct Connections {
+    // In contrast to SQLite concurrent writers don't need to be
+    // serialized. Consistency is ensured by transactions.
+    pool: ConnectionPool,
+}
+
+impl Connections {
+    pub fn init(url: &str, pool_size: u32) -> Fallible<Self> {
+        // Establish a test connection before creating the connection pool
+        // to fail early instead of letting r2d2 retry until it times out.
+        use diesel::Connection as _;
+        let _ = PgConnection::establish(url)?;
+        let manager = ConnectionManager::new(url);
+        let pool = ConnectionPool::builder()
+            .max_size(pool_size)
+            .build(manager)?;
+        Ok(Self { pool })
+    }
+
+    pub fn shared(&self) -> Fallible<DbReadOnly<'_>> {
+        DbReadOnly::try_new(&self.pool)
+    }
+
+    pub fn exclusive(&self) -> Fallible<DbReadWrite<'_>> {
+        DbReadWrite::try_new(&self.pool)
+    }
+}
+
+pub fn run_embedded_database_migrations(conn: DbReadWrite<'_>) {
+    log::info!("Running embedded database migrations");
+    conn.pg_conn().run_pending_migrations(MIGRATIONS).unwrap();
+}
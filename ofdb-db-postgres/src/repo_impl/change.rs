@@ -0,0 +1,80 @@
+use super::*;
+
+impl ChangeRepo for DbReadWrite<'_> {
+    fn load_changes(&self, after: Option<&ChangeCursor>, limit: u64) -> Result<Vec<Change>> {
+        load_changes(&mut self.conn.borrow_mut(), after, limit)
+    }
+}
+
+impl ChangeRepo for DbReadOnly<'_> {
+    fn load_changes(&self, after: Option<&ChangeCursor>, limit: u64) -> Result<Vec<Change>> {
+        load_changes(&mut self.conn.borrow_mut(), after, limit)
+    }
+}
+
+impl ChangeRepo for DbConnection<'_> {
+    fn load_changes(&self, after: Option<&ChangeCursor>, limit: u64) -> Result<Vec<Change>> {
+        load_changes(&mut self.conn.borrow_mut(), after, limit)
+    }
+}
+
+fn load_changes(
+    conn: &mut PgConnection,
+    after: Option<&ChangeCursor>,
+    limit: u64,
+) -> Result<Vec<Change>> {
+    use schema::change_log::dsl;
+    let after = after.copied().map_or(0, i64::from);
+    let limit = i64::try_from(limit).unwrap_or(i64::MAX);
+    Ok(schema::change_log::table
+        .filter(dsl::rowid.gt(after))
+        .order_by(dsl::rowid)
+        .limit(limit)
+        .load::<models::ChangeLogEntry>(conn)
+        .map_err(from_diesel_err)?
+        .into_iter()
+        .filter_map(load_change)
+        .collect())
+}
+
+fn load_change(entry: models::ChangeLogEntry) -> Option<Change> {
+    let models::ChangeLogEntry {
+        rowid,
+        changed_at,
+        kind,
+        item_type,
+        item_id,
+        item_rev,
+        item_status,
+        place_id,
+    } = entry;
+    let item = match (item_type.as_str(), item_rev, item_status, place_id) {
+        ("place", Some(rev), Some(status), _) => ChangedItem::Place {
+            id: item_id.into(),
+            revision: Revision::from(rev as u64),
+            status: load_review_status(status).ok()?,
+        },
+        ("event", rev, _, _) => ChangedItem::Event {
+            id: item_id.into(),
+            revision: rev.map(|rev| Revision::from(rev as u64)),
+        },
+        ("rating", _, _, Some(place_id)) => ChangedItem::Rating {
+            id: item_id.into(),
+            place_id: place_id.into(),
+        },
+        _ => {
+            log::warn!("Ignoring invalid change {rowid} of {item_type} {item_id}");
+            return None;
+        }
+    };
+    let kind = kind
+        .parse()
+        .map_err(|_| log::warn!("Ignoring change {rowid} with unknown kind '{kind}'"))
+        .ok()?;
+    Some(Change {
+        cursor: rowid.into(),
+        changed_at: Timestamp::try_from_millis(changed_at).ok()?,
+        kind,
+        item,
+    })
+}
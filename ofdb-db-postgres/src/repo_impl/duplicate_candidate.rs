@@ -0,0 +1,261 @@
+use std::collections::{HashMap, HashSet};
+
+use super::*;
+
+impl DuplicateCandidateRepo for DbReadWrite<'_> {
+    fn replace_open_duplicate_candidates(
+        &self,
+        candidates: &[DuplicateCandidate],
+    ) -> Result<usize> {
+        replace_open_duplicate_candidates(&mut self.conn.borrow_mut(), candidates)
+    }
+    fn count_open_duplicate_candidates(&self) -> Result<usize> {
+        count_open_duplicate_candidates(&mut self.conn.borrow_mut())
+    }
+    fn list_open_duplicate_candidates(
+        &self,
+        pagination: &Pagination,
+    ) -> Result<Vec<DuplicateCandidate>> {
+        list_open_duplicate_candidates(&mut self.conn.borrow_mut(), pagination)
+    }
+    fn review_duplicate_candidate(
+        &self,
+        place_id: &Id,
+        duplicate_place_id: &Id,
+        state: DuplicateCandidateState,
+        activity: &Activity,
+    ) -> Result<usize> {
+        review_duplicate_candidate(
+            &mut self.conn.borrow_mut(),
+            place_id,
+            duplicate_place_id,
+            state,
+            activity,
+        )
+    }
+}
+
+impl DuplicateCandidateRepo for DbConnection<'_> {
+    fn replace_open_duplicate_candidates(
+        &self,
+        candidates: &[DuplicateCandidate],
+    ) -> Result<usize> {
+        replace_open_duplicate_candidates(&mut self.conn.borrow_mut(), candidates)
+    }
+    fn count_open_duplicate_candidates(&self) -> Result<usize> {
+        count_open_duplicate_candidates(&mut self.conn.borrow_mut())
+    }
+    fn list_open_duplicate_candidates(
+        &self,
+        pagination: &Pagination,
+    ) -> Result<Vec<DuplicateCandidate>> {
+        list_open_duplicate_candidates(&mut self.conn.borrow_mut(), pagination)
+    }
+    fn review_duplicate_candidate(
+        &self,
+        place_id: &Id,
+        duplicate_place_id: &Id,
+        state: DuplicateCandidateState,
+        activity: &Activity,
+    ) -> Result<usize> {
+        review_duplicate_candidate(
+            &mut self.conn.borrow_mut(),
+            place_id,
+            duplicate_place_id,
+            state,
+            activity,
+        )
+    }
+}
+
+impl DuplicateCandidateRepo for DbReadOnly<'_> {
+    fn replace_open_duplicate_candidates(
+        &self,
+        _candidates: &[DuplicateCandidate],
+    ) -> Result<usize> {
+        unreachable!();
+    }
+    fn count_open_duplicate_candidates(&self) -> Result<usize> {
+        count_open_duplicate_candidates(&mut self.conn.borrow_mut())
+    }
+    fn list_open_duplicate_candidates(
+        &self,
+        pagination: &Pagination,
+    ) -> Result<Vec<DuplicateCandidate>> {
+        list_open_duplicate_candidates(&mut self.conn.borrow_mut(), pagination)
+    }
+    fn review_duplicate_candidate(
+        &self,
+        _place_id: &Id,
+        _duplicate_place_id: &Id,
+        _state: DuplicateCandidateState,
+        _activity: &Activity,
+    ) -> Result<usize> {
+        unreachable!();
+    }
+}
+
+const OPEN: &str = "open";
+
+fn replace_open_duplicate_candidates(
+    conn: &mut PgConnection,
+    candidates: &[DuplicateCandidate],
+) -> Result<usize> {
+    use schema::place_duplicate_candidate::dsl;
+    let existing: HashMap<_, _> = schema::place_duplicate_candidate::table
+        .select((
+            dsl::rowid,
+            dsl::place_id,
+            dsl::duplicate_place_id,
+            dsl::state,
+        ))
+        .load::<(i64, String, String, String)>(conn)
+        .map_err(from_diesel_err)?
+        .into_iter()
+        .map(|(rowid, place_id, duplicate_place_id, state)| {
+            ((place_id, duplicate_place_id), (rowid, state))
+        })
+        .collect();
+    let mut detected_rowids = HashSet::with_capacity(candidates.len());
+    let mut count = 0;
+    for candidate in candidates {
+        let key = (
+            candidate.place_id.to_string(),
+            candidate.duplicate_place_id.to_string(),
+        );
+        match existing.get(&key) {
+            Some((rowid, state)) => {
+                if state == OPEN {
+                    diesel::update(schema::place_duplicate_candidate::table.find(rowid))
+                        .set(dsl::similarity.eq(candidate.similarity))
+                        .execute(conn)
+                        .map_err(from_diesel_err)?;
+                }
+                detected_rowids.insert(*rowid);
+            }
+            None => {
+                let new_candidate = models::NewPlaceDuplicateCandidate {
+                    place_id: candidate.place_id.as_str(),
+                    duplicate_place_id: candidate.duplicate_place_id.as_str(),
+                    similarity: candidate.similarity,
+                    detected_at: candidate.detected_at.as_millis(),
+                    state: OPEN,
+                };
+                diesel::insert_into(schema::place_duplicate_candidate::table)
+                    .values(&new_candidate)
+                    .execute(conn)
+                    .map_err(from_diesel_err)?;
+                count += 1;
+            }
+        }
+    }
+    let outdated_rowids: Vec<_> = existing
+        .into_values()
+        .filter(|(rowid, state)| state == OPEN && !detected_rowids.contains(rowid))
+        .map(|(rowid, _)| rowid)
+        .collect();
+    for rowids in outdated_rowids.chunks(1000) {
+        diesel::delete(schema::place_duplicate_candidate::table.filter(dsl::rowid.eq_any(rowids)))
+            .execute(conn)
+            .map_err(from_diesel_err)?;
+    }
+    Ok(count)
+}
+
+fn count_open_duplicate_candidates(conn: &mut PgConnection) -> Result<usize> {
+    use schema::place_duplicate_candidate::dsl;
+    Ok(schema::place_duplicate_candidate::table
+        .filter(dsl::state.eq(OPEN))
+        .count()
+        .get_result::<i64>(conn)
+        .map_err(from_diesel_err)? as usize)
+}
+
+fn list_open_duplicate_candidates(
+    conn: &mut PgConnection,
+    pagination: &Pagination,
+) -> Result<Vec<DuplicateCandidate>> {
+    use schema::place_duplicate_candidate::dsl;
+    let mut query = schema::place_duplicate_candidate::table
+        .select((
+            dsl::place_id,
+            dsl::duplicate_place_id,
+            dsl::similarity,
+            dsl::detected_at,
+            dsl::state,
+            dsl::reviewed_at,
+            dsl::reviewed_by,
+        ))
+        .filter(dsl::state.eq(OPEN))
+        .order_by((dsl::similarity.desc(), dsl::rowid))
+        .into_boxed();
+
+    // Pagination
+    let offset = pagination.offset.unwrap_or(0) as i64;
+    if let Some(limit) = pagination.limit {
+        query = query.limit(limit as i64);
+    }
+    if offset > 0 {
+        query = query.offset(offset);
+    }
+
+    Ok(query
+        .load::<models::PlaceDuplicateCandidate>(conn)
+        .map_err(from_diesel_err)?
+        .into_iter()
+        .filter_map(load_duplicate_candidate)
+        .collect())
+}
+
+fn review_duplicate_candidate(
+    conn: &mut PgConnection,
+    place_id: &Id,
+    duplicate_place_id: &Id,
+    state: DuplicateCandidateState,
+    activity: &Activity,
+) -> Result<usize> {
+    use schema::place_duplicate_candidate::dsl;
+    diesel::update(
+        schema::place_duplicate_candidate::table
+            .filter(dsl::place_id.eq(place_id.as_str()))
+            .filter(dsl::duplicate_place_id.eq(duplicate_place_id.as_str()))
+            .filter(dsl::state.eq(OPEN)),
+    )
+    .set((
+        dsl::state.eq(state.as_str()),
+        dsl::reviewed_at.eq(activity.at.as_millis()),
+        dsl::reviewed_by.eq(activity.by.as_ref().map(EmailAddress::as_str)),
+    ))
+    .execute(conn)
+    .map_err(from_diesel_err)
+}
+
+fn load_duplicate_candidate(
+    candidate: models::PlaceDuplicateCandidate,
+) -> Option<DuplicateCandidate> {
+    let models::PlaceDuplicateCandidate {
+        place_id,
+        duplicate_place_id,
+        similarity,
+        detected_at,
+        state,
+        reviewed_at,
+        reviewed_by,
+    } = candidate;
+    let state = state
+        .parse()
+        .map_err(|_| log::warn!("Ignoring duplicate candidate with unknown state '{state}'"))
+        .ok()?;
+    let reviewed = reviewed_at.map(|at| Activity {
+        at: Timestamp::try_from_millis(at).unwrap(),
+        by: reviewed_by.map(EmailAddress::new_unchecked),
+    });
+    Some(DuplicateCandidate {
+        place_id: place_id.into(),
+        duplicate_place_id: duplicate_place_id.into(),
+        similarity,
+        detected_at: Timestamp::try_from_millis(detected_at).unwrap(),
+        state,
+        reviewed,
+    })
+}
@@ -0,0 +1,840 @@
+use super::*;
+
+impl EventRepo for DbReadOnly<'_> {
+    fn create_event(&self, _ev: Event, _activity: &ActivityLog) -> Result<()> {
+        unreachable!();
+    }
+    fn update_event(&self, _ev: &Event, _activity: &ActivityLog) -> Result<()> {
+        unreachable!();
+    }
+    fn archive_events(&self, _ids: &[&str], _archived: Timestamp) -> Result<usize> {
+        unreachable!();
+    }
+
+    fn get_event(&self, id: &str) -> Result<Event> {
+        get_event(&mut self.conn.borrow_mut(), id)
+    }
+    fn get_events_chronologically(&self, ids: &[&str]) -> Result<Vec<Event>> {
+        get_events_chronologically(&mut self.conn.borrow_mut(), ids)
+    }
+
+    fn all_events_chronologically(&self) -> Result<Vec<Event>> {
+        all_events_chronologically(&mut self.conn.borrow_mut())
+    }
+    fn all_events_in_bbox_chronologically(&self, bbox: &MapBbox) -> Result<Vec<Event>> {
+        all_events_in_bbox_chronologically(&mut self.conn.borrow_mut(), bbox)
+    }
+    fn all_archived_events_chronologically(&self) -> Result<Vec<Event>> {
+        all_archived_events_chronologically(&mut self.conn.borrow_mut())
+    }
+
+    fn count_events(&self) -> Result<usize> {
+        count_events(&mut self.conn.borrow_mut())
+    }
+
+    fn delete_event_with_matching_tags(&self, _id: &str, _tags: &[&str]) -> Result<bool> {
+        unreachable!();
+    }
+
+    fn is_event_owned_by_any_organization(&self, id: &str) -> Result<bool> {
+        is_event_owned_by_any_organization(&mut self.conn.borrow_mut(), id)
+    }
+
+    fn get_event_history(&self, id: &str, revision: Option<Revision>) -> Result<EventHistory> {
+        get_event_history(&mut self.conn.borrow_mut(), id, revision)
+    }
+
+    fn load_event_revision(&self, id: &str, rev: Revision) -> Result<Event> {
+        load_event_revision(&mut self.conn.borrow_mut(), id, rev)
+    }
+}
+
+impl EventRepo for DbReadWrite<'_> {
+    fn create_event(&self, ev: Event, activity: &ActivityLog) -> Result<()> {
+        create_event(&mut self.conn.borrow_mut(), ev, activity)
+    }
+    fn update_event(&self, ev: &Event, activity: &ActivityLog) -> Result<()> {
+        update_event(&mut self.conn.borrow_mut(), ev, activity)
+    }
+    fn archive_events(&self, ids: &[&str], archived: Timestamp) -> Result<usize> {
+        archive_events(&mut self.conn.borrow_mut(), ids, archived)
+    }
+
+    fn get_event(&self, id: &str) -> Result<Event> {
+        get_event(&mut self.conn.borrow_mut(), id)
+    }
+    fn get_events_chronologically(&self, ids: &[&str]) -> Result<Vec<Event>> {
+        get_events_chronologically(&mut self.conn.borrow_mut(), ids)
+    }
+
+    fn all_events_chronologically(&self) -> Result<Vec<Event>> {
+        all_events_chronologically(&mut self.conn.borrow_mut())
+    }
+    fn all_events_in_bbox_chronologically(&self, bbox: &MapBbox) -> Result<Vec<Event>> {
+        all_events_in_bbox_chronologically(&mut self.conn.borrow_mut(), bbox)
+    }
+    fn all_archived_events_chronologically(&self) -> Result<Vec<Event>> {
+        all_archived_events_chronologically(&mut self.conn.borrow_mut())
+    }
+
+    fn count_events(&self) -> Result<usize> {
+        count_events(&mut self.conn.borrow_mut())
+    }
+
+    fn delete_event_with_matching_tags(&self, id: &str, tags: &[&str]) -> Result<bool> {
+        delete_event_with_matching_tags(&mut self.conn.borrow_mut(), id, tags)
+    }
+
+    fn is_event_owned_by_any_organization(&self, id: &str) -> Result<bool> {
+        is_event_owned_by_any_organization(&mut self.conn.borrow_mut(), id)
+    }
+
+    fn get_event_history(&self, id: &str, revision: Option<Revision>) -> Result<EventHistory> {
+        get_event_history(&mut self.conn.borrow_mut(), id, revision)
+    }
+
+    fn load_event_revision(&self, id: &str, rev: Revision) -> Result<Event> {
+        load_event_revision(&mut self.conn.borrow_mut(), id, rev)
+    }
+}
+
+impl EventRepo for DbConnection<'_> {
+    fn create_event(&self, ev: Event, activity: &ActivityLog) -> Result<()> {
+        create_event(&mut self.conn.borrow_mut(), ev, activity)
+    }
+    fn update_event(&self, ev: &Event, activity: &ActivityLog) -> Result<()> {
+        update_event(&mut self.conn.borrow_mut(), ev, activity)
+    }
+    fn archive_events(&self, ids: &[&str], archived: Timestamp) -> Result<usize> {
+        archive_events(&mut self.conn.borrow_mut(), ids, archived)
+    }
+
+    fn get_event(&self, id: &str) -> Result<Event> {
+        get_event(&mut self.conn.borrow_mut(), id)
+    }
+    fn get_events_chronologically(&self, ids: &[&str]) -> Result<Vec<Event>> {
+        get_events_chronologically(&mut self.conn.borrow_mut(), ids)
+    }
+
+    fn all_events_chronologically(&self) -> Result<Vec<Event>> {
+        all_events_chronologically(&mut self.conn.borrow_mut())
+    }
+    fn all_events_in_bbox_chronologically(&self, bbox: &MapBbox) -> Result<Vec<Event>> {
+        all_events_in_bbox_chronologically(&mut self.conn.borrow_mut(), bbox)
+    }
+    fn all_archived_events_chronologically(&self) -> Result<Vec<Event>> {
+        all_archived_events_chronologically(&mut self.conn.borrow_mut())
+    }
+
+    fn count_events(&self) -> Result<usize> {
+        count_events(&mut self.conn.borrow_mut())
+    }
+
+    fn delete_event_with_matching_tags(&self, id: &str, tags: &[&str]) -> Result<bool> {
+        delete_event_with_matching_tags(&mut self.conn.borrow_mut(), id, tags)
+    }
+
+    fn is_event_owned_by_any_organization(&self, id: &str) -> Result<bool> {
+        is_event_owned_by_any_organization(&mut self.conn.borrow_mut(), id)
+    }
+
+    fn get_event_history(&self, id: &str, revision: Option<Revision>) -> Result<EventHistory> {
+        get_event_history(&mut self.conn.borrow_mut(), id, revision)
+    }
+
+    fn load_event_revision(&self, id: &str, rev: Revision) -> Result<Event> {
+        load_event_revision(&mut self.conn.borrow_mut(), id, rev)
+    }
+}
+
+fn create_event(conn: &mut PgConnection, e: Event, activity: &ActivityLog) -> Result<()> {
+    debug_assert!(e.revision.is_initial());
+    let translations = e.translations.clone();
+    let (new_event, tags) = into_new_event_with_tags(conn, e)?;
+    let activity = into_new_event_revision_activity(conn, activity)?;
+    conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        // Insert event
+        diesel::insert_into(schema::events::table)
+            .values(&new_event)
+            .execute(conn)?;
+        let id = resolve_event_id(conn, new_event.uid.as_ref()).map_err(|err| {
+            log::warn!(
+                "Failed to resolve id of newly created event {}: {}",
+                new_event.uid,
+                err,
+            );
+            diesel::result::Error::RollbackTransaction
+        })?;
+        // Insert event tags
+        let event_tags: Vec<_> = tags
+            .iter()
+            .map(|tag| models::NewEventTag { event_id: id, tag })
+            .collect();
+        diesel::insert_into(schema::event_tags::table)
+            .values(&event_tags)
+            .on_conflict_do_nothing()
+            .execute(conn)?;
+        insert_event_revision(conn, id, &new_event, &tags, &translations, activity)
+    })
+    .map_err(from_diesel_err)?;
+    Ok(())
+}
+
+fn update_event(conn: &mut PgConnection, event: &Event, activity: &ActivityLog) -> Result<()> {
+    let (id, revision) = resolve_event_id_with_current_revision(conn, event.id.as_ref())?;
+    // Check for a contiguous revision history without conflicts (optimistic
+    // locking)
+    if revision.next() != event.revision {
+        return Err(repo::Error::InvalidVersion);
+    }
+    let (new_event, new_tags) = into_new_event_with_tags(conn, event.clone())?;
+    let activity = into_new_event_revision_activity(conn, activity)?;
+    conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        use schema::{event_tags::dsl as et_dsl, events::dsl as e_dsl};
+        // Update event
+        let count = diesel::update(
+            e_dsl::events
+                .filter(e_dsl::id.eq(&id))
+                .filter(e_dsl::current_rev.eq(u64::from(revision) as i64)),
+        )
+        .set(&new_event)
+        .execute(conn)?;
+        if count == 0 {
+            // Concurrent modification
+            return Err(diesel::result::Error::RollbackTransaction);
+        }
+        // The changeset ignores missing values, but a recurrence
+        // might have been removed from the event
+        diesel::update(e_dsl::events.filter(e_dsl::id.eq(&id)))
+            .set((
+                e_dsl::rrule.eq(&new_event.rrule),
+                e_dsl::exdates.eq(&new_event.exdates),
+            ))
+            .execute(conn)?;
+        // Update event tags
+        let tags_diff = {
+            let old_tags = et_dsl::event_tags
+                .select(et_dsl::tag)
+                .filter(et_dsl::event_id.eq(id))
+                .load(conn)?;
+            super::util::tags_diff(&old_tags, &new_tags)
+        };
+        diesel::delete(
+            et_dsl::event_tags
+                .filter(et_dsl::event_id.eq(id))
+                .filter(et_dsl::tag.eq_any(&tags_diff.deleted)),
+        )
+        .execute(conn)?;
+        {
+            let new_tags: Vec<_> = tags_diff
+                .added
+                .iter()
+                .map(|tag| models::NewEventTag { event_id: id, tag })
+                .collect();
+            diesel::insert_into(et_dsl::event_tags)
+                .values(&new_tags)
+                .on_conflict_do_nothing()
+                .execute(conn)?;
+        }
+        insert_event_revision(
+            conn,
+            id,
+            &new_event,
+            &new_tags,
+            &event.translations,
+            activity,
+        )
+    })
+    .map_err(|err| match err {
+        diesel::result::Error::RollbackTransaction => repo::Error::InvalidVersion,
+        err => from_diesel_err(err),
+    })?;
+    Ok(())
+}
+
+// Activity columns of a new event revision: created_at, created_by, context, comment
+type NewEventRevisionActivity = (i64, Option<i64>, Option<String>, Option<String>);
+
+fn into_new_event_revision_activity(
+    conn: &mut PgConnection,
+    activity_log: &ActivityLog,
+) -> Result<NewEventRevisionActivity> {
+    let ActivityLog {
+        activity: Activity { at, by },
+        context,
+        comment,
+    } = activity_log;
+    let created_by = if let Some(email) = by {
+        Some(resolve_user_created_by_email(conn, email)?)
+    } else {
+        None
+    };
+    Ok((at.as_millis(), created_by, context.clone(), comment.clone()))
+}
+
+fn insert_event_revision(
+    conn: &mut PgConnection,
+    parent_rowid: i64,
+    event: &models::NewEvent,
+    tags: &[String],
+    translations: &[Translation],
+    activity: NewEventRevisionActivity,
+) -> diesel::QueryResult<()> {
+    let (created_at, created_by, context, comment) = activity;
+    let new_revision = models::NewEventRevision {
+        parent_rowid,
+        rev: event.current_rev,
+        created_at,
+        created_by,
+        context,
+        comment,
+        title: event.title.clone(),
+        description: event.description.clone(),
+        start: event.start,
+        end: event.end,
+        lat: event.lat,
+        lng: event.lng,
+        street: event.street.clone(),
+        zip: event.zip.clone(),
+        city: event.city.clone(),
+        country: event.country.clone(),
+        state: event.state.clone(),
+        email: event.email.clone(),
+        telephone: event.telephone.clone(),
+        homepage: event.homepage.clone(),
+        registration: event.registration,
+        organizer: event.organizer.clone(),
+        image_url: event.image_url.clone(),
+        image_link_url: event.image_link_url.clone(),
+        rrule: event.rrule.clone(),
+        exdates: event.exdates.clone(),
+    };
+    diesel::insert_into(schema::event_revision::table)
+        .values(&new_revision)
+        .execute(conn)?;
+    use schema::event_revision::dsl as rev_dsl;
+    let rowid = rev_dsl::event_revision
+        .select(rev_dsl::rowid)
+        .filter(rev_dsl::parent_rowid.eq(parent_rowid))
+        .filter(rev_dsl::rev.eq(event.current_rev))
+        .first::<i64>(conn)?;
+    let tags: Vec<_> = tags
+        .iter()
+        .map(|tag| models::NewEventRevisionTag {
+            parent_rowid: rowid,
+            tag,
+        })
+        .collect();
+    diesel::insert_into(schema::event_revision_tag::table)
+        .values(&tags)
+        .on_conflict_do_nothing()
+        .execute(conn)?;
+    let translations: Vec<_> = translations
+        .iter()
+        .map(
+            |Translation {
+                 lang,
+                 title,
+                 description,
+             }| models::NewEventRevisionTranslation {
+                parent_rowid: rowid,
+                lang: lang.as_str(),
+                title,
+                description: description.as_deref(),
+            },
+        )
+        .collect();
+    diesel::insert_into(schema::event_revision_translation::table)
+        .values(&translations)
+        .execute(conn)?;
+    Ok(())
+}
+
+fn load_event_revision_translations(
+    conn: &mut PgConnection,
+    event_revision_rowid: i64,
+) -> Result<Vec<Translation>> {
+    use schema::event_revision_translation::dsl;
+    Ok(schema::event_revision_translation::table
+        .filter(dsl::parent_rowid.eq(event_revision_rowid))
+        .load::<models::EventRevisionTranslation>(conn)
+        .map_err(from_diesel_err)?
+        .into_iter()
+        .filter_map(
+            |models::EventRevisionTranslation {
+                 parent_rowid: _,
+                 lang,
+                 title,
+                 description,
+             }| util::load_translation(lang, title, description),
+        )
+        .collect())
+}
+
+// The translations of the current revisions of all events
+// that are not archived
+fn load_current_event_translations(conn: &mut PgConnection) -> Result<Vec<(i64, Translation)>> {
+    use schema::{
+        event_revision::dsl as rev_dsl, event_revision_translation::dsl as tr_dsl,
+        events::dsl as e_dsl,
+    };
+    Ok(tr_dsl::event_revision_translation
+        .inner_join(rev_dsl::event_revision)
+        .inner_join(
+            e_dsl::events.on(e_dsl::id
+                .eq(rev_dsl::parent_rowid)
+                .and(e_dsl::current_rev.eq(rev_dsl::rev))),
+        )
+        .select((e_dsl::id, tr_dsl::lang, tr_dsl::title, tr_dsl::description))
+        .filter(e_dsl::archived.is_null())
+        .load::<(i64, String, String, Option<String>)>(conn)
+        .map_err(from_diesel_err)?
+        .into_iter()
+        .filter_map(|(event_id, lang, title, description)| {
+            util::load_translation(lang, title, description)
+                .map(|translation| (event_id, translation))
+        })
+        .collect())
+}
+
+fn get_events_chronologically(conn: &mut PgConnection, ids: &[&str]) -> Result<Vec<Event>> {
+    use schema::{event_tags::dsl as et_dsl, events::dsl as e_dsl, users::dsl as u_dsl};
+
+    let rows = e_dsl::events
+        .left_outer_join(u_dsl::users)
+        .select((
+            e_dsl::id,
+            e_dsl::uid,
+            e_dsl::title,
+            e_dsl::description,
+            e_dsl::start,
+            e_dsl::end,
+            e_dsl::lat,
+            e_dsl::lng,
+            e_dsl::street,
+            e_dsl::zip,
+            e_dsl::city,
+            e_dsl::country,
+            e_dsl::state,
+            e_dsl::email,
+            e_dsl::telephone,
+            e_dsl::homepage,
+            e_dsl::created_by,
+            e_dsl::registration,
+            e_dsl::organizer,
+            e_dsl::archived,
+            e_dsl::image_url,
+            e_dsl::image_link_url,
+            e_dsl::rrule,
+            e_dsl::exdates,
+            e_dsl::current_rev,
+            u_dsl::email.nullable(),
+        ))
+        .filter(e_dsl::uid.eq_any(ids))
+        .filter(e_dsl::archived.is_null())
+        .order_by(e_dsl::start)
+        .load::<models::EventEntity>(conn)
+        .map_err(from_diesel_err)?;
+    debug_assert!(rows.len() <= ids.len());
+    let mut events = Vec::with_capacity(rows.len());
+    for row in rows.into_iter() {
+        let models::EventEntity {
+            id,
+            uid,
+            title,
+            description,
+            start,
+            end,
+            lat,
+            lng,
+            street,
+            zip,
+            city,
+            country,
+            state,
+            email,
+            telephone,
+            homepage,
+            registration,
+            organizer,
+            archived,
+            image_url,
+            image_link_url,
+            rrule,
+            exdates,
+            current_rev,
+            created_by_email,
+            ..
+        } = row;
+
+        let tags = et_dsl::event_tags
+            .select(et_dsl::tag)
+            .filter(et_dsl::event_id.eq(id))
+            .load::<String>(conn)
+            .map_err(from_diesel_err)?;
+
+        let translations = {
+            use schema::event_revision::dsl as rev_dsl;
+            let revision_rowid = rev_dsl::event_revision
+                .select(rev_dsl::rowid)
+                .filter(rev_dsl::parent_rowid.eq(id))
+                .filter(rev_dsl::rev.eq(current_rev))
+                .first::<i64>(conn)
+                .optional()
+                .map_err(from_diesel_err)?;
+            match revision_rowid {
+                Some(rowid) => load_event_revision_translations(conn, rowid)?,
+                None => vec![],
+            }
+        };
+
+        let address = Address {
+            street,
+            zip,
+            city,
+            country,
+            state,
+        };
+
+        let address = if address.is_empty() {
+            None
+        } else {
+            Some(address)
+        };
+
+        let pos = if let (Some(lat), Some(lng)) = (lat, lng) {
+            MapPoint::try_from_lat_lng_deg(lat, lng)
+                .map(Some)
+                .unwrap_or_default()
+        } else {
+            None
+        };
+        let location = if pos.is_some() || address.is_some() {
+            Some(Location {
+                pos: pos.unwrap_or_default(),
+                address,
+            })
+        } else {
+            None
+        };
+        let contact = if organizer.is_some() || email.is_some() || telephone.is_some() {
+            Some(Contact {
+                name: organizer,
+                email: email.map(EmailAddress::new_unchecked),
+                phone: telephone,
+            })
+        } else {
+            None
+        };
+
+        let registration = registration.map(util::registration_type_from_i16);
+        let created_by = created_by_email.map(EmailAddress::new_unchecked);
+
+        let event = Event {
+            id: uid.into(),
+            revision: Revision::from(current_rev as u64),
+            title,
+            start: Timestamp::try_from_secs(start).unwrap(),
+            end: end.map(Timestamp::try_from_secs).transpose().unwrap(),
+            description,
+            location,
+            contact,
+            homepage: homepage.and_then(load_url),
+            tags,
+            created_by,
+            registration,
+            archived: archived.map(Timestamp::try_from_secs).transpose().unwrap(),
+            image_url: image_url.and_then(load_url),
+            image_link_url: image_link_url.and_then(load_url),
+            recurrence: util::load_recurrence(rrule, exdates),
+            translations,
+        };
+        events.push(event);
+    }
+
+    Ok(events)
+}
+
+fn get_event(conn: &mut PgConnection, id: &str) -> Result<Event> {
+    let events = get_events_chronologically(conn, &[id])?;
+    debug_assert!(events.len() <= 1);
+    events.into_iter().next().ok_or(repo::Error::NotFound)
+}
+
+fn all_events_chronologically(conn: &mut PgConnection) -> Result<Vec<Event>> {
+    query_all_events_chronologically(conn, false, None)
+}
+
+fn all_archived_events_chronologically(conn: &mut PgConnection) -> Result<Vec<Event>> {
+    query_all_events_chronologically(conn, true, None)
+}
+
+fn all_events_in_bbox_chronologically(
+    conn: &mut PgConnection,
+    bbox: &MapBbox,
+) -> Result<Vec<Event>> {
+    query_all_events_chronologically(conn, false, Some(bbox))
+}
+
+fn query_all_events_chronologically(
+    conn: &mut PgConnection,
+    archived: bool,
+    bbox: Option<&MapBbox>,
+) -> Result<Vec<Event>> {
+    use schema::{event_tags::dsl as et_dsl, events::dsl as e_dsl, users::dsl as u_dsl};
+    let mut query = e_dsl::events
+        .left_outer_join(u_dsl::users)
+        .select((
+            e_dsl::id,
+            e_dsl::uid,
+            e_dsl::title,
+            e_dsl::description,
+            e_dsl::start,
+            e_dsl::end,
+            e_dsl::lat,
+            e_dsl::lng,
+            e_dsl::street,
+            e_dsl::zip,
+            e_dsl::city,
+            e_dsl::country,
+            e_dsl::state,
+            e_dsl::email,
+            e_dsl::telephone,
+            e_dsl::homepage,
+            e_dsl::created_by,
+            e_dsl::registration,
+            e_dsl::organizer,
+            e_dsl::archived,
+            e_dsl::image_url,
+            e_dsl::image_link_url,
+            e_dsl::rrule,
+            e_dsl::exdates,
+            e_dsl::current_rev,
+            u_dsl::email.nullable(),
+        ))
+        .order_by(e_dsl::start)
+        .into_boxed();
+    query = if archived {
+        query.filter(e_dsl::archived.is_not_null())
+    } else {
+        query.filter(e_dsl::archived.is_null())
+    };
+    if let Some(bbox) = bbox {
+        query = query.filter(geom_within_bbox("events.geom", bbox));
+    }
+    let events: Vec<_> = query
+        .load::<models::EventEntity>(conn)
+        .map_err(from_diesel_err)?;
+    let tag_rels = et_dsl::event_tags.load(conn).map_err(from_diesel_err)?;
+    let translation_rels = load_current_event_translations(conn)?;
+    Ok(events
+        .into_iter()
+        .map(|e| util::event_from_event_entity_and_tags(e, &tag_rels, &translation_rels))
+        .collect())
+}
+
+fn count_events(conn: &mut PgConnection) -> Result<usize> {
+    use schema::events::dsl;
+    Ok(dsl::events
+        .select(diesel::dsl::count(dsl::id))
+        .filter(dsl::archived.is_null())
+        .first::<i64>(conn)
+        .map_err(from_diesel_err)? as usize)
+}
+
+fn archive_events(conn: &mut PgConnection, ids: &[&str], archived: Timestamp) -> Result<usize> {
+    use schema::events::dsl;
+    let count = diesel::update(
+        dsl::events
+            .filter(dsl::uid.eq_any(ids))
+            .filter(dsl::archived.is_null()),
+    )
+    .set(dsl::archived.eq(Some(archived.as_secs())))
+    .execute(conn)
+    .map_err(from_diesel_err)?;
+    debug_assert!(count <= ids.len());
+    Ok(count)
+}
+
+fn delete_event_with_matching_tags(
+    conn: &mut PgConnection,
+    id: &str,
+    tags: &[&str],
+) -> Result<bool> {
+    use schema::{event_tags::dsl as et_dsl, events::dsl as e_dsl};
+    let id = resolve_event_id(conn, id)?;
+    if !tags.is_empty() {
+        let ids: Vec<_> = et_dsl::event_tags
+            .select(et_dsl::event_id)
+            .distinct()
+            .filter(et_dsl::event_id.eq(id))
+            .filter(et_dsl::tag.eq_any(tags))
+            .load::<i64>(conn)
+            .map_err(from_diesel_err)?;
+        debug_assert!(ids.len() <= 1);
+        if ids.is_empty() {
+            return Ok(false);
+        }
+        debug_assert_eq!(id, *ids.first().unwrap());
+    }
+    diesel::delete(et_dsl::event_tags.filter(et_dsl::event_id.eq(id)))
+        .execute(conn)
+        .map_err(from_diesel_err)?;
+    {
+        use schema::{
+            event_revision::dsl as rev_dsl, event_revision_tag::dsl as rev_tag_dsl,
+            event_revision_translation::dsl as rev_tr_dsl,
+        };
+        diesel::delete(
+            rev_tag_dsl::event_revision_tag.filter(
+                rev_tag_dsl::parent_rowid.eq_any(
+                    rev_dsl::event_revision
+                        .select(rev_dsl::rowid)
+                        .filter(rev_dsl::parent_rowid.eq(id)),
+                ),
+            ),
+        )
+        .execute(conn)
+        .map_err(from_diesel_err)?;
+        diesel::delete(
+            rev_tr_dsl::event_revision_translation.filter(
+                rev_tr_dsl::parent_rowid.eq_any(
+                    rev_dsl::event_revision
+                        .select(rev_dsl::rowid)
+                        .filter(rev_dsl::parent_rowid.eq(id)),
+                ),
+            ),
+        )
+        .execute(conn)
+        .map_err(from_diesel_err)?;
+        diesel::delete(rev_dsl::event_revision.filter(rev_dsl::parent_rowid.eq(id)))
+            .execute(conn)
+            .map_err(from_diesel_err)?;
+    }
+    {
+        use schema::organization_event_clearance::dsl as clearance_dsl;
+        diesel::delete(
+            clearance_dsl::organization_event_clearance.filter(clearance_dsl::event_rowid.eq(id)),
+        )
+        .execute(conn)
+        .map_err(from_diesel_err)?;
+    }
+    diesel::delete(e_dsl::events.filter(e_dsl::id.eq(id)))
+        .execute(conn)
+        .map_err(from_diesel_err)?;
+    Ok(true)
+}
+
+fn get_event_history(
+    conn: &mut PgConnection,
+    id: &str,
+    revision: Option<Revision>,
+) -> Result<EventHistory> {
+    use schema::{
+        event_revision::dsl as rev_dsl, event_revision_tag::dsl as tag_dsl, events::dsl as e_dsl,
+        users::dsl as u_dsl,
+    };
+    let mut query = rev_dsl::event_revision
+        .inner_join(e_dsl::events.on(rev_dsl::parent_rowid.eq(e_dsl::id)))
+        .left_outer_join(u_dsl::users.on(rev_dsl::created_by.eq(u_dsl::id.nullable())))
+        .select((
+            (
+                e_dsl::id,
+                e_dsl::uid,
+                rev_dsl::title,
+                rev_dsl::description,
+                rev_dsl::start,
+                rev_dsl::end,
+                rev_dsl::lat,
+                rev_dsl::lng,
+                rev_dsl::street,
+                rev_dsl::zip,
+                rev_dsl::city,
+                rev_dsl::country,
+                rev_dsl::state,
+                rev_dsl::email,
+                rev_dsl::telephone,
+                rev_dsl::homepage,
+                rev_dsl::created_by,
+                rev_dsl::registration,
+                rev_dsl::organizer,
+                e_dsl::archived,
+                rev_dsl::image_url,
+                rev_dsl::image_link_url,
+                rev_dsl::rrule,
+                rev_dsl::exdates,
+                rev_dsl::rev,
+                u_dsl::email.nullable(),
+            ),
+            (
+                rev_dsl::rowid,
+                rev_dsl::created_at,
+                rev_dsl::context,
+                rev_dsl::comment,
+            ),
+        ))
+        .filter(e_dsl::uid.eq(id))
+        .order_by(rev_dsl::rev.desc())
+        .into_boxed();
+    if let Some(revision) = revision {
+        query = query.filter(rev_dsl::rev.eq(RevisionValue::from(revision) as i64));
+    }
+    let rows = query
+        .load::<(models::EventEntity, models::EventRevisionActivity)>(conn)
+        .map_err(from_diesel_err)?;
+    if rows.is_empty() {
+        return Err(repo::Error::NotFound);
+    }
+    let mut revisions = Vec::with_capacity(rows.len());
+    for (entity, activity) in rows {
+        let models::EventRevisionActivity {
+            rowid,
+            created_at,
+            context,
+            comment,
+        } = activity;
+        let tags = tag_dsl::event_revision_tag
+            .select(tag_dsl::tag)
+            .filter(tag_dsl::parent_rowid.eq(rowid))
+            .load::<String>(conn)
+            .map_err(from_diesel_err)?;
+        let translations = load_event_revision_translations(conn, rowid)?;
+        let event = util::event_from_event_entity(entity, tags, translations);
+        let activity = ActivityLog {
+            activity: Activity {
+                at: Timestamp::try_from_millis(created_at).unwrap(),
+                by: event.created_by.clone(),
+            },
+            context,
+            comment,
+        };
+        revisions.push((event, activity));
+    }
+    Ok(EventHistory {
+        id: id.into(),
+        revisions,
+    })
+}
+
+fn load_event_revision(conn: &mut PgConnection, id: &str, rev: Revision) -> Result<Event> {
+    let EventHistory { mut revisions, .. } = get_event_history(conn, id, Some(rev))?;
+    debug_assert_eq!(1, revisions.len());
+    let (event, _) = revisions.swap_remove(0);
+    Ok(event)
+}
+
+fn is_event_owned_by_any_organization(conn: &mut PgConnection, id: &str) -> Result<bool> {
+    use schema::{event_tags, events, organization_tag};
+    Ok(events::table
+        .select(events::id)
+        .filter(events::uid.eq(id))
+        .filter(
+            events::id.eq_any(event_tags::table.select(event_tags::event_id).filter(
+                event_tags::tag.eq_any(organization_tag::table.select(organization_tag::tag_label)),
+            )),
+        )
+        .first::<i64>(conn)
+        .optional()
+        .map_err(from_diesel_err)?
+        .is_some())
+}
@@ -0,0 +1,420 @@
+use super::*;
+
+impl EventClearanceRepo for DbReadWrite<'_> {
+    fn add_pending_clearance_for_events(
+        &self,
+        org_ids: &[Id],
+        pending_clearance: &PendingClearanceForEvent,
+    ) -> Result<usize> {
+        add_pending_clearance_for_events(&mut self.conn.borrow_mut(), org_ids, pending_clearance)
+    }
+    fn count_pending_clearances_for_events(&self, org_id: &Id) -> Result<u64> {
+        count_pending_clearances_for_events(&mut self.conn.borrow_mut(), org_id)
+    }
+    fn list_pending_clearances_for_events(
+        &self,
+        org_id: &Id,
+        pagination: &Pagination,
+    ) -> Result<Vec<PendingClearanceForEvent>> {
+        list_pending_clearances_for_events(&mut self.conn.borrow_mut(), org_id, pagination)
+    }
+    fn load_pending_clearances_for_events(
+        &self,
+        org_id: &Id,
+        event_ids: &[&str],
+    ) -> Result<Vec<PendingClearanceForEvent>> {
+        load_pending_clearances_for_events(&mut self.conn.borrow_mut(), org_id, event_ids)
+    }
+    fn update_pending_clearances_for_events(
+        &self,
+        org_id: &Id,
+        clearances: &[ClearanceForEvent],
+        activity: &ActivityLog,
+    ) -> Result<usize> {
+        update_pending_clearances_for_events(
+            &mut self.conn.borrow_mut(),
+            org_id,
+            clearances,
+            activity,
+        )
+    }
+    fn cleanup_pending_clearances_for_events(&self, org_id: &Id) -> Result<u64> {
+        cleanup_pending_clearances_for_events(&mut self.conn.borrow_mut(), org_id)
+    }
+    fn list_clearance_log_for_events(
+        &self,
+        org_id: &Id,
+        pagination: &Pagination,
+    ) -> Result<Vec<ClearanceLogForEvent>> {
+        list_clearance_log_for_events(&mut self.conn.borrow_mut(), org_id, pagination)
+    }
+    fn add_clearance_log_for_events(&self, org_id: &Id, log: &ClearanceLogForEvent) -> Result<()> {
+        add_clearance_log_for_events(&mut self.conn.borrow_mut(), org_id, log)
+    }
+}
+
+impl EventClearanceRepo for DbConnection<'_> {
+    fn add_pending_clearance_for_events(
+        &self,
+        org_ids: &[Id],
+        pending_clearance: &PendingClearanceForEvent,
+    ) -> Result<usize> {
+        add_pending_clearance_for_events(&mut self.conn.borrow_mut(), org_ids, pending_clearance)
+    }
+    fn count_pending_clearances_for_events(&self, org_id: &Id) -> Result<u64> {
+        count_pending_clearances_for_events(&mut self.conn.borrow_mut(), org_id)
+    }
+    fn list_pending_clearances_for_events(
+        &self,
+        org_id: &Id,
+        pagination: &Pagination,
+    ) -> Result<Vec<PendingClearanceForEvent>> {
+        list_pending_clearances_for_events(&mut self.conn.borrow_mut(), org_id, pagination)
+    }
+    fn load_pending_clearances_for_events(
+        &self,
+        org_id: &Id,
+        event_ids: &[&str],
+    ) -> Result<Vec<PendingClearanceForEvent>> {
+        load_pending_clearances_for_events(&mut self.conn.borrow_mut(), org_id, event_ids)
+    }
+    fn update_pending_clearances_for_events(
+        &self,
+        org_id: &Id,
+        clearances: &[ClearanceForEvent],
+        activity: &ActivityLog,
+    ) -> Result<usize> {
+        update_pending_clearances_for_events(
+            &mut self.conn.borrow_mut(),
+            org_id,
+            clearances,
+            activity,
+        )
+    }
+    fn cleanup_pending_clearances_for_events(&self, org_id: &Id) -> Result<u64> {
+        cleanup_pending_clearances_for_events(&mut self.conn.borrow_mut(), org_id)
+    }
+    fn list_clearance_log_for_events(
+        &self,
+        org_id: &Id,
+        pagination: &Pagination,
+    ) -> Result<Vec<ClearanceLogForEvent>> {
+        list_clearance_log_for_events(&mut self.conn.borrow_mut(), org_id, pagination)
+    }
+    fn add_clearance_log_for_events(&self, org_id: &Id, log: &ClearanceLogForEvent) -> Result<()> {
+        add_clearance_log_for_events(&mut self.conn.borrow_mut(), org_id, log)
+    }
+}
+
+impl EventClearanceRepo for DbReadOnly<'_> {
+    fn add_pending_clearance_for_events(
+        &self,
+        _org_ids: &[Id],
+        _pending_clearance: &PendingClearanceForEvent,
+    ) -> Result<usize> {
+        unreachable!();
+    }
+    fn count_pending_clearances_for_events(&self, org_id: &Id) -> Result<u64> {
+        count_pending_clearances_for_events(&mut self.conn.borrow_mut(), org_id)
+    }
+    fn list_pending_clearances_for_events(
+        &self,
+        org_id: &Id,
+        pagination: &Pagination,
+    ) -> Result<Vec<PendingClearanceForEvent>> {
+        list_pending_clearances_for_events(&mut self.conn.borrow_mut(), org_id, pagination)
+    }
+    fn load_pending_clearances_for_events(
+        &self,
+        org_id: &Id,
+        event_ids: &[&str],
+    ) -> Result<Vec<PendingClearanceForEvent>> {
+        load_pending_clearances_for_events(&mut self.conn.borrow_mut(), org_id, event_ids)
+    }
+    fn update_pending_clearances_for_events(
+        &self,
+        _org_id: &Id,
+        _clearances: &[ClearanceForEvent],
+        _activity: &ActivityLog,
+    ) -> Result<usize> {
+        unreachable!();
+    }
+    fn cleanup_pending_clearances_for_events(&self, _org_id: &Id) -> Result<u64> {
+        unreachable!();
+    }
+    fn list_clearance_log_for_events(
+        &self,
+        org_id: &Id,
+        pagination: &Pagination,
+    ) -> Result<Vec<ClearanceLogForEvent>> {
+        list_clearance_log_for_events(&mut self.conn.borrow_mut(), org_id, pagination)
+    }
+    fn add_clearance_log_for_events(
+        &self,
+        _org_id: &Id,
+        _log: &ClearanceLogForEvent,
+    ) -> Result<()> {
+        unreachable!();
+    }
+}
+
+fn add_pending_clearance_for_events(
+    conn: &mut PgConnection,
+    org_ids: &[Id],
+    pending_clearance: &PendingClearanceForEvent,
+) -> Result<usize> {
+    let PendingClearanceForEvent {
+        event_id,
+        created_at,
+        last_cleared_revision,
+    } = pending_clearance;
+    let event_rowid = resolve_event_id(conn, event_id.as_str())?;
+    let created_at = created_at.as_millis();
+    let last_cleared_revision = last_cleared_revision.map(|rev| RevisionValue::from(rev) as i64);
+    let mut insert_count = 0;
+    for org_id in org_ids {
+        let org_rowid = resolve_organization_rowid(conn, org_id)?;
+        let insertable = models::NewPendingClearanceForEvent {
+            org_rowid,
+            event_rowid,
+            created_at,
+            last_cleared_revision,
+        };
+        insert_count += diesel::insert_into(schema::organization_event_clearance::table)
+            .values(&insertable)
+            .on_conflict_do_nothing()
+            .execute(conn)
+            .map_err(from_diesel_err)?;
+    }
+    Ok(insert_count)
+}
+
+fn count_pending_clearances_for_events(conn: &mut PgConnection, org_id: &Id) -> Result<u64> {
+    use schema::{organization::dsl as org_dsl, organization_event_clearance::dsl};
+    Ok(schema::organization_event_clearance::table
+        .filter(
+            dsl::org_rowid.eq_any(
+                schema::organization::table
+                    .select(org_dsl::rowid)
+                    .filter(org_dsl::id.eq(org_id.as_str())),
+            ),
+        )
+        .count()
+        .get_result::<i64>(conn)
+        .map_err(from_diesel_err)? as u64)
+}
+
+fn list_pending_clearances_for_events(
+    conn: &mut PgConnection,
+    org_id: &Id,
+    pagination: &Pagination,
+) -> Result<Vec<PendingClearanceForEvent>> {
+    use schema::{
+        events::dsl as event_dsl, organization::dsl as org_dsl, organization_event_clearance::dsl,
+    };
+    let mut query = schema::organization_event_clearance::table
+        .inner_join(schema::events::table)
+        .select((event_dsl::uid, dsl::created_at, dsl::last_cleared_revision))
+        .filter(
+            dsl::org_rowid.eq_any(
+                schema::organization::table
+                    .select(org_dsl::rowid)
+                    .filter(org_dsl::id.eq(org_id.as_str())),
+            ),
+        )
+        .order_by(dsl::created_at)
+        .into_boxed();
+
+    // Pagination
+    let offset = pagination.offset.unwrap_or(0) as i64;
+    if let Some(limit) = pagination.limit {
+        query = query.limit(limit as i64);
+    }
+    if offset > 0 {
+        query = query.offset(offset);
+    }
+
+    Ok(query
+        .load::<models::PendingClearanceForEvent>(conn)
+        .map_err(from_diesel_err)?
+        .into_iter()
+        .map(Into::into)
+        .collect())
+}
+
+fn load_pending_clearances_for_events(
+    conn: &mut PgConnection,
+    org_id: &Id,
+    event_ids: &[&str],
+) -> Result<Vec<PendingClearanceForEvent>> {
+    use schema::{
+        events::dsl as event_dsl, organization::dsl as org_dsl, organization_event_clearance::dsl,
+    };
+    Ok(schema::organization_event_clearance::table
+        .inner_join(schema::events::table)
+        .select((event_dsl::uid, dsl::created_at, dsl::last_cleared_revision))
+        .filter(
+            dsl::org_rowid.eq_any(
+                schema::organization::table
+                    .select(org_dsl::rowid)
+                    .filter(org_dsl::id.eq(org_id.as_str())),
+            ),
+        )
+        .filter(event_dsl::uid.eq_any(event_ids))
+        .load::<models::PendingClearanceForEvent>(conn)
+        .map_err(from_diesel_err)?
+        .into_iter()
+        .map(Into::into)
+        .collect())
+}
+
+fn update_pending_clearances_for_events(
+    conn: &mut PgConnection,
+    org_id: &Id,
+    clearances: &[ClearanceForEvent],
+    activity: &ActivityLog,
+) -> Result<usize> {
+    let org_rowid = resolve_organization_rowid(conn, org_id)?;
+    let ActivityLog {
+        activity: Activity { at, by },
+        context,
+        comment,
+    } = activity;
+    let created_at = Timestamp::now().as_millis();
+    let mut total_rows_affected = 0;
+    for clearance in clearances {
+        let ClearanceForEvent {
+            event_id,
+            cleared_revision,
+        } = clearance;
+        let (event_rowid, current_revision) =
+            resolve_event_id_with_current_revision(conn, event_id.as_str())?;
+        let cleared_revision = if let Some(cleared_revision) = cleared_revision {
+            if *cleared_revision > current_revision {
+                return Err(repo::Error::InvalidVersion);
+            }
+            *cleared_revision
+        } else {
+            current_revision
+        };
+        use schema::organization_event_clearance::dsl;
+        let last_cleared_revision = Some(RevisionValue::from(cleared_revision) as i64);
+        let updatable = models::NewPendingClearanceForEvent {
+            org_rowid,
+            event_rowid,
+            created_at,
+            last_cleared_revision,
+        };
+        let rows_affected = diesel::update(schema::organization_event_clearance::table)
+            .set(&updatable)
+            .filter(dsl::org_rowid.eq(org_rowid))
+            .filter(dsl::event_rowid.eq(event_rowid))
+            .execute(conn)
+            .map_err(from_diesel_err)?;
+        debug_assert!(rows_affected <= 1);
+        if rows_affected > 0 {
+            let insertable = models::NewEventClearanceLog {
+                org_rowid,
+                event_id: event_id.as_str(),
+                cleared_revision: RevisionValue::from(cleared_revision) as i64,
+                created_at: at.as_millis(),
+                created_by: by.as_ref().map(EmailAddress::as_str),
+                context: context.as_deref(),
+                comment: comment.as_deref(),
+            };
+            diesel::insert_into(schema::organization_event_clearance_log::table)
+                .values(&insertable)
+                .execute(conn)
+                .map_err(from_diesel_err)?;
+        }
+        total_rows_affected += rows_affected;
+    }
+    Ok(total_rows_affected)
+}
+
+fn cleanup_pending_clearances_for_events(conn: &mut PgConnection, org_id: &Id) -> Result<u64> {
+    let org_rowid = resolve_organization_rowid(conn, org_id)?;
+    use schema::{events::dsl as event_dsl, organization_event_clearance::dsl};
+    let delete_rowids = schema::organization_event_clearance::table
+        .inner_join(schema::events::table)
+        .select(dsl::rowid)
+        .filter(dsl::org_rowid.eq(org_rowid))
+        .filter(dsl::last_cleared_revision.eq(event_dsl::current_rev.nullable()))
+        .load::<i64>(conn)
+        .map_err(from_diesel_err)?;
+    let delete_count = diesel::delete(
+        schema::organization_event_clearance::table.filter(dsl::rowid.eq_any(delete_rowids)),
+    )
+    .execute(conn)
+    .map_err(from_diesel_err)?;
+    Ok(delete_count as u64)
+}
+
+fn list_clearance_log_for_events(
+    conn: &mut PgConnection,
+    org_id: &Id,
+    pagination: &Pagination,
+) -> Result<Vec<ClearanceLogForEvent>> {
+    let org_rowid = resolve_organization_rowid(conn, org_id)?;
+    use schema::organization_event_clearance_log::dsl;
+    let mut query = schema::organization_event_clearance_log::table
+        .select((
+            dsl::event_id,
+            dsl::cleared_revision,
+            dsl::created_at,
+            dsl::created_by,
+            dsl::context,
+            dsl::comment,
+        ))
+        .filter(dsl::org_rowid.eq(org_rowid))
+        .order_by((dsl::created_at.desc(), dsl::rowid.desc()))
+        .into_boxed();
+
+    // Pagination
+    let offset = pagination.offset.unwrap_or(0) as i64;
+    if let Some(limit) = pagination.limit {
+        query = query.limit(limit as i64);
+    }
+    if offset > 0 {
+        query = query.offset(offset);
+    }
+
+    Ok(query
+        .load::<models::ClearanceLog>(conn)
+        .map_err(from_diesel_err)?
+        .into_iter()
+        .map(Into::into)
+        .collect())
+}
+
+fn add_clearance_log_for_events(
+    conn: &mut PgConnection,
+    org_id: &Id,
+    log: &ClearanceLogForEvent,
+) -> Result<()> {
+    let ClearanceLogForEvent {
+        event_id,
+        cleared_revision,
+        activity:
+            ActivityLog {
+                activity: Activity { at, by },
+                context,
+                comment,
+            },
+    } = log;
+    let org_rowid = resolve_organization_rowid(conn, org_id)?;
+    let insertable = models::NewEventClearanceLog {
+        org_rowid,
+        event_id: event_id.as_str(),
+        cleared_revision: RevisionValue::from(*cleared_revision) as i64,
+        created_at: at.as_millis(),
+        created_by: by.as_ref().map(EmailAddress::as_str),
+        context: context.as_deref(),
+        comment: comment.as_deref(),
+    };
+    diesel::insert_into(schema::organization_event_clearance_log::table)
+        .values(&insertable)
+        .execute(conn)
+        .map_err(from_diesel_err)?;
+    Ok(())
+}
@@ -0,0 +1,314 @@
+use super::*;
+
+impl OrganizationRepo for DbReadWrite<'_> {
+    fn create_org(&mut self, org: Organization) -> Result<()> {
+        create_org(&mut self.conn.borrow_mut(), org)
+    }
+    fn update_org(&mut self, org: Organization) -> Result<()> {
+        update_org(&mut self.conn.borrow_mut(), org)
+    }
+    fn delete_org(&mut self, id: &Id) -> Result<()> {
+        delete_org(&mut self.conn.borrow_mut(), id)
+    }
+    fn get_org_by_id(&self, id: &Id) -> Result<Organization> {
+        get_org_by_id(&mut self.conn.borrow_mut(), id)
+    }
+    fn all_orgs(&self) -> Result<Vec<Organization>> {
+        all_orgs(&mut self.conn.borrow_mut())
+    }
+    fn get_org_by_api_token(&self, token: &str) -> Result<Organization> {
+        get_org_by_api_token(&mut self.conn.borrow_mut(), token)
+    }
+    fn map_tag_to_clearance_org_id(&self, tag: &str) -> Result<Option<Id>> {
+        map_tag_to_clearance_org_id(&mut self.conn.borrow_mut(), tag)
+    }
+    fn get_moderated_tags_by_org(
+        &self,
+        excluded_org_id: Option<&Id>,
+    ) -> Result<Vec<(Id, ModeratedTag)>> {
+        get_moderated_tags_by_org(&mut self.conn.borrow_mut(), excluded_org_id)
+    }
+}
+
+impl OrganizationRepo for DbConnection<'_> {
+    fn create_org(&mut self, org: Organization) -> Result<()> {
+        create_org(&mut self.conn.borrow_mut(), org)
+    }
+    fn update_org(&mut self, org: Organization) -> Result<()> {
+        update_org(&mut self.conn.borrow_mut(), org)
+    }
+    fn delete_org(&mut self, id: &Id) -> Result<()> {
+        delete_org(&mut self.conn.borrow_mut(), id)
+    }
+    fn get_org_by_id(&self, id: &Id) -> Result<Organization> {
+        get_org_by_id(&mut self.conn.borrow_mut(), id)
+    }
+    fn all_orgs(&self) -> Result<Vec<Organization>> {
+        all_orgs(&mut self.conn.borrow_mut())
+    }
+    fn get_org_by_api_token(&self, token: &str) -> Result<Organization> {
+        get_org_by_api_token(&mut self.conn.borrow_mut(), token)
+    }
+    fn map_tag_to_clearance_org_id(&self, tag: &str) -> Result<Option<Id>> {
+        map_tag_to_clearance_org_id(&mut self.conn.borrow_mut(), tag)
+    }
+    fn get_moderated_tags_by_org(
+        &self,
+        excluded_org_id: Option<&Id>,
+    ) -> Result<Vec<(Id, ModeratedTag)>> {
+        get_moderated_tags_by_org(&mut self.conn.borrow_mut(), excluded_org_id)
+    }
+}
+
+impl OrganizationRepo for DbReadOnly<'_> {
+    fn create_org(&mut self, _org: Organization) -> Result<()> {
+        unreachable!();
+    }
+    fn update_org(&mut self, _org: Organization) -> Result<()> {
+        unreachable!();
+    }
+    fn delete_org(&mut self, _id: &Id) -> Result<()> {
+        unreachable!();
+    }
+    fn get_org_by_id(&self, id: &Id) -> Result<Organization> {
+        get_org_by_id(&mut self.conn.borrow_mut(), id)
+    }
+    fn all_orgs(&self) -> Result<Vec<Organization>> {
+        all_orgs(&mut self.conn.borrow_mut())
+    }
+    fn get_org_by_api_token(&self, token: &str) -> Result<Organization> {
+        get_org_by_api_token(&mut self.conn.borrow_mut(), token)
+    }
+    fn map_tag_to_clearance_org_id(&self, tag: &str) -> Result<Option<Id>> {
+        map_tag_to_clearance_org_id(&mut self.conn.borrow_mut(), tag)
+    }
+    fn get_moderated_tags_by_org(
+        &self,
+        excluded_org_id: Option<&Id>,
+    ) -> Result<Vec<(Id, ModeratedTag)>> {
+        get_moderated_tags_by_org(&mut self.conn.borrow_mut(), excluded_org_id)
+    }
+}
+
+fn create_org(conn: &mut PgConnection, mut o: Organization) -> Result<()> {
+    let org_id = o.id.clone();
+    let moderated_tags = std::mem::take(&mut o.moderated_tags);
+    let new_org = models::NewOrganization::from(o);
+    conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        diesel::insert_into(schema::organization::table)
+            .values(&new_org)
+            .execute(conn)?;
+        let org_rowid = resolve_organization_rowid(conn, &org_id).map_err(|err| {
+            log::warn!(
+                "Failed to resolve id of newly created organization '{}': {}",
+                org_id,
+                err
+            );
+            diesel::result::Error::RollbackTransaction
+        })?;
+        insert_org_tags(conn, org_rowid, &moderated_tags)
+    })
+    .map_err(from_diesel_err)?;
+    Ok(())
+}
+
+fn insert_org_tags(
+    conn: &mut PgConnection,
+    org_rowid: i64,
+    moderated_tags: &[ModeratedTag],
+) -> diesel::QueryResult<()> {
+    for ModeratedTag {
+        label,
+        allow_add,
+        allow_remove,
+        require_clearance,
+    } in moderated_tags
+    {
+        let org_tag = models::NewOrganizationTag {
+            org_rowid,
+            tag_label: label,
+            tag_allow_add: i16::from(*allow_add),
+            tag_allow_remove: i16::from(*allow_remove),
+            require_clearance: i16::from(*require_clearance),
+        };
+        diesel::insert_into(schema::organization_tag::table)
+            .values(&org_tag)
+            .execute(conn)?;
+    }
+    Ok(())
+}
+
+fn update_org(conn: &mut PgConnection, org: Organization) -> Result<()> {
+    use schema::{organization::dsl, organization_tag::dsl as tag_dsl};
+    let Organization {
+        id,
+        name,
+        api_token: _,
+        moderated_tags,
+    } = org;
+    let org_rowid = resolve_organization_rowid(conn, &id)?;
+    conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        diesel::update(dsl::organization.filter(dsl::rowid.eq(org_rowid)))
+            .set(dsl::name.eq(&name))
+            .execute(conn)?;
+        diesel::delete(tag_dsl::organization_tag.filter(tag_dsl::org_rowid.eq(org_rowid)))
+            .execute(conn)?;
+        insert_org_tags(conn, org_rowid, &moderated_tags)
+    })
+    .map_err(from_diesel_err)?;
+    Ok(())
+}
+
+fn delete_org(conn: &mut PgConnection, id: &Id) -> Result<()> {
+    use schema::{
+        organization::dsl, organization_api_token::dsl as api_token_dsl,
+        organization_event_clearance::dsl as event_clearance_dsl,
+        organization_event_clearance_log::dsl as event_clearance_log_dsl,
+        organization_member::dsl as member_dsl,
+        organization_place_clearance::dsl as place_clearance_dsl,
+        organization_place_clearance_log::dsl as place_clearance_log_dsl,
+        organization_tag::dsl as tag_dsl, organization_webhook::dsl as webhook_dsl,
+        webhook_delivery::dsl as delivery_dsl,
+    };
+    let org_rowid = resolve_organization_rowid(conn, id)?;
+    conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        let webhook_rowids = webhook_dsl::organization_webhook
+            .select(webhook_dsl::rowid)
+            .filter(webhook_dsl::org_rowid.eq(org_rowid));
+        diesel::delete(
+            delivery_dsl::webhook_delivery
+                .filter(delivery_dsl::webhook_rowid.eq_any(webhook_rowids)),
+        )
+        .execute(conn)?;
+        diesel::delete(
+            webhook_dsl::organization_webhook.filter(webhook_dsl::org_rowid.eq(org_rowid)),
+        )
+        .execute(conn)?;
+        diesel::delete(
+            api_token_dsl::organization_api_token.filter(api_token_dsl::org_rowid.eq(org_rowid)),
+        )
+        .execute(conn)?;
+        diesel::delete(
+            place_clearance_dsl::organization_place_clearance
+                .filter(place_clearance_dsl::org_rowid.eq(org_rowid)),
+        )
+        .execute(conn)?;
+        diesel::delete(
+            event_clearance_dsl::organization_event_clearance
+                .filter(event_clearance_dsl::org_rowid.eq(org_rowid)),
+        )
+        .execute(conn)?;
+        diesel::delete(
+            place_clearance_log_dsl::organization_place_clearance_log
+                .filter(place_clearance_log_dsl::org_rowid.eq(org_rowid)),
+        )
+        .execute(conn)?;
+        diesel::delete(
+            event_clearance_log_dsl::organization_event_clearance_log
+                .filter(event_clearance_log_dsl::org_rowid.eq(org_rowid)),
+        )
+        .execute(conn)?;
+        diesel::delete(member_dsl::organization_member.filter(member_dsl::org_rowid.eq(org_rowid)))
+            .execute(conn)?;
+        diesel::delete(tag_dsl::organization_tag.filter(tag_dsl::org_rowid.eq(org_rowid)))
+            .execute(conn)?;
+        diesel::delete(dsl::organization.filter(dsl::rowid.eq(org_rowid))).execute(conn)?;
+        Ok(())
+    })
+    .map_err(from_diesel_err)?;
+    Ok(())
+}
+
+fn get_org_by_id(conn: &mut PgConnection, id: &Id) -> Result<Organization> {
+    use schema::organization::dsl;
+    let org = dsl::organization
+        .filter(dsl::id.eq(id.as_str()))
+        .first(conn)
+        .map_err(from_diesel_err)?;
+    load_org(conn, org)
+}
+
+fn all_orgs(conn: &mut PgConnection) -> Result<Vec<Organization>> {
+    use schema::organization::dsl;
+    let orgs = dsl::organization
+        .order_by(dsl::id)
+        .load::<models::Organization>(conn)
+        .map_err(from_diesel_err)?;
+    orgs.into_iter().map(|org| load_org(conn, org)).collect()
+}
+
+fn get_org_by_api_token(conn: &mut PgConnection, token: &str) -> Result<Organization> {
+    use schema::organization::dsl;
+    let org = dsl::organization
+        .filter(dsl::api_token.eq(token))
+        .first(conn)
+        .map_err(from_diesel_err)?;
+    load_org(conn, org)
+}
+
+fn load_org(conn: &mut PgConnection, org: models::Organization) -> Result<Organization> {
+    use schema::organization_tag::dsl as org_tag_dsl;
+
+    let models::Organization {
+        rowid,
+        id,
+        name,
+        api_token,
+    } = org;
+
+    let moderated_tags = org_tag_dsl::organization_tag
+        .filter(org_tag_dsl::org_rowid.eq(rowid))
+        .load::<models::OrganizationTag>(conn)
+        .map_err(from_diesel_err)?
+        .into_iter()
+        .map(Into::into)
+        .collect();
+
+    Ok(Organization {
+        id: id.into(),
+        name,
+        api_token,
+        moderated_tags,
+    })
+}
+
+fn map_tag_to_clearance_org_id(conn: &mut PgConnection, tag: &str) -> Result<Option<Id>> {
+    use schema::{organization::dsl, organization_tag::dsl as tag_dsl};
+    Ok(schema::organization::table
+        .inner_join(schema::organization_tag::table)
+        .select(dsl::id)
+        .filter(tag_dsl::tag_label.eq(tag))
+        .filter(tag_dsl::require_clearance.ne(0))
+        .first::<String>(conn)
+        .optional()
+        .map_err(from_diesel_err)?
+        .map(Into::into))
+}
+
+fn get_moderated_tags_by_org(
+    conn: &mut PgConnection,
+    excluded_org_id: Option<&Id>,
+) -> Result<Vec<(Id, ModeratedTag)>> {
+    use schema::{organization::dsl as org_dsl, organization_tag::dsl as org_tag_dsl};
+    let query = org_tag_dsl::organization_tag
+        .inner_join(org_dsl::organization)
+        .select((
+            org_dsl::id,
+            org_tag_dsl::tag_label,
+            org_tag_dsl::tag_allow_add,
+            org_tag_dsl::tag_allow_remove,
+            org_tag_dsl::require_clearance,
+        ))
+        .order_by(org_dsl::id);
+    let moderated_tags = if let Some(excluded_org_id) = excluded_org_id {
+        query
+            .filter(org_dsl::id.ne(excluded_org_id.as_str()))
+            .load::<models::OrganizationTagWithId>(conn)
+            .map_err(from_diesel_err)?
+    } else {
+        query
+            .load::<models::OrganizationTagWithId>(conn)
+            .map_err(from_diesel_err)?
+    };
+    Ok(moderated_tags.into_iter().map(Into::into).collect())
+}
@@ -0,0 +1,152 @@
+use super::*;
+
+impl OrganizationMemberRepo for DbReadWrite<'_> {
+    fn add_or_update_org_member(&self, member: &OrganizationMember) -> Result<()> {
+        add_or_update_org_member(&mut self.conn.borrow_mut(), member)
+    }
+    fn remove_org_member(&self, org_id: &Id, email: &EmailAddress) -> Result<()> {
+        remove_org_member(&mut self.conn.borrow_mut(), org_id, email)
+    }
+    fn list_org_members(&self, org_id: &Id) -> Result<Vec<OrganizationMember>> {
+        list_org_members(&mut self.conn.borrow_mut(), org_id)
+    }
+    fn list_org_memberships_of_user(
+        &self,
+        email: &EmailAddress,
+    ) -> Result<Vec<OrganizationMember>> {
+        list_org_memberships_of_user(&mut self.conn.borrow_mut(), email)
+    }
+}
+
+impl OrganizationMemberRepo for DbConnection<'_> {
+    fn add_or_update_org_member(&self, member: &OrganizationMember) -> Result<()> {
+        add_or_update_org_member(&mut self.conn.borrow_mut(), member)
+    }
+    fn remove_org_member(&self, org_id: &Id, email: &EmailAddress) -> Result<()> {
+        remove_org_member(&mut self.conn.borrow_mut(), org_id, email)
+    }
+    fn list_org_members(&self, org_id: &Id) -> Result<Vec<OrganizationMember>> {
+        list_org_members(&mut self.conn.borrow_mut(), org_id)
+    }
+    fn list_org_memberships_of_user(
+        &self,
+        email: &EmailAddress,
+    ) -> Result<Vec<OrganizationMember>> {
+        list_org_memberships_of_user(&mut self.conn.borrow_mut(), email)
+    }
+}
+
+impl OrganizationMemberRepo for DbReadOnly<'_> {
+    fn add_or_update_org_member(&self, _member: &OrganizationMember) -> Result<()> {
+        unreachable!();
+    }
+    fn remove_org_member(&self, _org_id: &Id, _email: &EmailAddress) -> Result<()> {
+        unreachable!();
+    }
+    fn list_org_members(&self, org_id: &Id) -> Result<Vec<OrganizationMember>> {
+        list_org_members(&mut self.conn.borrow_mut(), org_id)
+    }
+    fn list_org_memberships_of_user(
+        &self,
+        email: &EmailAddress,
+    ) -> Result<Vec<OrganizationMember>> {
+        list_org_memberships_of_user(&mut self.conn.borrow_mut(), email)
+    }
+}
+
+fn add_or_update_org_member(conn: &mut PgConnection, member: &OrganizationMember) -> Result<()> {
+    let OrganizationMember {
+        org_id,
+        email,
+        role,
+    } = member;
+    let org_rowid = resolve_organization_rowid(conn, org_id)?;
+    let user_id = resolve_user_created_by_email(conn, email)?;
+    use schema::organization_member::dsl;
+    let rows_affected = diesel::update(
+        schema::organization_member::table
+            .filter(dsl::org_rowid.eq(org_rowid))
+            .filter(dsl::user_id.eq(user_id)),
+    )
+    .set(dsl::role.eq(role.as_str()))
+    .execute(conn)
+    .map_err(from_diesel_err)?;
+    if rows_affected > 0 {
+        debug_assert_eq!(1, rows_affected);
+        return Ok(());
+    }
+    let insertable = models::NewOrganizationMember {
+        org_rowid,
+        user_id,
+        role: role.as_str(),
+    };
+    diesel::insert_into(schema::organization_member::table)
+        .values(&insertable)
+        .execute(conn)
+        .map_err(from_diesel_err)?;
+    Ok(())
+}
+
+fn remove_org_member(conn: &mut PgConnection, org_id: &Id, email: &EmailAddress) -> Result<()> {
+    let org_rowid = resolve_organization_rowid(conn, org_id)?;
+    let user_id = resolve_user_created_by_email(conn, email)?;
+    use schema::organization_member::dsl;
+    let rows_affected = diesel::delete(
+        schema::organization_member::table
+            .filter(dsl::org_rowid.eq(org_rowid))
+            .filter(dsl::user_id.eq(user_id)),
+    )
+    .execute(conn)
+    .map_err(from_diesel_err)?;
+    if rows_affected < 1 {
+        return Err(repo::Error::NotFound);
+    }
+    debug_assert_eq!(1, rows_affected);
+    Ok(())
+}
+
+fn list_org_members(conn: &mut PgConnection, org_id: &Id) -> Result<Vec<OrganizationMember>> {
+    use schema::{organization::dsl as org_dsl, organization_member::dsl, users::dsl as user_dsl};
+    let members = schema::organization_member::table
+        .inner_join(schema::organization::table)
+        .inner_join(schema::users::table)
+        .select((org_dsl::id, user_dsl::email, dsl::role))
+        .filter(org_dsl::id.eq(org_id.as_str()))
+        .order_by(user_dsl::email)
+        .load::<models::OrganizationMember>(conn)
+        .map_err(from_diesel_err)?;
+    Ok(members.into_iter().filter_map(load_org_member).collect())
+}
+
+fn list_org_memberships_of_user(
+    conn: &mut PgConnection,
+    email: &EmailAddress,
+) -> Result<Vec<OrganizationMember>> {
+    use schema::{organization::dsl as org_dsl, organization_member::dsl, users::dsl as user_dsl};
+    let members = schema::organization_member::table
+        .inner_join(schema::organization::table)
+        .inner_join(schema::users::table)
+        .select((org_dsl::id, user_dsl::email, dsl::role))
+        .filter(lower(user_dsl::email).eq(lower(email.as_str())))
+        .order_by(org_dsl::name)
+        .load::<models::OrganizationMember>(conn)
+        .map_err(from_diesel_err)?;
+    Ok(members.into_iter().filter_map(load_org_member).collect())
+}
+
+fn load_org_member(member: models::OrganizationMember) -> Option<OrganizationMember> {
+    let models::OrganizationMember {
+        org_id,
+        email,
+        role,
+    } = member;
+    let role = role
+        .parse()
+        .map_err(|_| log::warn!("Ignoring unknown role '{role}' of member {email}"))
+        .ok()?;
+    Some(OrganizationMember {
+        org_id: org_id.into(),
+        email: EmailAddress::new_unchecked(email),
+        role,
+    })
+}
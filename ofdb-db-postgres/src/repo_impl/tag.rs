@@ -0,0 +1,66 @@
+use super::*;
+
+impl TagRepo for DbReadOnly<'_> {
+    fn create_tag_if_it_does_not_exist(&self, _tag: &Tag) -> Result<()> {
+        unreachable!();
+    }
+    fn all_tags(&self) -> Result<Vec<Tag>> {
+        all_tags(&mut self.conn.borrow_mut())
+    }
+    fn count_tags(&self) -> Result<usize> {
+        count_tags(&mut self.conn.borrow_mut())
+    }
+}
+
+impl TagRepo for DbReadWrite<'_> {
+    fn create_tag_if_it_does_not_exist(&self, tag: &Tag) -> Result<()> {
+        create_tag_if_it_does_not_exist(&mut self.conn.borrow_mut(), tag)
+    }
+    fn all_tags(&self) -> Result<Vec<Tag>> {
+        all_tags(&mut self.conn.borrow_mut())
+    }
+    fn count_tags(&self) -> Result<usize> {
+        count_tags(&mut self.conn.borrow_mut())
+    }
+}
+
+impl TagRepo for DbConnection<'_> {
+    fn create_tag_if_it_does_not_exist(&self, tag: &Tag) -> Result<()> {
+        create_tag_if_it_does_not_exist(&mut self.conn.borrow_mut(), tag)
+    }
+    fn all_tags(&self) -> Result<Vec<Tag>> {
+        all_tags(&mut self.conn.borrow_mut())
+    }
+    fn count_tags(&self) -> Result<usize> {
+        count_tags(&mut self.conn.borrow_mut())
+    }
+}
+
+fn create_tag_if_it_does_not_exist(conn: &mut PgConnection, t: &Tag) -> Result<()> {
+    // A failed statement would abort the enclosing transaction in
+    // PostgreSQL, i.e. unique violations must not occur at all.
+    diesel::insert_into(schema::tags::table)
+        .values(&models::Tag::from(t.clone()))
+        .on_conflict_do_nothing()
+        .execute(conn)
+        .map_err(from_diesel_err)?;
+    Ok(())
+}
+
+fn all_tags(conn: &mut PgConnection) -> Result<Vec<Tag>> {
+    use schema::tags::dsl::*;
+    Ok(tags
+        .load::<models::Tag>(conn)
+        .map_err(from_diesel_err)?
+        .into_iter()
+        .map(Tag::from)
+        .collect())
+}
+
+fn count_tags(conn: &mut PgConnection) -> Result<usize> {
+    use schema::tags::dsl::*;
+    Ok(tags
+        .select(diesel::dsl::count(id))
+        .first::<i64>(conn)
+        .map_err(from_diesel_err)? as usize)
+}
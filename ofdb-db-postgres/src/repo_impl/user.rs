@@ -0,0 +1,153 @@
+use super::*;
+
+impl UserRepo for DbReadOnly<'_> {
+    fn create_user(&self, _user: &User) -> Result<()> {
+        unreachable!();
+    }
+    fn update_user(&self, _user: &User) -> Result<()> {
+        unreachable!();
+    }
+    fn delete_user_by_email(&self, _email: &EmailAddress) -> Result<()> {
+        unreachable!();
+    }
+
+    fn all_users(&self) -> Result<Vec<User>> {
+        all_users(&mut self.conn.borrow_mut())
+    }
+    fn count_users(&self) -> Result<usize> {
+        count_users(&mut self.conn.borrow_mut())
+    }
+
+    fn get_user_by_email(&self, email: &EmailAddress) -> Result<User> {
+        get_user_by_email(&mut self.conn.borrow_mut(), email)
+    }
+    fn try_get_user_by_email(&self, email: &EmailAddress) -> Result<Option<User>> {
+        try_get_user_by_email(&mut self.conn.borrow_mut(), email)
+    }
+}
+
+impl UserRepo for DbReadWrite<'_> {
+    fn create_user(&self, user: &User) -> Result<()> {
+        create_user(&mut self.conn.borrow_mut(), user)
+    }
+    fn update_user(&self, user: &User) -> Result<()> {
+        update_user(&mut self.conn.borrow_mut(), user)
+    }
+    fn delete_user_by_email(&self, email: &EmailAddress) -> Result<()> {
+        delete_user_by_email(&mut self.conn.borrow_mut(), email)
+    }
+
+    fn all_users(&self) -> Result<Vec<User>> {
+        all_users(&mut self.conn.borrow_mut())
+    }
+    fn count_users(&self) -> Result<usize> {
+        count_users(&mut self.conn.borrow_mut())
+    }
+
+    fn get_user_by_email(&self, email: &EmailAddress) -> Result<User> {
+        get_user_by_email(&mut self.conn.borrow_mut(), email)
+    }
+    fn try_get_user_by_email(&self, email: &EmailAddress) -> Result<Option<User>> {
+        try_get_user_by_email(&mut self.conn.borrow_mut(), email)
+    }
+}
+
+impl UserRepo for DbConnection<'_> {
+    fn create_user(&self, user: &User) -> Result<()> {
+        create_user(&mut self.conn.borrow_mut(), user)
+    }
+    fn update_user(&self, user: &User) -> Result<()> {
+        update_user(&mut self.conn.borrow_mut(), user)
+    }
+    fn delete_user_by_email(&self, email: &EmailAddress) -> Result<()> {
+        delete_user_by_email(&mut self.conn.borrow_mut(), email)
+    }
+
+    fn all_users(&self) -> Result<Vec<User>> {
+        all_users(&mut self.conn.borrow_mut())
+    }
+    fn count_users(&self) -> Result<usize> {
+        count_users(&mut self.conn.borrow_mut())
+    }
+
+    fn get_user_by_email(&self, email: &EmailAddress) -> Result<User> {
+        get_user_by_email(&mut self.conn.borrow_mut(), email)
+    }
+    fn try_get_user_by_email(&self, email: &EmailAddress) -> Result<Option<User>> {
+        try_get_user_by_email(&mut self.conn.borrow_mut(), email)
+    }
+}
+
+fn create_user(conn: &mut PgConnection, u: &User) -> Result<()> {
+    let new_user = models::NewUser::from(u);
+    diesel::insert_into(schema::users::table)
+        .values(&new_user)
+        .execute(conn)
+        .map_err(from_diesel_err)?;
+    Ok(())
+}
+
+fn update_user(conn: &mut PgConnection, u: &User) -> Result<()> {
+    use schema::users::dsl;
+    let new_user = models::NewUser::from(u);
+    diesel::update(dsl::users.filter(lower(dsl::email).eq(lower(new_user.email))))
+        .set(&new_user)
+        .execute(conn)
+        .map_err(from_diesel_err)?;
+    Ok(())
+}
+
+fn delete_user_by_email(conn: &mut PgConnection, email: &EmailAddress) -> Result<()> {
+    use schema::{organization_member::dsl as member_dsl, users::dsl};
+    conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        let user_ids = dsl::users
+            .select(dsl::id)
+            .filter(lower(dsl::email).eq(lower(email.as_str())));
+        diesel::delete(
+            member_dsl::organization_member.filter(member_dsl::user_id.eq_any(user_ids)),
+        )
+        .execute(conn)?;
+        diesel::delete(dsl::users.filter(lower(dsl::email).eq(lower(email.as_str()))))
+            .execute(conn)?;
+        Ok(())
+    })
+    .map_err(from_diesel_err)?;
+    Ok(())
+}
+
+fn get_user_by_email(conn: &mut PgConnection, email: &EmailAddress) -> Result<User> {
+    use schema::users::dsl;
+    Ok(dsl::users
+        .filter(lower(dsl::email).eq(lower(email.as_str())))
+        .first::<models::UserEntity>(conn)
+        .map_err(from_diesel_err)?
+        .into())
+}
+
+fn try_get_user_by_email(conn: &mut PgConnection, email: &EmailAddress) -> Result<Option<User>> {
+    use schema::users::dsl;
+    Ok(dsl::users
+        .filter(lower(dsl::email).eq(lower(email.as_str())))
+        .first::<models::UserEntity>(conn)
+        .optional()
+        .map_err(from_diesel_err)?
+        .map(Into::into))
+}
+
+fn all_users(conn: &mut PgConnection) -> Result<Vec<User>> {
+    use schema::users::dsl;
+    Ok(dsl::users
+        .load::<models::UserEntity>(conn)
+        .map_err(from_diesel_err)?
+        .into_iter()
+        .map(Into::into)
+        .collect())
+}
+
+fn count_users(conn: &mut PgConnection) -> Result<usize> {
+    use schema::users::dsl;
+    Ok(dsl::users
+        .select(diesel::dsl::count(dsl::id))
+        .first::<i64>(conn)
+        .map_err(from_diesel_err)? as usize)
+}
@@ -0,0 +1,428 @@
+use num_traits::{FromPrimitive as _, ToPrimitive as _};
+
+use super::*;
+
+impl WebhookRepo for DbReadWrite<'_> {
+    fn create_webhook(&self, webhook: &Webhook) -> Result<()> {
+        create_webhook(&mut self.conn.borrow_mut(), webhook)
+    }
+    fn get_webhook(&self, id: &Id) -> Result<Webhook> {
+        get_webhook(&mut self.conn.borrow_mut(), id)
+    }
+    fn list_webhooks_of_org(&self, org_id: &Id) -> Result<Vec<Webhook>> {
+        list_webhooks_of_org(&mut self.conn.borrow_mut(), org_id)
+    }
+    fn delete_webhook(&self, org_id: &Id, id: &Id) -> Result<()> {
+        delete_webhook(&mut self.conn.borrow_mut(), org_id, id)
+    }
+    fn create_webhook_delivery(&self, delivery: &WebhookDelivery) -> Result<()> {
+        create_webhook_delivery(&mut self.conn.borrow_mut(), delivery)
+    }
+    fn update_webhook_delivery(&self, delivery: &WebhookDelivery) -> Result<()> {
+        update_webhook_delivery(&mut self.conn.borrow_mut(), delivery)
+    }
+    fn claim_webhook_delivery(
+        &self,
+        delivery: &WebhookDelivery,
+        claimed_until: Timestamp,
+    ) -> Result<bool> {
+        claim_webhook_delivery(&mut self.conn.borrow_mut(), delivery, claimed_until)
+    }
+    fn load_due_webhook_deliveries(
+        &self,
+        due_at: Timestamp,
+        limit: u64,
+    ) -> Result<Vec<WebhookDelivery>> {
+        load_due_webhook_deliveries(&mut self.conn.borrow_mut(), due_at, limit)
+    }
+    fn list_webhook_deliveries(
+        &self,
+        webhook_id: &Id,
+        pagination: &Pagination,
+    ) -> Result<Vec<WebhookDelivery>> {
+        list_webhook_deliveries(&mut self.conn.borrow_mut(), webhook_id, pagination)
+    }
+}
+
+impl WebhookRepo for DbConnection<'_> {
+    fn create_webhook(&self, webhook: &Webhook) -> Result<()> {
+        create_webhook(&mut self.conn.borrow_mut(), webhook)
+    }
+    fn get_webhook(&self, id: &Id) -> Result<Webhook> {
+        get_webhook(&mut self.conn.borrow_mut(), id)
+    }
+    fn list_webhooks_of_org(&self, org_id: &Id) -> Result<Vec<Webhook>> {
+        list_webhooks_of_org(&mut self.conn.borrow_mut(), org_id)
+    }
+    fn delete_webhook(&self, org_id: &Id, id: &Id) -> Result<()> {
+        delete_webhook(&mut self.conn.borrow_mut(), org_id, id)
+    }
+    fn create_webhook_delivery(&self, delivery: &WebhookDelivery) -> Result<()> {
+        create_webhook_delivery(&mut self.conn.borrow_mut(), delivery)
+    }
+    fn update_webhook_delivery(&self, delivery: &WebhookDelivery) -> Result<()> {
+        update_webhook_delivery(&mut self.conn.borrow_mut(), delivery)
+    }
+    fn claim_webhook_delivery(
+        &self,
+        delivery: &WebhookDelivery,
+        claimed_until: Timestamp,
+    ) -> Result<bool> {
+        claim_webhook_delivery(&mut self.conn.borrow_mut(), delivery, claimed_until)
+    }
+    fn load_due_webhook_deliveries(
+        &self,
+        due_at: Timestamp,
+        limit: u64,
+    ) -> Result<Vec<WebhookDelivery>> {
+        load_due_webhook_deliveries(&mut self.conn.borrow_mut(), due_at, limit)
+    }
+    fn list_webhook_deliveries(
+        &self,
+        webhook_id: &Id,
+        pagination: &Pagination,
+    ) -> Result<Vec<WebhookDelivery>> {
+        list_webhook_deliveries(&mut self.conn.borrow_mut(), webhook_id, pagination)
+    }
+}
+
+impl WebhookRepo for DbReadOnly<'_> {
+    fn create_webhook(&self, _webhook: &Webhook) -> Result<()> {
+        unreachable!();
+    }
+    fn get_webhook(&self, id: &Id) -> Result<Webhook> {
+        get_webhook(&mut self.conn.borrow_mut(), id)
+    }
+    fn list_webhooks_of_org(&self, org_id: &Id) -> Result<Vec<Webhook>> {
+        list_webhooks_of_org(&mut self.conn.borrow_mut(), org_id)
+    }
+    fn delete_webhook(&self, _org_id: &Id, _id: &Id) -> Result<()> {
+        unreachable!();
+    }
+    fn create_webhook_delivery(&self, _delivery: &WebhookDelivery) -> Result<()> {
+        unreachable!();
+    }
+    fn update_webhook_delivery(&self, _delivery: &WebhookDelivery) -> Result<()> {
+        unreachable!();
+    }
+    fn claim_webhook_delivery(
+        &self,
+        _delivery: &WebhookDelivery,
+        _claimed_until: Timestamp,
+    ) -> Result<bool> {
+        unreachable!();
+    }
+    fn load_due_webhook_deliveries(
+        &self,
+        due_at: Timestamp,
+        limit: u64,
+    ) -> Result<Vec<WebhookDelivery>> {
+        load_due_webhook_deliveries(&mut self.conn.borrow_mut(), due_at, limit)
+    }
+    fn list_webhook_deliveries(
+        &self,
+        webhook_id: &Id,
+        pagination: &Pagination,
+    ) -> Result<Vec<WebhookDelivery>> {
+        list_webhook_deliveries(&mut self.conn.borrow_mut(), webhook_id, pagination)
+    }
+}
+
+fn load_webhook(from: models::Webhook) -> Result<Webhook> {
+    let models::Webhook {
+        id,
+        org_id,
+        url,
+        secret,
+        created_at,
+    } = from;
+    let url = url.parse().map_err(|err| anyhow::anyhow!("{err}"))?;
+    Ok(Webhook {
+        id: id.into(),
+        org_id: org_id.into(),
+        url,
+        secret,
+        created_at: Timestamp::try_from_millis(created_at).map_err(|err| anyhow::anyhow!(err))?,
+    })
+}
+
+fn load_timestamp(millis: i64) -> Result<Timestamp> {
+    Ok(Timestamp::try_from_millis(millis).map_err(|err| anyhow::anyhow!(err))?)
+}
+
+fn load_webhook_delivery(from: models::WebhookDelivery) -> Result<WebhookDelivery> {
+    let models::WebhookDelivery {
+        id,
+        webhook_id,
+        event_type,
+        payload,
+        created_at,
+        status,
+        attempts,
+        next_attempt_at,
+        last_attempt_at,
+        last_response_status,
+        last_error,
+    } = from;
+    let event_type = event_type
+        .parse()
+        .map_err(|_| anyhow::anyhow!("Invalid webhook event type '{event_type}'"))?;
+    let status = WebhookDeliveryStatus::from_i16(status)
+        .ok_or_else(|| anyhow::anyhow!("Invalid webhook delivery status {status}"))?;
+    Ok(WebhookDelivery {
+        id: id.into(),
+        webhook_id: webhook_id.into(),
+        event_type,
+        payload,
+        created_at: load_timestamp(created_at)?,
+        status,
+        attempts: attempts as u32,
+        next_attempt_at: next_attempt_at.map(load_timestamp).transpose()?,
+        last_attempt_at: last_attempt_at.map(load_timestamp).transpose()?,
+        last_response_status: last_response_status.map(|status| status as u16),
+        last_error,
+    })
+}
+
+fn create_webhook(conn: &mut PgConnection, webhook: &Webhook) -> Result<()> {
+    let Webhook {
+        id,
+        org_id,
+        url,
+        secret,
+        created_at,
+    } = webhook;
+    let org_rowid = resolve_organization_rowid(conn, org_id)?;
+    let new_webhook = models::NewWebhook {
+        id: id.as_str(),
+        org_rowid,
+        url: url.as_str(),
+        secret,
+        created_at: created_at.as_millis(),
+    };
+    let _count = diesel::insert_into(schema::organization_webhook::table)
+        .values(&new_webhook)
+        .execute(conn)
+        .map_err(from_diesel_err)?;
+    debug_assert_eq!(1, _count);
+    Ok(())
+}
+
+fn get_webhook(conn: &mut PgConnection, id: &Id) -> Result<Webhook> {
+    use schema::{organization::dsl as org_dsl, organization_webhook::dsl};
+    let webhook = dsl::organization_webhook
+        .inner_join(org_dsl::organization)
+        .select((dsl::id, org_dsl::id, dsl::url, dsl::secret, dsl::created_at))
+        .filter(dsl::id.eq(id.as_str()))
+        .first::<models::Webhook>(conn)
+        .map_err(from_diesel_err)?;
+    load_webhook(webhook)
+}
+
+fn list_webhooks_of_org(conn: &mut PgConnection, org_id: &Id) -> Result<Vec<Webhook>> {
+    use schema::{organization::dsl as org_dsl, organization_webhook::dsl};
+    dsl::organization_webhook
+        .inner_join(org_dsl::organization)
+        .select((dsl::id, org_dsl::id, dsl::url, dsl::secret, dsl::created_at))
+        .filter(org_dsl::id.eq(org_id.as_str()))
+        .order_by(dsl::created_at)
+        .load::<models::Webhook>(conn)
+        .map_err(from_diesel_err)?
+        .into_iter()
+        .map(load_webhook)
+        .collect()
+}
+
+fn resolve_webhook_rowid(conn: &mut PgConnection, id: &Id) -> Result<i64> {
+    use schema::organization_webhook::dsl;
+    dsl::organization_webhook
+        .select(dsl::rowid)
+        .filter(dsl::id.eq(id.as_str()))
+        .first::<i64>(conn)
+        .map_err(from_diesel_err)
+}
+
+fn delete_webhook(conn: &mut PgConnection, org_id: &Id, id: &Id) -> Result<()> {
+    use schema::{organization_webhook::dsl, webhook_delivery::dsl as delivery_dsl};
+    let org_rowid = resolve_organization_rowid(conn, org_id)?;
+    let rowid = dsl::organization_webhook
+        .select(dsl::rowid)
+        .filter(dsl::id.eq(id.as_str()))
+        .filter(dsl::org_rowid.eq(org_rowid))
+        .first::<i64>(conn)
+        .map_err(from_diesel_err)?;
+    diesel::delete(delivery_dsl::webhook_delivery.filter(delivery_dsl::webhook_rowid.eq(rowid)))
+        .execute(conn)
+        .map_err(from_diesel_err)?;
+    let _count = diesel::delete(dsl::organization_webhook.filter(dsl::rowid.eq(rowid)))
+        .execute(conn)
+        .map_err(from_diesel_err)?;
+    debug_assert_eq!(1, _count);
+    Ok(())
+}
+
+fn create_webhook_delivery(conn: &mut PgConnection, delivery: &WebhookDelivery) -> Result<()> {
+    let WebhookDelivery {
+        id,
+        webhook_id,
+        event_type,
+        payload,
+        created_at,
+        status,
+        attempts,
+        next_attempt_at,
+        last_attempt_at,
+        last_response_status,
+        last_error,
+    } = delivery;
+    let webhook_rowid = resolve_webhook_rowid(conn, webhook_id)?;
+    let new_delivery = models::NewWebhookDelivery {
+        id: id.as_str(),
+        webhook_rowid,
+        event_type: event_type.as_str(),
+        payload,
+        created_at: created_at.as_millis(),
+        status: status.to_i16().unwrap(),
+        attempts: i64::from(*attempts),
+        next_attempt_at: next_attempt_at.map(Timestamp::as_millis),
+        last_attempt_at: last_attempt_at.map(Timestamp::as_millis),
+        last_response_status: last_response_status.map(i64::from),
+        last_error: last_error.as_deref(),
+    };
+    let _count = diesel::insert_into(schema::webhook_delivery::table)
+        .values(&new_delivery)
+        .execute(conn)
+        .map_err(from_diesel_err)?;
+    debug_assert_eq!(1, _count);
+    Ok(())
+}
+
+fn update_webhook_delivery(conn: &mut PgConnection, delivery: &WebhookDelivery) -> Result<()> {
+    use schema::webhook_delivery::dsl;
+    let WebhookDelivery {
+        id,
+        status,
+        attempts,
+        next_attempt_at,
+        last_attempt_at,
+        last_response_status,
+        last_error,
+        ..
+    } = delivery;
+    let count = diesel::update(dsl::webhook_delivery.filter(dsl::id.eq(id.as_str())))
+        .set((
+            dsl::status.eq(status.to_i16().unwrap()),
+            dsl::attempts.eq(i64::from(*attempts)),
+            dsl::next_attempt_at.eq(next_attempt_at.map(Timestamp::as_millis)),
+            dsl::last_attempt_at.eq(last_attempt_at.map(Timestamp::as_millis)),
+            dsl::last_response_status.eq(last_response_status.map(i64::from)),
+            dsl::last_error.eq(last_error.as_deref()),
+        ))
+        .execute(conn)
+        .map_err(from_diesel_err)?;
+    if count == 0 {
+        return Err(repo::Error::NotFound);
+    }
+    Ok(())
+}
+
+fn claim_webhook_delivery(
+    conn: &mut PgConnection,
+    delivery: &WebhookDelivery,
+    claimed_until: Timestamp,
+) -> Result<bool> {
+    use schema::webhook_delivery::dsl;
+    let Some(next_attempt_at) = delivery.next_attempt_at else {
+        return Ok(false);
+    };
+    // Fails if another instance has claimed or updated the delivery
+    // in the meantime
+    let count = diesel::update(
+        dsl::webhook_delivery
+            .filter(dsl::id.eq(delivery.id.as_str()))
+            .filter(dsl::status.eq(WebhookDeliveryStatus::Pending.to_i16().unwrap()))
+            .filter(dsl::attempts.eq(i64::from(delivery.attempts)))
+            .filter(dsl::next_attempt_at.eq(next_attempt_at.as_millis())),
+    )
+    .set(dsl::next_attempt_at.eq(claimed_until.as_millis()))
+    .execute(conn)
+    .map_err(from_diesel_err)?;
+    Ok(count > 0)
+}
+
+fn load_due_webhook_deliveries(
+    conn: &mut PgConnection,
+    due_at: Timestamp,
+    limit: u64,
+) -> Result<Vec<WebhookDelivery>> {
+    use schema::{organization_webhook::dsl as webhook_dsl, webhook_delivery::dsl};
+    let limit = i64::try_from(limit).unwrap_or(i64::MAX);
+    dsl::webhook_delivery
+        .inner_join(webhook_dsl::organization_webhook)
+        .select((
+            dsl::id,
+            webhook_dsl::id,
+            dsl::event_type,
+            dsl::payload,
+            dsl::created_at,
+            dsl::status,
+            dsl::attempts,
+            dsl::next_attempt_at,
+            dsl::last_attempt_at,
+            dsl::last_response_status,
+            dsl::last_error,
+        ))
+        .filter(dsl::status.eq(WebhookDeliveryStatus::Pending.to_i16().unwrap()))
+        .filter(dsl::next_attempt_at.le(due_at.as_millis()))
+        .order_by(dsl::next_attempt_at)
+        .then_order_by(dsl::rowid)
+        .limit(limit)
+        .load::<models::WebhookDelivery>(conn)
+        .map_err(from_diesel_err)?
+        .into_iter()
+        .map(load_webhook_delivery)
+        .collect()
+}
+
+fn list_webhook_deliveries(
+    conn: &mut PgConnection,
+    webhook_id: &Id,
+    pagination: &Pagination,
+) -> Result<Vec<WebhookDelivery>> {
+    use schema::{organization_webhook::dsl as webhook_dsl, webhook_delivery::dsl};
+    let mut query = dsl::webhook_delivery
+        .inner_join(webhook_dsl::organization_webhook)
+        .select((
+            dsl::id,
+            webhook_dsl::id,
+            dsl::event_type,
+            dsl::payload,
+            dsl::created_at,
+            dsl::status,
+            dsl::attempts,
+            dsl::next_attempt_at,
+            dsl::last_attempt_at,
+            dsl::last_response_status,
+            dsl::last_error,
+        ))
+        .filter(webhook_dsl::id.eq(webhook_id.as_str()))
+        .order_by(dsl::created_at.desc())
+        .then_order_by(dsl::rowid.desc())
+        .into_boxed();
+
+    // Pagination
+    let offset = pagination.offset.unwrap_or(0) as i64;
+    if let Some(limit) = pagination.limit {
+        query = query.limit(limit as i64);
+    }
+    if offset > 0 {
+        query = query.offset(offset);
+    }
+
+    query
+        .load::<models::WebhookDelivery>(conn)
+        .map_err(from_diesel_err)?
+        .into_iter()
+        .map(load_webhook_delivery)
+        .collect()
+}
@@ -0,0 +1,540 @@
+///////////////////////////////////////////////////////////////////////
+// Tags
+///////////////////////////////////////////////////////////////////////
+
+table! {
+    tags (id) {
+        id -> Text,
+    }
+}
+
+///////////////////////////////////////////////////////////////////////
+// Organizations
+///////////////////////////////////////////////////////////////////////
+
+table! {
+    organization (rowid) {
+        rowid -> BigInt,
+        id -> Text,
+        name -> Text,
+        api_token -> Text,
+    }
+}
+
+table! {
+    organization_tag (org_rowid, tag_label) {
+        org_rowid -> BigInt,
+        tag_label -> Text,
+        tag_allow_add -> SmallInt,
+        tag_allow_remove -> SmallInt,
+        require_clearance -> SmallInt,
+    }
+}
+
+joinable!(organization_tag -> organization (org_rowid));
+
+table! {
+    organization_place_clearance (org_rowid, place_rowid) {
+        rowid -> BigInt,
+        org_rowid -> BigInt,
+        place_rowid -> BigInt,
+        created_at -> BigInt,
+        // last cleared revision or NULL if the place is new and has not been cleared yet
+        last_cleared_revision -> Nullable<BigInt>,
+    }
+}
+
+joinable!(organization_place_clearance -> organization (org_rowid));
+joinable!(organization_place_clearance -> place (place_rowid));
+
+table! {
+    organization_event_clearance (org_rowid, event_rowid) {
+        rowid -> BigInt,
+        org_rowid -> BigInt,
+        event_rowid -> BigInt,
+        created_at -> BigInt,
+        // last cleared revision or NULL if the event is new and has not been cleared yet
+        last_cleared_revision -> Nullable<BigInt>,
+    }
+}
+
+joinable!(organization_event_clearance -> organization (org_rowid));
+joinable!(organization_event_clearance -> events (event_rowid));
+
+table! {
+    organization_api_token (rowid) {
+        rowid -> BigInt,
+        id -> Text,
+        org_rowid -> BigInt,
+        name -> Text,
+        token_hash -> Text,
+        // space separated list of scopes
+        scopes -> Text,
+        created_at -> BigInt,
+        expires_at -> Nullable<BigInt>,
+        last_used_at -> Nullable<BigInt>,
+        revoked_at -> Nullable<BigInt>,
+    }
+}
+
+joinable!(organization_api_token -> organization (org_rowid));
+
+table! {
+    organization_webhook (rowid) {
+        rowid -> BigInt,
+        id -> Text,
+        org_rowid -> BigInt,
+        url -> Text,
+        secret -> Text,
+        created_at -> BigInt,
+    }
+}
+
+joinable!(organization_webhook -> organization (org_rowid));
+
+table! {
+    organization_member (rowid) {
+        rowid -> BigInt,
+        org_rowid -> BigInt,
+        user_id -> BigInt,
+        role -> Text,
+    }
+}
+
+joinable!(organization_member -> organization (org_rowid));
+joinable!(organization_member -> users (user_id));
+
+table! {
+    organization_place_clearance_log (rowid) {
+        rowid -> BigInt,
+        org_rowid -> BigInt,
+        place_id -> Text,
+        cleared_revision -> BigInt,
+        created_at -> BigInt,
+        // e-mail address
+        created_by -> Nullable<Text>,
+        context -> Nullable<Text>,
+        comment -> Nullable<Text>,
+    }
+}
+
+joinable!(organization_place_clearance_log -> organization (org_rowid));
+
+table! {
+    organization_event_clearance_log (rowid) {
+        rowid -> BigInt,
+        org_rowid -> BigInt,
+        event_id -> Text,
+        cleared_revision -> BigInt,
+        created_at -> BigInt,
+        // e-mail address
+        created_by -> Nullable<Text>,
+        context -> Nullable<Text>,
+        comment -> Nullable<Text>,
+    }
+}
+
+joinable!(organization_event_clearance_log -> organization (org_rowid));
+
+table! {
+    webhook_delivery (rowid) {
+        rowid -> BigInt,
+        id -> Text,
+        webhook_rowid -> BigInt,
+        event_type -> Text,
+        payload -> Text,
+        created_at -> BigInt,
+        status -> SmallInt,
+        attempts -> BigInt,
+        next_attempt_at -> Nullable<BigInt>,
+        last_attempt_at -> Nullable<BigInt>,
+        last_response_status -> Nullable<BigInt>,
+        last_error -> Nullable<Text>,
+    }
+}
+
+joinable!(webhook_delivery -> organization_webhook (webhook_rowid));
+
+///////////////////////////////////////////////////////////////////////
+// Users
+///////////////////////////////////////////////////////////////////////
+
+table! {
+    users (id) {
+        id -> BigInt,
+        email -> Text,
+        email_confirmed -> Bool,
+        password -> Text,
+        role -> SmallInt,
+    }
+}
+
+table! {
+    user_tokens (id) {
+        id -> BigInt,
+        user_id -> BigInt,
+        expires_at -> BigInt,
+        nonce -> Text,
+    }
+}
+
+joinable!(user_tokens -> users (user_id));
+
+///////////////////////////////////////////////////////////////////////
+// Places
+///////////////////////////////////////////////////////////////////////
+
+table! {
+    place (rowid) {
+        rowid -> BigInt,
+        current_rev -> BigInt,
+        id -> Text,
+        license -> Text,
+    }
+}
+
+table! {
+    place_revision (rowid) {
+        rowid -> BigInt,
+        parent_rowid -> BigInt,
+        rev -> BigInt,
+        created_at -> BigInt,
+        created_by -> Nullable<BigInt>,
+        current_status -> SmallInt,
+        title -> Text,
+        description -> Text,
+        lat -> Double,
+        lon -> Double,
+        street -> Nullable<Text>,
+        zip -> Nullable<Text>,
+        city -> Nullable<Text>,
+        country -> Nullable<Text>,
+        state -> Nullable<Text>,
+        contact_name -> Nullable<Text>,
+        email -> Nullable<Text>,
+        phone -> Nullable<Text>,
+        homepage -> Nullable<Text>,
+        opening_hours -> Nullable<Text>,
+        founded_on -> Nullable<Text>,
+        image_url -> Nullable<Text>,
+        image_link_url -> Nullable<Text>,
+    }
+}
+
+joinable!(place_revision -> place (parent_rowid));
+
+table! {
+    place_revision_tag (parent_rowid, tag) {
+        parent_rowid -> BigInt,
+        tag -> Text,
+    }
+}
+
+joinable!(place_revision_tag -> place_revision (parent_rowid));
+
+table! {
+    place_revision_custom_link (parent_rowid, url) {
+        parent_rowid -> BigInt,
+        url -> Text,
+        title -> Nullable<Text>,
+        description -> Nullable<Text>,
+    }
+}
+
+joinable!(place_revision_custom_link -> place_revision (parent_rowid));
+
+table! {
+    place_revision_translation (parent_rowid, lang) {
+        parent_rowid -> BigInt,
+        lang -> Text,
+        title -> Text,
+        description -> Nullable<Text>,
+    }
+}
+
+joinable!(place_revision_translation -> place_revision (parent_rowid));
+
+table! {
+    place_revision_review (rowid) {
+        rowid -> BigInt,
+        parent_rowid -> BigInt,
+        rev -> BigInt,
+        created_at -> BigInt,
+        created_by -> Nullable<BigInt>,
+        status -> SmallInt,
+        context -> Nullable<Text>,
+        comment -> Nullable<Text>,
+    }
+}
+
+joinable!(place_revision_review -> place_revision (parent_rowid));
+
+table! {
+    place_rating (rowid) {
+        rowid -> BigInt,
+        parent_rowid -> BigInt,
+        created_at -> BigInt,
+        created_by -> Nullable<BigInt>,
+        archived_at -> Nullable<BigInt>,
+        archived_by -> Nullable<BigInt>,
+        id -> Text,
+        title -> Text,
+        value -> SmallInt,
+        context -> Text,
+        source -> Nullable<Text>,
+    }
+}
+
+joinable!(place_rating -> place (parent_rowid));
+
+table! {
+    place_rating_comment (rowid) {
+        rowid -> BigInt,
+        parent_rowid -> BigInt,
+        created_at -> BigInt,
+        created_by -> Nullable<BigInt>,
+        archived_at -> Nullable<BigInt>,
+        archived_by -> Nullable<BigInt>,
+        id -> Text,
+        text -> Text,
+    }
+}
+
+joinable!(place_rating_comment -> place_rating (parent_rowid));
+
+// The merged place is redirected to the target place
+table! {
+    place_redirect (rowid) {
+        rowid -> BigInt,
+        place_rowid -> BigInt,
+        target_place_rowid -> BigInt,
+    }
+}
+
+table! {
+    place_duplicate_candidate (rowid) {
+        rowid -> BigInt,
+        place_id -> Text,
+        duplicate_place_id -> Text,
+        similarity -> Double,
+        detected_at -> BigInt,
+        state -> Text,
+        reviewed_at -> Nullable<BigInt>,
+        reviewed_by -> Nullable<Text>,
+    }
+}
+
+///////////////////////////////////////////////////////////////////////
+// Events
+///////////////////////////////////////////////////////////////////////
+
+// TODO: Rename id -> rowid
+// TODO: Rename uid -> id
+table! {
+    events (id) {
+        id -> BigInt,
+        uid -> Text,
+        title -> Text,
+        description -> Nullable<Text>,
+        start -> BigInt,
+        end -> Nullable<BigInt>,
+        lat -> Nullable<Double>,
+        lng -> Nullable<Double>,
+        street -> Nullable<Text>,
+        zip -> Nullable<Text>,
+        city -> Nullable<Text>,
+        country -> Nullable<Text>,
+        state -> Nullable<Text>,
+        email -> Nullable<Text>,
+        telephone -> Nullable<Text>,
+        homepage -> Nullable<Text>,
+        created_by -> Nullable<BigInt>,
+        registration -> Nullable<SmallInt>,
+        organizer -> Nullable<Text>,
+        archived -> Nullable<BigInt>,
+        image_url -> Nullable<Text>,
+        image_link_url -> Nullable<Text>,
+        rrule -> Nullable<Text>,
+        exdates -> Nullable<Text>,
+        current_rev -> BigInt,
+    }
+}
+
+joinable!(events -> users (created_by));
+
+table! {
+    event_tags (event_id, tag) {
+        event_id -> BigInt,
+        tag -> Text,
+    }
+}
+
+joinable!(event_tags -> events (event_id));
+
+table! {
+    event_revision (rowid) {
+        rowid -> BigInt,
+        parent_rowid -> BigInt,
+        rev -> BigInt,
+        created_at -> BigInt,
+        created_by -> Nullable<BigInt>,
+        context -> Nullable<Text>,
+        comment -> Nullable<Text>,
+        title -> Text,
+        description -> Nullable<Text>,
+        start -> BigInt,
+        end -> Nullable<BigInt>,
+        lat -> Nullable<Double>,
+        lng -> Nullable<Double>,
+        street -> Nullable<Text>,
+        zip -> Nullable<Text>,
+        city -> Nullable<Text>,
+        country -> Nullable<Text>,
+        state -> Nullable<Text>,
+        email -> Nullable<Text>,
+        telephone -> Nullable<Text>,
+        homepage -> Nullable<Text>,
+        registration -> Nullable<SmallInt>,
+        organizer -> Nullable<Text>,
+        image_url -> Nullable<Text>,
+        image_link_url -> Nullable<Text>,
+        rrule -> Nullable<Text>,
+        exdates -> Nullable<Text>,
+    }
+}
+
+joinable!(event_revision -> events (parent_rowid));
+
+table! {
+    event_revision_tag (parent_rowid, tag) {
+        parent_rowid -> BigInt,
+        tag -> Text,
+    }
+}
+
+joinable!(event_revision_tag -> event_revision (parent_rowid));
+
+table! {
+    event_revision_translation (parent_rowid, lang) {
+        parent_rowid -> BigInt,
+        lang -> Text,
+        title -> Text,
+        description -> Nullable<Text>,
+    }
+}
+
+joinable!(event_revision_translation -> event_revision (parent_rowid));
+
+///////////////////////////////////////////////////////////////////////
+// Subscriptions
+///////////////////////////////////////////////////////////////////////
+
+// TODO: Rename id -> rowid
+// TODO: Rename uid -> id
+// TODO: Rename user_id -> user_rowid
+table! {
+    bbox_subscriptions (id) {
+        id -> BigInt,
+        uid -> Text,
+        user_id -> BigInt,
+        south_west_lat -> Double,
+        south_west_lng -> Double,
+        north_east_lat -> Double,
+        north_east_lng -> Double,
+    }
+}
+
+joinable!(bbox_subscriptions -> users (user_id));
+
+///////////////////////////////////////////////////////////////////////
+// Reminders
+///////////////////////////////////////////////////////////////////////
+
+diesel::table! {
+    sent_reminders (rowid) {
+        rowid -> BigInt,
+        place_rowid -> BigInt,
+        sent_at -> BigInt,
+        sent_to_email -> Text,
+    }
+}
+
+joinable!(sent_reminders -> place (place_rowid));
+
+///////////////////////////////////////////////////////////////////////
+// Review tokens
+///////////////////////////////////////////////////////////////////////
+
+table! {
+    review_tokens (rowid) {
+        rowid -> BigInt,
+        place_rowid -> BigInt,
+        revision -> BigInt,
+        expires_at -> BigInt,
+        nonce -> Text,
+    }
+}
+
+joinable!(review_tokens -> place (place_rowid));
+
+table! {
+    revoked_tokens (id) {
+        id -> Text,
+        expires_at -> BigInt,
+    }
+}
+
+///////////////////////////////////////////////////////////////////////
+// Changes
+///////////////////////////////////////////////////////////////////////
+
+table! {
+    change_log (rowid) {
+        rowid -> BigInt,
+        changed_at -> BigInt,
+        kind -> Text,
+        item_type -> Text,
+        item_id -> Text,
+        item_rev -> Nullable<BigInt>,
+        item_status -> Nullable<SmallInt>,
+        place_id -> Nullable<Text>,
+    }
+}
+
+///////////////////////////////////////////////////////////////////////
+
+allow_tables_to_appear_in_same_query!(
+    bbox_subscriptions,
+    change_log,
+    events,
+    event_tags,
+    event_revision,
+    event_revision_tag,
+    event_revision_translation,
+    place,
+    place_rating,
+    place_rating_comment,
+    place_duplicate_candidate,
+    place_redirect,
+    place_revision,
+    place_revision_review,
+    place_revision_tag,
+    place_revision_custom_link,
+    place_revision_translation,
+    organization,
+    organization_tag,
+    organization_place_clearance,
+    organization_event_clearance,
+    organization_api_token,
+    organization_webhook,
+    organization_member,
+    organization_place_clearance_log,
+    organization_event_clearance_log,
+    webhook_delivery,
+    tags,
+    users,
+    user_tokens,
+    review_tokens,
+    revoked_tokens,
+    sent_reminders
+);
@@ -0,0 +1,104 @@
+use ofdb_core::{entities::*, repositories::*};
+use ofdb_entities::builders::Builder as _;
+
+use super::{TestDatabase, remove_sqlite_file, temp_sqlite_file};
+use crate::import_sqlite_database;
+
+#[test]
+fn import_sqlite_database_into_empty_database() {
+    let db = TestDatabase::create();
+    let sqlite_file = temp_sqlite_file("import");
+    let sqlite_url = sqlite_file.to_str().unwrap();
+
+    let place = Place::build()
+        .title("foo")
+        .tags(vec!["bar"])
+        .license("CC0-1.0")
+        .finish();
+    let org = Organization {
+        id: Id::new(),
+        name: "org".into(),
+        api_token: "secret".into(),
+        moderated_tags: vec![ModeratedTag {
+            label: "bar".into(),
+            allow_add: true,
+            allow_remove: false,
+            require_clearance: true,
+        }],
+    };
+    let user = User {
+        email: "Jane.Doe@Example.com".parse().unwrap(),
+        email_confirmed: true,
+        password: "secret".parse().unwrap(),
+        role: Role::Scout,
+    };
+    {
+        let sqlite_connections = ofdb_db_sqlite::Connections::init(sqlite_url, 1).unwrap();
+        ofdb_db_sqlite::run_embedded_database_migrations(sqlite_connections.exclusive().unwrap());
+        let mut sqlite_db = sqlite_connections.exclusive().unwrap();
+        sqlite_db.create_user(&user).unwrap();
+        sqlite_db
+            .create_tag_if_it_does_not_exist(&Tag { id: "bar".into() })
+            .unwrap();
+        sqlite_db.create_or_update_place(place.clone()).unwrap();
+        let mut updated_place = place.clone();
+        updated_place.revision = place.revision.next();
+        updated_place.title = "updated".into();
+        updated_place.translations = vec![Translation {
+            lang: "en".parse().unwrap(),
+            title: "updated (en)".into(),
+            description: None,
+        }];
+        sqlite_db.create_or_update_place(updated_place).unwrap();
+        sqlite_db.create_org(org.clone()).unwrap();
+    }
+
+    let stats = import_sqlite_database(sqlite_url, &db.connections).unwrap();
+    assert_eq!(
+        Some(&("place_revision", 2)),
+        stats
+            .tables
+            .iter()
+            .find(|(table, _)| *table == "place_revision")
+    );
+    // Tags, users, places, the organization with its moderated tag,
+    // and the change log
+    assert_eq!(1 + 1 + 1 + 2 + 2 + 2 + 1 + 1 + 1 + 2, stats.total_rows());
+
+    {
+        let pg_db = db.connections.shared().unwrap();
+        assert_eq!(vec![user.clone()], pg_db.all_users().unwrap());
+        assert_eq!(vec![org], pg_db.all_orgs().unwrap());
+        let (imported_place, _) = pg_db.get_place(place.id.as_str()).unwrap();
+        assert_eq!("updated", imported_place.title);
+        assert_eq!("updated (en)", imported_place.translations[0].title);
+        assert_eq!(
+            2,
+            pg_db
+                .get_place_history(place.id.as_str(), None)
+                .unwrap()
+                .revisions
+                .len()
+        );
+    }
+
+    // New rows don't collide with the imported row ids
+    let pg_db = db.connections.exclusive().unwrap();
+    pg_db
+        .create_or_update_place(Place::build().title("new").license("CC0-1.0").finish())
+        .unwrap();
+    assert_eq!(2, pg_db.count_places().unwrap());
+    let changes = pg_db.load_changes(None, 10).unwrap();
+    assert_eq!(
+        vec![1, 2, 3],
+        changes
+            .into_iter()
+            .map(|change| i64::from(change.cursor))
+            .collect::<Vec<_>>()
+    );
+
+    // Only empty databases could be imported
+    assert!(import_sqlite_database(sqlite_url, &db.connections).is_err());
+
+    remove_sqlite_file(&sqlite_file);
+}
@@ -0,0 +1,639 @@
+use ofdb_core::{
+    entities::*,
+    repositories::*,
+    util::{
+        geo::{MapBbox, MapPoint},
+        time::Timestamp,
+    },
+};
+use ofdb_entities::builders::Builder as _;
+
+use super::TestDatabase;
+
+fn new_user(email: &str) -> User {
+    User {
+        email: email.parse().unwrap(),
+        email_confirmed: true,
+        password: "secret".parse().unwrap(),
+        role: Role::User,
+    }
+}
+
+fn new_event(title: &str, tags: &[&str]) -> Event {
+    Event {
+        id: Id::new(),
+        revision: Revision::initial(),
+        title: title.into(),
+        description: None,
+        start: Timestamp::try_from_secs(1_700_000_000).unwrap(),
+        end: None,
+        location: Some(Location {
+            pos: MapPoint::from_lat_lng_deg(48.7, 9.1),
+            address: None,
+        }),
+        contact: None,
+        tags: tags.iter().map(ToString::to_string).collect(),
+        homepage: None,
+        created_by: None,
+        registration: None,
+        archived: None,
+        image_url: None,
+        image_link_url: None,
+        recurrence: None,
+        translations: vec![],
+    }
+}
+
+fn activity_log() -> ActivityLog {
+    ActivityLog {
+        activity: Activity::now(None),
+        context: None,
+        comment: None,
+    }
+}
+
+#[test]
+fn compare_emails_of_users_case_insensitive() {
+    let db = TestDatabase::create();
+    let db = db.connections.exclusive().unwrap();
+    db.create_user(&new_user("Jane.Doe@Example.com")).unwrap();
+    let email = "jane.doe@example.com".parse().unwrap();
+    let user = db.get_user_by_email(&email).unwrap();
+    assert_eq!("Jane.Doe@Example.com", user.email.as_str());
+    // E-mail addresses are unique regardless of their case
+    assert!(db.create_user(&new_user("JANE.DOE@EXAMPLE.COM")).is_err());
+    db.delete_user_by_email(&email).unwrap();
+    assert!(db.try_get_user_by_email(&email).unwrap().is_none());
+}
+
+#[test]
+fn create_and_update_places() {
+    let db = TestDatabase::create();
+    let db = db.connections.exclusive().unwrap();
+    let place = Place::build()
+        .title("foo")
+        .tags(vec!["bar", "baz"])
+        .license("CC0-1.0")
+        .finish();
+    for tag in &place.tags {
+        db.create_tag_if_it_does_not_exist(&Tag { id: tag.clone() })
+            .unwrap();
+        // Existing tags are ignored
+        db.create_tag_if_it_does_not_exist(&Tag { id: tag.clone() })
+            .unwrap();
+    }
+    db.create_or_update_place(place.clone()).unwrap();
+    let mut updated_place = place.clone();
+    updated_place.revision = place.revision.next();
+    updated_place.title = "updated".into();
+    updated_place.tags = vec!["bar".into()];
+    db.create_or_update_place(updated_place.clone()).unwrap();
+
+    let (loaded_place, status) = db.get_place(place.id.as_str()).unwrap();
+    assert_eq!(ReviewStatus::Created, status);
+    assert_eq!("updated", loaded_place.title);
+    assert_eq!(vec!["bar".to_string()], loaded_place.tags);
+    assert_eq!(2, db.count_tags().unwrap());
+    assert_eq!(
+        2,
+        db.get_place_history(place.id.as_str(), None)
+            .unwrap()
+            .revisions
+            .len()
+    );
+    assert_eq!(
+        vec![TagFrequency("bar".into(), 1)],
+        db.most_popular_place_revision_tags(&Default::default(), &Default::default())
+            .unwrap()
+    );
+}
+
+#[test]
+fn create_and_update_events_with_tags() {
+    let db = TestDatabase::create();
+    let db = db.connections.exclusive().unwrap();
+    // Duplicate tags must be ignored
+    let event = new_event("foo", &["bar", "baz", "bar"]);
+    db.create_event(event.clone(), &activity_log()).unwrap();
+    let mut updated_event = event.clone();
+    updated_event.revision = event.revision.next();
+    updated_event.tags = vec!["baz".into(), "qux".into()];
+    db.update_event(&updated_event, &activity_log()).unwrap();
+    let mut loaded_event = db.get_event(event.id.as_str()).unwrap();
+    loaded_event.tags.sort();
+    assert_eq!(
+        vec!["baz".to_string(), "qux".to_string()],
+        loaded_event.tags
+    );
+    assert_eq!(
+        2,
+        db.get_event_history(event.id.as_str(), None)
+            .unwrap()
+            .revisions
+            .len()
+    );
+    // Outdated revisions are rejected
+    assert!(matches!(
+        db.update_event(&updated_event, &activity_log()),
+        Err(Error::InvalidVersion)
+    ));
+}
+
+#[test]
+fn query_places_and_events_by_bbox() {
+    let db = TestDatabase::create();
+    let db = db.connections.exclusive().unwrap();
+    let place_at = |title: &str, lat, lng| {
+        Place::build()
+            .title(title)
+            .pos(MapPoint::from_lat_lng_deg(lat, lng))
+            .license("CC0-1.0")
+            .finish()
+    };
+    let inside = place_at("inside", 48.7, 9.1);
+    let moved = place_at("moved", 48.8, 9.2);
+    let outside = place_at("outside", 10.0, 10.0);
+    let across_antimeridian = place_at("across", 0.0, 179.5);
+    for place in [&inside, &moved, &outside, &across_antimeridian] {
+        db.create_or_update_place(place.clone()).unwrap();
+    }
+    // Only the position of the current revision is considered
+    let mut moved_away = moved.clone();
+    moved_away.revision = moved.revision.next();
+    moved_away.location.pos = MapPoint::from_lat_lng_deg(10.0, 10.1);
+    db.create_or_update_place(moved_away).unwrap();
+
+    let bbox = MapBbox::new(
+        MapPoint::from_lat_lng_deg(48.0, 9.0),
+        MapPoint::from_lat_lng_deg(49.0, 10.0),
+    );
+    let titles = |places: Vec<(Place, ReviewStatus)>| {
+        let mut titles: Vec<_> = places.into_iter().map(|(place, _)| place.title).collect();
+        titles.sort_unstable();
+        titles
+    };
+    assert_eq!(
+        vec!["inside"],
+        titles(db.all_places_in_bbox(&bbox).unwrap())
+    );
+    let bbox = MapBbox::new(
+        MapPoint::from_lat_lng_deg(-1.0, 179.0),
+        MapPoint::from_lat_lng_deg(1.0, -179.0),
+    );
+    assert_eq!(
+        vec!["across"],
+        titles(db.all_places_in_bbox(&bbox).unwrap())
+    );
+
+    let without_location = Event {
+        location: None,
+        ..new_event("without location", &[])
+    };
+    let outside = Event {
+        location: Some(Location {
+            pos: MapPoint::from_lat_lng_deg(10.0, 10.0),
+            address: None,
+        }),
+        ..new_event("outside", &[])
+    };
+    for event in [new_event("inside", &[]), outside, without_location] {
+        db.create_event(event, &activity_log()).unwrap();
+    }
+    let bbox = MapBbox::new(
+        MapPoint::from_lat_lng_deg(48.0, 9.0),
+        MapPoint::from_lat_lng_deg(49.0, 10.0),
+    );
+    let events = db.all_events_in_bbox_chronologically(&bbox).unwrap();
+    assert_eq!(
+        vec!["inside"],
+        events.iter().map(|e| e.title.as_str()).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn revoke_tokens_repeatedly() {
+    let db = TestDatabase::create();
+    let db = db.connections.exclusive().unwrap();
+    db.add_revoked_token("token", Timestamp::try_from_millis(1_000).unwrap())
+        .unwrap();
+    db.add_revoked_token("token", Timestamp::try_from_millis(3_000).unwrap())
+        .unwrap();
+    assert!(db.is_token_revoked("token").unwrap());
+    assert_eq!(
+        0,
+        db.delete_expired_revoked_tokens(Timestamp::try_from_millis(2_000).unwrap())
+            .unwrap()
+    );
+    assert_eq!(
+        1,
+        db.delete_expired_revoked_tokens(Timestamp::try_from_millis(4_000).unwrap())
+            .unwrap()
+    );
+}
+
+#[test]
+fn write_concurrently() {
+    let db = TestDatabase::create();
+    // Writers are not serialized by a global lock
+    let first = db.connections.exclusive().unwrap();
+    let second = db.connections.exclusive().unwrap();
+    first.create_user(&new_user("first@example.com")).unwrap();
+    second.create_user(&new_user("second@example.com")).unwrap();
+    assert_eq!(2, first.count_users().unwrap());
+    assert_eq!(2, second.count_users().unwrap());
+}
+
+#[test]
+fn log_changes_in_commit_order() {
+    let db = TestDatabase::create();
+    let event = new_event("foo", &[]);
+    let second = db
+        .connections
+        .exclusive()
+        .unwrap()
+        .transaction(|conn| {
+            conn.create_event(event.clone(), &activity_log())?;
+            // Concurrent writers have to wait until the first one commits
+            let connections = db.connections.clone();
+            let second = std::thread::spawn(move || {
+                connections
+                    .exclusive()
+                    .unwrap()
+                    .create_event(new_event("bar", &[]), &activity_log())
+                    .unwrap();
+            });
+            std::thread::sleep(std::time::Duration::from_millis(200));
+            assert!(!second.is_finished());
+            Ok::<_, Error>(second)
+        })
+        .unwrap();
+    second.join().unwrap();
+
+    let db = db.connections.exclusive().unwrap();
+    let mut updated_event = event.clone();
+    updated_event.revision = event.revision.next();
+    db.update_event(&updated_event, &activity_log()).unwrap();
+    db.archive_events(&[event.id.as_str()], Timestamp::now())
+        .unwrap();
+    db.delete_event_with_matching_tags(event.id.as_str(), &[])
+        .unwrap();
+    let changes = db.load_changes(None, 10).unwrap();
+    assert_eq!(
+        vec![
+            (1, ChangeKind::Created, Some(event.id.clone())),
+            (2, ChangeKind::Created, None),
+            (3, ChangeKind::Updated, Some(event.id.clone())),
+            (4, ChangeKind::Archived, Some(event.id.clone())),
+            (5, ChangeKind::Deleted, Some(event.id.clone())),
+        ],
+        changes
+            .into_iter()
+            .map(|change| {
+                let ChangedItem::Event { id, .. } = change.item else {
+                    unreachable!();
+                };
+                (
+                    i64::from(change.cursor),
+                    change.kind,
+                    Some(id).filter(|id| *id == event.id),
+                )
+            })
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn update_and_delete_organizations() {
+    let db = TestDatabase::create();
+    let mut db = db.connections.exclusive().unwrap();
+    let moderated_tag = |label: &str| ModeratedTag {
+        label: label.into(),
+        allow_add: true,
+        allow_remove: false,
+        require_clearance: false,
+    };
+    let org = Organization {
+        id: Id::new(),
+        name: "org".into(),
+        api_token: "secret".into(),
+        moderated_tags: vec![moderated_tag("foo"), moderated_tag("bar")],
+    };
+    db.create_org(org.clone()).unwrap();
+    db.create_webhook(&Webhook {
+        id: Id::new(),
+        org_id: org.id.clone(),
+        url: "https://example.com/hook".parse().unwrap(),
+        secret: Webhook::generate_secret(),
+        created_at: Timestamp::now(),
+    })
+    .unwrap();
+
+    db.update_org(Organization {
+        name: "renamed".into(),
+        api_token: "ignored".into(),
+        moderated_tags: vec![moderated_tag("baz")],
+        ..org.clone()
+    })
+    .unwrap();
+    let updated = db.get_org_by_id(&org.id).unwrap();
+    assert_eq!("renamed", updated.name);
+    assert_eq!("secret", updated.api_token);
+    assert_eq!(vec![moderated_tag("baz")], updated.moderated_tags);
+
+    db.delete_org(&org.id).unwrap();
+    assert!(matches!(db.get_org_by_id(&org.id), Err(Error::NotFound)));
+    assert!(db.get_moderated_tags_by_org(None).unwrap().is_empty());
+    assert!(db.list_webhooks_of_org(&org.id).unwrap().is_empty());
+    assert!(matches!(db.delete_org(&org.id), Err(Error::NotFound)));
+}
+
+#[test]
+fn organization_members_and_clearance_log() {
+    let db = TestDatabase::create();
+    let mut db = db.connections.exclusive().unwrap();
+    let org = Organization {
+        id: Id::new(),
+        name: "org".into(),
+        api_token: "secret".into(),
+        moderated_tags: vec![],
+    };
+    db.create_org(org.clone()).unwrap();
+    db.create_user(&new_user("member@example.com")).unwrap();
+
+    // E-mail addresses are case-insensitive
+    let member = OrganizationMember {
+        org_id: org.id.clone(),
+        email: "Member@example.com".parse().unwrap(),
+        role: OrganizationRole::Viewer,
+    };
+    db.add_or_update_org_member(&member).unwrap();
+    db.add_or_update_org_member(&OrganizationMember {
+        role: OrganizationRole::Clearer,
+        ..member.clone()
+    })
+    .unwrap();
+    let memberships = db
+        .list_org_memberships_of_user(&"member@example.com".parse().unwrap())
+        .unwrap();
+    assert_eq!(1, memberships.len());
+    assert_eq!(OrganizationRole::Clearer, memberships[0].role);
+
+    let event = new_event("event", &[]);
+    db.create_event(event.clone(), &activity_log()).unwrap();
+    db.add_pending_clearance_for_events(
+        std::slice::from_ref(&org.id),
+        &PendingClearanceForEvent {
+            event_id: event.id.clone(),
+            created_at: Timestamp::now(),
+            last_cleared_revision: None,
+        },
+    )
+    .unwrap();
+    let activity = ActivityLog {
+        activity: Activity::now(Some(member.email.clone())),
+        context: None,
+        comment: None,
+    };
+    assert_eq!(
+        1,
+        db.update_pending_clearances_for_events(
+            &org.id,
+            &[ClearanceForEvent {
+                event_id: event.id.clone(),
+                cleared_revision: None,
+            }],
+            &activity,
+        )
+        .unwrap()
+    );
+    let clearance_log = db
+        .list_clearance_log_for_events(&org.id, &Pagination::default())
+        .unwrap();
+    assert_eq!(1, clearance_log.len());
+    assert_eq!(event.id, clearance_log[0].event_id);
+    assert_eq!(event.revision, clearance_log[0].cleared_revision);
+    assert_eq!(activity.activity.by, clearance_log[0].activity.activity.by);
+
+    // Restored clearances are recorded without a pending clearance
+    db.add_clearance_log_for_events(&org.id, &clearance_log[0])
+        .unwrap();
+    assert_eq!(
+        2,
+        db.list_clearance_log_for_events(&org.id, &Pagination::default())
+            .unwrap()
+            .len()
+    );
+
+    db.delete_user_by_email(&member.email).unwrap();
+    assert!(db.list_org_members(&org.id).unwrap().is_empty());
+    db.delete_org(&org.id).unwrap();
+}
+
+#[test]
+fn move_ratings_and_redirect_merged_places() {
+    let db = TestDatabase::create();
+    let db = db.connections.exclusive().unwrap();
+    let [first, second, third] = ["first", "second", "third"].map(|title| {
+        let place = Place::build().title(title).license("CC0-1.0").finish();
+        db.create_or_update_place(place.clone()).unwrap();
+        place.id
+    });
+    db.create_rating(Rating {
+        id: Id::new(),
+        place_id: first.clone(),
+        created_at: Timestamp::now(),
+        archived_at: None,
+        title: "rating".into(),
+        value: RatingValue::new(1),
+        context: RatingContext::Fairness,
+        source: None,
+    })
+    .unwrap();
+    assert_eq!(1, db.move_ratings_of_place(&first, &second).unwrap());
+    assert!(db.load_ratings_of_place(first.as_str()).unwrap().is_empty());
+    assert_eq!(1, db.load_ratings_of_place(second.as_str()).unwrap().len());
+
+    db.create_place_redirect(&first, &second).unwrap();
+    db.create_place_redirect(&second, &third).unwrap();
+    assert_eq!(
+        Some(third.clone()),
+        db.resolve_place_redirect(&first).unwrap()
+    );
+    assert_eq!(
+        Some(third.clone()),
+        db.resolve_place_redirect(&second).unwrap()
+    );
+    assert_eq!(None, db.resolve_place_redirect(&third).unwrap());
+}
+
+#[test]
+fn replace_and_review_duplicate_candidates() {
+    let db = TestDatabase::create();
+    let db = db.connections.exclusive().unwrap();
+    let candidate = |place_id: &str, duplicate_place_id: &str, similarity| DuplicateCandidate {
+        place_id: place_id.into(),
+        duplicate_place_id: duplicate_place_id.into(),
+        similarity,
+        detected_at: Timestamp::now(),
+        state: DuplicateCandidateState::Open,
+        reviewed: None,
+    };
+    assert_eq!(
+        2,
+        db.replace_open_duplicate_candidates(&[candidate("a", "b", 0.8), candidate("c", "d", 0.9)])
+            .unwrap()
+    );
+    let open = db
+        .list_open_duplicate_candidates(&Pagination::default())
+        .unwrap();
+    assert_eq!(2, open.len());
+    assert_eq!(Id::from("c"), open[0].place_id);
+
+    let activity = Activity::now(Some("scout@example.com".parse().unwrap()));
+    assert_eq!(
+        1,
+        db.review_duplicate_candidate(
+            &"a".into(),
+            &"b".into(),
+            DuplicateCandidateState::Dismissed,
+            &activity
+        )
+        .unwrap()
+    );
+    assert_eq!(
+        0,
+        db.review_duplicate_candidate(
+            &"a".into(),
+            &"b".into(),
+            DuplicateCandidateState::Merged,
+            &activity
+        )
+        .unwrap()
+    );
+
+    // Dismissed candidates are not reopened and open candidates
+    // that are not detected again are removed
+    assert_eq!(
+        1,
+        db.replace_open_duplicate_candidates(&[candidate("a", "b", 0.8), candidate("e", "f", 0.7)])
+            .unwrap()
+    );
+    assert_eq!(1, db.count_open_duplicate_candidates().unwrap());
+    let open = db
+        .list_open_duplicate_candidates(&Pagination {
+            offset: Some(0),
+            limit: Some(10),
+        })
+        .unwrap();
+    assert_eq!(Id::from("e"), open[0].place_id);
+}
+
+#[test]
+fn load_archived_ratings_and_events() {
+    let db = TestDatabase::create();
+    let db = db.connections.exclusive().unwrap();
+    let place = Place::build().title("place").license("CC0-1.0").finish();
+    db.create_or_update_place(place.clone()).unwrap();
+    let rating = Rating {
+        id: Id::new(),
+        place_id: place.id.clone(),
+        created_at: Timestamp::now(),
+        archived_at: None,
+        title: "rating".into(),
+        value: RatingValue::new(1),
+        context: RatingContext::Fairness,
+        source: None,
+    };
+    db.create_rating(rating.clone()).unwrap();
+    db.create_comment(Comment {
+        id: Id::new(),
+        rating_id: rating.id.clone(),
+        created_at: Timestamp::now(),
+        archived_at: None,
+        text: "comment".into(),
+    })
+    .unwrap();
+    let activity = Activity::now(None);
+    db.archive_comments_of_ratings(&[rating.id.as_str()], &activity)
+        .unwrap();
+    db.archive_ratings(&[rating.id.as_str()], &activity)
+        .unwrap();
+    assert!(
+        db.load_ratings_of_place(place.id.as_str())
+            .unwrap()
+            .is_empty()
+    );
+    assert_eq!(
+        1,
+        db.load_all_ratings_of_place(place.id.as_str())
+            .unwrap()
+            .len()
+    );
+    assert!(
+        db.load_comments_of_rating(rating.id.as_str())
+            .unwrap()
+            .is_empty()
+    );
+    assert_eq!(
+        1,
+        db.load_all_comments_of_rating(rating.id.as_str())
+            .unwrap()
+            .len()
+    );
+
+    let event = new_event("event", &[]);
+    db.create_event(event.clone(), &activity_log()).unwrap();
+    db.archive_events(&[event.id.as_str()], Timestamp::now())
+        .unwrap();
+    assert!(db.all_events_chronologically().unwrap().is_empty());
+    let archived_events = db.all_archived_events_chronologically().unwrap();
+    assert_eq!(1, archived_events.len());
+    assert_eq!(event.id, archived_events[0].id);
+}
+
+#[test]
+fn claim_webhook_delivery_only_once() {
+    let db = TestDatabase::create();
+    let mut db = db.connections.exclusive().unwrap();
+    let org = Organization {
+        id: Id::new(),
+        name: "org".into(),
+        api_token: "secret".into(),
+        moderated_tags: vec![],
+    };
+    db.create_org(org.clone()).unwrap();
+    let webhook = Webhook {
+        id: Id::new(),
+        org_id: org.id,
+        url: "https://example.com/hook".parse().unwrap(),
+        secret: Webhook::generate_secret(),
+        created_at: Timestamp::now(),
+    };
+    db.create_webhook(&webhook).unwrap();
+    let now = Timestamp::now();
+    let delivery = WebhookDelivery {
+        id: Id::new(),
+        webhook_id: webhook.id,
+        event_type: WebhookEventType::PlaceAdded,
+        payload: "{}".into(),
+        created_at: now,
+        status: WebhookDeliveryStatus::Pending,
+        attempts: 0,
+        next_attempt_at: Some(now),
+        last_attempt_at: None,
+        last_response_status: None,
+        last_error: None,
+    };
+    db.create_webhook_delivery(&delivery).unwrap();
+
+    let claimed_until = now + time::Duration::minutes(5);
+    assert!(db.claim_webhook_delivery(&delivery, claimed_until).unwrap());
+    assert!(!db.claim_webhook_delivery(&delivery, claimed_until).unwrap());
+    assert!(db.load_due_webhook_deliveries(now, 10).unwrap().is_empty());
+    let due = db.load_due_webhook_deliveries(claimed_until, 10).unwrap();
+    assert_eq!(1, due.len());
+    assert_eq!(
+        Some(claimed_until.as_millis()),
+        due[0].next_attempt_at.map(Timestamp::as_millis)
+    );
+}
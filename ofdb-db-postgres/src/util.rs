@@ -0,0 +1,659 @@
+use time::{Date, format_description::FormatItem, macros::format_description};
+
+use super::models::*;
+use ofdb_core::{
+    entities as e, repositories as repo,
+    usecases::Error as ParameterError,
+    util::{
+        geo::{MapBbox, MapPoint},
+        nonce::Nonce,
+        time::Timestamp,
+    },
+};
+
+pub(crate) fn load_url(url: String) -> Option<e::Url> {
+    match url.parse() {
+        Ok(url) => Some(url),
+        Err(err) => {
+            // The database should only contain valid URLs
+            log::error!("Failed to load URL '{}' from database: {}", url, err);
+            None
+        }
+    }
+}
+
+pub(crate) fn load_translation(
+    lang: String,
+    title: String,
+    description: Option<String>,
+) -> Option<e::Translation> {
+    match lang.parse() {
+        Ok(lang) => Some(e::Translation {
+            lang,
+            title,
+            description,
+        }),
+        Err(err) => {
+            // The database should only contain valid language tags
+            log::error!(
+                "Failed to load translation '{}' from database: {}",
+                lang,
+                err
+            );
+            None
+        }
+    }
+}
+
+pub(crate) fn load_recurrence(
+    rrule: Option<String>,
+    exdates: Option<String>,
+) -> Option<e::recurrence::Recurrence> {
+    let rrule = rrule?;
+    let rule = match rrule.parse() {
+        Ok(rule) => rule,
+        Err(err) => {
+            // The database should only contain valid rules
+            log::error!(
+                "Failed to load recurrence rule '{}' from database: {}",
+                rrule,
+                err
+            );
+            return None;
+        }
+    };
+    let exdates = exdates
+        .as_deref()
+        .unwrap_or_default()
+        .split(',')
+        .filter(|s| !s.is_empty())
+        .filter_map(|s| {
+            s.parse()
+                .ok()
+                .and_then(|secs| Timestamp::try_from_secs(secs).ok())
+                .or_else(|| {
+                    log::error!("Failed to load excluded date '{}' from database", s);
+                    None
+                })
+        })
+        .collect();
+    Some(e::recurrence::Recurrence { rule, exdates })
+}
+
+pub(crate) fn recurrence_into_rrule_and_exdates(
+    recurrence: Option<e::recurrence::Recurrence>,
+) -> (Option<String>, Option<String>) {
+    let Some(e::recurrence::Recurrence { rule, exdates }) = recurrence else {
+        return (None, None);
+    };
+    let exdates = (!exdates.is_empty()).then(|| {
+        exdates
+            .iter()
+            .map(|ts| ts.as_secs().to_string())
+            .collect::<Vec<_>>()
+            .join(",")
+    });
+    (Some(rule.to_string()), exdates)
+}
+
+pub(crate) fn registration_type_from_i16(i: i16) -> e::RegistrationType {
+    use ofdb_core::entities::RegistrationType::*;
+    match i {
+        1 => Email,
+        2 => Phone,
+        3 => Homepage,
+        _ => {
+            log::error!(
+                "Conversion Error:
+                       Invalid registration type:
+                       {} should be one of 1,2,3;
+                       Use 'Phone' instead.",
+                i
+            );
+            Phone
+        }
+    }
+}
+
+pub(crate) fn registration_type_into_i16(x: e::RegistrationType) -> i16 {
+    use ofdb_core::entities::RegistrationType::*;
+    match x {
+        Email => 1,
+        Phone => 2,
+        Homepage => 3,
+    }
+}
+
+const DATE_FORMAT: &[FormatItem] = format_description!("[year]-[month]-[day]");
+
+pub(crate) fn parse_date(date_string: String) -> std::result::Result<time::Date, repo::Error> {
+    let date = Date::parse(&date_string, &DATE_FORMAT).map_err(anyhow::Error::from)?;
+    Ok(date)
+}
+
+pub(crate) fn to_date_string(date: time::Date) -> String {
+    date.format(&DATE_FORMAT).unwrap()
+}
+
+pub(crate) fn event_from_event_entity_and_tags(
+    e: EventEntity,
+    tag_rels: &[EventTag],
+    translation_rels: &[(i64, e::Translation)],
+) -> e::Event {
+    let tags = tag_rels
+        .iter()
+        .filter(|r| r.event_id == e.id)
+        .map(|r| &r.tag)
+        .cloned()
+        .collect();
+    let translations = translation_rels
+        .iter()
+        .filter(|(event_id, _)| *event_id == e.id)
+        .map(|(_, translation)| translation)
+        .cloned()
+        .collect();
+    event_from_event_entity(e, tags, translations)
+}
+
+pub(crate) fn event_from_event_entity(
+    e: EventEntity,
+    tags: Vec<String>,
+    translations: Vec<e::Translation>,
+) -> e::Event {
+    let EventEntity {
+        uid,
+        title,
+        description,
+        start,
+        end,
+        lat,
+        lng,
+        street,
+        zip,
+        city,
+        country,
+        state,
+        email,
+        telephone,
+        homepage,
+        registration,
+        organizer,
+        archived,
+        image_url,
+        image_link_url,
+        rrule,
+        exdates,
+        current_rev,
+        created_by_email,
+        ..
+    } = e;
+    let address = if street.is_some()
+        || zip.is_some()
+        || city.is_some()
+        || country.is_some()
+        || state.is_some()
+    {
+        Some(e::Address {
+            street,
+            zip,
+            city,
+            country,
+            state,
+        })
+    } else {
+        None
+    };
+    let pos = if let (Some(lat), Some(lng)) = (lat, lng) {
+        MapPoint::try_from_lat_lng_deg(lat, lng)
+            .map(Some)
+            .unwrap_or_default()
+    } else {
+        None
+    };
+    let location = if address.is_some() || lat.is_some() || lng.is_some() {
+        Some(e::Location {
+            pos: pos.unwrap_or_default(),
+            address,
+        })
+    } else {
+        None
+    };
+    let contact = if email.is_some() || telephone.is_some() {
+        Some(e::Contact {
+            name: organizer,
+            email: email.map(e::EmailAddress::new_unchecked),
+            phone: telephone,
+        })
+    } else {
+        None
+    };
+
+    let registration = registration.map(registration_type_from_i16);
+    let created_by = created_by_email.map(e::EmailAddress::new_unchecked);
+
+    e::Event {
+        id: uid.into(),
+        revision: e::Revision::from(current_rev as u64),
+        title,
+        description,
+        start: Timestamp::try_from_secs(start).unwrap(),
+        end: end.map(Timestamp::try_from_secs).transpose().unwrap(),
+        location,
+        contact,
+        homepage: homepage.and_then(load_url),
+        tags,
+        created_by,
+        registration,
+        archived: archived.map(Timestamp::try_from_secs).transpose().unwrap(),
+        image_url: image_url.and_then(load_url),
+        image_link_url: image_link_url.and_then(load_url),
+        recurrence: load_recurrence(rrule, exdates),
+        translations,
+    }
+}
+
+impl From<Tag> for e::Tag {
+    fn from(t: Tag) -> e::Tag {
+        e::Tag { id: t.id }
+    }
+}
+
+impl From<e::Tag> for Tag {
+    fn from(t: e::Tag) -> Tag {
+        Tag { id: t.id }
+    }
+}
+
+impl<'a> From<&'a e::User> for NewUser<'a> {
+    fn from(u: &'a e::User) -> NewUser<'a> {
+        use num_traits::ToPrimitive;
+        Self {
+            email: u.email.as_str(),
+            email_confirmed: u.email_confirmed,
+            password: u.password.to_string(),
+            role: u.role.to_i16().unwrap_or_else(|| {
+                log::warn!("Could not convert role {:?} to i16. Use 0 instead.", u.role);
+                0
+            }),
+        }
+    }
+}
+
+impl From<UserEntity> for e::User {
+    fn from(u: UserEntity) -> e::User {
+        use num_traits::FromPrimitive;
+        let UserEntity {
+            email,
+            email_confirmed,
+            password,
+            role,
+            ..
+        } = u;
+        Self {
+            email: e::EmailAddress::new_unchecked(email),
+            email_confirmed,
+            password: password.into(),
+            role: e::Role::from_i16(role).unwrap_or_else(|| {
+                log::warn!(
+                    "Could not cast role from i16 (value: {}). Use {:?} instead.",
+                    role,
+                    e::Role::default()
+                );
+                e::Role::default()
+            }),
+        }
+    }
+}
+
+impl From<PlaceRatingComment> for e::Comment {
+    fn from(c: PlaceRatingComment) -> Self {
+        let PlaceRatingComment {
+            id,
+            rating_id,
+            created_at,
+            archived_at,
+            text,
+            ..
+        } = c;
+        Self {
+            id: id.into(),
+            rating_id: rating_id.into(),
+            created_at: Timestamp::try_from_millis(created_at).unwrap(),
+            archived_at: archived_at
+                .map(Timestamp::try_from_millis)
+                .transpose()
+                .unwrap(),
+            text,
+        }
+    }
+}
+
+impl From<PlaceRating> for e::Rating {
+    fn from(r: PlaceRating) -> Self {
+        let PlaceRating {
+            id,
+            place_id,
+            created_at,
+            archived_at,
+            title,
+            context,
+            value,
+            source,
+            ..
+        } = r;
+        Self {
+            id: id.into(),
+            place_id: place_id.into(),
+            created_at: Timestamp::try_from_millis(created_at).unwrap(),
+            archived_at: archived_at
+                .map(Timestamp::try_from_millis)
+                .transpose()
+                .unwrap(),
+            title,
+            value: (value as i8).into(),
+            context: rating_context_from_str(&context).unwrap(),
+            source,
+        }
+    }
+}
+
+impl From<BboxSubscriptionEntity> for e::BboxSubscription {
+    fn from(from: BboxSubscriptionEntity) -> Self {
+        let BboxSubscriptionEntity {
+            uid,
+            user_email,
+            south_west_lat,
+            south_west_lng,
+            north_east_lat,
+            north_east_lng,
+            ..
+        } = from;
+        let south_west =
+            MapPoint::try_from_lat_lng_deg(south_west_lat, south_west_lng).unwrap_or_default();
+        let north_east =
+            MapPoint::try_from_lat_lng_deg(north_east_lat, north_east_lng).unwrap_or_default();
+        let bbox = MapBbox::new(south_west, north_east);
+        let user_email = e::EmailAddress::new_unchecked(user_email);
+        Self {
+            id: uid.into(),
+            user_email,
+            bbox,
+        }
+    }
+}
+
+impl From<UserTokenEntity> for e::UserToken {
+    fn from(from: UserTokenEntity) -> Self {
+        Self {
+            email_nonce: e::EmailNonce {
+                email: e::EmailAddress::new_unchecked(from.user_email),
+                nonce: from.nonce.parse::<Nonce>().unwrap_or_default(),
+            },
+            expires_at: Timestamp::try_from_millis(from.expires_at).unwrap(),
+        }
+    }
+}
+
+impl From<ReviewTokenEntity> for e::ReviewToken {
+    fn from(from: ReviewTokenEntity) -> Self {
+        let place_revision = e::Revision::from(from.place_revision as u64);
+        let place_id = e::Id::from(from.place_id);
+        let nonce = from.nonce.parse::<Nonce>().unwrap_or_default();
+        let expires_at = Timestamp::try_from_millis(from.expires_at).unwrap();
+        let review_nonce = e::ReviewNonce {
+            place_id,
+            place_revision,
+            nonce,
+        };
+        Self {
+            review_nonce,
+            expires_at,
+        }
+    }
+}
+
+pub(crate) fn rating_context_to_string(context: e::RatingContext) -> String {
+    match context {
+        e::RatingContext::Diversity => "diversity",
+        e::RatingContext::Renewable => "renewable",
+        e::RatingContext::Fairness => "fairness",
+        e::RatingContext::Humanity => "humanity",
+        e::RatingContext::Transparency => "transparency",
+        e::RatingContext::Solidarity => "solidarity",
+    }
+    .into()
+}
+
+fn rating_context_from_str(context: &str) -> Result<e::RatingContext, ParameterError> {
+    Ok(match context {
+        "diversity" => e::RatingContext::Diversity,
+        "renewable" => e::RatingContext::Renewable,
+        "fairness" => e::RatingContext::Fairness,
+        "humanity" => e::RatingContext::Humanity,
+        "transparency" => e::RatingContext::Transparency,
+        "solidarity" => e::RatingContext::Solidarity,
+        _ => {
+            return Err(ParameterError::RatingContext(context.into()));
+        }
+    })
+}
+
+impl From<e::Organization> for NewOrganization {
+    fn from(o: e::Organization) -> Self {
+        let e::Organization {
+            id,
+            name,
+            api_token,
+            moderated_tags: _,
+        } = o;
+        NewOrganization {
+            id: id.into(),
+            name,
+            api_token,
+        }
+    }
+}
+
+impl From<OrganizationTag> for e::ModeratedTag {
+    fn from(from: OrganizationTag) -> Self {
+        let OrganizationTag {
+            org_rowid: _,
+            tag_label,
+            tag_allow_add,
+            tag_allow_remove,
+            require_clearance,
+        } = from;
+        let label = tag_label;
+        let allow_add = tag_allow_add != 0;
+        let allow_remove = tag_allow_remove != 0;
+        let require_clearance = require_clearance != 0;
+        Self {
+            label,
+            allow_add,
+            allow_remove,
+            require_clearance,
+        }
+    }
+}
+
+impl From<OrganizationTagWithId> for (e::Id, e::ModeratedTag) {
+    fn from(from: OrganizationTagWithId) -> Self {
+        let OrganizationTagWithId {
+            org_id,
+            tag_label,
+            tag_allow_add,
+            tag_allow_remove,
+            require_clearance,
+        } = from;
+        let label = tag_label;
+        let allow_add = tag_allow_add != 0;
+        let allow_remove = tag_allow_remove != 0;
+        let require_clearance = require_clearance != 0;
+        (
+            org_id.into(),
+            e::ModeratedTag {
+                label,
+                allow_add,
+                allow_remove,
+                require_clearance,
+            },
+        )
+    }
+}
+
+pub struct ChangeSet<T> {
+    pub added: Vec<T>,
+    pub deleted: Vec<T>,
+}
+
+pub fn tags_diff(old: &[String], new: &[String]) -> ChangeSet<String> {
+    let mut added = vec![];
+    let mut deleted = vec![];
+
+    for t in new {
+        if !old.iter().any(|x| x == t) {
+            added.push(t.to_owned());
+        }
+    }
+
+    for t in old {
+        if !new.iter().any(|x| x == t) {
+            deleted.push(t.to_owned());
+        }
+    }
+
+    ChangeSet { added, deleted }
+}
+
+impl From<PendingClearanceForPlace> for e::PendingClearanceForPlace {
+    fn from(from: PendingClearanceForPlace) -> Self {
+        let PendingClearanceForPlace {
+            place_id,
+            created_at,
+            last_cleared_revision,
+        } = from;
+        let last_cleared_revision = last_cleared_revision.map(|rev| e::Revision::from(rev as u64));
+        Self {
+            place_id: place_id.into(),
+            created_at: e::Timestamp::try_from_millis(created_at).unwrap(),
+            last_cleared_revision,
+        }
+    }
+}
+
+impl From<PendingClearanceForEvent> for e::PendingClearanceForEvent {
+    fn from(from: PendingClearanceForEvent) -> Self {
+        let PendingClearanceForEvent {
+            event_id,
+            created_at,
+            last_cleared_revision,
+        } = from;
+        let last_cleared_revision = last_cleared_revision.map(|rev| e::Revision::from(rev as u64));
+        Self {
+            event_id: event_id.into(),
+            created_at: e::Timestamp::try_from_millis(created_at).unwrap(),
+            last_cleared_revision,
+        }
+    }
+}
+
+fn load_clearance_log(from: ClearanceLog) -> (String, e::Revision, e::ActivityLog) {
+    let ClearanceLog {
+        item_id,
+        cleared_revision,
+        created_at,
+        created_by,
+        context,
+        comment,
+    } = from;
+    let activity = e::ActivityLog {
+        activity: e::Activity {
+            at: e::Timestamp::try_from_millis(created_at).unwrap(),
+            by: created_by.map(e::EmailAddress::new_unchecked),
+        },
+        context,
+        comment,
+    };
+    (
+        item_id,
+        e::Revision::from(cleared_revision as u64),
+        activity,
+    )
+}
+
+impl From<ClearanceLog> for e::ClearanceLogForPlace {
+    fn from(from: ClearanceLog) -> Self {
+        let (place_id, cleared_revision, activity) = load_clearance_log(from);
+        Self {
+            place_id: place_id.into(),
+            cleared_revision,
+            activity,
+        }
+    }
+}
+
+impl From<ClearanceLog> for e::ClearanceLogForEvent {
+    fn from(from: ClearanceLog) -> Self {
+        let (event_id, cleared_revision, activity) = load_clearance_log(from);
+        Self {
+            event_id: event_id.into(),
+            cleared_revision,
+            activity,
+        }
+    }
+}
+
+impl From<ApiToken> for e::ApiToken {
+    fn from(from: ApiToken) -> Self {
+        let ApiToken {
+            id,
+            org_id,
+            name,
+            token_hash,
+            scopes,
+            created_at,
+            expires_at,
+            last_used_at,
+            revoked_at,
+        } = from;
+        let scopes = scopes
+            .split_whitespace()
+            .filter_map(|scope| {
+                scope
+                    .parse()
+                    .map_err(|_| log::warn!("Ignoring unknown scope '{scope}' of API token {id}"))
+                    .ok()
+            })
+            .collect();
+        Self {
+            id: id.into(),
+            org_id: org_id.into(),
+            name,
+            token_hash,
+            scopes,
+            created_at: Timestamp::try_from_millis(created_at).unwrap(),
+            expires_at: expires_at.map(|at| Timestamp::try_from_millis(at).unwrap()),
+            last_used_at: last_used_at.map(|at| Timestamp::try_from_millis(at).unwrap()),
+            revoked_at: revoked_at.map(|at| Timestamp::try_from_millis(at).unwrap()),
+        }
+    }
+}
+
+#[test]
+fn test_tag_diff() {
+    let x = tags_diff(&[], &["b".into()]);
+    assert_eq!(x.added, vec!["b"]);
+    assert!(x.deleted.is_empty());
+
+    let x = tags_diff(&["a".into()], &[]);
+    assert!(x.added.is_empty());
+    assert_eq!(x.deleted, vec!["a"]);
+
+    let x = tags_diff(&["a".into()], &["b".into()]);
+    assert_eq!(x.added, vec!["b"]);
+    assert_eq!(x.deleted, vec!["a"]);
+
+    let x = tags_diff(&["a".into(), "b".into()], &["b".into()]);
+    assert!(x.added.is_empty());
+    assert_eq!(x.deleted, vec!["a"]);
+}
@@ -0,0 +1,86 @@
+-- Latest revision (mutable) from event_revision
+ALTER TABLE events ADD COLUMN current_rev INTEGER NOT NULL DEFAULT 0;
+
+-- Different revisions of an event
+CREATE TABLE event_revision (
+    rowid          INTEGER PRIMARY KEY,
+    parent_rowid   INTEGER NOT NULL,
+    --
+    rev            INTEGER NOT NULL,
+    created_at     INTEGER NOT NULL,
+    created_by     INTEGER,
+    context        TEXT,
+    comment        TEXT,
+    --
+    title          TEXT NOT NULL,
+    description    TEXT,
+    start          INTEGER NOT NULL,
+    end            INTEGER,
+    lat            FLOAT,
+    lng            FLOAT,
+    street         TEXT,
+    zip            TEXT,
+    city           TEXT,
+    country        TEXT,
+    state          TEXT,
+    email          TEXT,
+    telephone      TEXT,
+    homepage       TEXT,
+    registration   SMALLINT,
+    organizer      TEXT,
+    image_url      TEXT,
+    image_link_url TEXT,
+    rrule          TEXT,
+    exdates        TEXT,
+    --
+    UNIQUE (parent_rowid, rev),
+    FOREIGN KEY (parent_rowid) REFERENCES events(id),
+    FOREIGN KEY (created_by) REFERENCES users(id)
+);
+
+CREATE TABLE event_revision_tag (
+    parent_rowid INTEGER NOT NULL,
+    tag          TEXT NOT NULL,
+    --
+    PRIMARY KEY (parent_rowid, tag),
+    FOREIGN KEY (parent_rowid) REFERENCES event_revision(rowid)
+);
+
+-- The history of all existing events starts with their current state
+INSERT INTO event_revision SELECT
+NULL, -- rowid
+id, -- parent_rowid
+0, -- rev
+CAST(strftime('%s', 'now') AS INTEGER) * 1000, -- created_at (milliseconds)
+created_by,
+NULL, -- context
+NULL, -- comment
+title,
+description,
+start,
+end,
+lat,
+lng,
+street,
+zip,
+city,
+country,
+state,
+email,
+telephone,
+homepage,
+registration,
+organizer,
+image_url,
+image_link_url,
+rrule,
+exdates
+FROM events;
+
+INSERT INTO event_revision_tag SELECT
+event_revision.rowid,
+event_tags.tag
+FROM event_tags
+JOIN event_revision ON event_revision.parent_rowid=event_tags.event_id;
+
+CREATE INDEX event_revision_idx_parent_rowid ON event_revision (parent_rowid);
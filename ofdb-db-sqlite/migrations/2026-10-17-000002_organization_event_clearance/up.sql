@@ -0,0 +1,14 @@
+-- Pending authorization/approval of events by organizations
+CREATE TABLE organization_event_clearance (
+    rowid        INTEGER PRIMARY KEY,
+    --
+    org_rowid    INTEGER NOT NULL,
+    event_rowid  INTEGER NOT NULL,
+    --
+    created_at            INTEGER NOT NULL,
+    last_cleared_revision INTEGER, -- last cleared revision number or NULL if the event is new and has not been cleared yet
+    --
+    UNIQUE (org_rowid, event_rowid),
+    FOREIGN KEY (org_rowid) REFERENCES organization(rowid),
+    FOREIGN KEY (event_rowid) REFERENCES events(id)
+);
@@ -0,0 +1,22 @@
+-- Named, scoped and revocable API tokens of organizations
+CREATE TABLE organization_api_token (
+    rowid        INTEGER PRIMARY KEY,
+    id           TEXT NOT NULL,
+    --
+    org_rowid    INTEGER NOT NULL,
+    --
+    name         TEXT NOT NULL,
+    token_hash   TEXT NOT NULL, -- hex encoded SHA-256 hash of the secret token
+    scopes       TEXT NOT NULL, -- space separated list of scopes
+    --
+    created_at   INTEGER NOT NULL,
+    expires_at   INTEGER,
+    last_used_at INTEGER,
+    revoked_at   INTEGER,
+    --
+    UNIQUE (id),
+    UNIQUE (token_hash),
+    FOREIGN KEY (org_rowid) REFERENCES organization(rowid)
+);
+
+CREATE INDEX organization_api_token_idx_org_rowid ON organization_api_token(org_rowid);
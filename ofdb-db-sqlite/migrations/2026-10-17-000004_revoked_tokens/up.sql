@@ -0,0 +1,8 @@
+-- Revoked authentication tokens or sessions that must
+-- be rejected until they expire
+CREATE TABLE revoked_tokens (
+    id         TEXT PRIMARY KEY NOT NULL,
+    expires_at INTEGER NOT NULL
+);
+
+CREATE INDEX revoked_tokens_idx_expires_at ON revoked_tokens(expires_at);
@@ -0,0 +1,40 @@
+-- URLs of organizations that receive signed event payloads
+CREATE TABLE organization_webhook (
+    rowid      INTEGER PRIMARY KEY,
+    id         TEXT NOT NULL,
+    --
+    org_rowid  INTEGER NOT NULL,
+    --
+    url        TEXT NOT NULL,
+    secret     TEXT NOT NULL, -- shared secret for signing the payloads
+    created_at INTEGER NOT NULL,
+    --
+    UNIQUE (id),
+    FOREIGN KEY (org_rowid) REFERENCES organization(rowid)
+);
+
+CREATE INDEX organization_webhook_idx_org_rowid ON organization_webhook(org_rowid);
+
+-- Deliveries of payloads to webhooks including retries
+CREATE TABLE webhook_delivery (
+    rowid                INTEGER PRIMARY KEY,
+    id                   TEXT NOT NULL,
+    --
+    webhook_rowid        INTEGER NOT NULL,
+    --
+    event_type           TEXT NOT NULL,
+    payload              TEXT NOT NULL, -- JSON
+    created_at           INTEGER NOT NULL,
+    status               SMALLINT NOT NULL, -- 0 = pending, 1 = delivered, 2 = failed
+    attempts             INTEGER NOT NULL,
+    next_attempt_at      INTEGER, -- only set for pending deliveries
+    last_attempt_at      INTEGER,
+    last_response_status INTEGER,
+    last_error           TEXT,
+    --
+    UNIQUE (id),
+    FOREIGN KEY (webhook_rowid) REFERENCES organization_webhook(rowid)
+);
+
+CREATE INDEX webhook_delivery_idx_webhook_rowid ON webhook_delivery(webhook_rowid);
+CREATE INDEX webhook_delivery_idx_next_attempt_at ON webhook_delivery(next_attempt_at);
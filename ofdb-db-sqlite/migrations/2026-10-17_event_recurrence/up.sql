@@ -0,0 +1,4 @@
+-- Add recurrence rules (RRULE) and excluded dates (EXDATE) to events
+ALTER TABLE events ADD COLUMN rrule TEXT;
+-- Comma-separated list of unix timestamps in seconds
+ALTER TABLE events ADD COLUMN exdates TEXT;
@@ -0,0 +1,18 @@
+-- Pairs of possibly duplicate places that are detected periodically
+--
+-- Each pair is stored only once with the lower place id first.
+CREATE TABLE place_duplicate_candidate (
+    rowid              INTEGER PRIMARY KEY,
+    --
+    place_id           TEXT NOT NULL,
+    duplicate_place_id TEXT NOT NULL,
+    similarity         REAL NOT NULL,
+    detected_at        INTEGER NOT NULL,
+    state              TEXT NOT NULL, -- open, dismissed, or merged
+    reviewed_at        INTEGER,
+    reviewed_by        TEXT, -- e-mail address
+    --
+    UNIQUE (place_id, duplicate_place_id)
+);
+
+CREATE INDEX place_duplicate_candidate_idx_state ON place_duplicate_candidate(state);
@@ -0,0 +1,135 @@
+-- The feed of all changes in the order in which they have been committed
+--
+-- The rows are written by triggers within the same transaction as
+-- the change itself. Clients resume the feed after the last rowid
+-- they have seen, i.e. the rowid must increase monotonically and
+-- must never be reused.
+CREATE TABLE change_log (
+    rowid       INTEGER PRIMARY KEY AUTOINCREMENT,
+    --
+    changed_at  INTEGER NOT NULL, -- unix time in milliseconds
+    kind        TEXT NOT NULL, -- created, updated, reviewed, archived, rejected, or deleted
+    item_type   TEXT NOT NULL, -- place, event, or rating
+    item_id     TEXT NOT NULL,
+    item_rev    INTEGER, -- places and created/updated events
+    item_status SMALLINT, -- review status of places
+    place_id    TEXT -- place of ratings
+);
+
+-- Existing changes in chronological order
+INSERT INTO change_log (changed_at, kind, item_type, item_id, item_rev, item_status, place_id)
+SELECT changed_at, kind, item_type, item_id, item_rev, item_status, place_id FROM (
+    SELECT
+        review.created_at AS changed_at,
+        0 AS source,
+        review.rowid AS source_rowid,
+        CASE
+            WHEN review.rev > 0 THEN
+                CASE review.status WHEN 0 THEN 'archived' WHEN -1 THEN 'rejected' ELSE 'reviewed' END
+            WHEN rev.rev > 0 THEN 'updated'
+            ELSE 'created'
+        END AS kind,
+        'place' AS item_type,
+        place.id AS item_id,
+        rev.rev AS item_rev,
+        review.status AS item_status,
+        NULL AS place_id
+    FROM place_revision_review review
+    JOIN place_revision rev ON rev.rowid = review.parent_rowid
+    JOIN place ON place.rowid = rev.parent_rowid
+    UNION ALL
+    SELECT
+        rev.created_at, 1, rev.rowid,
+        CASE WHEN rev.rev > 0 THEN 'updated' ELSE 'created' END,
+        'event', events.uid, rev.rev, NULL, NULL
+    FROM event_revision rev
+    JOIN events ON events.id = rev.parent_rowid
+    UNION ALL
+    SELECT
+        events.archived * 1000, 2, events.id,
+        'archived', 'event', events.uid, NULL, NULL, NULL
+    FROM events
+    WHERE events.archived IS NOT NULL
+    UNION ALL
+    SELECT
+        rating.created_at, 3, rating.rowid,
+        'created', 'rating', rating.id, NULL, NULL, place.id
+    FROM place_rating rating
+    JOIN place ON place.rowid = rating.parent_rowid
+    UNION ALL
+    SELECT
+        rating.archived_at, 4, rating.rowid,
+        'archived', 'rating', rating.id, NULL, NULL, place.id
+    FROM place_rating rating
+    JOIN place ON place.rowid = rating.parent_rowid
+    WHERE rating.archived_at IS NOT NULL
+)
+ORDER BY changed_at, source, source_rowid;
+
+CREATE TRIGGER change_log_place_review AFTER INSERT ON place_revision_review
+BEGIN
+    INSERT INTO change_log (changed_at, kind, item_type, item_id, item_rev, item_status)
+    SELECT
+        NEW.created_at,
+        CASE
+            WHEN NEW.rev > 0 THEN
+                CASE NEW.status WHEN 0 THEN 'archived' WHEN -1 THEN 'rejected' ELSE 'reviewed' END
+            WHEN rev.rev > 0 THEN 'updated'
+            ELSE 'created'
+        END,
+        'place', place.id, rev.rev, NEW.status
+    FROM place_revision rev
+    JOIN place ON place.rowid = rev.parent_rowid
+    WHERE rev.rowid = NEW.parent_rowid;
+END;
+
+CREATE TRIGGER change_log_event_revision AFTER INSERT ON event_revision
+BEGIN
+    INSERT INTO change_log (changed_at, kind, item_type, item_id, item_rev)
+    SELECT
+        NEW.created_at,
+        CASE WHEN NEW.rev > 0 THEN 'updated' ELSE 'created' END,
+        'event', uid, NEW.rev
+    FROM events
+    WHERE id = NEW.parent_rowid;
+END;
+
+CREATE TRIGGER change_log_event_archive AFTER UPDATE OF archived ON events
+WHEN OLD.archived IS NULL AND NEW.archived IS NOT NULL
+BEGIN
+    INSERT INTO change_log (changed_at, kind, item_type, item_id)
+    VALUES (NEW.archived * 1000, 'archived', 'event', NEW.uid);
+END;
+
+CREATE TRIGGER change_log_event_delete AFTER DELETE ON events
+BEGIN
+    INSERT INTO change_log (changed_at, kind, item_type, item_id)
+    VALUES (CAST((julianday('now') - 2440587.5) * 86400000 AS INTEGER), 'deleted', 'event', OLD.uid);
+END;
+
+CREATE TRIGGER change_log_rating_create AFTER INSERT ON place_rating
+BEGIN
+    INSERT INTO change_log (changed_at, kind, item_type, item_id, place_id)
+    SELECT NEW.created_at, 'created', 'rating', NEW.id, id
+    FROM place
+    WHERE rowid = NEW.parent_rowid;
+END;
+
+CREATE TRIGGER change_log_rating_archive AFTER UPDATE OF archived_at ON place_rating
+WHEN OLD.archived_at IS NULL AND NEW.archived_at IS NOT NULL
+BEGIN
+    INSERT INTO change_log (changed_at, kind, item_type, item_id, place_id)
+    SELECT NEW.archived_at, 'archived', 'rating', NEW.id, id
+    FROM place
+    WHERE rowid = NEW.parent_rowid;
+END;
+
+-- Ratings are moved when merging places
+CREATE TRIGGER change_log_rating_move AFTER UPDATE OF parent_rowid ON place_rating
+WHEN OLD.parent_rowid != NEW.parent_rowid
+BEGIN
+    INSERT INTO change_log (changed_at, kind, item_type, item_id, place_id)
+    SELECT CAST((julianday('now') - 2440587.5) * 86400000 AS INTEGER), 'updated', 'rating', NEW.id, id
+    FROM place
+    WHERE rowid = NEW.parent_rowid;
+END;
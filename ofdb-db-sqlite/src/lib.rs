@@ -19,9 +19,7 @@ mod util;
 
 const MIGRATIONS: EmbeddedMigrations = embed_migrations!("./migrations");
 
-type Connection = SqliteConnection;
-
-type ConnectionManager = r2d2::ConnectionManager<Connection>;
+type ConnectionManager = r2d2::ConnectionManager<SqliteConnection>;
 type ConnectionPool = r2d2::Pool<ConnectionManager>;
 type PooledConnection = r2d2::PooledConnection<ConnectionManager>;
 
@@ -76,7 +74,7 @@ impl<'a> DbReadWrite<'a> {
 
     pub fn transaction<T, F, E>(&mut self, f: F) -> Result<T, uc::Error>
     where
-        F: FnOnce(&DbConnection) -> Result<T, E>,
+        F: FnOnce(&mut DbConnection) -> Result<T, E>,
         E: Into<uc::Error>,
     {
         let mut usecase_error = None;
@@ -90,7 +88,7 @@ impl<'a> DbReadWrite<'a> {
                 // TODO: Diesel v2.0 requires a mutable borrow of the connection
                 // and will pass this mutable borrow into the transaction as
                 // an additional parameter.
-                f(&DbConnection::new(conn))
+                f(&mut DbConnection::new(conn))
                     .map_err(Into::into)
                     .map_err(|err| {
                         usecase_error = Some(err);
@@ -0,0 +1,178 @@
+use super::*;
+
+impl ApiTokenRepo for DbReadWrite<'_> {
+    fn create_api_token(&self, api_token: &ApiToken) -> Result<()> {
+        create_api_token(&mut self.conn.borrow_mut(), api_token)
+    }
+    fn get_api_token_by_hash(&self, token_hash: &str) -> Result<ApiToken> {
+        get_api_token_by_hash(&mut self.conn.borrow_mut(), token_hash)
+    }
+    fn list_api_tokens_of_org(&self, org_id: &Id) -> Result<Vec<ApiToken>> {
+        list_api_tokens_of_org(&mut self.conn.borrow_mut(), org_id)
+    }
+    fn revoke_api_token(&self, org_id: &Id, id: &Id, revoked_at: Timestamp) -> Result<()> {
+        revoke_api_token(&mut self.conn.borrow_mut(), org_id, id, revoked_at)
+    }
+    fn update_api_token_last_used(&self, id: &Id, last_used_at: Timestamp) -> Result<()> {
+        update_api_token_last_used(&mut self.conn.borrow_mut(), id, last_used_at)
+    }
+}
+
+impl ApiTokenRepo for DbConnection<'_> {
+    fn create_api_token(&self, api_token: &ApiToken) -> Result<()> {
+        create_api_token(&mut self.conn.borrow_mut(), api_token)
+    }
+    fn get_api_token_by_hash(&self, token_hash: &str) -> Result<ApiToken> {
+        get_api_token_by_hash(&mut self.conn.borrow_mut(), token_hash)
+    }
+    fn list_api_tokens_of_org(&self, org_id: &Id) -> Result<Vec<ApiToken>> {
+        list_api_tokens_of_org(&mut self.conn.borrow_mut(), org_id)
+    }
+    fn revoke_api_token(&self, org_id: &Id, id: &Id, revoked_at: Timestamp) -> Result<()> {
+        revoke_api_token(&mut self.conn.borrow_mut(), org_id, id, revoked_at)
+    }
+    fn update_api_token_last_used(&self, id: &Id, last_used_at: Timestamp) -> Result<()> {
+        update_api_token_last_used(&mut self.conn.borrow_mut(), id, last_used_at)
+    }
+}
+
+impl ApiTokenRepo for DbReadOnly<'_> {
+    fn create_api_token(&self, _api_token: &ApiToken) -> Result<()> {
+        unreachable!();
+    }
+    fn get_api_token_by_hash(&self, token_hash: &str) -> Result<ApiToken> {
+        get_api_token_by_hash(&mut self.conn.borrow_mut(), token_hash)
+    }
+    fn list_api_tokens_of_org(&self, org_id: &Id) -> Result<Vec<ApiToken>> {
+        list_api_tokens_of_org(&mut self.conn.borrow_mut(), org_id)
+    }
+    fn revoke_api_token(&self, _org_id: &Id, _id: &Id, _revoked_at: Timestamp) -> Result<()> {
+        unreachable!();
+    }
+    fn update_api_token_last_used(&self, _id: &Id, _last_used_at: Timestamp) -> Result<()> {
+        unreachable!();
+    }
+}
+
+fn create_api_token(conn: &mut SqliteConnection, api_token: &ApiToken) -> Result<()> {
+    let ApiToken {
+        id,
+        org_id,
+        name,
+        token_hash,
+        scopes,
+        created_at,
+        expires_at,
+        last_used_at: _,
+        revoked_at: _,
+    } = api_token;
+    let org_rowid = resolve_organization_rowid(conn, org_id)?;
+    let scopes = scopes
+        .iter()
+        .map(|scope| scope.as_str())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let new_api_token = models::NewApiToken {
+        id: id.as_str(),
+        org_rowid,
+        name,
+        token_hash,
+        scopes,
+        created_at: created_at.as_millis(),
+        expires_at: expires_at.map(Timestamp::as_millis),
+    };
+    let _count = diesel::insert_into(schema::organization_api_token::table)
+        .values(&new_api_token)
+        .execute(conn)
+        .map_err(from_diesel_err)?;
+    debug_assert_eq!(1, _count);
+    Ok(())
+}
+
+fn get_api_token_by_hash(conn: &mut SqliteConnection, token_hash: &str) -> Result<ApiToken> {
+    use schema::{organization::dsl as org_dsl, organization_api_token::dsl};
+    Ok(dsl::organization_api_token
+        .inner_join(org_dsl::organization)
+        .select((
+            dsl::id,
+            org_dsl::id,
+            dsl::name,
+            dsl::token_hash,
+            dsl::scopes,
+            dsl::created_at,
+            dsl::expires_at,
+            dsl::last_used_at,
+            dsl::revoked_at,
+        ))
+        .filter(dsl::token_hash.eq(token_hash))
+        .first::<models::ApiToken>(conn)
+        .map_err(from_diesel_err)?
+        .into())
+}
+
+fn list_api_tokens_of_org(conn: &mut SqliteConnection, org_id: &Id) -> Result<Vec<ApiToken>> {
+    use schema::{organization::dsl as org_dsl, organization_api_token::dsl};
+    Ok(dsl::organization_api_token
+        .inner_join(org_dsl::organization)
+        .select((
+            dsl::id,
+            org_dsl::id,
+            dsl::name,
+            dsl::token_hash,
+            dsl::scopes,
+            dsl::created_at,
+            dsl::expires_at,
+            dsl::last_used_at,
+            dsl::revoked_at,
+        ))
+        .filter(org_dsl::id.eq(org_id.as_str()))
+        .order_by(dsl::created_at)
+        .load::<models::ApiToken>(conn)
+        .map_err(from_diesel_err)?
+        .into_iter()
+        .map(Into::into)
+        .collect())
+}
+
+fn revoke_api_token(
+    conn: &mut SqliteConnection,
+    org_id: &Id,
+    id: &Id,
+    revoked_at: Timestamp,
+) -> Result<()> {
+    use schema::organization_api_token::dsl;
+    let org_rowid = resolve_organization_rowid(conn, org_id)?;
+    let target = dsl::organization_api_token
+        .filter(dsl::id.eq(id.as_str()))
+        .filter(dsl::org_rowid.eq(org_rowid));
+    let count = diesel::update(target.filter(dsl::revoked_at.is_null()))
+        .set(dsl::revoked_at.eq(revoked_at.as_millis()))
+        .execute(conn)
+        .map_err(from_diesel_err)?;
+    if count == 0 {
+        // Revoking a token that has already been revoked is a no-op
+        let exists = diesel::select(diesel::dsl::exists(target))
+            .get_result::<bool>(conn)
+            .map_err(from_diesel_err)?;
+        if !exists {
+            return Err(repo::Error::NotFound);
+        }
+    }
+    Ok(())
+}
+
+fn update_api_token_last_used(
+    conn: &mut SqliteConnection,
+    id: &Id,
+    last_used_at: Timestamp,
+) -> Result<()> {
+    use schema::organization_api_token::dsl;
+    let count = diesel::update(dsl::organization_api_token.filter(dsl::id.eq(id.as_str())))
+        .set(dsl::last_used_at.eq(last_used_at.as_millis()))
+        .execute(conn)
+        .map_err(from_diesel_err)?;
+    if count == 0 {
+        return Err(repo::Error::NotFound);
+    }
+    Ok(())
+}
@@ -13,6 +13,9 @@ impl CommentRepository for DbReadWrite<'_> {
     fn load_comments_of_rating(&self, rating_id: &str) -> Result<Vec<Comment>> {
         load_comments_of_rating(&mut self.conn.borrow_mut(), rating_id)
     }
+    fn load_all_comments_of_rating(&self, rating_id: &str) -> Result<Vec<Comment>> {
+        load_all_comments_of_rating(&mut self.conn.borrow_mut(), rating_id)
+    }
     fn archive_comments(&self, ids: &[&str], activity: &Activity) -> Result<usize> {
         archive_comments(&mut self.conn.borrow_mut(), ids, activity)
     }
@@ -41,6 +44,9 @@ impl CommentRepository for DbConnection<'_> {
     fn load_comments_of_rating(&self, rating_id: &str) -> Result<Vec<Comment>> {
         load_comments_of_rating(&mut self.conn.borrow_mut(), rating_id)
     }
+    fn load_all_comments_of_rating(&self, rating_id: &str) -> Result<Vec<Comment>> {
+        load_all_comments_of_rating(&mut self.conn.borrow_mut(), rating_id)
+    }
     fn archive_comments(&self, ids: &[&str], activity: &Activity) -> Result<usize> {
         archive_comments(&mut self.conn.borrow_mut(), ids, activity)
     }
@@ -69,6 +75,9 @@ impl CommentRepository for DbReadOnly<'_> {
     fn load_comments_of_rating(&self, rating_id: &str) -> Result<Vec<Comment>> {
         load_comments_of_rating(&mut self.conn.borrow_mut(), rating_id)
     }
+    fn load_all_comments_of_rating(&self, rating_id: &str) -> Result<Vec<Comment>> {
+        load_all_comments_of_rating(&mut self.conn.borrow_mut(), rating_id)
+    }
 
     fn archive_comments(&self, _ids: &[&str], _activity: &Activity) -> Result<usize> {
         unreachable!();
@@ -148,8 +157,23 @@ fn load_comment(conn: &mut SqliteConnection, id: &str) -> Result<Comment> {
 }
 
 fn load_comments_of_rating(conn: &mut SqliteConnection, rating_id: &str) -> Result<Vec<Comment>> {
+    query_comments_of_rating(conn, rating_id, false)
+}
+
+fn load_all_comments_of_rating(
+    conn: &mut SqliteConnection,
+    rating_id: &str,
+) -> Result<Vec<Comment>> {
+    query_comments_of_rating(conn, rating_id, true)
+}
+
+fn query_comments_of_rating(
+    conn: &mut SqliteConnection,
+    rating_id: &str,
+    include_archived: bool,
+) -> Result<Vec<Comment>> {
     use schema::{place_rating::dsl as rating_dsl, place_rating_comment::dsl as comment_dsl};
-    Ok(schema::place_rating_comment::table
+    let mut query = schema::place_rating_comment::table
         .inner_join(schema::place_rating::table)
         .select((
             comment_dsl::rowid,
@@ -162,7 +186,11 @@ fn load_comments_of_rating(conn: &mut SqliteConnection, rating_id: &str) -> Resu
             rating_dsl::id,
         ))
         .filter(rating_dsl::id.eq(rating_id))
-        .filter(comment_dsl::archived_at.is_null())
+        .into_boxed();
+    if !include_archived {
+        query = query.filter(comment_dsl::archived_at.is_null());
+    }
+    Ok(query
         .load::<models::PlaceRatingComment>(conn)
         .map_err(from_diesel_err)?
         .into_iter()
@@ -18,18 +18,26 @@ use ofdb_core::{
 
 use super::{util::load_url, *};
 
+mod api_token;
+mod change;
 mod comment;
+mod duplicate_candidate;
 mod event;
+mod event_clearance;
 mod org;
+mod org_member;
 mod place;
 mod place_clearance;
+mod place_redirect;
 mod rating;
 mod reminder;
 mod review_token;
+mod revoked_token;
 mod subscription;
 mod tag;
 mod user;
 mod user_token;
+mod webhook;
 
 type Result<T> = std::result::Result<T, repo::Error>;
 
@@ -114,6 +122,27 @@ fn load_place_revision_custom_links(
         .collect())
 }
 
+fn load_place_revision_translations(
+    conn: &mut SqliteConnection,
+    place_revision_rowid: i64,
+) -> Result<Vec<Translation>> {
+    use schema::place_revision_translation::dsl;
+    Ok(schema::place_revision_translation::table
+        .filter(dsl::parent_rowid.eq(&place_revision_rowid))
+        .load::<models::PlaceRevisionTranslation>(conn)
+        .map_err(from_diesel_err)?
+        .into_iter()
+        .filter_map(
+            |models::PlaceRevisionTranslation {
+                 parent_rowid: _,
+                 lang,
+                 title,
+                 description,
+             }| util::load_translation(lang, title, description),
+        )
+        .collect())
+}
+
 fn load_place(
     conn: &mut SqliteConnection,
     place: models::JoinedPlaceRevision,
@@ -161,6 +190,8 @@ fn load_place(
 
     let custom_links = load_place_revision_custom_links(conn, id)?;
 
+    let translations = load_place_revision_translations(conn, id)?;
+
     let created_by = created_by_id
         .map(|user_id| load_email_by_user_id(conn, user_id))
         .transpose()?
@@ -193,6 +224,7 @@ fn load_place(
         opening_hours: opening_hours.map(Into::into),
         founded_on,
         tags,
+        translations,
     };
 
     Ok((place, load_review_status(current_status)?))
@@ -249,6 +281,8 @@ fn load_place_with_status_review(
 
     let custom_links = load_place_revision_custom_links(conn, id)?;
 
+    let translations = load_place_revision_translations(conn, id)?;
+
     let created_by = created_by_id
         .map(|user_id| load_email_by_user_id(conn, user_id))
         .transpose()?
@@ -300,6 +334,7 @@ fn load_place_with_status_review(
         founded_on,
         links: Some(links),
         tags,
+        translations,
     };
 
     let activity_log = ActivityLog {
@@ -327,6 +362,28 @@ fn resolve_organization_rowid(conn: &mut SqliteConnection, id: &Id) -> Result<i6
         .map_err(from_diesel_err)
 }
 
+fn resolve_event_id_with_current_revision(
+    conn: &mut SqliteConnection,
+    uid: &str,
+) -> Result<(i64, Revision)> {
+    use schema::events::dsl;
+    dsl::events
+        .select((dsl::id, dsl::current_rev))
+        .filter(dsl::uid.eq(uid))
+        .first::<(i64, i64)>(conn)
+        .map_err(from_diesel_err)
+        .map(|(id, rev)| (id, Revision::from(rev as u64)))
+}
+
+fn resolve_event_id(conn: &mut SqliteConnection, uid: &str) -> Result<i64> {
+    use schema::events::dsl;
+    dsl::events
+        .select(dsl::id)
+        .filter(dsl::uid.eq(uid))
+        .first(conn)
+        .map_err(from_diesel_err)
+}
+
 fn resolve_place_rowid(conn: &mut SqliteConnection, id: &Id) -> Result<i64> {
     use schema::place::dsl;
     schema::place::table
@@ -412,6 +469,8 @@ fn into_new_place_revision(
         founded_on,
         tags,
         links,
+        // Stored separately, see `create_or_update_place()`
+        translations: _,
     } = place;
     let parent_rowid = if new_revision.is_initial() {
         // Create a new place
@@ -504,6 +563,7 @@ fn into_new_event_with_tags(
 ) -> Result<(models::NewEvent, Vec<String>)> {
     let Event {
         id,
+        revision,
         title,
         start,
         end,
@@ -517,6 +577,7 @@ fn into_new_event_with_tags(
         image_url,
         image_link_url,
         tags,
+        recurrence,
         ..
     } = event;
 
@@ -552,6 +613,7 @@ fn into_new_event_with_tags(
     };
 
     let registration = registration.map(util::registration_type_into_i16);
+    let (rrule, exdates) = util::recurrence_into_rrule_and_exdates(recurrence);
 
     let created_by = if let Some(ref email) = created_by {
         Some(resolve_user_created_by_email(conn, email)?)
@@ -582,6 +644,9 @@ fn into_new_event_with_tags(
             archived: archived.map(Timestamp::as_secs),
             image_url: image_url.map(Into::into),
             image_link_url: image_link_url.map(Into::into),
+            rrule,
+            exdates,
+            current_rev: u64::from(revision) as i64,
         },
         tags,
     ))
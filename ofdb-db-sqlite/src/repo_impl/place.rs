@@ -184,7 +184,8 @@ impl PlaceRepo for DbReadOnly<'_> {
     }
 }
 
-fn create_or_update_place(conn: &mut SqliteConnection, place: Place) -> Result<()> {
+fn create_or_update_place(conn: &mut SqliteConnection, mut place: Place) -> Result<()> {
+    let translations = std::mem::take(&mut place.translations);
     let (_place_id, new_place, tags, custom_links) = into_new_place_revision(conn, place)?;
     diesel::insert_into(schema::place_revision::table)
         .values(&new_place)
@@ -257,6 +258,27 @@ fn create_or_update_place(conn: &mut SqliteConnection, place: Place) -> Result<(
         .execute(conn)
         .map_err(from_diesel_err)?;
 
+    // Insert into place_revision_translation
+    let insertable_translations: Vec<_> = translations
+        .iter()
+        .map(
+            |Translation {
+                 lang,
+                 title,
+                 description,
+             }| models::NewPlaceRevisionTranslation {
+                parent_rowid,
+                lang: lang.as_str(),
+                title,
+                description: description.as_deref(),
+            },
+        )
+        .collect();
+    diesel::insert_into(schema::place_revision_translation::table)
+        .values(&insertable_translations)
+        .execute(conn)
+        .map_err(from_diesel_err)?;
+
     Ok(())
 }
 
@@ -29,12 +29,28 @@ impl PlaceClearanceRepo for DbReadWrite<'_> {
         &self,
         org_id: &Id,
         clearances: &[ClearanceForPlace],
+        activity: &ActivityLog,
     ) -> Result<usize> {
-        update_pending_clearances_for_places(&mut self.conn.borrow_mut(), org_id, clearances)
+        update_pending_clearances_for_places(
+            &mut self.conn.borrow_mut(),
+            org_id,
+            clearances,
+            activity,
+        )
     }
     fn cleanup_pending_clearances_for_places(&self, org_id: &Id) -> Result<u64> {
         cleanup_pending_clearances_for_places(&mut self.conn.borrow_mut(), org_id)
     }
+    fn list_clearance_log_for_places(
+        &self,
+        org_id: &Id,
+        pagination: &Pagination,
+    ) -> Result<Vec<ClearanceLogForPlace>> {
+        list_clearance_log_for_places(&mut self.conn.borrow_mut(), org_id, pagination)
+    }
+    fn add_clearance_log_for_places(&self, org_id: &Id, log: &ClearanceLogForPlace) -> Result<()> {
+        add_clearance_log_for_places(&mut self.conn.borrow_mut(), org_id, log)
+    }
 }
 
 impl PlaceClearanceRepo for DbConnection<'_> {
@@ -66,12 +82,28 @@ impl PlaceClearanceRepo for DbConnection<'_> {
         &self,
         org_id: &Id,
         clearances: &[ClearanceForPlace],
+        activity: &ActivityLog,
     ) -> Result<usize> {
-        update_pending_clearances_for_places(&mut self.conn.borrow_mut(), org_id, clearances)
+        update_pending_clearances_for_places(
+            &mut self.conn.borrow_mut(),
+            org_id,
+            clearances,
+            activity,
+        )
     }
     fn cleanup_pending_clearances_for_places(&self, org_id: &Id) -> Result<u64> {
         cleanup_pending_clearances_for_places(&mut self.conn.borrow_mut(), org_id)
     }
+    fn list_clearance_log_for_places(
+        &self,
+        org_id: &Id,
+        pagination: &Pagination,
+    ) -> Result<Vec<ClearanceLogForPlace>> {
+        list_clearance_log_for_places(&mut self.conn.borrow_mut(), org_id, pagination)
+    }
+    fn add_clearance_log_for_places(&self, org_id: &Id, log: &ClearanceLogForPlace) -> Result<()> {
+        add_clearance_log_for_places(&mut self.conn.borrow_mut(), org_id, log)
+    }
 }
 
 impl PlaceClearanceRepo for DbReadOnly<'_> {
@@ -103,12 +135,27 @@ impl PlaceClearanceRepo for DbReadOnly<'_> {
         &self,
         _org_id: &Id,
         _clearances: &[ClearanceForPlace],
+        _activity: &ActivityLog,
     ) -> Result<usize> {
         unreachable!();
     }
     fn cleanup_pending_clearances_for_places(&self, _org_id: &Id) -> Result<u64> {
         unreachable!();
     }
+    fn list_clearance_log_for_places(
+        &self,
+        org_id: &Id,
+        pagination: &Pagination,
+    ) -> Result<Vec<ClearanceLogForPlace>> {
+        list_clearance_log_for_places(&mut self.conn.borrow_mut(), org_id, pagination)
+    }
+    fn add_clearance_log_for_places(
+        &self,
+        _org_id: &Id,
+        _log: &ClearanceLogForPlace,
+    ) -> Result<()> {
+        unreachable!();
+    }
 }
 
 fn add_pending_clearance_for_places(
@@ -231,8 +278,14 @@ fn update_pending_clearances_for_places(
     conn: &mut SqliteConnection,
     org_id: &Id,
     clearances: &[ClearanceForPlace],
+    activity: &ActivityLog,
 ) -> Result<usize> {
     let org_rowid = resolve_organization_rowid(conn, org_id)?;
+    let ActivityLog {
+        activity: Activity { at, by },
+        context,
+        comment,
+    } = activity;
     let created_at = Timestamp::now().as_millis();
     let mut total_rows_affected = 0;
     for clearance in clearances {
@@ -270,6 +323,21 @@ fn update_pending_clearances_for_places(
             .execute(conn)
             .map_err(from_diesel_err)?;
         debug_assert!(rows_affected <= 1);
+        if rows_affected > 0 {
+            let insertable = models::NewPlaceClearanceLog {
+                org_rowid,
+                place_id: place_id.as_str(),
+                cleared_revision: RevisionValue::from(cleared_revision) as i64,
+                created_at: at.as_millis(),
+                created_by: by.as_ref().map(EmailAddress::as_str),
+                context: context.as_deref(),
+                comment: comment.as_deref(),
+            };
+            diesel::insert_into(schema::organization_place_clearance_log::table)
+                .values(&insertable)
+                .execute(conn)
+                .map_err(from_diesel_err)?;
+        }
         total_rows_affected += rows_affected;
     }
     Ok(total_rows_affected)
@@ -302,3 +370,79 @@ fn cleanup_pending_clearances_for_places(conn: &mut SqliteConnection, org_id: &I
     .map_err(from_diesel_err)?;
     Ok(delete_count as u64)
 }
+
+fn list_clearance_log_for_places(
+    conn: &mut SqliteConnection,
+    org_id: &Id,
+    pagination: &Pagination,
+) -> Result<Vec<ClearanceLogForPlace>> {
+    let org_rowid = resolve_organization_rowid(conn, org_id)?;
+    use schema::organization_place_clearance_log::dsl;
+    let mut query = schema::organization_place_clearance_log::table
+        .select((
+            dsl::place_id,
+            dsl::cleared_revision,
+            dsl::created_at,
+            dsl::created_by,
+            dsl::context,
+            dsl::comment,
+        ))
+        .filter(dsl::org_rowid.eq(org_rowid))
+        .order_by((dsl::created_at.desc(), dsl::rowid.desc()))
+        .into_boxed();
+
+    // Pagination
+    let offset = pagination.offset.unwrap_or(0) as i64;
+    // SQLite does not support an OFFSET without a LIMIT
+    // <https://www.sqlite.org/lang_select.html>
+    if let Some(limit) = pagination.limit {
+        query = query.limit(limit as i64);
+        // Optional OFFSET
+        if offset > 0 {
+            query = query.offset(offset);
+        }
+    } else if offset > 0 {
+        // Mandatory LIMIT
+        query = query.limit(i64::MAX);
+        query = query.offset(offset);
+    }
+
+    Ok(query
+        .load::<models::ClearanceLog>(conn)
+        .map_err(from_diesel_err)?
+        .into_iter()
+        .map(Into::into)
+        .collect())
+}
+
+fn add_clearance_log_for_places(
+    conn: &mut SqliteConnection,
+    org_id: &Id,
+    log: &ClearanceLogForPlace,
+) -> Result<()> {
+    let ClearanceLogForPlace {
+        place_id,
+        cleared_revision,
+        activity:
+            ActivityLog {
+                activity: Activity { at, by },
+                context,
+                comment,
+            },
+    } = log;
+    let org_rowid = resolve_organization_rowid(conn, org_id)?;
+    let insertable = models::NewPlaceClearanceLog {
+        org_rowid,
+        place_id: place_id.as_str(),
+        cleared_revision: RevisionValue::from(*cleared_revision) as i64,
+        created_at: at.as_millis(),
+        created_by: by.as_ref().map(EmailAddress::as_str),
+        context: context.as_deref(),
+        comment: comment.as_deref(),
+    };
+    diesel::insert_into(schema::organization_place_clearance_log::table)
+        .values(&insertable)
+        .execute(conn)
+        .map_err(from_diesel_err)?;
+    Ok(())
+}
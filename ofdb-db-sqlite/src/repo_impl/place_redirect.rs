@@ -0,0 +1,77 @@
+use super::*;
+
+impl PlaceRedirectRepo for DbReadWrite<'_> {
+    fn create_place_redirect(&self, merged_place_id: &Id, place_id: &Id) -> Result<()> {
+        create_place_redirect(&mut self.conn.borrow_mut(), merged_place_id, place_id)
+    }
+    fn resolve_place_redirect(&self, id: &Id) -> Result<Option<Id>> {
+        resolve_place_redirect(&mut self.conn.borrow_mut(), id)
+    }
+}
+
+impl PlaceRedirectRepo for DbConnection<'_> {
+    fn create_place_redirect(&self, merged_place_id: &Id, place_id: &Id) -> Result<()> {
+        create_place_redirect(&mut self.conn.borrow_mut(), merged_place_id, place_id)
+    }
+    fn resolve_place_redirect(&self, id: &Id) -> Result<Option<Id>> {
+        resolve_place_redirect(&mut self.conn.borrow_mut(), id)
+    }
+}
+
+impl PlaceRedirectRepo for DbReadOnly<'_> {
+    fn create_place_redirect(&self, _merged_place_id: &Id, _place_id: &Id) -> Result<()> {
+        unreachable!();
+    }
+    fn resolve_place_redirect(&self, id: &Id) -> Result<Option<Id>> {
+        resolve_place_redirect(&mut self.conn.borrow_mut(), id)
+    }
+}
+
+fn create_place_redirect(
+    conn: &mut SqliteConnection,
+    merged_place_id: &Id,
+    place_id: &Id,
+) -> Result<()> {
+    use schema::place_redirect::dsl;
+    let place_rowid = resolve_place_rowid(conn, merged_place_id)?;
+    let target_place_rowid = resolve_place_rowid(conn, place_id)?;
+    // Places that have been merged into the merged place before
+    diesel::update(schema::place_redirect::table.filter(dsl::target_place_rowid.eq(place_rowid)))
+        .set(dsl::target_place_rowid.eq(target_place_rowid))
+        .execute(conn)
+        .map_err(from_diesel_err)?;
+    let new_redirect = models::NewPlaceRedirect {
+        place_rowid,
+        target_place_rowid,
+    };
+    diesel::insert_into(schema::place_redirect::table)
+        .values(&new_redirect)
+        .execute(conn)
+        .map_err(from_diesel_err)?;
+    Ok(())
+}
+
+fn resolve_place_redirect(conn: &mut SqliteConnection, id: &Id) -> Result<Option<Id>> {
+    use schema::{place::dsl, place_redirect::dsl as redirect_dsl};
+    let target_place_rowid = schema::place_redirect::table
+        .select(redirect_dsl::target_place_rowid)
+        .filter(
+            redirect_dsl::place_rowid.eq_any(
+                schema::place::table
+                    .select(dsl::rowid)
+                    .filter(dsl::id.eq(id.as_str())),
+            ),
+        )
+        .first::<i64>(conn)
+        .optional()
+        .map_err(from_diesel_err)?;
+    let Some(target_place_rowid) = target_place_rowid else {
+        return Ok(None);
+    };
+    let target_place_id = schema::place::table
+        .select(dsl::id)
+        .filter(dsl::rowid.eq(target_place_rowid))
+        .first::<String>(conn)
+        .map_err(from_diesel_err)?;
+    Ok(Some(target_place_id.into()))
+}
@@ -14,6 +14,9 @@ impl RatingRepository for DbReadWrite<'_> {
     fn load_ratings_of_place(&self, place_id: &str) -> Result<Vec<Rating>> {
         load_ratings_of_place(&mut self.conn.borrow_mut(), place_id)
     }
+    fn load_all_ratings_of_place(&self, place_id: &str) -> Result<Vec<Rating>> {
+        load_all_ratings_of_place(&mut self.conn.borrow_mut(), place_id)
+    }
 
     fn archive_ratings(&self, ids: &[&str], activity: &Activity) -> Result<usize> {
         archive_ratings(&mut self.conn.borrow_mut(), ids, activity)
@@ -25,6 +28,10 @@ impl RatingRepository for DbReadWrite<'_> {
     fn load_place_ids_of_ratings(&self, ids: &[&str]) -> Result<Vec<String>> {
         load_place_ids_of_ratings(&mut self.conn.borrow_mut(), ids)
     }
+
+    fn move_ratings_of_place(&self, from_place_id: &Id, to_place_id: &Id) -> Result<usize> {
+        move_ratings_of_place(&mut self.conn.borrow_mut(), from_place_id, to_place_id)
+    }
 }
 
 impl RatingRepository for DbConnection<'_> {
@@ -41,6 +48,9 @@ impl RatingRepository for DbConnection<'_> {
     fn load_ratings_of_place(&self, place_id: &str) -> Result<Vec<Rating>> {
         load_ratings_of_place(&mut self.conn.borrow_mut(), place_id)
     }
+    fn load_all_ratings_of_place(&self, place_id: &str) -> Result<Vec<Rating>> {
+        load_all_ratings_of_place(&mut self.conn.borrow_mut(), place_id)
+    }
 
     fn archive_ratings(&self, ids: &[&str], activity: &Activity) -> Result<usize> {
         archive_ratings(&mut self.conn.borrow_mut(), ids, activity)
@@ -52,6 +62,10 @@ impl RatingRepository for DbConnection<'_> {
     fn load_place_ids_of_ratings(&self, ids: &[&str]) -> Result<Vec<String>> {
         load_place_ids_of_ratings(&mut self.conn.borrow_mut(), ids)
     }
+
+    fn move_ratings_of_place(&self, from_place_id: &Id, to_place_id: &Id) -> Result<usize> {
+        move_ratings_of_place(&mut self.conn.borrow_mut(), from_place_id, to_place_id)
+    }
 }
 
 impl RatingRepository for DbReadOnly<'_> {
@@ -68,6 +82,9 @@ impl RatingRepository for DbReadOnly<'_> {
     fn load_ratings_of_place(&self, place_id: &str) -> Result<Vec<Rating>> {
         load_ratings_of_place(&mut self.conn.borrow_mut(), place_id)
     }
+    fn load_all_ratings_of_place(&self, place_id: &str) -> Result<Vec<Rating>> {
+        load_all_ratings_of_place(&mut self.conn.borrow_mut(), place_id)
+    }
 
     fn archive_ratings(&self, _ids: &[&str], _activity: &Activity) -> Result<usize> {
         unreachable!();
@@ -83,6 +100,10 @@ impl RatingRepository for DbReadOnly<'_> {
     fn load_place_ids_of_ratings(&self, ids: &[&str]) -> Result<Vec<String>> {
         load_place_ids_of_ratings(&mut self.conn.borrow_mut(), ids)
     }
+
+    fn move_ratings_of_place(&self, _from_place_id: &Id, _to_place_id: &Id) -> Result<usize> {
+        unreachable!();
+    }
 }
 
 fn create_rating(conn: &mut SqliteConnection, rating: Rating) -> Result<()> {
@@ -150,8 +171,20 @@ fn load_rating(conn: &mut SqliteConnection, id: &str) -> Result<Rating> {
 }
 
 fn load_ratings_of_place(conn: &mut SqliteConnection, place_id: &str) -> Result<Vec<Rating>> {
+    query_ratings_of_place(conn, place_id, false)
+}
+
+fn load_all_ratings_of_place(conn: &mut SqliteConnection, place_id: &str) -> Result<Vec<Rating>> {
+    query_ratings_of_place(conn, place_id, true)
+}
+
+fn query_ratings_of_place(
+    conn: &mut SqliteConnection,
+    place_id: &str,
+    include_archived: bool,
+) -> Result<Vec<Rating>> {
     use schema::{place::dsl, place_rating::dsl as rating_dsl};
-    Ok(schema::place_rating::table
+    let mut query = schema::place_rating::table
         .inner_join(schema::place::table)
         .select((
             rating_dsl::rowid,
@@ -167,7 +200,11 @@ fn load_ratings_of_place(conn: &mut SqliteConnection, place_id: &str) -> Result<
             dsl::id,
         ))
         .filter(dsl::id.eq(place_id))
-        .filter(rating_dsl::archived_at.is_null())
+        .into_boxed();
+    if !include_archived {
+        query = query.filter(rating_dsl::archived_at.is_null());
+    }
+    Ok(query
         .load::<models::PlaceRating>(conn)
         .map_err(from_diesel_err)?
         .into_iter()
@@ -242,3 +279,18 @@ fn archive_ratings_of_places(
     .execute(conn)
     .map_err(from_diesel_err)
 }
+
+// The comments reference their rating and are moved implicitly
+fn move_ratings_of_place(
+    conn: &mut SqliteConnection,
+    from_place_id: &Id,
+    to_place_id: &Id,
+) -> Result<usize> {
+    use schema::place_rating::dsl;
+    let from_rowid = resolve_place_rowid(conn, from_place_id)?;
+    let to_rowid = resolve_place_rowid(conn, to_place_id)?;
+    diesel::update(schema::place_rating::table.filter(dsl::parent_rowid.eq(from_rowid)))
+        .set(dsl::parent_rowid.eq(to_rowid))
+        .execute(conn)
+        .map_err(from_diesel_err)
+}
@@ -0,0 +1,69 @@
+use super::*;
+
+impl RevokedTokenRepo for DbReadWrite<'_> {
+    fn add_revoked_token(&self, id: &str, expires_at: Timestamp) -> Result<()> {
+        add_revoked_token(&mut self.conn.borrow_mut(), id, expires_at)
+    }
+    fn is_token_revoked(&self, id: &str) -> Result<bool> {
+        is_token_revoked(&mut self.conn.borrow_mut(), id)
+    }
+    fn delete_expired_revoked_tokens(&self, expired_before: Timestamp) -> Result<usize> {
+        delete_expired_revoked_tokens(&mut self.conn.borrow_mut(), expired_before)
+    }
+}
+
+impl RevokedTokenRepo for DbReadOnly<'_> {
+    fn add_revoked_token(&self, _id: &str, _expires_at: Timestamp) -> Result<()> {
+        unreachable!();
+    }
+    fn is_token_revoked(&self, id: &str) -> Result<bool> {
+        is_token_revoked(&mut self.conn.borrow_mut(), id)
+    }
+    fn delete_expired_revoked_tokens(&self, _expired_before: Timestamp) -> Result<usize> {
+        unreachable!();
+    }
+}
+
+impl RevokedTokenRepo for DbConnection<'_> {
+    fn add_revoked_token(&self, id: &str, expires_at: Timestamp) -> Result<()> {
+        add_revoked_token(&mut self.conn.borrow_mut(), id, expires_at)
+    }
+    fn is_token_revoked(&self, id: &str) -> Result<bool> {
+        is_token_revoked(&mut self.conn.borrow_mut(), id)
+    }
+    fn delete_expired_revoked_tokens(&self, expired_before: Timestamp) -> Result<usize> {
+        delete_expired_revoked_tokens(&mut self.conn.borrow_mut(), expired_before)
+    }
+}
+
+fn add_revoked_token(conn: &mut SqliteConnection, id: &str, expires_at: Timestamp) -> Result<()> {
+    let revoked_token = models::NewRevokedToken {
+        id,
+        expires_at: expires_at.as_millis(),
+    };
+    // Revoking a token multiple times only updates the expiration time
+    diesel::replace_into(schema::revoked_tokens::table)
+        .values(&revoked_token)
+        .execute(conn)
+        .map_err(from_diesel_err)?;
+    Ok(())
+}
+
+fn is_token_revoked(conn: &mut SqliteConnection, id: &str) -> Result<bool> {
+    use schema::revoked_tokens::dsl;
+    diesel::select(diesel::dsl::exists(
+        dsl::revoked_tokens.filter(dsl::id.eq(id)),
+    ))
+    .get_result(conn)
+    .map_err(from_diesel_err)
+}
+
+fn delete_expired_revoked_tokens(
+    conn: &mut SqliteConnection,
+    expired_before: Timestamp,
+) -> Result<usize> {
+    use schema::revoked_tokens::dsl;
+    diesel::delete(dsl::revoked_tokens.filter(dsl::expires_at.lt(expired_before.as_millis())))
+        .execute(conn)
+        .map_err(from_diesel_err)
+}
@@ -98,10 +98,19 @@ fn update_user(conn: &mut SqliteConnection, u: &User) -> Result<()> {
 }
 
 fn delete_user_by_email(conn: &mut SqliteConnection, email: &EmailAddress) -> Result<()> {
-    use schema::users::dsl;
-    diesel::delete(dsl::users.filter(dsl::email.eq(email.as_str())))
-        .execute(conn)
-        .map_err(from_diesel_err)?;
+    use schema::{organization_member::dsl as member_dsl, users::dsl};
+    conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        let user_ids = dsl::users
+            .select(dsl::id)
+            .filter(dsl::email.eq(email.as_str()));
+        diesel::delete(
+            member_dsl::organization_member.filter(member_dsl::user_id.eq_any(user_ids)),
+        )
+        .execute(conn)?;
+        diesel::delete(dsl::users.filter(dsl::email.eq(email.as_str()))).execute(conn)?;
+        Ok(())
+    })
+    .map_err(from_diesel_err)?;
     Ok(())
 }
 
@@ -47,6 +47,114 @@ table! {
 joinable!(organization_place_clearance -> organization (org_rowid));
 joinable!(organization_place_clearance -> place (place_rowid));
 
+table! {
+    organization_event_clearance (org_rowid, event_rowid) {
+        rowid -> BigInt,
+        org_rowid -> BigInt,
+        event_rowid -> BigInt,
+        created_at -> BigInt,
+        // last cleared revision or NULL if the event is new and has not been cleared yet
+        last_cleared_revision -> Nullable<BigInt>,
+    }
+}
+
+joinable!(organization_event_clearance -> organization (org_rowid));
+joinable!(organization_event_clearance -> events (event_rowid));
+
+table! {
+    organization_api_token (rowid) {
+        rowid -> BigInt,
+        id -> Text,
+        org_rowid -> BigInt,
+        name -> Text,
+        token_hash -> Text,
+        // space separated list of scopes
+        scopes -> Text,
+        created_at -> BigInt,
+        expires_at -> Nullable<BigInt>,
+        last_used_at -> Nullable<BigInt>,
+        revoked_at -> Nullable<BigInt>,
+    }
+}
+
+joinable!(organization_api_token -> organization (org_rowid));
+
+table! {
+    organization_webhook (rowid) {
+        rowid -> BigInt,
+        id -> Text,
+        org_rowid -> BigInt,
+        url -> Text,
+        secret -> Text,
+        created_at -> BigInt,
+    }
+}
+
+joinable!(organization_webhook -> organization (org_rowid));
+
+table! {
+    organization_member (rowid) {
+        rowid -> BigInt,
+        org_rowid -> BigInt,
+        user_id -> BigInt,
+        role -> Text,
+    }
+}
+
+joinable!(organization_member -> organization (org_rowid));
+joinable!(organization_member -> users (user_id));
+
+table! {
+    organization_place_clearance_log (rowid) {
+        rowid -> BigInt,
+        org_rowid -> BigInt,
+        place_id -> Text,
+        cleared_revision -> BigInt,
+        created_at -> BigInt,
+        // e-mail address
+        created_by -> Nullable<Text>,
+        context -> Nullable<Text>,
+        comment -> Nullable<Text>,
+    }
+}
+
+joinable!(organization_place_clearance_log -> organization (org_rowid));
+
+table! {
+    organization_event_clearance_log (rowid) {
+        rowid -> BigInt,
+        org_rowid -> BigInt,
+        event_id -> Text,
+        cleared_revision -> BigInt,
+        created_at -> BigInt,
+        // e-mail address
+        created_by -> Nullable<Text>,
+        context -> Nullable<Text>,
+        comment -> Nullable<Text>,
+    }
+}
+
+joinable!(organization_event_clearance_log -> organization (org_rowid));
+
+table! {
+    webhook_delivery (rowid) {
+        rowid -> BigInt,
+        id -> Text,
+        webhook_rowid -> BigInt,
+        event_type -> Text,
+        payload -> Text,
+        created_at -> BigInt,
+        status -> SmallInt,
+        attempts -> BigInt,
+        next_attempt_at -> Nullable<BigInt>,
+        last_attempt_at -> Nullable<BigInt>,
+        last_response_status -> Nullable<BigInt>,
+        last_error -> Nullable<Text>,
+    }
+}
+
+joinable!(webhook_delivery -> organization_webhook (webhook_rowid));
+
 ///////////////////////////////////////////////////////////////////////
 // Users
 ///////////////////////////////////////////////////////////////////////
@@ -135,6 +243,17 @@ table! {
 
 joinable!(place_revision_custom_link -> place_revision (parent_rowid));
 
+table! {
+    place_revision_translation (parent_rowid, lang) {
+        parent_rowid -> BigInt,
+        lang -> Text,
+        title -> Text,
+        description -> Nullable<Text>,
+    }
+}
+
+joinable!(place_revision_translation -> place_revision (parent_rowid));
+
 table! {
     place_revision_review (rowid) {
         rowid -> BigInt,
@@ -183,6 +302,28 @@ table! {
 
 joinable!(place_rating_comment -> place_rating (parent_rowid));
 
+// The merged place is redirected to the target place
+table! {
+    place_redirect (rowid) {
+        rowid -> BigInt,
+        place_rowid -> BigInt,
+        target_place_rowid -> BigInt,
+    }
+}
+
+table! {
+    place_duplicate_candidate (rowid) {
+        rowid -> BigInt,
+        place_id -> Text,
+        duplicate_place_id -> Text,
+        similarity -> Double,
+        detected_at -> BigInt,
+        state -> Text,
+        reviewed_at -> Nullable<BigInt>,
+        reviewed_by -> Nullable<Text>,
+    }
+}
+
 ///////////////////////////////////////////////////////////////////////
 // Events
 ///////////////////////////////////////////////////////////////////////
@@ -213,6 +354,9 @@ table! {
         archived -> Nullable<BigInt>,
         image_url -> Nullable<Text>,
         image_link_url -> Nullable<Text>,
+        rrule -> Nullable<Text>,
+        exdates -> Nullable<Text>,
+        current_rev -> BigInt,
     }
 }
 
@@ -227,6 +371,60 @@ table! {
 
 joinable!(event_tags -> events (event_id));
 
+table! {
+    event_revision (rowid) {
+        rowid -> BigInt,
+        parent_rowid -> BigInt,
+        rev -> BigInt,
+        created_at -> BigInt,
+        created_by -> Nullable<BigInt>,
+        context -> Nullable<Text>,
+        comment -> Nullable<Text>,
+        title -> Text,
+        description -> Nullable<Text>,
+        start -> BigInt,
+        end -> Nullable<BigInt>,
+        lat -> Nullable<Double>,
+        lng -> Nullable<Double>,
+        street -> Nullable<Text>,
+        zip -> Nullable<Text>,
+        city -> Nullable<Text>,
+        country -> Nullable<Text>,
+        state -> Nullable<Text>,
+        email -> Nullable<Text>,
+        telephone -> Nullable<Text>,
+        homepage -> Nullable<Text>,
+        registration -> Nullable<SmallInt>,
+        organizer -> Nullable<Text>,
+        image_url -> Nullable<Text>,
+        image_link_url -> Nullable<Text>,
+        rrule -> Nullable<Text>,
+        exdates -> Nullable<Text>,
+    }
+}
+
+joinable!(event_revision -> events (parent_rowid));
+
+table! {
+    event_revision_tag (parent_rowid, tag) {
+        parent_rowid -> BigInt,
+        tag -> Text,
+    }
+}
+
+joinable!(event_revision_tag -> event_revision (parent_rowid));
+
+table! {
+    event_revision_translation (parent_rowid, lang) {
+        parent_rowid -> BigInt,
+        lang -> Text,
+        title -> Text,
+        description -> Nullable<Text>,
+    }
+}
+
+joinable!(event_revision_translation -> event_revision (parent_rowid));
+
 ///////////////////////////////////////////////////////////////////////
 // Subscriptions
 ///////////////////////////////////////////////////////////////////////
@@ -279,25 +477,64 @@ table! {
 
 joinable!(review_tokens -> place (place_rowid));
 
+table! {
+    revoked_tokens (id) {
+        id -> Text,
+        expires_at -> BigInt,
+    }
+}
+
+///////////////////////////////////////////////////////////////////////
+// Changes
+///////////////////////////////////////////////////////////////////////
+
+table! {
+    change_log (rowid) {
+        rowid -> BigInt,
+        changed_at -> BigInt,
+        kind -> Text,
+        item_type -> Text,
+        item_id -> Text,
+        item_rev -> Nullable<BigInt>,
+        item_status -> Nullable<SmallInt>,
+        place_id -> Nullable<Text>,
+    }
+}
+
 ///////////////////////////////////////////////////////////////////////
 
 allow_tables_to_appear_in_same_query!(
     bbox_subscriptions,
+    change_log,
     events,
     event_tags,
+    event_revision,
+    event_revision_tag,
+    event_revision_translation,
     place,
     place_rating,
     place_rating_comment,
+    place_duplicate_candidate,
+    place_redirect,
     place_revision,
     place_revision_review,
     place_revision_tag,
     place_revision_custom_link,
+    place_revision_translation,
     organization,
     organization_tag,
     organization_place_clearance,
+    organization_event_clearance,
+    organization_api_token,
+    organization_webhook,
+    organization_member,
+    organization_place_clearance_log,
+    organization_event_clearance_log,
+    webhook_delivery,
     tags,
     users,
     user_tokens,
     review_tokens,
+    revoked_tokens,
     sent_reminders
 );
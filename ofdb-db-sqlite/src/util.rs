@@ -22,6 +22,80 @@ pub(crate) fn load_url(url: String) -> Option<e::Url> {
     }
 }
 
+pub(crate) fn load_translation(
+    lang: String,
+    title: String,
+    description: Option<String>,
+) -> Option<e::Translation> {
+    match lang.parse() {
+        Ok(lang) => Some(e::Translation {
+            lang,
+            title,
+            description,
+        }),
+        Err(err) => {
+            // The database should only contain valid language tags
+            log::error!(
+                "Failed to load translation '{}' from database: {}",
+                lang,
+                err
+            );
+            None
+        }
+    }
+}
+
+pub(crate) fn load_recurrence(
+    rrule: Option<String>,
+    exdates: Option<String>,
+) -> Option<e::recurrence::Recurrence> {
+    let rrule = rrule?;
+    let rule = match rrule.parse() {
+        Ok(rule) => rule,
+        Err(err) => {
+            // The database should only contain valid rules
+            log::error!(
+                "Failed to load recurrence rule '{}' from database: {}",
+                rrule,
+                err
+            );
+            return None;
+        }
+    };
+    let exdates = exdates
+        .as_deref()
+        .unwrap_or_default()
+        .split(',')
+        .filter(|s| !s.is_empty())
+        .filter_map(|s| {
+            s.parse()
+                .ok()
+                .and_then(|secs| Timestamp::try_from_secs(secs).ok())
+                .or_else(|| {
+                    log::error!("Failed to load excluded date '{}' from database", s);
+                    None
+                })
+        })
+        .collect();
+    Some(e::recurrence::Recurrence { rule, exdates })
+}
+
+pub(crate) fn recurrence_into_rrule_and_exdates(
+    recurrence: Option<e::recurrence::Recurrence>,
+) -> (Option<String>, Option<String>) {
+    let Some(e::recurrence::Recurrence { rule, exdates }) = recurrence else {
+        return (None, None);
+    };
+    let exdates = (!exdates.is_empty()).then(|| {
+        exdates
+            .iter()
+            .map(|ts| ts.as_secs().to_string())
+            .collect::<Vec<_>>()
+            .join(",")
+    });
+    (Some(rule.to_string()), exdates)
+}
+
 pub(crate) fn registration_type_from_i16(i: i16) -> e::RegistrationType {
     use ofdb_core::entities::RegistrationType::*;
     match i {
@@ -83,11 +157,52 @@ mod tests {
         assert_eq!(p, 2);
         assert_eq!(u, 3);
     }
+
+    #[test]
+    fn store_and_load_recurrence() {
+        use ofdb_core::{entities::recurrence::Recurrence, util::time::Timestamp};
+        let recurrence = Recurrence {
+            rule: "FREQ=MONTHLY;BYDAY=1SA".parse().unwrap(),
+            exdates: vec![
+                Timestamp::try_from_secs(1_717_228_800).unwrap(),
+                Timestamp::try_from_secs(1_719_648_000).unwrap(),
+            ],
+        };
+        let (rrule, exdates) = super::recurrence_into_rrule_and_exdates(Some(recurrence.clone()));
+        assert_eq!(Some("FREQ=MONTHLY;BYDAY=1SA"), rrule.as_deref());
+        assert_eq!(Some("1717228800,1719648000"), exdates.as_deref());
+        assert_eq!(Some(recurrence), super::load_recurrence(rrule, exdates));
+        assert_eq!((None, None), super::recurrence_into_rrule_and_exdates(None));
+        assert_eq!(None, super::load_recurrence(None, None));
+    }
+}
+
+pub(crate) fn event_from_event_entity_and_tags(
+    e: EventEntity,
+    tag_rels: &[EventTag],
+    translation_rels: &[(i64, e::Translation)],
+) -> e::Event {
+    let tags = tag_rels
+        .iter()
+        .filter(|r| r.event_id == e.id)
+        .map(|r| &r.tag)
+        .cloned()
+        .collect();
+    let translations = translation_rels
+        .iter()
+        .filter(|(event_id, _)| *event_id == e.id)
+        .map(|(_, translation)| translation)
+        .cloned()
+        .collect();
+    event_from_event_entity(e, tags, translations)
 }
 
-pub(crate) fn event_from_event_entity_and_tags(e: EventEntity, tag_rels: &[EventTag]) -> e::Event {
+pub(crate) fn event_from_event_entity(
+    e: EventEntity,
+    tags: Vec<String>,
+    translations: Vec<e::Translation>,
+) -> e::Event {
     let EventEntity {
-        id,
         uid,
         title,
         description,
@@ -108,15 +223,12 @@ pub(crate) fn event_from_event_entity_and_tags(e: EventEntity, tag_rels: &[Event
         archived,
         image_url,
         image_link_url,
+        rrule,
+        exdates,
+        current_rev,
         created_by_email,
         ..
     } = e;
-    let tags = tag_rels
-        .iter()
-        .filter(|r| r.event_id == id)
-        .map(|r| &r.tag)
-        .cloned()
-        .collect();
     let address = if street.is_some()
         || zip.is_some()
         || city.is_some()
@@ -163,6 +275,7 @@ pub(crate) fn event_from_event_entity_and_tags(e: EventEntity, tag_rels: &[Event
 
     e::Event {
         id: uid.into(),
+        revision: e::Revision::from(current_rev as u64),
         title,
         description,
         start: Timestamp::try_from_secs(start).unwrap(),
@@ -176,6 +289,8 @@ pub(crate) fn event_from_event_entity_and_tags(e: EventEntity, tag_rels: &[Event
         archived: archived.map(Timestamp::try_from_secs).transpose().unwrap(),
         image_url: image_url.and_then(load_url),
         image_link_url: image_link_url.and_then(load_url),
+        recurrence: load_recurrence(rrule, exdates),
+        translations,
     }
 }
 
@@ -468,6 +583,104 @@ impl From<PendingClearanceForPlace> for e::PendingClearanceForPlace {
     }
 }
 
+impl From<PendingClearanceForEvent> for e::PendingClearanceForEvent {
+    fn from(from: PendingClearanceForEvent) -> Self {
+        let PendingClearanceForEvent {
+            event_id,
+            created_at,
+            last_cleared_revision,
+        } = from;
+        let last_cleared_revision = last_cleared_revision.map(|rev| e::Revision::from(rev as u64));
+        Self {
+            event_id: event_id.into(),
+            created_at: e::Timestamp::try_from_millis(created_at).unwrap(),
+            last_cleared_revision,
+        }
+    }
+}
+
+fn load_clearance_log(from: ClearanceLog) -> (String, e::Revision, e::ActivityLog) {
+    let ClearanceLog {
+        item_id,
+        cleared_revision,
+        created_at,
+        created_by,
+        context,
+        comment,
+    } = from;
+    let activity = e::ActivityLog {
+        activity: e::Activity {
+            at: e::Timestamp::try_from_millis(created_at).unwrap(),
+            by: created_by.map(e::EmailAddress::new_unchecked),
+        },
+        context,
+        comment,
+    };
+    (
+        item_id,
+        e::Revision::from(cleared_revision as u64),
+        activity,
+    )
+}
+
+impl From<ClearanceLog> for e::ClearanceLogForPlace {
+    fn from(from: ClearanceLog) -> Self {
+        let (place_id, cleared_revision, activity) = load_clearance_log(from);
+        Self {
+            place_id: place_id.into(),
+            cleared_revision,
+            activity,
+        }
+    }
+}
+
+impl From<ClearanceLog> for e::ClearanceLogForEvent {
+    fn from(from: ClearanceLog) -> Self {
+        let (event_id, cleared_revision, activity) = load_clearance_log(from);
+        Self {
+            event_id: event_id.into(),
+            cleared_revision,
+            activity,
+        }
+    }
+}
+
+impl From<ApiToken> for e::ApiToken {
+    fn from(from: ApiToken) -> Self {
+        let ApiToken {
+            id,
+            org_id,
+            name,
+            token_hash,
+            scopes,
+            created_at,
+            expires_at,
+            last_used_at,
+            revoked_at,
+        } = from;
+        let scopes = scopes
+            .split_whitespace()
+            .filter_map(|scope| {
+                scope
+                    .parse()
+                    .map_err(|_| log::warn!("Ignoring unknown scope '{scope}' of API token {id}"))
+                    .ok()
+            })
+            .collect();
+        Self {
+            id: id.into(),
+            org_id: org_id.into(),
+            name,
+            token_hash,
+            scopes,
+            created_at: Timestamp::try_from_millis(created_at).unwrap(),
+            expires_at: expires_at.map(|at| Timestamp::try_from_millis(at).unwrap()),
+            last_used_at: last_used_at.map(|at| Timestamp::try_from_millis(at).unwrap()),
+            revoked_at: revoked_at.map(|at| Timestamp::try_from_millis(at).unwrap()),
+        }
+    }
+}
+
 #[test]
 fn test_tag_diff() {
     let x = tags_diff(&[], &["b".into()]);
@@ -1,32 +1,63 @@
-use std::{ops::Bound, path::Path, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    ops::Bound,
+    path::{Path, PathBuf},
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering as AtomicOrdering},
+    },
+};
+
+use time::Weekday;
 
-use anyhow::{Result as Fallible, bail};
+use anyhow::{Result as Fallible, anyhow, bail};
 use num_traits::ToPrimitive;
 use parking_lot::Mutex;
 use strum::IntoEnumIterator as _;
 use tantivy::{
     DocAddress, DocId, Index, IndexReader, IndexWriter, Order, ReloadPolicy, Score, SegmentReader,
     TantivyDocument,
-    collector::TopDocs,
-    query::{BooleanQuery, Occur, Query, QueryParser, RangeQuery, TermQuery},
+    collector::{Collector, DocSetCollector, SegmentCollector, TopDocs},
+    columnar::{Column, StrColumn},
+    directory::{
+        Directory as _, DirectoryLock, INDEX_WRITER_LOCK, MmapDirectory, error::LockError,
+    },
+    query::{BooleanQuery, FuzzyTermQuery, Occur, Query, QueryParser, RangeQuery, TermQuery},
     schema::*,
-    tokenizer::{LowerCaser, RawTokenizer, RemoveLongFilter, SimpleTokenizer, TextAnalyzer},
+    tokenizer::{
+        AsciiFoldingFilter, Language, LowerCaser, RawTokenizer, RemoveLongFilter, SimpleTokenizer,
+        Stemmer, TextAnalyzer,
+    },
 };
 
 use ofdb_core::{
     db::{
-        EventAndPlaceIndexer, EventIndexer, IdIndex, IdIndexer, IndexQuery, IndexQueryMode,
-        IndexedPlace, Indexer, PlaceIndex, PlaceIndexer,
+        EventAndPlaceIndex, EventAndPlaceIndexer, EventIndexer, IdIndex, IdIndexer, IndexQuery,
+        IndexQueryMode, IndexedEntry, IndexedKind, IndexedPlace, Indexer, PlaceCluster, PlaceIndex,
+        PlaceIndexer,
     },
+    entities::opening_hours::{MINUTES_PER_DAY, OpeningHoursRules},
     entities::{
         Address, AvgRatingValue, AvgRatings, Category, Contact, Event, Id, Place, RatingContext,
-        ReviewStatus, ReviewStatusPrimitive,
+        ReviewStatus, ReviewStatusPrimitive, Revision, TagCount, TagFrequency, Translation,
     },
-    util::geo::{LatCoord, LngCoord, MapPoint},
+    util::geo::{Distance, LatCoord, LngCoord, MapPoint},
 };
 
 const OVERALL_INDEX_HEAP_SIZE_IN_BYTES: usize = 50_000_000;
 
+/// Version of the index that is stored in a subdirectory `v<version>`
+/// of the index directory.
+///
+/// Must be incremented on every change that requires to rebuild
+/// existing indexes, e.g. when adding or modifying fields, changing
+/// tokenizers, or indexing different contents.
+pub const INDEX_VERSION: u32 = 2;
+
+/// Upper bound for the number of indexed occurrences of recurring events.
+const MAX_INDEXED_OCCURRENCES: usize = 1_000;
+
 const PLACE_KIND_FLAG: i64 = 1;
 const EVENT_KIND_FLAG: i64 = 2;
 const ALL_KINDS_MASK: i64 = PLACE_KIND_FLAG | EVENT_KIND_FLAG;
@@ -44,12 +75,16 @@ struct IndexedFields {
     kind: Field,
     id: Field,
     status: Field,
+    revision: Field,
     lat: Field,
     lng: Field,
     ts_min: Field, // minimum time stamp with second precision, e.g. event start
     ts_max: Field, // maximum time stamp with second precision, e.g. event end
     title: Field,
     description: Field,
+    translations: Field, // stored translations, see `translations_to_object()`
+    translated_title: Field,
+    translated_description: Field,
     address_street: Field,
     address_city: Field,
     address_zip: Field,
@@ -57,6 +92,7 @@ struct IndexedFields {
     address_state: Field,
     contact_name: Field,
     tag: Field,
+    opening_slot: Field, // weekly time slots when a place is open, see `opening_slot()`
     ratings_diversity: Field,
     ratings_fairness: Field,
     ratings_humanity: Field,
@@ -64,17 +100,25 @@ struct IndexedFields {
     ratings_solidarity: Field,
     ratings_transparency: Field,
     total_rating: Field,
+    // Normalized full text for fuzzy matching, see `IndexOptions`
+    fuzzy_text_de: Option<Field>,
+    fuzzy_text_en: Option<Field>,
+    fuzzy_text_fr: Option<Field>,
 }
 
 const FIELD_NAME_KIND: &str = "kind";
 const FIELD_NAME_ID: &str = "id";
 const FIELD_NAME_STATUS: &str = "status";
+const FIELD_NAME_REVISION: &str = "rev";
 const FIELD_NAME_LAT: &str = "lat";
 const FIELD_NAME_LNG: &str = "lon";
 const FIELD_NAME_TS_MIN: &str = "ts_min";
 const FIELD_NAME_TS_MAX: &str = "ts_max";
 const FIELD_NAME_TITLE: &str = "tit";
 const FIELD_NAME_DESCRIPTION: &str = "dsc";
+const FIELD_NAME_TRANSLATIONS: &str = "i18n";
+const FIELD_NAME_TRANSLATED_TITLE: &str = "i18n_tit";
+const FIELD_NAME_TRANSLATED_DESCRIPTION: &str = "i18n_dsc";
 const FIELD_NAME_CONTACT_NAME: &str = "cnt_name";
 const FIELD_NAME_ADDRESS_STREET: &str = "adr_street";
 const FIELD_NAME_ADDRESS_CITY: &str = "adr_city";
@@ -82,6 +126,7 @@ const FIELD_NAME_ADDRESS_ZIP: &str = "adr_zip";
 const FIELD_NAME_ADDRESS_COUNTRY: &str = "adr_country";
 const FIELD_NAME_ADDRESS_STATE: &str = "adr_state";
 const FIELD_NAME_TAG: &str = "tag";
+const FIELD_NAME_OPENING_SLOT: &str = "hrs_slot";
 const FIELD_NAME_RATINGS_DIVERSITY: &str = "rat_diversity";
 const FIELD_NAME_RATINGS_FAIRNESS: &str = "rat_fairness";
 const FIELD_NAME_RATINGS_HUMANITY: &str = "rat_humanity";
@@ -89,9 +134,30 @@ const FIELD_NAME_RATINGS_RENEWABLE: &str = "rat_renewable";
 const FIELD_NAME_RATINGS_SOLIDARITY: &str = "rat_solidarity";
 const FIELD_NAME_RATINGS_TRANSPARENCY: &str = "rat_transparency";
 const FIELD_NAME_TOTAL_RATING: &str = "rat_total";
+const FIELD_NAME_FUZZY_TEXT_DE: &str = "fuz_de";
+const FIELD_NAME_FUZZY_TEXT_EN: &str = "fuz_en";
+const FIELD_NAME_FUZZY_TEXT_FR: &str = "fuz_fr";
+
+/// Options for building the full-text search index.
+#[derive(Debug, Clone, Copy)]
+pub struct IndexOptions {
+    /// Additionally index the full text with German, English, and
+    /// French stemming and ASCII folding (e.g. ä → ae, ß → ss) that
+    /// enables fuzzy queries, see `IndexQuery::fuzzy`.
+    ///
+    /// Translations are only indexed with the stemming of their
+    /// language if it is supported.
+    pub fuzzy_text: bool,
+}
+
+impl Default for IndexOptions {
+    fn default() -> Self {
+        Self { fuzzy_text: true }
+    }
+}
 
 impl IndexedFields {
-    fn build_schema() -> (Self, Schema) {
+    fn build_schema(options: IndexOptions) -> (Self, Schema) {
         let id_options = TextOptions::default()
             .set_indexing_options(
                 TextFieldIndexing::default()
@@ -99,13 +165,16 @@ impl IndexedFields {
                     .set_index_option(IndexRecordOption::Basic),
             )
             .set_stored();
+        // Tags are already normalized and stored as is in the
+        // fast field for counting them, see `TagCountCollector`
         let tag_options = TextOptions::default()
             .set_indexing_options(
                 TextFieldIndexing::default()
                     .set_tokenizer(TAG_TOKENIZER)
                     .set_index_option(IndexRecordOption::WithFreqs),
             )
-            .set_stored();
+            .set_stored()
+            .set_fast(None);
         // Common options for indexing text fields
         let indexed_text_options = TextOptions::default().set_indexing_options(
             TextFieldIndexing::default()
@@ -115,17 +184,50 @@ impl IndexedFields {
         // Text fields that are returned as part of the search result
         // additionally need to be stored explicitly
         let stored_text_options = indexed_text_options.clone().set_stored();
+        let fuzzy_text_options = |tokenizer| {
+            TextOptions::default().set_indexing_options(
+                TextFieldIndexing::default()
+                    .set_tokenizer(tokenizer)
+                    .set_index_option(IndexRecordOption::WithFreqs),
+            )
+        };
         let mut schema_builder = SchemaBuilder::default();
+        let (fuzzy_text_de, fuzzy_text_en, fuzzy_text_fr) = if options.fuzzy_text {
+            (
+                Some(schema_builder.add_text_field(
+                    FIELD_NAME_FUZZY_TEXT_DE,
+                    fuzzy_text_options(FUZZY_TEXT_DE_TOKENIZER),
+                )),
+                Some(schema_builder.add_text_field(
+                    FIELD_NAME_FUZZY_TEXT_EN,
+                    fuzzy_text_options(FUZZY_TEXT_EN_TOKENIZER),
+                )),
+                Some(schema_builder.add_text_field(
+                    FIELD_NAME_FUZZY_TEXT_FR,
+                    fuzzy_text_options(FUZZY_TEXT_FR_TOKENIZER),
+                )),
+            )
+        } else {
+            (None, None, None)
+        };
         let fields = Self {
             kind: schema_builder.add_i64_field(FIELD_NAME_KIND, INDEXED),
             id: schema_builder.add_text_field(FIELD_NAME_ID, id_options),
             status: schema_builder.add_i64_field(FIELD_NAME_STATUS, INDEXED | STORED),
-            lat: schema_builder.add_f64_field(FIELD_NAME_LAT, INDEXED | STORED),
-            lng: schema_builder.add_f64_field(FIELD_NAME_LNG, INDEXED | STORED),
+            revision: schema_builder.add_u64_field(FIELD_NAME_REVISION, STORED),
+            lat: schema_builder.add_f64_field(FIELD_NAME_LAT, INDEXED | STORED | FAST),
+            lng: schema_builder.add_f64_field(FIELD_NAME_LNG, INDEXED | STORED | FAST),
             ts_min: schema_builder.add_i64_field(FIELD_NAME_TS_MIN, INDEXED | STORED),
             ts_max: schema_builder.add_i64_field(FIELD_NAME_TS_MAX, INDEXED | STORED),
             title: schema_builder.add_text_field(FIELD_NAME_TITLE, stored_text_options.clone()),
             description: schema_builder.add_text_field(FIELD_NAME_DESCRIPTION, stored_text_options),
+            translations: schema_builder.add_json_field(FIELD_NAME_TRANSLATIONS, STORED),
+            translated_title: schema_builder
+                .add_text_field(FIELD_NAME_TRANSLATED_TITLE, indexed_text_options.clone()),
+            translated_description: schema_builder.add_text_field(
+                FIELD_NAME_TRANSLATED_DESCRIPTION,
+                indexed_text_options.clone(),
+            ),
             contact_name: schema_builder
                 .add_text_field(FIELD_NAME_CONTACT_NAME, indexed_text_options.clone()),
             address_street: schema_builder
@@ -139,6 +241,7 @@ impl IndexedFields {
             address_state: schema_builder
                 .add_text_field(FIELD_NAME_ADDRESS_STATE, indexed_text_options),
             tag: schema_builder.add_text_field(FIELD_NAME_TAG, tag_options),
+            opening_slot: schema_builder.add_u64_field(FIELD_NAME_OPENING_SLOT, INDEXED),
             ratings_diversity: schema_builder.add_f64_field(FIELD_NAME_RATINGS_DIVERSITY, STORED),
             ratings_fairness: schema_builder.add_f64_field(FIELD_NAME_RATINGS_FAIRNESS, STORED),
             ratings_humanity: schema_builder.add_f64_field(FIELD_NAME_RATINGS_HUMANITY, STORED),
@@ -147,10 +250,96 @@ impl IndexedFields {
             ratings_transparency: schema_builder
                 .add_f64_field(FIELD_NAME_RATINGS_TRANSPARENCY, STORED),
             total_rating: schema_builder.add_u64_field(FIELD_NAME_TOTAL_RATING, STORED | FAST),
+            fuzzy_text_de,
+            fuzzy_text_en,
+            fuzzy_text_fr,
         };
         (fields, schema_builder.build())
     }
 
+    // All text fields that are searched by a text query
+    const fn full_text_fields(&self) -> [Field; 10] {
+        [
+            self.title,
+            self.description,
+            self.translated_title,
+            self.translated_description,
+            self.address_street,
+            self.address_city,
+            self.address_zip,
+            self.address_country,
+            self.address_state,
+            self.contact_name,
+        ]
+    }
+
+    // The fuzzy text fields with their tokenizer and primary language
+    fn fuzzy_text_fields(&self) -> impl Iterator<Item = (Field, &'static str, &'static str)> {
+        [
+            (self.fuzzy_text_de, FUZZY_TEXT_DE_TOKENIZER, "de"),
+            (self.fuzzy_text_en, FUZZY_TEXT_EN_TOKENIZER, "en"),
+            (self.fuzzy_text_fr, FUZZY_TEXT_FR_TOKENIZER, "fr"),
+        ]
+        .into_iter()
+        .filter_map(|(field, tokenizer, lang)| field.map(|field| (field, tokenizer, lang)))
+    }
+
+    // Copy the folded full text of the document into the fuzzy text fields.
+    //
+    // The language of the original text is unknown and it is copied into
+    // all fuzzy text fields. Translations are only copied into the field of
+    // their language or into all fields if their language is not supported.
+    fn add_fuzzy_text(&self, doc: &mut TantivyDocument, translations: &[Translation]) {
+        if self.fuzzy_text_fields().next().is_none() {
+            return;
+        }
+        let translated_text_fields = [self.translated_title, self.translated_description];
+        let full_text_fields = self.full_text_fields();
+        let text = doc
+            .field_values()
+            .filter(|(field, _)| {
+                full_text_fields.contains(field) && !translated_text_fields.contains(field)
+            })
+            .filter_map(|(_, value)| value.as_str().map(fold_text))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let is_supported = |lang: &str| self.fuzzy_text_fields().any(|(_, _, l)| l == lang);
+        for (field, _, lang) in self.fuzzy_text_fields() {
+            let mut text = text.clone();
+            for translation in translations.iter().filter(|t| {
+                let primary = t.lang.primary();
+                primary == lang || !is_supported(primary)
+            }) {
+                let Translation {
+                    title, description, ..
+                } = translation;
+                text.push('\n');
+                text.push_str(&fold_text(title));
+                if let Some(description) = description {
+                    text.push('\n');
+                    text.push_str(&fold_text(description));
+                }
+            }
+            doc.add_text(field, &text);
+        }
+    }
+
+    fn add_translations(&self, doc: &mut TantivyDocument, translations: &[Translation]) {
+        if translations.is_empty() {
+            return;
+        }
+        for Translation {
+            title, description, ..
+        } in translations
+        {
+            doc.add_text(self.translated_title, title);
+            if let Some(description) = description {
+                doc.add_text(self.translated_description, description);
+            }
+        }
+        doc.add_object(self.translations, translations_to_object(translations));
+    }
+
     fn read_indexed_place(&self, doc: &TantivyDocument) -> IndexedPlace {
         let mut lat: Option<LatCoord> = Default::default();
         let mut lng: Option<LngCoord> = Default::default();
@@ -202,6 +391,14 @@ impl IndexedFields {
                         log::error!("Invalid tag value: {:?}", value);
                     }
                 }
+                f if f == self.translations => {
+                    debug_assert!(place.translations.is_empty());
+                    if let Some(translations) = translations_from_value(&value) {
+                        place.translations = translations;
+                    } else {
+                        log::error!("Invalid translations value: {:?}", value);
+                    }
+                }
                 f if f == self.ratings_diversity => {
                     debug_assert!(place.ratings.diversity == Default::default());
                     place.ratings.diversity = value.as_f64().map(Into::into).unwrap_or_default();
@@ -227,6 +424,8 @@ impl IndexedFields {
                     place.ratings.transparency = value.as_f64().map(Into::into).unwrap_or_default();
                 }
                 f if f == self.total_rating => (),
+                // Opening slots are only indexed
+                //f if f == self.opening_slot => (),
                 // Address fields are currently not stored
                 //f if f == self.address_street => (),
                 //f if f == self.address_city => (),
@@ -252,11 +451,15 @@ pub(crate) struct TantivyIndex {
     index_reader: IndexReader,
     index_writer: IndexWriter,
     text_query_parser: QueryParser,
+    fuzzy_text_analyzers: Vec<(Field, TextAnalyzer)>,
 }
 
 const ID_TOKENIZER: &str = "raw";
 const TAG_TOKENIZER: &str = "tag";
 const TEXT_TOKENIZER: &str = "default";
+const FUZZY_TEXT_DE_TOKENIZER: &str = "fuzzy_de";
+const FUZZY_TEXT_EN_TOKENIZER: &str = "fuzzy_en";
+const FUZZY_TEXT_FR_TOKENIZER: &str = "fuzzy_fr";
 
 const MAX_TOKEN_LEN: usize = 40;
 
@@ -276,6 +479,107 @@ fn register_tokenizers(index: &Index) {
         .filter(RemoveLongFilter::limit(MAX_TOKEN_LEN))
         .build();
     index.tokenizers().register(TEXT_TOKENIZER, text_tokenizer);
+    for (name, language) in [
+        (FUZZY_TEXT_DE_TOKENIZER, Language::German),
+        (FUZZY_TEXT_EN_TOKENIZER, Language::English),
+        (FUZZY_TEXT_FR_TOKENIZER, Language::French),
+    ] {
+        let fuzzy_text_tokenizer = TextAnalyzer::builder(SimpleTokenizer::default())
+            .filter(LowerCaser)
+            .filter(AsciiFoldingFilter)
+            .filter(RemoveLongFilter::limit(MAX_TOKEN_LEN))
+            .filter(Stemmer::new(language))
+            .build();
+        index.tokenizers().register(name, fuzzy_text_tokenizer);
+    }
+}
+
+const TRANSLATION_TITLE_KEY: &str = "tit";
+const TRANSLATION_DESCRIPTION_KEY: &str = "dsc";
+
+// Translations are stored as a JSON object with the language
+// tags as keys, e.g. `{"en": {"tit": "Title", "dsc": "Description"}}`
+fn translations_to_object(translations: &[Translation]) -> BTreeMap<String, OwnedValue> {
+    translations
+        .iter()
+        .map(
+            |Translation {
+                 lang,
+                 title,
+                 description,
+             }| {
+                let mut object = vec![(
+                    TRANSLATION_TITLE_KEY.to_owned(),
+                    OwnedValue::Str(title.clone()),
+                )];
+                if let Some(description) = description {
+                    object.push((
+                        TRANSLATION_DESCRIPTION_KEY.to_owned(),
+                        OwnedValue::Str(description.clone()),
+                    ));
+                }
+                (lang.to_string(), OwnedValue::Object(object))
+            },
+        )
+        .collect()
+}
+
+fn translations_from_value<'a>(value: &impl Value<'a>) -> Option<Vec<Translation>> {
+    let mut translations = Vec::new();
+    for (lang, value) in value.as_object()? {
+        let mut title = None;
+        let mut description = None;
+        for (key, value) in value.as_object()? {
+            match key {
+                TRANSLATION_TITLE_KEY => title = value.as_str(),
+                TRANSLATION_DESCRIPTION_KEY => description = value.as_str(),
+                _ => return None,
+            }
+        }
+        translations.push(Translation {
+            lang: lang.parse().ok()?,
+            title: title?.to_owned(),
+            description: description.map(ToOwned::to_owned),
+        });
+    }
+    Some(translations)
+}
+
+// Replace German umlauts by their transcription before the
+// remaining characters are folded into ASCII by the tokenizer,
+// and append joined variants of hyphenated compound words,
+// e.g. "Bio-Läden" → "bio-laeden bioladen".
+fn fold_text(text: &str) -> String {
+    let mut folded = String::with_capacity(text.len() + text.len() / 4);
+    for c in text.chars().flat_map(char::to_lowercase) {
+        match c {
+            'ä' => folded.push_str("ae"),
+            'ö' => folded.push_str("oe"),
+            'ü' => folded.push_str("ue"),
+            'ß' => folded.push_str("ss"),
+            c => folded.push(c),
+        }
+    }
+    let compounds: Vec<_> = folded
+        .split_whitespace()
+        .filter(|word| word.contains('-'))
+        .map(|word| word.replace('-', ""))
+        .collect();
+    for compound in compounds {
+        folded.push(' ');
+        folded.push_str(&compound);
+    }
+    folded
+}
+
+// The maximum Levenshtein distance for fuzzy matching a
+// term, i.e. short terms need to match exactly.
+const fn max_edit_distance(term_len: usize) -> u8 {
+    match term_len {
+        0..=3 => 0,
+        4..=7 => 1,
+        _ => 2,
+    }
 }
 
 fn f64_to_u64(val: f64, min: f64, max: f64) -> u64 {
@@ -321,6 +625,28 @@ fn u64_to_avg_rating(val: u64) -> AvgRatingValue {
     .into()
 }
 
+// Resolution of the weekly time slots for opening hours
+const OPENING_SLOT_MINUTES: u16 = 5;
+
+const OPENING_SLOTS_PER_DAY: u16 = MINUTES_PER_DAY / OPENING_SLOT_MINUTES;
+
+fn opening_slot(weekday: Weekday, minute_of_day: u16) -> u64 {
+    debug_assert!(minute_of_day < MINUTES_PER_DAY);
+    u64::from(weekday.number_days_from_monday()) * u64::from(OPENING_SLOTS_PER_DAY)
+        + u64::from(minute_of_day / OPENING_SLOT_MINUTES)
+}
+
+// All weekly time slots that start while the place is open.
+fn opening_slots(rules: &OpeningHoursRules) -> impl Iterator<Item = u64> + '_ {
+    (0..7)
+        .map(|days| Weekday::Monday.nth_next(days))
+        .flat_map(|weekday| {
+            (0..OPENING_SLOTS_PER_DAY).map(move |slot| (weekday, slot * OPENING_SLOT_MINUTES))
+        })
+        .filter(|(weekday, minute_of_day)| rules.is_open_at(*weekday, *minute_of_day, false))
+        .map(|(weekday, minute_of_day)| opening_slot(weekday, minute_of_day))
+}
+
 fn map_bound<T>(bound: Bound<T>, f: impl Fn(T) -> Term) -> Bound<Term> {
     match bound {
         Bound::Included(v) => Bound::Included(f(v)),
@@ -352,26 +678,57 @@ enum TopDocsMode {
 
 impl TantivyIndex {
     #[allow(dead_code)]
-    pub fn create_in_ram() -> Fallible<Self> {
+    pub fn create_in_ram(options: IndexOptions) -> Fallible<Self> {
         let no_path: Option<&Path> = None;
-        Self::create(no_path)
+        Self::create(no_path, options)
     }
 
-    pub fn create<P: AsRef<Path>>(path: Option<P>) -> Fallible<Self> {
-        let (fields, schema) = IndexedFields::build_schema();
+    pub fn create<P: AsRef<Path>>(path: Option<P>, options: IndexOptions) -> Fallible<Self> {
+        let (fields, schema) = IndexedFields::build_schema(options);
 
-        // TODO: Open index from existing directory
         let index = if let Some(path) = path {
-            log::info!(
-                "Creating full-text search index in directory: {}",
-                path.as_ref().to_string_lossy()
-            );
-            Index::create_in_dir(path, schema)?
+            let path = path.as_ref();
+            if Index::exists(&MmapDirectory::open(path)?)? {
+                log::info!(
+                    "Opening full-text search index in directory: {}",
+                    path.to_string_lossy()
+                );
+                let index = Index::open_in_dir(path)?;
+                if index.schema() != schema {
+                    bail!(
+                        "Incompatible full-text search index in directory {}: rebuild it with `openfairdb reindex`",
+                        path.to_string_lossy()
+                    );
+                }
+                index
+            } else {
+                log::info!(
+                    "Creating full-text search index in directory: {}",
+                    path.to_string_lossy()
+                );
+                Index::create_in_dir(path, schema)?
+            }
         } else {
             log::warn!("Creating full-text search index in RAM");
             Index::create_in_ram(schema)
         };
+        Self::with_index(index, fields)
+    }
+
+    /// Open an existing index only if its schema matches the current schema.
+    fn open_compatible(path: &Path, options: IndexOptions) -> Fallible<Option<Self>> {
+        if !Index::exists(&MmapDirectory::open(path)?)? {
+            return Ok(None);
+        }
+        let (fields, schema) = IndexedFields::build_schema(options);
+        let index = Index::open_in_dir(path)?;
+        if index.schema() != schema {
+            return Ok(None);
+        }
+        Self::with_index(index, fields).map(Some)
+    }
 
+    fn with_index(index: Index, fields: IndexedFields) -> Fallible<Self> {
         register_tokenizers(&index);
 
         // Prefer to manually reload the index reader during `flush()`
@@ -383,27 +740,46 @@ impl TantivyIndex {
             .reload_policy(ReloadPolicy::Manual)
             .try_into()?;
         let index_writer = index.writer(OVERALL_INDEX_HEAP_SIZE_IN_BYTES)?;
-        let text_query_parser = QueryParser::for_index(
-            &index,
-            vec![
-                fields.title,
-                fields.description,
-                fields.address_street,
-                fields.address_city,
-                fields.address_zip,
-                fields.address_country,
-                fields.address_state,
-                fields.contact_name,
-            ],
-        );
+        let text_query_parser = QueryParser::for_index(&index, fields.full_text_fields().to_vec());
+        let fuzzy_text_analyzers = fields
+            .fuzzy_text_fields()
+            .map(|(field, tokenizer, _)| {
+                let analyzer = index
+                    .tokenizers()
+                    .get(tokenizer)
+                    .expect("registered tokenizer");
+                (field, analyzer)
+            })
+            .collect();
         Ok(Self {
             fields,
             index_reader,
             index_writer,
             text_query_parser,
+            fuzzy_text_analyzers,
         })
     }
 
+    // Matches any term of the text with a tolerance for typos.
+    fn fuzzy_text_query(&self, text: &str) -> Option<BooleanQuery> {
+        let text = fold_text(text);
+        let mut term_queries: Vec<(Occur, Box<dyn Query>)> = Vec::new();
+        for (field, analyzer) in &self.fuzzy_text_analyzers {
+            let mut analyzer = analyzer.clone();
+            let mut token_stream = analyzer.token_stream(&text);
+            token_stream.process(&mut |token| {
+                let distance = max_edit_distance(token.text.chars().count());
+                let term = Term::from_field_text(*field, &token.text);
+                let term_query = FuzzyTermQuery::new(term, distance, true);
+                term_queries.push((Occur::Should, Box::new(term_query)));
+            });
+        }
+        if term_queries.is_empty() {
+            return None;
+        }
+        Some(BooleanQuery::from(term_queries))
+    }
+
     fn build_query(
         &self,
         query_mode: IndexQueryMode,
@@ -650,18 +1026,35 @@ impl TantivyIndex {
             log::debug!("Query text: {}", text);
             debug_assert!(!text.trim().is_empty());
             let text = text.to_lowercase();
+            let mut text_queries: Vec<(Occur, Box<dyn Query>)> = Vec::with_capacity(2);
             match self.text_query_parser.parse_query(&text) {
                 Ok(text_query) => {
-                    if query.hash_tags.is_empty() && query.text_tags.is_empty() {
-                        sub_queries.push((Occur::Must, Box::new(text_query)));
-                    } else {
-                        text_and_tags_queries.push((Occur::Should, Box::new(text_query)));
-                    }
+                    text_queries.push((Occur::Should, text_query));
                 }
                 Err(err) => {
                     log::warn!("Failed to parse query text '{}': {:?}", text, err);
                 }
             }
+            if query.fuzzy {
+                if let Some(fuzzy_text_query) = self.fuzzy_text_query(&text) {
+                    // Exact matches are still scored higher
+                    text_queries.push((Occur::Should, Box::new(fuzzy_text_query)));
+                } else if self.fuzzy_text_analyzers.is_empty() {
+                    log::warn!("Fuzzy text queries are not supported by the index");
+                }
+            }
+            let text_query: Option<Box<dyn Query>> = if text_queries.len() > 1 {
+                Some(Box::new(BooleanQuery::from(text_queries)))
+            } else {
+                text_queries.pop().map(|(_, text_query)| text_query)
+            };
+            if let Some(text_query) = text_query {
+                if query.hash_tags.is_empty() && query.text_tags.is_empty() {
+                    sub_queries.push((Occur::Must, text_query));
+                } else {
+                    text_and_tags_queries.push((Occur::Should, text_query));
+                }
+            }
         }
 
         // Text tags (optional)
@@ -701,6 +1094,18 @@ impl TantivyIndex {
             sub_queries.push((Occur::Must, Box::new(ts_max_query)));
         }
 
+        // Opening hours
+        if let Some(open_at) = query.open_at {
+            log::debug!("Query open at: {}", open_at);
+            let minute_of_day = u16::from(open_at.hour()) * 60 + u16::from(open_at.minute());
+            let slot_term = Term::from_field_u64(
+                self.fields.opening_slot,
+                opening_slot(open_at.weekday(), minute_of_day),
+            );
+            let slot_query = TermQuery::new(slot_term, IndexRecordOption::Basic);
+            sub_queries.push((Occur::Must, Box::new(slot_query)));
+        }
+
         // Boosting the score by the rating does only make sense if the
         // query actually contains search terms or tags. Otherwise the
         // results are sorted only by their rating, e.g. if the query
@@ -741,90 +1146,326 @@ impl TantivyIndex {
 
         let (search_query, top_docs_mode) = self.build_query(query_mode, query);
         let searcher = self.index_reader.searcher();
-        // TODO: Try to combine redundant code from different search strategies
-        match top_docs_mode {
-            TopDocsMode::Score => {
-                let collector = TopDocs::with_limit(limit).order_by_score();
-                let top_docs = searcher.search(&search_query, &collector)?;
-                for (_, doc_addr) in top_docs {
-                    match searcher.doc::<TantivyDocument>(doc_addr) {
-                        Ok(doc) => {
-                            doc_collector.collect_document(doc_addr, doc);
+        let doc_addrs: Vec<_> = if let Some(center) = query
+            .center
+            .filter(|_| query.max_distance.is_some() || query.order_by_distance)
+        {
+            let max_distance = query.max_distance;
+            let order_by_distance = query.order_by_distance;
+            let collector =
+                TopDocs::with_limit(limit).tweak_score(move |segment_reader: &SegmentReader| {
+                    let fast_fields = segment_reader.fast_fields();
+                    let lat_reader = fast_fields.f64(FIELD_NAME_LAT).unwrap();
+                    let lng_reader = fast_fields.f64(FIELD_NAME_LNG).unwrap();
+                    let total_rating_reader = fast_fields.u64(FIELD_NAME_TOTAL_RATING).unwrap();
+                    move |doc: DocId, original_score: Score| {
+                        let distance = lat_reader
+                            .first(doc)
+                            .zip(lng_reader.first(doc))
+                            .and_then(|(lat, lng)| MapPoint::try_from_lat_lng_deg(lat, lng).ok())
+                            .and_then(|pos| MapPoint::distance(center, pos));
+                        let Some(distance) = distance else {
+                            return f64::NEG_INFINITY;
+                        };
+                        if max_distance.is_some_and(|max_distance| distance > max_distance) {
+                            // Excluded from the results, see below
+                            return f64::NEG_INFINITY;
                         }
-                        Err(err) => {
-                            log::warn!("Failed to load document {:?}: {}", doc_addr, err);
+                        if order_by_distance {
+                            return -distance.to_meters();
+                        }
+                        let total_rating = total_rating_reader.first(doc).unwrap_or_default();
+                        match top_docs_mode {
+                            TopDocsMode::Score => f64::from(original_score),
+                            TopDocsMode::Rating => total_rating as f64,
+                            TopDocsMode::ScoreBoostedByRating => {
+                                f64::from(score_boosted_by_rating(original_score, total_rating))
+                            }
                         }
                     }
+                });
+            // Documents outside of the circle are only collected
+            // if the limit has not been reached
+            searcher
+                .search(&search_query, &collector)?
+                .into_iter()
+                .filter(|(score, _)| *score > f64::NEG_INFINITY)
+                .map(|(_, doc_addr)| doc_addr)
+                .collect()
+        } else {
+            match top_docs_mode {
+                TopDocsMode::Score => {
+                    let collector = TopDocs::with_limit(limit).order_by_score();
+                    searcher
+                        .search(&search_query, &collector)?
+                        .into_iter()
+                        .map(|(_, doc_addr)| doc_addr)
+                        .collect()
+                }
+                TopDocsMode::Rating => {
+                    let collector = TopDocs::with_limit(limit)
+                        .order_by_fast_field(FIELD_NAME_TOTAL_RATING, Order::Desc);
+                    let top_docs: Vec<(Option<u64>, _)> =
+                        searcher.search(&search_query, &collector)?;
+                    top_docs.into_iter().map(|(_, doc_addr)| doc_addr).collect()
+                }
+                TopDocsMode::ScoreBoostedByRating => {
+                    let collector = TopDocs::with_limit(limit).tweak_score(
+                        move |segment_reader: &SegmentReader| {
+                            let total_rating_reader = segment_reader
+                                .fast_fields()
+                                .u64(FIELD_NAME_TOTAL_RATING)
+                                .unwrap();
+                            move |doc: DocId, original_score: Score| {
+                                score_boosted_by_rating(
+                                    original_score,
+                                    total_rating_reader.first(doc).unwrap_or_default(),
+                                )
+                            }
+                        },
+                    );
+                    searcher
+                        .search(&search_query, &collector)?
+                        .into_iter()
+                        .map(|(_, doc_addr)| doc_addr)
+                        .collect()
                 }
-                Ok(doc_collector)
             }
-            TopDocsMode::Rating => {
-                let collector = TopDocs::with_limit(limit)
-                    .order_by_fast_field(FIELD_NAME_TOTAL_RATING, Order::Desc);
-                let top_docs: Vec<(Option<u64>, _)> = searcher.search(&search_query, &collector)?;
-                for (_, doc_addr) in top_docs {
-                    match searcher.doc::<TantivyDocument>(doc_addr) {
-                        Ok(doc) => {
-                            doc_collector.collect_document(doc_addr, doc);
-                        }
-                        Err(err) => {
-                            log::warn!("Failed to load document {:?}: {}", doc_addr, err);
-                        }
-                    }
+        };
+        for doc_addr in doc_addrs {
+            match searcher.doc::<TantivyDocument>(doc_addr) {
+                Ok(doc) => {
+                    doc_collector.collect_document(doc_addr, doc);
+                }
+                Err(err) => {
+                    log::warn!("Failed to load document {:?}: {}", doc_addr, err);
                 }
-                Ok(doc_collector)
             }
-            TopDocsMode::ScoreBoostedByRating => {
-                let collector = {
-                    let total_rating_field = FIELD_NAME_TOTAL_RATING;
-                    TopDocs::with_limit(limit).tweak_score(move |segment_reader: &SegmentReader| {
-                        let total_rating_reader = segment_reader
-                            .fast_fields()
-                            .u64(total_rating_field)
-                            .unwrap();
-
-                        move |doc: DocId, original_score: Score| {
-                            let total_rating = f64::from(u64_to_avg_rating(
-                                total_rating_reader.first(doc).unwrap_or_default(),
-                            ));
-                            let boost_factor =
-                                if total_rating < f64::from(AvgRatingValue::default()) {
-                                    // Negative ratings result in a boost factor < 1
-                                    (total_rating - f64::from(AvgRatingValue::min()))
-                                        / (f64::from(AvgRatingValue::default())
-                                            - f64::from(AvgRatingValue::min()))
-                                } else {
-                                    // Default rating results in a boost factor of 1
-                                    // Positive ratings result in a boost factor > 1
-                                    // The total rating is scaled by the number of different rating
-                                    // context variants to
-                                    // achieve better results by emphasizing the rating factor.
-                                    1.0 + f64::from(RatingContext::total_count())
-                                        * (total_rating - f64::from(AvgRatingValue::default()))
-                                };
-                            // Transform the original score by log2() to narrow the range. Otherwise
-                            // the rating boost factor is not powerful enough to promote highly
-                            // rated entries over entries that received a much higher score.
-                            debug_assert!(original_score >= 0.0);
-                            let unboosted_score = (1.0 + original_score).log2();
-                            unboosted_score * (boost_factor as f32)
-                        }
-                    })
-                };
-                let top_docs = searcher.search(&search_query, &collector)?;
-                for (_, doc_addr) in top_docs {
-                    match searcher.doc::<TantivyDocument>(doc_addr) {
-                        Ok(doc) => {
-                            doc_collector.collect_document(doc_addr, doc);
-                        }
-                        Err(err) => {
-                            log::warn!("Failed to load document {:?}: {}", doc_addr, err);
-                        }
-                    }
+        }
+        Ok(doc_collector)
+    }
+}
+
+fn score_boosted_by_rating(original_score: Score, total_rating: u64) -> Score {
+    let total_rating = f64::from(u64_to_avg_rating(total_rating));
+    let boost_factor = if total_rating < f64::from(AvgRatingValue::default()) {
+        // Negative ratings result in a boost factor < 1
+        (total_rating - f64::from(AvgRatingValue::min()))
+            / (f64::from(AvgRatingValue::default()) - f64::from(AvgRatingValue::min()))
+    } else {
+        // Default rating results in a boost factor of 1
+        // Positive ratings result in a boost factor > 1
+        // The total rating is scaled by the number of different rating
+        // context variants to
+        // achieve better results by emphasizing the rating factor.
+        1.0 + f64::from(RatingContext::total_count())
+            * (total_rating - f64::from(AvgRatingValue::default()))
+    };
+    // Transform the original score by log2() to narrow the range. Otherwise
+    // the rating boost factor is not powerful enough to promote highly
+    // rated entries over entries that received a much higher score.
+    debug_assert!(original_score >= 0.0);
+    let unboosted_score = (1.0 + original_score).log2();
+    unboosted_score * (boost_factor as f32)
+}
+
+// Counts the tags of all matching documents
+struct TagCountCollector {
+    // Documents that are farther away from the center are excluded
+    max_distance: Option<(MapPoint, Distance)>,
+}
+
+impl Collector for TagCountCollector {
+    type Fruit = HashMap<String, TagCount>;
+    type Child = TagCountSegmentCollector;
+
+    fn for_segment(
+        &self,
+        _segment_local_id: u32,
+        segment_reader: &SegmentReader,
+    ) -> tantivy::Result<Self::Child> {
+        let tag_column = segment_reader.fast_fields().str(FIELD_NAME_TAG)?;
+        let ord_counts = vec![0; tag_column.as_ref().map_or(0, |c| c.num_terms())];
+        let pos_columns = if self.max_distance.is_some() {
+            let fast_fields = segment_reader.fast_fields();
+            Some((
+                fast_fields.f64(FIELD_NAME_LAT)?,
+                fast_fields.f64(FIELD_NAME_LNG)?,
+            ))
+        } else {
+            None
+        };
+        Ok(TagCountSegmentCollector {
+            tag_column,
+            max_distance: self.max_distance,
+            pos_columns,
+            ord_counts,
+        })
+    }
+
+    fn requires_scoring(&self) -> bool {
+        false
+    }
+
+    fn merge_fruits(&self, fruits: Vec<Self::Fruit>) -> tantivy::Result<Self::Fruit> {
+        let mut merged = HashMap::new();
+        for (tag, count) in fruits.into_iter().flatten() {
+            *merged.entry(tag).or_default() += count;
+        }
+        Ok(merged)
+    }
+}
+
+struct TagCountSegmentCollector {
+    tag_column: Option<StrColumn>,
+    max_distance: Option<(MapPoint, Distance)>,
+    // Latitude and longitude, only needed for `max_distance`
+    pos_columns: Option<(Column<f64>, Column<f64>)>,
+    // Counts indexed by the term ordinal of the segment
+    ord_counts: Vec<TagCount>,
+}
+
+impl SegmentCollector for TagCountSegmentCollector {
+    type Fruit = HashMap<String, TagCount>;
+
+    fn collect(&mut self, doc: DocId, _score: Score) {
+        let Some(tag_column) = &self.tag_column else {
+            return;
+        };
+        if let Some((center, max_distance)) = self.max_distance {
+            let Some((lat_column, lng_column)) = &self.pos_columns else {
+                return;
+            };
+            let Some(pos) = lat_column
+                .first(doc)
+                .zip(lng_column.first(doc))
+                .and_then(|(lat, lng)| MapPoint::try_from_lat_lng_deg(lat, lng).ok())
+            else {
+                return;
+            };
+            if !MapPoint::distance(center, pos).is_some_and(|distance| distance <= max_distance) {
+                return;
+            }
+        }
+        for ord in tag_column.term_ords(doc) {
+            self.ord_counts[ord as usize] += 1;
+        }
+    }
+
+    fn harvest(self) -> Self::Fruit {
+        let Some(tag_column) = self.tag_column else {
+            return Default::default();
+        };
+        let mut tag_counts = HashMap::new();
+        for (ord, count) in self.ord_counts.into_iter().enumerate() {
+            if count == 0 {
+                continue;
+            }
+            let mut tag = String::new();
+            match tag_column.ord_to_str(ord as u64, &mut tag) {
+                Ok(true) => {
+                    tag_counts.insert(tag, count);
+                }
+                Ok(false) => {
+                    log::warn!("Missing tag with ordinal {ord}");
+                }
+                Err(err) => {
+                    log::warn!("Failed to read tag with ordinal {ord}: {err}");
                 }
-                Ok(doc_collector)
             }
         }
+        tag_counts
+    }
+}
+
+// Sums up the positions of all matching documents per grid cell
+#[derive(Debug, Default, Clone, Copy)]
+struct ClusterCell {
+    lat_sum: f64,
+    lng_sum: f64,
+    count: u64,
+}
+
+type ClusterCellIndex = (i64, i64);
+
+// Aggregates the positions of all matching documents into the
+// cells of a regular lat/lng grid
+struct ClusterCollector {
+    cell_size_deg: f64,
+    // Documents that are farther away from the center are excluded
+    max_distance: Option<(MapPoint, Distance)>,
+}
+
+impl Collector for ClusterCollector {
+    type Fruit = HashMap<ClusterCellIndex, ClusterCell>;
+    type Child = ClusterSegmentCollector;
+
+    fn for_segment(
+        &self,
+        _segment_local_id: u32,
+        segment_reader: &SegmentReader,
+    ) -> tantivy::Result<Self::Child> {
+        let fast_fields = segment_reader.fast_fields();
+        Ok(ClusterSegmentCollector {
+            cell_size_deg: self.cell_size_deg,
+            max_distance: self.max_distance,
+            lat_column: fast_fields.f64(FIELD_NAME_LAT)?,
+            lng_column: fast_fields.f64(FIELD_NAME_LNG)?,
+            cells: Default::default(),
+        })
+    }
+
+    fn requires_scoring(&self) -> bool {
+        false
+    }
+
+    fn merge_fruits(&self, fruits: Vec<Self::Fruit>) -> tantivy::Result<Self::Fruit> {
+        let mut merged = HashMap::<_, ClusterCell>::new();
+        for (index, cell) in fruits.into_iter().flatten() {
+            let merged_cell = merged.entry(index).or_default();
+            merged_cell.lat_sum += cell.lat_sum;
+            merged_cell.lng_sum += cell.lng_sum;
+            merged_cell.count += cell.count;
+        }
+        Ok(merged)
+    }
+}
+
+struct ClusterSegmentCollector {
+    cell_size_deg: f64,
+    max_distance: Option<(MapPoint, Distance)>,
+    lat_column: Column<f64>,
+    lng_column: Column<f64>,
+    cells: HashMap<ClusterCellIndex, ClusterCell>,
+}
+
+impl SegmentCollector for ClusterSegmentCollector {
+    type Fruit = HashMap<ClusterCellIndex, ClusterCell>;
+
+    fn collect(&mut self, doc: DocId, _score: Score) {
+        let Some((lat, lng)) = self.lat_column.first(doc).zip(self.lng_column.first(doc)) else {
+            return;
+        };
+        let Ok(pos) = MapPoint::try_from_lat_lng_deg(lat, lng) else {
+            return;
+        };
+        if let Some((center, max_distance)) = self.max_distance
+            && !MapPoint::distance(center, pos).is_some_and(|distance| distance <= max_distance)
+        {
+            return;
+        }
+        let index = (
+            (lat / self.cell_size_deg).floor() as i64,
+            (lng / self.cell_size_deg).floor() as i64,
+        );
+        let cell = self.cells.entry(index).or_default();
+        cell.lat_sum += lat;
+        cell.lng_sum += lng;
+        cell.count += 1;
+    }
+
+    fn harvest(self) -> Self::Fruit {
+        self.cells
     }
 }
 
@@ -908,6 +1549,42 @@ impl IdIndex for TantivyIndex {
         self.query_documents(query_mode, query, limit, collector)
             .map(Into::into)
     }
+
+    fn all_indexed_entries(&self) -> Fallible<Vec<IndexedEntry>> {
+        let searcher = self.index_reader.searcher();
+        let mut entries = Vec::with_capacity(searcher.num_docs() as usize);
+        for (kind, kind_flag) in [
+            (IndexedKind::Place, PLACE_KIND_FLAG),
+            (IndexedKind::Event, EVENT_KIND_FLAG),
+        ] {
+            let kind_query = TermQuery::new(
+                Term::from_field_i64(self.fields.kind, kind_flag),
+                IndexRecordOption::Basic,
+            );
+            for doc_addr in searcher.search(&kind_query, &DocSetCollector)? {
+                let doc = searcher.doc::<TantivyDocument>(doc_addr)?;
+                let Some(id) = doc.get_first(self.fields.id).and_then(|v| v.as_str()) else {
+                    log::error!("Document ({:?}) has no id", doc_addr);
+                    continue;
+                };
+                let status = doc
+                    .get_first(self.fields.status)
+                    .and_then(|v| v.as_i64())
+                    .and_then(|v| ReviewStatus::try_from(v as ReviewStatusPrimitive).ok());
+                let revision = doc
+                    .get_first(self.fields.revision)
+                    .and_then(|v| v.as_u64())
+                    .map(Revision::from);
+                entries.push(IndexedEntry {
+                    kind,
+                    id: id.into(),
+                    status,
+                    revision,
+                });
+            }
+        }
+        Ok(entries)
+    }
 }
 
 impl Indexer for TantivyIndex {
@@ -943,10 +1620,12 @@ impl PlaceIndexer for TantivyIndex {
             doc.add_i64(self.fields.status, status);
         }
         doc.add_text(self.fields.id, &place.id);
+        doc.add_u64(self.fields.revision, place.revision.into());
         doc.add_f64(self.fields.lat, place.location.pos.lat().to_deg());
         doc.add_f64(self.fields.lng, place.location.pos.lng().to_deg());
         doc.add_text(self.fields.title, &place.title);
         doc.add_text(self.fields.description, &place.description);
+        self.fields.add_translations(&mut doc, &place.translations);
         if let Some(ref address) = place.location.address {
             let Address {
                 street,
@@ -980,6 +1659,23 @@ impl PlaceIndexer for TantivyIndex {
         for tag in &place.tags {
             doc.add_text(self.fields.tag, tag);
         }
+        if let Some(ref opening_hours) = place.opening_hours {
+            match opening_hours.rules() {
+                Ok(rules) => {
+                    for slot in opening_slots(&rules) {
+                        doc.add_u64(self.fields.opening_slot, slot);
+                    }
+                }
+                Err(err) => {
+                    log::info!(
+                        "Place {} has invalid opening hours '{}': {}",
+                        place.id,
+                        opening_hours.as_str(),
+                        err
+                    );
+                }
+            }
+        }
         doc.add_u64(self.fields.total_rating, avg_rating_to_u64(ratings.total()));
         doc.add_f64(self.fields.ratings_diversity, ratings.diversity.into());
         doc.add_f64(self.fields.ratings_fairness, ratings.fairness.into());
@@ -990,6 +1686,7 @@ impl PlaceIndexer for TantivyIndex {
             self.fields.ratings_transparency,
             ratings.transparency.into(),
         );
+        self.fields.add_fuzzy_text(&mut doc, &place.translations);
         self.index_writer.add_document(doc)?;
         Ok(())
     }
@@ -1002,6 +1699,7 @@ impl EventIndexer for TantivyIndex {
         let mut doc = TantivyDocument::default();
         doc.add_i64(self.fields.kind, EVENT_KIND_FLAG);
         doc.add_text(self.fields.id, &event.id);
+        doc.add_u64(self.fields.revision, event.revision.into());
         if let Some(ref location) = event.location {
             doc.add_f64(self.fields.lat, location.pos.lat().to_deg());
             doc.add_f64(self.fields.lng, location.pos.lng().to_deg());
@@ -1030,15 +1728,32 @@ impl EventIndexer for TantivyIndex {
                 }
             }
         }
-        doc.add_i64(self.fields.ts_min, event.start.as_secs());
-        if let Some(end) = event.end {
+        // Recurring events are indexed with the time stamps of all
+        // their occurrences and filtered again after expansion.
+        let duration = event.end.map(|end| {
             debug_assert!(event.start <= end);
-            doc.add_i64(self.fields.ts_max, end.as_secs());
+            end.as_secs() - event.start.as_secs()
+        });
+        let mut occurrence_starts = event.occurrence_starts();
+        for start in occurrence_starts.by_ref().take(MAX_INDEXED_OCCURRENCES) {
+            doc.add_i64(self.fields.ts_min, start.as_secs());
+            if let Some(duration) = duration {
+                doc.add_i64(self.fields.ts_max, start.as_secs() + duration);
+            }
+        }
+        if occurrence_starts.next().is_some() {
+            // Remaining occurrences are only found by
+            // queries without an upper bound
+            doc.add_i64(self.fields.ts_min, i64::MAX);
+            if duration.is_some() {
+                doc.add_i64(self.fields.ts_max, i64::MAX);
+            }
         }
         doc.add_text(self.fields.title, &event.title);
         if let Some(ref description) = event.description {
             doc.add_text(self.fields.description, description);
         }
+        self.fields.add_translations(&mut doc, &event.translations);
         if let Some(ref contact) = event.contact {
             let Contact { name, .. } = contact;
             if let Some(contact_name) = name {
@@ -1048,6 +1763,7 @@ impl EventIndexer for TantivyIndex {
         for tag in &event.tags {
             doc.add_text(self.fields.tag, tag);
         }
+        self.fields.add_fuzzy_text(&mut doc, &event.translations);
         self.index_writer.add_document(doc)?;
         Ok(())
     }
@@ -1059,30 +1775,314 @@ impl PlaceIndex for TantivyIndex {
         self.query_documents(IndexQueryMode::WithRating, query, limit, collector)
             .map(Into::into)
     }
+
+    fn count_place_tags(&self, query: &IndexQuery) -> Fallible<Vec<TagFrequency>> {
+        let (search_query, _) = self.build_query(IndexQueryMode::WithoutRating, query);
+        let searcher = self.index_reader.searcher();
+        let collector = TagCountCollector {
+            max_distance: query.center.zip(query.max_distance),
+        };
+        let tag_counts = searcher.search(&search_query, &collector)?;
+        let mut tag_frequencies: Vec<_> = tag_counts
+            .into_iter()
+            .map(|(tag, count)| TagFrequency(tag, count))
+            .collect();
+        tag_frequencies
+            .sort_unstable_by(|lhs, rhs| rhs.1.cmp(&lhs.1).then_with(|| lhs.0.cmp(&rhs.0)));
+        Ok(tag_frequencies)
+    }
+
+    fn cluster_places(
+        &self,
+        query: &IndexQuery,
+        cell_size_deg: f64,
+    ) -> Fallible<Vec<PlaceCluster>> {
+        if !cell_size_deg.is_finite() || cell_size_deg <= 0.0 {
+            bail!("Invalid cell size: {}", cell_size_deg);
+        }
+        let (search_query, _) = self.build_query(IndexQueryMode::WithoutRating, query);
+        let searcher = self.index_reader.searcher();
+        let collector = ClusterCollector {
+            cell_size_deg,
+            max_distance: query.center.zip(query.max_distance),
+        };
+        let cells = searcher.search(&search_query, &collector)?;
+        let mut clusters: Vec<_> = cells
+            .into_values()
+            .filter_map(|cell| {
+                let count = cell.count as f64;
+                let centroid =
+                    MapPoint::try_from_lat_lng_deg(cell.lat_sum / count, cell.lng_sum / count)
+                        .ok()?;
+                Some(PlaceCluster {
+                    centroid,
+                    count: cell.count,
+                })
+            })
+            .collect();
+        clusters.sort_unstable_by(|lhs, rhs| {
+            rhs.count.cmp(&lhs.count).then_with(|| {
+                (lhs.centroid.lat().to_deg(), lhs.centroid.lng().to_deg())
+                    .partial_cmp(&(rhs.centroid.lat().to_deg(), rhs.centroid.lng().to_deg()))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+        });
+        Ok(clusters)
+    }
 }
 
 impl EventAndPlaceIndexer for TantivyIndex {}
 
+fn versioned_index_dir(index_dir: &Path, version: u32) -> PathBuf {
+    index_dir.join(format!("v{version}"))
+}
+
+// Indexes of other versions, most recent first. An unversioned
+// index that is stored directly in the index directory comes last.
+fn outdated_index_dirs(index_dir: &Path) -> Fallible<Vec<PathBuf>> {
+    let mut versions = Vec::new();
+    for entry in fs::read_dir(index_dir)? {
+        let entry = entry?;
+        let version = entry
+            .file_name()
+            .to_str()
+            .and_then(|name| name.strip_prefix('v'))
+            .and_then(|version| version.parse::<u32>().ok());
+        if let Some(version) = version
+            && version != INDEX_VERSION
+            && entry.file_type()?.is_dir()
+        {
+            versions.push(version);
+        }
+    }
+    versions.sort_unstable_by(|lhs, rhs| rhs.cmp(lhs));
+    let mut dirs: Vec<_> = versions
+        .into_iter()
+        .map(|version| versioned_index_dir(index_dir, version))
+        .collect();
+    if Index::exists(&MmapDirectory::open(index_dir)?)? {
+        dirs.push(index_dir.to_path_buf());
+    }
+    Ok(dirs)
+}
+
+/// Lock all search indexes in the given index directory for writing.
+///
+/// Fails if one of the indexes is opened by another writer, e.g. by
+/// a running server. The locks are released when dropped.
+pub fn lock_index_dir(index_dir: &Path) -> Fallible<Vec<DirectoryLock>> {
+    if !index_dir.is_dir() {
+        return Ok(vec![]);
+    }
+    let mut dirs = outdated_index_dirs(index_dir)?;
+    let current_dir = versioned_index_dir(index_dir, INDEX_VERSION);
+    if current_dir.is_dir() {
+        dirs.insert(0, current_dir);
+    }
+    dirs.into_iter()
+        .map(|dir| {
+            MmapDirectory::open(&dir)?
+                .acquire_lock(&INDEX_WRITER_LOCK)
+                .map_err(|err| match err {
+                    LockError::LockBusy => {
+                        anyhow!("The search index in {} is in use", dir.display())
+                    }
+                    err => err.into(),
+                })
+        })
+        .collect()
+}
+
+// Outdated indexes that are replaced by rebuilding the current index
+struct Migration {
+    index_dir: PathBuf,
+    outdated_dirs: Vec<PathBuf>,
+    // Serves all queries until the current index has been rebuilt
+    // if available, i.e. if the schema didn't change
+    outdated_index: Option<TantivyIndex>,
+    // Serves all queries otherwise if available
+    fallback_index: Option<Box<dyn EventAndPlaceIndex + Send>>,
+}
+
+impl Migration {
+    fn remove_outdated_dirs(self) -> Fallible<()> {
+        let Self {
+            index_dir,
+            outdated_dirs,
+            outdated_index,
+            fallback_index: _,
+        } = self;
+        // Release the lock on the directory
+        drop(outdated_index);
+        for dir in outdated_dirs {
+            log::info!("Removing outdated search index in {}", dir.display());
+            if dir == index_dir {
+                // Only the files of the unversioned index are stored directly
+                // in the index directory, not the subdirectories
+                for entry in fs::read_dir(&dir)? {
+                    let entry = entry?;
+                    if entry.file_type()?.is_file() {
+                        fs::remove_file(entry.path())?;
+                    }
+                }
+            } else {
+                fs::remove_dir_all(dir)?;
+            }
+        }
+        Ok(())
+    }
+}
+
 #[derive(Clone)]
-pub struct SearchEngine(Arc<Mutex<Box<dyn EventAndPlaceIndexer + Send>>>);
+pub struct SearchEngine {
+    index: Arc<Mutex<Box<dyn EventAndPlaceIndexer + Send>>>,
+    migration: Arc<Mutex<Option<Migration>>>,
+    // Revision counter of the indexed documents
+    revision: Arc<AtomicU64>,
+}
 
 impl SearchEngine {
     #[allow(dead_code)]
     pub fn init_in_ram() -> Fallible<SearchEngine> {
-        let index = TantivyIndex::create_in_ram()?;
-        Ok(SearchEngine(Arc::new(Mutex::new(Box::new(index)))))
+        let index = TantivyIndex::create_in_ram(IndexOptions::default())?;
+        Ok(Self::new(index, None))
+    }
+
+    fn new(index: TantivyIndex, migration: Option<Migration>) -> Self {
+        Self {
+            index: Arc::new(Mutex::new(Box::new(index))),
+            migration: Arc::new(Mutex::new(migration)),
+            revision: Default::default(),
+        }
+    }
+
+    /// The current revision of the index that is incremented
+    /// whenever documents are added, updated, or removed,
+    /// e.g. for invalidating cached search results.
+    pub fn revision(&self) -> u64 {
+        self.revision.load(AtomicOrdering::Acquire)
+    }
+
+    fn increment_revision(&self) {
+        self.revision.fetch_add(1, AtomicOrdering::AcqRel);
     }
 
     pub fn init_with_path<P: AsRef<Path>>(path: Option<P>) -> Fallible<SearchEngine> {
-        let index = TantivyIndex::create(path)?;
-        Ok(SearchEngine(Arc::new(Mutex::new(Box::new(index)))))
+        Self::init_with_path_and_options(path, IndexOptions::default())
+    }
+
+    /// Open or create the index of the current [`INDEX_VERSION`]
+    /// in a subdirectory of the given index directory.
+    ///
+    /// If indexes of other versions are found the index needs to be
+    /// rebuilt, see [`Self::is_migrating()`].
+    pub fn init_with_path_and_options<P: AsRef<Path>>(
+        path: Option<P>,
+        options: IndexOptions,
+    ) -> Fallible<SearchEngine> {
+        let Some(index_dir) = path else {
+            let index = TantivyIndex::create_in_ram(options)?;
+            return Ok(Self::new(index, None));
+        };
+        let index_dir = index_dir.as_ref();
+        let current_dir = versioned_index_dir(index_dir, INDEX_VERSION);
+        fs::create_dir_all(&current_dir)?;
+        let index = TantivyIndex::create(Some(&current_dir), options)?;
+        let outdated_dirs = outdated_index_dirs(index_dir)?;
+        if outdated_dirs.is_empty() {
+            return Ok(Self::new(index, None));
+        }
+        log::warn!(
+            "Found outdated search indexes that need to be rebuilt: {}",
+            outdated_dirs
+                .iter()
+                .map(|dir| dir.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        let mut outdated_index = None;
+        for dir in &outdated_dirs {
+            match TantivyIndex::open_compatible(dir, options) {
+                Ok(Some(index)) => {
+                    log::info!(
+                        "Using outdated search index in {} until rebuilt",
+                        dir.display()
+                    );
+                    outdated_index = Some(index);
+                    break;
+                }
+                Ok(None) => {}
+                Err(err) => {
+                    log::warn!(
+                        "Failed to open outdated search index in {}: {err}",
+                        dir.display()
+                    );
+                }
+            }
+        }
+        if outdated_index.is_none() {
+            log::warn!("No compatible search index available until rebuilt");
+        }
+        let migration = Migration {
+            index_dir: index_dir.to_path_buf(),
+            outdated_dirs,
+            outdated_index,
+            fallback_index: None,
+        };
+        Ok(Self::new(index, Some(migration)))
+    }
+
+    /// Indicates that the index needs to be rebuilt after
+    /// upgrading from an outdated version.
+    ///
+    /// Queries are served by a compatible outdated index if available
+    /// until [`Self::finish_migration()`] is invoked. Otherwise they are
+    /// served by the fallback index, see [`Self::set_migration_fallback()`].
+    pub fn is_migrating(&self) -> bool {
+        self.migration.lock().is_some()
+    }
+
+    /// Serve queries by the given index until the migration has finished
+    /// if no compatible outdated index is available.
+    ///
+    /// Has no effect if not migrating.
+    pub fn set_migration_fallback(&self, index: Box<dyn EventAndPlaceIndex + Send>) {
+        if let Some(migration) = self.migration.lock().as_mut() {
+            migration.fallback_index = Some(index);
+        }
+    }
+
+    /// Switch to the rebuilt index and remove all outdated indexes.
+    pub fn finish_migration(&self) -> Fallible<()> {
+        let Some(migration) = self.migration.lock().take() else {
+            return Ok(());
+        };
+        // Queries are now served by the rebuilt index
+        self.increment_revision();
+        migration.remove_outdated_dirs()
+    }
+
+    fn with_query_index<T>(&self, query: impl FnOnce(&dyn EventAndPlaceIndex) -> T) -> T {
+        let migration = self.migration.lock();
+        if let Some(migration) = migration.as_ref() {
+            if let Some(outdated_index) = &migration.outdated_index {
+                return query(outdated_index);
+            }
+            if let Some(fallback_index) = &migration.fallback_index {
+                return query(&**fallback_index);
+            }
+        }
+        drop(migration);
+        query(&**self.index.lock())
     }
 }
 
 impl Indexer for SearchEngine {
     fn flush_index(&mut self) -> Fallible<()> {
-        let mut inner = self.0.lock();
-        inner.flush_index()
+        let mut inner = self.index.lock();
+        let res = inner.flush_index();
+        self.increment_revision();
+        res
     }
 }
 
@@ -1093,22 +2093,39 @@ impl IdIndex for SearchEngine {
         query: &IndexQuery,
         limit: usize,
     ) -> Fallible<Vec<Id>> {
-        let inner = self.0.lock();
-        inner.query_ids(mode, query, limit)
+        self.with_query_index(|index| index.query_ids(mode, query, limit))
+    }
+
+    fn all_indexed_entries(&self) -> Fallible<Vec<IndexedEntry>> {
+        let inner = self.index.lock();
+        inner.all_indexed_entries()
     }
 }
 
 impl IdIndexer for SearchEngine {
     fn remove_by_id(&self, id: &Id) -> Fallible<()> {
-        let inner = self.0.lock();
-        inner.remove_by_id(id)
+        let inner = self.index.lock();
+        let res = inner.remove_by_id(id);
+        self.increment_revision();
+        res
     }
 }
 
 impl PlaceIndex for SearchEngine {
     fn query_places(&self, query: &IndexQuery, limit: usize) -> Fallible<Vec<IndexedPlace>> {
-        let inner = self.0.lock();
-        inner.query_places(query, limit)
+        self.with_query_index(|index| index.query_places(query, limit))
+    }
+
+    fn count_place_tags(&self, query: &IndexQuery) -> Fallible<Vec<TagFrequency>> {
+        self.with_query_index(|index| index.count_place_tags(query))
+    }
+
+    fn cluster_places(
+        &self,
+        query: &IndexQuery,
+        cell_size_deg: f64,
+    ) -> Fallible<Vec<PlaceCluster>> {
+        self.with_query_index(|index| index.cluster_places(query, cell_size_deg))
     }
 }
 
@@ -1119,15 +2136,19 @@ impl PlaceIndexer for SearchEngine {
         status: ReviewStatus,
         ratings: &AvgRatings,
     ) -> Fallible<()> {
-        let inner = self.0.lock();
-        inner.add_or_update_place(place, status, ratings)
+        let inner = self.index.lock();
+        let res = inner.add_or_update_place(place, status, ratings);
+        self.increment_revision();
+        res
     }
 }
 
 impl EventIndexer for SearchEngine {
     fn add_or_update_event(&self, event: &Event) -> Fallible<()> {
-        let inner = self.0.lock();
-        inner.add_or_update_event(event)
+        let inner = self.index.lock();
+        let res = inner.add_or_update_event(event);
+        self.increment_revision();
+        res
     }
 }
 
@@ -0,0 +1,150 @@
+use std::{fmt, str::FromStr};
+
+use sha2::{Digest, Sha256};
+use strum::{EnumIter, EnumString, IntoStaticStr};
+
+use crate::{id::Id, nonce::Nonce, time::Timestamp};
+
+/// Permissions that could be granted to an API token.
+#[rustfmt::skip]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, EnumIter, EnumString, IntoStaticStr)]
+pub enum ApiTokenScope {
+    #[strum(serialize = "places:write")]
+    PlacesWrite,
+    #[strum(serialize = "events:read")]
+    EventsRead,
+    #[strum(serialize = "events:write")]
+    EventsWrite,
+    #[strum(serialize = "history:read")]
+    HistoryRead,
+    #[strum(serialize = "clearance:read")]
+    ClearanceRead,
+    #[strum(serialize = "clearance:write")]
+    ClearanceWrite,
+    #[strum(serialize = "export:csv")]
+    ExportCsv,
+    #[strum(serialize = "export:ical")]
+    ExportIcal,
+    #[strum(serialize = "export:geojson")]
+    ExportGeoJson,
+}
+
+impl ApiTokenScope {
+    pub fn as_str(self) -> &'static str {
+        self.into()
+    }
+}
+
+impl fmt::Display for ApiTokenScope {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A named API token of an organization.
+///
+/// Only the hash of the secret token is stored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiToken {
+    pub id: Id,
+    pub org_id: Id,
+    pub name: String,
+    pub token_hash: String,
+    pub scopes: Vec<ApiTokenScope>,
+    pub created_at: Timestamp,
+    pub expires_at: Option<Timestamp>,
+    pub last_used_at: Option<Timestamp>,
+    pub revoked_at: Option<Timestamp>,
+}
+
+impl ApiToken {
+    /// Generate a new, random secret.
+    pub fn generate_secret() -> String {
+        format!("{}{}", Nonce::new(), Nonce::new())
+    }
+
+    /// The hash of a secret as stored in the database.
+    pub fn hash_secret(secret: &str) -> String {
+        Sha256::digest(secret.as_bytes())
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect()
+    }
+
+    pub fn is_valid_at(&self, at: Timestamp) -> bool {
+        self.revoked_at.is_none() && self.expires_at.is_none_or(|expires_at| at < expires_at)
+    }
+
+    pub fn has_scope(&self, scope: ApiTokenScope) -> bool {
+        self.scopes.contains(&scope)
+    }
+}
+
+/// Parse a whitespace separated list of scopes.
+pub fn parse_api_token_scopes(
+    s: &str,
+) -> Result<Vec<ApiTokenScope>, <ApiTokenScope as FromStr>::Err> {
+    let mut scopes = s
+        .split_whitespace()
+        .map(ApiTokenScope::from_str)
+        .collect::<Result<Vec<_>, _>>()?;
+    scopes.sort_unstable();
+    scopes.dedup();
+    Ok(scopes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::Duration;
+
+    fn token() -> ApiToken {
+        ApiToken {
+            id: Id::new(),
+            org_id: Id::new(),
+            name: "test".into(),
+            token_hash: ApiToken::hash_secret("secret"),
+            scopes: vec![ApiTokenScope::EventsWrite],
+            created_at: Timestamp::now(),
+            expires_at: None,
+            last_used_at: None,
+            revoked_at: None,
+        }
+    }
+
+    #[test]
+    fn parse_and_format_scopes() {
+        let scopes = parse_api_token_scopes("export:csv events:write  export:csv").unwrap();
+        assert_eq!(
+            scopes,
+            vec![ApiTokenScope::EventsWrite, ApiTokenScope::ExportCsv]
+        );
+        assert_eq!(ApiTokenScope::ClearanceRead.to_string(), "clearance:read");
+        assert!(parse_api_token_scopes("events:delete").is_err());
+    }
+
+    #[test]
+    fn hash_secret() {
+        let hash = ApiToken::hash_secret("secret");
+        assert_eq!(hash.len(), 64);
+        assert_eq!(hash, ApiToken::hash_secret("secret"));
+        assert_ne!(hash, ApiToken::hash_secret("Secret"));
+        assert_ne!(ApiToken::generate_secret(), ApiToken::generate_secret());
+    }
+
+    #[test]
+    fn validity() {
+        let now = Timestamp::now();
+        assert!(token().is_valid_at(now));
+        let expired = ApiToken {
+            expires_at: Some(now - Duration::seconds(1)),
+            ..token()
+        };
+        assert!(!expired.is_valid_at(now));
+        let revoked = ApiToken {
+            revoked_at: Some(now),
+            ..token()
+        };
+        assert!(!revoked.is_valid_at(now));
+    }
+}
@@ -3,14 +3,16 @@ pub trait Builder {
     fn build() -> Self::Build;
 }
 
-pub use self::{address_builder::*, place_builder::*};
+pub use self::{address_builder::*, event_builder::*, place_builder::*};
 
 pub mod place_builder {
 
     use std::str::FromStr;
 
     use super::*;
-    use crate::{activity::*, geo::*, id::*, links::*, location::*, place::*, revision::*};
+    use crate::{
+        activity::*, geo::*, id::*, links::*, location::*, place::*, revision::*, translation::*,
+    };
 
     #[derive(Debug)]
     pub struct PlaceBuild {
@@ -42,6 +44,10 @@ pub mod place_builder {
             self.place.tags = tags.into_iter().map(|x| x.into()).collect();
             self
         }
+        pub fn translations(mut self, translations: Vec<Translation>) -> Self {
+            self.place.translations = translations;
+            self
+        }
         pub fn license(mut self, license: &str) -> Self {
             self.place.license = license.into();
             self
@@ -111,6 +117,71 @@ pub mod place_builder {
                     founded_on: None,
                     links: None,
                     tags: vec![],
+                    translations: vec![],
+                },
+            }
+        }
+    }
+}
+
+pub mod event_builder {
+
+    use super::*;
+    use crate::{event::*, id::*, revision::*, time::*};
+
+    #[derive(Debug)]
+    pub struct EventBuild {
+        event: Event,
+    }
+
+    impl EventBuild {
+        pub fn id(mut self, id: &str) -> Self {
+            self.event.id = id.into();
+            self
+        }
+        pub fn revision(mut self, v: u64) -> Self {
+            self.event.revision = v.into();
+            self
+        }
+        pub fn title(mut self, title: &str) -> Self {
+            self.event.title = title.into();
+            self
+        }
+        pub fn tags(mut self, tags: Vec<impl Into<String>>) -> Self {
+            self.event.tags = tags.into_iter().map(|x| x.into()).collect();
+            self
+        }
+        pub fn created_by(mut self, email: &str) -> Self {
+            self.event.created_by = Some(email.parse().unwrap());
+            self
+        }
+        pub fn finish(self) -> Event {
+            self.event
+        }
+    }
+
+    impl Builder for Event {
+        type Build = EventBuild;
+        fn build() -> EventBuild {
+            EventBuild {
+                event: Event {
+                    id: Id::new(),
+                    revision: Revision::initial(),
+                    title: "".into(),
+                    description: None,
+                    start: Timestamp::try_from_secs(0).unwrap(),
+                    end: None,
+                    location: None,
+                    contact: None,
+                    tags: vec![],
+                    homepage: None,
+                    created_by: None,
+                    registration: None,
+                    archived: None,
+                    image_url: None,
+                    image_link_url: None,
+                    recurrence: None,
+                    translations: vec![],
                 },
             }
         }
@@ -0,0 +1,123 @@
+use std::{fmt, num::ParseIntError, str::FromStr};
+
+use strum::{EnumString, IntoStaticStr};
+use thiserror::Error;
+
+use crate::{id::Id, review::ReviewStatus, revision::Revision, time::Timestamp};
+
+/// The position of a change in the feed of all changes.
+///
+/// Changes are numbered consecutively in the order in which
+/// they have been committed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ChangeCursor(i64);
+
+impl From<i64> for ChangeCursor {
+    fn from(from: i64) -> Self {
+        Self(from)
+    }
+}
+
+impl From<ChangeCursor> for i64 {
+    fn from(from: ChangeCursor) -> Self {
+        from.0
+    }
+}
+
+impl fmt::Display for ChangeCursor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ChangeCursorParseError {
+    #[error(transparent)]
+    Number(#[from] ParseIntError),
+    #[error("Negative cursor")]
+    Negative,
+}
+
+impl FromStr for ChangeCursor {
+    type Err = ChangeCursorParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let seq = s.parse::<i64>()?;
+        if seq < 0 {
+            return Err(ChangeCursorParseError::Negative);
+        }
+        Ok(Self(seq))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, IntoStaticStr)]
+#[strum(serialize_all = "lowercase")]
+pub enum ChangeKind {
+    Created,
+    Updated,
+    /// The review status of a place has been changed,
+    /// e.g. it has been confirmed or restored.
+    Reviewed,
+    Archived,
+    Rejected,
+    /// Events could be deleted by their owner
+    Deleted,
+}
+
+impl ChangeKind {
+    pub fn as_str(self) -> &'static str {
+        self.into()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChangedItem {
+    Place {
+        id: Id,
+        revision: Revision,
+        status: ReviewStatus,
+    },
+    Event {
+        id: Id,
+        /// Only available for created or updated events
+        revision: Option<Revision>,
+    },
+    Rating {
+        id: Id,
+        place_id: Id,
+    },
+}
+
+/// A single entry of the change feed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    pub cursor: ChangeCursor,
+    pub changed_at: Timestamp,
+    pub kind: ChangeKind,
+    pub item: ChangedItem,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cursor_roundtrip() {
+        let cursor = ChangeCursor::from(42);
+        assert_eq!(cursor.to_string(), "42");
+        assert_eq!(cursor, cursor.to_string().parse().unwrap());
+    }
+
+    #[test]
+    fn parse_invalid_cursor() {
+        assert!("".parse::<ChangeCursor>().is_err());
+        assert!("-1".parse::<ChangeCursor>().is_err());
+        assert!("1.2".parse::<ChangeCursor>().is_err());
+        assert!("a".parse::<ChangeCursor>().is_err());
+    }
+
+    #[test]
+    fn parse_kind() {
+        assert_eq!(Ok(ChangeKind::Deleted), "deleted".parse());
+        assert_eq!("reviewed", ChangeKind::Reviewed.as_str());
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{id::Id, revision::Revision, time::Timestamp};
+use crate::{activity::ActivityLog, id::Id, revision::Revision, time::Timestamp};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PendingClearanceForPlace {
@@ -12,3 +12,32 @@ pub struct ClearanceForPlace {
     pub place_id: Id,
     pub cleared_revision: Option<Revision>,
 }
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingClearanceForEvent {
+    pub event_id: Id,
+    pub created_at: Timestamp,
+    pub last_cleared_revision: Option<Revision>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClearanceForEvent {
+    pub event_id: Id,
+    pub cleared_revision: Option<Revision>,
+}
+
+/// A recorded clearance of a place revision.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClearanceLogForPlace {
+    pub place_id: Id,
+    pub cleared_revision: Revision,
+    pub activity: ActivityLog,
+}
+
+/// A recorded clearance of an event revision.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClearanceLogForEvent {
+    pub event_id: Id,
+    pub cleared_revision: Revision,
+    pub activity: ActivityLog,
+}
@@ -0,0 +1,69 @@
+use std::fmt;
+
+use strum::{EnumString, IntoStaticStr};
+
+use crate::{activity::Activity, id::Id, time::Timestamp};
+
+/// The review state of a pair of possibly duplicate places.
+#[rustfmt::skip]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, IntoStaticStr)]
+#[strum(serialize_all = "lowercase")]
+pub enum DuplicateCandidateState {
+    /// Waiting for a review
+    Open,
+    /// The places are not duplicates
+    Dismissed,
+    /// The places have been merged
+    Merged,
+}
+
+impl DuplicateCandidateState {
+    pub fn as_str(self) -> &'static str {
+        self.into()
+    }
+}
+
+impl fmt::Display for DuplicateCandidateState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A pair of places that are possibly duplicates.
+///
+/// Each pair is stored only once with the lower id first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicateCandidate {
+    pub place_id: Id,
+    pub duplicate_place_id: Id,
+    /// The score of the detected duplicate from 0.0 (different)
+    /// to 1.0 (most likely identical)
+    pub similarity: f64,
+    pub detected_at: Timestamp,
+    pub state: DuplicateCandidateState,
+    pub reviewed: Option<Activity>,
+}
+
+impl DuplicateCandidate {
+    /// Order the ids of a pair of places.
+    pub fn ordered_pair(lhs: Id, rhs: Id) -> (Id, Id) {
+        if lhs <= rhs { (lhs, rhs) } else { (rhs, lhs) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_state() {
+        for state in [
+            DuplicateCandidateState::Open,
+            DuplicateCandidateState::Dismissed,
+            DuplicateCandidateState::Merged,
+        ] {
+            assert_eq!(Ok(state), state.as_str().parse());
+        }
+        assert_eq!("merged", DuplicateCandidateState::Merged.to_string());
+    }
+}
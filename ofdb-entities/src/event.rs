@@ -1,6 +1,11 @@
 use std::str::FromStr;
 
-use crate::{contact::*, email::*, id::*, location::*, time::*, url::*};
+use time::Duration;
+
+use crate::{
+    activity::*, contact::*, email::*, id::*, location::*, recurrence::*, revision::*, time::*,
+    translation::*, url::*,
+};
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum RegistrationType {
@@ -28,6 +33,7 @@ impl FromStr for RegistrationType {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Event {
     pub id           : Id,
+    pub revision     : Revision,
     pub title        : String,
     pub description  : Option<String>,
     // Both start/end time stamps are stored with second precision!
@@ -45,6 +51,8 @@ pub struct Event {
     pub archived     : Option<Timestamp>,
     pub image_url     : Option<Url>,
     pub image_link_url: Option<Url>,
+    pub recurrence    : Option<Recurrence>,
+    pub translations  : Vec<Translation>,
 }
 
 impl Event {
@@ -69,12 +77,52 @@ impl Event {
         }
     }
 
+    /// The start times of all occurrences in chronological order.
+    ///
+    /// The iterator is infinite for recurring events without an end.
+    pub fn occurrence_starts(&self) -> Box<dyn Iterator<Item = Timestamp> + '_> {
+        match &self.recurrence {
+            Some(recurrence) => Box::new(recurrence.occurrences(self.start)),
+            None => Box::new(std::iter::once(self.start)),
+        }
+    }
+
+    /// A single occurrence of this event with the same duration.
+    pub fn occurrence(&self, start: Timestamp) -> Self {
+        let end = self
+            .end
+            .map(|end| start + Duration::seconds(end.as_secs() - self.start.as_secs()));
+        Self {
+            start,
+            end,
+            ..self.clone()
+        }
+    }
+
     pub fn is_owned<'a>(&self, moderated_tags: impl IntoIterator<Item = &'a str>) -> bool {
         // Exclusive ownership of events is determined by the associated tags
         moderated_tags
             .into_iter()
             .any(|moderated_tag| self.tags.iter().any(|tag| tag == moderated_tag))
     }
+
+    /// Replace the title and description by the translation that
+    /// matches the preferred languages best and return its language.
+    pub fn translate(&mut self, preferred: &[Language]) -> Option<Language> {
+        let translation = select_translation(&self.translations, preferred)?;
+        self.title.clone_from(&translation.title);
+        if translation.description.is_some() {
+            self.description.clone_from(&translation.description);
+        }
+        Some(translation.lang.clone())
+    }
+}
+
+/// All stored revisions of an event, the most recent first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventHistory {
+    pub id: Id,
+    pub revisions: Vec<(Event, ActivityLog)>,
 }
 
 #[cfg(test)]
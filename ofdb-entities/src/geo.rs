@@ -525,6 +525,51 @@ impl MapBbox {
         Self::new(sw, ne)
     }
 
+    /// Create the smallest bounding box that contains all points
+    /// within the given great-circle distance around the center
+    pub fn enclosing_circle(center: MapPoint, radius: Distance) -> Self {
+        debug_assert!(center.is_valid());
+        debug_assert!(radius.is_valid());
+        let (lat_rad, lng_rad) = center.to_lat_lng_rad();
+        // The smallest radius of the earth results in the largest
+        // angular distance to avoid excluding points at the border
+        let angular_radius = radius.to_meters() / WGS84_MINOR_SEMIAXIS.to_meters();
+        let lat_min = lat_rad - angular_radius;
+        let lat_max = lat_rad + angular_radius;
+        if lat_min <= LatCoord::RAD_MIN || lat_max >= LatCoord::RAD_MAX {
+            // One of the poles is included and the circle
+            // covers all longitudes
+            let sw = MapPoint::new(
+                LatCoord::from_rad(lat_min.max(LatCoord::RAD_MIN)),
+                LngCoord::min(),
+            );
+            let ne = MapPoint::new(
+                LatCoord::from_rad(lat_max.min(LatCoord::RAD_MAX)),
+                LngCoord::max(),
+            );
+            return Self::new(sw, ne);
+        }
+        let lng_delta = (angular_radius.sin() / lat_rad.cos()).asin();
+        let wrap_lng = |lng: f64| {
+            if lng < LngCoord::RAD_MIN {
+                lng + 2.0 * std::f64::consts::PI
+            } else if lng > LngCoord::RAD_MAX {
+                lng - 2.0 * std::f64::consts::PI
+            } else {
+                lng
+            }
+        };
+        let sw = MapPoint::new(
+            LatCoord::from_rad(lat_min),
+            LngCoord::from_rad(wrap_lng(lng_rad - lng_delta)),
+        );
+        let ne = MapPoint::new(
+            LatCoord::from_rad(lat_max),
+            LngCoord::from_rad(wrap_lng(lng_rad + lng_delta)),
+        );
+        Self::new(sw, ne)
+    }
+
     pub const fn southwest(&self) -> MapPoint {
         self.sw
     }
@@ -805,6 +850,42 @@ mod tests {
         assert!(bbox4.contains_point(MapPoint::from_lat_lng_deg(lat4, lng4)));
     }
 
+    #[test]
+    fn bbox_enclosing_circle() {
+        let radius = Distance::from_meters(2_000.0);
+        let center = MapPoint::from_lat_lng_deg(48.7758, 9.1829);
+        let bbox = MapBbox::enclosing_circle(center, radius);
+        assert!(bbox.is_valid());
+        assert!(bbox.contains_point(center));
+        for (lat, lng) in [
+            (2_000.0, 0.0),
+            (-2_000.0, 0.0),
+            (0.0, 2_000.0),
+            (0.0, -2_000.0),
+        ] {
+            // Points on the circle (approximately)
+            let pt = MapPoint::from_lat_lng_deg(
+                48.7758 + lat / 111_200.0,
+                9.1829 + lng / (111_200.0 * 48.7758_f64.to_radians().cos()),
+            );
+            assert!(MapPoint::distance(center, pt).unwrap() <= radius);
+            assert!(bbox.contains_point(pt));
+        }
+        let far = MapPoint::from_lat_lng_deg(48.7758, 9.25);
+        assert!(!bbox.contains_point(far));
+
+        // Wrap around
+        let bbox = MapBbox::enclosing_circle(MapPoint::from_lat_lng_deg(0.0, 179.99), radius);
+        assert!(bbox.southwest().lng() > bbox.northeast().lng());
+        assert!(bbox.contains_point(MapPoint::from_lat_lng_deg(0.0, -179.995)));
+
+        // Including the north pole
+        let bbox = MapBbox::enclosing_circle(MapPoint::from_lat_lng_deg(89.99, 0.0), radius);
+        assert_eq!(LngCoord::min(), bbox.southwest().lng());
+        assert_eq!(LngCoord::max(), bbox.northeast().lng());
+        assert!(bbox.contains_point(MapPoint::from_lat_lng_deg(89.995, 180.0)));
+    }
+
     // ---- BENCHMARKS ---- //
     //
     // To run the benchmarks you need Rust nightly.
@@ -13,10 +13,13 @@
 
 pub mod activity;
 pub mod address;
+pub mod api_token;
 pub mod category;
+pub mod change;
 pub mod clearance;
 pub mod comment;
 pub mod contact;
+pub mod duplicate;
 pub mod email;
 pub mod event;
 pub mod geo;
@@ -24,16 +27,20 @@ pub mod id;
 pub mod links;
 pub mod location;
 pub mod nonce;
+pub mod opening_hours;
 pub mod organization;
 pub mod password;
 pub mod place;
 pub mod rating;
+pub mod recurrence;
 pub mod review;
 pub mod revision;
 pub mod subscription;
 pub mod tag;
 pub mod time;
+pub mod translation;
 pub mod user;
+pub mod webhook;
 #[cfg(feature = "url")]
 pub mod url {
     pub use url::{ParseError, Url};
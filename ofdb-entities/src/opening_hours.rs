@@ -0,0 +1,538 @@
+//! Structured representation of the OpenStreetMap
+//! [opening_hours](https://wiki.openstreetmap.org/wiki/Key:opening_hours)
+//! syntax.
+//!
+//! Only the commonly used subset of the specification is supported:
+//! weekday ranges, time spans (including spans that cross midnight),
+//! public holidays (`PH`), `off`/`closed` and `24/7`.
+
+use std::fmt;
+
+use thiserror::Error;
+use time::Weekday;
+
+pub const MINUTES_PER_DAY: u16 = 24 * 60;
+
+/// A time span within a day in minutes since midnight.
+///
+/// The end is exclusive and might exceed [`MINUTES_PER_DAY`]
+/// if the span extends into the following day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeSpan {
+    pub start: u16,
+    pub end: u16,
+}
+
+impl TimeSpan {
+    pub const fn all_day() -> Self {
+        Self {
+            start: 0,
+            end: MINUTES_PER_DAY,
+        }
+    }
+
+    pub const fn contains(&self, minute_of_day: u16) -> bool {
+        self.start <= minute_of_day && minute_of_day < self.end
+    }
+
+    /// The part of the span that extends into the following day.
+    pub const fn overflow(&self) -> Option<Self> {
+        if self.end > MINUTES_PER_DAY {
+            Some(Self {
+                start: 0,
+                end: self.end - MINUTES_PER_DAY,
+            })
+        } else {
+            None
+        }
+    }
+}
+
+/// An inclusive range of weekdays that might wrap around
+/// the end of the week, e.g. `Sa-Mo`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WeekdayRange {
+    pub start: Weekday,
+    pub end: Weekday,
+}
+
+impl WeekdayRange {
+    pub const fn single(weekday: Weekday) -> Self {
+        Self {
+            start: weekday,
+            end: weekday,
+        }
+    }
+
+    pub fn contains(&self, weekday: Weekday) -> bool {
+        let start = self.start.number_days_from_monday();
+        let end = self.end.number_days_from_monday();
+        let day = weekday.number_days_from_monday();
+        if start <= end {
+            start <= day && day <= end
+        } else {
+            day >= start || day <= end
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleSequence {
+    /// Selected weekdays. Together with `public_holiday` being `false`
+    /// an empty selector matches every day.
+    pub weekdays: Vec<WeekdayRange>,
+    /// The rule applies on public holidays (`PH`).
+    pub public_holiday: bool,
+    /// Empty if open all day or if closed.
+    pub times: Vec<TimeSpan>,
+    pub closed: bool,
+    /// Additional rules (separated by `,`) extend instead
+    /// of override the preceding rules.
+    pub additional: bool,
+}
+
+impl RuleSequence {
+    fn matches(&self, weekday: Weekday, public_holiday: bool) -> bool {
+        if self.weekdays.is_empty() && !self.public_holiday {
+            return true;
+        }
+        (self.public_holiday && public_holiday)
+            || self.weekdays.iter().any(|range| range.contains(weekday))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpeningHoursRules(Vec<RuleSequence>);
+
+impl OpeningHoursRules {
+    pub fn rules(&self) -> &[RuleSequence] {
+        &self.0
+    }
+
+    /// The time spans that start on the given day.
+    pub fn time_spans(&self, weekday: Weekday, public_holiday: bool) -> Vec<TimeSpan> {
+        let mut spans = vec![];
+        for rule in self.0.iter().filter(|r| r.matches(weekday, public_holiday)) {
+            if !rule.additional || rule.closed {
+                spans.clear();
+            }
+            if rule.closed {
+                continue;
+            }
+            if rule.times.is_empty() {
+                spans.push(TimeSpan::all_day());
+            } else {
+                spans.extend_from_slice(&rule.times);
+            }
+        }
+        spans
+    }
+
+    /// Check if open at the given local time.
+    ///
+    /// The `public_holiday` flag only refers to the given day,
+    /// the day before is never considered as a public holiday.
+    pub fn is_open_at(&self, weekday: Weekday, minute_of_day: u16, public_holiday: bool) -> bool {
+        debug_assert!(minute_of_day < MINUTES_PER_DAY);
+        self.time_spans(weekday, public_holiday)
+            .iter()
+            .any(|span| span.contains(minute_of_day))
+            || self
+                .time_spans(weekday.previous(), false)
+                .iter()
+                .filter_map(TimeSpan::overflow)
+                .any(|span| span.contains(minute_of_day))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ParseError {
+    #[error("Empty rule")]
+    EmptyRule,
+    #[error("Unexpected input '{0}'")]
+    UnexpectedInput(String),
+    #[error("Invalid time '{0}'")]
+    InvalidTime(String),
+    #[error("Incomplete time span")]
+    IncompleteTimeSpan,
+    #[error("Incomplete weekday range")]
+    IncompleteWeekdayRange,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token {
+    Weekday(Weekday),
+    PublicHoliday,
+    Time(u16),
+    TwentyFourSeven,
+    Closed,
+    Open,
+    Dash,
+    Comma,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Weekday(w) => write!(f, "{w}"),
+            Self::PublicHoliday => write!(f, "PH"),
+            Self::Time(t) => write!(f, "{:02}:{:02}", t / 60, t % 60),
+            Self::TwentyFourSeven => write!(f, "24/7"),
+            Self::Closed => write!(f, "off"),
+            Self::Open => write!(f, "open"),
+            Self::Dash => write!(f, "-"),
+            Self::Comma => write!(f, ","),
+        }
+    }
+}
+
+fn parse_weekday(s: &str) -> Option<Weekday> {
+    let weekday = match s {
+        "Mo" => Weekday::Monday,
+        "Tu" => Weekday::Tuesday,
+        "We" => Weekday::Wednesday,
+        "Th" => Weekday::Thursday,
+        "Fr" => Weekday::Friday,
+        "Sa" => Weekday::Saturday,
+        "Su" => Weekday::Sunday,
+        _ => return None,
+    };
+    Some(weekday)
+}
+
+fn parse_time(s: &str) -> Result<u16, ParseError> {
+    let invalid = || ParseError::InvalidTime(s.to_owned());
+    let (hh, mm) = s.split_once(':').ok_or_else(invalid)?;
+    if hh.is_empty() || hh.len() > 2 || mm.len() != 2 {
+        return Err(invalid());
+    }
+    let hh: u16 = hh.parse().map_err(|_| invalid())?;
+    let mm: u16 = mm.parse().map_err(|_| invalid())?;
+    // Extended hours up to 48:00 are permitted for spans that
+    // end on the following day.
+    if mm >= 60 || hh * 60 + mm > 2 * MINUTES_PER_DAY {
+        return Err(invalid());
+    }
+    Ok(hh * 60 + mm)
+}
+
+fn tokenize(s: &str) -> Result<Vec<Token>, ParseError> {
+    let mut tokens = vec![];
+    let mut rest = s.trim_start();
+    while let Some(c) = rest.chars().next() {
+        let len = match c {
+            '-' => {
+                tokens.push(Token::Dash);
+                1
+            }
+            ',' => {
+                tokens.push(Token::Comma);
+                1
+            }
+            '0'..='9' => {
+                let len = rest
+                    .find(|c: char| !(c.is_ascii_digit() || c == ':' || c == '/'))
+                    .unwrap_or(rest.len());
+                let word = &rest[..len];
+                if word == "24/7" {
+                    tokens.push(Token::TwentyFourSeven);
+                } else {
+                    tokens.push(Token::Time(parse_time(word)?));
+                }
+                len
+            }
+            c if c.is_ascii_alphabetic() => {
+                let len = rest
+                    .find(|c: char| !c.is_ascii_alphabetic())
+                    .unwrap_or(rest.len());
+                let word = &rest[..len];
+                let token = match word {
+                    "PH" => Token::PublicHoliday,
+                    "off" | "closed" => Token::Closed,
+                    "open" => Token::Open,
+                    _ => Token::Weekday(
+                        parse_weekday(word)
+                            .ok_or_else(|| ParseError::UnexpectedInput(word.to_owned()))?,
+                    ),
+                };
+                tokens.push(token);
+                len
+            }
+            _ => {
+                return Err(ParseError::UnexpectedInput(c.to_string()));
+            }
+        };
+        rest = rest[len..].trim_start();
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<Token> {
+        self.tokens.get(self.pos).copied()
+    }
+
+    fn peek_after_comma(&self) -> Option<Token> {
+        if self.peek() == Some(Token::Comma) {
+            self.tokens.get(self.pos + 1).copied()
+        } else {
+            None
+        }
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.peek();
+        if token.is_some() {
+            self.pos += 1;
+        }
+        token
+    }
+
+    fn parse_weekday_range(&mut self, start: Weekday) -> Result<WeekdayRange, ParseError> {
+        if self.peek() != Some(Token::Dash) {
+            return Ok(WeekdayRange::single(start));
+        }
+        self.next();
+        match self.next() {
+            Some(Token::Weekday(end)) => Ok(WeekdayRange { start, end }),
+            _ => Err(ParseError::IncompleteWeekdayRange),
+        }
+    }
+
+    fn parse_time_span(&mut self, start: u16) -> Result<TimeSpan, ParseError> {
+        if self.next() != Some(Token::Dash) {
+            return Err(ParseError::IncompleteTimeSpan);
+        }
+        let Some(Token::Time(mut end)) = self.next() else {
+            return Err(ParseError::IncompleteTimeSpan);
+        };
+        if start >= MINUTES_PER_DAY {
+            return Err(ParseError::InvalidTime(Token::Time(start).to_string()));
+        }
+        if end <= start {
+            // The span ends on the following day
+            end += MINUTES_PER_DAY;
+        }
+        if end > 2 * MINUTES_PER_DAY {
+            return Err(ParseError::InvalidTime(Token::Time(end).to_string()));
+        }
+        Ok(TimeSpan { start, end })
+    }
+
+    fn parse_rule(&mut self, additional: bool) -> Result<RuleSequence, ParseError> {
+        let mut rule = RuleSequence {
+            weekdays: vec![],
+            public_holiday: false,
+            times: vec![],
+            closed: false,
+            additional,
+        };
+        let start_pos = self.pos;
+        if self.peek() == Some(Token::TwentyFourSeven) {
+            self.next();
+            return Ok(rule);
+        }
+        // Weekday selector
+        loop {
+            match self.peek() {
+                Some(Token::Weekday(start)) => {
+                    self.next();
+                    let range = self.parse_weekday_range(start)?;
+                    rule.weekdays.push(range);
+                }
+                Some(Token::PublicHoliday) => {
+                    self.next();
+                    rule.public_holiday = true;
+                }
+                _ => break,
+            }
+            match self.peek_after_comma() {
+                Some(Token::Weekday(_) | Token::PublicHoliday) => {
+                    self.next();
+                }
+                _ => break,
+            }
+        }
+        // Time selector
+        while let Some(Token::Time(start)) = self.peek() {
+            self.next();
+            rule.times.push(self.parse_time_span(start)?);
+            match self.peek_after_comma() {
+                Some(Token::Time(_)) => {
+                    self.next();
+                }
+                _ => break,
+            }
+        }
+        // Rule modifier
+        match self.peek() {
+            Some(Token::Closed) => {
+                self.next();
+                rule.closed = true;
+                rule.times.clear();
+            }
+            Some(Token::Open) => {
+                self.next();
+            }
+            _ => (),
+        }
+        if self.pos == start_pos {
+            return Err(match self.peek() {
+                Some(token) => ParseError::UnexpectedInput(token.to_string()),
+                None => ParseError::EmptyRule,
+            });
+        }
+        Ok(rule)
+    }
+}
+
+impl std::str::FromStr for OpeningHoursRules {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut rules = vec![];
+        for part in s.split(';') {
+            let mut parser = Parser {
+                tokens: tokenize(part)?,
+                pos: 0,
+            };
+            if parser.tokens.is_empty() {
+                // Tolerate a trailing semicolon
+                if rules.is_empty() {
+                    return Err(ParseError::EmptyRule);
+                }
+                continue;
+            }
+            rules.push(parser.parse_rule(false)?);
+            while let Some(token) = parser.next() {
+                if token != Token::Comma {
+                    return Err(ParseError::UnexpectedInput(token.to_string()));
+                }
+                rules.push(parser.parse_rule(true)?);
+            }
+        }
+        Ok(Self(rules))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> OpeningHoursRules {
+        s.parse().unwrap()
+    }
+
+    const fn hm(h: u16, m: u16) -> u16 {
+        h * 60 + m
+    }
+
+    #[test]
+    fn parse_24_7() {
+        let rules = parse("24/7");
+        for weekday in [Weekday::Monday, Weekday::Sunday] {
+            assert!(rules.is_open_at(weekday, 0, false));
+            assert!(rules.is_open_at(weekday, MINUTES_PER_DAY - 1, true));
+        }
+    }
+
+    #[test]
+    fn parse_weekday_ranges_and_time_spans() {
+        let rules = parse("Mo-Fr 08:00-12:00,14:00-18:30; Sa 10:00-14:00");
+        assert_eq!(rules.rules().len(), 2);
+        assert_eq!(
+            rules.time_spans(Weekday::Wednesday, false),
+            vec![
+                TimeSpan {
+                    start: hm(8, 0),
+                    end: hm(12, 0)
+                },
+                TimeSpan {
+                    start: hm(14, 0),
+                    end: hm(18, 30)
+                }
+            ]
+        );
+        assert!(rules.is_open_at(Weekday::Monday, hm(8, 0), false));
+        assert!(!rules.is_open_at(Weekday::Monday, hm(12, 0), false));
+        assert!(rules.is_open_at(Weekday::Friday, hm(18, 29), false));
+        assert!(rules.is_open_at(Weekday::Saturday, hm(13, 0), false));
+        assert!(!rules.is_open_at(Weekday::Sunday, hm(13, 0), false));
+    }
+
+    #[test]
+    fn wrapping_weekday_range() {
+        let rules = parse("Sa-Mo 10:00-16:00");
+        assert!(rules.is_open_at(Weekday::Sunday, hm(11, 0), false));
+        assert!(rules.is_open_at(Weekday::Monday, hm(11, 0), false));
+        assert!(!rules.is_open_at(Weekday::Tuesday, hm(11, 0), false));
+    }
+
+    #[test]
+    fn spans_crossing_midnight() {
+        let rules = parse("Fr,Sa 22:00-03:00");
+        assert!(rules.is_open_at(Weekday::Friday, hm(23, 0), false));
+        assert!(rules.is_open_at(Weekday::Saturday, hm(2, 59), false));
+        assert!(!rules.is_open_at(Weekday::Saturday, hm(3, 0), false));
+        assert!(rules.is_open_at(Weekday::Sunday, hm(1, 0), false));
+        assert!(!rules.is_open_at(Weekday::Friday, hm(1, 0), false));
+    }
+
+    #[test]
+    fn public_holidays() {
+        let rules = parse("Mo-Fr 09:00-17:00; PH off");
+        assert!(rules.is_open_at(Weekday::Monday, hm(10, 0), false));
+        assert!(!rules.is_open_at(Weekday::Monday, hm(10, 0), true));
+        let rules = parse("Mo-Sa 09:00-17:00; Su,PH 11:00-15:00");
+        assert!(rules.is_open_at(Weekday::Sunday, hm(12, 0), false));
+        assert!(rules.is_open_at(Weekday::Tuesday, hm(12, 0), true));
+        assert!(!rules.is_open_at(Weekday::Tuesday, hm(16, 0), true));
+    }
+
+    #[test]
+    fn later_rules_override_earlier_rules() {
+        let rules = parse("Mo-Su 08:00-20:00; We closed; Su 10:00-12:00");
+        assert!(!rules.is_open_at(Weekday::Wednesday, hm(10, 0), false));
+        assert!(!rules.is_open_at(Weekday::Sunday, hm(13, 0), false));
+        assert!(rules.is_open_at(Weekday::Sunday, hm(11, 0), false));
+    }
+
+    #[test]
+    fn additional_rules_extend_earlier_rules() {
+        let rules = parse("Mo 10:00-12:00, Mo 14:00-16:00");
+        assert!(rules.is_open_at(Weekday::Monday, hm(11, 0), false));
+        assert!(rules.is_open_at(Weekday::Monday, hm(15, 0), false));
+        assert!(!rules.is_open_at(Weekday::Monday, hm(13, 0), false));
+    }
+
+    #[test]
+    fn open_all_day_on_selected_weekdays() {
+        let rules = parse("Sa-Su");
+        assert!(rules.is_open_at(Weekday::Saturday, 0, false));
+        assert!(!rules.is_open_at(Weekday::Friday, hm(12, 0), false));
+    }
+
+    #[test]
+    fn reject_invalid_input() {
+        for s in [
+            "",
+            ";",
+            "Mo-Fr 9-17",
+            "Mo-Fr 08:00",
+            "Mo- 08:00-12:00",
+            "Mo-Fr 25:00-26:00",
+            "Mo-Fr 08:60-12:00",
+            "daily 08:00-12:00",
+            "Mo-Fr 08:00-12:00 Sa",
+            "Mo-Fr 08:00-12:00,",
+            "nach Vereinbarung",
+        ] {
+            assert!(s.parse::<OpeningHoursRules>().is_err(), "{s}");
+        }
+    }
+}
@@ -1,4 +1,8 @@
-use crate::id::Id;
+use std::fmt;
+
+use strum::{EnumIter, EnumString, IntoStaticStr};
+
+use crate::{api_token::ApiTokenScope, email::EmailAddress, id::Id};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ModeratedTag {
@@ -28,3 +32,74 @@ pub struct Organization {
     pub api_token: String,
     pub moderated_tags: Vec<ModeratedTag>,
 }
+
+/// The role of a registered user within an organization.
+///
+/// Each role includes the permissions of all preceding roles.
+#[rustfmt::skip]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, EnumIter, EnumString, IntoStaticStr)]
+#[strum(serialize_all = "lowercase")]
+pub enum OrganizationRole {
+    /// Read pending clearances, the history, and exports
+    Viewer,
+    /// Create and edit places and events on behalf of the organization
+    Editor,
+    /// Clear pending changes of places and events
+    Clearer,
+}
+
+impl OrganizationRole {
+    pub fn as_str(self) -> &'static str {
+        self.into()
+    }
+
+    pub fn grants(self, scope: ApiTokenScope) -> bool {
+        use ApiTokenScope as S;
+        let required_role = match scope {
+            S::EventsRead
+            | S::HistoryRead
+            | S::ClearanceRead
+            | S::ExportCsv
+            | S::ExportIcal
+            | S::ExportGeoJson => Self::Viewer,
+            S::PlacesWrite | S::EventsWrite => Self::Editor,
+            S::ClearanceWrite => Self::Clearer,
+        };
+        self >= required_role
+    }
+}
+
+impl fmt::Display for OrganizationRole {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A registered user that acts on behalf of an organization.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrganizationMember {
+    pub org_id: Id,
+    pub email: EmailAddress,
+    pub role: OrganizationRole,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roles_include_the_permissions_of_preceding_roles() {
+        use ApiTokenScope as S;
+        assert!(OrganizationRole::Viewer.grants(S::ClearanceRead));
+        assert!(!OrganizationRole::Viewer.grants(S::EventsWrite));
+        assert!(OrganizationRole::Editor.grants(S::EventsWrite));
+        assert!(!OrganizationRole::Editor.grants(S::ClearanceWrite));
+        assert!(OrganizationRole::Clearer.grants(S::ClearanceWrite));
+        assert!(OrganizationRole::Clearer.grants(S::EventsRead));
+        assert_eq!(
+            Ok(OrganizationRole::Clearer),
+            "clearer".parse::<OrganizationRole>()
+        );
+        assert_eq!("editor", OrganizationRole::Editor.to_string());
+    }
+}
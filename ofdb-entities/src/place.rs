@@ -2,8 +2,16 @@ use std::str::FromStr;
 use time::Date;
 
 use crate::{
-    activity::*, contact::*, email::EmailAddress, id::*, links::*, location::*, review::*,
+    activity::*,
+    contact::*,
+    email::EmailAddress,
+    id::*,
+    links::*,
+    location::*,
+    opening_hours::{OpeningHoursRules, ParseError as OpeningHoursRulesParseError},
+    review::*,
     revision::*,
+    translation::*,
 };
 
 // Immutable part of a place.
@@ -23,6 +31,18 @@ impl OpeningHours {
     pub const fn min_len() -> usize {
         4
     }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Parse the structured rules.
+    ///
+    /// Legacy values that have been stored before the syntax has been
+    /// enforced might not comply with the OpenStreetMap specification.
+    pub fn rules(&self) -> Result<OpeningHoursRules, OpeningHoursRulesParseError> {
+        self.0.parse()
+    }
 }
 
 impl FromStr for OpeningHours {
@@ -64,6 +84,7 @@ pub struct PlaceRevision {
     pub founded_on: Option<Date>,
     pub links: Option<Links>,
     pub tags: Vec<String>,
+    pub translations: Vec<Translation>,
 }
 
 // Convenience type that merges the tuple (PlaceRoot, PlaceRevision)
@@ -82,6 +103,7 @@ pub struct Place {
     pub founded_on: Option<Date>,
     pub links: Option<Links>,
     pub tags: Vec<String>,
+    pub translations: Vec<Translation>,
 }
 
 impl Place {
@@ -109,6 +131,17 @@ impl Place {
             .into_iter()
             .any(|moderated_tag| self.tags.iter().any(|tag| tag == moderated_tag))
     }
+
+    /// Replace the title and description by the translation that
+    /// matches the preferred languages best and return its language.
+    pub fn translate(&mut self, preferred: &[Language]) -> Option<Language> {
+        let translation = select_translation(&self.translations, preferred)?;
+        self.title.clone_from(&translation.title);
+        if let Some(description) = &translation.description {
+            self.description.clone_from(description);
+        }
+        Some(translation.lang.clone())
+    }
 }
 
 impl From<(PlaceRoot, PlaceRevision)> for Place {
@@ -126,6 +159,7 @@ impl From<(PlaceRoot, PlaceRevision)> for Place {
                 founded_on,
                 links,
                 tags,
+                translations,
             },
         ) = from;
         Self {
@@ -141,6 +175,7 @@ impl From<(PlaceRoot, PlaceRevision)> for Place {
             founded_on,
             links,
             tags,
+            translations,
         }
     }
 }
@@ -160,6 +195,7 @@ impl From<Place> for (PlaceRoot, PlaceRevision) {
             founded_on,
             links,
             tags,
+            translations,
         } = from;
         (
             PlaceRoot { id, license },
@@ -174,6 +210,7 @@ impl From<Place> for (PlaceRoot, PlaceRevision) {
                 founded_on,
                 links,
                 tags,
+                translations,
             },
         )
     }
@@ -0,0 +1,817 @@
+//! Recurring events as specified by the iCalendar
+//! [RRULE](https://icalendar.org/iCalendar-RFC-5545/3-3-10-recurrence-rule.html)
+//! and [EXDATE](https://icalendar.org/iCalendar-RFC-5545/3-8-5-1-exception-date-times.html)
+//! properties.
+//!
+//! Only the commonly used subset of the specification is supported:
+//! `FREQ` (`DAILY`, `WEEKLY`, `MONTHLY`, `YEARLY`), `INTERVAL`, `COUNT`,
+//! `UNTIL`, `BYDAY` (with ordinals only for monthly rules), `BYMONTHDAY`
+//! and `WKST`.
+//!
+//! All date-time values are evaluated in UTC.
+
+use std::{fmt, str::FromStr};
+
+use thiserror::Error;
+use time::{
+    Date, Duration, Month, PrimitiveDateTime, Time, Weekday, format_description::FormatItem,
+    macros::format_description,
+};
+
+use crate::time::Timestamp;
+
+/// Stop expanding a rule that did not produce any
+/// occurrence for this number of consecutive periods.
+const MAX_EMPTY_PERIODS: u32 = 1_000;
+
+const UNTIL_DATE_TIME_FORMAT: &[FormatItem] =
+    format_description!("[year][month][day]T[hour][minute][second]Z");
+
+const UNTIL_FLOATING_DATE_TIME_FORMAT: &[FormatItem] =
+    format_description!("[year][month][day]T[hour][minute][second]");
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// A weekday, optionally restricted to the n-th
+/// occurrence within a month, e.g. `-1FR` for the last friday.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WeekdayNum {
+    /// Counted from the end of the month if negative
+    pub nth: Option<i8>,
+    pub weekday: Weekday,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecurrenceEnd {
+    Count(u32),
+    /// Inclusive
+    Until(Timestamp),
+}
+
+#[rustfmt::skip]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecurrenceRule {
+    pub frequency    : Frequency,
+    pub interval     : u32,
+    pub end          : Option<RecurrenceEnd>,
+    pub by_day       : Vec<WeekdayNum>,
+    pub by_month_day : Vec<i8>,
+    pub week_start   : Weekday,
+}
+
+impl RecurrenceRule {
+    pub const fn new(frequency: Frequency) -> Self {
+        Self {
+            frequency,
+            interval: 1,
+            end: None,
+            by_day: Vec::new(),
+            by_month_day: Vec::new(),
+            week_start: Weekday::Monday,
+        }
+    }
+
+    /// All occurrences in chronological order, starting with `dtstart`.
+    ///
+    /// The iterator is infinite if the rule has no end.
+    pub fn occurrences(&self, dtstart: Timestamp) -> Occurrences<'_> {
+        let dtstart = primitive_date_time(dtstart);
+        Occurrences {
+            rule: self,
+            dtstart,
+            next_period: 0,
+            pending: vec![dtstart].into_iter(),
+            count: 0,
+            empty_periods: 0,
+            done: false,
+        }
+    }
+
+    fn matches_day(&self, date: Date) -> bool {
+        (self.by_day.is_empty() || self.by_day.iter().any(|d| d.weekday == date.weekday()))
+            && (self.by_month_day.is_empty()
+                || self
+                    .by_month_day
+                    .iter()
+                    .any(|&d| month_day(date.year(), date.month(), d) == Some(date.day())))
+    }
+
+    /// Candidate dates of the n-th period after the period of `dtstart`.
+    ///
+    /// Returns `None` if the period exceeds the supported date range.
+    fn period_dates(&self, dtstart: Date, period: u32) -> Option<Vec<Date>> {
+        let step = i64::from(period.checked_mul(self.interval)?);
+        let dates = match self.frequency {
+            Frequency::Daily => {
+                let date = dtstart.checked_add(Duration::days(step))?;
+                if self.matches_day(date) {
+                    vec![date]
+                } else {
+                    vec![]
+                }
+            }
+            Frequency::Weekly => {
+                let offset = (7 + dtstart.weekday().number_days_from_monday()
+                    - self.week_start.number_days_from_monday())
+                    % 7;
+                let week_start = dtstart
+                    .checked_sub(Duration::days(offset.into()))?
+                    .checked_add(Duration::weeks(step))?;
+                if self.by_day.is_empty() {
+                    vec![week_start.checked_add(Duration::days(offset.into()))?]
+                } else {
+                    (0..7)
+                        .filter_map(|d| week_start.checked_add(Duration::days(d)))
+                        .filter(|date| self.by_day.iter().any(|d| d.weekday == date.weekday()))
+                        .collect()
+                }
+            }
+            Frequency::Monthly => {
+                let months = i64::from(u8::from(dtstart.month()) - 1) + step;
+                let year = dtstart
+                    .year()
+                    .checked_add(i32::try_from(months / 12).ok()?)?;
+                if year > Date::MAX.year() {
+                    return None;
+                }
+                let month = Month::try_from(u8::try_from(months % 12 + 1).ok()?).ok()?;
+                let mut days = self.month_days(year, month, dtstart.day());
+                days.sort_unstable();
+                days.dedup();
+                days.into_iter()
+                    .filter_map(|day| Date::from_calendar_date(year, month, day).ok())
+                    .collect()
+            }
+            Frequency::Yearly => {
+                let year = dtstart.year().checked_add(i32::try_from(step).ok()?)?;
+                if year > Date::MAX.year() {
+                    return None;
+                }
+                // Invalid dates like February 29th in non-leap years are skipped
+                Date::from_calendar_date(year, dtstart.month(), dtstart.day())
+                    .ok()
+                    .into_iter()
+                    .collect()
+            }
+        };
+        Some(dates)
+    }
+
+    fn month_days(&self, year: i32, month: Month, default_day: u8) -> Vec<u8> {
+        let by_month_day: Vec<_> = self
+            .by_month_day
+            .iter()
+            .filter_map(|&d| month_day(year, month, d))
+            .collect();
+        let by_day: Vec<_> = self
+            .by_day
+            .iter()
+            .flat_map(|d| weekday_num_days(year, month, *d))
+            .collect();
+        match (self.by_month_day.is_empty(), self.by_day.is_empty()) {
+            (true, true) => vec![default_day],
+            (false, true) => by_month_day,
+            (true, false) => by_day,
+            (false, false) => by_month_day
+                .into_iter()
+                .filter(|d| by_day.contains(d))
+                .collect(),
+        }
+    }
+}
+
+/// The day of the month counted from the end if negative.
+fn month_day(year: i32, month: Month, day: i8) -> Option<u8> {
+    let days_in_month = month.length(year);
+    let day = if day < 0 {
+        i16::from(days_in_month) + 1 + i16::from(day)
+    } else {
+        i16::from(day)
+    };
+    u8::try_from(day)
+        .ok()
+        .filter(|day| (1..=days_in_month).contains(day))
+}
+
+fn weekday_num_days(year: i32, month: Month, weekday_num: WeekdayNum) -> Vec<u8> {
+    let WeekdayNum { nth, weekday } = weekday_num;
+    let days_in_month = month.length(year);
+    let Ok(first_of_month) = Date::from_calendar_date(year, month, 1) else {
+        return vec![];
+    };
+    let first = 1
+        + (7 + weekday.number_days_from_monday()
+            - first_of_month.weekday().number_days_from_monday())
+            % 7;
+    let days: Vec<_> = (first..=days_in_month).step_by(7).collect();
+    match nth {
+        None => days,
+        Some(nth) => {
+            let index = if nth < 0 {
+                days.len().checked_sub(usize::from(nth.unsigned_abs()))
+            } else {
+                usize::from(nth.unsigned_abs()).checked_sub(1)
+            };
+            index
+                .and_then(|i| days.get(i))
+                .copied()
+                .into_iter()
+                .collect()
+        }
+    }
+}
+
+fn primitive_date_time(ts: Timestamp) -> PrimitiveDateTime {
+    let date_time =
+        time::OffsetDateTime::from_unix_timestamp(ts.as_secs()).expect("valid unix timestamp");
+    PrimitiveDateTime::new(date_time.date(), date_time.time())
+}
+
+/// Iterator over the occurrences of a [`RecurrenceRule`].
+#[derive(Debug)]
+pub struct Occurrences<'a> {
+    rule: &'a RecurrenceRule,
+    dtstart: PrimitiveDateTime,
+    next_period: u32,
+    pending: std::vec::IntoIter<PrimitiveDateTime>,
+    count: u32,
+    empty_periods: u32,
+    done: bool,
+}
+
+impl Iterator for Occurrences<'_> {
+    type Item = Timestamp;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            if let Some(next) = self.pending.next() {
+                let next = Timestamp::from(next);
+                let exceeded = match self.rule.end {
+                    Some(RecurrenceEnd::Count(count)) => self.count >= count,
+                    Some(RecurrenceEnd::Until(until)) => next > until,
+                    None => false,
+                };
+                if exceeded {
+                    self.done = true;
+                    return None;
+                }
+                self.count += 1;
+                return Some(next);
+            }
+            if self.empty_periods >= MAX_EMPTY_PERIODS {
+                self.done = true;
+                return None;
+            }
+            let Some(dates) = self
+                .rule
+                .period_dates(self.dtstart.date(), self.next_period)
+            else {
+                self.done = true;
+                return None;
+            };
+            self.next_period += 1;
+            let time = self.dtstart.time();
+            let dtstart = self.dtstart;
+            // The start itself always counts as the first occurrence
+            let candidates: Vec<_> = dates
+                .into_iter()
+                .map(|date| PrimitiveDateTime::new(date, time))
+                .filter(|date_time| *date_time > dtstart)
+                .collect();
+            if candidates.is_empty() {
+                self.empty_periods += 1;
+            } else {
+                self.empty_periods = 0;
+            }
+            self.pending = candidates.into_iter();
+        }
+        None
+    }
+}
+
+/// A recurrence rule together with the start times
+/// of occurrences that are excluded from the rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recurrence {
+    pub rule: RecurrenceRule,
+    pub exdates: Vec<Timestamp>,
+}
+
+impl Recurrence {
+    /// All occurrences in chronological order, without the excluded dates.
+    pub fn occurrences(&self, dtstart: Timestamp) -> impl Iterator<Item = Timestamp> + '_ {
+        self.rule
+            .occurrences(dtstart)
+            .filter(|ts| !self.exdates.iter().any(|ex| ex.as_secs() == ts.as_secs()))
+    }
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ParseError {
+    #[error("Missing frequency")]
+    MissingFrequency,
+    #[error("Invalid rule part: {0}")]
+    InvalidPart(String),
+    #[error("Duplicate rule part: {0}")]
+    DuplicatePart(String),
+    #[error("Unsupported rule part: {0}")]
+    UnsupportedPart(String),
+    #[error("Invalid value: {0}")]
+    InvalidValue(String),
+    #[error("COUNT and UNTIL are mutually exclusive")]
+    CountAndUntil,
+}
+
+impl FromStr for RecurrenceRule {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let s = s
+            .get(..6)
+            .filter(|prefix| prefix.eq_ignore_ascii_case("RRULE:"))
+            .map_or(s, |_| &s[6..]);
+        let mut frequency = None;
+        let mut interval = None;
+        let mut count = None;
+        let mut until = None;
+        let mut by_day = None;
+        let mut by_month_day = None;
+        let mut week_start = None;
+        for part in s.split(';').filter(|part| !part.is_empty()) {
+            let Some((name, value)) = part.split_once('=') else {
+                return Err(ParseError::InvalidPart(part.to_owned()));
+            };
+            let name = name.trim().to_ascii_uppercase();
+            let value = value.trim().to_ascii_uppercase();
+            let is_duplicate = match name.as_str() {
+                "FREQ" => frequency.replace(parse_frequency(&value)?).is_some(),
+                "INTERVAL" => interval.replace(parse_positive(&value)?).is_some(),
+                "COUNT" => count.replace(parse_positive(&value)?).is_some(),
+                "UNTIL" => until.replace(parse_until(&value)?).is_some(),
+                "BYDAY" => by_day
+                    .replace(parse_list(&value, parse_weekday_num)?)
+                    .is_some(),
+                "BYMONTHDAY" => by_month_day
+                    .replace(parse_list(&value, parse_month_day)?)
+                    .is_some(),
+                "WKST" => week_start.replace(parse_weekday(&value)?).is_some(),
+                _ => return Err(ParseError::UnsupportedPart(name)),
+            };
+            if is_duplicate {
+                return Err(ParseError::DuplicatePart(name));
+            }
+        }
+        let frequency = frequency.ok_or(ParseError::MissingFrequency)?;
+        let end = match (count, until) {
+            (Some(_), Some(_)) => return Err(ParseError::CountAndUntil),
+            (Some(count), None) => Some(RecurrenceEnd::Count(count)),
+            (None, Some(until)) => Some(RecurrenceEnd::Until(until)),
+            (None, None) => None,
+        };
+        let by_day: Vec<WeekdayNum> = by_day.unwrap_or_default();
+        let by_month_day: Vec<i8> = by_month_day.unwrap_or_default();
+        if frequency != Frequency::Monthly && by_day.iter().any(|d| d.nth.is_some()) {
+            return Err(ParseError::UnsupportedPart("BYDAY".to_owned()));
+        }
+        if frequency == Frequency::Yearly && !by_day.is_empty() {
+            return Err(ParseError::UnsupportedPart("BYDAY".to_owned()));
+        }
+        if matches!(frequency, Frequency::Weekly | Frequency::Yearly) && !by_month_day.is_empty() {
+            return Err(ParseError::UnsupportedPart("BYMONTHDAY".to_owned()));
+        }
+        Ok(Self {
+            frequency,
+            interval: interval.unwrap_or(1),
+            end,
+            by_day,
+            by_month_day,
+            week_start: week_start.unwrap_or(Weekday::Monday),
+        })
+    }
+}
+
+fn parse_frequency(s: &str) -> Result<Frequency, ParseError> {
+    match s {
+        "DAILY" => Ok(Frequency::Daily),
+        "WEEKLY" => Ok(Frequency::Weekly),
+        "MONTHLY" => Ok(Frequency::Monthly),
+        "YEARLY" => Ok(Frequency::Yearly),
+        "SECONDLY" | "MINUTELY" | "HOURLY" => Err(ParseError::UnsupportedPart(format!("FREQ={s}"))),
+        _ => Err(ParseError::InvalidValue(s.to_owned())),
+    }
+}
+
+fn parse_positive(s: &str) -> Result<u32, ParseError> {
+    s.parse()
+        .ok()
+        .filter(|&n| n > 0)
+        .ok_or_else(|| ParseError::InvalidValue(s.to_owned()))
+}
+
+fn parse_until(s: &str) -> Result<Timestamp, ParseError> {
+    let invalid = || ParseError::InvalidValue(s.to_owned());
+    let (date, time) = match s.split_once('T') {
+        Some((date, time)) => (date, Some(time.strip_suffix('Z').unwrap_or(time))),
+        None => (s, None),
+    };
+    let number = |s: &str| s.parse::<u32>().map_err(|_| invalid());
+    if date.len() != 8 || !date.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(invalid());
+    }
+    let year = i32::try_from(number(&date[..4])?).map_err(|_| invalid())?;
+    let month = u8::try_from(number(&date[4..6])?)
+        .ok()
+        .and_then(|m| Month::try_from(m).ok())
+        .ok_or_else(invalid)?;
+    let day = u8::try_from(number(&date[6..])?).map_err(|_| invalid())?;
+    let date = Date::from_calendar_date(year, month, day).map_err(|_| invalid())?;
+    let time = match time {
+        Some(time) => {
+            if time.len() != 6 || !time.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(invalid());
+            }
+            let [hour, minute, second] =
+                [&time[..2], &time[2..4], &time[4..]].map(|x| x.parse::<u8>().unwrap_or(u8::MAX));
+            Time::from_hms(hour, minute, second).map_err(|_| invalid())?
+        }
+        // A date includes the whole day
+        None => Time::from_hms(23, 59, 59).expect("valid time"),
+    };
+    Ok(PrimitiveDateTime::new(date, time).into())
+}
+
+fn parse_list<T>(
+    s: &str,
+    parse_item: impl Fn(&str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    s.split(',').map(str::trim).map(parse_item).collect()
+}
+
+fn parse_weekday(s: &str) -> Result<Weekday, ParseError> {
+    match s {
+        "MO" => Ok(Weekday::Monday),
+        "TU" => Ok(Weekday::Tuesday),
+        "WE" => Ok(Weekday::Wednesday),
+        "TH" => Ok(Weekday::Thursday),
+        "FR" => Ok(Weekday::Friday),
+        "SA" => Ok(Weekday::Saturday),
+        "SU" => Ok(Weekday::Sunday),
+        _ => Err(ParseError::InvalidValue(s.to_owned())),
+    }
+}
+
+fn parse_weekday_num(s: &str) -> Result<WeekdayNum, ParseError> {
+    let split_at = s
+        .len()
+        .checked_sub(2)
+        .filter(|&i| s.is_char_boundary(i))
+        .ok_or_else(|| ParseError::InvalidValue(s.to_owned()))?;
+    let (nth, weekday) = s.split_at(split_at);
+    let weekday = parse_weekday(weekday)?;
+    let nth = if nth.is_empty() {
+        None
+    } else {
+        let nth = nth
+            .parse::<i8>()
+            .ok()
+            .filter(|n| (1..=5).contains(&n.unsigned_abs()))
+            .ok_or_else(|| ParseError::InvalidValue(s.to_owned()))?;
+        Some(nth)
+    };
+    Ok(WeekdayNum { nth, weekday })
+}
+
+fn parse_month_day(s: &str) -> Result<i8, ParseError> {
+    s.parse::<i8>()
+        .ok()
+        .filter(|d| (1..=31).contains(&d.unsigned_abs()))
+        .ok_or_else(|| ParseError::InvalidValue(s.to_owned()))
+}
+
+const fn weekday_str(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Monday => "MO",
+        Weekday::Tuesday => "TU",
+        Weekday::Wednesday => "WE",
+        Weekday::Thursday => "TH",
+        Weekday::Friday => "FR",
+        Weekday::Saturday => "SA",
+        Weekday::Sunday => "SU",
+    }
+}
+
+impl fmt::Display for Frequency {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::Daily => "DAILY",
+            Self::Weekly => "WEEKLY",
+            Self::Monthly => "MONTHLY",
+            Self::Yearly => "YEARLY",
+        })
+    }
+}
+
+impl fmt::Display for WeekdayNum {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(nth) = self.nth {
+            write!(f, "{nth}")?;
+        }
+        f.write_str(weekday_str(self.weekday))
+    }
+}
+
+/// Formats the rule as RRULE value without the `RRULE:` prefix.
+///
+/// The alternate format `{:#}` writes `UNTIL` as floating date-time
+/// without time zone, which is required if the start of the
+/// recurring event is a floating date-time.
+impl fmt::Display for RecurrenceRule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "FREQ={}", self.frequency)?;
+        if self.interval != 1 {
+            write!(f, ";INTERVAL={}", self.interval)?;
+        }
+        match self.end {
+            Some(RecurrenceEnd::Count(count)) => write!(f, ";COUNT={count}")?,
+            Some(RecurrenceEnd::Until(until)) => {
+                let format = if f.alternate() {
+                    UNTIL_FLOATING_DATE_TIME_FORMAT
+                } else {
+                    UNTIL_DATE_TIME_FORMAT
+                };
+                write!(f, ";UNTIL={}", until.format(format))?;
+            }
+            None => {}
+        }
+        if !self.by_day.is_empty() {
+            let by_day: Vec<_> = self.by_day.iter().map(ToString::to_string).collect();
+            write!(f, ";BYDAY={}", by_day.join(","))?;
+        }
+        if !self.by_month_day.is_empty() {
+            let by_month_day: Vec<_> = self.by_month_day.iter().map(ToString::to_string).collect();
+            write!(f, ";BYMONTHDAY={}", by_month_day.join(","))?;
+        }
+        if self.week_start != Weekday::Monday {
+            write!(f, ";WKST={}", weekday_str(self.week_start))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::datetime;
+
+    fn ts(date_time: PrimitiveDateTime) -> Timestamp {
+        date_time.into()
+    }
+
+    fn occurrences(rule: &str, dtstart: PrimitiveDateTime, n: usize) -> Vec<Timestamp> {
+        rule.parse::<RecurrenceRule>()
+            .unwrap()
+            .occurrences(ts(dtstart))
+            .take(n)
+            .collect()
+    }
+
+    #[test]
+    fn parse_and_format() {
+        for rule in [
+            "FREQ=DAILY",
+            "FREQ=WEEKLY;INTERVAL=2;COUNT=10;BYDAY=MO,WE",
+            "FREQ=MONTHLY;UNTIL=20241231T235959Z;BYDAY=-1FR",
+            "FREQ=MONTHLY;BYMONTHDAY=1,-1",
+            "FREQ=WEEKLY;BYDAY=TU;WKST=SU",
+            "FREQ=YEARLY",
+        ] {
+            assert_eq!(rule, rule.parse::<RecurrenceRule>().unwrap().to_string());
+        }
+        assert_eq!(
+            "FREQ=WEEKLY;BYDAY=SA",
+            "RRULE:freq=weekly;interval=1;byday=sa;"
+                .parse::<RecurrenceRule>()
+                .unwrap()
+                .to_string()
+        );
+        assert_eq!(
+            "FREQ=DAILY;UNTIL=20240301T235959Z",
+            "FREQ=DAILY;UNTIL=20240301"
+                .parse::<RecurrenceRule>()
+                .unwrap()
+                .to_string()
+        );
+        assert_eq!(
+            "FREQ=DAILY;UNTIL=20240301T235959",
+            format!(
+                "{:#}",
+                "FREQ=DAILY;UNTIL=20240301"
+                    .parse::<RecurrenceRule>()
+                    .unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn reject_invalid_or_unsupported_rules() {
+        assert_eq!(
+            Err(ParseError::MissingFrequency),
+            "".parse::<RecurrenceRule>()
+        );
+        assert_eq!(
+            Err(ParseError::MissingFrequency),
+            "INTERVAL=2".parse::<RecurrenceRule>()
+        );
+        assert_eq!(
+            Err(ParseError::CountAndUntil),
+            "FREQ=DAILY;COUNT=3;UNTIL=20240101".parse::<RecurrenceRule>()
+        );
+        assert_eq!(
+            Err(ParseError::DuplicatePart("FREQ".into())),
+            "FREQ=DAILY;FREQ=WEEKLY".parse::<RecurrenceRule>()
+        );
+        assert_eq!(
+            Err(ParseError::UnsupportedPart("BYSETPOS".into())),
+            "FREQ=MONTHLY;BYDAY=MO;BYSETPOS=1".parse::<RecurrenceRule>()
+        );
+        assert_eq!(
+            Err(ParseError::UnsupportedPart("BYDAY".into())),
+            "FREQ=WEEKLY;BYDAY=1MO".parse::<RecurrenceRule>()
+        );
+        assert!("FREQ=HOURLY".parse::<RecurrenceRule>().is_err());
+        assert!("FREQ=WEEKLY;INTERVAL=0".parse::<RecurrenceRule>().is_err());
+        assert!("FREQ=WEEKLY;BYDAY=XY".parse::<RecurrenceRule>().is_err());
+        assert!("FREQ=MONTHLY;BYDAY=6MO".parse::<RecurrenceRule>().is_err());
+        assert!(
+            "FREQ=MONTHLY;BYMONTHDAY=32"
+                .parse::<RecurrenceRule>()
+                .is_err()
+        );
+        assert!(
+            "FREQ=DAILY;UNTIL=20240230"
+                .parse::<RecurrenceRule>()
+                .is_err()
+        );
+        assert!("every week".parse::<RecurrenceRule>().is_err());
+    }
+
+    #[test]
+    fn weekly_on_multiple_days() {
+        assert_eq!(
+            vec![
+                ts(datetime!(2024-05-01 18:00)), // Wednesday
+                ts(datetime!(2024-05-06 18:00)),
+                ts(datetime!(2024-05-08 18:00)),
+                ts(datetime!(2024-05-13 18:00)),
+            ],
+            occurrences("FREQ=WEEKLY;BYDAY=MO,WE", datetime!(2024-05-01 18:00), 4)
+        );
+    }
+
+    #[test]
+    fn biweekly_with_week_start() {
+        let dtstart = datetime!(2024-05-05 10:00); // Sunday
+        assert_eq!(
+            vec![
+                ts(dtstart),
+                ts(datetime!(2024-05-07 10:00)),
+                ts(datetime!(2024-05-19 10:00)),
+                ts(datetime!(2024-05-21 10:00)),
+            ],
+            occurrences("FREQ=WEEKLY;INTERVAL=2;BYDAY=SU,TU;WKST=SU", dtstart, 4)
+        );
+        assert_eq!(
+            vec![
+                ts(dtstart),
+                ts(datetime!(2024-05-14 10:00)),
+                ts(datetime!(2024-05-19 10:00)),
+                ts(datetime!(2024-05-28 10:00)),
+            ],
+            occurrences("FREQ=WEEKLY;INTERVAL=2;BYDAY=SU,TU", dtstart, 4)
+        );
+    }
+
+    #[test]
+    fn monthly_on_the_last_friday() {
+        assert_eq!(
+            vec![
+                ts(datetime!(2024-01-26 15:00)),
+                ts(datetime!(2024-02-23 15:00)),
+                ts(datetime!(2024-03-29 15:00)),
+            ],
+            occurrences("FREQ=MONTHLY;BYDAY=-1FR", datetime!(2024-01-26 15:00), 3)
+        );
+    }
+
+    #[test]
+    fn monthly_on_the_first_saturday() {
+        assert_eq!(
+            vec![
+                ts(datetime!(2024-04-06 09:00)),
+                ts(datetime!(2024-05-04 09:00)),
+                ts(datetime!(2024-06-01 09:00)),
+            ],
+            occurrences("FREQ=MONTHLY;BYDAY=1SA", datetime!(2024-04-06 09:00), 3)
+        );
+    }
+
+    #[test]
+    fn monthly_skips_invalid_dates() {
+        assert_eq!(
+            vec![
+                ts(datetime!(2024-01-31 12:00)),
+                ts(datetime!(2024-03-31 12:00)),
+                ts(datetime!(2024-05-31 12:00)),
+            ],
+            occurrences("FREQ=MONTHLY", datetime!(2024-01-31 12:00), 3)
+        );
+        assert_eq!(
+            vec![
+                ts(datetime!(2024-01-31 12:00)),
+                ts(datetime!(2024-02-29 12:00)),
+                ts(datetime!(2024-03-31 12:00)),
+            ],
+            occurrences("FREQ=MONTHLY;BYMONTHDAY=-1", datetime!(2024-01-31 12:00), 3)
+        );
+    }
+
+    #[test]
+    fn yearly_on_leap_day() {
+        assert_eq!(
+            vec![
+                ts(datetime!(2024-02-29 12:00)),
+                ts(datetime!(2028-02-29 12:00)),
+            ],
+            occurrences("FREQ=YEARLY", datetime!(2024-02-29 12:00), 2)
+        );
+    }
+
+    #[test]
+    fn daily_with_count_and_until() {
+        let dtstart = datetime!(2024-05-30 08:00);
+        assert_eq!(
+            3,
+            occurrences("FREQ=DAILY;COUNT=3", dtstart, usize::MAX).len()
+        );
+        assert_eq!(
+            vec![
+                ts(dtstart),
+                ts(datetime!(2024-05-31 08:00)),
+                ts(datetime!(2024-06-01 08:00)),
+            ],
+            occurrences("FREQ=DAILY;UNTIL=20240601T080000Z", dtstart, usize::MAX)
+        );
+        assert_eq!(
+            vec![ts(dtstart), ts(datetime!(2024-06-03 08:00))],
+            occurrences("FREQ=DAILY;BYDAY=MO,TH;COUNT=2", dtstart, usize::MAX)
+        );
+    }
+
+    #[test]
+    fn start_always_counts_as_first_occurrence() {
+        // Thursday
+        let dtstart = datetime!(2024-05-30 08:00);
+        assert_eq!(
+            vec![ts(dtstart), ts(datetime!(2024-06-03 08:00))],
+            occurrences("FREQ=WEEKLY;BYDAY=MO;COUNT=2", dtstart, usize::MAX)
+        );
+    }
+
+    #[test]
+    fn infinite_rule_without_matching_periods_terminates() {
+        // There is no 30th of February
+        let dtstart = datetime!(2024-02-01 08:00);
+        assert_eq!(
+            vec![ts(dtstart)],
+            occurrences(
+                "FREQ=MONTHLY;INTERVAL=12;BYMONTHDAY=30",
+                dtstart,
+                usize::MAX
+            )
+        );
+    }
+
+    #[test]
+    fn exclude_dates() {
+        let dtstart = datetime!(2024-05-01 18:00);
+        let recurrence = Recurrence {
+            rule: "FREQ=WEEKLY;COUNT=4".parse().unwrap(),
+            exdates: vec![ts(datetime!(2024-05-08 18:00))],
+        };
+        assert_eq!(
+            vec![
+                ts(dtstart),
+                ts(datetime!(2024-05-15 18:00)),
+                ts(datetime!(2024-05-22 18:00)),
+            ],
+            recurrence.occurrences(ts(dtstart)).collect::<Vec<_>>()
+        );
+    }
+}
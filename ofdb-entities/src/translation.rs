@@ -0,0 +1,176 @@
+//! Titles and descriptions in other languages than the original text.
+//!
+//! Languages are identified by tags as specified by
+//! [BCP 47](https://www.rfc-editor.org/info/bcp47), e.g. `de`,
+//! `en-GB`, or `zh-Hant-TW`.
+
+use std::{fmt, str::FromStr};
+
+use thiserror::Error;
+
+/// A well-formed language tag in canonical case.
+///
+/// Only the syntax is validated, i.e. the subtags are not
+/// checked against the IANA language subtag registry.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Language(String);
+
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("Invalid language tag")]
+pub struct LanguageParseError;
+
+impl Language {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The primary language subtag, e.g. `en` for `en-GB`.
+    pub fn primary(&self) -> &str {
+        self.0.split('-').next().unwrap_or_default()
+    }
+}
+
+impl FromStr for Language {
+    type Err = LanguageParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut subtags = s.trim().split(['-', '_']);
+        let primary = subtags.next().unwrap_or_default();
+        if !(2..=8).contains(&primary.len())
+            || primary.len() == 4
+            || !primary.bytes().all(|b| b.is_ascii_alphabetic())
+        {
+            return Err(LanguageParseError);
+        }
+        let mut tag = primary.to_ascii_lowercase();
+        let mut extension = false;
+        for (i, subtag) in subtags.enumerate() {
+            if !(1..=8).contains(&subtag.len())
+                || !subtag.bytes().all(|b| b.is_ascii_alphanumeric())
+            {
+                return Err(LanguageParseError);
+            }
+            tag.push('-');
+            if subtag.len() == 1 {
+                // Singletons introduce extensions and private use subtags
+                extension = true;
+            }
+            if extension || i > 1 {
+                tag.push_str(&subtag.to_ascii_lowercase());
+            } else if subtag.len() == 4 && subtag.bytes().all(|b| b.is_ascii_alphabetic()) {
+                // Script, e.g. `Hant`
+                tag.push_str(&subtag[..1].to_ascii_uppercase());
+                tag.push_str(&subtag[1..].to_ascii_lowercase());
+            } else if subtag.len() == 2 {
+                // Region, e.g. `GB`
+                tag.push_str(&subtag.to_ascii_uppercase());
+            } else {
+                tag.push_str(&subtag.to_ascii_lowercase());
+            }
+        }
+        Ok(Self(tag))
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<Language> for String {
+    fn from(from: Language) -> Self {
+        from.0
+    }
+}
+
+/// The title and an optional description in another language.
+///
+/// A missing description falls back to the original description.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Translation {
+    pub lang: Language,
+    pub title: String,
+    pub description: Option<String>,
+}
+
+/// Select the translation that matches the preferred languages
+/// (in descending order of preference) best.
+///
+/// Exact matches take precedence over translations that only share
+/// the primary language, e.g. `de-AT` also matches `de` or (with
+/// lower priority) `de-CH`.
+pub fn select_translation<'a>(
+    translations: &'a [Translation],
+    preferred: &[Language],
+) -> Option<&'a Translation> {
+    preferred.iter().find_map(|lang| {
+        let find =
+            |matches: &dyn Fn(&Language) -> bool| translations.iter().find(|t| matches(&t.lang));
+        find(&|t| t == lang)
+            .or_else(|| find(&|t| t.as_str() == lang.primary()))
+            .or_else(|| find(&|t| t.primary() == lang.primary()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lang(s: &str) -> Language {
+        s.parse().unwrap()
+    }
+
+    fn translation(lang: &str) -> Translation {
+        Translation {
+            lang: self::lang(lang),
+            title: format!("title {lang}"),
+            description: None,
+        }
+    }
+
+    #[test]
+    fn parse_language_tags_in_canonical_case() {
+        assert_eq!("de", lang("de").as_str());
+        assert_eq!("en-GB", lang("EN-gb").as_str());
+        assert_eq!("en-GB", lang("en_GB").as_str());
+        assert_eq!("zh-Hant-TW", lang("zh-hant-tw").as_str());
+        assert_eq!("es-419", lang("es-419").as_str());
+        assert_eq!("de-CH-1901", lang("de-ch-1901").as_str());
+        assert_eq!("en-US-x-twain", lang("en-US-x-TWAIN").as_str());
+        assert_eq!("zh", lang("zh-Hant").primary());
+    }
+
+    #[test]
+    fn reject_invalid_language_tags() {
+        for tag in [
+            "",
+            "d",
+            "de-",
+            "-de",
+            "toolongtag",
+            "1de",
+            "de-ÄT",
+            "de AT",
+            "abcd",
+        ] {
+            assert_eq!(Err(LanguageParseError), tag.parse::<Language>(), "{tag}");
+        }
+    }
+
+    #[test]
+    fn select_best_matching_translation() {
+        let translations = vec![translation("en"), translation("de-CH"), translation("de")];
+        let select = |preferred: &[&str]| {
+            let preferred = preferred.iter().map(|s| lang(s)).collect::<Vec<_>>();
+            select_translation(&translations, &preferred).map(|t| t.lang.as_str())
+        };
+        assert_eq!(Some("de"), select(&["de"]));
+        assert_eq!(Some("de-CH"), select(&["de-CH"]));
+        assert_eq!(Some("de"), select(&["de-AT"]));
+        assert_eq!(Some("en"), select(&["en-US", "de"]));
+        assert_eq!(Some("de"), select(&["fr", "de"]));
+        assert_eq!(None, select(&["fr"]));
+        assert_eq!(None, select(&[]));
+    }
+}
@@ -0,0 +1,98 @@
+use std::fmt;
+
+use num_derive::{FromPrimitive, ToPrimitive};
+use strum::{EnumIter, EnumString, IntoStaticStr};
+
+use crate::{id::Id, nonce::Nonce, time::Timestamp, url::Url};
+
+/// The type of an event that is sent to webhooks.
+#[rustfmt::skip]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter, EnumString, IntoStaticStr)]
+pub enum WebhookEventType {
+    #[strum(serialize = "place.added")]
+    PlaceAdded,
+    #[strum(serialize = "place.updated")]
+    PlaceUpdated,
+    #[strum(serialize = "place.clearance_pending")]
+    PlaceClearancePending,
+    #[strum(serialize = "event.added")]
+    EventAdded,
+    #[strum(serialize = "event.updated")]
+    EventUpdated,
+    #[strum(serialize = "event.clearance_pending")]
+    EventClearancePending,
+}
+
+impl WebhookEventType {
+    pub fn as_str(self) -> &'static str {
+        self.into()
+    }
+}
+
+impl fmt::Display for WebhookEventType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A URL of an organization that receives signed event payloads.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Webhook {
+    pub id: Id,
+    pub org_id: Id,
+    pub url: Url,
+    /// The shared secret for signing the payloads
+    pub secret: String,
+    pub created_at: Timestamp,
+}
+
+impl Webhook {
+    /// Generate a new, random secret.
+    pub fn generate_secret() -> String {
+        format!("{}{}", Nonce::new(), Nonce::new())
+    }
+}
+
+#[rustfmt::skip]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromPrimitive, ToPrimitive)]
+pub enum WebhookDeliveryStatus {
+    Pending   = 0,
+    Delivered = 1,
+    Failed    = 2,
+}
+
+/// A single payload that is sent to a webhook,
+/// including the outcome of all attempts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebhookDelivery {
+    pub id: Id,
+    pub webhook_id: Id,
+    pub event_type: WebhookEventType,
+    /// The serialized JSON payload
+    pub payload: String,
+    pub created_at: Timestamp,
+    pub status: WebhookDeliveryStatus,
+    pub attempts: u32,
+    /// Only set for pending deliveries
+    pub next_attempt_at: Option<Timestamp>,
+    pub last_attempt_at: Option<Timestamp>,
+    pub last_response_status: Option<u16>,
+    pub last_error: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn event_type_strings() {
+        use strum::IntoEnumIterator as _;
+        for event_type in WebhookEventType::iter() {
+            assert_eq!(event_type, event_type.as_str().parse().unwrap());
+        }
+        assert_eq!(
+            WebhookEventType::PlaceClearancePending.to_string(),
+            "place.clearance_pending"
+        );
+    }
+}
@@ -1,5 +1,8 @@
 use gloo_net::http::Request;
-use ofdb_boundary::{ClearanceForPlace, PendingClearanceForPlace, PlaceHistory, ResultCount};
+use ofdb_boundary::{
+    ClearanceForEvent, ClearanceForPlace, EventHistory, PendingClearanceForEvent,
+    PendingClearanceForPlace, PlaceHistory, ResultCount,
+};
 
 use crate::{Result, into_json};
 
@@ -45,4 +48,31 @@ impl ClearanceApi {
         let response = request.send().await?;
         into_json(response).await
     }
+    pub async fn event_clearances(&self) -> Result<Vec<PendingClearanceForEvent>> {
+        let url = format!("{}/events/clearance", self.url);
+        let request = Request::get(&url)
+            .header("Authorization", &self.auth_header_value())
+            .header("Content-Type", "application/json");
+        let response = request.send().await?;
+        into_json(response).await
+    }
+    pub async fn event_history(&self, id: &str) -> Result<EventHistory> {
+        let url = format!("{}/events/{}/history", self.url, id);
+        let request = Request::get(&url)
+            .header("Authorization", &self.auth_header_value())
+            .header("Content-Type", "application/json");
+        let response = request.send().await?;
+        into_json(response).await
+    }
+    pub async fn update_event_clearances(
+        &self,
+        clearances: Vec<ClearanceForEvent>,
+    ) -> Result<ResultCount> {
+        let url = format!("{}/events/clearance", self.url);
+        let request = Request::post(&url)
+            .header("Authorization", &self.auth_header_value())
+            .json(&clearances)?;
+        let response = request.send().await?;
+        into_json(response).await
+    }
 }
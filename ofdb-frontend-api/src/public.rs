@@ -3,8 +3,8 @@ use percent_encoding::{NON_ALPHANUMERIC, utf8_percent_encode};
 use web_sys::RequestCredentials;
 
 use ofdb_boundary::{
-    Credentials, Entry, Event, MapBbox, NewPlace, NewPlaceRating, Rating, RequestPasswordReset,
-    SearchResponse, TagFrequency, UpdatePlace,
+    Credentials, Entry, Event, MapBbox, NewPlace, NewPlaceRating, Rating, RefreshToken,
+    RequestPasswordReset, SearchResponse, TagFrequency, UpdatePlace,
 };
 
 use crate::{Result, UserApi, bbox_string, into_json};
@@ -94,6 +94,17 @@ impl PublicApi {
         Ok(UserApi::new(self.url.clone(), token))
     }
 
+    /// Start a new session with the refresh token of a previous login.
+    pub async fn refresh_login(&self, refresh_token: String) -> Result<UserApi> {
+        let url = format!("{}/login/refresh", self.url);
+        let response = Request::post(&url)
+            .json(&RefreshToken { refresh_token })?
+            .send()
+            .await?;
+        let token = into_json(response).await?;
+        Ok(UserApi::new(self.url.clone(), token))
+    }
+
     pub async fn request_password_reset(&self, email: String) -> Result<()> {
         let url = format!("{}/users/reset-password-request", self.url);
         let response = Request::post(&url)
@@ -0,0 +1,79 @@
+use std::{
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+use anyhow::bail;
+
+use ofdb_core::gateways::image_storage::ImageStorage;
+use ofdb_entities::url::Url;
+
+/// Stores images as files in a local directory.
+///
+/// The directory is expected to be served under the base URL,
+/// i.e. the key of an image becomes the path of its URL.
+#[derive(Debug, Clone)]
+pub struct FileSystemImageStorage {
+    dir: PathBuf,
+    base_url: String,
+}
+
+impl FileSystemImageStorage {
+    pub fn new(dir: impl Into<PathBuf>, base_url: &str) -> Self {
+        Self {
+            dir: dir.into(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+}
+
+impl ImageStorage for FileSystemImageStorage {
+    fn store_image(&self, key: &str, _content_type: &str, data: &[u8]) -> anyhow::Result<Url> {
+        let relative_path = Path::new(key);
+        if !relative_path
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)))
+        {
+            bail!("Invalid image key: {key}");
+        }
+        let path = self.dir.join(relative_path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        // Readers must never see partially written files
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, data)?;
+        fs::rename(&tmp_path, &path)?;
+        log::debug!("Stored image {}", path.display());
+        Ok(format!("{}/{key}", self.base_url).parse()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    #[test]
+    fn store_images_in_subdirectories() {
+        let dir = env::temp_dir().join(format!("ofdb-image-storage-{}", std::process::id()));
+        let storage = FileSystemImageStorage::new(&dir, "https://example.com/images/");
+        let url = storage
+            .store_image("places/x/y.jpg", "image/jpeg", b"data")
+            .unwrap();
+        assert_eq!(url.as_str(), "https://example.com/images/places/x/y.jpg");
+        assert_eq!(fs::read(dir.join("places/x/y.jpg")).unwrap(), b"data");
+        assert!(
+            storage
+                .store_image("../y.jpg", "image/jpeg", b"data")
+                .is_err()
+        );
+        assert!(
+            storage
+                .store_image("/tmp/y.jpg", "image/jpeg", b"data")
+                .is_err()
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,4 +1,6 @@
 pub mod email;
+pub mod image_storage;
 pub mod notify;
 pub mod opencage;
 pub mod user_communication;
+pub mod webhook;
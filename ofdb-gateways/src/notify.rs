@@ -149,6 +149,9 @@ impl NotificationGateway for Notify {
             E::ReminderCreated { email, recipients } => {
                 self.email_gw.compose_and_send(recipients, email);
             }
+            E::PlaceClearancePending { .. } | E::EventClearancePending { .. } => {
+                // Organizations are notified by webhooks
+            }
         }
     }
 }
@@ -158,5 +161,8 @@ fn compose_and_send_emails(
     recipients: &[EmailAddress],
     email_content: &EmailContent,
 ) {
+    if recipients.is_empty() {
+        return;
+    }
     gw.compose_and_send(recipients, email_content);
 }
@@ -271,12 +271,14 @@ mod tests {
                 ..Default::default()
             }),
             tags: vec!["<tag1>".into(), "<tag2>".into()],
+            translations: vec![],
         }
     }
 
     fn new_event() -> Event {
         Event {
             id: "<id>".into(),
+            revision: Revision::initial(),
             created_by: Some("created_by@example.com".parse().unwrap()),
             archived: None,
             start: Timestamp::now(),
@@ -303,6 +305,8 @@ mod tests {
             image_url: None,
             image_link_url: None,
             tags: vec!["<tag1>".into(), "<tag2>".into()],
+            recurrence: None,
+            translations: vec![],
         }
     }
 
@@ -0,0 +1,201 @@
+use std::time::Duration;
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use ofdb_core::gateways::webhook::WebhookGateway;
+use ofdb_entities::{
+    time::Timestamp,
+    webhook::{Webhook, WebhookDelivery},
+};
+
+pub const EVENT_HEADER: &str = "X-Ofdb-Event";
+pub const DELIVERY_HEADER: &str = "X-Ofdb-Delivery";
+pub const TIMESTAMP_HEADER: &str = "X-Ofdb-Timestamp";
+pub const SIGNATURE_HEADER: &str = "X-Ofdb-Signature";
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Sends the payloads of webhook deliveries as signed HTTP POST requests.
+///
+/// The Unix time stamp of the request in seconds is sent in the
+/// `X-Ofdb-Timestamp` header. The time stamp and the payload joined
+/// by a period, i.e. `<timestamp>.<payload>`, are signed with the secret
+/// of the webhook using HMAC-SHA256. The hex encoded signature is sent in
+/// the `X-Ofdb-Signature` header as `sha256=<signature>`.
+///
+/// Receivers should reject requests with outdated time stamps
+/// to prevent replay attacks.
+#[derive(Debug, Clone)]
+pub struct HttpWebhookGateway {
+    client: reqwest::blocking::Client,
+}
+
+impl HttpWebhookGateway {
+    pub fn new() -> anyhow::Result<Self> {
+        let client = reqwest::blocking::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()?;
+        Ok(Self { client })
+    }
+}
+
+impl WebhookGateway for HttpWebhookGateway {
+    fn deliver(&self, webhook: &Webhook, delivery: &WebhookDelivery) -> anyhow::Result<u16> {
+        let timestamp = Timestamp::now().as_secs();
+        let signature = sign_payload(
+            &webhook.secret,
+            &format!("{timestamp}.{}", delivery.payload),
+        );
+        let response = self
+            .client
+            .post(webhook.url.as_str())
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(EVENT_HEADER, delivery.event_type.as_str())
+            .header(DELIVERY_HEADER, delivery.id.as_str())
+            .header(TIMESTAMP_HEADER, timestamp.to_string())
+            .header(SIGNATURE_HEADER, format!("sha256={signature}"))
+            .body(delivery.payload.clone())
+            .send()?;
+        Ok(response.status().as_u16())
+    }
+}
+
+/// The hex encoded HMAC-SHA256 signature of a payload.
+pub fn sign_payload(secret: &str, payload: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(payload.as_bytes());
+    mac.finalize()
+        .into_bytes()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    use ofdb_entities::{id::Id, time::Timestamp, webhook::*};
+
+    use super::*;
+
+    #[test]
+    fn sign() {
+        // Test vector from RFC 4231 (Test Case 2)
+        assert_eq!(
+            sign_payload("Jefe", "what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    /// Accept a single HTTP request and respond with the given status.
+    ///
+    /// Returns the URL of the stub and a handle that yields
+    /// the headers (in lowercase) and the body of the request.
+    fn http_stub(status: u16) -> (String, thread::JoinHandle<(Vec<String>, String)>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut headers = vec![];
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                let line = line.trim_end().to_lowercase();
+                if line.is_empty() {
+                    break;
+                }
+                headers.push(line);
+            }
+            let content_length = headers
+                .iter()
+                .find_map(|h| h.strip_prefix("content-length: "))
+                .map(|len| len.parse().unwrap())
+                .unwrap_or(0);
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            write!(
+                reader.get_mut(),
+                "HTTP/1.1 {status} Stub\r\ncontent-length: 0\r\nconnection: close\r\n\r\n"
+            )
+            .unwrap();
+            (headers, String::from_utf8(body).unwrap())
+        });
+        (url, handle)
+    }
+
+    fn webhook_and_delivery(url: &str) -> (Webhook, WebhookDelivery) {
+        let now = Timestamp::now();
+        let webhook = Webhook {
+            id: Id::new(),
+            org_id: Id::new(),
+            url: url.parse().unwrap(),
+            secret: "secret".into(),
+            created_at: now,
+        };
+        let delivery = WebhookDelivery {
+            id: "delivery".into(),
+            webhook_id: webhook.id.clone(),
+            event_type: WebhookEventType::PlaceAdded,
+            payload: r#"{"type":"place.added"}"#.into(),
+            created_at: now,
+            status: WebhookDeliveryStatus::Pending,
+            attempts: 0,
+            next_attempt_at: Some(now),
+            last_attempt_at: None,
+            last_response_status: None,
+            last_error: None,
+        };
+        (webhook, delivery)
+    }
+
+    #[test]
+    fn post_signed_payload() {
+        let (url, stub) = http_stub(202);
+        let (webhook, delivery) = webhook_and_delivery(&url);
+        let gw = HttpWebhookGateway::new().unwrap();
+        assert_eq!(gw.deliver(&webhook, &delivery).unwrap(), 202);
+
+        let (headers, body) = stub.join().unwrap();
+        assert_eq!(body, delivery.payload);
+        assert!(headers.contains(&"post /hook http/1.1".to_string()));
+        assert!(headers.contains(&"x-ofdb-event: place.added".to_string()));
+        assert!(headers.contains(&"x-ofdb-delivery: delivery".to_string()));
+        let timestamp = headers
+            .iter()
+            .find_map(|h| h.strip_prefix("x-ofdb-timestamp: "))
+            .unwrap();
+        assert!(timestamp.parse::<i64>().unwrap() <= Timestamp::now().as_secs());
+        let signature = format!(
+            "x-ofdb-signature: sha256={}",
+            sign_payload("secret", &format!("{timestamp}.{}", delivery.payload))
+        );
+        assert!(headers.contains(&signature));
+    }
+
+    #[test]
+    fn return_error_status() {
+        let (url, stub) = http_stub(500);
+        let (webhook, delivery) = webhook_and_delivery(&url);
+        let gw = HttpWebhookGateway::new().unwrap();
+        assert_eq!(gw.deliver(&webhook, &delivery).unwrap(), 500);
+        stub.join().unwrap();
+    }
+
+    #[test]
+    fn fail_if_unreachable() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        drop(listener);
+        let (webhook, delivery) = webhook_and_delivery(&url);
+        let gw = HttpWebhookGateway::new().unwrap();
+        assert!(gw.deliver(&webhook, &delivery).is_err());
+    }
+}
@@ -0,0 +1,172 @@
+//! Export places and events as [GeoJSON](https://datatracker.ietf.org/doc/html/rfc7946).
+
+use ofdb_boundary::{CustomLink, EntrySearchRatings};
+use serde::Serialize;
+
+use crate::{adapters::json, core::entities::*};
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type")]
+pub struct FeatureCollection<P> {
+    pub features: Vec<Feature<P>>,
+}
+
+impl<P> FromIterator<Feature<P>> for FeatureCollection<P> {
+    fn from_iter<I: IntoIterator<Item = Feature<P>>>(iter: I) -> Self {
+        Self {
+            features: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type")]
+pub struct Feature<P> {
+    pub id: String,
+    /// Features without a (valid) location have no geometry
+    pub geometry: Option<Geometry>,
+    pub properties: P,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type")]
+pub enum Geometry {
+    Point {
+        /// Longitude and latitude in degrees (in this order!)
+        coordinates: [f64; 2],
+    },
+}
+
+impl From<MapPoint> for Geometry {
+    fn from(pos: MapPoint) -> Self {
+        Self::Point {
+            coordinates: [pos.lng().to_deg(), pos.lat().to_deg()],
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct PlaceProperties {
+    pub created: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_by: Option<String>,
+    pub version: u64,
+    pub title: String,
+    pub description: String,
+    pub street: Option<String>,
+    pub zip: Option<String>,
+    pub city: Option<String>,
+    pub country: Option<String>,
+    pub state: Option<String>,
+    pub contact_name: Option<String>,
+    pub email: Option<String>,
+    pub telephone: Option<String>,
+    pub homepage: Option<String>,
+    pub opening_hours: Option<String>,
+    pub founded_on: Option<String>,
+    pub categories: Vec<String>,
+    pub tags: Vec<String>,
+    pub ratings: EntrySearchRatings,
+    pub license: String,
+    pub image_url: Option<String>,
+    pub image_link_url: Option<String>,
+    #[serde(rename = "custom", skip_serializing_if = "Vec::is_empty")]
+    pub custom_links: Vec<CustomLink>,
+}
+
+impl From<(Place, Vec<Category>, AvgRatings)> for Feature<PlaceProperties> {
+    fn from(from: (Place, Vec<Category>, AvgRatings)) -> Self {
+        let (place, categories, ratings) = from;
+
+        let Place {
+            id,
+            license,
+            revision,
+            created:
+                Activity {
+                    at: created_at,
+                    by: created_by,
+                },
+            title,
+            description,
+            location,
+            links,
+            tags,
+            contact,
+            opening_hours,
+            founded_on,
+            translations: _,
+        } = place;
+
+        let Location { pos, address } = location;
+
+        let Address {
+            street,
+            zip,
+            city,
+            country,
+            state,
+        } = address.unwrap_or_default();
+
+        let Contact {
+            name: contact_name,
+            email,
+            phone: telephone,
+        } = contact.unwrap_or_default();
+
+        let Links {
+            homepage,
+            image,
+            image_href,
+            custom,
+        } = links.unwrap_or_default();
+
+        let properties = PlaceProperties {
+            created: created_at.as_secs(),
+            created_by: created_by.map(EmailAddress::into_string),
+            version: revision.into(),
+            title,
+            description,
+            street,
+            zip,
+            city,
+            country,
+            state,
+            contact_name,
+            email: email.map(EmailAddress::into_string),
+            telephone,
+            homepage: homepage.map(Into::into),
+            opening_hours: opening_hours.map(Into::into),
+            founded_on: founded_on.as_ref().map(ToString::to_string),
+            categories: categories.into_iter().map(|c| c.id.to_string()).collect(),
+            tags,
+            ratings: json::entry_search_ratings(ratings),
+            license,
+            image_url: image.map(Into::into),
+            image_link_url: image_href.map(Into::into),
+            custom_links: custom.into_iter().map(Into::into).collect(),
+        };
+
+        Self {
+            id: id.into(),
+            geometry: pos.is_valid().then(|| pos.into()),
+            properties,
+        }
+    }
+}
+
+impl From<Event> for Feature<ofdb_boundary::Event> {
+    fn from(from: Event) -> Self {
+        let geometry = from
+            .location
+            .as_ref()
+            .map(|l| l.pos)
+            .filter(|pos| pos.is_valid())
+            .map(Into::into);
+        Self {
+            id: from.id.to_string(),
+            geometry,
+            properties: from.into(),
+        }
+    }
+}
@@ -25,6 +25,19 @@ pub mod from_json {
         }
     }
 
+    pub fn translation(from: Translation) -> usecases::TranslationParam {
+        let Translation {
+            lang,
+            title,
+            description,
+        } = from;
+        usecases::TranslationParam {
+            lang,
+            title,
+            description,
+        }
+    }
+
     pub fn try_new_place(p: NewPlace) -> anyhow::Result<usecases::NewPlace> {
         let NewPlace {
             title,
@@ -48,6 +61,7 @@ pub mod from_json {
             image_url,
             image_link_url,
             links,
+            translations,
         } = p;
 
         let email = email
@@ -77,6 +91,7 @@ pub mod from_json {
             image_url,
             image_link_url,
             custom_links: links.into_iter().map(custom_link).collect(),
+            translations: translations.into_iter().map(translation).collect(),
         })
     }
 
@@ -103,6 +118,7 @@ pub mod from_json {
             image_url,
             image_link_url,
             links,
+            translations,
         } = p;
 
         let email = email
@@ -133,6 +149,7 @@ pub mod from_json {
             image_url,
             image_link_url,
             custom_links,
+            translations: translations.into_iter().map(translation).collect(),
         })
     }
 
@@ -181,6 +198,9 @@ pub mod from_json {
             organizer,
             image_url,
             image_link_url,
+            rrule,
+            exdates,
+            translations,
         } = ev;
 
         let email = email
@@ -193,6 +213,14 @@ pub mod from_json {
 
         let start = e::Timestamp::try_from_secs(start)?;
         let end = end.map(e::Timestamp::try_from_secs).transpose()?;
+        let exdates = exdates
+            .map(|exdates| {
+                exdates
+                    .into_iter()
+                    .map(e::Timestamp::try_from_secs)
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .transpose()?;
 
         Ok(usecases::NewEvent {
             title,
@@ -215,14 +243,52 @@ pub mod from_json {
             organizer,
             image_url,
             image_link_url,
+            rrule,
+            exdates,
+            translations: translations.into_iter().map(translation).collect(),
         })
     }
 
+    pub fn new_organization(from: NewOrganization) -> usecases::NewOrganization {
+        let NewOrganization {
+            name,
+            moderated_tags,
+        } = from;
+        usecases::NewOrganization {
+            name,
+            moderated_tags: moderated_tags.into_iter().map(Into::into).collect(),
+        }
+    }
+
     pub fn try_new_user(new_user: NewUser) -> anyhow::Result<usecases::NewUser> {
         let NewUser { email, password } = new_user;
         let email = email.parse::<e::EmailAddress>()?;
         Ok(usecases::NewUser { email, password })
     }
+
+    pub fn try_new_api_token(
+        new_token: NewApiToken,
+    ) -> Result<usecases::NewApiToken, usecases::Error> {
+        let NewApiToken {
+            name,
+            scopes,
+            expires_at,
+        } = new_token;
+        let scopes = scopes
+            .iter()
+            .map(|scope| scope.parse::<e::ApiTokenScope>())
+            .collect::<Result<_, _>>()
+            .map_err(|_| usecases::Error::ApiTokenScope)?;
+        let expires_at = expires_at
+            .map(e::Timestamp::try_from)
+            .transpose()
+            .map_err(|_| usecases::Error::TokenExpired)?;
+        Ok(usecases::NewApiToken {
+            name,
+            scopes,
+            expires_at,
+        })
+    }
 }
 
 pub mod to_json {
@@ -239,6 +305,10 @@ pub mod to_json {
         match t {
             U::SimilarChars => DuplicateType::SimilarChars,
             U::SimilarWords => DuplicateType::SimilarWords,
+            U::SamePhone => DuplicateType::SamePhone,
+            U::SameEmail => DuplicateType::SameEmail,
+            U::SameAddress => DuplicateType::SameAddress,
+            U::SameHomepage => DuplicateType::SameHomepage,
         }
     }
 }
@@ -252,7 +322,7 @@ pub fn place_serach_result_from_indexed_place(from: IndexedPlace) -> PlaceSearch
         tags,
         pos,
         ratings,
-        ..
+        translations: _,
     } = from;
     // The status should never be undefined! It is optional only
     // for technical reasons.
@@ -262,6 +332,23 @@ pub fn place_serach_result_from_indexed_place(from: IndexedPlace) -> PlaceSearch
     let categories = categories.into_iter().map(|c| c.id.to_string()).collect();
     let lat = pos.lat().to_deg();
     let lng = pos.lng().to_deg();
+    let ratings = entry_search_ratings(ratings);
+    PlaceSearchResult {
+        id,
+        status,
+        lat,
+        lng,
+        title,
+        description,
+        categories,
+        tags,
+        ratings,
+        distance: None,
+        lang: None,
+    }
+}
+
+pub fn entry_search_ratings(ratings: e::AvgRatings) -> EntrySearchRatings {
     let e::AvgRatings {
         diversity,
         fairness,
@@ -271,7 +358,7 @@ pub fn place_serach_result_from_indexed_place(from: IndexedPlace) -> PlaceSearch
         transparency,
     } = ratings;
     let total = ratings.total().into();
-    let ratings = EntrySearchRatings {
+    EntrySearchRatings {
         total,
         diversity: diversity.into(),
         fairness: fairness.into(),
@@ -279,17 +366,6 @@ pub fn place_serach_result_from_indexed_place(from: IndexedPlace) -> PlaceSearch
         renewable: renewable.into(),
         solidarity: solidarity.into(),
         transparency: transparency.into(),
-    };
-    PlaceSearchResult {
-        id,
-        status,
-        lat,
-        lng,
-        title,
-        description,
-        categories,
-        tags,
-        ratings,
     }
 }
 
@@ -307,6 +383,7 @@ pub fn entry_from_place_with_ratings(place: e::Place, ratings: Vec<e::Rating>) -
         founded_on,
         links,
         tags,
+        translations,
     } = place;
 
     let e::Location { pos, address } = location;
@@ -339,6 +416,10 @@ pub fn entry_from_place_with_ratings(place: e::Place, ratings: Vec<e::Rating>) -
 
     let (tags, categories) = e::Category::split_from_tags(tags);
 
+    let opening_hours_invalid = opening_hours
+        .as_ref()
+        .is_some_and(|opening_hours| opening_hours.rules().is_err());
+
     Entry {
         id: id.into(),
         created: created.at.as_secs(),
@@ -365,5 +446,8 @@ pub fn entry_from_place_with_ratings(place: e::Place, ratings: Vec<e::Rating>) -
         image_url: image_url.map(Into::into),
         image_link_url: image_link_url.map(Into::into),
         custom_links: custom_links.into_iter().map(Into::into).collect(),
+        opening_hours_invalid,
+        translations: translations.into_iter().map(Into::into).collect(),
+        lang: None,
     }
 }
@@ -1,2 +1,4 @@
 pub mod csv;
+pub mod geojson;
 pub mod json;
+pub mod mvt;
@@ -0,0 +1,327 @@
+//! Encode places and events as [Mapbox Vector Tiles](https://github.com/mapbox/vector-tile-spec/tree/master/2.1).
+//!
+//! Only point features are supported.
+
+use std::{collections::HashMap, f64::consts::PI};
+
+use crate::core::{entities::*, usecases::MAX_ZOOM};
+
+/// The resolution of a tile in each dimension
+pub const TILE_EXTENT: u32 = 4096;
+
+// The maximum latitude that is covered by the Web Mercator projection
+const MAX_LAT_DEG: f64 = 85.051_128_779_806_59;
+
+const VERSION: u32 = 2;
+
+// Protobuf wire types
+const WIRE_TYPE_VARINT: u32 = 0;
+const WIRE_TYPE_64BIT: u32 = 1;
+const WIRE_TYPE_LEN: u32 = 2;
+
+const GEOM_TYPE_POINT: u32 = 1;
+const GEOM_COMMAND_MOVE_TO: u32 = 1;
+
+/// The address of a tile in the Web Mercator tiling scheme,
+/// i.e. the origin is in the north-west.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TileId {
+    pub z: u8,
+    pub x: u32,
+    pub y: u32,
+}
+
+impl TileId {
+    pub fn try_new(z: u8, x: u32, y: u32) -> Option<Self> {
+        if z > MAX_ZOOM {
+            return None;
+        }
+        let tiles = 1u32 << z;
+        if x >= tiles || y >= tiles {
+            return None;
+        }
+        Some(Self { z, x, y })
+    }
+
+    fn tiles(self) -> f64 {
+        f64::from(1u32 << self.z)
+    }
+
+    /// The area that is covered by this tile.
+    pub fn bbox(self) -> MapBbox {
+        let tiles = self.tiles();
+        let lng_deg = |x: u32| f64::from(x) / tiles * 360.0 - 180.0;
+        let lat_deg = |y: u32| {
+            (PI * (1.0 - 2.0 * f64::from(y) / tiles))
+                .sinh()
+                .atan()
+                .to_degrees()
+        };
+        MapBbox::new(
+            MapPoint::from_lat_lng_deg(lat_deg(self.y + 1), lng_deg(self.x)),
+            MapPoint::from_lat_lng_deg(lat_deg(self.y), lng_deg(self.x + 1)),
+        )
+    }
+
+    /// Project a position into the coordinate space of this tile.
+    ///
+    /// Positions outside of this tile result in coordinates
+    /// outside of the range `0..TILE_EXTENT`.
+    pub fn project(self, pos: MapPoint) -> (i32, i32) {
+        let tiles = self.tiles();
+        let lat_rad = pos
+            .lat()
+            .to_deg()
+            .clamp(-MAX_LAT_DEG, MAX_LAT_DEG)
+            .to_radians();
+        let x = (pos.lng().to_deg() + 180.0) / 360.0 * tiles;
+        let y = (1.0 - lat_rad.tan().asinh() / PI) / 2.0 * tiles;
+        let extent = f64::from(TILE_EXTENT);
+        (
+            ((x - f64::from(self.x)) * extent).round() as i32,
+            ((y - f64::from(self.y)) * extent).round() as i32,
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    String(String),
+    Double(f64),
+    Int(i64),
+    Uint(u64),
+}
+
+impl Value {
+    fn encode(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        match self {
+            Self::String(s) => write_bytes(&mut buf, 1, s.as_bytes()),
+            Self::Double(d) => {
+                write_key(&mut buf, 3, WIRE_TYPE_64BIT);
+                buf.extend_from_slice(&d.to_le_bytes());
+            }
+            Self::Int(i) => write_varint_field(&mut buf, 4, *i as u64),
+            Self::Uint(u) => write_varint_field(&mut buf, 5, *u),
+        }
+        buf
+    }
+}
+
+impl From<String> for Value {
+    fn from(from: String) -> Self {
+        Self::String(from)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(from: &str) -> Self {
+        Self::String(from.to_owned())
+    }
+}
+
+impl From<f64> for Value {
+    fn from(from: f64) -> Self {
+        Self::Double(from)
+    }
+}
+
+impl From<i64> for Value {
+    fn from(from: i64) -> Self {
+        Self::Int(from)
+    }
+}
+
+impl From<u64> for Value {
+    fn from(from: u64) -> Self {
+        Self::Uint(from)
+    }
+}
+
+/// A named layer of point features.
+#[derive(Debug, Default)]
+pub struct Layer {
+    name: String,
+    keys: Vec<String>,
+    key_indexes: HashMap<String, u32>,
+    // Values are deduplicated by their encoding
+    values: Vec<Vec<u8>>,
+    value_indexes: HashMap<Vec<u8>, u32>,
+    features: Vec<Vec<u8>>,
+}
+
+impl Layer {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.features.is_empty()
+    }
+
+    pub fn add_point<'a>(
+        &mut self,
+        (x, y): (i32, i32),
+        properties: impl IntoIterator<Item = (&'a str, Value)>,
+    ) {
+        let mut tags = Vec::new();
+        for (key, value) in properties {
+            tags.push(self.key_index(key));
+            tags.push(self.value_index(value));
+        }
+        let geometry = [
+            command_integer(GEOM_COMMAND_MOVE_TO, 1),
+            zigzag(x),
+            zigzag(y),
+        ];
+        let mut feature = Vec::new();
+        write_packed_varints(&mut feature, 2, tags);
+        write_varint_field(&mut feature, 3, GEOM_TYPE_POINT.into());
+        write_packed_varints(&mut feature, 4, geometry);
+        self.features.push(feature);
+    }
+
+    fn key_index(&mut self, key: &str) -> u32 {
+        if let Some(index) = self.key_indexes.get(key) {
+            return *index;
+        }
+        let index = self.keys.len() as u32;
+        self.keys.push(key.to_owned());
+        self.key_indexes.insert(key.to_owned(), index);
+        index
+    }
+
+    fn value_index(&mut self, value: Value) -> u32 {
+        let encoded = value.encode();
+        if let Some(index) = self.value_indexes.get(&encoded) {
+            return *index;
+        }
+        let index = self.values.len() as u32;
+        self.values.push(encoded.clone());
+        self.value_indexes.insert(encoded, index);
+        index
+    }
+
+    fn encode(&self, buf: &mut Vec<u8>) {
+        write_varint_field(buf, 15, VERSION.into());
+        write_bytes(buf, 1, self.name.as_bytes());
+        for feature in &self.features {
+            write_bytes(buf, 2, feature);
+        }
+        for key in &self.keys {
+            write_bytes(buf, 3, key.as_bytes());
+        }
+        for value in &self.values {
+            write_bytes(buf, 4, value);
+        }
+        write_varint_field(buf, 5, TILE_EXTENT.into());
+    }
+}
+
+/// Encode all non-empty layers into a single tile.
+pub fn encode_tile(layers: &[Layer]) -> Vec<u8> {
+    let mut buf = Vec::new();
+    for layer in layers.iter().filter(|layer| !layer.is_empty()) {
+        let mut encoded_layer = Vec::new();
+        layer.encode(&mut encoded_layer);
+        write_bytes(&mut buf, 3, &encoded_layer);
+    }
+    buf
+}
+
+const fn command_integer(id: u32, count: u32) -> u32 {
+    (id & 0x7) | (count << 3)
+}
+
+const fn zigzag(n: i32) -> u32 {
+    ((n << 1) ^ (n >> 31)) as u32
+}
+
+fn write_varint(buf: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buf.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+fn write_key(buf: &mut Vec<u8>, field: u32, wire_type: u32) {
+    write_varint(buf, u64::from((field << 3) | wire_type));
+}
+
+fn write_varint_field(buf: &mut Vec<u8>, field: u32, value: u64) {
+    write_key(buf, field, WIRE_TYPE_VARINT);
+    write_varint(buf, value);
+}
+
+fn write_bytes(buf: &mut Vec<u8>, field: u32, bytes: &[u8]) {
+    write_key(buf, field, WIRE_TYPE_LEN);
+    write_varint(buf, bytes.len() as u64);
+    buf.extend_from_slice(bytes);
+}
+
+fn write_packed_varints(buf: &mut Vec<u8>, field: u32, values: impl IntoIterator<Item = u32>) {
+    let mut packed = Vec::new();
+    for value in values {
+        write_varint(&mut packed, value.into());
+    }
+    write_bytes(buf, field, &packed);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tile_bbox_and_projection() {
+        assert!(TileId::try_new(1, 2, 0).is_none());
+        assert!(TileId::try_new(MAX_ZOOM + 1, 0, 0).is_none());
+
+        let world = TileId::try_new(0, 0, 0).unwrap();
+        let bbox = world.bbox();
+        assert!((bbox.southwest().lng().to_deg() + 180.0).abs() < 1e-6);
+        assert!((bbox.northeast().lat().to_deg() - MAX_LAT_DEG).abs() < 1e-6);
+        assert_eq!(
+            (2048, 2048),
+            world.project(MapPoint::from_lat_lng_deg(0, 0))
+        );
+
+        // Stuttgart
+        let pos = MapPoint::from_lat_lng_deg(48.7755, 9.1827);
+        let tile = TileId::try_new(10, 538, 352).unwrap();
+        assert!(tile.bbox().contains_point(pos));
+        let (x, y) = tile.project(pos);
+        assert!((0..TILE_EXTENT as i32).contains(&x));
+        assert!((0..TILE_EXTENT as i32).contains(&y));
+    }
+
+    #[test]
+    fn encode_point_feature() {
+        assert_eq!(0, zigzag(0));
+        assert_eq!(1, zigzag(-1));
+        assert_eq!(2, zigzag(1));
+        assert_eq!(9, command_integer(GEOM_COMMAND_MOVE_TO, 1));
+
+        let mut layer = Layer::new("places");
+        layer.add_point((25, 17), [("id", Value::from("a")), ("n", 1u64.into())]);
+        layer.add_point((1, 1), [("id", Value::from("b")), ("n", 1u64.into())]);
+        assert_eq!(2, layer.keys.len());
+        // The value `1` is shared by both features
+        assert_eq!(3, layer.values.len());
+        assert_eq!(
+            vec![
+                0x12, 0x04, 0x00, 0x00, 0x01, 0x01, // tags
+                0x18, 0x01, // type = point
+                0x22, 0x03, 0x09, 0x32, 0x22 // geometry
+            ],
+            layer.features[0]
+        );
+
+        assert!(encode_tile(&[Layer::new("empty")]).is_empty());
+        let tile = encode_tile(&[layer]);
+        assert_eq!(0x1a, tile[0]);
+    }
+}
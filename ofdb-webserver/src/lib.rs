@@ -12,16 +12,19 @@ extern crate log;
 #[macro_use]
 extern crate serde;
 
-use ofdb_core::gateways::{geocode::GeoCodingGateway, notify::NotificationGateway};
-use ofdb_db_sqlite::Connections;
+use ofdb_application::db::Connections;
+use ofdb_core::gateways::{
+    geocode::GeoCodingGateway, image_storage::ImageStorage, notify::NotificationGateway,
+};
 use ofdb_db_tantivy as tantivy;
 
 mod adapters;
 mod core;
 mod web;
 
-pub use web::Cfg;
+pub use web::{Cfg, ImagesCfg, jwt::JwtCfg};
 
+#[allow(clippy::too_many_arguments)]
 pub async fn run(
     connections: Connections,
     search_engine: tantivy::SearchEngine,
@@ -29,6 +32,7 @@ pub async fn run(
     cfg: Cfg,
     geo_gw: Box<dyn GeoCodingGateway + Send + Sync>,
     notify_gw: Box<dyn NotificationGateway + Send + Sync>,
+    image_storage: Option<Box<dyn ImageStorage + Send + Sync>>,
     version: &'static str,
 ) {
     let search_engine = web::tantivy::SearchEngine(search_engine);
@@ -40,6 +44,7 @@ pub async fn run(
         cfg,
         geo_gw,
         notify_gw,
+        image_storage,
         version,
     )
     .await;
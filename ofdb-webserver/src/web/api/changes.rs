@@ -0,0 +1,17 @@
+use super::*;
+
+#[get("/changes?<cursor>&<limit>")]
+pub fn get_changes(
+    db: sqlite::Connections,
+    cursor: Option<&str>,
+    limit: Option<u64>,
+) -> Result<json::Changes> {
+    let usecases::Changes {
+        changes,
+        next_cursor,
+    } = usecases::load_changes(&db.shared()?, cursor, limit)?;
+    Ok(Json(json::Changes {
+        changes: changes.into_iter().map(Into::into).collect(),
+        next_cursor: next_cursor.as_ref().map(ToString::to_string),
+    }))
+}
@@ -19,6 +19,7 @@ pub fn get_entry(
     db: sqlite::Connections,
     ids: String,
     query: GetEntryQuery,
+    languages: Languages,
 ) -> Result<Vec<json::Entry>> {
     // TODO: Only lookup and return a single entity
     // TODO: Add a new method for searching multiple ids
@@ -31,9 +32,13 @@ pub fn get_entry(
         let db = db.shared()?;
         let places = usecases::load_places(&db, &ids, *org_tag)?;
         let mut results = Vec::with_capacity(places.len());
-        for (place, _) in places.into_iter() {
+        for (mut place, _) in places.into_iter() {
             let r = db.load_ratings_of_place(place.id.as_ref())?;
-            results.push(json::entry_from_place_with_ratings(place, r));
+            let lang = place.translate(&languages.0);
+            results.push(json::Entry {
+                lang: lang.map(Into::into),
+                ..json::entry_from_place_with_ratings(place, r)
+            });
         }
         results
     };
@@ -166,7 +171,11 @@ pub fn post_entry(
     body: JsonResult<json::NewPlace>,
     cfg: &State<Cfg>,
 ) -> Result<String> {
-    let org = auth.organization(&connections.shared()?).ok();
+    let org = match auth.organization(&connections, ApiTokenScope::PlacesWrite) {
+        Ok(org) => Some(org),
+        Err(AppError::Business(Error::Parameter(ParameterError::Unauthorized))) => None,
+        Err(err) => return Err(err.into()),
+    };
     if org.is_none() && auth.account_email().is_err() && cfg.protect_with_captcha {
         auth.has_captcha()?;
     }
@@ -196,7 +205,11 @@ pub fn put_entry(
     data: JsonResult<json::UpdatePlace>,
     cfg: &State<Cfg>,
 ) -> Result<String> {
-    let org = auth.organization(&connections.shared()?).ok();
+    let org = match auth.organization(&connections, ApiTokenScope::PlacesWrite) {
+        Ok(org) => Some(org),
+        Err(AppError::Business(Error::Parameter(ParameterError::Unauthorized))) => None,
+        Err(err) => return Err(err.into()),
+    };
     if org.is_none() && auth.account_email().is_err() && cfg.protect_with_captcha {
         auth.has_captcha()?;
     }
@@ -48,6 +48,12 @@ impl<T: io::Write> From<csv::IntoInnerError<csv::Writer<T>>> for Error {
     }
 }
 
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Other(anyhow!(err))
+    }
+}
+
 impl From<io::Error> for Error {
     fn from(err: io::Error) -> Self {
         Self::Other(anyhow!(err))
@@ -76,6 +82,12 @@ impl<'r, 'o: 'r> Responder<'r, 'o> for Error {
                                 | ParameterError::EmailNotConfirmed => {
                                     json_error_response(req, err, Status::Forbidden)
                                 }
+                                ParameterError::ImageType => {
+                                    json_error_response(req, err, Status::UnsupportedMediaType)
+                                }
+                                ParameterError::ImageSize => {
+                                    json_error_response(req, err, Status::PayloadTooLarge)
+                                }
                                 _ => json_error_response(req, err, Status::BadRequest),
                             };
                         }
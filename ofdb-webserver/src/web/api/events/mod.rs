@@ -70,8 +70,12 @@ pub fn post_event_with_token(
     ev: JsonResult<NewEvent>,
     geo_gw: &State<GeoCoding>,
 ) -> Result<String> {
-    let org = auth.organization(&connections.shared()?)?;
+    let (org, member) = auth.organization_with_member(&connections, ApiTokenScope::EventsWrite)?;
     let mut new_event = from_json::try_new_event(ev?.into_inner())?;
+    if member.is_some() {
+        // Members of the organization act with their own account
+        new_event.created_by = member;
+    }
     check_and_set_address_location(&mut new_event, &*geo_gw.0);
     let event = flows::create_event(
         &connections,
@@ -101,10 +105,18 @@ pub fn post_event(mut _db: sqlite::Connections, _e: JsonResult<NewEvent>) -> Htt
 // }
 
 #[get("/events/<id>")]
-pub fn get_event(db: sqlite::Connections, id: String) -> Result<json::Event> {
+pub fn get_event(db: sqlite::Connections, id: String, languages: Languages) -> Result<json::Event> {
     let mut ev = usecases::get_event(&db.shared()?, &id)?;
     ev.created_by = None; // don't show creators email to unregistered users
-    Ok(Json(ev.into()))
+    Ok(Json(translated_event(ev, &languages)))
+}
+
+fn translated_event(mut event: Event, languages: &Languages) -> json::Event {
+    let lang = event.translate(&languages.0);
+    json::Event {
+        lang: lang.map(Into::into),
+        ..event.into()
+    }
 }
 
 #[put("/events/<_id>", format = "application/json", data = "<_e>", rank = 2)]
@@ -124,8 +136,12 @@ pub fn put_event_with_token(
     ev: JsonResult<NewEvent>,
     geo_gw: &State<GeoCoding>,
 ) -> Result<()> {
-    let org = auth.organization(&connections.shared()?)?;
+    let (org, member) = auth.organization_with_member(&connections, ApiTokenScope::EventsWrite)?;
     let mut new_event = from_json::try_new_event(ev?.into_inner())?;
+    if member.is_some() {
+        // Members of the organization act with their own account
+        new_event.created_by = member;
+    }
     check_and_set_address_location(&mut new_event, &*geo_gw.0);
     flows::update_event(
         &connections,
@@ -138,6 +154,120 @@ pub fn put_event_with_token(
     Ok(Json(()))
 }
 
+#[get("/events/<id>/history/<revision>")]
+pub fn get_event_history_revision(
+    db: sqlite::Connections,
+    auth: Auth,
+    id: &str,
+    revision: RevisionValue,
+) -> Result<json::EventHistory> {
+    // The history contains e-mail addresses of registered users
+    // and is only permitted for scouts and admins or organizations!
+    if auth.user_with_min_role(&db.shared()?, Role::Scout).is_err() {
+        auth.organization(&db, ApiTokenScope::HistoryRead)?;
+    }
+    let event_history = db.shared()?.get_event_history(id, Some(revision.into()))?;
+    Ok(Json(event_history.into()))
+}
+
+#[get("/events/<id>/history", rank = 2)]
+pub fn get_event_history(
+    db: sqlite::Connections,
+    auth: Auth,
+    id: &str,
+) -> Result<json::EventHistory> {
+    // The history contains e-mail addresses of registered users
+    // and is only permitted for scouts and admins or organizations!
+    if auth.user_with_min_role(&db.shared()?, Role::Scout).is_err() {
+        auth.organization(&db, ApiTokenScope::HistoryRead)?;
+    }
+    let event_history = db.shared()?.get_event_history(id, None)?;
+    Ok(Json(event_history.into()))
+}
+
+#[post("/events/<id>/history/<revision>/restore")]
+pub fn post_event_history_revision_restore(
+    connections: sqlite::Connections,
+    mut search_engine: tantivy::SearchEngine,
+    auth: Auth,
+    id: &str,
+    revision: RevisionValue,
+) -> Result<json::Event> {
+    let (org, member) = auth.organization_with_member(&connections, ApiTokenScope::EventsWrite)?;
+    let mut event = flows::restore_event(
+        &connections,
+        &mut *search_engine,
+        &org,
+        id,
+        revision.into(),
+        member,
+    )?;
+    event.created_by = None; // don't show creators email
+    Ok(Json(event.into()))
+}
+
+#[get("/events/clearance/count")]
+pub fn count_pending_clearances(db: sqlite::Connections, auth: Auth) -> Result<json::ResultCount> {
+    let org = auth.organization(&db, ApiTokenScope::ClearanceRead)?;
+    let db = db.shared()?;
+    let count = usecases::clearance::event::count_pending_clearances(&db, &org)?;
+    Ok(Json(json::ResultCount { count }))
+}
+
+#[get("/events/clearance?<offset>&<limit>")]
+pub fn list_pending_clearances(
+    db: sqlite::Connections,
+    auth: Auth,
+    offset: Option<u64>,
+    limit: Option<u64>,
+) -> Result<Vec<json::PendingClearanceForEvent>> {
+    let pagination = Pagination { offset, limit };
+    let org = auth.organization(&db, ApiTokenScope::ClearanceRead)?;
+    let db = db.shared()?;
+    let pending_clearances =
+        usecases::clearance::event::list_pending_clearances(&db, &org, &pagination)?;
+    Ok(Json(
+        pending_clearances.into_iter().map(Into::into).collect(),
+    ))
+}
+
+#[post("/events/clearance", data = "<clearances>")]
+pub fn update_pending_clearances(
+    db: sqlite::Connections,
+    auth: Auth,
+    clearances: JsonResult<Vec<json::ClearanceForEvent>>,
+) -> Result<json::ResultCount> {
+    let clearances: Vec<_> = clearances?
+        .into_inner()
+        .into_iter()
+        .map(Into::into)
+        .collect();
+    let (org, member) = auth.organization_with_member(&db, ApiTokenScope::ClearanceWrite)?;
+    let count = usecases::clearance::event::update_pending_clearances(
+        &db.exclusive()?,
+        &org,
+        &clearances,
+        member,
+    )?;
+    Ok(Json(json::ResultCount {
+        count: count as u64,
+    }))
+}
+
+#[get("/events/clearance/log?<offset>&<limit>")]
+pub fn list_clearance_log(
+    db: sqlite::Connections,
+    auth: Auth,
+    offset: Option<u64>,
+    limit: Option<u64>,
+) -> Result<Vec<json::ClearanceLogForEvent>> {
+    let pagination = Pagination { offset, limit };
+    let org = auth.organization(&db, ApiTokenScope::ClearanceRead)?;
+    let db = db.shared()?;
+    let clearance_log = usecases::clearance::event::list_clearance_log(&db, &org, &pagination)?;
+    Ok(Json(clearance_log.into_iter().map(Into::into).collect()))
+}
+
 pub struct EventQueryContext<'r> {
     query: usecases::EventQuery,
     errors: form::Errors<'r>,
@@ -303,6 +433,9 @@ impl<'r> FromForm<'r> for EventQuery {
                     ctx.query.text = Some(value.to_string());
                 }
             }
+            "lang" => {
+                // Handled by the `Languages` request guard
+            }
             name => {
                 ctx.errors
                     .push(Error::from(ErrorKind::Unexpected).with_name(name));
@@ -349,16 +482,16 @@ pub fn get_events_with_token(
     search_engine: tantivy::SearchEngine,
     auth: Auth,
     query: EventQuery,
+    languages: Languages,
 ) -> Result<Vec<json::Event>> {
-    let db = connections.shared()?;
-    let org = match auth.organization(&db) {
+    let org = match auth.organization(&connections, ApiTokenScope::EventsRead) {
         Ok(org) => org,
         Err(AppError::Business(Error::Parameter(ParameterError::Unauthorized))) => {
-            drop(db);
-            return get_events_chronologically(connections, search_engine, query);
+            return get_events_chronologically(connections, search_engine, query, languages);
         }
         Err(e) => return Err(e.into()),
     };
+    let db = connections.shared()?;
     let events = usecases::query_events(&db, &*search_engine, query.into_inner())?;
     // Release the database connection asap
     drop(db);
@@ -374,7 +507,7 @@ pub fn get_events_with_token(
                     .map(|moderated_tag| moderated_tag.label.as_str()),
             )
         })
-        .map(json::Event::from)
+        .map(|e| translated_event(e, &languages))
         .collect();
 
     Ok(Json(events))
@@ -385,6 +518,7 @@ pub fn get_events_chronologically(
     connections: sqlite::Connections,
     search_engine: tantivy::SearchEngine,
     query: EventQuery,
+    languages: Languages,
 ) -> Result<Vec<json::Event>> {
     let query = query.into_inner();
     if query.created_by.is_some() {
@@ -400,7 +534,7 @@ pub fn get_events_chronologically(
     let events: Vec<_> = events
         .into_iter()
         .map(|e| usecases::filter_event(e, moderated_tags.iter().map(String::as_str)))
-        .map(json::Event::from)
+        .map(|e| translated_event(e, &languages))
         .collect();
 
     Ok(Json(events))
@@ -441,7 +575,7 @@ pub fn delete_event(mut _db: sqlite::Connections, _id: &str) -> HttpStatus {
 
 #[delete("/events/<id>")]
 pub fn delete_event_with_token(db: sqlite::Connections, auth: Auth, id: &str) -> StatusResult {
-    let org = auth.organization(&db.shared()?)?;
+    let org = auth.organization(&db, ApiTokenScope::EventsWrite)?;
     usecases::delete_event(&db.exclusive()?, &org.api_token, id)?;
     // TODO: Replace with HttpStatus::NoContent
     Ok(HttpStatus::Ok)
@@ -0,0 +1,104 @@
+use super::*;
+
+fn create_org_with_clearance_tag(db: &sqlite::Connections, token: &str, tag: &str) {
+    db.exclusive()
+        .unwrap()
+        .create_org(Organization {
+            id: token.into(),
+            name: token.into(),
+            moderated_tags: vec![ModeratedTag {
+                label: tag.into(),
+                allow_add: true,
+                allow_remove: true,
+                require_clearance: true,
+            }],
+            api_token: token.into(),
+        })
+        .unwrap();
+}
+
+fn count_pending_clearances(client: &Client, token: &str) -> u64 {
+    let res = client
+        .get("/events/clearance/count")
+        .header(Header::new("Authorization", format!("Bearer {token}")))
+        .dispatch();
+    assert_eq!(res.status(), HttpStatus::Ok);
+    let count: json::ResultCount = serde_json::from_str(&res.into_string().unwrap()).unwrap();
+    count.count
+}
+
+fn get_events_by_tag(client: &Client, tag: &str) -> Vec<json::Event> {
+    let res = client.get(format!("/events?tag={tag}")).dispatch();
+    assert_eq!(res.status(), HttpStatus::Ok);
+    serde_json::from_str(&res.into_string().unwrap()).unwrap()
+}
+
+#[test]
+fn without_api_token() {
+    let (client, _) = setup();
+    let res = client.get("/events/clearance/count").dispatch();
+    assert_eq!(res.status(), HttpStatus::Unauthorized);
+    let res = client.get("/events/clearance").dispatch();
+    assert_eq!(res.status(), HttpStatus::Unauthorized);
+    let res = client
+        .post("/events/clearance")
+        .header(ContentType::JSON)
+        .body("[]")
+        .dispatch();
+    assert_eq!(res.status(), HttpStatus::Unauthorized);
+}
+
+#[test]
+fn clear_newly_created_event() {
+    let (client, db) = setup();
+    create_org_with_clearance_tag(&db, "foo", "org-tag");
+    db.exclusive()
+        .unwrap()
+        .create_org(Organization {
+            id: "bar".into(),
+            name: "bar".into(),
+            moderated_tags: vec![],
+            api_token: "bar".into(),
+        })
+        .unwrap();
+    // Events that are tagged by another organization need to be cleared
+    let res = client
+        .post("/events")
+        .header(ContentType::JSON)
+        .header(Header::new("Authorization", "Bearer bar"))
+        .body(r#"{"title":"x","start":4132508400,"tags":["org-tag"],"created_by":"foo@bar.com"}"#)
+        .dispatch();
+    assert_eq!(res.status(), HttpStatus::Ok);
+    let id: String = serde_json::from_str(&res.into_string().unwrap()).unwrap();
+
+    assert_eq!(count_pending_clearances(&client, "foo"), 1);
+    let res = client
+        .get("/events/clearance?offset=0&limit=10")
+        .header(Header::new("Authorization", "Bearer foo"))
+        .dispatch();
+    assert_eq!(res.status(), HttpStatus::Ok);
+    let pending: Vec<json::PendingClearanceForEvent> =
+        serde_json::from_str(&res.into_string().unwrap()).unwrap();
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].event_id, id);
+    assert_eq!(pending[0].last_cleared_revision, None);
+    // Not yet cleared
+    assert!(get_events_by_tag(&client, "org-tag").is_empty());
+
+    let res = client
+        .post("/events/clearance")
+        .header(ContentType::JSON)
+        .header(Header::new("Authorization", "Bearer foo"))
+        .body(format!(
+            r#"[{{"event_id":"{id}","cleared_revision":null}}]"#
+        ))
+        .dispatch();
+    assert_eq!(res.status(), HttpStatus::Ok);
+    let count: json::ResultCount = serde_json::from_str(&res.into_string().unwrap()).unwrap();
+    assert_eq!(count.count, 1);
+
+    assert_eq!(count_pending_clearances(&client, "foo"), 0);
+    let events = get_events_by_tag(&client, "org-tag");
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].id, id);
+}
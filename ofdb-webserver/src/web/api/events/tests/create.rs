@@ -380,6 +380,39 @@ mod with_api_token {
                     .dispatch();
         assert_eq!(res.status(), HttpStatus::BadRequest);
     }
+
+    #[test]
+    fn with_recurrence_rule() {
+        let (client, db) = setup();
+        db.exclusive()
+            .unwrap()
+            .create_org(Organization {
+                id: "foo".into(),
+                name: "bar".into(),
+                moderated_tags: vec!["org-tag".into()],
+                api_token: "foo".into(),
+            })
+            .unwrap();
+        let res = client
+                    .post("/events")
+                    .header(ContentType::JSON)
+                    .header(Header::new("Authorization", "Bearer foo"))
+                    .body(r#"{"title":"x","start":4132508400,"created_by":"foo@bar.com","rrule":"FREQ=WEEKLY;BYDAY=SA","exdates":[4133113200]}"#)
+                    .dispatch();
+        assert_eq!(res.status(), HttpStatus::Ok);
+        let ev = db.shared().unwrap().all_events_chronologically().unwrap()[0].clone();
+        let recurrence = ev.recurrence.unwrap();
+        assert_eq!(recurrence.rule.to_string(), "FREQ=WEEKLY;BYDAY=SA");
+        assert_eq!(recurrence.exdates[0].as_secs(), 4133113200);
+
+        let res = client
+                    .post("/events")
+                    .header(ContentType::JSON)
+                    .header(Header::new("Authorization", "Bearer foo"))
+                    .body(r#"{"title":"x","start":4132508400,"created_by":"foo@bar.com","rrule":"every saturday"}"#)
+                    .dispatch();
+        assert_eq!(res.status(), HttpStatus::BadRequest);
+    }
 }
 
 #[test]
@@ -65,7 +65,16 @@ fn with_api_token() {
     // Manually delete the implicitly added org tag from the 2nd event!
     let mut e2 = db.shared().unwrap().get_event(id2.as_ref()).unwrap();
     e2.tags.retain(|t| t != "tag");
-    db.exclusive().unwrap().update_event(&e2).unwrap();
+    e2.revision = e2.revision.next();
+    let activity = ActivityLog {
+        activity: Activity::now(None),
+        context: None,
+        comment: None,
+    };
+    db.exclusive()
+        .unwrap()
+        .update_event(&e2, &activity)
+        .unwrap();
     assert_eq!(db.shared().unwrap().count_events().unwrap(), 2);
     // The 1st event has the owned tag and should be deleted.
     let res = client
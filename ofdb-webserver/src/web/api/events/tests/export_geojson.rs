@@ -0,0 +1,104 @@
+use super::*;
+
+#[test]
+fn export_geojson() {
+    let (client, db, mut search_engine, notify) = setup2();
+
+    let users = vec![
+        User {
+            email: "scout@example.com".parse().unwrap(),
+            email_confirmed: true,
+            password: "secret".parse::<Password>().unwrap(),
+            role: Role::Scout,
+        },
+        User {
+            email: "user@example.com".parse().unwrap(),
+            email_confirmed: true,
+            password: "secret".parse::<Password>().unwrap(),
+            role: Role::User,
+        },
+    ];
+    for u in users {
+        db.exclusive().unwrap().create_user(&u).unwrap();
+    }
+
+    db.exclusive()
+        .unwrap()
+        .create_org(Organization {
+            id: "foo".into(),
+            name: "foo_name".into(),
+            moderated_tags: vec!["tag".into()],
+            api_token: "foo".into(),
+        })
+        .unwrap();
+    let e1 = usecases::NewEvent {
+        title: "title1".into(),
+        start: Timestamp::now(),
+        tags: Some(vec!["bla".into()]), // org tag will be added implicitly!
+        created_by: Some("createdby1@example.com".parse().unwrap()),
+        email: Some("email1@example.com".parse().unwrap()),
+        city: Some("Stuttgart".into()),
+        lat: Some(48.5),
+        lng: Some(9.25),
+        ..Default::default()
+    };
+    let id1 = flows::create_event(&db, &mut *search_engine, &notify, Some("foo"), e1)
+        .unwrap()
+        .id;
+    let e2 = usecases::NewEvent {
+        title: "title2".into(),
+        start: Timestamp::now(),
+        created_by: Some("createdby2@example.com".parse().unwrap()),
+        ..Default::default()
+    };
+    let id2 = flows::create_event(&db, &mut *search_engine, &notify, Some("foo"), e2)
+        .unwrap()
+        .id;
+
+    let response = client.get("/export/events.geojson").dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+
+    // Regular users are not allowed to export events
+    let login = client
+        .post("/login")
+        .header(ContentType::JSON)
+        .body(r#"{"email": "user@example.com", "password": "secret"}"#)
+        .dispatch();
+    assert_eq!(login.status(), Status::Ok);
+    let response = client.get("/export/events.geojson").dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+
+    let login = client
+        .post("/login")
+        .header(ContentType::JSON)
+        .body(r#"{"email": "scout@example.com", "password": "secret"}"#)
+        .dispatch();
+    assert_eq!(login.status(), Status::Ok);
+    let response = client.get("/export/events.geojson").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(
+        response.content_type(),
+        Some(ContentType::new("application", "geo+json"))
+    );
+    let collection: serde_json::Value =
+        serde_json::from_str(&response.into_string().unwrap()).unwrap();
+    assert_eq!(collection["type"], "FeatureCollection");
+    let features = collection["features"].as_array().unwrap();
+    assert_eq!(features.len(), 2);
+
+    let f1 = features.iter().find(|f| f["id"] == id1.as_str()).unwrap();
+    assert_eq!(f1["type"], "Feature");
+    assert_eq!(f1["geometry"]["type"], "Point");
+    let coordinates = f1["geometry"]["coordinates"].as_array().unwrap();
+    assert!((coordinates[0].as_f64().unwrap() - 9.25).abs() < 0.001);
+    assert!((coordinates[1].as_f64().unwrap() - 48.5).abs() < 0.001);
+    assert_eq!(f1["properties"]["title"], "title1");
+    assert_eq!(f1["properties"]["city"], "Stuttgart");
+    assert_eq!(f1["properties"]["email"], "email1@example.com");
+    assert_eq!(f1["properties"]["tags"], serde_json::json!(["bla", "tag"]));
+
+    // Events without a location have no geometry
+    let f2 = features.iter().find(|f| f["id"] == id2.as_str()).unwrap();
+    assert!(f2["geometry"].is_null());
+    assert_eq!(f2["properties"]["title"], "title2");
+}
@@ -126,3 +126,48 @@ fn export_ical() {
     assert!(body_str.contains("CATEGORIES:bli\\,tag2\r\n"));
     assert!(body_str.contains(r#"CONTACT:email2@example.com\\\, phone2"#));
 }
+
+#[test]
+fn export_recurring_event_as_ical() {
+    let (client, db, mut search_engine, notify) = setup2();
+    db.exclusive()
+        .unwrap()
+        .create_user(&User {
+            email: "scout@example.com".parse().unwrap(),
+            email_confirmed: true,
+            password: "secret".parse::<Password>().unwrap(),
+            role: Role::Scout,
+        })
+        .unwrap();
+    let start = Timestamp::try_from_secs(4133959200).unwrap();
+    let e = usecases::NewEvent {
+        created_by: Some("createdby@example.com".parse().unwrap()),
+        rrule: Some("FREQ=MONTHLY;BYDAY=-1FR;UNTIL=21011231".into()),
+        exdates: Some(vec![Timestamp::try_from_secs(4138797600).unwrap()]),
+        ..usecases::NewEvent::new("Repair Café".into(), start)
+    };
+    let id = flows::create_event(&db, &mut *search_engine, &notify, None, e)
+        .unwrap()
+        .id;
+
+    let login = client
+        .post("/login")
+        .header(ContentType::JSON)
+        .body(r#"{"email": "scout@example.com", "password": "secret"}"#)
+        .dispatch();
+    assert_eq!(login.status(), Status::Ok);
+    let response = client
+        .get(format!(
+            "/export/events.ical?start_min={}",
+            start.as_secs() + 1
+        ))
+        .dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let body_str = response.into_string().unwrap();
+
+    assert_eq!(1, body_str.matches("BEGIN:VEVENT").count());
+    assert!(body_str.contains(&format!("UID:{id}\r\n")));
+    assert!(body_str.contains("DTSTART:21001231T180000\r\n"));
+    assert!(body_str.contains("RRULE:FREQ=MONTHLY;UNTIL=21011231T235959;BYDAY=-1FR\r\n"));
+    assert!(body_str.contains("EXDATE:21010225T180000\r\n"));
+}
@@ -0,0 +1,125 @@
+use super::*;
+
+fn create_org(db: &sqlite::Connections, token: &str, tag: &str) {
+    db.exclusive()
+        .unwrap()
+        .create_org(Organization {
+            id: token.into(),
+            name: token.into(),
+            moderated_tags: vec![tag.into()],
+            api_token: token.into(),
+        })
+        .unwrap();
+}
+
+fn create_and_update_event(client: &Client, token: &str) -> String {
+    let res = client
+        .post("/events")
+        .header(ContentType::JSON)
+        .header(Header::new("Authorization", format!("Bearer {token}")))
+        .body(r#"{"title":"old","start":4132508400,"created_by":"foo@bar.com"}"#)
+        .dispatch();
+    assert_eq!(res.status(), HttpStatus::Ok);
+    let id: String = serde_json::from_str(&res.into_string().unwrap()).unwrap();
+    let res = client
+        .put(format!("/events/{id}"))
+        .header(ContentType::JSON)
+        .header(Header::new("Authorization", format!("Bearer {token}")))
+        .body(r#"{"title":"new","start":4132508400,"created_by":"changed@bar.com"}"#)
+        .dispatch();
+    assert_eq!(res.status(), HttpStatus::Ok);
+    id
+}
+
+#[test]
+fn without_api_token() {
+    let (client, db) = setup();
+    create_org(&db, "foo", "org-tag");
+    let id = create_and_update_event(&client, "foo");
+    let res = client.get(format!("/events/{id}/history")).dispatch();
+    assert_eq!(res.status(), HttpStatus::Unauthorized);
+    let res = client
+        .post(format!("/events/{id}/history/0/restore"))
+        .dispatch();
+    assert_eq!(res.status(), HttpStatus::Unauthorized);
+}
+
+#[test]
+fn get_all_revisions() {
+    let (client, db) = setup();
+    create_org(&db, "foo", "org-tag");
+    let id = create_and_update_event(&client, "foo");
+    let res = client
+        .get(format!("/events/{id}/history"))
+        .header(Header::new("Authorization", "Bearer foo"))
+        .dispatch();
+    assert_eq!(res.status(), HttpStatus::Ok);
+    let history: json::EventHistory = serde_json::from_str(&res.into_string().unwrap()).unwrap();
+    assert_eq!(history.id, id);
+    assert_eq!(history.revisions.len(), 2);
+    let latest = &history.revisions[0];
+    assert_eq!(latest.rev, 1);
+    assert_eq!(latest.event.title, "new");
+    assert_eq!(latest.act.by.as_deref(), Some("changed@bar.com"));
+    let initial = &history.revisions[1];
+    assert_eq!(initial.rev, 0);
+    assert_eq!(initial.event.title, "old");
+    assert_eq!(initial.event.tags, vec!["org-tag"]);
+    assert_eq!(initial.act.by.as_deref(), Some("foo@bar.com"));
+}
+
+#[test]
+fn get_single_revision() {
+    let (client, db) = setup();
+    create_org(&db, "foo", "org-tag");
+    let id = create_and_update_event(&client, "foo");
+    let res = client
+        .get(format!("/events/{id}/history/0"))
+        .header(Header::new("Authorization", "Bearer foo"))
+        .dispatch();
+    assert_eq!(res.status(), HttpStatus::Ok);
+    let history: json::EventHistory = serde_json::from_str(&res.into_string().unwrap()).unwrap();
+    assert_eq!(history.revisions.len(), 1);
+    assert_eq!(history.revisions[0].event.title, "old");
+    let res = client
+        .get(format!("/events/{id}/history/2"))
+        .header(Header::new("Authorization", "Bearer foo"))
+        .dispatch();
+    assert_eq!(res.status(), HttpStatus::NotFound);
+}
+
+#[test]
+fn restore_revision() {
+    let (client, db) = setup();
+    create_org(&db, "foo", "org-tag");
+    let id = create_and_update_event(&client, "foo");
+    let res = client
+        .post(format!("/events/{id}/history/0/restore"))
+        .header(Header::new("Authorization", "Bearer foo"))
+        .dispatch();
+    assert_eq!(res.status(), HttpStatus::Ok);
+    let event = db.shared().unwrap().get_event(&id).unwrap();
+    assert_eq!(event.title, "old");
+    assert_eq!(event.revision, Revision::from(2));
+    let history = db.shared().unwrap().get_event_history(&id, None).unwrap();
+    assert_eq!(history.revisions.len(), 3);
+    let (_, activity) = &history.revisions[0];
+    assert_eq!(activity.comment.as_deref(), Some("Restored revision 0"));
+    assert_eq!(activity.context.as_deref(), Some("Restored by org foo"));
+    assert!(activity.activity.by.is_none());
+}
+
+#[test]
+fn restore_revision_of_event_owned_by_another_organization() {
+    let (client, db) = setup();
+    create_org(&db, "foo", "org-tag");
+    create_org(&db, "bar", "other-tag");
+    let id = create_and_update_event(&client, "foo");
+    let res = client
+        .post(format!("/events/{id}/history/0/restore"))
+        .header(Header::new("Authorization", "Bearer bar"))
+        .dispatch();
+    assert_eq!(res.status(), HttpStatus::Forbidden);
+    let event = db.shared().unwrap().get_event(&id).unwrap();
+    assert_eq!(event.title, "new");
+}
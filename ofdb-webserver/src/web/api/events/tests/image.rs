@@ -0,0 +1,71 @@
+use super::*;
+
+fn create_org_with_event(
+    db: &sqlite::Connections,
+    search_engine: &mut tantivy::SearchEngine,
+) -> Id {
+    for (id, tag) in [("foo", "org-tag"), ("bar", "other-tag")] {
+        db.exclusive()
+            .unwrap()
+            .create_org(Organization {
+                id: id.into(),
+                name: id.into(),
+                moderated_tags: vec![tag.into()],
+                api_token: id.into(),
+            })
+            .unwrap();
+    }
+    let e = usecases::NewEvent {
+        title: "x".into(),
+        tags: Some(vec!["org-tag".into()]),
+        created_by: Some("foo@bar.com".parse().unwrap()),
+        start: Timestamp::now(),
+        ..Default::default()
+    };
+    flows::create_event(db, &mut **search_engine, &DummyNotifyGW, Some("foo"), e)
+        .unwrap()
+        .id
+}
+
+#[test]
+fn without_api_token() {
+    let (client, db, mut search_engine, _) = setup2();
+    let id = create_org_with_event(&db, &mut search_engine);
+    let res = client
+        .post(format!("/events/{id}/image"))
+        .header(ContentType::PNG)
+        .body(png_image())
+        .dispatch();
+    assert_eq!(res.status(), HttpStatus::Unauthorized);
+}
+
+#[test]
+fn with_api_token_of_another_org() {
+    let (client, db, mut search_engine, _) = setup2();
+    let id = create_org_with_event(&db, &mut search_engine);
+    let res = client
+        .post(format!("/events/{id}/image"))
+        .header(ContentType::PNG)
+        .header(Header::new("Authorization", "Bearer bar"))
+        .body(png_image())
+        .dispatch();
+    assert_eq!(res.status(), HttpStatus::Forbidden);
+    let event = db.shared().unwrap().get_event(id.as_ref()).unwrap();
+    assert!(event.image_url.is_none());
+}
+
+#[test]
+fn with_api_token() {
+    let (client, db, mut search_engine, _) = setup2();
+    let id = create_org_with_event(&db, &mut search_engine);
+    let res = client
+        .post(format!("/events/{id}/image"))
+        .header(ContentType::PNG)
+        .header(Header::new("Authorization", "Bearer foo"))
+        .body(png_image())
+        .dispatch();
+    assert_eq!(res.status(), HttpStatus::Ok);
+    let image: json::UploadedImage = serde_json::from_str(&res.into_string().unwrap()).unwrap();
+    let event = db.shared().unwrap().get_event(id.as_ref()).unwrap();
+    assert_eq!(Some(image.url), event.image_url.map(|url| url.to_string()));
+}
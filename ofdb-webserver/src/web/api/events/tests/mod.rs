@@ -3,9 +3,13 @@ use rocket::http::Header;
 use super::{super::tests::prelude::*, *};
 
 mod archive;
+mod clearance;
 mod create;
 mod delete;
 mod export_csv;
+mod export_geojson;
 mod export_ical;
+mod history;
+mod image;
 mod read;
 mod update;
@@ -40,22 +40,32 @@ fn all() {
     for id in event_ids {
         db.exclusive()
             .unwrap()
-            .create_event(Event {
-                id: id.into(),
-                title: id.into(),
-                description: None,
-                start: Timestamp::now(),
-                end: None,
-                location: None,
-                contact: None,
-                tags: vec![],
-                homepage: None,
-                created_by: None,
-                registration: None,
-                archived: None,
-                image_url: None,
-                image_link_url: None,
-            })
+            .create_event(
+                Event {
+                    id: id.into(),
+                    revision: Revision::initial(),
+                    title: id.into(),
+                    description: None,
+                    start: Timestamp::now(),
+                    end: None,
+                    location: None,
+                    contact: None,
+                    tags: vec![],
+                    homepage: None,
+                    created_by: None,
+                    registration: None,
+                    archived: None,
+                    image_url: None,
+                    image_link_url: None,
+                    recurrence: None,
+                    translations: vec![],
+                },
+                &ActivityLog {
+                    activity: Activity::now(None),
+                    context: None,
+                    comment: None,
+                },
+            )
             .unwrap();
     }
     let req = client.get("/events").header(ContentType::JSON);
@@ -387,3 +397,70 @@ fn filtered_by_bounding_box() {
     assert!(!body_str.contains("\"title\":\"0.3-5\""));
     assert!(body_str.contains("\"title\":\"12-0\""));
 }
+
+#[test]
+fn recurring_events_within_time_window() {
+    let (client, db, mut search_engine, notify) = setup2();
+    let start = Timestamp::now() + Duration::days(1);
+    let e = usecases::NewEvent {
+        title: "weekly".into(),
+        end: Some(start + Duration::hours(2)),
+        created_by: Some("test@example.com".parse().unwrap()),
+        rrule: Some("FREQ=WEEKLY;COUNT=10".into()),
+        exdates: Some(vec![start + Duration::weeks(2)]),
+        ..usecases::NewEvent::new("weekly".into(), start)
+    };
+    let id = flows::create_event(&db, &mut *search_engine, &notify, None, e)
+        .unwrap()
+        .id;
+    let mut e = usecases::NewEvent::new("single".into(), start + Duration::weeks(3));
+    e.created_by = Some("test@example.com".parse().unwrap());
+    flows::create_event(&db, &mut *search_engine, &notify, None, e).unwrap();
+
+    let res = client
+        .get(format!(
+            "/events?start_min={}&start_max={}",
+            (start + Duration::days(1)).as_secs(),
+            (start + Duration::weeks(4)).as_secs()
+        ))
+        .header(ContentType::JSON)
+        .dispatch();
+    assert_eq!(res.status(), HttpStatus::Ok);
+    let events: Vec<json::Event> = serde_json::from_str(&res.into_string().unwrap()).unwrap();
+    let occurrences: Vec<_> = events
+        .iter()
+        .map(|e| (e.title.as_str(), e.start.as_i64()))
+        .collect();
+    assert_eq!(
+        occurrences,
+        vec![
+            ("weekly", (start + Duration::weeks(1)).as_secs()),
+            ("weekly", (start + Duration::weeks(3)).as_secs()),
+            ("single", (start + Duration::weeks(3)).as_secs()),
+            ("weekly", (start + Duration::weeks(4)).as_secs()),
+        ]
+    );
+    assert!(
+        events
+            .iter()
+            .filter(|e| e.title == "weekly")
+            .all(|e| e.id == id.as_str()
+                && e.rrule.as_deref() == Some("FREQ=WEEKLY;COUNT=10")
+                && e.end.as_ref().map(|end| end.as_i64() - e.start.as_i64()) == Some(7200))
+    );
+
+    // The last occurrence
+    let res = client
+        .get(format!(
+            "/events?start_min={}",
+            (start + Duration::weeks(9)).as_secs()
+        ))
+        .header(ContentType::JSON)
+        .dispatch();
+    let events: Vec<json::Event> = serde_json::from_str(&res.into_string().unwrap()).unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(
+        events[0].start.as_i64(),
+        (start + Duration::weeks(9)).as_secs()
+    );
+}
@@ -1,6 +1,7 @@
 use super::{events::EventQuery, tantivy::SearchEngine, *};
 use icalendar::{CalendarDateTime, EventLike};
 use std::iter::once;
+use time::{format_description::FormatItem, macros::format_description};
 
 #[get("/export/events.csv?<query..>")]
 pub fn csv_export(
@@ -9,7 +10,14 @@ pub fn csv_export(
     auth: Auth,
     query: EventQuery,
 ) -> result::Result<(ContentType, String), ApiError> {
-    let events = query_events(connections, search_engine, auth, query)?;
+    let events = query_events(
+        connections,
+        search_engine,
+        auth,
+        query,
+        true,
+        ApiTokenScope::ExportCsv,
+    )?;
 
     let records: Vec<_> = events.map(adapters::csv::EventRecord::from).collect();
 
@@ -31,69 +39,19 @@ pub fn entries_csv_export(
     auth: Auth,
     query: search::SearchQuery,
 ) -> result::Result<(ContentType, String), ApiError> {
-    let db = connections.shared()?;
-
-    let moderated_tags = match auth.organization(&db) {
-        Ok(org) => org.moderated_tags,
-        Err(err) => match err {
-            AppError::Business(BError::Parameter(ParameterError::Unauthorized)) => {
-                vec![]
-            }
-            _ => Err(err)?,
-        },
-    };
-
-    let user = auth.user_with_min_role(&db, Role::Scout)?;
-
-    let (req, limit) = search::parse_search_query(&query)?;
-    let limit = if let Some(limit) = limit {
-        // Limited
-        limit
-    } else {
-        // Unlimited
-        db.count_places()? + 100
-    };
-
-    let entries_categories_and_ratings = {
-        let all_categories: Vec<_> = db.all_categories()?;
-        usecases::search(&db, &*search_engine, req, limit)?
-            .0
-            .into_iter()
-            .filter_map(|indexed_entry| {
-                let IndexedPlace {
-                    ref id,
-                    ref ratings,
-                    ..
-                } = indexed_entry;
-                match db.get_place(id) {
-                    Ok((mut place, _)) => {
-                        let (tags, categories) = Category::split_from_tags(place.tags);
-                        place.tags = tags;
-                        let categories = all_categories
-                            .iter()
-                            .filter(|c1| categories.iter().any(|c2| c1.id == c2.id))
-                            .cloned()
-                            .collect::<Vec<Category>>();
-                        let place = usecases::export_place(
-                            place,
-                            user.role,
-                            moderated_tags
-                                .iter()
-                                .map(|moderated_tag| moderated_tag.label.as_str()),
-                        );
-                        Some((place, categories, ratings.total()))
-                    }
-                    _ => None,
-                }
-            })
-            .collect::<Vec<_>>()
-    };
-    // Release the database connection asap
-    drop(db);
-
-    let records: Vec<_> = entries_categories_and_ratings
+    let places = query_places(
+        connections,
+        search_engine,
+        auth,
+        query,
+        ApiTokenScope::ExportCsv,
+    )?;
+
+    let records: Vec<_> = places
         .into_iter()
-        .map(adapters::csv::CsvRecord::from)
+        .map(|(place, categories, ratings)| {
+            adapters::csv::CsvRecord::from((place, categories, ratings.total()))
+        })
         .collect();
 
     let buf: Vec<u8> = vec![];
@@ -108,6 +66,54 @@ pub fn entries_csv_export(
     Ok((ContentType::CSV, data))
 }
 
+#[get("/export/entries.geojson?<query..>")]
+pub fn entries_geojson_export(
+    connections: sqlite::Connections,
+    search_engine: SearchEngine,
+    auth: Auth,
+    query: search::SearchQuery,
+) -> result::Result<(ContentType, String), ApiError> {
+    let places = query_places(
+        connections,
+        search_engine,
+        auth,
+        query,
+        ApiTokenScope::ExportGeoJson,
+    )?;
+    let collection = places
+        .into_iter()
+        .map(adapters::geojson::Feature::from)
+        .collect::<adapters::geojson::FeatureCollection<_>>();
+    let data = serde_json::to_string(&collection)?;
+    Ok((geojson_content_type(), data))
+}
+
+#[get("/export/events.geojson?<query..>")]
+pub fn events_geojson_export(
+    connections: sqlite::Connections,
+    search_engine: SearchEngine,
+    auth: Auth,
+    query: EventQuery,
+) -> result::Result<(ContentType, String), ApiError> {
+    let events = query_events(
+        connections,
+        search_engine,
+        auth,
+        query,
+        true,
+        ApiTokenScope::ExportGeoJson,
+    )?;
+    let collection = events
+        .map(adapters::geojson::Feature::from)
+        .collect::<adapters::geojson::FeatureCollection<_>>();
+    let data = serde_json::to_string(&collection)?;
+    Ok((geojson_content_type(), data))
+}
+
+fn geojson_content_type() -> ContentType {
+    ContentType::new("application", "geo+json")
+}
+
 // TODO: make this configurable
 const ICAL_CALENDAR_NAME: &str = "OpenFairDB events";
 
@@ -118,7 +124,16 @@ pub fn events_ical_export(
     auth: Auth,
     query: EventQuery,
 ) -> result::Result<(ContentType, String), ApiError> {
-    let events = query_events(connections, search_engine, auth, query)?;
+    // Recurring events are exported with their recurrence rule
+    // instead of a separate VEVENT for each occurrence
+    let events = query_events(
+        connections,
+        search_engine,
+        auth,
+        query,
+        false,
+        ApiTokenScope::ExportIcal,
+    )?;
 
     let mut calendar = events
         .filter_map(event_to_ical)
@@ -141,6 +156,7 @@ fn event_to_ical(event: Event) -> Option<icalendar::Event> {
         tags,
         end,
         location,
+        recurrence,
         ..
     } = event;
     let start_time_as_millis = start.as_millis();
@@ -210,31 +226,119 @@ fn event_to_ical(event: Event) -> Option<icalendar::Event> {
     if let Some(desc) = description {
         event = event.description(&desc).done();
     }
+    if let Some(recurrence) = recurrence {
+        // https://icalendar.org/iCalendar-RFC-5545/3-8-5-3-recurrence-rule.html
+        // The start is a floating date-time and so must be the end of the rule.
+        event = event
+            .append_property(Property::new("RRULE", format!("{:#}", recurrence.rule)).done())
+            .done();
+        if !recurrence.exdates.is_empty() {
+            // https://icalendar.org/iCalendar-RFC-5545/3-8-5-1-exception-date-times.html
+            let exdates = recurrence
+                .exdates
+                .iter()
+                .map(|ts| ts.format(ICAL_FLOATING_DATE_TIME_FORMAT))
+                .collect::<Vec<_>>()
+                .join(",");
+            event = event
+                .append_property(Property::new("EXDATE", exdates).done())
+                .done();
+        }
+    }
     Some(event)
 }
 
+const ICAL_FLOATING_DATE_TIME_FORMAT: &[FormatItem] =
+    format_description!("[year][month][day]T[hour][minute][second]");
+
 fn timestamp_as_calendar_date_time(ts: Timestamp) -> Option<CalendarDateTime> {
     let unix_timestamp_in_millis = ts.as_millis();
     let naive_dt = chrono::NaiveDateTime::from_timestamp_millis(unix_timestamp_in_millis)?;
     Some(CalendarDateTime::Floating(naive_dt))
 }
 
+fn query_places(
+    connections: sqlite::Connections,
+    search_engine: SearchEngine,
+    auth: Auth,
+    query: search::SearchQuery,
+    scope: ApiTokenScope,
+) -> result::Result<Vec<(Place, Vec<Category>, AvgRatings)>, ApiError> {
+    let moderated_tags = match auth.organization(&connections, scope) {
+        Ok(org) => org.moderated_tags,
+        Err(err) => match err {
+            AppError::Business(BError::Parameter(ParameterError::Unauthorized)) => {
+                vec![]
+            }
+            _ => Err(err)?,
+        },
+    };
+
+    let db = connections.shared()?;
+    let user = auth.user_with_min_role(&db, Role::Scout)?;
+
+    let (req, limit) = search::parse_search_query(&query)?;
+    let limit = if let Some(limit) = limit {
+        // Limited
+        limit
+    } else {
+        // Unlimited
+        db.count_places()? + 100
+    };
+
+    let all_categories: Vec<_> = db.all_categories()?;
+    let places = usecases::search(&db, &*search_engine, req, limit)?
+        .0
+        .into_iter()
+        .filter_map(|indexed_entry| {
+            let IndexedPlace {
+                ref id, ratings, ..
+            } = indexed_entry;
+            match db.get_place(id) {
+                Ok((mut place, _)) => {
+                    let (tags, categories) = Category::split_from_tags(place.tags);
+                    place.tags = tags;
+                    let categories = all_categories
+                        .iter()
+                        .filter(|c1| categories.iter().any(|c2| c1.id == c2.id))
+                        .cloned()
+                        .collect::<Vec<Category>>();
+                    let place = usecases::export_place(
+                        place,
+                        user.role,
+                        moderated_tags
+                            .iter()
+                            .map(|moderated_tag| moderated_tag.label.as_str()),
+                    );
+                    Some((place, categories, ratings))
+                }
+                _ => None,
+            }
+        })
+        .collect::<Vec<_>>();
+    // Release the database connection asap
+    drop(db);
+
+    Ok(places)
+}
+
 fn query_events(
     connections: sqlite::Connections,
     search_engine: SearchEngine,
     auth: Auth,
     query: EventQuery,
+    expand_recurring_events: bool,
+    scope: ApiTokenScope,
 ) -> result::Result<impl Iterator<Item = Event>, ApiError> {
     let query = query.into_inner();
-    let db = connections.shared()?;
-
-    let moderated_tags = match auth.organization(&db) {
+    let moderated_tags = match auth.organization(&connections, scope) {
         Ok(org) => org.moderated_tags,
         _ => {
             vec![]
         }
     };
 
+    let db = connections.shared()?;
     let user = auth.user_with_min_role(&db, Role::Scout)?;
 
     let limit = if let Some(limit) = query.limit {
@@ -248,7 +352,11 @@ fn query_events(
         limit: Some(limit),
         ..query
     };
-    let events = usecases::query_events(&db, &*search_engine, query)?;
+    let events = if expand_recurring_events {
+        usecases::query_events(&db, &*search_engine, query)?
+    } else {
+        usecases::query_event_series(&db, &*search_engine, query)?
+    };
     // Release the database connection asap
     drop(db);
 
@@ -0,0 +1,124 @@
+use rocket::data::{Data, ToByteUnit};
+
+use super::*;
+use crate::web::{Cfg, tantivy};
+use ofdb_application::prelude::{ImageUpload, UploadedImage};
+use ofdb_core::gateways::image_storage::ImageStorage;
+
+#[allow(clippy::too_many_arguments)]
+#[post("/entries/<id>/image", data = "<data>")]
+pub async fn post_entry_image(
+    auth: Auth,
+    connections: sqlite::Connections,
+    mut search_engine: tantivy::SearchEngine,
+    notify: &State<Notify>,
+    images: &State<Images>,
+    id: &str,
+    content_type: Option<&ContentType>,
+    data: Data<'_>,
+    cfg: &State<Cfg>,
+) -> Result<json::UploadedImage> {
+    let storage = image_storage(images)?;
+    let org = match auth.organization(&connections, ApiTokenScope::PlacesWrite) {
+        Ok(org) => Some(org),
+        Err(AppError::Business(Error::Parameter(ParameterError::Unauthorized))) => None,
+        Err(err) => return Err(err.into()),
+    };
+    if org.is_none() {
+        // Anonymous uploads are not permitted, not even with a captcha
+        auth.account_email()?;
+    }
+    let content_type = media_type(content_type);
+    let data = read_image(data, cfg).await?;
+    let image = flows::upload_place_image(
+        &connections,
+        &mut *search_engine,
+        &*notify.0,
+        storage,
+        id.into(),
+        ImageUpload {
+            content_type: &content_type,
+            data: &data,
+        },
+        auth.account_email().ok(),
+        org.as_ref(),
+        &cfg.accepted_licenses,
+    )?;
+    Ok(Json(uploaded_image_to_json(image)))
+}
+
+#[allow(clippy::too_many_arguments)]
+#[post("/events/<id>/image", data = "<data>")]
+pub async fn post_event_image(
+    auth: Auth,
+    connections: sqlite::Connections,
+    mut search_engine: tantivy::SearchEngine,
+    notify: &State<Notify>,
+    images: &State<Images>,
+    id: &str,
+    content_type: Option<&ContentType>,
+    data: Data<'_>,
+    cfg: &State<Cfg>,
+) -> Result<json::UploadedImage> {
+    let storage = image_storage(images)?;
+    let (org, member) = auth.organization_with_member(&connections, ApiTokenScope::EventsWrite)?;
+    let content_type = media_type(content_type);
+    let data = read_image(data, cfg).await?;
+    let image = flows::upload_event_image(
+        &connections,
+        &mut *search_engine,
+        &*notify.0,
+        storage,
+        &org,
+        id,
+        ImageUpload {
+            content_type: &content_type,
+            data: &data,
+        },
+        member,
+    )?;
+    Ok(Json(uploaded_image_to_json(image)))
+}
+
+fn image_storage(images: &Images) -> result::Result<&(dyn ImageStorage + Send + Sync), ApiError> {
+    images.0.as_deref().ok_or_else(|| {
+        ApiError::OtherWithStatus(
+            anyhow::anyhow!("Image uploads are not enabled"),
+            Status::NotImplemented,
+        )
+    })
+}
+
+/// The media type without any parameters, e.g. `image/jpeg`.
+fn media_type(content_type: Option<&ContentType>) -> String {
+    content_type
+        .map(|content_type| {
+            format!("{}/{}", content_type.top(), content_type.sub()).to_ascii_lowercase()
+        })
+        .unwrap_or_default()
+}
+
+async fn read_image(data: Data<'_>, cfg: &Cfg) -> result::Result<Vec<u8>, ApiError> {
+    let data = data
+        .open(cfg.images.max_file_size.bytes())
+        .into_bytes()
+        .await?;
+    if !data.is_complete() {
+        return Err(ParameterError::ImageSize.into());
+    }
+    Ok(data.into_inner())
+}
+
+fn uploaded_image_to_json(image: UploadedImage) -> json::UploadedImage {
+    let UploadedImage { url, thumbnails } = image;
+    json::UploadedImage {
+        url: url.to_string(),
+        thumbnails: thumbnails
+            .into_iter()
+            .map(|(size, url)| json::Thumbnail {
+                size,
+                url: url.to_string(),
+            })
+            .collect(),
+    }
+}
@@ -27,15 +27,19 @@ use ofdb_application::{error::AppError, error::BError as Error, prelude as flows
 use ofdb_core::usecases::Error as ParameterError;
 
 pub mod captcha;
+mod changes;
 mod count;
 mod entries;
 mod error;
 pub mod events;
 mod export;
+mod images;
+mod organizations;
 mod places;
 mod ratings;
 mod search;
 mod subscriptions;
+mod tiles;
 mod users;
 mod util;
 
@@ -52,18 +56,25 @@ pub fn routes() -> Vec<Route> {
     routes![
         // ---   search   --- //
         search::get_search,
+        search::get_search_clusters,
         search::post_search_duplicates,
+        // ---   tiles   --- //
+        tiles::get_tile,
         // ---   entries   --- //
         entries::get_entry,
         entries::get_entries_recently_changed,
         entries::get_entries_most_popular_tags,
+        // ---   changes   --- //
+        changes::get_changes,
         entries::post_entry,
         entries::put_entry,
         // ---   places   --- //
         places::count_pending_clearances,
         places::list_pending_clearances,
         places::post_review,
+        places::post_merge,
         places::update_pending_clearances,
+        places::list_clearance_log,
         places::get_place,
         places::get_place_history,
         places::get_place_history_revision,
@@ -77,11 +88,22 @@ pub fn routes() -> Vec<Route> {
         events::get_events_with_token,
         events::put_event,
         events::put_event_with_token,
+        events::get_event_history,
+        events::get_event_history_revision,
+        events::post_event_history_revision_restore,
+        events::count_pending_clearances,
+        events::list_pending_clearances,
+        events::update_pending_clearances,
+        events::list_clearance_log,
         events::post_events_archive,
         events::delete_event,
         events::delete_event_with_token,
+        // ---   images   --- //
+        images::post_entry_image,
+        images::post_event_image,
         // ---   users   --- //
         users::post_login,
+        users::post_login_refresh,
         users::post_logout,
         users::confirm_email_address,
         users::post_request_password_reset,
@@ -89,6 +111,7 @@ pub fn routes() -> Vec<Route> {
         users::post_user,
         users::get_user,
         users::get_current_user,
+        users::get_current_user_organizations,
         users::delete_user,
         // ---   subscriptions   --- //
         subscriptions::subscribe_to_bbox,
@@ -97,7 +120,28 @@ pub fn routes() -> Vec<Route> {
         // ---   export   --- //
         export::csv_export,
         export::entries_csv_export,
+        export::entries_geojson_export,
+        export::events_geojson_export,
         export::events_ical_export,
+        // ---   organizations   --- //
+        organizations::post_organization,
+        organizations::get_organizations,
+        organizations::get_organization,
+        organizations::put_organization,
+        organizations::delete_organization,
+        organizations::post_moderated_tag,
+        organizations::delete_moderated_tag,
+        organizations::get_organization_places,
+        organizations::get_organization_members,
+        organizations::post_organization_member,
+        organizations::delete_organization_member,
+        organizations::post_api_token,
+        organizations::get_api_tokens,
+        organizations::delete_api_token,
+        organizations::post_webhook,
+        organizations::get_webhooks,
+        organizations::delete_webhook,
+        organizations::get_webhook_deliveries,
         // ---   ratings   --- //
         ratings::post_rating,
         ratings::load_rating,
@@ -0,0 +1,281 @@
+use super::*;
+use crate::adapters::json::from_json;
+use rocket::{http::Status as HttpStatus, put};
+
+const MAX_ORGANIZATION_PLACES_LIMIT: usize = 1000;
+
+#[post("/organizations", format = "application/json", data = "<new_org>")]
+pub fn post_organization(
+    db: sqlite::Connections,
+    auth: Auth,
+    new_org: JsonResult<json::NewOrganization>,
+) -> Result<json::CreatedOrganization> {
+    let new_org = from_json::new_organization(new_org?.into_inner());
+    let mut db = db.exclusive()?;
+    auth.user_with_min_role(&db, Role::Admin)?;
+    let org = usecases::create_org(&mut db, new_org)?;
+    let api_token = org.api_token.clone();
+    Ok(Json(json::CreatedOrganization {
+        organization: org.into(),
+        api_token,
+    }))
+}
+
+#[get("/organizations")]
+pub fn get_organizations(db: sqlite::Connections, auth: Auth) -> Result<Vec<json::Organization>> {
+    let db = db.shared()?;
+    auth.user_with_min_role(&db, Role::Admin)?;
+    let orgs = db.all_orgs()?;
+    Ok(Json(orgs.into_iter().map(Into::into).collect()))
+}
+
+#[get("/organizations/<org_id>")]
+pub fn get_organization(
+    db: sqlite::Connections,
+    auth: Auth,
+    org_id: &str,
+) -> Result<json::Organization> {
+    let db = db.shared()?;
+    auth.user_with_min_role(&db, Role::Admin)?;
+    let org = db.get_org_by_id(&org_id.into())?;
+    Ok(Json(org.into()))
+}
+
+#[put(
+    "/organizations/<org_id>",
+    format = "application/json",
+    data = "<update>"
+)]
+pub fn put_organization(
+    db: sqlite::Connections,
+    auth: Auth,
+    org_id: &str,
+    update: JsonResult<json::UpdateOrganization>,
+) -> Result<json::Organization> {
+    let json::UpdateOrganization { name } = update?.into_inner();
+    let mut db = db.exclusive()?;
+    auth.user_with_min_role(&db, Role::Admin)?;
+    let org = usecases::rename_org(&mut db, &org_id.into(), &name)?;
+    Ok(Json(org.into()))
+}
+
+#[delete("/organizations/<org_id>")]
+pub fn delete_organization(db: sqlite::Connections, auth: Auth, org_id: &str) -> StatusResult {
+    let mut db = db.exclusive()?;
+    auth.user_with_min_role(&db, Role::Admin)?;
+    usecases::delete_org(&mut db, &org_id.into())?;
+    Ok(HttpStatus::NoContent)
+}
+
+#[post(
+    "/organizations/<org_id>/moderated-tags",
+    format = "application/json",
+    data = "<moderated_tag>"
+)]
+pub fn post_moderated_tag(
+    db: sqlite::Connections,
+    auth: Auth,
+    org_id: &str,
+    moderated_tag: JsonResult<json::ModeratedTag>,
+) -> Result<json::Organization> {
+    let moderated_tag = moderated_tag?.into_inner().into();
+    let mut db = db.exclusive()?;
+    auth.user_with_min_role(&db, Role::Admin)?;
+    let org = usecases::add_or_update_moderated_tag(&mut db, &org_id.into(), moderated_tag)?;
+    Ok(Json(org.into()))
+}
+
+#[delete("/organizations/<org_id>/moderated-tags/<label>")]
+pub fn delete_moderated_tag(
+    db: sqlite::Connections,
+    auth: Auth,
+    org_id: &str,
+    label: &str,
+) -> Result<json::Organization> {
+    let mut db = db.exclusive()?;
+    auth.user_with_min_role(&db, Role::Admin)?;
+    let org = usecases::remove_moderated_tag(&mut db, &org_id.into(), label)?;
+    Ok(Json(org.into()))
+}
+
+#[get("/organizations/<org_id>/places?<limit>")]
+pub fn get_organization_places(
+    db: sqlite::Connections,
+    search_engine: tantivy::SearchEngine,
+    auth: Auth,
+    org_id: &str,
+    limit: Option<usize>,
+) -> Result<Vec<json::PlaceSearchResult>> {
+    let db = db.shared()?;
+    auth.user_with_min_role(&db, Role::Admin)?;
+    let limit = limit
+        .unwrap_or(MAX_ORGANIZATION_PLACES_LIMIT)
+        .min(MAX_ORGANIZATION_PLACES_LIMIT);
+    let places = usecases::org_places(&db, &*search_engine, &org_id.into(), limit)?;
+    Ok(Json(
+        places
+            .into_iter()
+            .map(json::place_serach_result_from_indexed_place)
+            .collect(),
+    ))
+}
+
+#[get("/organizations/<org_id>/members")]
+pub fn get_organization_members(
+    db: sqlite::Connections,
+    auth: Auth,
+    org_id: &str,
+) -> Result<Vec<json::OrganizationMember>> {
+    let db = db.shared()?;
+    auth.user_with_min_role(&db, Role::Admin)?;
+    let org = db.get_org_by_id(&org_id.into())?;
+    let members = db.list_org_members(&org.id)?;
+    Ok(Json(members.into_iter().map(Into::into).collect()))
+}
+
+#[post(
+    "/organizations/<org_id>/members",
+    format = "application/json",
+    data = "<new_member>"
+)]
+pub fn post_organization_member(
+    db: sqlite::Connections,
+    auth: Auth,
+    org_id: &str,
+    new_member: JsonResult<json::NewOrganizationMember>,
+) -> Result<json::OrganizationMember> {
+    let json::NewOrganizationMember { email, role } = new_member?.into_inner();
+    let db = db.exclusive()?;
+    auth.user_with_min_role(&db, Role::Admin)?;
+    let member =
+        usecases::add_or_update_org_member(&db, &org_id.into(), &email.parse()?, role.into())?;
+    Ok(Json(member.into()))
+}
+
+#[delete("/organizations/<org_id>/members/<email>")]
+pub fn delete_organization_member(
+    db: sqlite::Connections,
+    auth: Auth,
+    org_id: &str,
+    email: &str,
+) -> StatusResult {
+    let db = db.exclusive()?;
+    auth.user_with_min_role(&db, Role::Admin)?;
+    usecases::remove_org_member(&db, &org_id.into(), &email.parse()?)?;
+    Ok(HttpStatus::NoContent)
+}
+
+#[post(
+    "/organizations/<org_id>/api-tokens",
+    format = "application/json",
+    data = "<new_token>"
+)]
+pub fn post_api_token(
+    db: sqlite::Connections,
+    auth: Auth,
+    org_id: &str,
+    new_token: JsonResult<json::NewApiToken>,
+) -> Result<json::CreatedApiToken> {
+    let new_token = from_json::try_new_api_token(new_token?.into_inner())?;
+    let db = db.exclusive()?;
+    auth.user_with_min_role(&db, Role::Admin)?;
+    let (api_token, secret) =
+        usecases::create_api_token(&db, &org_id.into(), new_token, Timestamp::now())?;
+    Ok(Json(json::CreatedApiToken {
+        api_token: api_token.into(),
+        secret,
+    }))
+}
+
+#[get("/organizations/<org_id>/api-tokens")]
+pub fn get_api_tokens(
+    db: sqlite::Connections,
+    auth: Auth,
+    org_id: &str,
+) -> Result<Vec<json::ApiToken>> {
+    let db = db.shared()?;
+    auth.user_with_min_role(&db, Role::Admin)?;
+    let api_tokens = usecases::list_api_tokens(&db, &org_id.into())?;
+    Ok(Json(api_tokens.into_iter().map(Into::into).collect()))
+}
+
+#[delete("/organizations/<org_id>/api-tokens/<id>")]
+pub fn delete_api_token(
+    db: sqlite::Connections,
+    auth: Auth,
+    org_id: &str,
+    id: &str,
+) -> StatusResult {
+    let db = db.exclusive()?;
+    auth.user_with_min_role(&db, Role::Admin)?;
+    usecases::revoke_api_token(&db, &org_id.into(), &id.into(), Timestamp::now())?;
+    Ok(HttpStatus::NoContent)
+}
+
+const MAX_WEBHOOK_DELIVERIES_LIMIT: u64 = 1000;
+
+#[post(
+    "/organizations/<org_id>/webhooks",
+    format = "application/json",
+    data = "<new_webhook>"
+)]
+pub fn post_webhook(
+    db: sqlite::Connections,
+    auth: Auth,
+    org_id: &str,
+    new_webhook: JsonResult<json::NewWebhook>,
+) -> Result<json::CreatedWebhook> {
+    let json::NewWebhook { url } = new_webhook?.into_inner();
+    let db = db.exclusive()?;
+    auth.user_with_min_role(&db, Role::Admin)?;
+    let webhook = usecases::create_webhook(&db, &org_id.into(), &url, Timestamp::now())?;
+    let secret = webhook.secret.clone();
+    Ok(Json(json::CreatedWebhook {
+        webhook: webhook.into(),
+        secret,
+    }))
+}
+
+#[get("/organizations/<org_id>/webhooks")]
+pub fn get_webhooks(
+    db: sqlite::Connections,
+    auth: Auth,
+    org_id: &str,
+) -> Result<Vec<json::Webhook>> {
+    let db = db.shared()?;
+    auth.user_with_min_role(&db, Role::Admin)?;
+    let webhooks = usecases::list_webhooks(&db, &org_id.into())?;
+    Ok(Json(webhooks.into_iter().map(Into::into).collect()))
+}
+
+#[delete("/organizations/<org_id>/webhooks/<id>")]
+pub fn delete_webhook(db: sqlite::Connections, auth: Auth, org_id: &str, id: &str) -> StatusResult {
+    let db = db.exclusive()?;
+    auth.user_with_min_role(&db, Role::Admin)?;
+    usecases::delete_webhook(&db, &org_id.into(), &id.into())?;
+    Ok(HttpStatus::NoContent)
+}
+
+#[get("/organizations/<org_id>/webhooks/<id>/deliveries?<offset>&<limit>")]
+pub fn get_webhook_deliveries(
+    db: sqlite::Connections,
+    auth: Auth,
+    org_id: &str,
+    id: &str,
+    offset: Option<u64>,
+    limit: Option<u64>,
+) -> Result<Vec<json::WebhookDelivery>> {
+    let db = db.shared()?;
+    auth.user_with_min_role(&db, Role::Admin)?;
+    let pagination = Pagination {
+        offset,
+        limit: Some(
+            limit
+                .unwrap_or(MAX_WEBHOOK_DELIVERIES_LIMIT)
+                .min(MAX_WEBHOOK_DELIVERIES_LIMIT),
+        ),
+    };
+    let deliveries =
+        usecases::list_webhook_deliveries(&db, &org_id.into(), &id.into(), &pagination)?;
+    Ok(Json(deliveries.into_iter().map(Into::into).collect()))
+}
@@ -24,17 +24,12 @@ pub fn get_place_history_revision(
     id: String,
     revision: RevisionValue,
 ) -> Result<json::PlaceHistory> {
-    let place_history = {
-        let db = db.shared()?;
-
-        // The history contains e-mail addresses of registered users
-        // is only permitted for scouts and admins or organizations!
-        if auth.user_with_min_role(&db, Role::Scout).is_err() {
-            auth.organization(&db)?;
-        }
-
-        db.get_place_history(&id, Some(revision.into()))?
-    };
+    // The history contains e-mail addresses of registered users
+    // is only permitted for scouts and admins or organizations!
+    if auth.user_with_min_role(&db.shared()?, Role::Scout).is_err() {
+        auth.organization(&db, ApiTokenScope::HistoryRead)?;
+    }
+    let place_history = db.shared()?.get_place_history(&id, Some(revision.into()))?;
     Ok(Json(place_history.into()))
 }
 
@@ -44,25 +39,20 @@ pub fn get_place_history(
     auth: Auth,
     id: String,
 ) -> Result<json::PlaceHistory> {
-    let place_history = {
-        let db = db.shared()?;
-
-        // The history contains e-mail addresses of registered users
-        // is only permitted for scouts and admins or for organizations!
-        if auth.user_with_min_role(&db, Role::Scout).is_err() {
-            auth.organization(&db)?;
-        }
-
-        db.get_place_history(&id, None)?
-    };
+    // The history contains e-mail addresses of registered users
+    // is only permitted for scouts and admins or for organizations!
+    if auth.user_with_min_role(&db.shared()?, Role::Scout).is_err() {
+        auth.organization(&db, ApiTokenScope::HistoryRead)?;
+    }
+    let place_history = db.shared()?.get_place_history(&id, None)?;
     Ok(Json(place_history.into()))
 }
 
 #[get("/places/clearance/count")]
 pub fn count_pending_clearances(db: sqlite::Connections, auth: Auth) -> Result<json::ResultCount> {
+    let org = auth.organization(&db, ApiTokenScope::ClearanceRead)?;
     let db = db.shared()?;
-    let count =
-        usecases::clearance::place::count_pending_clearances(&db, &auth.organization(&db)?)?;
+    let count = usecases::clearance::place::count_pending_clearances(&db, &org)?;
     Ok(Json(json::ResultCount { count }))
 }
 
@@ -74,12 +64,10 @@ pub fn list_pending_clearances(
     limit: Option<u64>,
 ) -> Result<Vec<json::PendingClearanceForPlace>> {
     let pagination = Pagination { offset, limit };
+    let org = auth.organization(&db, ApiTokenScope::ClearanceRead)?;
     let db = db.shared()?;
-    let pending_clearances = usecases::clearance::place::list_pending_clearances(
-        &db,
-        &auth.organization(&db)?,
-        &pagination,
-    )?;
+    let pending_clearances =
+        usecases::clearance::place::list_pending_clearances(&db, &org, &pagination)?;
     Ok(Json(
         pending_clearances.into_iter().map(Into::into).collect(),
     ))
@@ -96,14 +84,32 @@ pub fn update_pending_clearances(
         .into_iter()
         .map(Into::into)
         .collect();
-    let org = auth.organization(&db.shared()?)?;
-    let count =
-        usecases::clearance::place::update_pending_clearances(&db.exclusive()?, &org, &clearances)?;
+    let (org, member) = auth.organization_with_member(&db, ApiTokenScope::ClearanceWrite)?;
+    let count = usecases::clearance::place::update_pending_clearances(
+        &db.exclusive()?,
+        &org,
+        &clearances,
+        member,
+    )?;
     Ok(Json(json::ResultCount {
         count: count as u64,
     }))
 }
 
+#[get("/places/clearance/log?<offset>&<limit>")]
+pub fn list_clearance_log(
+    db: sqlite::Connections,
+    auth: Auth,
+    offset: Option<u64>,
+    limit: Option<u64>,
+) -> Result<Vec<json::ClearanceLogForPlace>> {
+    let pagination = Pagination { offset, limit };
+    let org = auth.organization(&db, ApiTokenScope::ClearanceRead)?;
+    let db = db.shared()?;
+    let clearance_log = usecases::clearance::place::list_clearance_log(&db, &org, &pagination)?;
+    Ok(Json(clearance_log.into_iter().map(Into::into).collect()))
+}
+
 #[post("/places/<ids>/review", data = "<review>")]
 pub fn post_review(
     auth: Auth,
@@ -157,6 +163,33 @@ pub fn post_review(
     Ok(Json(()))
 }
 
+#[post("/places/<id>/merge", data = "<merge>")]
+pub fn post_merge(
+    auth: Auth,
+    db: sqlite::Connections,
+    mut search_engine: tantivy::SearchEngine,
+    id: &str,
+    merge: JsonResult<json::MergePlace>,
+) -> Result<()> {
+    let merged_by = {
+        let db = db.shared()?;
+        // Only scouts and admins are entitled to merge places
+        auth.user_with_min_role(&db, Role::Scout)?.email
+    };
+    let json::MergePlace {
+        merged_place_id,
+        comment,
+    } = merge?.into_inner();
+    let merge = usecases::MergePlaces {
+        place_id: id.into(),
+        merged_place_id: merged_place_id.into(),
+        merged_by,
+        comment,
+    };
+    flows::merge_places(&db, &mut *search_engine, merge)?;
+    Ok(Json(()))
+}
+
 #[post("/places/review-with-token", data = "<review>")]
 pub fn post_review_with_token(
     connections: sqlite::Connections,
@@ -2,32 +2,53 @@ use std::result;
 
 use rocket::serde::json::Json;
 use rocket::{self, FromForm, get, post};
+use time::{PrimitiveDateTime, macros::format_description};
 
 use super::{JsonResult, Result};
 use crate::{
-    adapters::json::{self, from_json},
+    adapters::json::{self, from_json, to_json},
     core::{
         prelude::*,
         usecases,
         util::{self, geo},
     },
-    web::{sqlite, tantivy},
+    web::{guards::Languages, sqlite, tantivy},
 };
 use ofdb_application::error::BError as Error;
 use ofdb_core::usecases::Error as ParameterError;
 
 #[derive(FromForm, Clone)]
 pub struct SearchQuery {
-    bbox: String,
+    bbox: Option<String>,
     categories: Option<String>,
     ids: Option<String>,
     org_tag: Option<String>,
     tags: Option<String>,
     text: Option<String>,
     status: Option<String>,
+    open_at: Option<String>,
+    fuzzy: Option<bool>,
+    center: Option<String>,
+    radius: Option<f64>,
+    sort: Option<String>,
+    facets: Option<String>,
     limit: Option<usize>,
 }
 
+/// Sort the search results by their distance from the `center`.
+const SORT_BY_DISTANCE: &str = "distance";
+
+fn parse_local_date_time(s: &str) -> result::Result<PrimitiveDateTime, ParameterError> {
+    PrimitiveDateTime::parse(
+        s,
+        format_description!("[year]-[month]-[day]T[hour]:[minute]"),
+    )
+    .map_err(|err| {
+        log::debug!("Failed to parse local date time '{}': {}", s, err);
+        ParameterError::InvalidLocalDateTime
+    })
+}
+
 pub fn parse_search_query(
     query: &'_ SearchQuery,
 ) -> result::Result<(usecases::SearchRequest<'_>, Option<usize>), AppError> {
@@ -39,15 +60,49 @@ pub fn parse_search_query(
         tags,
         text,
         status,
+        open_at,
+        fuzzy,
+        center,
+        radius,
+        sort,
+        facets: _,
         limit,
     } = query;
 
-    let bbox = bbox
-        .parse::<geo::MapBbox>()
-        .map_err(|_| ParameterError::Bbox)
+    let center = center
+        .as_deref()
+        .map(str::parse::<geo::MapPoint>)
+        .transpose()
+        .map_err(|_| ParameterError::InvalidPosition)
         .map_err(Error::Parameter)
         .map_err(AppError::Business)?;
 
+    let radius = radius.map(geo::Distance::from_meters);
+
+    let order_by_distance = match sort.as_deref() {
+        None => false,
+        Some(SORT_BY_DISTANCE) => true,
+        Some(sort) => {
+            log::debug!("Unsupported sort order '{sort}'");
+            return Err(AppError::Business(Error::Parameter(ParameterError::Sort)));
+        }
+    };
+
+    let bbox = match (bbox, center, radius) {
+        (Some(bbox), _, _) => bbox
+            .parse::<geo::MapBbox>()
+            .map_err(|_| ParameterError::Bbox)
+            .map_err(Error::Parameter)
+            .map_err(AppError::Business)?,
+        // Search only within the radius
+        (None, Some(center), Some(radius)) if center.is_valid() && radius.is_valid() => {
+            geo::MapBbox::enclosing_circle(center, radius)
+        }
+        _ => {
+            return Err(AppError::Business(Error::Parameter(ParameterError::Bbox)));
+        }
+    };
+
     let ids = ids.as_deref().map(util::split_ids).unwrap_or_default();
 
     let categories = categories
@@ -81,6 +136,13 @@ pub fn parse_search_query(
         })
         .collect();
 
+    let open_at = open_at
+        .as_deref()
+        .map(parse_local_date_time)
+        .transpose()
+        .map_err(Error::Parameter)
+        .map_err(AppError::Business)?;
+
     Ok((
         usecases::SearchRequest {
             bbox,
@@ -90,11 +152,36 @@ pub fn parse_search_query(
             hash_tags,
             text,
             status,
+            open_at,
+            fuzzy: fuzzy.unwrap_or(false),
+            center,
+            radius,
+            order_by_distance,
         },
         *limit,
     ))
 }
 
+const FACET_TAGS: &str = "tags";
+const FACET_CATEGORIES: &str = "categories";
+const MAX_FACET_TAGS: usize = 20;
+
+fn parse_facets(facets: &str) -> result::Result<(bool, bool), ParameterError> {
+    let mut tags = false;
+    let mut categories = false;
+    for facet in util::split_ids(facets) {
+        match facet {
+            FACET_TAGS => tags = true,
+            FACET_CATEGORIES => categories = true,
+            _ => {
+                log::debug!("Unsupported facet '{facet}'");
+                return Err(ParameterError::Facet);
+            }
+        }
+    }
+    Ok((tags, categories))
+}
+
 const DEFAULT_RESULT_LIMIT: usize = 100;
 const MAX_RESULT_LIMIT: usize = 2000;
 
@@ -104,8 +191,15 @@ pub fn get_search(
     connections: sqlite::Connections,
     search_engine: tantivy::SearchEngine,
     query: SearchQuery,
+    languages: Languages,
 ) -> Result<json::SearchResponse> {
     let (req, limit) = parse_search_query(&query)?;
+    let facets = query
+        .facets
+        .as_deref()
+        .map(parse_facets)
+        .transpose()
+        .map_err(Error::Parameter)?;
 
     let limit = if let Some(limit) = limit {
         if limit > MAX_RESULT_LIMIT {
@@ -128,33 +222,93 @@ pub fn get_search(
         DEFAULT_RESULT_LIMIT
     };
 
+    let facets = if let Some((tags, categories)) = facets {
+        let usecases::SearchFacets {
+            tags: tag_counts,
+            categories: category_counts,
+        } = usecases::search_facets(&*search_engine, req.clone(), MAX_FACET_TAGS)?;
+        Some(json::SearchFacets {
+            tags: tags.then(|| tag_counts.into_iter().map(Into::into).collect()),
+            categories: categories.then(|| {
+                category_counts
+                    .into_iter()
+                    .map(|(id, count)| json::CategoryFrequency(id.into(), count))
+                    .collect()
+            }),
+        })
+    } else {
+        None
+    };
+
+    let center = req.center;
     let (visible, invisible) =
         usecases::search(&connections.shared()?, &*search_engine, req, limit)?;
 
-    let visible: Vec<json::PlaceSearchResult> = visible
-        .into_iter()
-        .map(json::place_serach_result_from_indexed_place)
-        .collect();
+    let to_search_result = |mut place: IndexedPlace| {
+        let distance = center
+            .and_then(|center| geo::MapPoint::distance(center, place.pos))
+            .map(geo::Distance::to_meters);
+        let lang = place.translate(&languages.0);
+        json::PlaceSearchResult {
+            distance,
+            lang: lang.map(Into::into),
+            ..json::place_serach_result_from_indexed_place(place)
+        }
+    };
 
-    let invisible: Vec<json::PlaceSearchResult> = invisible
-        .into_iter()
-        .map(json::place_serach_result_from_indexed_place)
-        .collect();
+    let visible: Vec<json::PlaceSearchResult> = visible.into_iter().map(to_search_result).collect();
 
-    Ok(Json(json::SearchResponse { visible, invisible }))
+    let invisible: Vec<json::PlaceSearchResult> =
+        invisible.into_iter().map(to_search_result).collect();
+
+    Ok(Json(json::SearchResponse {
+        visible,
+        invisible,
+        facets,
+    }))
+}
+
+#[get("/search/clusters?<zoom>&<query..>")]
+pub fn get_search_clusters(
+    search_engine: tantivy::SearchEngine,
+    zoom: u8,
+    query: SearchQuery,
+) -> Result<Vec<json::PlaceCluster>> {
+    let (req, _) = parse_search_query(&query)?;
+    let clusters = usecases::search_clusters(&*search_engine, req, zoom)?;
+    Ok(Json(
+        clusters
+            .into_iter()
+            .map(|cluster| json::PlaceCluster {
+                lat: cluster.centroid.lat().to_deg(),
+                lng: cluster.centroid.lng().to_deg(),
+                count: cluster.count,
+            })
+            .collect(),
+    ))
 }
 
 #[post("/search/duplicates", data = "<body>")]
 pub fn post_search_duplicates(
+    connections: sqlite::Connections,
     search_engine: tantivy::SearchEngine,
     body: JsonResult<ofdb_boundary::NewPlace>,
-) -> Result<Vec<json::PlaceSearchResult>> {
+) -> Result<Vec<json::DuplicatePlace>> {
     let new_place = from_json::try_new_place(body?.into_inner())?;
-    let duplicate_places = usecases::search_duplicates(&*search_engine, &new_place)?;
+    let duplicates =
+        usecases::search_duplicates(&connections.shared()?, &*search_engine, &new_place)?;
     Ok(Json(
-        duplicate_places
+        duplicates
             .into_iter()
-            .map(json::place_serach_result_from_indexed_place)
+            .map(|(place, duplicate)| json::DuplicatePlace {
+                place: json::place_serach_result_from_indexed_place(place),
+                score: duplicate.score,
+                reasons: duplicate
+                    .reasons
+                    .into_iter()
+                    .map(to_json::duplicate_type)
+                    .collect(),
+            })
             .collect(),
     ))
 }
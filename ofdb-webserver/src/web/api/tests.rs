@@ -21,6 +21,8 @@ pub mod prelude {
         setup_with_cfg(Cfg {
             accepted_licenses: default_accepted_licenses(),
             protect_with_captcha: false,
+            jwt: Default::default(),
+            images: Default::default(),
         })
     }
 
@@ -55,6 +57,17 @@ pub mod prelude {
         accepted_licenses
     }
 
+    pub fn png_image() -> Vec<u8> {
+        let mut data = Vec::new();
+        image::RgbImage::new(16, 8)
+            .write_to(
+                &mut std::io::Cursor::new(&mut data),
+                image::ImageFormat::Png,
+            )
+            .unwrap();
+        data
+    }
+
     pub fn create_place(client: &Client) -> ofdb_entities::id::Id {
         let body_string = client.post("/entries")
                     .header(ContentType::JSON)
@@ -266,6 +279,91 @@ fn get_multiple_places() {
     );
 }
 
+#[test]
+fn get_entry_and_search_with_translations() {
+    let (client, connections, mut search_engine, notify) = setup2();
+    let place = flows::create_place(
+        &connections,
+        &mut *search_engine,
+        &notify,
+        usecases::NewPlace {
+            title: "Weltladen".into(),
+            description: "Fairer Handel".into(),
+            translations: vec![
+                usecases::TranslationParam {
+                    lang: "en".into(),
+                    title: "World shop".into(),
+                    description: Some("Fair trade".into()),
+                },
+                usecases::TranslationParam {
+                    lang: "fr".into(),
+                    title: "Boutique du monde".into(),
+                    description: None,
+                },
+            ],
+            ..default_new_entry()
+        },
+        None,
+        None,
+        &default_accepted_licenses(),
+    )
+    .unwrap();
+    let get_entry = |query: &str, accept_language: Option<&str>| {
+        let mut req = client.get(format!("/entries/{}{query}", place.id));
+        if let Some(accept_language) = accept_language {
+            req.add_header(rocket::http::Header::new(
+                "Accept-Language",
+                accept_language.to_owned(),
+            ));
+        }
+        let response = req.dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        let mut entries: Vec<json::Entry> =
+            serde_json::from_str(&response.into_string().unwrap()).unwrap();
+        entries.pop().unwrap()
+    };
+
+    // The original text without any preferences
+    let entry = get_entry("", None);
+    assert_eq!(None, entry.lang);
+    assert_eq!("Weltladen", entry.title);
+    assert_eq!(2, entry.translations.len());
+
+    let entry = get_entry("", Some("it, fr-CH;q=0.9, en;q=0.8"));
+    assert_eq!(Some("fr"), entry.lang.as_deref());
+    assert_eq!("Boutique du monde", entry.title);
+    assert_eq!("Fairer Handel", entry.description);
+
+    // The query parameter takes precedence over the header
+    let entry = get_entry("?lang=en-GB", Some("fr"));
+    assert_eq!(Some("en"), entry.lang.as_deref());
+    assert_eq!("World shop", entry.title);
+    assert_eq!("Fair trade", entry.description);
+
+    // An empty list selects the original text
+    let entry = get_entry("?lang=", Some("fr"));
+    assert_eq!(None, entry.lang);
+    assert_eq!("Weltladen", entry.title);
+
+    let response = client
+        .get(format!("/entries/{}?lang=no%20language", place.id))
+        .dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let response = client
+        .get("/search?bbox=-10,-10,10,10&text=boutique&lang=de,en")
+        .dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let search: json::SearchResponse =
+        serde_json::from_str(&response.into_string().unwrap()).unwrap();
+    assert_eq!(1, search.visible.len());
+    assert_eq!(Some("en"), search.visible[0].lang.as_deref());
+    assert_eq!("World shop", search.visible[0].title);
+
+    let response = client.get("/events?lang=en").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+}
+
 fn default_new_entry() -> usecases::NewPlace {
     usecases::NewPlace {
         title: Default::default(),
@@ -289,6 +387,7 @@ fn default_new_entry() -> usecases::NewPlace {
         image_url: None,
         image_link_url: None,
         custom_links: vec![],
+        translations: vec![],
     }
 }
 
@@ -436,6 +535,18 @@ fn search_with_text() {
     assert!(body_str.contains(&format!("\"{}\"", place_ids[1])));
     assert!(!body_str.contains(&format!("\"{}\"", place_ids[2])));
 
+    // Search with typos
+    let req = client.get("/search?bbox=-10,-10,10,10&text=Fooo");
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let body_str = response.into_string().unwrap();
+    assert!(!body_str.contains(&format!("\"{}\"", place_ids[0])));
+    let req = client.get("/search?bbox=-10,-10,10,10&text=Fooo&fuzzy=true");
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let body_str = response.into_string().unwrap();
+    assert!(body_str.contains(&format!("\"{}\"", place_ids[0])));
+
     // Search case insensitive "Foo" and "foo" with bbox
     let req = client.get("/search?bbox=1.8,0.5,3.0,3.0&text=Foo");
     let response = req.dispatch();
@@ -529,6 +640,246 @@ fn search_partial_text() {
     assert!(body_str.contains(&format!("\"{}\"", place_ids[3])));
 }
 
+#[test]
+fn search_within_radius() {
+    let entries = vec![
+        new_entry_with_text("near", "cafe", 48.0, 9.008),
+        new_entry_with_text("far", "cafe", 48.0, 9.038),
+        new_entry_with_text("nearest", "cafe", 48.0, 9.001),
+    ];
+    let (client, connections, mut search_engine, notify) = setup2();
+    let place_ids: Vec<_> = entries
+        .into_iter()
+        .map(|e| {
+            flows::create_place(
+                &connections,
+                &mut *search_engine,
+                &notify,
+                e,
+                None,
+                None,
+                &default_accepted_licenses(),
+            )
+            .unwrap()
+            .id
+            .to_string()
+        })
+        .collect();
+
+    let res = client
+        .get("/search?center=48.0,9.0&radius=2000&sort=distance")
+        .dispatch();
+    assert_eq!(res.status(), Status::Ok);
+    let search_response: json::SearchResponse =
+        serde_json::from_str(&res.into_string().unwrap()).unwrap();
+    assert!(search_response.invisible.is_empty());
+    let ids: Vec<_> = search_response
+        .visible
+        .iter()
+        .map(|p| p.id.as_str())
+        .collect();
+    assert_eq!(vec![place_ids[2].as_str(), place_ids[0].as_str()], ids);
+    let distance = search_response.visible[1].distance.unwrap();
+    assert!((590.0..600.0).contains(&distance));
+
+    // Without center
+    let res = client.get("/search?radius=2000").dispatch();
+    assert_eq!(res.status(), Status::BadRequest);
+
+    // Unsupported sort order
+    let res = client
+        .get("/search?center=48.0,9.0&radius=2000&sort=rating")
+        .dispatch();
+    assert_eq!(res.status(), Status::BadRequest);
+
+    // No distances for plain bbox searches
+    let res = client.get("/search?bbox=47,8,49,10").dispatch();
+    let body = res.into_string().unwrap();
+    assert!(body.contains(&place_ids[1]));
+    assert!(!body.contains("distance"));
+}
+
+#[test]
+fn search_clusters() {
+    let entries = vec![
+        new_entry_with_category(Category::ID_NON_PROFIT, 48.1, 9.1),
+        new_entry_with_category(Category::ID_NON_PROFIT, 48.3, 9.3),
+        new_entry_with_category(Category::ID_COMMERCIAL, 52.5, 13.4),
+    ];
+    let (client, connections, mut search_engine, notify) = setup2();
+    for e in entries {
+        flows::create_place(
+            &connections,
+            &mut *search_engine,
+            &notify,
+            e,
+            None,
+            None,
+            &default_accepted_licenses(),
+        )
+        .unwrap();
+    }
+
+    let res = client
+        .get("/search/clusters?bbox=40,0,60,20&zoom=4")
+        .dispatch();
+    assert_eq!(res.status(), Status::Ok);
+    let clusters: Vec<json::PlaceCluster> =
+        serde_json::from_str(&res.into_string().unwrap()).unwrap();
+    assert_eq!(2, clusters.len());
+    assert_eq!(2, clusters[0].count);
+    assert!((clusters[0].lat - 48.2).abs() < 1e-6);
+    assert!((clusters[0].lng - 9.2).abs() < 1e-6);
+    assert_eq!(1, clusters[1].count);
+
+    // Same filters as for searching
+    let res = client
+        .get(format!(
+            "/search/clusters?bbox=40,0,60,20&zoom=4&categories={}",
+            Category::ID_COMMERCIAL
+        ))
+        .dispatch();
+    let clusters: Vec<json::PlaceCluster> =
+        serde_json::from_str(&res.into_string().unwrap()).unwrap();
+    assert_eq!(1, clusters.len());
+    assert!((clusters[0].lat - 52.5).abs() < 1e-6);
+
+    let res = client
+        .get("/search/clusters?bbox=40,0,60,20&zoom=23")
+        .dispatch();
+    assert_eq!(res.status(), Status::BadRequest);
+
+    let res = client.get("/search/clusters?bbox=40,0,60,20").dispatch();
+    assert_ne!(res.status(), Status::Ok);
+}
+
+#[test]
+fn get_vector_tile() {
+    let (client, connections, mut search_engine, notify) = setup2();
+    let create_place = |search_engine: &mut tantivy::SearchEngine, title: &str| {
+        flows::create_place(
+            &connections,
+            &mut **search_engine,
+            &notify,
+            new_entry_with_text(title, "place", 48.7755, 9.1827),
+            None,
+            None,
+            &default_accepted_licenses(),
+        )
+        .unwrap()
+        .id
+        .to_string()
+    };
+    let place_id = create_place(&mut search_engine, "foo");
+    let mut new_event = usecases::NewEvent::new(
+        "upcoming".into(),
+        Timestamp::now() + time::Duration::days(1),
+    );
+    new_event.lat = Some(48.78);
+    new_event.lng = Some(9.18);
+    let event_id = flows::create_event(&connections, &mut *search_engine, &notify, None, new_event)
+        .unwrap()
+        .id
+        .to_string();
+
+    let tile_url = "/tiles/10/538/352.mvt";
+    let res = client.get(tile_url).dispatch();
+    assert_eq!(res.status(), Status::Ok);
+    assert_eq!(
+        Some(ContentType::new("application", "vnd.mapbox-vector-tile")),
+        res.content_type()
+    );
+    let tile = res.into_bytes().unwrap();
+    let contains = |tile: &[u8], s: &str| tile.windows(s.len()).any(|w| w == s.as_bytes());
+    assert!(contains(&tile, "places"));
+    assert!(contains(&tile, &place_id));
+    assert!(contains(&tile, "events"));
+    assert!(contains(&tile, &event_id));
+
+    // The cached tile is invalidated after indexing another place
+    let other_place_id = create_place(&mut search_engine, "bar");
+    let tile = client.get(tile_url).dispatch().into_bytes().unwrap();
+    assert!(contains(&tile, &place_id));
+    assert!(contains(&tile, &other_place_id));
+
+    // Neighbouring tile
+    let tile = client
+        .get("/tiles/10/539/352.mvt")
+        .dispatch()
+        .into_bytes()
+        .unwrap();
+    assert!(tile.is_empty());
+
+    let res = client.get("/tiles/10/538/1024.mvt").dispatch();
+    assert_eq!(res.status(), Status::NotFound);
+    let res = client.get("/tiles/10/538/352.png").dispatch();
+    assert_eq!(res.status(), Status::NotFound);
+}
+
+#[test]
+fn search_with_facets() {
+    let entries = vec![
+        usecases::NewPlace {
+            tags: vec!["foo".into(), "bar".into()],
+            ..new_entry_with_category(Category::ID_NON_PROFIT, 1.0, 1.0)
+        },
+        usecases::NewPlace {
+            tags: vec!["foo".into()],
+            ..new_entry_with_category(Category::ID_COMMERCIAL, 2.0, 2.0)
+        },
+    ];
+    let (client, connections, mut search_engine, notify) = setup2();
+    for e in entries {
+        flows::create_place(
+            &connections,
+            &mut *search_engine,
+            &notify,
+            e,
+            None,
+            None,
+            &default_accepted_licenses(),
+        )
+        .unwrap();
+    }
+
+    let res = client
+        .get("/search?bbox=0,0,3,3&facets=tags,categories")
+        .dispatch();
+    assert_eq!(res.status(), Status::Ok);
+    let search_response: json::SearchResponse =
+        serde_json::from_str(&res.into_string().unwrap()).unwrap();
+    let facets = search_response.facets.unwrap();
+    let tags: Vec<_> = facets
+        .tags
+        .unwrap()
+        .into_iter()
+        .map(|json::TagFrequency(tag, count)| (tag, count))
+        .collect();
+    assert_eq!(vec![("foo".to_string(), 2), ("bar".to_string(), 1)], tags);
+    let categories: Vec<_> = facets
+        .categories
+        .unwrap()
+        .into_iter()
+        .map(|json::CategoryFrequency(id, count)| (id, count))
+        .collect();
+    assert_eq!(2, categories.len());
+    assert!(categories.contains(&(Category::ID_NON_PROFIT.to_string(), 1)));
+    assert!(categories.contains(&(Category::ID_COMMERCIAL.to_string(), 1)));
+
+    let res = client.get("/search?bbox=0,0,3,3&facets=tags").dispatch();
+    let search_response: json::SearchResponse =
+        serde_json::from_str(&res.into_string().unwrap()).unwrap();
+    let facets = search_response.facets.unwrap();
+    assert!(facets.tags.is_some());
+    assert!(facets.categories.is_none());
+
+    let res = client.get("/search?bbox=0,0,3,3").dispatch();
+    assert!(!res.into_string().unwrap().contains("facets"));
+
+    let res = client.get("/search?bbox=0,0,3,3&facets=ratings").dispatch();
+    assert_eq!(res.status(), Status::BadRequest);
+}
+
 #[test]
 fn search_with_text_terms_inclusive_exclusive() {
     let entries = vec![
@@ -677,6 +1028,77 @@ fn search_with_city() {
     assert!(body_str.contains(&format!("\"{}\"", place_ids[2])));
 }
 
+#[test]
+fn search_with_open_at() {
+    let entries = vec![
+        usecases::NewPlace {
+            opening_hours: Some("Mo-Fr 09:00-18:00".into()),
+            ..default_new_entry()
+        },
+        usecases::NewPlace {
+            opening_hours: Some("Sa,Su 10:00-16:00".into()),
+            ..default_new_entry()
+        },
+        default_new_entry(),
+    ];
+    let (client, connections, mut search_engine, notify) = setup2();
+    let place_ids: Vec<_> = entries
+        .into_iter()
+        .map(|e| {
+            flows::create_place(
+                &connections,
+                &mut *search_engine,
+                &notify,
+                e,
+                None,
+                None,
+                &default_accepted_licenses(),
+            )
+            .unwrap()
+            .id
+            .to_string()
+        })
+        .collect();
+
+    // Friday
+    let req = client.get("/search?bbox=-10,-10,10,10&open_at=2024-05-17T17:45");
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let body_str = response.into_string().unwrap();
+    assert!(body_str.contains(&format!("\"{}\"", place_ids[0])));
+    assert!(!body_str.contains(&format!("\"{}\"", place_ids[1])));
+    assert!(!body_str.contains(&format!("\"{}\"", place_ids[2])));
+
+    // Saturday
+    let req = client.get("/search?bbox=-10,-10,10,10&open_at=2024-05-18T10:00");
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let body_str = response.into_string().unwrap();
+    assert!(!body_str.contains(&format!("\"{}\"", place_ids[0])));
+    assert!(body_str.contains(&format!("\"{}\"", place_ids[1])));
+    assert!(!body_str.contains(&format!("\"{}\"", place_ids[2])));
+
+    let req = client.get("/search?bbox=-10,-10,10,10&open_at=1715960700");
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+}
+
+#[test]
+fn get_entry_with_legacy_opening_hours() {
+    let (client, db) = setup();
+    let mut place = Place::build().id("legacy").license("CC0-1.0").finish();
+    place.opening_hours = Some("Mo-Fr 9-18 Uhr".parse().unwrap());
+    db.exclusive()
+        .unwrap()
+        .create_or_update_place(place)
+        .unwrap();
+    let response = client.get("/entries/legacy").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let entries: Vec<json::Entry> = serde_json::from_str(&response.into_string().unwrap()).unwrap();
+    assert_eq!(entries[0].opening_hours.as_deref(), Some("Mo-Fr 9-18 Uhr"));
+    assert!(entries[0].opening_hours_invalid);
+}
+
 #[test]
 fn search_with_tags() {
     let entries = vec![
@@ -1517,6 +1939,78 @@ fn login_logout_succeeds_jwt() {
     assert_eq!(res.status(), Status::Unauthorized);
 }
 
+#[test]
+#[cfg(feature = "jwt")]
+fn refresh_jwt_login() {
+    let (client, db) = setup();
+    let user = User {
+        email: "foo@bar".parse().unwrap(),
+        email_confirmed: true,
+        password: "secret".parse::<Password>().unwrap(),
+        role: Role::Guest,
+    };
+    db.exclusive().unwrap().create_user(&user).unwrap();
+
+    let res = client
+        .post("/login")
+        .header(ContentType::JSON)
+        .body(r#"{"email": "foo@bar", "password": "secret"}"#)
+        .dispatch();
+    assert_eq!(res.status(), Status::Ok);
+    let jwt_token: ofdb_boundary::JwtToken =
+        serde_json::from_str(&res.into_string().unwrap()).unwrap();
+    let refresh_token = jwt_token.refresh_token.unwrap();
+
+    // An access token can't be used for refreshing
+    let res = client
+        .post("/login/refresh")
+        .header(ContentType::JSON)
+        .body(format!(r#"{{"refresh_token":"{}"}}"#, jwt_token.token))
+        .dispatch();
+    assert_eq!(res.status(), Status::Unauthorized);
+
+    let res = client
+        .post("/login/refresh")
+        .header(ContentType::JSON)
+        .body(format!(r#"{{"refresh_token":"{}"}}"#, refresh_token))
+        .dispatch();
+    assert_eq!(res.status(), Status::Ok);
+    let refreshed: ofdb_boundary::JwtToken =
+        serde_json::from_str(&res.into_string().unwrap()).unwrap();
+    let auth_header =
+        rocket::http::Header::new("Authorization", format!("Bearer {}", refreshed.token));
+    let res = client
+        .get("/users/current")
+        .header(auth_header.clone())
+        .dispatch();
+    assert_eq!(res.status(), Status::Ok);
+
+    // A refresh token can only be used once
+    let res = client
+        .post("/login/refresh")
+        .header(ContentType::JSON)
+        .body(format!(r#"{{"refresh_token":"{}"}}"#, refresh_token))
+        .dispatch();
+    assert_eq!(res.status(), Status::Unauthorized);
+
+    // Logout revokes the whole session
+    let res = client
+        .post("/logout")
+        .header(ContentType::JSON)
+        .header(auth_header)
+        .dispatch();
+    assert_eq!(res.status(), Status::Ok);
+    let res = client
+        .post("/login/refresh")
+        .header(ContentType::JSON)
+        .body(format!(
+            r#"{{"refresh_token":"{}"}}"#,
+            refreshed.refresh_token.unwrap()
+        ))
+        .dispatch();
+    assert_eq!(res.status(), Status::Unauthorized);
+}
+
 #[test]
 #[cfg(feature = "jwt")]
 fn review_place_after_logout_must_fail() {
@@ -1598,8 +2092,68 @@ fn review_place_after_logout_must_fail() {
 }
 
 #[test]
-fn confirm_email_address() {
-    let (client, db) = setup();
+fn merge_duplicate_places() {
+    let (client, db, mut search_engine, notify) = setup2();
+    let user = User {
+        email: "scout@example.com".parse().unwrap(),
+        email_confirmed: true,
+        password: "secret".parse::<Password>().unwrap(),
+        role: Role::Scout,
+    };
+    db.exclusive().unwrap().create_user(&user).unwrap();
+    let [place_id, merged_place_id] = ["original", "duplicate"].map(|title| {
+        flows::create_place(
+            &db,
+            &mut *search_engine,
+            &notify,
+            usecases::NewPlace {
+                title: title.into(),
+                ..default_new_entry()
+            },
+            None,
+            None,
+            &default_accepted_licenses(),
+        )
+        .unwrap()
+        .id
+    });
+    let merge = |client: &Client| {
+        client
+            .post(format!("/places/{place_id}/merge"))
+            .header(ContentType::JSON)
+            .body(format!(
+                r#"{{"merged_place_id":"{merged_place_id}","comment":"duplicate"}}"#
+            ))
+            .dispatch()
+            .status()
+    };
+
+    // Only scouts and admins are entitled to merge places
+    assert_eq!(Status::Unauthorized, merge(&client));
+    let res = client
+        .post("/login")
+        .header(ContentType::JSON)
+        .body(r#"{"email": "scout@example.com", "password": "secret"}"#)
+        .dispatch();
+    assert_eq!(res.status(), Status::Ok);
+    assert_eq!(Status::Ok, merge(&client));
+    // The duplicate has already been archived
+    assert_eq!(Status::BadRequest, merge(&client));
+
+    // The merged place is replaced by the surviving place
+    let res = client
+        .get(format!("/entries/{merged_place_id},{place_id}"))
+        .dispatch();
+    assert_eq!(res.status(), Status::Ok);
+    let entries: Vec<json::Entry> = serde_json::from_str(&res.into_string().unwrap()).unwrap();
+    assert_eq!(1, entries.len());
+    assert_eq!(place_id.as_str(), entries[0].id);
+    assert_eq!("original", entries[0].title);
+}
+
+#[test]
+fn confirm_email_address() {
+    let (client, db) = setup();
     let users = vec![User {
         email: "a@bar.de".parse().unwrap(),
         email_confirmed: false,
@@ -1763,6 +2317,178 @@ fn recently_changed_entries() {
     assert!(!body_since_until_str.contains("\"id\":\"new\""));
 }
 
+#[test]
+fn change_feed() {
+    let (client, db, mut search_engine, notify) = setup2();
+
+    let mut place = Place::build().id("place").finish();
+    place.created.at = Timestamp::try_from_millis(1000).unwrap();
+    db.exclusive()
+        .unwrap()
+        .create_or_update_place(place)
+        .unwrap();
+    db.exclusive()
+        .unwrap()
+        .create_rating(Rating {
+            id: "rating".into(),
+            place_id: "place".into(),
+            created_at: Timestamp::try_from_millis(2000).unwrap(),
+            archived_at: None,
+            title: "rating".into(),
+            value: RatingValue::from(2),
+            context: RatingContext::Diversity,
+            source: None,
+        })
+        .unwrap();
+    // Archive both at the same time
+    let archived = Activity {
+        at: Timestamp::try_from_millis(3000).unwrap(),
+        by: None,
+    };
+    db.exclusive()
+        .unwrap()
+        .archive_ratings(&["rating"], &archived)
+        .unwrap();
+    db.exclusive()
+        .unwrap()
+        .review_places(
+            &["place"],
+            ReviewStatus::Archived,
+            &ActivityLog {
+                activity: archived,
+                context: None,
+                comment: None,
+            },
+        )
+        .unwrap();
+    let new_event = usecases::NewEvent {
+        title: "event".into(),
+        start: Timestamp::now(),
+        created_by: Some("foo@bar.com".parse().unwrap()),
+        ..Default::default()
+    };
+    let event_id = flows::create_event(&db, &mut *search_engine, &notify, None, new_event)
+        .unwrap()
+        .id;
+    db.exclusive()
+        .unwrap()
+        .archive_events(
+            &[event_id.as_str()],
+            Timestamp::try_from_secs(Timestamp::now().as_secs() + 1).unwrap(),
+        )
+        .unwrap();
+
+    let res = client.get("/changes?limit=3").dispatch();
+    assert_eq!(res.status(), Status::Ok);
+    test_json(&res);
+    let changes: json::Changes = serde_json::from_str(&res.into_string().unwrap()).unwrap();
+    let items: Vec<_> = changes
+        .changes
+        .into_iter()
+        .map(|c| (c.kind, c.item))
+        .collect();
+    assert_eq!(
+        items,
+        vec![
+            (
+                json::ChangeKind::Created,
+                json::ChangedItem::Place {
+                    id: "place".into(),
+                    revision: 0,
+                    status: json::ReviewStatus::Created,
+                }
+            ),
+            (
+                json::ChangeKind::Created,
+                json::ChangedItem::Rating {
+                    id: "rating".into(),
+                    place_id: "place".into(),
+                }
+            ),
+            (
+                json::ChangeKind::Archived,
+                json::ChangedItem::Rating {
+                    id: "rating".into(),
+                    place_id: "place".into(),
+                }
+            ),
+        ]
+    );
+
+    let cursor = changes.next_cursor.unwrap();
+    let res = client.get(format!("/changes?cursor={cursor}")).dispatch();
+    assert_eq!(res.status(), Status::Ok);
+    let changes: json::Changes = serde_json::from_str(&res.into_string().unwrap()).unwrap();
+    let items: Vec<_> = changes
+        .changes
+        .into_iter()
+        .map(|c| (c.kind, c.item))
+        .collect();
+    assert_eq!(
+        items,
+        vec![
+            (
+                json::ChangeKind::Archived,
+                json::ChangedItem::Place {
+                    id: "place".into(),
+                    revision: 0,
+                    status: json::ReviewStatus::Archived,
+                }
+            ),
+            (
+                json::ChangeKind::Created,
+                json::ChangedItem::Event {
+                    id: event_id.to_string(),
+                    revision: Some(0),
+                }
+            ),
+            (
+                json::ChangeKind::Archived,
+                json::ChangedItem::Event {
+                    id: event_id.to_string(),
+                    revision: None,
+                }
+            ),
+        ]
+    );
+
+    // Deleted events
+    db.exclusive()
+        .unwrap()
+        .delete_event_with_matching_tags(event_id.as_str(), &[])
+        .unwrap();
+    let cursor = changes.next_cursor.unwrap();
+    let res = client.get(format!("/changes?cursor={cursor}")).dispatch();
+    assert_eq!(res.status(), Status::Ok);
+    let changes: json::Changes = serde_json::from_str(&res.into_string().unwrap()).unwrap();
+    let items: Vec<_> = changes
+        .changes
+        .into_iter()
+        .map(|c| (c.kind, c.item))
+        .collect();
+    assert_eq!(
+        items,
+        vec![(
+            json::ChangeKind::Deleted,
+            json::ChangedItem::Event {
+                id: event_id.to_string(),
+                revision: None,
+            }
+        )]
+    );
+
+    // No more changes
+    let cursor = changes.next_cursor.unwrap();
+    let res = client.get(format!("/changes?cursor={cursor}")).dispatch();
+    assert_eq!(res.status(), Status::Ok);
+    let changes: json::Changes = serde_json::from_str(&res.into_string().unwrap()).unwrap();
+    assert!(changes.changes.is_empty());
+    assert_eq!(changes.next_cursor, Some(cursor));
+
+    let res = client.get("/changes?cursor=invalid").dispatch();
+    assert_eq!(res.status(), Status::BadRequest);
+}
+
 #[test]
 fn count_most_popular_tags_on_empty_db_to_verify_sql() {
     // Check that the requests succeeds on an empty database just
@@ -2074,6 +2800,138 @@ fn entries_export_csv() {
     assert_eq!(response.status(), Status::Unauthorized);
 }
 
+#[test]
+fn entries_export_geojson() {
+    let (client, db, mut search_engine, _) = setup2();
+
+    let users = vec![
+        User {
+            email: "scout@example.com".parse().unwrap(),
+            email_confirmed: true,
+            password: "secret".parse::<Password>().unwrap(),
+            role: Role::Scout,
+        },
+        User {
+            email: "user@example.com".parse().unwrap(),
+            email_confirmed: true,
+            password: "secret".parse::<Password>().unwrap(),
+            role: Role::User,
+        },
+    ];
+    for u in users {
+        db.exclusive().unwrap().create_user(&u).unwrap();
+    }
+
+    let mut entries = vec![
+        Place::build()
+            .id("entry1")
+            .license("license1")
+            .title("title1")
+            .description("desc1")
+            .pos(MapPoint::from_lat_lng_deg(0.1, 0.2))
+            .tags(vec!["bla", Category::TAG_NON_PROFIT])
+            .finish(),
+        Place::build()
+            .id("entry2")
+            .pos(MapPoint::from_lat_lng_deg(2.0, 2.0))
+            .finish(),
+    ];
+    entries[0].created.by = Some("user@example.com".parse().unwrap());
+    entries[0].contact = Some(Contact {
+        name: Some("John Smith".to_string()),
+        email: Some("john.smith@example.com".parse().unwrap()),
+        phone: None,
+    });
+    for e in entries {
+        db.exclusive().unwrap().create_or_update_place(e).unwrap();
+    }
+    db.exclusive()
+        .unwrap()
+        .create_rating(Rating {
+            id: "123".into(),
+            place_id: "entry1".into(),
+            created_at: Timestamp::try_from_secs(123).unwrap(),
+            archived_at: None,
+            title: "rating1".into(),
+            value: RatingValue::from(2),
+            context: RatingContext::Diversity,
+            source: None,
+        })
+        .unwrap();
+    let places = db.shared().unwrap().all_places().unwrap();
+    for (place, status) in places {
+        let ratings = db
+            .shared()
+            .unwrap()
+            .load_ratings_of_place(place.id.as_ref())
+            .unwrap();
+        search_engine
+            .add_or_update_place(&place, status, &place.avg_ratings(&ratings))
+            .unwrap();
+    }
+    search_engine.flush_index().unwrap();
+
+    let response = client
+        .get("/export/entries.geojson?bbox=-1,-1,1,1")
+        .dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+
+    // Regular users are not allowed to export places
+    let response = client
+        .post("/login")
+        .header(ContentType::JSON)
+        .body(r#"{"email": "user@example.com", "password": "secret"}"#)
+        .dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let response = client
+        .get("/export/entries.geojson?bbox=-1,-1,1,1")
+        .dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+
+    let response = client
+        .post("/login")
+        .header(ContentType::JSON)
+        .body(r#"{"email": "scout@example.com", "password": "secret"}"#)
+        .dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let response = client
+        .get("/export/entries.geojson?bbox=-1,-1,1,1")
+        .dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(
+        response.content_type(),
+        Some(ContentType::new("application", "geo+json"))
+    );
+    let collection: serde_json::Value =
+        serde_json::from_str(&response.into_string().unwrap()).unwrap();
+    assert_eq!(collection["type"], "FeatureCollection");
+    let features = collection["features"].as_array().unwrap();
+    assert_eq!(features.len(), 1);
+    let feature = &features[0];
+    assert_eq!(feature["type"], "Feature");
+    assert_eq!(feature["id"], "entry1");
+    assert_eq!(feature["geometry"]["type"], "Point");
+    assert_eq!(
+        feature["geometry"]["coordinates"],
+        serde_json::json!([
+            LngCoord::from_deg(0.2).to_deg(),
+            LatCoord::from_deg(0.1).to_deg()
+        ])
+    );
+    let properties = &feature["properties"];
+    assert_eq!(properties["title"], "title1");
+    assert_eq!(properties["license"], "license1");
+    assert_eq!(properties["tags"], serde_json::json!(["bla"]));
+    assert_eq!(
+        properties["categories"],
+        serde_json::json!([Category::ID_NON_PROFIT])
+    );
+    assert_eq!(properties["email"], "john.smith@example.com");
+    assert_eq!(properties["ratings"]["diversity"], 2.0);
+    // Only admins and owners see who created the place
+    assert!(properties.get("created_by").is_none());
+}
+
 #[test]
 fn search_duplicates() {
     let (client, db) = setup();
@@ -2104,6 +2962,34 @@ fn search_duplicates() {
     assert_eq!(place.id.to_string(), duplicate_places.first().unwrap().id);
 }
 
+#[test]
+fn search_duplicates_with_same_contact_data() {
+    let (client, _) = setup();
+    let res = client.post("/entries")
+                    .header(ContentType::JSON)
+                    .body(r#"{"title":"Weltladen","description":"bla","lat":0.0,"lng":0.0,"categories":["x"],"license":"CC0-1.0","tags":[],"telephone":"+49 711 123456","homepage":"https://www.example.com/"}"#)
+                    .dispatch();
+    assert_eq!(res.status(), Status::Ok);
+    let res = client
+        .post("/search/duplicates")
+        .header(ContentType::JSON)
+        .body(r#"{"title":"Fair Trade Shop","description":"bla","lat":0.0005,"lng":0.0005,"telephone":"0711 / 123456","homepage":"example.com"}"#)
+        .dispatch();
+    assert_eq!(res.status(), Status::Ok);
+    let body_str = res.into_string().unwrap();
+    let duplicates: Vec<ofdb_boundary::DuplicatePlace> = serde_json::from_str(&body_str).unwrap();
+    assert_eq!(1, duplicates.len());
+    assert_eq!("Weltladen", duplicates[0].place.title);
+    assert_eq!(
+        vec![
+            ofdb_boundary::DuplicateType::SamePhone,
+            ofdb_boundary::DuplicateType::SameHomepage
+        ],
+        duplicates[0].reasons
+    );
+    assert!(duplicates[0].score > 0.5);
+}
+
 #[test]
 fn get_version() {
     let (client, _) = setup();
@@ -2121,6 +3007,8 @@ mod with_captcha_protection_enabled {
         let cfg = Cfg {
             protect_with_captcha: true,
             accepted_licenses: default_accepted_licenses(),
+            jwt: Default::default(),
+            images: Default::default(),
         };
         setup_with_cfg(cfg)
     }
@@ -2312,3 +3200,592 @@ fn review_place_with_token_and_invalid_revision() {
     test_json(&res);
     assert_eq!(res.status(), Status::BadRequest);
 }
+
+#[test]
+fn manage_organizations() {
+    let (client, db) = setup();
+    let res = client
+        .post("/entries")
+        .header(ContentType::JSON)
+        .body(r#"{"title":"foo","description":"blablabla","lat":0.0,"lng":0.0,"categories":["x"],"license":"CC0-1.0","tags":["bar"]}"#)
+        .dispatch();
+    assert_eq!(res.status(), Status::Ok);
+    let new_org = r##"{"name":" Foo ","moderated_tags":[{"label":"#Bar","allow_add":true,"allow_remove":false,"require_clearance":true}]}"##;
+
+    // Only admins are allowed to manage organizations
+    let res = client
+        .post("/organizations")
+        .header(ContentType::JSON)
+        .body(new_org)
+        .dispatch();
+    assert_eq!(res.status(), Status::Unauthorized);
+    let res = client.get("/organizations").dispatch();
+    assert_eq!(res.status(), Status::Unauthorized);
+
+    let admin = User {
+        email: "admin@example.com".parse().unwrap(),
+        email_confirmed: true,
+        password: "secret".parse::<Password>().unwrap(),
+        role: Role::Admin,
+    };
+    db.exclusive().unwrap().create_user(&admin).unwrap();
+    let res = client
+        .post("/login")
+        .header(ContentType::JSON)
+        .body(r#"{"email": "admin@example.com", "password": "secret"}"#)
+        .dispatch();
+    assert_eq!(res.status(), Status::Ok);
+
+    let res = client
+        .post("/organizations")
+        .header(ContentType::JSON)
+        .body(r#"{"name":" "}"#)
+        .dispatch();
+    assert_eq!(res.status(), Status::BadRequest);
+
+    let res = client
+        .post("/organizations")
+        .header(ContentType::JSON)
+        .body(new_org)
+        .dispatch();
+    assert_eq!(res.status(), Status::Ok);
+    let created: json::CreatedOrganization =
+        serde_json::from_str(&res.into_string().unwrap()).unwrap();
+    let org_id = created.organization.id;
+    assert_eq!(created.organization.name, "Foo");
+    assert_eq!(created.organization.moderated_tags[0].label, "bar");
+    assert_eq!(
+        org_id,
+        db.shared()
+            .unwrap()
+            .get_org_by_api_token(&created.api_token)
+            .unwrap()
+            .id
+            .to_string()
+    );
+
+    // A tag could only be moderated by a single organization
+    let res = client
+        .post("/organizations")
+        .header(ContentType::JSON)
+        .body(new_org)
+        .dispatch();
+    assert_eq!(res.status(), Status::Forbidden);
+
+    let res = client
+        .put(format!("/organizations/{org_id}"))
+        .header(ContentType::JSON)
+        .body(r#"{"name":"Renamed"}"#)
+        .dispatch();
+    assert_eq!(res.status(), Status::Ok);
+    let res = client
+        .post(format!("/organizations/{org_id}/moderated-tags"))
+        .header(ContentType::JSON)
+        .body(r#"{"label":"baz","allow_add":false,"allow_remove":false,"require_clearance":false}"#)
+        .dispatch();
+    assert_eq!(res.status(), Status::Ok);
+    let res = client
+        .delete(format!("/organizations/{org_id}/moderated-tags/baz"))
+        .dispatch();
+    assert_eq!(res.status(), Status::Ok);
+
+    let res = client.get("/organizations").dispatch();
+    assert_eq!(res.status(), Status::Ok);
+    let orgs: Vec<json::Organization> = serde_json::from_str(&res.into_string().unwrap()).unwrap();
+    assert_eq!(orgs.len(), 1);
+    assert_eq!(orgs[0].name, "Renamed");
+    assert_eq!(
+        orgs[0]
+            .moderated_tags
+            .iter()
+            .map(|tag| tag.label.as_str())
+            .collect::<Vec<_>>(),
+        vec!["bar"]
+    );
+
+    let res = client
+        .get(format!("/organizations/{org_id}/places"))
+        .dispatch();
+    assert_eq!(res.status(), Status::Ok);
+    let places: Vec<json::PlaceSearchResult> =
+        serde_json::from_str(&res.into_string().unwrap()).unwrap();
+    assert_eq!(places.len(), 1);
+    assert_eq!(places[0].title, "foo");
+
+    let res = client.delete(format!("/organizations/{org_id}")).dispatch();
+    assert_eq!(res.status(), Status::NoContent);
+    let res = client.get(format!("/organizations/{org_id}")).dispatch();
+    assert_eq!(res.status(), Status::NotFound);
+    assert!(db.shared().unwrap().all_orgs().unwrap().is_empty());
+}
+
+#[test]
+fn manage_api_tokens_of_organization() {
+    let (client, db) = setup();
+    db.exclusive()
+        .unwrap()
+        .create_org(Organization {
+            id: "foo".into(),
+            name: "foo".into(),
+            moderated_tags: vec![],
+            api_token: "foo".into(),
+        })
+        .unwrap();
+    let new_token = r#"{"name":"import","scopes":["clearance:read"]}"#;
+
+    // Only admins are allowed to manage API tokens
+    let res = client
+        .post("/organizations/foo/api-tokens")
+        .header(ContentType::JSON)
+        .body(new_token)
+        .dispatch();
+    assert_eq!(res.status(), Status::Unauthorized);
+
+    let admin = User {
+        email: "admin@example.com".parse().unwrap(),
+        email_confirmed: true,
+        password: "secret".parse::<Password>().unwrap(),
+        role: Role::Admin,
+    };
+    db.exclusive().unwrap().create_user(&admin).unwrap();
+    let res = client
+        .post("/login")
+        .header(ContentType::JSON)
+        .body(r#"{"email": "admin@example.com", "password": "secret"}"#)
+        .dispatch();
+    assert_eq!(res.status(), Status::Ok);
+
+    let res = client
+        .post("/organizations/foo/api-tokens")
+        .header(ContentType::JSON)
+        .body(r#"{"name":"import","scopes":["unknown:scope"]}"#)
+        .dispatch();
+    assert_eq!(res.status(), Status::BadRequest);
+    let res = client
+        .post("/organizations/bar/api-tokens")
+        .header(ContentType::JSON)
+        .body(new_token)
+        .dispatch();
+    assert_eq!(res.status(), Status::BadRequest);
+
+    let res = client
+        .post("/organizations/foo/api-tokens")
+        .header(ContentType::JSON)
+        .body(new_token)
+        .dispatch();
+    assert_eq!(res.status(), Status::Ok);
+    let created: json::CreatedApiToken = serde_json::from_str(&res.into_string().unwrap()).unwrap();
+    assert_eq!(created.api_token.name, "import");
+    assert_eq!(created.api_token.scopes, vec!["clearance:read"]);
+    assert!(created.api_token.last_used_at.is_none());
+    let bearer = rocket::http::Header::new("Authorization", format!("Bearer {}", created.secret));
+
+    let res = client
+        .get("/places/clearance/count")
+        .header(bearer.clone())
+        .dispatch();
+    assert_eq!(res.status(), Status::Ok);
+
+    // Missing scope
+    let res = client
+        .post("/places/clearance")
+        .header(ContentType::JSON)
+        .header(bearer.clone())
+        .body("[]")
+        .dispatch();
+    assert_eq!(res.status(), Status::Forbidden);
+
+    // Missing scope is not ignored for optional authorization
+    let res = client
+        .post("/entries")
+        .header(ContentType::JSON)
+        .header(bearer.clone())
+        .body(r#"{"title":"foo","description":"bar","lat":0.0,"lng":0.0,"categories":[],"license":"CC0-1.0","tags":[]}"#)
+        .dispatch();
+    assert_eq!(res.status(), Status::Forbidden);
+
+    let res = client.get("/organizations/foo/api-tokens").dispatch();
+    assert_eq!(res.status(), Status::Ok);
+    let api_tokens: Vec<json::ApiToken> =
+        serde_json::from_str(&res.into_string().unwrap()).unwrap();
+    assert_eq!(api_tokens.len(), 1);
+    assert_eq!(api_tokens[0].id, created.api_token.id);
+    assert!(api_tokens[0].last_used_at.is_some());
+    assert!(api_tokens[0].revoked_at.is_none());
+
+    // Repeated usages are not recorded immediately
+    let res = client
+        .get("/places/clearance/count")
+        .header(bearer.clone())
+        .dispatch();
+    assert_eq!(res.status(), Status::Ok);
+    let res = client.get("/organizations/foo/api-tokens").dispatch();
+    let recorded: Vec<json::ApiToken> = serde_json::from_str(&res.into_string().unwrap()).unwrap();
+    assert_eq!(recorded[0].last_used_at, api_tokens[0].last_used_at);
+
+    let res = client
+        .delete(format!(
+            "/organizations/foo/api-tokens/{}",
+            created.api_token.id
+        ))
+        .dispatch();
+    assert_eq!(res.status(), Status::NoContent);
+
+    let res = client
+        .get("/places/clearance/count")
+        .header(bearer)
+        .dispatch();
+    assert_eq!(res.status(), Status::Unauthorized);
+}
+
+#[test]
+fn manage_webhooks_of_organization() {
+    let (client, db) = setup();
+    db.exclusive()
+        .unwrap()
+        .create_org(Organization {
+            id: "foo".into(),
+            name: "foo".into(),
+            moderated_tags: vec![],
+            api_token: "foo".into(),
+        })
+        .unwrap();
+    let new_webhook = r#"{"url":"https://example.org/hook"}"#;
+
+    // Only admins are allowed to manage webhooks
+    let res = client
+        .post("/organizations/foo/webhooks")
+        .header(ContentType::JSON)
+        .body(new_webhook)
+        .dispatch();
+    assert_eq!(res.status(), Status::Unauthorized);
+
+    let admin = User {
+        email: "admin@example.com".parse().unwrap(),
+        email_confirmed: true,
+        password: "secret".parse::<Password>().unwrap(),
+        role: Role::Admin,
+    };
+    db.exclusive().unwrap().create_user(&admin).unwrap();
+    let res = client
+        .post("/login")
+        .header(ContentType::JSON)
+        .body(r#"{"email": "admin@example.com", "password": "secret"}"#)
+        .dispatch();
+    assert_eq!(res.status(), Status::Ok);
+
+    let res = client
+        .post("/organizations/foo/webhooks")
+        .header(ContentType::JSON)
+        .body(r#"{"url":"mailto:foo@example.org"}"#)
+        .dispatch();
+    assert_eq!(res.status(), Status::BadRequest);
+
+    let res = client
+        .post("/organizations/foo/webhooks")
+        .header(ContentType::JSON)
+        .body(new_webhook)
+        .dispatch();
+    assert_eq!(res.status(), Status::Ok);
+    let created: json::CreatedWebhook = serde_json::from_str(&res.into_string().unwrap()).unwrap();
+    assert_eq!(created.webhook.url, "https://example.org/hook");
+    assert!(!created.secret.is_empty());
+
+    let res = client.get("/organizations/foo/webhooks").dispatch();
+    assert_eq!(res.status(), Status::Ok);
+    let body = res.into_string().unwrap();
+    assert!(!body.contains(&created.secret));
+    let webhooks: Vec<json::Webhook> = serde_json::from_str(&body).unwrap();
+    assert_eq!(webhooks, vec![created.webhook.clone()]);
+
+    usecases::enqueue_webhook_deliveries(
+        &db.exclusive().unwrap(),
+        &["foo".into()],
+        WebhookEventType::PlaceAdded,
+        "{}",
+        Timestamp::now(),
+    )
+    .unwrap();
+    let deliveries_url = format!(
+        "/organizations/foo/webhooks/{}/deliveries",
+        created.webhook.id
+    );
+    let res = client.get(&deliveries_url).dispatch();
+    assert_eq!(res.status(), Status::Ok);
+    let deliveries: Vec<json::WebhookDelivery> =
+        serde_json::from_str(&res.into_string().unwrap()).unwrap();
+    assert_eq!(deliveries.len(), 1);
+    assert_eq!(deliveries[0].event_type, "place.added");
+    assert_eq!(deliveries[0].status, json::WebhookDeliveryStatus::Pending);
+    assert_eq!(deliveries[0].attempts, 0);
+
+    // Deliveries of other organizations are not accessible
+    let res = client
+        .get(format!(
+            "/organizations/bar/webhooks/{}/deliveries",
+            created.webhook.id
+        ))
+        .dispatch();
+    assert_eq!(res.status(), Status::BadRequest);
+
+    let res = client
+        .delete(format!(
+            "/organizations/foo/webhooks/{}",
+            created.webhook.id
+        ))
+        .dispatch();
+    assert_eq!(res.status(), Status::NoContent);
+    let res = client.get("/organizations/foo/webhooks").dispatch();
+    let webhooks: Vec<json::Webhook> = serde_json::from_str(&res.into_string().unwrap()).unwrap();
+    assert!(webhooks.is_empty());
+}
+
+#[test]
+fn upload_image_of_place() {
+    let (client, db) = setup();
+    let place_id = create_place(&client);
+    db.exclusive()
+        .unwrap()
+        .create_org(Organization {
+            id: "a".into(),
+            name: "a".into(),
+            moderated_tags: vec![],
+            api_token: "a".into(),
+        })
+        .unwrap();
+
+    let res = client
+        .post(format!("/entries/{place_id}/image"))
+        .header(ContentType::PNG)
+        .body(png_image())
+        .dispatch();
+    assert_eq!(res.status(), Status::Unauthorized);
+
+    let res = client
+        .post(format!("/entries/{place_id}/image"))
+        .header(ContentType::GIF)
+        .header(rocket::http::Header::new("Authorization", "Bearer a"))
+        .body(png_image())
+        .dispatch();
+    assert_eq!(res.status(), Status::UnsupportedMediaType);
+
+    let res = client
+        .post(format!("/entries/{place_id}/image"))
+        .header(ContentType::PNG)
+        .header(rocket::http::Header::new("Authorization", "Bearer a"))
+        .body(png_image())
+        .dispatch();
+    assert_eq!(res.status(), Status::Ok);
+    test_json(&res);
+    let image: json::UploadedImage = serde_json::from_str(&res.into_string().unwrap()).unwrap();
+    assert!(
+        image
+            .url
+            .starts_with(&format!("https://img.example.com/places/{place_id}/"))
+    );
+    assert_eq!(
+        vec![128, 256, 512],
+        image.thumbnails.iter().map(|t| t.size).collect::<Vec<_>>()
+    );
+    let (place, _) = db.shared().unwrap().get_place(place_id.as_str()).unwrap();
+    assert_eq!(Revision::from(1), place.revision);
+    assert_eq!(
+        Some(image.url),
+        place.links.unwrap().image.map(|url| url.to_string())
+    );
+}
+
+#[test]
+fn upload_too_large_image_of_place() {
+    let (client, db) = setup_with_cfg(Cfg {
+        accepted_licenses: default_accepted_licenses(),
+        protect_with_captcha: false,
+        jwt: Default::default(),
+        images: crate::web::ImagesCfg {
+            max_file_size: 10,
+            serve_dir: None,
+        },
+    });
+    let place_id = create_place(&client);
+    db.exclusive()
+        .unwrap()
+        .create_org(Organization {
+            id: "a".into(),
+            name: "a".into(),
+            moderated_tags: vec![],
+            api_token: "a".into(),
+        })
+        .unwrap();
+    let res = client
+        .post(format!("/entries/{place_id}/image"))
+        .header(ContentType::PNG)
+        .header(rocket::http::Header::new("Authorization", "Bearer a"))
+        .body(png_image())
+        .dispatch();
+    assert_eq!(res.status(), Status::PayloadTooLarge);
+}
+
+#[test]
+#[cfg(feature = "jwt")]
+fn organization_members_act_with_their_own_account() {
+    let (client, db) = setup();
+    db.exclusive()
+        .unwrap()
+        .create_org(Organization {
+            id: "foo".into(),
+            name: "foo".into(),
+            moderated_tags: vec![ModeratedTag {
+                label: "bar".into(),
+                allow_add: true,
+                allow_remove: false,
+                require_clearance: true,
+            }],
+            api_token: "foo".into(),
+        })
+        .unwrap();
+    for (email, role) in [
+        ("admin@example.com", Role::Admin),
+        ("member@example.com", Role::User),
+    ] {
+        db.exclusive()
+            .unwrap()
+            .create_user(&User {
+                email: email.parse().unwrap(),
+                email_confirmed: true,
+                password: "secret".parse::<Password>().unwrap(),
+                role,
+            })
+            .unwrap();
+    }
+    let res = client
+        .post("/entries")
+        .header(ContentType::JSON)
+        .body(r#"{"title":"foo","description":"blablabla","lat":0.0,"lng":0.0,"categories":["x"],"license":"CC0-1.0","tags":["bar"]}"#)
+        .dispatch();
+    assert_eq!(res.status(), Status::Ok);
+    let place_id: String = serde_json::from_str(&res.into_string().unwrap()).unwrap();
+
+    // Only admins are allowed to manage members
+    let res = client
+        .post("/login")
+        .header(ContentType::JSON)
+        .body(r#"{"email": "admin@example.com", "password": "secret"}"#)
+        .dispatch();
+    assert_eq!(res.status(), Status::Ok);
+    let res = client
+        .post("/organizations/foo/members")
+        .header(ContentType::JSON)
+        .body(r#"{"email":"unknown@example.com","role":"viewer"}"#)
+        .dispatch();
+    assert_eq!(res.status(), Status::BadRequest);
+    let res = client
+        .post("/organizations/foo/members")
+        .header(ContentType::JSON)
+        .body(r#"{"email":"member@example.com","role":"viewer"}"#)
+        .dispatch();
+    assert_eq!(res.status(), Status::Ok);
+    let res = client.get("/organizations/foo/members").dispatch();
+    assert_eq!(res.status(), Status::Ok);
+    let members: Vec<json::OrganizationMember> =
+        serde_json::from_str(&res.into_string().unwrap()).unwrap();
+    assert_eq!(members.len(), 1);
+    assert_eq!(members[0].email, "member@example.com");
+    assert_eq!(members[0].role, json::OrganizationRole::Viewer);
+    let res = client.post("/logout").header(ContentType::JSON).dispatch();
+    assert_eq!(res.status(), Status::Ok);
+
+    let res = client.get("/places/clearance/count").dispatch();
+    assert_eq!(res.status(), Status::Unauthorized);
+
+    let res = client
+        .post("/login")
+        .header(ContentType::JSON)
+        .body(r#"{"email": "member@example.com", "password": "secret"}"#)
+        .dispatch();
+    assert_eq!(res.status(), Status::Ok);
+    let jwt_token: ofdb_boundary::JwtToken =
+        serde_json::from_str(&res.into_string().unwrap()).unwrap();
+    let bearer = rocket::http::Header::new("Authorization", format!("Bearer {}", jwt_token.token));
+    let res = client.get("/users/current/organizations").dispatch();
+    assert_eq!(res.status(), Status::Ok);
+    let memberships: Vec<json::OrganizationMember> =
+        serde_json::from_str(&res.into_string().unwrap()).unwrap();
+    assert_eq!(memberships.len(), 1);
+    assert_eq!(memberships[0].org_id, "foo");
+    // The session cookie doesn't authorize the organization
+    let res = client.get("/places/clearance/count").dispatch();
+    assert_eq!(res.status(), Status::Unauthorized);
+    let res = client
+        .get("/places/clearance/count")
+        .header(bearer.clone())
+        .dispatch();
+    assert_eq!(res.status(), Status::Ok);
+    assert_eq!(res.into_string().unwrap(), r#"{"count":1}"#);
+
+    // Viewers are not allowed to clear places
+    let clearances = format!(r#"[{{"place_id":"{place_id}"}}]"#);
+    let res = client
+        .post("/places/clearance")
+        .header(ContentType::JSON)
+        .header(bearer.clone())
+        .body(&clearances)
+        .dispatch();
+    assert_eq!(res.status(), Status::Forbidden);
+    let res = client
+        .post("/organizations/foo/members")
+        .header(ContentType::JSON)
+        .body(r#"{"email":"member@example.com","role":"clearer"}"#)
+        .dispatch();
+    assert_eq!(res.status(), Status::Unauthorized);
+
+    usecases::add_or_update_org_member(
+        &db.exclusive().unwrap(),
+        &"foo".into(),
+        &"member@example.com".parse().unwrap(),
+        OrganizationRole::Clearer,
+    )
+    .unwrap();
+    let res = client
+        .post("/places/clearance")
+        .header(ContentType::JSON)
+        .header(bearer.clone())
+        .body(&clearances)
+        .dispatch();
+    assert_eq!(res.status(), Status::Ok);
+    assert_eq!(res.into_string().unwrap(), r#"{"count":1}"#);
+
+    // The clearance is recorded together with the member
+    let res = client
+        .get("/places/clearance/log")
+        .header(bearer.clone())
+        .dispatch();
+    assert_eq!(res.status(), Status::Ok);
+    let clearance_log: Vec<json::ClearanceLogForPlace> =
+        serde_json::from_str(&res.into_string().unwrap()).unwrap();
+    assert_eq!(clearance_log.len(), 1);
+    assert_eq!(clearance_log[0].place_id, place_id);
+    assert_eq!(
+        clearance_log[0].created_by.as_deref(),
+        Some("member@example.com")
+    );
+
+    // Events are created by the member only if authorized by the membership
+    for (authorization, created_by) in [
+        (bearer, "member@example.com"),
+        (
+            rocket::http::Header::new("Authorization", "Bearer foo"),
+            "creator@example.com",
+        ),
+    ] {
+        let res = client
+            .post("/events")
+            .header(ContentType::JSON)
+            .header(authorization)
+            .body(r#"{"title":"x","start":4132508400,"created_by":"creator@example.com"}"#)
+            .dispatch();
+        assert_eq!(res.status(), Status::Ok);
+        let event_id: String = serde_json::from_str(&res.into_string().unwrap()).unwrap();
+        let event = db.shared().unwrap().get_event(&event_id).unwrap();
+        assert_eq!(event.created_by.unwrap().as_str(), created_by);
+    }
+}
@@ -0,0 +1,120 @@
+use std::collections::HashSet;
+
+use anyhow::anyhow;
+
+use super::*;
+use crate::{
+    adapters::mvt::{self, TileId},
+    web::tile_cache::TileCache,
+};
+
+const MAX_PLACES_PER_TILE: usize = 4096;
+const MAX_EVENTS_PER_TILE: usize = 1024;
+
+const LAYER_PLACES: &str = "places";
+const LAYER_EVENTS: &str = "events";
+
+fn mvt_content_type() -> ContentType {
+    ContentType::new("application", "vnd.mapbox-vector-tile")
+}
+
+#[get("/tiles/<z>/<x>/<y>")]
+pub fn get_tile(
+    connections: sqlite::Connections,
+    search_engine: tantivy::SearchEngine,
+    tile_cache: &State<TileCache>,
+    z: u8,
+    x: u32,
+    y: &str,
+) -> result::Result<(ContentType, Vec<u8>), ApiError> {
+    let tile_id = y
+        .strip_suffix(".mvt")
+        .and_then(|y| y.parse().ok())
+        .and_then(|y| TileId::try_new(z, x, y))
+        .ok_or_else(|| {
+            ApiError::OtherWithStatus(anyhow!("Invalid tile {z}/{x}/{y}"), Status::NotFound)
+        })?;
+
+    // Read the revision before querying the index to
+    // prevent caching outdated tiles.
+    let revision = search_engine.revision();
+    if let Some(data) = tile_cache.get(&tile_id, revision) {
+        return Ok((mvt_content_type(), data));
+    }
+
+    let db = connections.shared()?;
+    let bbox = tile_id.bbox();
+
+    let req = usecases::SearchRequest {
+        bbox,
+        ids: vec![],
+        categories: vec![],
+        org_tag: None,
+        hash_tags: vec![],
+        text: None,
+        status: vec![],
+        open_at: None,
+        fuzzy: false,
+        center: None,
+        radius: None,
+        order_by_distance: false,
+    };
+    // Invisible places outside of the tile are ignored
+    let (places, _) = usecases::search(&db, &*search_engine, req, MAX_PLACES_PER_TILE)?;
+    let mut places_layer = mvt::Layer::new(LAYER_PLACES);
+    for place in places {
+        let IndexedPlace {
+            id,
+            pos,
+            title,
+            tags,
+            ratings,
+            ..
+        } = place;
+        let (tags, categories) = Category::split_from_tags(tags);
+        let categories: Vec<_> = categories.into_iter().map(|c| c.id.to_string()).collect();
+        places_layer.add_point(
+            tile_id.project(pos),
+            [
+                ("id", id.into()),
+                ("title", title.into()),
+                ("categories", categories.join(",").into()),
+                ("tags", tags.join(",").into()),
+                ("rating", f64::from(ratings.total()).into()),
+            ],
+        );
+    }
+
+    let query = usecases::EventQuery {
+        bbox: Some(bbox),
+        start_min: Some(Timestamp::now()),
+        limit: Some(MAX_EVENTS_PER_TILE),
+        ..Default::default()
+    };
+    let events = usecases::query_events(&db, &*search_engine, query)?;
+    let mut events_layer = mvt::Layer::new(LAYER_EVENTS);
+    let mut event_ids = HashSet::new();
+    // Only the next occurrence of recurring events
+    for event in events {
+        let Some(pos) = event.location.as_ref().map(|l| l.pos) else {
+            continue;
+        };
+        let id = event.id.to_string();
+        if !bbox.contains_point(pos) || !event_ids.insert(id.clone()) {
+            continue;
+        }
+        events_layer.add_point(
+            tile_id.project(pos),
+            [
+                ("id", id.into()),
+                ("title", event.title.into()),
+                ("start", event.start.as_secs().into()),
+                ("tags", event.tags.join(",").into()),
+            ],
+        );
+    }
+
+    let data = mvt::encode_tile(&[places_layer, events_layer]);
+    tile_cache.insert(tile_id, revision, data.clone());
+    Ok((mvt_content_type(), data))
+}
@@ -24,8 +24,14 @@ pub fn post_login(
 
     let mut response = None;
     if cfg!(feature = "jwt") {
-        let token = jwt_state.generate_token(&login.email)?;
-        response = Some(ofdb_boundary::JwtToken { token });
+        let jwt::Tokens {
+            token,
+            refresh_token,
+        } = jwt_state.generate_tokens(&login.email)?;
+        response = Some(ofdb_boundary::JwtToken {
+            token,
+            refresh_token: Some(refresh_token),
+        });
     }
     if cfg!(feature = "cookies") {
         cookies.add_private(
@@ -35,19 +41,52 @@ pub fn post_login(
     Ok(Json(response))
 }
 
+#[post(
+    "/login/refresh",
+    format = "application/json",
+    data = "<refresh_token>"
+)]
+pub fn post_login_refresh(
+    db: sqlite::Connections,
+    refresh_token: JsonResult<ofdb_boundary::RefreshToken>,
+    jwt_state: &State<jwt::JwtState>,
+) -> Result<ofdb_boundary::JwtToken> {
+    if !cfg!(feature = "jwt") {
+        return Err(ParameterError::Unauthorized.into());
+    }
+    let ofdb_boundary::RefreshToken { refresh_token } = refresh_token?.into_inner();
+    let jwt::Tokens {
+        token,
+        refresh_token,
+    } = jwt_state
+        .refresh_tokens(&db.exclusive()?, &refresh_token)
+        .map_err(|err| {
+            log::debug!("Failed to refresh tokens: {err}");
+            ParameterError::Unauthorized
+        })?;
+    Ok(Json(ofdb_boundary::JwtToken {
+        token,
+        refresh_token: Some(refresh_token),
+    }))
+}
+
 #[post("/logout", format = "application/json")]
 pub fn post_logout(
+    db: sqlite::Connections,
     auth: Auth,
     cookies: &CookieJar<'_>,
     jwt_state: &State<jwt::JwtState>,
-) -> Json<()> {
+) -> Result<()> {
     cookies.remove_private(Cookie::build(COOKIE_EMAIL_KEY));
     if cfg!(feature = "jwt") {
+        let db = db.exclusive()?;
         for bearer in auth.bearer_tokens() {
-            jwt_state.blacklist_token(bearer.to_owned());
+            if let Err(err) = jwt_state.revoke_session(&db, bearer) {
+                log::debug!("Failed to revoke session: {err}");
+            }
         }
     }
-    Json(())
+    Ok(Json(()))
 }
 
 #[post("/users", format = "application/json", data = "<new_user>")]
@@ -120,6 +159,16 @@ pub fn get_current_user(db: sqlite::Connections, account: Account) -> Result<jso
     Ok(Json(user.into()))
 }
 
+/// The organizations on whose behalf the current user acts.
+#[get("/users/current/organizations", format = "application/json")]
+pub fn get_current_user_organizations(
+    db: sqlite::Connections,
+    account: Account,
+) -> Result<Vec<json::OrganizationMember>> {
+    let memberships = db.shared()?.list_org_memberships_of_user(account.email())?;
+    Ok(Json(memberships.into_iter().map(Into::into).collect()))
+}
+
 #[get("/users/<email>", format = "application/json", rank = 2)]
 pub fn get_user(db: sqlite::Connections, account: Account, email: String) -> Result<json::User> {
     let user = usecases::get_user(&db.shared()?, account.email(), &email.parse()?)?;
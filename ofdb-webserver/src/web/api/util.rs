@@ -22,8 +22,9 @@ pub fn get_duplicates(
     if ids.is_empty() {
         return Ok(Json(vec![]));
     }
-    let places = connections.shared()?.get_places(&ids)?;
-    let results = usecases::find_duplicates(&*search_engine, &places)?;
+    let db = connections.shared()?;
+    let places = db.get_places(&ids)?;
+    let results = usecases::find_duplicates(&db, &*search_engine, &places)?;
     Ok(Json(
         results
             .into_iter()
@@ -31,7 +32,7 @@ pub fn get_duplicates(
                 (
                     id1.to_string(),
                     id2.to_string(),
-                    to_json::duplicate_type(dup),
+                    to_json::duplicate_type(dup.main_reason()),
                 )
             })
             .collect(),
@@ -0,0 +1,130 @@
+use maud::Markup;
+use rocket::{
+    self, FromForm,
+    form::Form,
+    get, post,
+    request::FlashMessage,
+    response::{Flash, Redirect},
+    uri,
+};
+
+use super::{Result, view};
+use crate::{
+    core::{prelude::*, usecases},
+    web::{guards::*, sqlite::Connections, tantivy::SearchEngine},
+};
+use ofdb_application::prelude::merge_places;
+
+const DUPLICATES_PER_PAGE: u64 = 20;
+
+type ActionResult = std::result::Result<Redirect, Flash<Redirect>>;
+
+#[get("/dashboard/duplicates?<offset>")]
+pub fn get_duplicates(
+    db: Connections,
+    account: Account,
+    flash: Option<FlashMessage>,
+    offset: Option<u64>,
+) -> Result<Markup> {
+    let db = db.shared()?;
+    let scout = usecases::authorize_user_by_email(&db, account.email(), Role::Scout)?;
+    let offset = offset.unwrap_or(0);
+    let total_count = db.count_open_duplicate_candidates()?;
+    let candidates = db
+        .list_open_duplicate_candidates(&Pagination {
+            offset: Some(offset),
+            limit: Some(DUPLICATES_PER_PAGE),
+        })?
+        .into_iter()
+        .map(|candidate| {
+            let title = |id: &Id| db.get_place(id.as_str()).ok().map(|(place, _)| place.title);
+            view::DuplicateCandidatePresenter {
+                place_title: title(&candidate.place_id),
+                duplicate_place_title: title(&candidate.duplicate_place_id),
+                candidate,
+            }
+        })
+        .collect::<Vec<_>>();
+    Ok(view::duplicate_candidates(
+        view::DuplicateCandidatesPresenter {
+            email: &scout.email,
+            flash,
+            candidates: &candidates,
+            offset,
+            limit: DUPLICATES_PER_PAGE,
+            total_count: total_count as u64,
+        },
+    ))
+}
+
+#[derive(FromForm)]
+pub struct MergeDuplicate<'r> {
+    place_id: &'r str,
+    merged_place_id: &'r str,
+}
+
+#[allow(clippy::result_large_err)]
+#[post("/dashboard/duplicates/actions/merge", data = "<data>")]
+pub fn post_merge_duplicate(
+    db: Connections,
+    mut search_engine: SearchEngine,
+    account: Account,
+    data: Form<MergeDuplicate>,
+) -> ActionResult {
+    let MergeDuplicate {
+        place_id,
+        merged_place_id,
+    } = data.into_inner();
+    let result = authorize_scout(&db, account.email()).and_then(|email| {
+        let merge = usecases::MergePlaces {
+            place_id: place_id.into(),
+            merged_place_id: merged_place_id.into(),
+            merged_by: email,
+            comment: None,
+        };
+        Ok(merge_places(&db, &mut *search_engine, merge)?)
+    });
+    redirect_to_duplicates(result, "Failed to merge the places")
+}
+
+#[derive(FromForm)]
+pub struct DismissDuplicate<'r> {
+    place_id: &'r str,
+    duplicate_place_id: &'r str,
+}
+
+#[allow(clippy::result_large_err)]
+#[post("/dashboard/duplicates/actions/dismiss", data = "<data>")]
+pub fn post_dismiss_duplicate(
+    db: Connections,
+    account: Account,
+    data: Form<DismissDuplicate>,
+) -> ActionResult {
+    let DismissDuplicate {
+        place_id,
+        duplicate_place_id,
+    } = data.into_inner();
+    let result = authorize_scout(&db, account.email()).and_then(|email| {
+        Ok(usecases::dismiss_duplicate_candidate(
+            &db.exclusive()?,
+            place_id.into(),
+            duplicate_place_id.into(),
+            email,
+        )?)
+    });
+    redirect_to_duplicates(result, "Failed to dismiss the duplicate")
+}
+
+fn authorize_scout(db: &Connections, email: &EmailAddress) -> Result<EmailAddress> {
+    let scout = usecases::authorize_user_by_email(&db.shared()?, email, Role::Scout)?;
+    Ok(scout.email)
+}
+
+#[allow(clippy::result_large_err)]
+fn redirect_to_duplicates<T>(result: Result<T>, failure: &str) -> ActionResult {
+    let redirect = Redirect::to(uri!(get_duplicates(_)));
+    match result {
+        Ok(_) => Ok(redirect),
+        Err(err) => Err(Flash::error(redirect, format!("{failure}: {err}"))),
+    }
+}
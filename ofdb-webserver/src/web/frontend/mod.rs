@@ -1,7 +1,7 @@
 use maud::Markup;
 use num_traits::FromPrimitive;
 use rocket::{
-    self, FromForm, Route,
+    self, FromForm, Responder, Route,
     form::Form,
     get,
     http::Status,
@@ -26,7 +26,9 @@ use ofdb_application::prelude::*;
 use ofdb_core::{repositories::Error as RepoError, usecases::Error as ParameterError};
 
 mod app_clearance;
+mod duplicates;
 mod login;
+mod organizations;
 mod password;
 mod register;
 mod view;
@@ -230,11 +232,28 @@ fn review_place(
     Ok(())
 }
 
+#[allow(clippy::large_enum_variant)]
+#[derive(Responder)]
+pub enum EntryResponse {
+    Page(Markup),
+    Redirect(Redirect),
+}
+
 #[get("/entries/<id>")]
-pub fn get_entry(pool: sqlite::Connections, id: &str, account: Option<Account>) -> Result<Markup> {
+pub fn get_entry(
+    pool: sqlite::Connections,
+    id: &str,
+    account: Option<Account>,
+) -> Result<EntryResponse> {
     //TODO: dry out
     let (user, place, ratings): (Option<User>, _, _) = {
         let db = pool.shared()?;
+        // Merged places permanently redirect to the surviving place
+        if let Some(place_id) = db.resolve_place_redirect(&id.into())? {
+            return Ok(EntryResponse::Redirect(Redirect::moved(uri!(get_entry(
+                place_id.as_str()
+            )))));
+        }
         let (place, _) = db.get_place(id)?;
         let ratings = db.load_ratings_of_place(place.id.as_ref())?;
         let ratings_with_comments = db.zip_ratings_with_comments(ratings)?;
@@ -245,10 +264,10 @@ pub fn get_entry(pool: sqlite::Connections, id: &str, account: Option<Account>)
         };
         (user, place, ratings_with_comments)
     };
-    Ok(match user {
+    Ok(EntryResponse::Page(match user {
         Some(u) => view::entry(Some(&u.email), (place, ratings, u.role).into()),
         None => view::entry(None, (place, ratings).into()),
-    })
+    }))
 }
 
 #[get("/events/<id>")]
@@ -334,23 +353,20 @@ pub fn get_events_chronologically(
 #[get("/dashboard")]
 pub fn get_dashboard(db: sqlite::Connections, account: Account) -> Result<Markup> {
     let db = db.shared()?;
+    let user = usecases::authorize_user_by_email(&db, account.email(), Role::Scout)?;
     let tag_count = db.count_tags()?;
     let place_count = db.count_places()?;
     let user_count = db.count_users()?;
     let event_count = db.count_events()?;
-    let user = db
-        .try_get_user_by_email(account.email())?
-        .ok_or(ParameterError::Unauthorized)?;
-    if user.role == Role::Admin {
-        return Ok(view::dashboard(view::DashBoardPresenter {
-            user,
-            place_count,
-            event_count,
-            tag_count,
-            user_count,
-        }));
-    }
-    Err(ParameterError::Unauthorized.into())
+    let open_duplicate_count = db.count_open_duplicate_candidates()?;
+    Ok(view::dashboard(view::DashBoardPresenter {
+        user,
+        place_count,
+        event_count,
+        tag_count,
+        user_count,
+        open_duplicate_count,
+    }))
 }
 
 #[derive(FromForm)]
@@ -407,6 +423,9 @@ pub fn routes() -> Vec<Route> {
         get_index,
         get_index_html,
         get_dashboard,
+        duplicates::get_duplicates,
+        duplicates::post_merge_duplicate,
+        duplicates::post_dismiss_duplicate,
         get_search,
         get_entry,
         get_place_history,
@@ -428,6 +447,15 @@ pub fn routes() -> Vec<Route> {
         register::get_register,
         register::post_register,
         register::get_email_confirmation,
+        organizations::get_organizations,
+        organizations::get_organization,
+        organizations::post_organization,
+        organizations::post_rename_organization,
+        organizations::post_delete_organization,
+        organizations::post_moderated_tag,
+        organizations::post_remove_moderated_tag,
+        organizations::post_organization_member,
+        organizations::post_remove_organization_member,
         password::get_reset_password,
         password::post_reset_password_request,
         password::post_reset_password,
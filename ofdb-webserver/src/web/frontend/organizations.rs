@@ -0,0 +1,244 @@
+use maud::Markup;
+use rocket::{
+    self, FromForm,
+    form::Form,
+    get, post,
+    request::FlashMessage,
+    response::{Flash, Redirect},
+    uri,
+};
+
+use super::{Result, view};
+use crate::{
+    core::{prelude::*, usecases},
+    web::{guards::*, sqlite::Connections, tantivy::SearchEngine},
+};
+use ofdb_application::db::DbReadWrite;
+use ofdb_core::usecases::Error as ParameterError;
+
+const MAX_ORGANIZATION_PLACES: usize = 100;
+
+type ActionResult = std::result::Result<Redirect, Flash<Redirect>>;
+
+#[get("/organizations")]
+pub fn get_organizations(
+    db: Connections,
+    account: Account,
+    flash: Option<FlashMessage>,
+) -> Result<Markup> {
+    let db = db.shared()?;
+    let admin = usecases::authorize_user_by_email(&db, account.email(), Role::Admin)?;
+    let orgs = db.all_orgs()?;
+    Ok(view::organizations(&admin.email, flash, &orgs))
+}
+
+#[get("/organizations/<id>")]
+pub fn get_organization(
+    db: Connections,
+    search_engine: SearchEngine,
+    account: Account,
+    flash: Option<FlashMessage>,
+    id: &str,
+) -> Result<Markup> {
+    let db = db.shared()?;
+    let admin = usecases::authorize_user_by_email(&db, account.email(), Role::Admin)?;
+    let org = db.get_org_by_id(&id.into())?;
+    let members = db.list_org_members(&org.id)?;
+    let places = usecases::org_places(&db, &*search_engine, &org.id, MAX_ORGANIZATION_PLACES)?;
+    Ok(view::organization(
+        &admin.email,
+        flash,
+        &org,
+        &members,
+        &places,
+    ))
+}
+
+#[derive(FromForm)]
+pub struct OrganizationName<'r> {
+    name: &'r str,
+}
+
+#[allow(clippy::result_large_err)]
+#[post("/organizations", data = "<data>")]
+pub fn post_organization(
+    db: Connections,
+    account: Account,
+    data: Form<OrganizationName>,
+) -> ActionResult {
+    let OrganizationName { name } = data.into_inner();
+    let new_org = usecases::NewOrganization {
+        name: name.to_owned(),
+        moderated_tags: vec![],
+    };
+    with_admin(&db, account.email(), |db| usecases::create_org(db, new_org))
+        .map(|org| Redirect::to(uri!(get_organization(org.id.as_str()))))
+        .map_err(|err| {
+            Flash::error(
+                Redirect::to(uri!(get_organizations)),
+                format!("Failed to create the organization: {err}"),
+            )
+        })
+}
+
+#[allow(clippy::result_large_err)]
+#[post("/organizations/<id>/actions/rename", data = "<data>")]
+pub fn post_rename_organization(
+    db: Connections,
+    account: Account,
+    id: &str,
+    data: Form<OrganizationName>,
+) -> ActionResult {
+    let OrganizationName { name } = data.into_inner();
+    redirect_to_organization(
+        id,
+        with_admin(&db, account.email(), |db| {
+            usecases::rename_org(db, &id.into(), name)
+        }),
+        "Failed to rename the organization",
+    )
+}
+
+#[allow(clippy::result_large_err)]
+#[post("/organizations/<id>/actions/delete")]
+pub fn post_delete_organization(db: Connections, account: Account, id: &str) -> ActionResult {
+    with_admin(&db, account.email(), |db| {
+        usecases::delete_org(db, &id.into())
+    })
+    .map(|()| Redirect::to(uri!(get_organizations)))
+    .map_err(|err| {
+        Flash::error(
+            Redirect::to(uri!(get_organization(id))),
+            format!("Failed to delete the organization: {err}"),
+        )
+    })
+}
+
+#[derive(FromForm)]
+pub struct ModeratedTagForm<'r> {
+    label: &'r str,
+    allow_add: bool,
+    allow_remove: bool,
+    require_clearance: bool,
+}
+
+#[allow(clippy::result_large_err)]
+#[post("/organizations/<id>/moderated-tags", data = "<data>")]
+pub fn post_moderated_tag(
+    db: Connections,
+    account: Account,
+    id: &str,
+    data: Form<ModeratedTagForm>,
+) -> ActionResult {
+    let ModeratedTagForm {
+        label,
+        allow_add,
+        allow_remove,
+        require_clearance,
+    } = data.into_inner();
+    let moderated_tag = ModeratedTag {
+        label: label.to_owned(),
+        allow_add,
+        allow_remove,
+        require_clearance,
+    };
+    redirect_to_organization(
+        id,
+        with_admin(&db, account.email(), |db| {
+            usecases::add_or_update_moderated_tag(db, &id.into(), moderated_tag)
+        }),
+        "Failed to save the moderated tag",
+    )
+}
+
+#[derive(FromForm)]
+pub struct RemoveModeratedTag<'r> {
+    label: &'r str,
+}
+
+#[allow(clippy::result_large_err)]
+#[post("/organizations/<id>/moderated-tags/actions/remove", data = "<data>")]
+pub fn post_remove_moderated_tag(
+    db: Connections,
+    account: Account,
+    id: &str,
+    data: Form<RemoveModeratedTag>,
+) -> ActionResult {
+    let RemoveModeratedTag { label } = data.into_inner();
+    redirect_to_organization(
+        id,
+        with_admin(&db, account.email(), |db| {
+            usecases::remove_moderated_tag(db, &id.into(), label)
+        }),
+        "Failed to remove the moderated tag",
+    )
+}
+
+#[derive(FromForm)]
+pub struct MemberForm<'r> {
+    email: &'r str,
+    role: &'r str,
+}
+
+#[allow(clippy::result_large_err)]
+#[post("/organizations/<id>/members", data = "<data>")]
+pub fn post_organization_member(
+    db: Connections,
+    account: Account,
+    id: &str,
+    data: Form<MemberForm>,
+) -> ActionResult {
+    let MemberForm { email, role } = data.into_inner();
+    redirect_to_organization(
+        id,
+        with_admin(&db, account.email(), |db| {
+            let email = email.parse().map_err(|_| ParameterError::EmailAddress)?;
+            let role = role.parse().map_err(|_| ParameterError::OrganizationRole)?;
+            usecases::add_or_update_org_member(db, &id.into(), &email, role)
+        }),
+        "Failed to save the member",
+    )
+}
+
+#[derive(FromForm)]
+pub struct RemoveMember<'r> {
+    email: &'r str,
+}
+
+#[allow(clippy::result_large_err)]
+#[post("/organizations/<id>/members/actions/remove", data = "<data>")]
+pub fn post_remove_organization_member(
+    db: Connections,
+    account: Account,
+    id: &str,
+    data: Form<RemoveMember>,
+) -> ActionResult {
+    let RemoveMember { email } = data.into_inner();
+    redirect_to_organization(
+        id,
+        with_admin(&db, account.email(), |db| {
+            let email = email.parse().map_err(|_| ParameterError::EmailAddress)?;
+            usecases::remove_org_member(db, &id.into(), &email)
+        }),
+        "Failed to remove the member",
+    )
+}
+
+fn with_admin<T>(
+    db: &Connections,
+    email: &EmailAddress,
+    f: impl FnOnce(&mut DbReadWrite<'_>) -> std::result::Result<T, ParameterError>,
+) -> Result<T> {
+    let mut db = db.exclusive()?;
+    usecases::authorize_user_by_email(&db, email, Role::Admin)?;
+    Ok(f(&mut db)?)
+}
+
+#[allow(clippy::result_large_err)]
+fn redirect_to_organization<T>(id: &str, result: Result<T>, failure: &str) -> ActionResult {
+    let redirect = Redirect::to(uri!(get_organization(id)));
+    match result {
+        Ok(_) => Ok(redirect),
+        Err(err) => Err(Flash::error(redirect, format!("{failure}: {err}"))),
+    }
+}
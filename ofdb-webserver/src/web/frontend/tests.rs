@@ -216,6 +216,7 @@ mod events {
         let (client, db, _) = setup();
         let events = vec![Event {
             id: "1234".into(),
+            revision: Revision::initial(),
             title: "A great event".into(),
             description: Some("Foo bar baz".into()),
             start: Timestamp::try_from_secs(0).unwrap(),
@@ -229,12 +230,19 @@ mod events {
             archived: None,
             image_url: None,
             image_link_url: None,
+            recurrence: None,
+            translations: vec![],
         }];
 
         {
             let db_conn = db.exclusive().unwrap();
             for e in events {
-                db_conn.create_event(e).unwrap();
+                let activity = ActivityLog {
+                    activity: Activity::now(None),
+                    context: None,
+                    comment: None,
+                };
+                db_conn.create_event(e, &activity).unwrap();
             }
         }
 
@@ -298,6 +306,7 @@ mod entry {
             image_url: None,
             image_link_url: None,
             custom_links: vec![],
+            translations: vec![],
         };
         let gw = DummyNotifyGW;
         let mut accepted_licenses = HashSet::new();
@@ -330,6 +339,31 @@ mod entry {
         assert!(!body_str.contains("action=\"/comments/actions/archive\""));
     }
 
+    #[test]
+    fn redirect_merged_entry() {
+        let (client, db, mut search) = setup();
+        let (id, _, _) = create_place_with_rating(&db, &mut *search);
+        let (merged_id, _, _) = create_place_with_rating(&db, &mut *search);
+        create_user(&db, "foo", Role::Scout);
+        flows::merge_places(
+            &db,
+            &mut *search,
+            usecases::MergePlaces {
+                place_id: id.as_str().into(),
+                merged_place_id: merged_id.as_str().into(),
+                merged_by: "foo@example.com".parse().unwrap(),
+                comment: None,
+            },
+        )
+        .unwrap();
+        let res = client.get(format!("/entries/{merged_id}")).dispatch();
+        assert_eq!(res.status(), Status::MovedPermanently);
+        assert_eq!(
+            Some(format!("/entries/{id}").as_str()),
+            res.headers().get_one("Location")
+        );
+    }
+
     #[test]
     fn get_entry_details_as_admin() {
         let (client, db, mut search) = setup();
@@ -478,6 +512,186 @@ mod admin {
         let user = get_user(&db, "user");
         assert_eq!(user.role, Role::Scout);
     }
+
+    #[test]
+    fn manage_organizations() {
+        let (client, db, _) = setup();
+        create_user(&db, "admin", Role::Admin);
+        create_user(&db, "user", Role::User);
+        login_user(&client, "user");
+        let res = client.get("/organizations").dispatch();
+        assert_eq!(res.status(), Status::Unauthorized);
+
+        login_user(&client, "admin");
+        let res = client
+            .post("/organizations")
+            .header(ContentType::Form)
+            .body("name=Foo")
+            .dispatch();
+        assert_eq!(res.status(), Status::SeeOther);
+        let org = db.shared().unwrap().all_orgs().unwrap().remove(0);
+        assert_eq!(org.name, "Foo");
+
+        let res = client
+            .post(format!("/organizations/{}/moderated-tags", org.id))
+            .header(ContentType::Form)
+            .body("label=bar&allow_add=true")
+            .dispatch();
+        assert_eq!(res.status(), Status::SeeOther);
+        let org = db.shared().unwrap().get_org_by_id(&org.id).unwrap();
+        assert_eq!(org.moderated_tags[0].label, "bar");
+        assert!(org.moderated_tags[0].allow_add);
+        assert!(!org.moderated_tags[0].require_clearance);
+
+        let res = client
+            .post(format!("/organizations/{}/members", org.id))
+            .header(ContentType::Form)
+            .body("email=user%40example.com&role=editor")
+            .dispatch();
+        assert_eq!(res.status(), Status::SeeOther);
+        let members = db.shared().unwrap().list_org_members(&org.id).unwrap();
+        assert_eq!(members[0].role, OrganizationRole::Editor);
+
+        let res = client.get(format!("/organizations/{}", org.id)).dispatch();
+        assert_eq!(res.status(), Status::Ok);
+        let body = res.into_string().unwrap();
+        assert!(body.contains("#bar"));
+        assert!(body.contains("user@example.com"));
+
+        let res = client
+            .post(format!("/organizations/{}/members/actions/remove", org.id))
+            .header(ContentType::Form)
+            .body("email=user%40example.com")
+            .dispatch();
+        assert_eq!(res.status(), Status::SeeOther);
+        assert!(
+            db.shared()
+                .unwrap()
+                .list_org_members(&org.id)
+                .unwrap()
+                .is_empty()
+        );
+
+        let res = client
+            .post(format!("/organizations/{}/actions/delete", org.id))
+            .dispatch();
+        assert_eq!(res.status(), Status::SeeOther);
+        assert!(db.shared().unwrap().all_orgs().unwrap().is_empty());
+    }
+}
+
+mod duplicates {
+    use super::*;
+    use crate::core::usecases;
+    use ofdb_application::prelude as flows;
+
+    fn create_place(
+        db: &sqlite::Connections,
+        search: &mut dyn ofdb_core::db::PlaceIndexer,
+        title: &str,
+    ) -> Id {
+        let new_place = usecases::NewPlace {
+            title: title.into(),
+            description: "desc".into(),
+            lat: 3.7,
+            lng: -50.0,
+            street: None,
+            zip: None,
+            city: None,
+            country: None,
+            state: None,
+            contact_name: None,
+            email: None,
+            telephone: None,
+            homepage: None,
+            opening_hours: None,
+            founded_on: None,
+            categories: vec![],
+            tags: vec![],
+            license: "CC0-1.0".into(),
+            image_url: None,
+            image_link_url: None,
+            custom_links: vec![],
+            translations: vec![],
+        };
+        let accepted_licenses = ["CC0-1.0".to_string()].into_iter().collect();
+        flows::create_place(
+            db,
+            search,
+            &DummyNotifyGW,
+            new_place,
+            None,
+            None,
+            &accepted_licenses,
+        )
+        .unwrap()
+        .id
+    }
+
+    #[test]
+    fn review_duplicates_as_scout() {
+        let (client, db, mut search) = setup();
+        let first = create_place(&db, &mut *search, "Fair Coffee Shop");
+        let second = create_place(&db, &mut *search, "Fair Cofee Shop");
+        assert_eq!(1, flows::detect_duplicates(&db, &*search).unwrap());
+        let (place_id, duplicate_place_id) = DuplicateCandidate::ordered_pair(first, second);
+        create_user(&db, "user", Role::User);
+        create_user(&db, "scout", Role::Scout);
+
+        login_user(&client, "user");
+        let res = client.get("/dashboard").dispatch();
+        assert_eq!(res.status(), Status::Unauthorized);
+        let res = client.get("/dashboard/duplicates").dispatch();
+        assert_eq!(res.status(), Status::Unauthorized);
+
+        login_user(&client, "scout");
+        let res = client.get("/dashboard").dispatch();
+        assert_eq!(res.status(), Status::Ok);
+        let body = res.into_string().unwrap();
+        assert!(body.contains("1 possible duplicates to review"));
+        assert!(!body.contains("action=\"search-users\""));
+        let res = client.get("/dashboard/duplicates").dispatch();
+        assert_eq!(res.status(), Status::Ok);
+        let body = res.into_string().unwrap();
+        assert!(body.contains("Fair Coffee Shop"));
+        assert!(body.contains("Fair Cofee Shop"));
+
+        let res = client
+            .post("/dashboard/duplicates/actions/dismiss")
+            .header(ContentType::Form)
+            .body(format!(
+                "place_id={place_id}&duplicate_place_id={duplicate_place_id}"
+            ))
+            .dispatch();
+        assert_eq!(res.status(), Status::SeeOther);
+        assert_eq!(
+            0,
+            db.shared()
+                .unwrap()
+                .count_open_duplicate_candidates()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn merge_duplicates_as_scout() {
+        let (client, db, mut search) = setup();
+        let first = create_place(&db, &mut *search, "Fair Coffee Shop");
+        let second = create_place(&db, &mut *search, "Fair Coffee Shop");
+        flows::detect_duplicates(&db, &*search).unwrap();
+        create_user(&db, "scout", Role::Scout);
+        login_user(&client, "scout");
+        let res = client
+            .post("/dashboard/duplicates/actions/merge")
+            .header(ContentType::Form)
+            .body(format!("place_id={first}&merged_place_id={second}"))
+            .dispatch();
+        assert_eq!(res.status(), Status::SeeOther);
+        assert!(res.headers().get_one("Set-Cookie").is_none());
+        let db = db.shared().unwrap();
+        assert_eq!(0, db.count_open_duplicate_candidates().unwrap());
+        assert_eq!(Some(first), db.resolve_place_redirect(&second).unwrap());
+    }
 }
 
 mod login {
@@ -9,11 +9,17 @@ pub struct DashBoardPresenter {
     pub event_count: usize,
     pub tag_count: usize,
     pub user_count: usize,
+    pub open_duplicate_count: usize,
 }
 
 pub fn dashboard(data: DashBoardPresenter) -> Markup {
+    let is_admin = data.user.role == Role::Admin;
     page(
-        "Admin Dashboard",
+        if is_admin {
+            "Admin Dashboard"
+        } else {
+            "Dashboard"
+        },
         Some(&data.user.email),
         None,
         None,
@@ -38,8 +44,18 @@ pub fn dashboard(data: DashBoardPresenter) -> Markup {
                         td {(data.tag_count)}
                     }
                 }
-                h3 { "User Management" }
-                (super::search_users_form())
+                h3 { "Possible Duplicates" }
+                p {
+                    a href="/dashboard/duplicates" {
+                        (data.open_duplicate_count) " possible duplicates to review"
+                    }
+                }
+                @if is_admin {
+                    h3 { "User Management" }
+                    (super::search_users_form())
+                    h3 { "Organizations" }
+                    a href="/organizations" { "Manage organizations and moderated tags" }
+                }
             }
         },
     )
@@ -0,0 +1,114 @@
+use maud::{Markup, html};
+use rocket::request::FlashMessage;
+
+use super::page;
+use crate::core::prelude::*;
+
+pub struct DuplicateCandidatePresenter {
+    pub candidate: DuplicateCandidate,
+    /// Missing if the place doesn't exist anymore
+    pub place_title: Option<String>,
+    pub duplicate_place_title: Option<String>,
+}
+
+pub struct DuplicateCandidatesPresenter<'a> {
+    pub email: &'a EmailAddress,
+    pub flash: Option<FlashMessage<'a>>,
+    pub candidates: &'a [DuplicateCandidatePresenter],
+    pub offset: u64,
+    pub limit: u64,
+    pub total_count: u64,
+}
+
+pub fn duplicate_candidates(data: DuplicateCandidatesPresenter) -> Markup {
+    let DuplicateCandidatesPresenter {
+        email,
+        flash,
+        candidates,
+        offset,
+        limit,
+        total_count,
+    } = data;
+    page(
+        "Possible Duplicates",
+        Some(email),
+        flash,
+        None,
+        html! {
+            main class="duplicates" {
+                h3 { "Possible Duplicates" }
+                p { a href="/dashboard" { "Dashboard" } }
+                @if candidates.is_empty() {
+                    p { "There are no possible duplicates to review." }
+                } @else {
+                    table {
+                        thead {
+                            tr {
+                                th { "Place" }
+                                th { "Possible duplicate" }
+                                th { "Score" }
+                                th { "Detected" }
+                                th {}
+                            }
+                        }
+                        tbody {
+                            @for c in candidates {
+                                (duplicate_candidate_row(c))
+                            }
+                        }
+                    }
+                    p class="pagination" {
+                        @if offset > 0 {
+                            a href=(format!("/dashboard/duplicates?offset={}", offset.saturating_sub(limit))) { "previous" }
+                        }
+                        " " (offset + 1) "–" (offset + candidates.len() as u64) " of " (total_count) " "
+                        @if offset + limit < total_count {
+                            a href=(format!("/dashboard/duplicates?offset={}", offset + limit)) { "next" }
+                        }
+                    }
+                }
+            }
+        },
+    )
+}
+
+fn duplicate_candidate_row(data: &DuplicateCandidatePresenter) -> Markup {
+    let DuplicateCandidatePresenter {
+        candidate,
+        place_title,
+        duplicate_place_title,
+    } = data;
+    let place_id = candidate.place_id.as_str();
+    let duplicate_place_id = candidate.duplicate_place_id.as_str();
+    html! {
+        tr {
+            td { (place_link(place_id, place_title.as_deref())) }
+            td { (place_link(duplicate_place_id, duplicate_place_title.as_deref())) }
+            td { (format!("{:.0} %", candidate.similarity * 100.0)) }
+            td { (candidate.detected_at.to_string()) }
+            td {
+                form action="/dashboard/duplicates/actions/merge" method="POST" {
+                    input type="hidden" name="place_id" value=(place_id);
+                    input type="hidden" name="merged_place_id" value=(duplicate_place_id);
+                    input type="submit" value="merge into first";
+                }
+                form action="/dashboard/duplicates/actions/merge" method="POST" {
+                    input type="hidden" name="place_id" value=(duplicate_place_id);
+                    input type="hidden" name="merged_place_id" value=(place_id);
+                    input type="submit" value="merge into second";
+                }
+                form action="/dashboard/duplicates/actions/dismiss" method="POST" {
+                    input type="hidden" name="place_id" value=(place_id);
+                    input type="hidden" name="duplicate_place_id" value=(duplicate_place_id);
+                    input type="submit" value="dismiss";
+                }
+            }
+        }
+    }
+}
+
+fn place_link(id: &str, title: Option<&str>) -> Markup {
+    html! {
+        a href=(format!("/entries/{id}")) { (title.unwrap_or(id)) }
+    }
+}
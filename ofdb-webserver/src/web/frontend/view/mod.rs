@@ -10,18 +10,22 @@ const LEAFLET_JS_SHA512: &str = "sha512-QVftwZFqvtRNi0ZyCtsznlKSWOStnDORoefr1eny
 const MAP_JS_URL: &str = "/map.js";
 
 mod dashboard;
+mod duplicates;
 mod entry;
 mod event;
 mod login;
+mod organization;
 mod page;
 mod password;
 mod place;
 mod register;
 
 pub use dashboard::*;
+pub use duplicates::*;
 pub use entry::*;
 pub use event::*;
 pub use login::*;
+pub use organization::*;
 use page::*;
 pub use password::*;
 pub use place::*;
@@ -0,0 +1,180 @@
+use maud::{Markup, html};
+use rocket::request::FlashMessage;
+
+use super::page;
+use crate::core::{db::IndexedPlace, prelude::*};
+
+pub fn organizations(
+    email: &EmailAddress,
+    flash: Option<FlashMessage>,
+    orgs: &[Organization],
+) -> Markup {
+    page(
+        "Organizations",
+        Some(email),
+        flash,
+        None,
+        html! {
+            main class="organizations" {
+                h3 { "Organizations" }
+                @if orgs.is_empty() {
+                    p { "There are no organizations yet." }
+                } @else {
+                    table {
+                        thead {
+                            tr {
+                                th { "Name" }
+                                th { "Moderated tags" }
+                            }
+                        }
+                        tbody {
+                            @for org in orgs {
+                                tr {
+                                    td {
+                                        a href=(format!("/organizations/{}", org.id)) { (org.name) }
+                                    }
+                                    td {
+                                        (org.moderated_tags
+                                            .iter()
+                                            .map(|tag| format!("#{}", tag.label))
+                                            .collect::<Vec<_>>()
+                                            .join(", "))
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                h4 { "New organization" }
+                form action="/organizations" method="POST" {
+                    input type="text" name="name" placeholder="name" required;
+                    input type="submit" value="create";
+                }
+            }
+        },
+    )
+}
+
+pub fn organization(
+    email: &EmailAddress,
+    flash: Option<FlashMessage>,
+    org: &Organization,
+    members: &[OrganizationMember],
+    places: &[IndexedPlace],
+) -> Markup {
+    page(
+        &format!("Organization: {}", org.name),
+        Some(email),
+        flash,
+        None,
+        html! {
+            main class="organization" {
+                h3 { (org.name) }
+                p { a href="/organizations" { "All organizations" } }
+                form action=(format!("/organizations/{}/actions/rename", org.id)) method="POST" {
+                    input type="text" name="name" value=(org.name) required;
+                    input type="submit" value="rename";
+                }
+                h4 { "Moderated tags" }
+                @if org.moderated_tags.is_empty() {
+                    p { "This organization does not moderate any tags." }
+                } @else {
+                    table {
+                        thead {
+                            tr {
+                                th { "Tag" }
+                                th { "Allow add" }
+                                th { "Allow remove" }
+                                th { "Require clearance" }
+                                th {}
+                            }
+                        }
+                        tbody {
+                            @for tag in &org.moderated_tags {
+                                tr {
+                                    td { "#" (tag.label) }
+                                    td { (yes_no(tag.allow_add)) }
+                                    td { (yes_no(tag.allow_remove)) }
+                                    td { (yes_no(tag.require_clearance)) }
+                                    td {
+                                        form action=(format!("/organizations/{}/moderated-tags/actions/remove", org.id)) method="POST" {
+                                            input type="hidden" name="label" value=(tag.label);
+                                            input type="submit" value="remove";
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                h4 { "Add or update a moderated tag" }
+                form action=(format!("/organizations/{}/moderated-tags", org.id)) method="POST" {
+                    input type="text" name="label" placeholder="tag" required;
+                    label { input type="checkbox" name="allow_add" value="true"; "allow add" }
+                    label { input type="checkbox" name="allow_remove" value="true"; "allow remove" }
+                    label { input type="checkbox" name="require_clearance" value="true"; "require clearance" }
+                    input type="submit" value="save";
+                }
+                h4 { "Members" }
+                @if members.is_empty() {
+                    p { "This organization does not have any members." }
+                } @else {
+                    table {
+                        thead {
+                            tr {
+                                th { "E-mail" }
+                                th { "Role" }
+                                th {}
+                            }
+                        }
+                        tbody {
+                            @for member in members {
+                                tr {
+                                    td { (member.email) }
+                                    td { (member.role) }
+                                    td {
+                                        form action=(format!("/organizations/{}/members/actions/remove", org.id)) method="POST" {
+                                            input type="hidden" name="email" value=(member.email);
+                                            input type="submit" value="remove";
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                h4 { "Add a member or change the role" }
+                form action=(format!("/organizations/{}/members", org.id)) method="POST" {
+                    input type="email" name="email" placeholder="e-mail of a registered user" required;
+                    select name="role" {
+                        @for role in [OrganizationRole::Viewer, OrganizationRole::Editor, OrganizationRole::Clearer] {
+                            option value=(role) { (role) }
+                        }
+                    }
+                    input type="submit" value="save";
+                }
+                h4 { "Places" }
+                @if places.is_empty() {
+                    p { "No places are tagged with the moderated tags." }
+                } @else {
+                    ul {
+                        @for place in places {
+                            li {
+                                a href=(format!("/entries/{}", place.id)) { (place.title) }
+                            }
+                        }
+                    }
+                }
+                h4 { "Delete organization" }
+                form action=(format!("/organizations/{}/actions/delete", org.id)) method="POST" {
+                    p { "All moderated tags, members, clearances, API tokens, and webhooks of the organization will be deleted." }
+                    input type="submit" value="delete";
+                }
+            }
+        },
+    )
+}
+
+const fn yes_no(value: bool) -> &'static str {
+    if value { "yes" } else { "no" }
+}
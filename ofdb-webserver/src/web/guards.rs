@@ -9,19 +9,22 @@ use rocket::{
 use time::{Duration, OffsetDateTime};
 
 use crate::{
-    core::{prelude::*, repositories::OrganizationRepo, usecases},
-    web::jwt,
+    core::{prelude::*, usecases},
+    web::{jwt, sqlite},
 };
 use ofdb_application::error::AppError;
 use ofdb_core::{
     entities::EmailAddress,
-    gateways::{geocode::GeoCodingGateway, notify::NotificationGateway},
+    gateways::{
+        geocode::GeoCodingGateway, image_storage::ImageStorage, notify::NotificationGateway,
+    },
     usecases::Error as ParameterError,
 };
 
 pub const COOKIE_EMAIL_KEY: &str = "ofdb-user-email";
 pub const COOKIE_CAPTCHA_KEY: &str = "ofdb-captcha";
 pub const MAX_CAPTCHA_TTL: Duration = Duration::seconds(120);
+pub const ORGANIZATION_ID_HEADER: &str = "X-Organization-Id";
 
 type Result<T> = std::result::Result<T, AppError>;
 
@@ -38,6 +41,10 @@ fn get_bearer_token(auth_header_val: &str) -> Option<&str> {
 pub struct Auth {
     bearer_tokens: Vec<String>,
     account_email: Option<EmailAddress>,
+    // Only accounts that are authenticated by a JWT in the header
+    // are allowed to act on behalf of an organization.
+    jwt_account_email: Option<EmailAddress>,
+    organization_id: Option<Id>,
     has_captcha: bool,
 }
 
@@ -60,14 +67,59 @@ impl Auth {
         }
     }
 
-    pub fn organization<R>(&self, repo: &R) -> Result<Organization>
-    where
-        R: OrganizationRepo + UserRepo,
-    {
-        Ok(usecases::authorize_organization_by_possible_api_tokens(
-            repo,
-            &self.bearer_tokens,
-        )?)
+    /// Authorize an organization either by one of the bearer tokens
+    /// or by the membership of the logged in user.
+    ///
+    /// The database must not be locked by the caller, because
+    /// the usage of API tokens is recorded.
+    pub fn organization(
+        &self,
+        connections: &sqlite::Connections,
+        scope: ApiTokenScope,
+    ) -> Result<Organization> {
+        self.organization_with_member(connections, scope)
+            .map(|(org, _)| org)
+    }
+
+    /// Authorize an organization and return the acting member.
+    ///
+    /// The member is `None` if the organization has been authorized
+    /// by an API token. Members must authenticate with a JWT, the
+    /// session cookie would also be sent with cross-site requests.
+    ///
+    /// Members of multiple organizations need to select one of them
+    /// with the `X-Organization-Id` header.
+    pub fn organization_with_member(
+        &self,
+        connections: &sqlite::Connections,
+        scope: ApiTokenScope,
+    ) -> Result<(Organization, Option<EmailAddress>)> {
+        let (org, api_token) = {
+            let db = connections.shared()?;
+            match usecases::authorize_organization_by_possible_api_tokens(
+                &db,
+                &self.bearer_tokens,
+                scope,
+            ) {
+                Err(ParameterError::Unauthorized) => {
+                    let Some(email) = &self.jwt_account_email else {
+                        return Err(ParameterError::Unauthorized.into());
+                    };
+                    let org = usecases::authorize_organization_by_member(
+                        &db,
+                        email,
+                        self.organization_id.as_ref(),
+                        scope,
+                    )?;
+                    return Ok((org, Some(email.clone())));
+                }
+                result => result?,
+            }
+        };
+        if let Some(api_token) = api_token {
+            record_api_token_usage(connections, &api_token);
+        }
+        Ok((org, None))
     }
 
     pub fn user_with_min_role<R>(&self, repo: &R, min_required_role: Role) -> Result<User>
@@ -90,6 +142,15 @@ impl Auth {
             .collect()
     }
 
+    fn organization_id_from_header(request: &Request) -> Option<Id> {
+        request
+            .headers()
+            .get_one(ORGANIZATION_ID_HEADER)
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+            .map(Id::from)
+    }
+
     fn account_email_from_cookie(request: &Request) -> Option<EmailAddress> {
         request
             .cookies()
@@ -102,9 +163,14 @@ impl Auth {
         bearer_tokens: &[String],
     ) -> Option<EmailAddress> {
         let jwt_state = request.guard::<&State<jwt::JwtState>>().await.succeeded()?;
+        let connections = request.guard::<sqlite::Connections>().await.succeeded()?;
+        let db = connections
+            .shared()
+            .map_err(|err| log::warn!("Failed to validate JWT: {err}"))
+            .ok()?;
         bearer_tokens
             .iter()
-            .filter_map(|token| jwt_state.validate_token_and_get_email(token).ok())
+            .filter_map(|token| jwt_state.validate_token_and_get_email(&db, token).ok())
             .next()
     }
 
@@ -123,26 +189,54 @@ impl Auth {
     }
 }
 
+fn record_api_token_usage(connections: &sqlite::Connections, api_token: &ApiToken) {
+    let now = Timestamp::now();
+    if !usecases::is_api_token_usage_outdated(api_token, now) {
+        return;
+    }
+    if let Err(err) = connections
+        .exclusive()
+        .map_err(|err| err.to_string())
+        .and_then(|db| {
+            usecases::record_api_token_usage(&db, &api_token.id, now).map_err(|err| err.to_string())
+        })
+    {
+        log::warn!(
+            "Failed to record usage of API token {}: {err}",
+            api_token.id
+        );
+    }
+}
+
 #[rocket::async_trait]
 impl<'r> FromRequest<'r> for Auth {
     type Error = ();
     async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
         let bearer_tokens = Self::bearer_tokens_from_header(request);
 
+        let mut jwt_account_email = None;
+        if cfg!(feature = "jwt") {
+            jwt_account_email =
+                Self::account_email_from_jwt_in_header(request, &bearer_tokens).await;
+        }
+
         // decide account_email source
         let mut account_email = None;
         if cfg!(feature = "cookies") {
             account_email = Self::account_email_from_cookie(request);
         }
-        if cfg!(feature = "jwt") && account_email.is_none() {
-            account_email = Self::account_email_from_jwt_in_header(request, &bearer_tokens).await;
+        if account_email.is_none() {
+            account_email = jwt_account_email.clone();
         }
 
+        let organization_id = Self::organization_id_from_header(request);
         let has_captcha = Self::captcha_from_cookie(request);
 
         let auth = Self {
             bearer_tokens,
             account_email,
+            jwt_account_email,
+            organization_id,
             has_captcha,
         };
 
@@ -171,6 +265,67 @@ impl<'r> FromRequest<'r> for Account {
     }
 }
 
+/// The preferred languages of the client in descending order.
+///
+/// The `lang` query parameter (a comma-separated list of language
+/// tags) takes precedence over the `Accept-Language` header. An empty
+/// list selects the original text.
+#[derive(Debug, Default)]
+pub struct Languages(pub Vec<Language>);
+
+impl Languages {
+    fn from_query_param(value: &str) -> std::result::Result<Self, LanguageParseError> {
+        value
+            .split(',')
+            .map(str::trim)
+            .filter(|tag| !tag.is_empty())
+            .map(str::parse)
+            .collect::<std::result::Result<_, _>>()
+            .map(Self)
+    }
+
+    // Invalid language tags and weights are ignored
+    fn from_accept_language(value: &str) -> Self {
+        let mut weighted: Vec<_> = value
+            .split(',')
+            .filter_map(|range| {
+                let mut params = range.split(';');
+                let lang = params.next()?.trim().parse::<Language>().ok()?;
+                let weight = params
+                    .filter_map(|param| param.trim().strip_prefix("q="))
+                    .find_map(|q| q.trim().parse::<f32>().ok())
+                    .unwrap_or(1.0);
+                (weight > 0.0).then_some((lang, weight))
+            })
+            .collect();
+        // Stable sort to preserve the order of equally weighted languages
+        weighted.sort_by(|(_, lhs), (_, rhs)| rhs.total_cmp(lhs));
+        Self(weighted.into_iter().map(|(lang, _)| lang).collect())
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Languages {
+    type Error = ();
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        if let Some(value) = request.query_value::<&str>("lang") {
+            return match value.ok().map(Self::from_query_param) {
+                Some(Ok(languages)) => Outcome::Success(languages),
+                _ => Outcome::Error((Status::BadRequest, ())),
+            };
+        }
+        let languages = request
+            .headers()
+            .get("Accept-Language")
+            .map(Self::from_accept_language)
+            .fold(Self::default(), |mut languages, next| {
+                languages.0.extend(next.0);
+                languages
+            });
+        Outcome::Success(languages)
+    }
+}
+
 pub struct GeoCoding(pub Box<dyn GeoCodingGateway + Send + Sync>);
 
 pub struct Notify(pub Box<dyn NotificationGateway + Send + Sync>);
@@ -182,4 +337,7 @@ impl Deref for Notify {
     }
 }
 
+/// Storage for uploaded images, if configured.
+pub struct Images(pub Option<Box<dyn ImageStorage + Send + Sync>>);
+
 pub struct Version(pub &'static str);
@@ -1,88 +1,202 @@
-use std::collections::HashSet;
+use std::path::PathBuf;
 
-use crate::core::entities::EmailAddress;
+use crate::core::{
+    entities::{EmailAddress, Nonce},
+    repositories::RevokedTokenRepo,
+    usecases,
+    util::time::Timestamp,
+};
 use anyhow::{Result, anyhow};
 use jwt_service::JwtService;
-use parking_lot::{Mutex, MutexGuard};
 use serde::{Deserialize, Serialize};
 use time::{Duration, OffsetDateTime};
 
+#[derive(Debug, Clone)]
+pub struct JwtCfg {
+    /// File system directory with the secret signing keys.
+    ///
+    /// Each file with the extension `.key` contains a single key
+    /// that is identified by its file name (`kid`). New tokens are
+    /// signed with the key that has the greatest `kid`. All other keys
+    /// are only used for validating tokens that have been issued before.
+    ///
+    /// If missing a random key is generated and all tokens
+    /// become invalid after a restart.
+    pub key_dir: Option<PathBuf>,
+    /// Validity of access tokens
+    pub token_valid: Duration,
+    /// Validity of refresh tokens
+    pub refresh_token_valid: Duration,
+}
+
+impl Default for JwtCfg {
+    fn default() -> Self {
+        Self {
+            key_dir: None,
+            token_valid: Duration::days(1),
+            refresh_token_valid: Duration::days(30),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum TokenType {
+    Access,
+    Refresh,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Claims {
     /// The email in our case
     sub: String,
     /// Expiry time as Unix timestamp
     exp: usize,
+    /// Unique id of the token
+    jti: String,
+    /// Id of the login session that is shared by
+    /// the access and refresh tokens
+    sid: String,
+    typ: TokenType,
+}
+
+impl Claims {
+    fn expires_at(&self) -> Timestamp {
+        Timestamp::try_from_secs(self.exp as i64).unwrap_or_else(|_| Timestamp::now())
+    }
+}
+
+#[derive(Debug)]
+pub struct Tokens {
+    pub token: String,
+    pub refresh_token: String,
 }
 
 pub struct JwtState {
     jwt_service: JwtService,
-    time_valid: Duration,
-    blacklist: Mutex<HashSet<String>>,
+    token_valid: Duration,
+    refresh_token_valid: Duration,
 }
 
 impl JwtState {
-    pub fn new() -> Self {
-        Self {
-            jwt_service: JwtService::new(),
-            time_valid: Duration::days(1),
-            blacklist: Mutex::new(HashSet::new()),
-        }
+    pub fn new(cfg: &JwtCfg) -> Result<Self> {
+        let jwt_service = match &cfg.key_dir {
+            Some(key_dir) => JwtService::load_keys(key_dir)?,
+            None => {
+                log::warn!("No JWT keys configured: all sessions will expire on restart");
+                JwtService::new()
+            }
+        };
+        Ok(Self {
+            jwt_service,
+            token_valid: cfg.token_valid,
+            refresh_token_valid: cfg.refresh_token_valid,
+        })
+    }
+
+    /// Start a new session
+    pub fn generate_tokens(&self, email: &str) -> Result<Tokens> {
+        let sid = Nonce::new().to_string();
+        self.generate_session_tokens(email, sid)
+    }
+
+    fn generate_session_tokens(&self, email: &str, sid: String) -> Result<Tokens> {
+        let token = self.generate_token(email, sid.clone(), TokenType::Access)?;
+        let refresh_token = self.generate_token(email, sid, TokenType::Refresh)?;
+        Ok(Tokens {
+            token,
+            refresh_token,
+        })
     }
 
-    pub fn generate_token(&self, email: &str) -> Result<String> {
-        let exp = usize::try_from((OffsetDateTime::now_utc() + self.time_valid).unix_timestamp())?;
+    fn generate_token(&self, email: &str, sid: String, typ: TokenType) -> Result<String> {
+        let time_valid = match typ {
+            TokenType::Access => self.token_valid,
+            TokenType::Refresh => self.refresh_token_valid,
+        };
+        let exp = usize::try_from((OffsetDateTime::now_utc() + time_valid).unix_timestamp())?;
         let claims = Claims {
             sub: email.to_string(),
             exp,
+            jti: Nonce::new().to_string(),
+            sid,
+            typ,
         };
         let token = self.jwt_service.encode(&claims)?;
         Ok(token)
     }
 
-    pub fn validate_token_and_get_email(&self, token: &str) -> Result<EmailAddress> {
-        if self.is_on_blacklist(token) {
+    fn validate_token<R>(&self, repo: &R, token: &str, typ: TokenType) -> Result<Claims>
+    where
+        R: RevokedTokenRepo,
+    {
+        let claims = self.jwt_service.decode(token)?;
+        if claims.typ != typ {
+            return Err(anyhow!("Unexpected token type"));
+        }
+        if usecases::is_token_revoked(repo, &claims.jti)?
+            || usecases::is_token_revoked(repo, &claims.sid)?
+        {
             return Err(anyhow!("Token is no longer valid"));
         }
-        let claims = self.jwt_service.decode(token)?;
-        let email = claims.sub.parse()?;
-        Ok(email)
-    }
-
-    pub fn blacklist_token(&self, token: String) {
-        self.remove_invalid_tokens(); // do housekeeping
-        self.lock().insert(token);
+        Ok(claims)
     }
 
-    fn is_on_blacklist(&self, token: &str) -> bool {
-        self.lock().get(token).is_some()
+    pub fn validate_token_and_get_email<R>(&self, repo: &R, token: &str) -> Result<EmailAddress>
+    where
+        R: RevokedTokenRepo,
+    {
+        let claims = self.validate_token(repo, token, TokenType::Access)?;
+        let email = claims.sub.parse()?;
+        Ok(email)
     }
 
-    // TODO: maybe this can be done more efficiently
-    fn remove_invalid_tokens(&self) {
-        let invalid_tokens = self
-            .lock()
-            .iter()
-            .filter(|token| self.jwt_service.decode(token).is_err())
-            .cloned()
-            .collect::<Vec<_>>();
-        for token in invalid_tokens {
-            self.lock().remove(&token);
-        }
+    /// Exchange a refresh token for new tokens of the same session.
+    ///
+    /// Each refresh token can only be used once.
+    pub fn refresh_tokens<R>(&self, repo: &R, refresh_token: &str) -> Result<Tokens>
+    where
+        R: RevokedTokenRepo,
+    {
+        let claims = self.validate_token(repo, refresh_token, TokenType::Refresh)?;
+        usecases::revoke_token(repo, &claims.jti, claims.expires_at())?;
+        self.generate_session_tokens(&claims.sub, claims.sid)
     }
 
-    fn lock(&self) -> MutexGuard<'_, HashSet<String>> {
-        self.blacklist.lock()
+    /// Revoke all tokens of the session that the given token belongs to.
+    pub fn revoke_session<R>(&self, repo: &R, token: &str) -> Result<()>
+    where
+        R: RevokedTokenRepo,
+    {
+        usecases::delete_expired_revoked_tokens(repo)?; // do housekeeping
+        // The refresh token of the session remains valid
+        // after the access token has expired
+        let claims = self.jwt_service.decode_expired(token)?;
+        // All tokens of the session expire before
+        let expires_at = Timestamp::now() + self.token_valid.max(self.refresh_token_valid);
+        usecases::revoke_token(repo, &claims.sid, expires_at)?;
+        Ok(())
     }
 }
 
 #[cfg(feature = "jwt")]
 mod jwt_service {
+    use std::{collections::HashMap, fs, path::Path};
+
+    use anyhow::{anyhow, bail};
     use base64::Engine;
-    use jsonwebtoken::{DecodingKey, EncodingKey, Header, Validation, decode, encode};
+    use jsonwebtoken::{
+        DecodingKey, EncodingKey, Header, Validation, decode, decode_header, encode,
+    };
 
     use super::{Claims, Result};
 
+    const KEY_FILE_EXTENSION: &str = "key";
+
+    const MIN_SECRET_LEN: usize = 32;
+
+    const RANDOM_KEY_ID: &str = "random";
+
     /// generate a Rocket-compatible secret (Rocket expects a
     /// 256-bit base64 encoded string)
     fn generate_rocket_secret() -> String {
@@ -111,20 +225,71 @@ mod jwt_service {
     }
 
     pub struct JwtService {
-        key: Key,
+        current_kid: String,
+        keys: HashMap<String, Key>,
     }
 
     impl JwtService {
         pub fn new() -> Self {
-            Self { key: Key::random() }
+            let current_kid = RANDOM_KEY_ID.to_string();
+            let keys = [(current_kid.clone(), Key::random())].into();
+            Self { current_kid, keys }
+        }
+        pub fn load_keys(key_dir: &Path) -> Result<Self> {
+            let mut keys = HashMap::new();
+            for entry in fs::read_dir(key_dir)? {
+                let path = entry?.path();
+                if path.extension().is_none_or(|ext| ext != KEY_FILE_EXTENSION) {
+                    continue;
+                }
+                let Some(kid) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                    continue;
+                };
+                let secret = fs::read_to_string(&path)?.trim().to_string();
+                if secret.len() < MIN_SECRET_LEN {
+                    bail!("The JWT key '{kid}' must have at least {MIN_SECRET_LEN} characters");
+                }
+                keys.insert(kid.to_string(), Key::new(secret));
+            }
+            let current_kid = keys
+                .keys()
+                .max()
+                .cloned()
+                .ok_or_else(|| anyhow!("No JWT keys found in {}", key_dir.display()))?;
+            log::info!(
+                "Loaded {} JWT key(s), signing with '{current_kid}'",
+                keys.len()
+            );
+            Ok(Self { current_kid, keys })
         }
         pub fn encode(&self, claims: &Claims) -> Result<String> {
-            let token = encode(&Header::default(), claims, &self.key.encoding_key)?;
+            let header = Header {
+                kid: Some(self.current_kid.clone()),
+                ..Default::default()
+            };
+            let token = encode(&header, claims, &self.keys[&self.current_kid].encoding_key)?;
             Ok(token)
         }
         pub fn decode(&self, token: &str) -> Result<Claims> {
-            let token_data =
-                decode::<Claims>(token, &self.key.decoding_key, &Validation::default())?;
+            self.decode_with(token, &Validation::default())
+        }
+        /// Decode a token that might have expired.
+        ///
+        /// The signature is still verified.
+        pub fn decode_expired(&self, token: &str) -> Result<Claims> {
+            let mut validation = Validation::default();
+            validation.validate_exp = false;
+            self.decode_with(token, &validation)
+        }
+        fn decode_with(&self, token: &str, validation: &Validation) -> Result<Claims> {
+            let kid = decode_header(token)?
+                .kid
+                .ok_or_else(|| anyhow!("Missing key id"))?;
+            let key = self
+                .keys
+                .get(&kid)
+                .ok_or_else(|| anyhow!("Unknown key id '{kid}'"))?;
+            let token_data = decode::<Claims>(token, &key.decoding_key, validation)?;
             Ok(token_data.claims)
         }
     }
@@ -133,54 +298,211 @@ mod jwt_service {
 #[cfg(not(feature = "jwt"))]
 mod jwt_service {
     use super::{Claims, Result};
+    use std::path::Path;
     pub struct JwtService;
     impl JwtService {
         pub fn new() -> Self {
             Self {}
         }
+        pub fn load_keys(_key_dir: &Path) -> Result<Self> {
+            Ok(Self {})
+        }
         pub fn encode(&self, _claims: &Claims) -> Result<String> {
             unimplemented!()
         }
         pub fn decode(&self, _token: &str) -> Result<Claims> {
             unimplemented!()
         }
+        pub fn decode_expired(&self, _token: &str) -> Result<Claims> {
+            unimplemented!()
+        }
     }
 }
 
 #[cfg(test)]
 #[cfg(feature = "jwt")]
 mod tests {
+    use std::fs;
+
     use super::*;
+    use crate::web::sqlite;
+
+    fn setup_db() -> sqlite::Connections {
+        let connections = ofdb_application::db::Connections::from(
+            ofdb_db_sqlite::Connections::init(":memory:", 1).unwrap(),
+        );
+        ofdb_application::db::run_embedded_database_migrations(connections.exclusive().unwrap());
+        sqlite::Connections::from(connections)
+    }
+
+    fn jwt_state() -> JwtState {
+        JwtState::new(&JwtCfg::default()).unwrap()
+    }
 
     #[test]
-    fn blacklisting_works() {
-        let jwt_state = JwtState::new();
-        let token = jwt_state.generate_token("foo@bar.org").unwrap();
-        jwt_state.blacklist_token(token.clone());
-        assert!(jwt_state.is_on_blacklist(&token));
+    fn revoking_works() {
+        let db = setup_db();
+        let jwt_state = jwt_state();
+        let tokens = jwt_state.generate_tokens("foo@bar.org").unwrap();
+        jwt_state
+            .revoke_session(&db.exclusive().unwrap(), &tokens.token)
+            .unwrap();
+        let db = db.shared().unwrap();
+        assert!(
+            jwt_state
+                .validate_token_and_get_email(&db, &tokens.token)
+                .is_err()
+        );
+        assert!(
+            jwt_state
+                .refresh_tokens(&db, &tokens.refresh_token)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn revoke_session_with_expired_token() {
+        let db = setup_db();
+        let jwt_state = JwtState::new(&JwtCfg {
+            // Exceeds the default leeway for validating the expiration
+            token_valid: Duration::minutes(-5),
+            ..Default::default()
+        })
+        .unwrap();
+        let tokens = jwt_state.generate_tokens("foo@bar.org").unwrap();
+        let db = db.exclusive().unwrap();
+        assert!(
+            jwt_state
+                .validate_token_and_get_email(&db, &tokens.token)
+                .is_err()
+        );
+        jwt_state.revoke_session(&db, &tokens.token).unwrap();
+        assert!(
+            jwt_state
+                .refresh_tokens(&db, &tokens.refresh_token)
+                .is_err()
+        );
+        // Tokens with an invalid signature are still rejected
+        let mut forged = tokens.token;
+        forged.pop();
+        assert!(jwt_state.revoke_session(&db, &forged).is_err());
     }
 
     #[test]
     fn validation_works() {
-        let jwt_state = JwtState::new();
-        let token = jwt_state.generate_token("foo@bar.org").unwrap();
-        let email = jwt_state.validate_token_and_get_email(&token).unwrap();
+        let db = setup_db();
+        let jwt_state = jwt_state();
+        let tokens = jwt_state.generate_tokens("foo@bar.org").unwrap();
+        let email = jwt_state
+            .validate_token_and_get_email(&db.shared().unwrap(), &tokens.token)
+            .unwrap();
         assert_eq!(email, "foo@bar.org".parse().unwrap());
-        jwt_state.blacklist_token(token.clone());
-        assert!(jwt_state.validate_token_and_get_email(&token).is_err())
+        // Refresh tokens could not be used for authentication
+        assert!(
+            jwt_state
+                .validate_token_and_get_email(&db.shared().unwrap(), &tokens.refresh_token)
+                .is_err()
+        );
+        assert!(
+            jwt_state
+                .validate_token_and_get_email(&db.shared().unwrap(), "dubidubidu")
+                .is_err()
+        );
     }
 
     #[test]
-    fn invalid_tokens_are_removed() {
-        let jwt_state = JwtState::new();
-        let token = jwt_state.generate_token("foo@bar.org").unwrap();
-        let invalid_token = "dubidubidu".to_string();
-        jwt_state.blacklist_token(token.clone());
-        jwt_state.blacklist_token(invalid_token.clone());
-        assert!(jwt_state.is_on_blacklist(&token));
-        assert!(jwt_state.is_on_blacklist(&invalid_token));
-        jwt_state.remove_invalid_tokens();
-        assert!(jwt_state.is_on_blacklist(&token));
-        assert!(!jwt_state.is_on_blacklist(&invalid_token));
+    fn refresh_tokens_can_only_be_used_once() {
+        let db = setup_db();
+        let jwt_state = jwt_state();
+        let tokens = jwt_state.generate_tokens("foo@bar.org").unwrap();
+        let refreshed = jwt_state
+            .refresh_tokens(&db.exclusive().unwrap(), &tokens.refresh_token)
+            .unwrap();
+        let db_rw = db.exclusive().unwrap();
+        assert!(
+            jwt_state
+                .refresh_tokens(&db_rw, &tokens.refresh_token)
+                .is_err()
+        );
+        assert!(
+            jwt_state
+                .validate_token_and_get_email(&db_rw, &refreshed.token)
+                .is_ok()
+        );
+        // The previous access token remains valid until it expires
+        assert!(
+            jwt_state
+                .validate_token_and_get_email(&db_rw, &tokens.token)
+                .is_ok()
+        );
+        // Revoking the session revokes all tokens
+        jwt_state.revoke_session(&db_rw, &tokens.token).unwrap();
+        assert!(
+            jwt_state
+                .validate_token_and_get_email(&db_rw, &refreshed.token)
+                .is_err()
+        );
+        assert!(
+            jwt_state
+                .refresh_tokens(&db_rw, &refreshed.refresh_token)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn persistent_keys_with_rotation() {
+        let db = setup_db();
+        let key_dir = std::env::temp_dir().join(format!("ofdb-jwt-keys-{}", Nonce::new()));
+        fs::create_dir_all(&key_dir).unwrap();
+        fs::write(key_dir.join("2026-01.key"), "a".repeat(32)).unwrap();
+        fs::write(key_dir.join("README"), "ignored").unwrap();
+        let cfg = JwtCfg {
+            key_dir: Some(key_dir.clone()),
+            ..Default::default()
+        };
+        let old_token = JwtState::new(&cfg)
+            .unwrap()
+            .generate_tokens("foo@bar.org")
+            .unwrap()
+            .token;
+
+        // Restart with an additional key
+        fs::write(key_dir.join("2026-02.key"), "b".repeat(32)).unwrap();
+        let jwt_state = JwtState::new(&cfg).unwrap();
+        let new_token = jwt_state.generate_tokens("foo@bar.org").unwrap().token;
+        let kid = |token: &str| jsonwebtoken::decode_header(token).unwrap().kid.unwrap();
+        assert_eq!(kid(&old_token), "2026-01");
+        assert_eq!(kid(&new_token), "2026-02");
+        let db_ro = db.shared().unwrap();
+        assert!(
+            jwt_state
+                .validate_token_and_get_email(&db_ro, &old_token)
+                .is_ok()
+        );
+        assert!(
+            jwt_state
+                .validate_token_and_get_email(&db_ro, &new_token)
+                .is_ok()
+        );
+
+        // Remove the old key
+        fs::remove_file(key_dir.join("2026-01.key")).unwrap();
+        let jwt_state = JwtState::new(&cfg).unwrap();
+        assert!(
+            jwt_state
+                .validate_token_and_get_email(&db_ro, &old_token)
+                .is_err()
+        );
+        assert!(
+            jwt_state
+                .validate_token_and_get_email(&db_ro, &new_token)
+                .is_ok()
+        );
+
+        // Reject short keys
+        fs::write(key_dir.join("2026-03.key"), "c").unwrap();
+        assert!(JwtState::new(&cfg).is_err());
+
+        fs::remove_dir_all(key_dir).unwrap();
     }
 }
@@ -1,4 +1,4 @@
-use std::{collections::HashSet, result};
+use std::{collections::HashSet, path::PathBuf, result, thread};
 
 use crate::core::{
     db::{EventIndexer, PlaceIndexer},
@@ -6,13 +6,15 @@ use crate::core::{
     usecases,
 };
 
-use ofdb_application::error::AppError;
+use ofdb_application::{error::AppError, prelude as flows};
 use ofdb_core::{
-    gateways::{geocode::GeoCodingGateway, notify::NotificationGateway},
+    gateways::{
+        geocode::GeoCodingGateway, image_storage::ImageStorage, notify::NotificationGateway,
+    },
     rating::Rated,
 };
 
-use rocket::{Rocket, Route, config::Config as RocketCfg, serde::json::Json};
+use rocket::{Rocket, Route, config::Config as RocketCfg, fs::FileServer, serde::json::Json};
 
 pub mod api;
 #[cfg(feature = "frontend")]
@@ -22,6 +24,7 @@ pub mod jwt;
 mod popular_tags_cache;
 mod sqlite;
 pub mod tantivy;
+mod tile_cache;
 
 #[cfg(test)]
 pub mod tests;
@@ -30,9 +33,30 @@ pub mod tests;
 pub struct Cfg {
     pub accepted_licenses: HashSet<String>,
     pub protect_with_captcha: bool,
+    pub jwt: jwt::JwtCfg,
+    pub images: ImagesCfg,
+}
+
+#[derive(Debug, Clone)]
+pub struct ImagesCfg {
+    /// Maximum size of uploaded image files in bytes
+    pub max_file_size: u64,
+    /// File system directory with the stored images that
+    /// should be served at `/images`.
+    pub serve_dir: Option<PathBuf>,
+}
+
+impl Default for ImagesCfg {
+    fn default() -> Self {
+        Self {
+            max_file_size: 5 * 1024 * 1024,
+            serve_dir: None,
+        }
+    }
 }
 
 use popular_tags_cache::PopularTagsCache;
+use tile_cache::TileCache;
 
 type Result<T> = result::Result<Json<T>, AppError>;
 
@@ -75,6 +99,19 @@ fn index_all_events_chronologically<D: EventRepo>(
     Ok(Json(()))
 }
 
+// Rebuild the search index after upgrading from an outdated
+// version while already serving requests.
+fn migrate_search_index(
+    db: &sqlite::Connections,
+    search_engine: &mut tantivy::SearchEngine,
+) -> result::Result<(), AppError> {
+    flows::rebuild_index(db, &mut **search_engine)?;
+    // Repair documents that have been modified concurrently
+    flows::check_index(db, &mut **search_engine, true)?;
+    search_engine.finish_migration()?;
+    Ok(())
+}
+
 pub(crate) struct InstanceOptions {
     mounts: Vec<(&'static str, Vec<Route>)>,
     rocket_cfg: Option<RocketCfg>,
@@ -85,6 +122,7 @@ pub(crate) struct InstanceOptions {
 pub(crate) struct Gateways {
     geocoding: Box<dyn GeoCodingGateway + Send + Sync>,
     notify: Box<dyn NotificationGateway + Send + Sync>,
+    images: Option<Box<dyn ImageStorage + Send + Sync>>,
 }
 
 pub(crate) struct Connections {
@@ -107,22 +145,43 @@ pub(crate) fn rocket_instance(
         db,
         mut search_engine,
     } = connections;
-    let Gateways { geocoding, notify } = gateways;
+    let Gateways {
+        geocoding,
+        notify,
+        images,
+    } = gateways;
 
-    info!("Indexing all places...");
-    index_all_places(&db.exclusive().unwrap(), &mut *search_engine).unwrap();
+    if search_engine.is_migrating() {
+        // Serve queries from the database until the index has been rebuilt
+        search_engine.set_migration_fallback(Box::new(flows::FallbackIndex::new((*db).clone())));
+        info!("Rebuilding the search index in the background...");
+        let db = db.clone();
+        let mut search_engine = search_engine.clone();
+        thread::spawn(
+            move || match migrate_search_index(&db, &mut search_engine) {
+                Ok(()) => info!("Finished rebuilding the search index"),
+                Err(err) => error!("Failed to rebuild the search index: {err}"),
+            },
+        );
+    } else {
+        info!("Indexing all places...");
+        index_all_places(&db.exclusive().unwrap(), &mut *search_engine).unwrap();
 
-    info!("Indexing all events...");
-    index_all_events_chronologically(&db.exclusive().unwrap(), &mut *search_engine).unwrap();
+        info!("Indexing all events...");
+        index_all_events_chronologically(&db.exclusive().unwrap(), &mut *search_engine).unwrap();
+    }
 
     info!("Deleting expired user e-mail tokens...");
     usecases::delete_expired_user_tokens(&db.exclusive().unwrap()).unwrap();
 
+    info!("Deleting expired revoked tokens...");
+    usecases::delete_expired_revoked_tokens(&db.exclusive().unwrap()).unwrap();
+
     info!("Caching most popular tags...");
     let tags_cache = PopularTagsCache::new_from_db(&db.shared().unwrap()).unwrap();
 
     let captcha_cache = api::captcha::CaptchaCache::new();
-    let jwt_state = jwt::JwtState::new();
+    let jwt_state = jwt::JwtState::new(&cfg.jwt).expect("JWT keys");
 
     info!("Initialization finished");
 
@@ -133,6 +192,7 @@ pub(crate) fn rocket_instance(
 
     let geo_gw = guards::GeoCoding(geocoding);
     let notify_gw = guards::Notify(notify);
+    let images = guards::Images(images);
     let version = guards::Version(version);
 
     let mut instance = r
@@ -140,9 +200,11 @@ pub(crate) fn rocket_instance(
         .manage(search_engine)
         .manage(captcha_cache)
         .manage(tags_cache)
+        .manage(TileCache::new())
         .manage(jwt_state)
         .manage(geo_gw)
         .manage(notify_gw)
+        .manage(images)
         .manage(cfg)
         .manage(version);
 
@@ -162,6 +224,7 @@ fn mounts() -> Vec<(&'static str, Vec<Route>)> {
     vec![("/api", api::routes()), ("/", frontend::routes())]
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn run(
     db: sqlite::Connections,
     search_engine: tantivy::SearchEngine,
@@ -169,9 +232,13 @@ pub async fn run(
     cfg: Cfg,
     geocoding: Box<dyn GeoCodingGateway + Send + Sync>,
     notify: Box<dyn NotificationGateway + Send + Sync>,
+    images: Option<Box<dyn ImageStorage + Send + Sync>>,
     version: &'static str,
 ) {
-    let mounts = mounts();
+    let mut mounts = mounts();
+    if let Some(dir) = &cfg.images.serve_dir {
+        mounts.push(("/images", FileServer::from(dir).into()));
+    }
     let options = InstanceOptions {
         mounts,
         rocket_cfg: None,
@@ -179,7 +246,11 @@ pub async fn run(
         version,
     };
     let connections = Connections { db, search_engine };
-    let gateways = Gateways { geocoding, notify };
+    let gateways = Gateways {
+        geocoding,
+        notify,
+        images,
+    };
 
     let instance = rocket_instance(options, connections, gateways);
     let server_task = if enable_cors {
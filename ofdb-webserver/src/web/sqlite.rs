@@ -1,5 +1,5 @@
 use anyhow::Result as Fallible;
-use ofdb_db_sqlite::{Connections as ConnectionPool, DbReadOnly, DbReadWrite};
+use ofdb_application::db::{Connections as ConnectionPool, DbReadOnly, DbReadWrite};
 use rocket::{
     Request, State,
     outcome::try_outcome,
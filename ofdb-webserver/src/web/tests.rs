@@ -25,6 +25,8 @@ pub fn setup(
         Cfg {
             accepted_licenses: crate::web::api::tests::prelude::default_accepted_licenses(),
             protect_with_captcha: false,
+            jwt: Default::default(),
+            images: Default::default(),
         },
     )
 }
@@ -34,8 +36,10 @@ pub fn setup_with_cfg(
     cfg: Cfg,
 ) -> (Client, sqlite::Connections, tantivy::SearchEngine) {
     let rocket_cfg = RocketCfg::debug_default();
-    let connections = ofdb_db_sqlite::Connections::init(":memory:", 1).unwrap();
-    ofdb_db_sqlite::run_embedded_database_migrations(connections.exclusive().unwrap());
+    let connections = ofdb_application::db::Connections::from(
+        ofdb_db_sqlite::Connections::init(":memory:", 1).unwrap(),
+    );
+    ofdb_application::db::run_embedded_database_migrations(connections.exclusive().unwrap());
     let search_engine = tantivy::SearchEngine::init_in_ram().unwrap();
     let db = sqlite::Connections::from(connections);
     let notify_gw = DummyNotifyGW;
@@ -54,6 +58,7 @@ pub fn setup_with_cfg(
     let gateways = super::Gateways {
         geocoding: Box::new(geo_gw),
         notify: Box::new(notify_gw),
+        images: Some(Box::new(DummyImageStorage)),
     };
     let rocket = super::rocket_instance(options, connections, gateways);
     let client = Client::tracked(rocket).unwrap();
@@ -96,3 +101,11 @@ impl ofdb_core::gateways::geocode::GeoCodingGateway for DummyGeoGW {
         None
     }
 }
+
+pub struct DummyImageStorage;
+
+impl ofdb_core::gateways::image_storage::ImageStorage for DummyImageStorage {
+    fn store_image(&self, key: &str, _: &str, _: &[u8]) -> anyhow::Result<Url> {
+        Ok(format!("https://img.example.com/{key}").parse()?)
+    }
+}
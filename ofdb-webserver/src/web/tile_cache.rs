@@ -0,0 +1,56 @@
+use std::{collections::HashMap, time::Duration};
+
+use parking_lot::RwLock;
+use time::OffsetDateTime;
+
+use crate::adapters::mvt::TileId;
+
+// Upcoming events change over time even if the index doesn't
+const MAX_TILE_AGE: Duration = Duration::from_secs(5 * 60);
+
+const MAX_CACHED_TILES: usize = 10_000;
+
+#[derive(Default)]
+struct Cache {
+    // The revision of the search index for all cached tiles
+    revision: u64,
+    tiles: HashMap<TileId, (OffsetDateTime, Vec<u8>)>,
+}
+
+/// Encoded vector tiles that are invalidated whenever
+/// the search index is modified.
+#[derive(Default)]
+pub struct TileCache(RwLock<Cache>);
+
+impl TileCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, tile_id: &TileId, revision: u64) -> Option<Vec<u8>> {
+        let cache = self.0.read();
+        if cache.revision != revision {
+            return None;
+        }
+        let (created_at, data) = cache.tiles.get(tile_id)?;
+        let age = Duration::try_from(OffsetDateTime::now_utc() - *created_at).ok()?;
+        (age < MAX_TILE_AGE).then(|| data.clone())
+    }
+
+    /// Insert a tile that has been generated from the given
+    /// revision of the search index.
+    pub fn insert(&self, tile_id: TileId, revision: u64, data: Vec<u8>) {
+        let mut cache = self.0.write();
+        if revision < cache.revision {
+            // Outdated
+            return;
+        }
+        if revision > cache.revision || cache.tiles.len() >= MAX_CACHED_TILES {
+            cache.tiles.clear();
+            cache.revision = revision;
+        }
+        cache
+            .tiles
+            .insert(tile_id, (OffsetDateTime::now_utc(), data));
+    }
+}
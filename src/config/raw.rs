@@ -15,7 +15,9 @@ pub struct Config {
     pub email: Option<Email>,
     pub gateway: Option<Gateway>,
     pub reminders: Option<Reminders>,
+    pub duplicates: Option<Duplicates>,
     pub subscriptions: Option<Subscriptions>,
+    pub images: Option<Images>,
 }
 
 impl Default for Config {
@@ -28,7 +30,9 @@ impl Default for Config {
 #[derive(Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct Db {
-    pub connection_sqlite: String,
+    pub backend: Option<DbBackend>,
+    pub connection_sqlite: Option<String>,
+    pub connection_postgres: Option<String>,
     pub connection_pool_size: u8,
     pub index_dir: Option<PathBuf>,
 }
@@ -39,6 +43,13 @@ impl Default for Db {
     }
 }
 
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DbBackend {
+    Sqlite,
+    Postgres,
+}
+
 #[derive(Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct Geocoding {
@@ -83,6 +94,7 @@ pub struct WebServer {
     pub captcha: bool,
     pub cors: bool,
     pub base_url: Option<String>,
+    pub jwt: Option<Jwt>,
 }
 
 impl Default for WebServer {
@@ -93,6 +105,22 @@ impl Default for WebServer {
     }
 }
 
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Jwt {
+    pub key_dir: Option<PathBuf>,
+    #[serde(default, deserialize_with = "deserialize_duration")]
+    pub token_expire_in: Option<Duration>,
+    #[serde(default, deserialize_with = "deserialize_duration")]
+    pub refresh_token_expire_in: Option<Duration>,
+}
+
+impl Default for Jwt {
+    fn default() -> Self {
+        WebServer::default().jwt.expect("JWT configuration")
+    }
+}
+
 #[derive(Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct Email {
@@ -212,6 +240,35 @@ impl Default for OwnerReminders {
     }
 }
 
+#[derive(Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Duplicates {
+    #[serde(deserialize_with = "deserialize_duration")]
+    pub task_interval_time: Option<Duration>,
+}
+
+impl Default for Duplicates {
+    fn default() -> Self {
+        Config::default()
+            .duplicates
+            .expect("Duplicates configuration")
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Images {
+    pub storage_dir: Option<PathBuf>,
+    pub base_url: Option<String>,
+    pub max_file_size: Option<u64>,
+}
+
+impl Default for Images {
+    fn default() -> Self {
+        Config::default().images.expect("Images configuration")
+    }
+}
+
 #[derive(Default, Clone, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct Subscriptions {
@@ -252,6 +309,12 @@ mod tests {
         assert!(cfg.token_expire_in.is_some());
     }
 
+    #[test]
+    fn default_duplicates_config() {
+        let cfg = Duplicates::default();
+        assert!(cfg.task_interval_time.is_some());
+    }
+
     #[test]
     fn parse_full_config_example_from_file() {
         let cfg_string = fs::read_to_string("src/config/openfairdb.full-example.toml").unwrap();
@@ -0,0 +1,26 @@
+use ofdb_application::{db::Connections, prelude::detect_duplicates};
+use ofdb_db_tantivy::SearchEngine;
+
+use crate::config;
+
+pub async fn run(connections: Connections, search_engine: SearchEngine, cfg: config::Duplicates) {
+    let mut interval = tokio::time::interval(cfg.task_interval_time);
+
+    log::info!("Detect duplicate places periodically (interval = {interval:?})");
+
+    // The first tick completes immediately while the search index
+    // is still being built during startup. Otherwise all open
+    // candidates would be removed.
+    interval.tick().await;
+
+    loop {
+        interval.tick().await;
+        if search_engine.is_migrating() {
+            log::info!("Skipping the detection of duplicate places while rebuilding the index");
+            continue;
+        }
+        if let Err(err) = detect_duplicates(&connections, &search_engine) {
+            log::warn!("Duplicate places could not be detected: {err}");
+        }
+    }
+}
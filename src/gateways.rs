@@ -1,13 +1,19 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, fs};
 
+use ofdb_application::prelude::WebhookNotifications;
 use ofdb_core::{
     entities::{EmailAddress, EmailContent},
-    gateways::{geocode::GeoCodingGateway, notify::NotificationType},
+    gateways::{
+        geocode::GeoCodingGateway,
+        image_storage::ImageStorage,
+        notify::{NotificationEvent, NotificationGateway, NotificationType},
+    },
 };
 use ofdb_gateways::{
     email::{
         EmailGateway, mailgun::Mailgun, send_to_json_file::SendToJsonFile, sendmail::Sendmail,
     },
+    image_storage::FileSystemImageStorage,
     notify::Notify,
     opencage::OpenCage,
 };
@@ -66,6 +72,19 @@ pub fn notification_gateway(
     }
 }
 
+/// Sends notifications by e-mail and to the webhooks of organizations.
+pub struct Notifications {
+    pub email: Notify,
+    pub webhooks: WebhookNotifications,
+}
+
+impl NotificationGateway for Notifications {
+    fn notify(&self, event: NotificationEvent) {
+        self.webhooks.notify(event.clone());
+        self.email.notify(event);
+    }
+}
+
 pub fn geocoding_gateway(
     cfg: Option<config::GeocodingGateway>,
 ) -> Box<dyn GeoCodingGateway + Send + Sync> {
@@ -77,6 +96,20 @@ pub fn geocoding_gateway(
     }
 }
 
+pub fn image_storage(
+    cfg: &config::Images,
+) -> anyhow::Result<Option<Box<dyn ImageStorage + Send + Sync>>> {
+    let Some(dir) = &cfg.storage_dir else {
+        log::info!("Image uploads are disabled because no storage directory was configured");
+        return Ok(None);
+    };
+    fs::create_dir_all(dir)?;
+    Ok(Some(Box::new(FileSystemImageStorage::new(
+        dir.clone(),
+        &cfg.base_url,
+    ))))
+}
+
 struct NoGeoCodingGateway;
 
 impl GeoCodingGateway for NoGeoCodingGateway {
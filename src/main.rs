@@ -1,21 +1,36 @@
 // Copyright (c) 2018 - 2024 slowtec GmbH <post@slowtec.de>
 // Copyright (c) 2015 - 2018 Markus Kohlhase <mail@markus-kohlhase.de>
 
-use std::{env, path::PathBuf};
+use std::{
+    env,
+    fs::{self, File},
+    io::{self, BufReader, BufWriter},
+    path::{Path, PathBuf},
+};
+
+use anyhow::bail;
 
 use clap::{Parser, Subcommand};
 use dotenvy::dotenv;
 
+use ofdb_application::{
+    db::Connections,
+    prelude::{DumpOptions, WebhookNotifications, check_index, dump, rebuild_index, restore},
+};
 use ofdb_core::{
-    RepoError, entities::MapPoint, gateways::geocode::GeoCodingGateway, repositories::EventRepo,
+    RepoError,
+    entities::{Activity, ActivityLog, MapPoint},
+    gateways::geocode::GeoCodingGateway,
+    repositories::EventRepo,
 };
 
-use ofdb_db_sqlite::Connections;
 use ofdb_db_tantivy as tantivy;
 
 mod config;
+mod duplicate_detection;
 mod gateways;
 mod recurring_reminder;
+mod webhook_delivery;
 
 fn update_event_locations<R, G>(repo: &R, geo: &G) -> Result<(), RepoError>
 where
@@ -37,7 +52,13 @@ where
             continue;
         };
         if pos.is_valid() {
-            match repo.update_event(&e) {
+            e.revision = e.revision.next();
+            let activity = ActivityLog {
+                activity: Activity::now(None),
+                context: None,
+                comment: None,
+            };
+            match repo.update_event(&e, &activity) {
                 Err(err) => {
                     log::warn!("Failed to update location of event {}: {err}", e.id);
                 }
@@ -50,6 +71,51 @@ where
     Ok(())
 }
 
+fn sibling_dir(dir: &Path, suffix: &str) -> PathBuf {
+    let mut name = dir.file_name().unwrap_or_default().to_os_string();
+    name.push(suffix);
+    dir.with_file_name(name)
+}
+
+/// Rebuild the search index in a new directory and replace
+/// the existing index directory when finished.
+///
+/// Fails if the existing index is still used by a running server.
+fn reindex(connections: &Connections, index_dir: &Path) -> anyhow::Result<()> {
+    let index_locks = tantivy::lock_index_dir(index_dir)?;
+    let new_index_dir = sibling_dir(index_dir, ".new");
+    if new_index_dir.exists() {
+        log::warn!(
+            "Removing incomplete search index in {}",
+            new_index_dir.display()
+        );
+        fs::remove_dir_all(&new_index_dir)?;
+    }
+    fs::create_dir_all(&new_index_dir)?;
+    {
+        let mut search_engine = tantivy::SearchEngine::init_with_path(Some(&new_index_dir))?;
+        rebuild_index(connections, &mut search_engine)?;
+    }
+    log::info!(
+        "Replacing search index in {} with {}",
+        index_dir.display(),
+        new_index_dir.display()
+    );
+    let old_index_dir = sibling_dir(index_dir, ".old");
+    if index_dir.exists() {
+        if old_index_dir.exists() {
+            fs::remove_dir_all(&old_index_dir)?;
+        }
+        fs::rename(index_dir, &old_index_dir)?;
+    }
+    fs::rename(&new_index_dir, index_dir)?;
+    drop(index_locks);
+    if old_index_dir.exists() {
+        fs::remove_dir_all(&old_index_dir)?;
+    }
+    Ok(())
+}
+
 #[derive(Parser, Debug)]
 #[command(author, version, about)]
 struct Args {
@@ -64,6 +130,38 @@ struct Args {
 enum Command {
     /// Update the location of ALL events by resolving their address
     FixEventAddressLocation,
+    /// Write the contents of the database into an archive (JSON lines)
+    Dump {
+        /// Archive file (default: stdout)
+        #[arg(short, long)]
+        file: Option<PathBuf>,
+        /// Include the password hashes of all users
+        #[arg(long)]
+        with_password_hashes: bool,
+        /// Include the API tokens of organizations and the secrets of webhooks
+        #[arg(long)]
+        with_secrets: bool,
+    },
+    /// Restore an archive into an empty database and rebuild the search index
+    Restore {
+        /// Archive file (default: stdin)
+        #[arg(short, long)]
+        file: Option<PathBuf>,
+    },
+    /// Copy all data from an SQLite database into the empty PostgreSQL database
+    /// and rebuild the search index
+    ImportSqlite {
+        /// SQLite database file
+        file: PathBuf,
+    },
+    /// Rebuild the search index in a new directory and replace the current index
+    Reindex,
+    /// Compare the search index with the database
+    Check {
+        /// Reindex missing or outdated and remove orphaned documents
+        #[arg(long)]
+        repair: bool,
+    },
 }
 
 const ENV_NAME_DB_URL: &str = "DATABASE_URL";
@@ -93,25 +191,45 @@ pub async fn main() -> anyhow::Result<()> {
     };
 
     if let Ok(db_url) = env::var(ENV_NAME_DB_URL) {
-        log::info!("Use DB connection {db_url} defined by {ENV_NAME_DB_URL}");
-        cfg.db.conn_sqlite = db_url;
+        log::info!("Use DB connection defined by {ENV_NAME_DB_URL}");
+        cfg.db.connection =
+            if db_url.starts_with("postgres://") || db_url.starts_with("postgresql://") {
+                config::DbConnection::Postgres(db_url)
+            } else {
+                config::DbConnection::Sqlite(db_url)
+            };
     }
 
     log::info!("Start server with the following config:\n {cfg:#?}");
 
     let config::Db {
-        conn_sqlite,
+        connection,
         conn_pool_size,
         index_dir,
     } = cfg.db;
 
-    log::info!("Connecting to SQLite database '{conn_sqlite}' (pool size = {conn_pool_size})");
-    let connections = Connections::init(&conn_sqlite, u32::from(conn_pool_size)).unwrap();
+    let connections = match connection {
+        config::DbConnection::Sqlite(path) => {
+            log::info!("Connecting to SQLite database '{path}' (pool size = {conn_pool_size})");
+            Connections::from(
+                ofdb_db_sqlite::Connections::init(&path, u32::from(conn_pool_size)).unwrap(),
+            )
+        }
+        config::DbConnection::Postgres(url) => {
+            // The URL is not logged, because it might contain a password
+            log::info!("Connecting to PostgreSQL database (pool size = {conn_pool_size})");
+            Connections::from(
+                ofdb_db_postgres::Connections::init(&url, u32::from(conn_pool_size)).unwrap(),
+            )
+        }
+    };
 
-    ofdb_db_sqlite::run_embedded_database_migrations(connections.exclusive().unwrap());
+    ofdb_application::db::run_embedded_database_migrations(connections.exclusive().unwrap());
 
-    log::info!("Initializing Tantivy full-text search engine");
-    let search_engine = tantivy::SearchEngine::init_with_path(index_dir).unwrap();
+    let init_search_engine = || {
+        log::info!("Initializing Tantivy full-text search engine");
+        tantivy::SearchEngine::init_with_path(index_dir.as_deref())
+    };
 
     let geo_gw = gateways::geocoding_gateway(cfg.geocoding.gateway);
 
@@ -121,25 +239,129 @@ pub async fn main() -> anyhow::Result<()> {
         cfg.subscriptions,
     );
 
-    let recurring_reminder_task = recurring_reminder::run(
-        connections.clone(),
-        notify_gw.clone(),
-        cfg.reminders,
-        cfg.webserver.clone(),
-    );
-    let recurring_reminder_abort_handle = tokio::spawn(recurring_reminder_task).abort_handle();
-
     match args.command {
         Some(cmd) => match cmd {
             Command::FixEventAddressLocation => {
                 log::info!("Updating all event locations...");
                 update_event_locations(&connections.exclusive().unwrap(), &*geo_gw).unwrap();
             }
+            Command::Dump {
+                file,
+                with_password_hashes,
+                with_secrets,
+            } => {
+                let options = DumpOptions {
+                    include_password_hashes: with_password_hashes,
+                    include_secrets: with_secrets,
+                };
+                let db = connections.shared()?;
+                let summary = match file {
+                    Some(path) => dump(&db, &mut BufWriter::new(File::create(path)?), options)?,
+                    None => dump(&db, &mut BufWriter::new(io::stdout().lock()), options)?,
+                };
+                log::info!("Dumped {summary:?}");
+            }
+            Command::Restore { file } => {
+                let mut search_engine = init_search_engine()?;
+                let summary = match file {
+                    Some(path) => restore(
+                        &connections,
+                        &mut search_engine,
+                        BufReader::new(File::open(path)?),
+                    )?,
+                    None => restore(&connections, &mut search_engine, io::stdin().lock())?,
+                };
+                log::info!("Restored {summary:?}");
+            }
+            Command::ImportSqlite { file } => {
+                let Connections::Postgres(target) = &connections else {
+                    bail!("Importing an SQLite database requires the PostgreSQL backend");
+                };
+                let Some(sqlite_url) = file.to_str() else {
+                    bail!("Invalid SQLite database file {}", file.display());
+                };
+                let stats = ofdb_db_postgres::import_sqlite_database(sqlite_url, target)?;
+                log::info!(
+                    "Imported {} rows from {}",
+                    stats.total_rows(),
+                    file.display()
+                );
+                // Otherwise the in-memory index is built when starting the server
+                if let Some(index_dir) = &index_dir {
+                    reindex(&connections, index_dir)?;
+                }
+            }
+            Command::Reindex => {
+                let Some(index_dir) = &index_dir else {
+                    bail!("No search index directory configured");
+                };
+                reindex(&connections, index_dir)?;
+            }
+            Command::Check { repair } => {
+                if index_dir.is_none() {
+                    bail!("No search index directory configured");
+                }
+                let mut search_engine = init_search_engine()?;
+                let mismatches = check_index(&connections, &mut search_engine, repair)?;
+                if repair {
+                    // The index is complete after all mismatches have been repaired
+                    search_engine.finish_migration()?;
+                }
+                if mismatches.is_empty() {
+                    log::info!("The search index is consistent with the database");
+                } else if !repair {
+                    bail!(
+                        "Found {} mismatches between the database and the search index",
+                        mismatches.len()
+                    );
+                }
+            }
         },
         None => {
+            let search_engine = init_search_engine().unwrap();
+            let recurring_reminder_task = recurring_reminder::run(
+                connections.clone(),
+                notify_gw.clone(),
+                cfg.reminders,
+                cfg.webserver.clone(),
+            );
+            let recurring_reminder_abort_handle =
+                tokio::spawn(recurring_reminder_task).abort_handle();
+
+            let duplicate_detection_task = duplicate_detection::run(
+                connections.clone(),
+                search_engine.clone(),
+                cfg.duplicates,
+            );
+            let duplicate_detection_abort_handle =
+                tokio::spawn(duplicate_detection_task).abort_handle();
+
+            let webhook_delivery_task = webhook_delivery::run(connections.clone());
+            let webhook_delivery_abort_handle = tokio::spawn(webhook_delivery_task).abort_handle();
+
+            let notify_gw = gateways::Notifications {
+                email: notify_gw,
+                webhooks: WebhookNotifications::new(connections.clone()),
+            };
+
+            let config::Jwt {
+                key_dir,
+                token_expire_in,
+                refresh_token_expire_in,
+            } = cfg.webserver.jwt.clone();
+            let image_storage = gateways::image_storage(&cfg.images)?;
             let web_server_cfg = ofdb_webserver::Cfg {
                 accepted_licenses: cfg.entries.accepted_licenses,
                 protect_with_captcha: cfg.webserver.protect_with_captcha,
+                jwt: ofdb_webserver::JwtCfg {
+                    key_dir,
+                    token_valid: token_expire_in.try_into()?,
+                    refresh_token_valid: refresh_token_expire_in.try_into()?,
+                },
+                images: ofdb_webserver::ImagesCfg {
+                    max_file_size: cfg.images.max_file_size,
+                    serve_dir: cfg.images.storage_dir,
+                },
             };
             ofdb_webserver::run(
                 connections,
@@ -148,11 +370,14 @@ pub async fn main() -> anyhow::Result<()> {
                 web_server_cfg,
                 geo_gw,
                 Box::new(notify_gw),
+                image_storage,
                 env!("CARGO_PKG_VERSION"),
             )
             .await;
+            recurring_reminder_abort_handle.abort();
+            webhook_delivery_abort_handle.abort();
+            duplicate_detection_abort_handle.abort();
         }
     }
-    recurring_reminder_abort_handle.abort();
     Ok(())
 }
@@ -1,8 +1,8 @@
 use time::Duration;
 
+use ofdb_application::db::Connections;
 use ofdb_application::prelude::{SendReminderParams, send_update_reminders};
 use ofdb_core::{entities::Timestamp, usecases::RecipientRole};
-use ofdb_db_sqlite::Connections;
 use ofdb_gateways::notify::Notify;
 use ofdb_gateways::user_communication::ReminderFormatter;
 
@@ -0,0 +1,36 @@
+use std::time::Duration;
+
+use ofdb_application::db::Connections;
+use ofdb_application::prelude::deliver_webhooks;
+use ofdb_gateways::webhook::HttpWebhookGateway;
+
+/// How often pending webhook deliveries are (re-)sent.
+const TASK_INTERVAL_TIME: Duration = Duration::from_secs(60);
+
+pub async fn run(connections: Connections) {
+    let gateway = match HttpWebhookGateway::new() {
+        Ok(gateway) => gateway,
+        Err(err) => {
+            log::error!("Could not create webhook gateway: {err}");
+            return;
+        }
+    };
+    let mut interval = tokio::time::interval(TASK_INTERVAL_TIME);
+
+    log::info!("Deliver webhook payloads (interval = {interval:?})");
+
+    loop {
+        interval.tick().await;
+        let connections = connections.clone();
+        let gateway = gateway.clone();
+        // The gateway sends blocking HTTP requests
+        let result =
+            tokio::task::spawn_blocking(move || deliver_webhooks(&connections, &gateway)).await;
+        match result {
+            Ok(Ok(0)) => (),
+            Ok(Ok(count)) => log::debug!("Attempted {count} webhook deliveries"),
+            Ok(Err(err)) => log::warn!("Webhook payloads could not be delivered: {err}"),
+            Err(err) => log::error!("Webhook delivery task failed: {err}"),
+        }
+    }
+}